@@ -0,0 +1,16 @@
+-- Event lifecycle: events are prepared as drafts, published to the public
+-- listing and eventually either cancelled or completed.
+
+CREATE TYPE event_status            AS ENUM ('draft', 'published',
+                                             'cancelled', 'completed');
+
+-- Events which existed before the lifecycle are already public. New events
+-- are created as drafts by the application.
+ALTER TABLE event
+    ADD COLUMN status               event_status NOT NULL DEFAULT 'published',
+    ADD COLUMN cancellation_reason  TEXT,
+    ADD CONSTRAINT check_event_cancellation_reason_iff_cancelled
+        CHECK ((status = 'cancelled') = (cancellation_reason IS NOT NULL));
+
+CREATE INDEX event_status_idx
+    ON event (status);
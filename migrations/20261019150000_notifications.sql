@@ -0,0 +1,55 @@
+-- In-app notifications about decisions and mentions concerning a user,
+-- together with the user's choice of which kinds they want to receive.
+
+CREATE TYPE notification_kind       AS ENUM ('staff_decision', 'assignment_decision',
+                                             'timesheet_rejection', 'comment_mention');
+
+CREATE TABLE notification
+(
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -------------------------------------------------------
+    user_id     UUID NOT NULL,
+    -------------------------------------------------------
+    kind        notification_kind NOT NULL,
+    message     TEXT NOT NULL,
+    link        VARCHAR(255),
+    read_at     TIMESTAMP,
+    -------------------------------------------------------
+    created_at  TIMESTAMP NOT NULL DEFAULT now(),
+    edited_at   TIMESTAMP NOT NULL DEFAULT now(),
+    deleted_at  TIMESTAMP,
+    -------------------------------------------------------
+    FOREIGN KEY (user_id) REFERENCES user_record (id),
+    -------------------------------------------------------
+    CONSTRAINT check_notification_message_len
+        CHECK (char_length(message) >= 1),
+    CONSTRAINT check_notification_created_at_lte_read_at
+        CHECK (read_at IS NULL OR read_at >= created_at),
+    CONSTRAINT check_notification_created_at_lte_edited_at
+        CHECK (edited_at >= created_at)
+);
+
+CREATE INDEX notification_user_id_read_at_idx
+    ON notification (user_id, read_at);
+
+
+--
+-- Kinds without a row are delivered, users only store the ones they changed.
+--
+CREATE TABLE notification_preference
+(
+    user_id     UUID NOT NULL,
+    kind        notification_kind NOT NULL,
+    -------------------------------------------------------
+    is_enabled  BOOLEAN NOT NULL DEFAULT TRUE,
+    -------------------------------------------------------
+    created_at  TIMESTAMP NOT NULL DEFAULT now(),
+    edited_at   TIMESTAMP NOT NULL DEFAULT now(),
+    deleted_at  TIMESTAMP,
+    -------------------------------------------------------
+    PRIMARY KEY (user_id, kind),
+    FOREIGN KEY (user_id) REFERENCES user_record (id),
+    -------------------------------------------------------
+    CONSTRAINT check_notification_preference_created_at_lte_edited_at
+        CHECK (edited_at >= created_at)
+);
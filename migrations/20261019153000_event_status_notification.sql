@@ -0,0 +1,4 @@
+-- The accepted staff of an event is notified once the event is cancelled or
+-- completed, next to the comment left on the event.
+
+ALTER TYPE notification_kind ADD VALUE 'event_status_change';
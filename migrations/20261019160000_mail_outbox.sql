@@ -0,0 +1,68 @@
+-- Email delivery of notifications: a persistent outbox the mail worker sends
+-- from, and the user's choice between immediate mails and a daily digest.
+
+ALTER TYPE notification_kind ADD VALUE 'timesheet_approval' AFTER 'assignment_decision';
+
+CREATE TYPE mail_delivery           AS ENUM ('immediate', 'daily_digest');
+CREATE TYPE mail_status             AS ENUM ('pending', 'digest', 'bundled',
+                                             'sent', 'failed');
+
+CREATE TABLE mail_setting
+(
+    user_id         UUID PRIMARY KEY,
+    -------------------------------------------------------
+    delivery        mail_delivery NOT NULL DEFAULT 'immediate',
+    last_digest_at  TIMESTAMP,
+    -------------------------------------------------------
+    created_at      TIMESTAMP NOT NULL DEFAULT now(),
+    edited_at       TIMESTAMP NOT NULL DEFAULT now(),
+    deleted_at      TIMESTAMP,
+    -------------------------------------------------------
+    FOREIGN KEY (user_id) REFERENCES user_record (id),
+    -------------------------------------------------------
+    CONSTRAINT check_mail_setting_created_at_lte_edited_at
+        CHECK (edited_at >= created_at)
+);
+
+
+--
+-- Mails are rendered when queued, `summary` is the plain text without the
+-- greeting the digest gets composed from. Mails waiting for the daily digest
+-- have the `digest` status until they get `bundled` into a digest mail.
+--
+CREATE TABLE mail_outbox
+(
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -------------------------------------------------------
+    user_id         UUID NOT NULL,
+    -------------------------------------------------------
+    recipient       VARCHAR(255) NOT NULL,
+    subject         VARCHAR(255) NOT NULL,
+    body_text       TEXT NOT NULL,
+    body_html       TEXT NOT NULL,
+    summary         TEXT NOT NULL,
+    status          mail_status NOT NULL DEFAULT 'pending',
+    attempts        INT NOT NULL DEFAULT 0,
+    last_error      TEXT,
+    next_attempt_at TIMESTAMP NOT NULL DEFAULT now(),
+    sent_at         TIMESTAMP,
+    -------------------------------------------------------
+    created_at      TIMESTAMP NOT NULL DEFAULT now(),
+    edited_at       TIMESTAMP NOT NULL DEFAULT now(),
+    deleted_at      TIMESTAMP,
+    -------------------------------------------------------
+    FOREIGN KEY (user_id) REFERENCES user_record (id),
+    -------------------------------------------------------
+    CONSTRAINT check_mail_outbox_attempts_gte_0
+        CHECK (attempts >= 0),
+    CONSTRAINT check_mail_outbox_sent_at_iff_sent
+        CHECK ((status = 'sent') = (sent_at IS NOT NULL)),
+    CONSTRAINT check_mail_outbox_created_at_lte_edited_at
+        CHECK (edited_at >= created_at)
+);
+
+CREATE INDEX mail_outbox_status_next_attempt_at_idx
+    ON mail_outbox (status, next_attempt_at);
+
+CREATE INDEX mail_outbox_user_id_idx
+    ON mail_outbox (user_id);
@@ -0,0 +1,37 @@
+-- Planned staffing of an event: headcount x hours of a role, optionally
+-- limited to a single company and day. A line without a day is planned for
+-- every day of the event, a line without a company for any company.
+
+CREATE TABLE event_budget_line
+(
+    id           UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -------------------------------------------------------
+    event_id     UUID NOT NULL,
+    company_id   UUID,
+    role         event_role NOT NULL DEFAULT 'staff',
+    date         DATE,
+    headcount    INT NOT NULL,
+    -- Planned hours of a single person per day.
+    hours        REAL NOT NULL,
+    hourly_rate  FLOAT NOT NULL,
+    -------------------------------------------------------
+    created_at   TIMESTAMP NOT NULL DEFAULT now(),
+    edited_at    TIMESTAMP NOT NULL DEFAULT now(),
+    deleted_at   TIMESTAMP,
+    -------------------------------------------------------
+    FOREIGN KEY (event_id) REFERENCES event (id),
+    FOREIGN KEY (company_id) REFERENCES company (id),
+    -------------------------------------------------------
+    CONSTRAINT check_event_budget_line_headcount_gt_0
+        CHECK (headcount > 0),
+    CONSTRAINT check_event_budget_line_hours_range
+        CHECK (hours > 0 AND hours <= 24),
+    CONSTRAINT check_event_budget_line_hourly_rate_gte_0
+        CHECK (hourly_rate >= 0),
+    CONSTRAINT check_event_budget_line_created_at_lte_edited_at
+        CHECK (edited_at >= created_at)
+);
+
+CREATE INDEX event_budget_line_event_id_idx
+    ON event_budget_line (event_id)
+    WHERE deleted_at IS NULL;
@@ -0,0 +1,30 @@
+-- Timesheet approval: the transitions a timesheet goes through on its way
+-- from the employee to their manager and back, along with who made them.
+
+CREATE TYPE timesheet_transition AS ENUM (
+    'submit',
+    'withdraw',
+    'approve',
+    'reject',
+    'reopen'
+);
+
+CREATE TABLE timesheet_history
+(
+    id            UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -------------------------------------------------------
+    timesheet_id  UUID NOT NULL,
+    actor_id      UUID NOT NULL,
+    transition    timesheet_transition NOT NULL,
+    from_status   approval_status NOT NULL,
+    to_status     approval_status NOT NULL,
+    note          VARCHAR(255),
+    -------------------------------------------------------
+    created_at    TIMESTAMP NOT NULL DEFAULT now(),
+    -------------------------------------------------------
+    FOREIGN KEY (timesheet_id) REFERENCES timesheet (id),
+    FOREIGN KEY (actor_id) REFERENCES user_record (id)
+);
+
+CREATE INDEX timesheet_history_timesheet_id_idx
+    ON timesheet_history (timesheet_id);
@@ -0,0 +1,53 @@
+-- Periodic background jobs. Every application instance registers the jobs it
+-- can run, a due job is claimed by a single instance (`FOR UPDATE SKIP
+-- LOCKED`) which leases it until the run finishes. Failed runs are retried
+-- with a growing delay.
+
+CREATE TYPE job_run_status          AS ENUM ('running', 'succeeded', 'failed');
+
+CREATE TABLE job
+(
+    name            VARCHAR(64) PRIMARY KEY,
+    -------------------------------------------------------
+    period_seconds  INTEGER NOT NULL,
+    next_run_at     TIMESTAMP NOT NULL DEFAULT now(),
+    -- Failed runs since the last successful one.
+    failed_attempts INTEGER NOT NULL DEFAULT 0,
+    -------------------------------------------------------
+    created_at      TIMESTAMP NOT NULL DEFAULT now(),
+    edited_at       TIMESTAMP NOT NULL DEFAULT now(),
+    -------------------------------------------------------
+    CONSTRAINT check_job_period_seconds_gt_0
+        CHECK (period_seconds > 0),
+    CONSTRAINT check_job_failed_attempts_gte_0
+        CHECK (failed_attempts >= 0),
+    CONSTRAINT check_job_created_at_lte_edited_at
+        CHECK (edited_at >= created_at)
+);
+
+
+CREATE TABLE job_run
+(
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -------------------------------------------------------
+    job_name        VARCHAR(64) NOT NULL,
+    -- 1 for the first attempt, counting up while the runs keep failing.
+    attempt         INTEGER NOT NULL,
+    status          job_run_status NOT NULL DEFAULT 'running',
+    -- What the job did, or why it failed.
+    output          TEXT,
+    -------------------------------------------------------
+    started_at      TIMESTAMP NOT NULL DEFAULT now(),
+    finished_at     TIMESTAMP,
+    -------------------------------------------------------
+    FOREIGN KEY (job_name) REFERENCES job (name),
+    -------------------------------------------------------
+    CONSTRAINT check_job_run_attempt_gt_0
+        CHECK (attempt > 0),
+    CONSTRAINT check_job_run_finished_when_not_running
+        CHECK ((status = 'running') = (finished_at IS NULL)),
+    CONSTRAINT check_job_run_started_at_lte_finished_at
+        CHECK (finished_at >= started_at)
+);
+
+CREATE INDEX job_run_job_name_idx ON job_run (job_name, started_at DESC);
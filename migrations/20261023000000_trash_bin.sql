@@ -0,0 +1,44 @@
+-- Soft deletions done as one operation. Deleting a user, company, event,
+-- employment or task soft-deletes the rows depending on it as well, all of
+-- them are marked with the deletion so that they can be restored together,
+-- or purged once the retention period passes.
+
+CREATE TYPE deleted_entity          AS ENUM ('user', 'company', 'event', 'employment', 'task');
+
+CREATE TABLE deletion
+(
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -------------------------------------------------------
+    entity      deleted_entity NOT NULL,
+    record_id   UUID NOT NULL,
+    -- The company of a deleted employment, which is keyed by both.
+    related_id  UUID,
+    label       VARCHAR(255) NOT NULL,
+    -- Not a foreign key, the user may be purged before the records they deleted.
+    deleted_by  UUID,
+    -------------------------------------------------------
+    deleted_at  TIMESTAMP NOT NULL DEFAULT now(),
+    -------------------------------------------------------
+    CONSTRAINT check_deletion_related_id_for_employment
+        CHECK ((entity = 'employment') = (related_id IS NOT NULL))
+);
+
+CREATE INDEX deletion_deleted_at_idx
+    ON deletion (deleted_at);
+
+ALTER TABLE user_record         ADD COLUMN deletion_id UUID REFERENCES deletion (id);
+ALTER TABLE company             ADD COLUMN deletion_id UUID REFERENCES deletion (id);
+ALTER TABLE event               ADD COLUMN deletion_id UUID REFERENCES deletion (id);
+ALTER TABLE employment          ADD COLUMN deletion_id UUID REFERENCES deletion (id);
+ALTER TABLE associated_company  ADD COLUMN deletion_id UUID REFERENCES deletion (id);
+ALTER TABLE event_staff         ADD COLUMN deletion_id UUID REFERENCES deletion (id);
+ALTER TABLE task                ADD COLUMN deletion_id UUID REFERENCES deletion (id);
+ALTER TABLE assigned_staff      ADD COLUMN deletion_id UUID REFERENCES deletion (id);
+ALTER TABLE task_checklist_item ADD COLUMN deletion_id UUID REFERENCES deletion (id);
+ALTER TABLE task_dependency     ADD COLUMN deletion_id UUID REFERENCES deletion (id);
+ALTER TABLE comment             ADD COLUMN deletion_id UUID REFERENCES deletion (id);
+ALTER TABLE timesheet           ADD COLUMN deletion_id UUID REFERENCES deletion (id);
+ALTER TABLE workday             ADD COLUMN deletion_id UUID REFERENCES deletion (id);
+ALTER TABLE event_budget_line   ADD COLUMN deletion_id UUID REFERENCES deletion (id);
+ALTER TABLE calendar_feed       ADD COLUMN deletion_id UUID REFERENCES deletion (id);
+ALTER TABLE submission_policy   ADD COLUMN deletion_id UUID REFERENCES deletion (id);
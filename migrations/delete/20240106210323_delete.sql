@@ -4,16 +4,41 @@
 -- Note: Make sure any change in order of tables gets synced
 --       with the SQL deletion script.
 --
+DELETE FROM event_template_checklist_item;
+DELETE FROM event_template_task_dependency;
+DELETE FROM event_template_task; -- must be after its checklist items and dependencies
+DELETE FROM event_template_company;
+DELETE FROM event_template_staff;
+DELETE FROM event_template; -- must be before `company`, `employment` and `event`
+DELETE FROM event_budget_line;
 DELETE FROM assigned_staff;
 DELETE FROM associated_company;
 DELETE FROM address;
-DELETE FROM comment;
-DELETE FROM task; -- must be after `comment`
-DELETE FROM event_staff; -- must be after `task`
-DELETE FROM workday;
-DELETE FROM timesheet; -- must be after `workday`, before `company`
+DELETE FROM comment_mention;
+DELETE FROM comment_revision;
+DELETE FROM comment; -- must be after its mentions and revisions
+DELETE FROM task_checklist_item;
+DELETE FROM task_dependency;
+DELETE FROM task; -- must be after `comment`, `task_checklist_item` and `task_dependency`
+DELETE FROM event_staff; -- must be after `task` and `comment_mention`
+DELETE FROM workday_flag;
+DELETE FROM timesheet_amendment_day;
+DELETE FROM timesheet_amendment; -- must be after its days
+DELETE FROM workday; -- must be after `workday_flag` and `timesheet_amendment_day`
+DELETE FROM timesheet_history;
+DELETE FROM submission_reminder;
+DELETE FROM timesheet; -- must be after `workday`, `timesheet_history`, `submission_reminder` and `timesheet_amendment`, before `company`
 DELETE FROM employment; -- must be after `event_staff` and `timesheet`
-DELETE FROM company; -- must be after `address` and `associated_company`
-DELETE FROM user_record;
+DELETE FROM submission_policy;
+DELETE FROM company; -- must be after `address`, `associated_company` and `submission_policy`
+DELETE FROM calendar_feed;
+DELETE FROM mail_outbox;
+DELETE FROM mail_setting;
+DELETE FROM notification;
+DELETE FROM notification_preference;
+DELETE FROM user_record; -- must be after the notifications, mails and calendar feeds
 DELETE FROM event; -- must be after `comment`
 DELETE FROM wage_preset;
+DELETE FROM job_run;
+DELETE FROM job; -- must be after its runs
+DELETE FROM deletion; -- must be after every table with a `deletion_id`
@@ -1,4 +1,10 @@
 
+-- Views
+DROP VIEW IF EXISTS report_monthly_hours;
+DROP VIEW IF EXISTS report_staff_participation;
+DROP VIEW IF EXISTS report_timesheet_review;
+DROP VIEW IF EXISTS report_task_completion;
+
 -- Triggers
 DROP TRIGGER IF EXISTS
     trigger_update_timesheet_total_hours_after_insert
@@ -9,38 +15,86 @@ DROP TRIGGER IF EXISTS
 DROP TRIGGER IF EXISTS
     trigger_update_timesheet_total_hours_after_delete
     On workday;
+DROP TRIGGER IF EXISTS trigger_notify_task_change ON task;
+DROP TRIGGER IF EXISTS trigger_notify_event_staff_change ON event_staff;
+DROP TRIGGER IF EXISTS trigger_notify_assigned_staff_change ON assigned_staff;
+DROP TRIGGER IF EXISTS trigger_notify_comment_change ON comment;
+DROP TRIGGER IF EXISTS trigger_track_timesheet_review ON timesheet;
 
 -- Functions
 DROP FUNCTION IF EXISTS update_timesheet_total_hours_after_insert;
 DROP FUNCTION IF EXISTS compute_timesheet_total_hours;
+DROP FUNCTION IF EXISTS notify_event_change;
+DROP FUNCTION IF EXISTS notify_assigned_staff_change;
+DROP FUNCTION IF EXISTS notify_comment_change;
+DROP FUNCTION IF EXISTS publish_event_change;
+DROP FUNCTION IF EXISTS track_timesheet_review;
+DROP FUNCTION IF EXISTS employment_subordinates;
+DROP FUNCTION IF EXISTS timesheet_deadline;
+DROP FUNCTION IF EXISTS submission_deadline;
 
 --
 -- Tables
 --
 -- Note: Keep a same table order as that present in the emptying SQL script.
 --
+DROP TABLE IF EXISTS event_template_checklist_item;
+DROP TABLE IF EXISTS event_template_task_dependency;
+DROP TABLE IF EXISTS event_template_task;
+DROP TABLE IF EXISTS event_template_company;
+DROP TABLE IF EXISTS event_template_staff;
+DROP TABLE IF EXISTS event_template;
+DROP TABLE IF EXISTS event_budget_line;
 DROP TABLE IF EXISTS assigned_staff;
 DROP TABLE IF EXISTS associated_company;
 DROP TABLE IF EXISTS address;
+DROP TABLE IF EXISTS comment_mention;
+DROP TABLE IF EXISTS comment_revision;
 DROP TABLE IF EXISTS comment;
+DROP TABLE IF EXISTS task_checklist_item;
+DROP TABLE IF EXISTS task_dependency;
 DROP TABLE IF EXISTS task;
 DROP TABLE IF EXISTS event_staff;
+DROP TABLE IF EXISTS workday_flag;
+DROP TABLE IF EXISTS timesheet_amendment_day;
+DROP TABLE IF EXISTS timesheet_amendment;
 DROP TABLE IF EXISTS workday;
+DROP TABLE IF EXISTS timesheet_history;
+DROP TABLE IF EXISTS submission_reminder;
 DROP TABLE IF EXISTS timesheet;
 DROP TABLE IF EXISTS employment;
+DROP TABLE IF EXISTS submission_policy;
 DROP TABLE IF EXISTS company;
+DROP TABLE IF EXISTS calendar_feed;
+DROP TABLE IF EXISTS mail_outbox;
+DROP TABLE IF EXISTS mail_setting;
+DROP TABLE IF EXISTS notification;
+DROP TABLE IF EXISTS notification_preference;
 DROP TABLE IF EXISTS user_record;
 DROP TABLE IF EXISTS event;
 DROP TABLE IF EXISTS wage_preset;
+DROP TABLE IF EXISTS job_run;
+DROP TABLE IF EXISTS job;
+DROP TABLE IF EXISTS deletion;
 
 -- Enums
 DROP TYPE IF EXISTS acceptance_status;
+DROP TYPE IF EXISTS amendment_status;
 DROP TYPE IF EXISTS approval_status;
 DROP TYPE IF EXISTS association;
+DROP TYPE IF EXISTS deleted_entity;
 DROP TYPE IF EXISTS employment_contract;
 DROP TYPE IF EXISTS employee_level;
 DROP TYPE IF EXISTS event_role;
+DROP TYPE IF EXISTS event_status;
 DROP TYPE IF EXISTS gender;
+DROP TYPE IF EXISTS job_run_status;
+DROP TYPE IF EXISTS mail_delivery;
+DROP TYPE IF EXISTS mail_status;
+DROP TYPE IF EXISTS notification_kind;
+DROP TYPE IF EXISTS overdue_policy;
 DROP TYPE IF EXISTS task_priority;
+DROP TYPE IF EXISTS task_status;
+DROP TYPE IF EXISTS timesheet_transition;
 DROP TYPE IF EXISTS user_role;
 DROP TYPE IF EXISTS user_status;
@@ -0,0 +1,32 @@
+-- Task workflow: status columns of the event task board, due dates,
+-- effort estimates and the ordering of tasks within a board column.
+
+CREATE TYPE task_status             AS ENUM ('todo', 'in_progress', 'blocked',
+                                             'review', 'done');
+
+ALTER TABLE task
+    ADD COLUMN status           task_status NOT NULL DEFAULT 'todo',
+    ADD COLUMN due_at           TIMESTAMP,
+    ADD COLUMN estimated_hours  REAL,
+    ADD COLUMN position         INTEGER NOT NULL DEFAULT 0,
+    ADD CONSTRAINT check_task_estimated_hours_gte_0
+        CHECK (estimated_hours IS NULL OR estimated_hours >= 0.0),
+    ADD CONSTRAINT check_task_position_gte_0
+        CHECK (position >= 0);
+
+-- Tasks finished before the workflow existed belong to the `done` column.
+UPDATE task
+SET status = 'done'
+WHERE finished_at IS NOT NULL;
+
+-- Give existing tasks a stable order within their column.
+UPDATE task
+SET position = ordered.row_number - 1
+FROM (SELECT id,
+             ROW_NUMBER() OVER (PARTITION BY event_id, status
+                                ORDER BY title) AS row_number
+      FROM task) AS ordered
+WHERE task.id = ordered.id;
+
+CREATE INDEX task_event_id_status_position_idx
+    ON task (event_id, status, position);
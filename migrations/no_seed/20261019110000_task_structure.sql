@@ -0,0 +1,61 @@
+-- Task structure: subtasks, checklist items and "blocked by" dependencies.
+
+ALTER TABLE task
+    ADD COLUMN parent_task_id   UUID,
+    ADD FOREIGN KEY (parent_task_id) REFERENCES task (id),
+    ADD CONSTRAINT check_task_parent_task_id_neq_id
+        CHECK (parent_task_id IS NULL OR parent_task_id != id);
+
+CREATE INDEX task_parent_task_id_idx
+    ON task (parent_task_id);
+
+
+CREATE TABLE task_checklist_item
+(
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -------------------------------------------------------
+    task_id     UUID NOT NULL,
+    -------------------------------------------------------
+    content     VARCHAR(255) NOT NULL,
+    position    INTEGER NOT NULL DEFAULT 0,
+    finished_at TIMESTAMP,
+    -------------------------------------------------------
+    created_at  TIMESTAMP NOT NULL DEFAULT now(),
+    edited_at   TIMESTAMP NOT NULL DEFAULT now(),
+    deleted_at  TIMESTAMP,
+    -------------------------------------------------------
+    FOREIGN KEY (task_id) REFERENCES task (id),
+    -------------------------------------------------------
+    CONSTRAINT check_task_checklist_item_content_len
+        CHECK (char_length(content) >= 1),
+    CONSTRAINT check_task_checklist_item_position_gte_0
+        CHECK (position >= 0),
+    CONSTRAINT check_task_checklist_item_created_at_lte_edited_at
+        CHECK (edited_at >= created_at)
+);
+
+
+--
+-- `task_id` can't be finished until `blocker_id` is.
+--
+CREATE TABLE task_dependency
+(
+    task_id     UUID NOT NULL,
+    blocker_id  UUID NOT NULL,
+    -------------------------------------------------------
+    created_at  TIMESTAMP NOT NULL DEFAULT now(),
+    edited_at   TIMESTAMP NOT NULL DEFAULT now(),
+    deleted_at  TIMESTAMP,
+    -------------------------------------------------------
+    PRIMARY KEY (task_id, blocker_id),
+    FOREIGN KEY (task_id) REFERENCES task (id),
+    FOREIGN KEY (blocker_id) REFERENCES task (id),
+    -------------------------------------------------------
+    CONSTRAINT check_task_dependency_task_id_neq_blocker_id
+        CHECK (task_id != blocker_id),
+    CONSTRAINT check_task_dependency_created_at_lte_edited_at
+        CHECK (edited_at >= created_at)
+);
+
+CREATE INDEX task_dependency_blocker_id_idx
+    ON task_dependency (blocker_id);
@@ -0,0 +1,158 @@
+-- Event templates: a reusable snapshot of an event, its tasks and its people,
+-- from which next editions of the event get instantiated.
+--
+-- All dates are stored relative to the start of the event.
+
+CREATE TABLE event_template
+(
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -------------------------------------------------------
+    company_id      UUID NOT NULL,
+    source_event_id UUID,
+    -------------------------------------------------------
+    name            VARCHAR(255) NOT NULL,
+    description     TEXT,
+    website         VARCHAR(255),
+    accepts_staff   BOOLEAN NOT NULL DEFAULT true,
+    duration_days   INTEGER NOT NULL DEFAULT 0,
+    -------------------------------------------------------
+    created_at      TIMESTAMP NOT NULL DEFAULT now(),
+    edited_at       TIMESTAMP NOT NULL DEFAULT now(),
+    deleted_at      TIMESTAMP,
+    -------------------------------------------------------
+    FOREIGN KEY (company_id) REFERENCES company (id),
+    FOREIGN KEY (source_event_id) REFERENCES event (id),
+    -------------------------------------------------------
+    CONSTRAINT check_event_template_name_len
+        CHECK (char_length(name) >= 1),
+    CONSTRAINT check_event_template_duration_days_gte_0
+        CHECK (duration_days >= 0),
+    CONSTRAINT check_event_template_created_at_lte_edited_at
+        CHECK (edited_at >= created_at)
+);
+
+CREATE INDEX event_template_company_id_idx
+    ON event_template (company_id);
+
+
+CREATE TABLE event_template_task
+(
+    id                 UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -------------------------------------------------------
+    template_id        UUID NOT NULL,
+    parent_id          UUID,
+    -------------------------------------------------------
+    title              VARCHAR(255) NOT NULL,
+    description        TEXT,
+    priority           task_priority NOT NULL DEFAULT 'medium',
+    accepts_staff      BOOLEAN NOT NULL DEFAULT true,
+    estimated_hours    REAL,
+    -- minutes between the start of the event and the task deadline
+    due_offset_minutes INTEGER,
+    position           INTEGER NOT NULL DEFAULT 0,
+    -------------------------------------------------------
+    created_at         TIMESTAMP NOT NULL DEFAULT now(),
+    edited_at          TIMESTAMP NOT NULL DEFAULT now(),
+    deleted_at         TIMESTAMP,
+    -------------------------------------------------------
+    FOREIGN KEY (template_id) REFERENCES event_template (id),
+    FOREIGN KEY (parent_id) REFERENCES event_template_task (id),
+    -------------------------------------------------------
+    CONSTRAINT check_event_template_task_title_len
+        CHECK (char_length(title) >= 1),
+    CONSTRAINT check_event_template_task_estimated_hours_gte_0
+        CHECK (estimated_hours IS NULL OR estimated_hours >= 0.0),
+    CONSTRAINT check_event_template_task_position_gte_0
+        CHECK (position >= 0),
+    CONSTRAINT check_event_template_task_created_at_lte_edited_at
+        CHECK (edited_at >= created_at)
+);
+
+CREATE INDEX event_template_task_template_id_idx
+    ON event_template_task (template_id);
+
+
+CREATE TABLE event_template_checklist_item
+(
+    id               UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -------------------------------------------------------
+    template_task_id UUID NOT NULL,
+    -------------------------------------------------------
+    content          VARCHAR(255) NOT NULL,
+    position         INTEGER NOT NULL DEFAULT 0,
+    -------------------------------------------------------
+    created_at       TIMESTAMP NOT NULL DEFAULT now(),
+    edited_at        TIMESTAMP NOT NULL DEFAULT now(),
+    deleted_at       TIMESTAMP,
+    -------------------------------------------------------
+    FOREIGN KEY (template_task_id) REFERENCES event_template_task (id),
+    -------------------------------------------------------
+    CONSTRAINT check_event_template_checklist_item_content_len
+        CHECK (char_length(content) >= 1),
+    CONSTRAINT check_event_template_checklist_item_created_at_lte_edited_at
+        CHECK (edited_at >= created_at)
+);
+
+
+CREATE TABLE event_template_task_dependency
+(
+    template_task_id UUID NOT NULL,
+    blocker_id       UUID NOT NULL,
+    -------------------------------------------------------
+    created_at       TIMESTAMP NOT NULL DEFAULT now(),
+    edited_at        TIMESTAMP NOT NULL DEFAULT now(),
+    deleted_at       TIMESTAMP,
+    -------------------------------------------------------
+    PRIMARY KEY (template_task_id, blocker_id),
+    FOREIGN KEY (template_task_id) REFERENCES event_template_task (id),
+    FOREIGN KEY (blocker_id) REFERENCES event_template_task (id),
+    -------------------------------------------------------
+    CONSTRAINT check_event_template_task_dependency_created_at_lte_edited_at
+        CHECK (edited_at >= created_at)
+);
+
+
+CREATE TABLE event_template_company
+(
+    template_id UUID NOT NULL,
+    company_id  UUID NOT NULL,
+    -------------------------------------------------------
+    type        association NOT NULL,
+    -------------------------------------------------------
+    created_at  TIMESTAMP NOT NULL DEFAULT now(),
+    edited_at   TIMESTAMP NOT NULL DEFAULT now(),
+    deleted_at  TIMESTAMP,
+    -------------------------------------------------------
+    PRIMARY KEY (template_id, company_id),
+    FOREIGN KEY (template_id) REFERENCES event_template (id),
+    FOREIGN KEY (company_id) REFERENCES company (id),
+    -------------------------------------------------------
+    CONSTRAINT check_event_template_company_created_at_lte_edited_at
+        CHECK (edited_at >= created_at)
+);
+
+
+--
+-- The staffing of an edition. People are invited again in their old roles
+-- and have to be accepted by the organizers of the new edition.
+--
+CREATE TABLE event_template_staff
+(
+    template_id UUID NOT NULL,
+    user_id     UUID NOT NULL,
+    company_id  UUID NOT NULL,
+    -------------------------------------------------------
+    role        event_role NOT NULL DEFAULT 'staff',
+    -------------------------------------------------------
+    created_at  TIMESTAMP NOT NULL DEFAULT now(),
+    edited_at   TIMESTAMP NOT NULL DEFAULT now(),
+    deleted_at  TIMESTAMP,
+    -------------------------------------------------------
+    PRIMARY KEY (template_id, user_id, company_id),
+    FOREIGN KEY (template_id) REFERENCES event_template (id),
+    FOREIGN KEY (user_id, company_id)
+        REFERENCES employment (user_id, company_id),
+    -------------------------------------------------------
+    CONSTRAINT check_event_template_staff_created_at_lte_edited_at
+        CHECK (edited_at >= created_at)
+);
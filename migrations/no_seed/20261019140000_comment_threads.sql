@@ -0,0 +1,56 @@
+-- Comment threads: replies, @mentions of event staff and the edit history.
+
+ALTER TABLE comment
+    ADD COLUMN parent_id    UUID,
+    ADD FOREIGN KEY (parent_id) REFERENCES comment (id),
+    ADD CONSTRAINT check_comment_parent_id_neq_id
+        CHECK (parent_id IS NULL OR parent_id != id);
+
+CREATE INDEX comment_parent_id_idx
+    ON comment (parent_id);
+
+
+CREATE TABLE comment_mention
+(
+    comment_id  UUID NOT NULL,
+    staff_id    UUID NOT NULL,
+    -------------------------------------------------------
+    created_at  TIMESTAMP NOT NULL DEFAULT now(),
+    edited_at   TIMESTAMP NOT NULL DEFAULT now(),
+    deleted_at  TIMESTAMP,
+    -------------------------------------------------------
+    PRIMARY KEY (comment_id, staff_id),
+    FOREIGN KEY (comment_id) REFERENCES comment (id),
+    FOREIGN KEY (staff_id) REFERENCES event_staff (id),
+    -------------------------------------------------------
+    CONSTRAINT check_comment_mention_created_at_lte_edited_at
+        CHECK (edited_at >= created_at)
+);
+
+CREATE INDEX comment_mention_staff_id_idx
+    ON comment_mention (staff_id);
+
+
+--
+-- Prior versions of a comment, `created_at` is the moment it got replaced.
+--
+CREATE TABLE comment_revision
+(
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -------------------------------------------------------
+    comment_id  UUID NOT NULL,
+    -------------------------------------------------------
+    content     TEXT NOT NULL,
+    -------------------------------------------------------
+    created_at  TIMESTAMP NOT NULL DEFAULT now(),
+    edited_at   TIMESTAMP NOT NULL DEFAULT now(),
+    deleted_at  TIMESTAMP,
+    -------------------------------------------------------
+    FOREIGN KEY (comment_id) REFERENCES comment (id),
+    -------------------------------------------------------
+    CONSTRAINT check_comment_revision_created_at_lte_edited_at
+        CHECK (edited_at >= created_at)
+);
+
+CREATE INDEX comment_revision_comment_id_idx
+    ON comment_revision (comment_id);
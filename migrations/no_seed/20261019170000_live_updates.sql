@@ -0,0 +1,104 @@
+-- Changes of an event's panels are published on the `event_change` channel
+-- so every application instance can push them to its open pages.
+-- The payload is a JSON object: {"event_id": "<uuid>", "topic": "<topic>"}.
+
+CREATE OR REPLACE FUNCTION publish_event_change(changed_event_id UUID, topic TEXT)
+RETURNS VOID AS $$
+BEGIN
+    IF changed_event_id IS NOT NULL THEN
+        PERFORM pg_notify(
+            'event_change',
+            json_build_object('event_id', changed_event_id, 'topic', topic)::TEXT
+        );
+    END IF;
+END;
+$$ LANGUAGE plpgsql;
+
+-- For tables referencing the event directly, the topic is the trigger argument.
+CREATE OR REPLACE FUNCTION notify_event_change()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        PERFORM publish_event_change(OLD.event_id, TG_ARGV[0]);
+        RETURN OLD;
+    END IF;
+
+    PERFORM publish_event_change(NEW.event_id, TG_ARGV[0]);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION notify_assigned_staff_change()
+RETURNS TRIGGER AS $$
+DECLARE
+    changed_task_id UUID;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        changed_task_id := OLD.task_id;
+    ELSE
+        changed_task_id := NEW.task_id;
+    END IF;
+
+    PERFORM publish_event_change(
+        (SELECT event_id FROM task WHERE id = changed_task_id),
+        'assigned-staff'
+    );
+
+    IF TG_OP = 'DELETE' THEN
+        RETURN OLD;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Task comments are published under their own topic, they don't belong
+-- to the event discussion.
+CREATE OR REPLACE FUNCTION notify_comment_change()
+RETURNS TRIGGER AS $$
+DECLARE
+    changed_event_id UUID;
+    changed_task_id UUID;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        changed_event_id := OLD.event_id;
+        changed_task_id := OLD.task_id;
+    ELSE
+        changed_event_id := NEW.event_id;
+        changed_task_id := NEW.task_id;
+    END IF;
+
+    IF changed_event_id IS NOT NULL THEN
+        PERFORM publish_event_change(changed_event_id, 'comments');
+    ELSE
+        PERFORM publish_event_change(
+            (SELECT event_id FROM task WHERE id = changed_task_id),
+            'task-comments'
+        );
+    END IF;
+
+    IF TG_OP = 'DELETE' THEN
+        RETURN OLD;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_notify_task_change
+AFTER INSERT OR UPDATE OR DELETE ON task
+FOR EACH ROW
+EXECUTE FUNCTION notify_event_change('tasks');
+
+CREATE TRIGGER trigger_notify_event_staff_change
+AFTER INSERT OR UPDATE OR DELETE ON event_staff
+FOR EACH ROW
+EXECUTE FUNCTION notify_event_change('staff');
+
+CREATE TRIGGER trigger_notify_assigned_staff_change
+AFTER INSERT OR UPDATE OR DELETE ON assigned_staff
+FOR EACH ROW
+EXECUTE FUNCTION notify_assigned_staff_change();
+
+CREATE TRIGGER trigger_notify_comment_change
+AFTER INSERT OR UPDATE OR DELETE ON comment
+FOR EACH ROW
+EXECUTE FUNCTION notify_comment_change();
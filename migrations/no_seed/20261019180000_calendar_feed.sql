@@ -0,0 +1,29 @@
+-- Secret calendar feed URLs. A feed without an event lists everything the
+-- user works on, a feed of an event is meant for its organizers.
+-- Rotating a feed deletes the old token and creates a new one.
+
+CREATE TABLE calendar_feed
+(
+    token       UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -------------------------------------------------------
+    user_id     UUID NOT NULL,
+    event_id    UUID,
+    -------------------------------------------------------
+    created_at  TIMESTAMP NOT NULL DEFAULT now(),
+    edited_at   TIMESTAMP NOT NULL DEFAULT now(),
+    deleted_at  TIMESTAMP,
+    -------------------------------------------------------
+    FOREIGN KEY (user_id) REFERENCES user_record (id),
+    FOREIGN KEY (event_id) REFERENCES event (id),
+    -------------------------------------------------------
+    CONSTRAINT check_calendar_feed_created_at_lte_edited_at
+        CHECK (edited_at >= created_at)
+);
+
+CREATE UNIQUE INDEX calendar_feed_user_id_uniq
+    ON calendar_feed (user_id)
+    WHERE event_id IS NULL AND deleted_at IS NULL;
+
+CREATE UNIQUE INDEX calendar_feed_user_id_event_id_uniq
+    ON calendar_feed (user_id, event_id)
+    WHERE event_id IS NOT NULL AND deleted_at IS NULL;
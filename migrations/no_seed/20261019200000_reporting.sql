@@ -0,0 +1,121 @@
+-- Reporting: aggregate views behind the administration dashboard, along with
+-- the times timesheets were submitted and reviewed the approval latency is
+-- measured with.
+
+ALTER TABLE timesheet
+    ADD COLUMN submitted_at TIMESTAMP,
+    ADD COLUMN reviewed_at  TIMESTAMP;
+
+-- It isn't known when the already reviewed timesheets were submitted, they
+-- are left out of the latency.
+UPDATE timesheet
+SET submitted_at = edited_at
+WHERE status = 'pending';
+
+UPDATE timesheet
+SET reviewed_at = edited_at
+WHERE status IN ('accepted', 'rejected');
+
+CREATE OR REPLACE FUNCTION track_timesheet_review()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.status IS DISTINCT FROM OLD.status THEN
+        IF NEW.status = 'pending' THEN
+            NEW.submitted_at := now();
+            NEW.reviewed_at := NULL;
+        ELSIF NEW.status IN ('accepted', 'rejected') THEN
+            NEW.reviewed_at := now();
+        END IF;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_track_timesheet_review
+    BEFORE UPDATE OF status ON timesheet
+    FOR EACH ROW EXECUTE FUNCTION track_timesheet_review();
+
+-- Hours reported per event, company and month.
+CREATE VIEW report_monthly_hours AS
+SELECT timesheet.event_id,
+       event.name                                        AS event_name,
+       timesheet.company_id,
+       company.name                                      AS company_name,
+       date_trunc('month', workday.date)::DATE           AS month,
+       SUM(workday.total_hours)::FLOAT8                  AS reported_hours,
+       COALESCE(SUM(workday.total_hours)
+                    FILTER (WHERE timesheet.status = 'accepted'),
+                0)::FLOAT8                               AS accepted_hours,
+       COUNT(DISTINCT timesheet.user_id)                 AS staff_count
+FROM workday
+    INNER JOIN timesheet ON workday.timesheet_id = timesheet.id
+    INNER JOIN event ON timesheet.event_id = event.id
+    INNER JOIN company ON timesheet.company_id = company.id
+WHERE workday.deleted_at IS NULL
+  AND workday.total_hours > 0
+  AND timesheet.deleted_at IS NULL
+GROUP BY timesheet.event_id, event.name,
+         timesheet.company_id, company.name,
+         date_trunc('month', workday.date);
+
+-- Every accepted staff member of an event with the hours they reported there.
+CREATE VIEW report_staff_participation AS
+SELECT event_staff.user_id,
+       event_staff.company_id,
+       company.name                         AS company_name,
+       event_staff.event_id,
+       event.start_date,
+       event.end_date,
+       COALESCE(worked.hours, 0)::FLOAT8    AS worked_hours
+FROM event_staff
+    INNER JOIN event ON event_staff.event_id = event.id
+    INNER JOIN company ON event_staff.company_id = company.id
+    LEFT JOIN (SELECT timesheet.user_id,
+                      timesheet.company_id,
+                      timesheet.event_id,
+                      SUM(workday.total_hours) AS hours
+               FROM timesheet
+                   INNER JOIN workday ON workday.timesheet_id = timesheet.id
+               WHERE timesheet.deleted_at IS NULL
+                 AND workday.deleted_at IS NULL
+               GROUP BY timesheet.user_id, timesheet.company_id,
+                        timesheet.event_id) AS worked
+        ON worked.user_id = event_staff.user_id
+       AND worked.company_id = event_staff.company_id
+       AND worked.event_id = event_staff.event_id
+WHERE event_staff.status = 'accepted'
+  AND event_staff.deleted_at IS NULL
+  AND event.deleted_at IS NULL;
+
+-- Submitted timesheets, the latency is missing until they are reviewed.
+CREATE VIEW report_timesheet_review AS
+SELECT timesheet.id                         AS timesheet_id,
+       timesheet.company_id,
+       company.name                         AS company_name,
+       timesheet.event_id,
+       timesheet.status,
+       timesheet.submitted_at,
+       timesheet.reviewed_at,
+       (EXTRACT(EPOCH FROM timesheet.reviewed_at - timesheet.submitted_at)
+            / 3600)::FLOAT8                 AS latency_hours
+FROM timesheet
+    INNER JOIN company ON timesheet.company_id = company.id
+WHERE timesheet.deleted_at IS NULL
+  AND timesheet.submitted_at IS NOT NULL;
+
+-- Task completion of every event.
+CREATE VIEW report_task_completion AS
+SELECT event.id                                                AS event_id,
+       event.name                                              AS event_name,
+       event.start_date,
+       event.end_date,
+       COUNT(task.id)                                          AS tasks,
+       COUNT(task.id) FILTER (WHERE task.status = 'done')      AS done_tasks,
+       COUNT(task.id) FILTER (WHERE task.status <> 'done'
+                                AND task.due_at < now())       AS overdue_tasks
+FROM event
+    LEFT JOIN task ON task.event_id = event.id
+                  AND task.deleted_at IS NULL
+WHERE event.deleted_at IS NULL
+GROUP BY event.id, event.name, event.start_date, event.end_date;
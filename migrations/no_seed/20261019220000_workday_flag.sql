@@ -0,0 +1,47 @@
+-- Timesheet review per workday: a manager flags the days which need a change
+-- along with a suggested correction, and may approve a timesheet with those
+-- corrections applied. The employee then has to acknowledge the adjustments.
+
+ALTER TYPE timesheet_transition ADD VALUE 'adjust';
+ALTER TYPE timesheet_transition ADD VALUE 'acknowledge';
+
+-- Set when the timesheet was approved with adjustments, both are cleared by
+-- any later transition except the acknowledgement.
+ALTER TABLE timesheet
+    ADD COLUMN adjusted_at     TIMESTAMP,
+    ADD COLUMN acknowledged_at TIMESTAMP;
+
+CREATE TABLE workday_flag
+(
+    id                UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -------------------------------------------------------
+    timesheet_id      UUID NOT NULL,
+    date              DATE NOT NULL,
+    flagged_by        UUID NOT NULL,
+    reason            VARCHAR(255) NOT NULL,
+    suggested_hours   REAL,
+    suggested_comment VARCHAR(255),
+    -- The workday as the manager saw it, the employee's changes are compared
+    -- with it once the timesheet is submitted again.
+    original_hours    REAL NOT NULL,
+    original_comment  TEXT,
+    -- Whether the suggestion was applied by approving with adjustments.
+    applied           BOOLEAN NOT NULL DEFAULT false,
+    -------------------------------------------------------
+    created_at        TIMESTAMP NOT NULL DEFAULT now(),
+    resolved_at       TIMESTAMP,
+    -------------------------------------------------------
+    FOREIGN KEY (timesheet_id, date)
+        REFERENCES workday (timesheet_id, date) ON DELETE CASCADE,
+    FOREIGN KEY (flagged_by) REFERENCES user_record (id),
+    -------------------------------------------------------
+    CONSTRAINT check_workday_flag_suggested_hours_between_0_and_24
+        CHECK (suggested_hours BETWEEN 0.0 AND 24.0),
+    CONSTRAINT check_workday_flag_applied_is_resolved
+        CHECK (NOT applied OR resolved_at IS NOT NULL)
+);
+
+-- A workday has at most one open flag.
+CREATE UNIQUE INDEX workday_flag_open_idx
+    ON workday_flag (timesheet_id, date)
+    WHERE resolved_at IS NULL;
@@ -0,0 +1,26 @@
+-- Timesheet review queue: everyone a manager can review timesheets of, that
+-- is their direct subordinates as well as the subordinates delegated to them
+-- through the managers below them.
+
+CREATE OR REPLACE FUNCTION employment_subordinates(superior_id UUID)
+RETURNS TABLE (user_id UUID, company_id UUID, is_direct BOOLEAN) AS $$
+    WITH RECURSIVE subordinates AS (
+        SELECT employment.user_id, employment.company_id, true AS is_direct
+        FROM employment
+        WHERE employment.manager_id = superior_id
+          AND employment.deleted_at IS NULL
+        UNION
+        SELECT employment.user_id, employment.company_id, false
+        FROM employment
+            INNER JOIN subordinates
+                ON employment.manager_id = subordinates.user_id
+               AND employment.company_id = subordinates.company_id
+        WHERE employment.deleted_at IS NULL
+    )
+    SELECT subordinates.user_id,
+           subordinates.company_id,
+           bool_or(subordinates.is_direct)
+    FROM subordinates
+    WHERE subordinates.user_id <> superior_id
+    GROUP BY subordinates.user_id, subordinates.company_id;
+$$ LANGUAGE sql STABLE;
@@ -0,0 +1,61 @@
+-- Timesheet submission deadlines: a company may require the timesheets to be
+-- submitted by a day of the month after the one they end in. The employees
+-- are reminded ahead of the deadline, and the sheets still not submitted
+-- afterwards are either submitted as they are or locked.
+
+ALTER TYPE notification_kind ADD VALUE 'submission_reminder';
+
+CREATE TYPE overdue_policy          AS ENUM ('submit', 'lock');
+
+CREATE TABLE submission_policy
+(
+    company_id      UUID PRIMARY KEY,
+    -------------------------------------------------------
+    deadline_day    INTEGER NOT NULL,
+    -- How many days before the deadline the employees are reminded.
+    reminder_days   INTEGER[] NOT NULL DEFAULT '{3, 1}',
+    overdue_policy  overdue_policy NOT NULL DEFAULT 'lock',
+    -------------------------------------------------------
+    created_at      TIMESTAMP NOT NULL DEFAULT now(),
+    edited_at       TIMESTAMP NOT NULL DEFAULT now(),
+    deleted_at      TIMESTAMP,
+    -------------------------------------------------------
+    FOREIGN KEY (company_id) REFERENCES company (id),
+    -------------------------------------------------------
+    -- Every month has the deadline day.
+    CONSTRAINT check_submission_policy_deadline_day_between_1_and_28
+        CHECK (deadline_day BETWEEN 1 AND 28),
+    CONSTRAINT check_submission_policy_reminder_days_between_0_and_28
+        CHECK (0 <= ALL (reminder_days) AND 28 >= ALL (reminder_days)),
+    CONSTRAINT check_submission_policy_created_at_lte_edited_at
+        CHECK (edited_at >= created_at)
+);
+
+
+--
+-- The reminders which were sent, so each of them is sent once.
+--
+CREATE TABLE submission_reminder
+(
+    timesheet_id    UUID NOT NULL,
+    deadline        DATE NOT NULL,
+    days_before     INTEGER NOT NULL,
+    -------------------------------------------------------
+    created_at      TIMESTAMP NOT NULL DEFAULT now(),
+    -------------------------------------------------------
+    PRIMARY KEY (timesheet_id, deadline, days_before),
+    FOREIGN KEY (timesheet_id) REFERENCES timesheet (id)
+);
+
+-- `overdue_at` is set once the policy was applied to a sheet missing its
+-- deadline. A locked sheet can only be submitted as it is, until its manager
+-- rejects or reopens it.
+ALTER TABLE timesheet
+    ADD COLUMN overdue_at TIMESTAMP,
+    ADD COLUMN locked_at  TIMESTAMP;
+
+-- A timesheet is due by the deadline day of the month after the one it ends in.
+CREATE OR REPLACE FUNCTION submission_deadline(end_date DATE, deadline_day INTEGER)
+RETURNS DATE AS $$
+    SELECT (date_trunc('month', end_date) + interval '1 month')::DATE + (deadline_day - 1);
+$$ LANGUAGE sql IMMUTABLE;
@@ -0,0 +1,67 @@
+-- Corrections of accepted timesheets. The workdays of an accepted timesheet
+-- stay as they were paid, an approved amendment is settled as a wage
+-- difference in the payroll of the next month instead.
+
+CREATE TYPE amendment_status AS ENUM (
+    'pending',
+    'approved',
+    'rejected'
+);
+
+CREATE TABLE timesheet_amendment
+(
+    id               UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -------------------------------------------------------
+    timesheet_id     UUID NOT NULL,
+    proposed_by      UUID NOT NULL,
+    reason           VARCHAR(255) NOT NULL,
+    status           amendment_status NOT NULL DEFAULT 'pending',
+    decided_by       UUID,
+    decision_note    VARCHAR(255),
+    decided_at       TIMESTAMP,
+    -- The first day of the month whose payroll pays or recovers the
+    -- difference, set once the amendment is approved.
+    settlement_month DATE,
+    -------------------------------------------------------
+    created_at       TIMESTAMP NOT NULL DEFAULT now(),
+    edited_at        TIMESTAMP NOT NULL DEFAULT now(),
+    deleted_at       TIMESTAMP,
+    -------------------------------------------------------
+    FOREIGN KEY (timesheet_id) REFERENCES timesheet (id),
+    FOREIGN KEY (proposed_by) REFERENCES user_record (id),
+    FOREIGN KEY (decided_by) REFERENCES user_record (id),
+    -------------------------------------------------------
+    CONSTRAINT check_timesheet_amendment_decided
+        CHECK ((status = 'pending') = (decided_at IS NULL)),
+    CONSTRAINT check_timesheet_amendment_settled_iff_approved
+        CHECK ((status = 'approved') = (settlement_month IS NOT NULL))
+);
+
+-- A timesheet has at most one amendment waiting for the manager.
+CREATE UNIQUE INDEX timesheet_amendment_pending_idx
+    ON timesheet_amendment (timesheet_id)
+    WHERE status = 'pending' AND deleted_at IS NULL;
+
+CREATE INDEX timesheet_amendment_settlement_idx
+    ON timesheet_amendment (settlement_month)
+    WHERE status = 'approved' AND deleted_at IS NULL;
+
+CREATE TABLE timesheet_amendment_day
+(
+    amendment_id     UUID NOT NULL,
+    timesheet_id     UUID NOT NULL,
+    date             DATE NOT NULL,
+    -------------------------------------------------------
+    -- The workday as it was paid, including the amendments approved before.
+    original_hours   REAL NOT NULL,
+    original_comment TEXT,
+    total_hours      REAL NOT NULL,
+    comment          TEXT,
+    -------------------------------------------------------
+    PRIMARY KEY (amendment_id, date),
+    FOREIGN KEY (amendment_id) REFERENCES timesheet_amendment (id),
+    FOREIGN KEY (timesheet_id, date) REFERENCES workday (timesheet_id, date),
+    -------------------------------------------------------
+    CONSTRAINT check_timesheet_amendment_day_total_hours_between_0_and_24
+        CHECK (total_hours BETWEEN 0.0 AND 24.0)
+);
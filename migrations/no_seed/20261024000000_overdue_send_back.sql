@@ -0,0 +1,13 @@
+-- A timesheet its manager sends back after the deadline is no longer overdue,
+-- it is due by the deadline day of the month after the one it was sent back in.
+CREATE OR REPLACE FUNCTION timesheet_deadline(timesheet_id UUID, end_date DATE, deadline_day INTEGER)
+RETURNS DATE AS $$
+    SELECT COALESCE(submission_deadline(MAX(timesheet_history.created_at)::DATE, deadline_day),
+                    due.deadline)
+    FROM (SELECT submission_deadline(end_date, deadline_day) AS deadline) AS due
+        LEFT JOIN timesheet_history
+            ON timesheet_history.timesheet_id = timesheet_deadline.timesheet_id
+           AND timesheet_history.transition IN ('reject', 'reopen')
+           AND timesheet_history.created_at::DATE > due.deadline
+    GROUP BY due.deadline;
+$$ LANGUAGE sql STABLE;
@@ -0,0 +1,14 @@
+use actix_web::web;
+
+use crate::handlers::calendar_feed::{
+    get_event_calendar_feed, get_user_calendar_feed, rotate_event_calendar_feed,
+    rotate_user_calendar_feed,
+};
+
+pub fn configure_calendar_feed_endpoints(config: &mut web::ServiceConfig) {
+    config
+        .service(get_user_calendar_feed)
+        .service(rotate_user_calendar_feed)
+        .service(get_event_calendar_feed)
+        .service(rotate_event_calendar_feed);
+}
@@ -1,7 +1,7 @@
 use actix_web::web;
 
 use crate::handlers::comment::{
-    create_event_comment, create_task_comment, delete_comment, get_comment,
+    create_event_comment, create_task_comment, delete_comment, get_comment, get_comment_history,
     open_comment_update_mode, open_event_comments_for_user, open_task_comments_for_user,
     update_comment,
 };
@@ -15,5 +15,6 @@ pub fn configure_comment_endpoints(config: &mut web::ServiceConfig) {
         .service(open_comment_update_mode)
         .service(get_comment)
         .service(update_comment)
+        .service(get_comment_history)
         .service(delete_comment);
 }
@@ -4,6 +4,9 @@ use crate::handlers::employment::{
     create_employment, delete_employment, get_employment, get_employments_per_user,
     get_subordinates, toggle_employment_create, toggle_employment_edit, update_employment,
 };
+use crate::handlers::employment_import::{
+    commit_employment_import, preview_employment_import, toggle_employment_import,
+};
 
 pub fn configure_employment_endpoints(config: &mut web::ServiceConfig) {
     config
@@ -14,5 +17,8 @@ pub fn configure_employment_endpoints(config: &mut web::ServiceConfig) {
         .service(update_employment)
         .service(delete_employment)
         .service(toggle_employment_edit)
-        .service(toggle_employment_create);
+        .service(toggle_employment_create)
+        .service(toggle_employment_import)
+        .service(preview_employment_import)
+        .service(commit_employment_import);
 }
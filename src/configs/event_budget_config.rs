@@ -0,0 +1,10 @@
+use actix_web::web;
+
+use crate::handlers::event_budget::{create_budget_line, delete_budget_line, get_event_budget};
+
+pub fn configure_event_budget_endpoints(config: &mut web::ServiceConfig) {
+    config
+        .service(get_event_budget)
+        .service(create_budget_line)
+        .service(delete_budget_line);
+}
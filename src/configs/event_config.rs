@@ -1,7 +1,7 @@
 use actix_web::web;
 
 use crate::handlers::event::{
-    create_event, delete_event, get_event, get_events, remove_event_avatar,
+    change_event_status, create_event, delete_event, get_event, get_events, remove_event_avatar,
     switch_event_accepts_staff, toggle_event_creation_mode, toggle_event_edit_mode, update_event,
     upload_event_avatar,
 };
@@ -17,5 +17,6 @@ pub fn configure_event_endpoints(config: &mut web::ServiceConfig) {
         .service(remove_event_avatar)
         .service(toggle_event_edit_mode)
         .service(toggle_event_creation_mode)
-        .service(switch_event_accepts_staff);
+        .service(switch_event_accepts_staff)
+        .service(change_event_status);
 }
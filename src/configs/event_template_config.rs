@@ -0,0 +1,13 @@
+use actix_web::web;
+
+use crate::handlers::event_template::{
+    create_event_from_template, create_event_template, delete_event_template, get_event_templates,
+};
+
+pub fn configure_event_template_endpoints(config: &mut web::ServiceConfig) {
+    config
+        .service(get_event_templates)
+        .service(create_event_template)
+        .service(create_event_from_template)
+        .service(delete_event_template);
+}
@@ -0,0 +1,17 @@
+use actix_web::web;
+
+use crate::handlers::{
+    export::{export_company_timesheets, export_event_staff, export_event_tasks},
+    income_confirmation::{export_income_confirmations, get_income_confirmations},
+    payroll::export_company_payroll,
+};
+
+pub fn configure_export_endpoints(config: &mut web::ServiceConfig) {
+    config
+        .service(export_event_staff)
+        .service(export_event_tasks)
+        .service(export_company_timesheets)
+        .service(export_company_payroll)
+        .service(get_income_confirmations)
+        .service(export_income_confirmations);
+}
@@ -0,0 +1,7 @@
+use actix_web::web;
+
+use crate::handlers::job::{get_jobs, run_job};
+
+pub fn configure_job_endpoints(config: &mut web::ServiceConfig) {
+    config.service(get_jobs).service(run_job);
+}
@@ -0,0 +1,7 @@
+use actix_web::web;
+
+use crate::handlers::live_update::get_event_live_updates;
+
+pub fn configure_live_update_endpoints(config: &mut web::ServiceConfig) {
+    config.service(get_event_live_updates);
+}
@@ -1,10 +1,22 @@
 pub mod assigned_staff_config;
 pub mod associated_company_config;
+pub mod calendar_feed_config;
 pub mod comment_config;
 pub mod company_config;
 pub mod employment_config;
+pub mod event_budget_config;
 pub mod event_config;
+pub mod event_template_config;
+pub mod export_config;
+pub mod job_config;
+pub mod live_update_config;
+pub mod notification_config;
+pub mod report_config;
 pub mod staff_config;
+pub mod submission_policy_config;
+pub mod task_checklist_config;
 pub mod task_config;
+pub mod task_dependency_config;
 pub mod timesheet_config;
+pub mod trash_config;
 pub mod user_config;
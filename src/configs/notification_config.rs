@@ -0,0 +1,18 @@
+use actix_web::web;
+
+use crate::handlers::notification::{
+    get_notification_badge, get_notification_preferences, get_notifications,
+    mark_all_notifications_read, mark_notification_read, update_mail_setting,
+    update_notification_preference,
+};
+
+pub fn configure_notification_endpoints(config: &mut web::ServiceConfig) {
+    config
+        .service(get_notifications)
+        .service(get_notification_badge)
+        .service(mark_notification_read)
+        .service(mark_all_notifications_read)
+        .service(get_notification_preferences)
+        .service(update_notification_preference)
+        .service(update_mail_setting);
+}
@@ -0,0 +1,7 @@
+use actix_web::web;
+
+use crate::handlers::report::{export_report, get_reports};
+
+pub fn configure_report_endpoints(config: &mut web::ServiceConfig) {
+    config.service(get_reports).service(export_report);
+}
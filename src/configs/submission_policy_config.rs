@@ -0,0 +1,14 @@
+use actix_web::web;
+
+use crate::handlers::submission_policy::{
+    delete_submission_policy, get_overdue_timesheets, get_submission_policy,
+    update_submission_policy,
+};
+
+pub fn configure_submission_policy_endpoints(config: &mut web::ServiceConfig) {
+    config
+        .service(get_submission_policy)
+        .service(update_submission_policy)
+        .service(delete_submission_policy)
+        .service(get_overdue_timesheets);
+}
@@ -0,0 +1,13 @@
+use actix_web::web;
+
+use crate::handlers::task_checklist::{
+    create_checklist_item, delete_checklist_item, open_task_checklist, update_checklist_item,
+};
+
+pub fn configure_task_checklist_endpoints(config: &mut web::ServiceConfig) {
+    config
+        .service(open_task_checklist)
+        .service(create_checklist_item)
+        .service(update_checklist_item)
+        .service(delete_checklist_item);
+}
@@ -1,7 +1,8 @@
 use actix_web::web;
 
 use crate::handlers::event_task::{
-    create_task, delete_task, get_event_tasks, open_single_task_panel, open_task_creation_panel,
+    create_task, delete_task, get_event_critical_path, get_event_tasks, get_subtasks,
+    get_task_board, move_task, open_single_task_panel, open_task_creation_panel,
     open_task_edit_panel, open_tasks_panel, update_task, update_task_completion,
 };
 
@@ -12,8 +13,12 @@ pub fn configure_task_endpoints(config: &mut web::ServiceConfig) {
         .service(open_task_creation_panel)
         .service(open_task_edit_panel)
         .service(get_event_tasks)
+        .service(get_task_board)
+        .service(get_subtasks)
+        .service(get_event_critical_path)
         .service(create_task)
         .service(update_task)
         .service(update_task_completion)
+        .service(move_task)
         .service(delete_task);
 }
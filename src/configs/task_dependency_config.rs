@@ -0,0 +1,12 @@
+use actix_web::web;
+
+use crate::handlers::task_dependency::{
+    create_task_dependency, delete_task_dependency, open_task_dependencies,
+};
+
+pub fn configure_task_dependency_endpoints(config: &mut web::ServiceConfig) {
+    config
+        .service(open_task_dependencies)
+        .service(create_task_dependency)
+        .service(delete_task_dependency);
+}
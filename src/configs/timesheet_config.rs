@@ -1,10 +1,19 @@
 use actix_web::web;
 
-use crate::handlers::timesheet::{
-    create_timesheet, get_all_timesheets_for_employment, get_expected_wage_calculation,
-    get_sheet_hours, get_timesheet, get_timesheets_for_review, get_work_day,
-    open_sheet_submit_page, open_timesheet_for_review, reset_timesheet_data,
-    toggle_work_day_edit_mode, update_timesheet, update_work_day,
+use crate::handlers::{
+    review_queue::{get_review_queue, review_queued_timesheets},
+    timesheet::{
+        create_timesheet, flag_work_day, get_all_timesheets_for_employment,
+        get_expected_wage_calculation, get_sheet_hours, get_timesheet,
+        get_timesheet_review_preview, get_timesheets_for_review, get_work_day,
+        open_sheet_submit_page, open_timesheet_for_review, remove_work_day_flag,
+        reset_timesheet_data, toggle_work_day_edit_mode, transition_timesheet, update_timesheet,
+        update_work_day,
+    },
+    timesheet_amendment::{
+        decide_timesheet_amendment, get_timesheet_amendments, propose_timesheet_amendment,
+    },
+    timesheet_consistency::{get_timesheet_consistency, repair_timesheet_consistency},
 };
 
 pub fn configure_timesheet_endpoints(config: &mut web::ServiceConfig) {
@@ -13,13 +22,24 @@ pub fn configure_timesheet_endpoints(config: &mut web::ServiceConfig) {
         .service(get_timesheet)
         .service(create_timesheet)
         .service(update_timesheet)
+        .service(transition_timesheet)
+        .service(flag_work_day)
+        .service(remove_work_day_flag)
         .service(reset_timesheet_data)
         .service(toggle_work_day_edit_mode)
         .service(update_work_day)
         .service(get_work_day)
         .service(open_timesheet_for_review)
         .service(get_timesheets_for_review)
+        .service(get_timesheet_review_preview)
+        .service(get_review_queue)
+        .service(review_queued_timesheets)
         .service(get_expected_wage_calculation)
         .service(open_sheet_submit_page)
-        .service(get_sheet_hours);
+        .service(get_sheet_hours)
+        .service(get_timesheet_amendments)
+        .service(propose_timesheet_amendment)
+        .service(decide_timesheet_amendment)
+        .service(get_timesheet_consistency)
+        .service(repair_timesheet_consistency);
 }
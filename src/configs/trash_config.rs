@@ -0,0 +1,7 @@
+use actix_web::web;
+
+use crate::handlers::trash::{get_trash, restore_deletion};
+
+pub fn configure_trash_endpoints(config: &mut web::ServiceConfig) {
+    config.service(get_trash).service(restore_deletion);
+}
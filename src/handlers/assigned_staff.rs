@@ -7,14 +7,16 @@ use uuid::Uuid;
 
 use crate::{
     errors::{handle_database_error, parse_error},
-    handlers::common::extract_path_tuple_ids,
+    handlers::common::{check_task_editable, extract_path_tuple_ids},
     models::EventRole,
     repositories::{
         assigned_staff::{
             assigned_staff_repo::AssignedStaffRepository,
             models::{AssignedStaffData, AssignedStaffFilter, NewAssignedStaff},
         },
+        event::event_repo::EventRepository,
         event_staff::event_staff_repo::StaffRepository,
+        task::task_repo::TaskRepository,
     },
     templates::staff::{
         AllAssignedStaffTemplate, AssignedStaff, AssignedStaffManagementTemplate,
@@ -108,6 +110,8 @@ pub async fn get_assigned_staff(
 pub async fn create_assigned_staff(
     task_id: web::Path<String>,
     new_task_staff: web::Json<NewAssignedStaffData>,
+    event_repo: web::Data<EventRepository>,
+    task_repo: web::Data<TaskRepository>,
     assigned_repo: web::Data<AssignedStaffRepository>,
 ) -> HttpResponse {
     let id_parse = Uuid::from_str(task_id.into_inner().as_str());
@@ -116,6 +120,9 @@ pub async fn create_assigned_staff(
     }
 
     let parsed_id = id_parse.expect("Should be valid.");
+    if let Err(response) = check_task_editable(parsed_id, &event_repo, &task_repo).await {
+        return response;
+    }
 
     let task_staff_data = NewAssignedStaff {
         task_id: parsed_id,
@@ -142,6 +149,8 @@ pub async fn create_assigned_staff(
 pub async fn update_assigned_staff(
     path: web::Path<(String, String)>,
     task_staff_data: web::Json<AssignedStaffData>,
+    event_repo: web::Data<EventRepository>,
+    task_repo: web::Data<TaskRepository>,
     assigned_repo: web::Data<AssignedStaffRepository>,
     staff_repo: web::Data<StaffRepository>,
 ) -> HttpResponse {
@@ -151,6 +160,9 @@ pub async fn update_assigned_staff(
     }
 
     let (task_id, staff_id) = parsed_ids.unwrap();
+    if let Err(response) = check_task_editable(task_id, &event_repo, &task_repo).await {
+        return response;
+    }
 
     let decider = staff_repo.read_one(task_staff_data.decided_by).await;
     if decider.is_err() {
@@ -189,6 +201,8 @@ pub async fn update_assigned_staff(
 #[delete("/task/{task_id}/staff")]
 pub async fn delete_all_rejected_assigned_staff(
     task_id: web::Path<String>,
+    event_repo: web::Data<EventRepository>,
+    task_repo: web::Data<TaskRepository>,
     assigned_repo: web::Data<AssignedStaffRepository>,
 ) -> HttpResponse {
     let id_parse = Uuid::from_str(task_id.into_inner().as_str());
@@ -197,6 +211,10 @@ pub async fn delete_all_rejected_assigned_staff(
     }
 
     let parsed_id = id_parse.expect("Should be valid.");
+    if let Err(response) = check_task_editable(parsed_id, &event_repo, &task_repo).await {
+        return response;
+    }
+
     let result = assigned_repo.delete_rejected(parsed_id).await;
     if let Err(error) = result {
         return handle_database_error(error);
@@ -212,6 +230,8 @@ pub async fn delete_all_rejected_assigned_staff(
 #[delete("/task/{task_id}/staff/{staff_id}")]
 pub async fn delete_assigned_staff(
     path: web::Path<(String, String)>,
+    event_repo: web::Data<EventRepository>,
+    task_repo: web::Data<TaskRepository>,
     assigned_repo: web::Data<AssignedStaffRepository>,
 ) -> HttpResponse {
     let parsed_ids = extract_path_tuple_ids(path.into_inner());
@@ -220,6 +240,10 @@ pub async fn delete_assigned_staff(
     }
 
     let (task_id, staff_id) = parsed_ids.unwrap();
+    if let Err(response) = check_task_editable(task_id, &event_repo, &task_repo).await {
+        return response;
+    }
+
     let result = assigned_repo.delete(task_id, staff_id).await;
     if let Err(error) = result {
         return handle_database_error(error);
@@ -0,0 +1,231 @@
+use std::str::FromStr;
+
+use actix_web::{get, http, post, web, HttpRequest, HttpResponse};
+use askama::Template;
+use uuid::Uuid;
+
+use crate::{
+    errors::{handle_database_error, parse_error},
+    handlers::common::{extract_path_tuple_ids, is_event_organizer},
+    repositories::{
+        calendar_feed::{calendar_feed_repo::CalendarFeedRepository, models::CalendarFeed},
+        event_staff::event_staff_repo::StaffRepository,
+    },
+    templates::calendar::CalendarFeedTemplate,
+    utils::ical::{calendar::render_calendar, models::CalendarEntry},
+};
+
+fn render_feed_panel(
+    request: &HttpRequest,
+    feed: CalendarFeed,
+    title: String,
+    rotate_url: String,
+    back_url: String,
+) -> HttpResponse {
+    let connection = request.connection_info();
+    let path = format!("/calendar/{}.ics", feed.token);
+
+    let template = CalendarFeedTemplate {
+        title,
+        feed_url: format!("{}://{}{}", connection.scheme(), connection.host(), path),
+        webcal_url: format!("webcal://{}{}", connection.host(), path),
+        rotate_url,
+        back_url,
+    };
+
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError()
+            .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be valid now."))
+}
+
+/// The feed itself, calendar applications can't log in so the secret token
+/// in the URL is the only authorization.
+#[get("/calendar/{token}.ics")]
+pub async fn get_calendar_feed(
+    token: web::Path<String>,
+    calendar_feed_repo: web::Data<CalendarFeedRepository>,
+    staff_repo: web::Data<StaffRepository>,
+) -> HttpResponse {
+    let token_parse = Uuid::from_str(token.into_inner().as_str());
+    if token_parse.is_err() {
+        return HttpResponse::NotFound().body(parse_error(http::StatusCode::NOT_FOUND));
+    }
+
+    let feed_res = calendar_feed_repo
+        .read_by_token(token_parse.expect("Should be valid."))
+        .await;
+    if let Err(error) = feed_res {
+        return handle_database_error(error);
+    }
+    let feed = feed_res.expect("Should be valid.");
+
+    let (name, events_res, tasks_res) = match feed.event_id {
+        Some(event_id) => {
+            // Organizers lose the feed once they stop organizing the event.
+            if !is_event_organizer(event_id, feed.user_id, &staff_repo).await {
+                return HttpResponse::NotFound().body(parse_error(http::StatusCode::NOT_FOUND));
+            }
+
+            let event_res = calendar_feed_repo.read_event(event_id).await;
+            let name = event_res
+                .as_ref()
+                .map(|event| event.name.clone())
+                .unwrap_or_default();
+            (
+                name,
+                event_res.map(|event| vec![event]),
+                calendar_feed_repo.read_tasks_for_event(event_id).await,
+            )
+        }
+        None => (
+            "Orchestrate".to_string(),
+            calendar_feed_repo.read_events_for_user(feed.user_id).await,
+            calendar_feed_repo.read_tasks_for_user(feed.user_id).await,
+        ),
+    };
+
+    if let Err(error) = events_res {
+        return handle_database_error(error);
+    }
+    if let Err(error) = tasks_res {
+        return handle_database_error(error);
+    }
+
+    let entries: Vec<CalendarEntry> = events_res
+        .expect("Should be valid.")
+        .into_iter()
+        .map(CalendarEntry::from)
+        .chain(
+            tasks_res
+                .expect("Should be valid.")
+                .into_iter()
+                .map(CalendarEntry::from),
+        )
+        .collect();
+
+    HttpResponse::Ok()
+        .content_type("text/calendar; charset=utf-8")
+        .body(render_calendar(&name, &entries))
+}
+
+#[get("/user/{user_id}/calendar-feed")]
+pub async fn get_user_calendar_feed(
+    request: HttpRequest,
+    user_id: web::Path<String>,
+    calendar_feed_repo: web::Data<CalendarFeedRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(user_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let parsed_id = id_parse.expect("Should be valid.");
+    let result = calendar_feed_repo.read_or_create(parsed_id, None).await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_feed_panel(
+        &request,
+        result.expect("Should be valid."),
+        "My Calendar".to_string(),
+        format!("/protected/user/{}/calendar-feed", parsed_id),
+        format!("/protected/user/{}", parsed_id),
+    )
+}
+
+#[post("/user/{user_id}/calendar-feed")]
+pub async fn rotate_user_calendar_feed(
+    request: HttpRequest,
+    user_id: web::Path<String>,
+    calendar_feed_repo: web::Data<CalendarFeedRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(user_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let parsed_id = id_parse.expect("Should be valid.");
+    let result = calendar_feed_repo.rotate(parsed_id, None).await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_feed_panel(
+        &request,
+        result.expect("Should be valid."),
+        "My Calendar".to_string(),
+        format!("/protected/user/{}/calendar-feed", parsed_id),
+        format!("/protected/user/{}", parsed_id),
+    )
+}
+
+#[get("/event/{event_id}/calendar-feed/{user_id}")]
+pub async fn get_event_calendar_feed(
+    request: HttpRequest,
+    path: web::Path<(String, String)>,
+    calendar_feed_repo: web::Data<CalendarFeedRepository>,
+    staff_repo: web::Data<StaffRepository>,
+) -> HttpResponse {
+    let parsed_ids = extract_path_tuple_ids(path.into_inner());
+    if parsed_ids.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+    let (event_id, user_id) = parsed_ids.expect("Should be valid.");
+
+    if !is_event_organizer(event_id, user_id, &staff_repo).await {
+        return HttpResponse::Forbidden().body(parse_error(http::StatusCode::FORBIDDEN));
+    }
+
+    let result = calendar_feed_repo
+        .read_or_create(user_id, Some(event_id))
+        .await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_feed_panel(
+        &request,
+        result.expect("Should be valid."),
+        "Event Calendar".to_string(),
+        format!("/protected/event/{}/calendar-feed/{}", event_id, user_id),
+        format!("/protected/event/{}/staff-panel/{}", event_id, user_id),
+    )
+}
+
+#[post("/event/{event_id}/calendar-feed/{user_id}")]
+pub async fn rotate_event_calendar_feed(
+    request: HttpRequest,
+    path: web::Path<(String, String)>,
+    calendar_feed_repo: web::Data<CalendarFeedRepository>,
+    staff_repo: web::Data<StaffRepository>,
+) -> HttpResponse {
+    let parsed_ids = extract_path_tuple_ids(path.into_inner());
+    if parsed_ids.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+    let (event_id, user_id) = parsed_ids.expect("Should be valid.");
+
+    if !is_event_organizer(event_id, user_id, &staff_repo).await {
+        return HttpResponse::Forbidden().body(parse_error(http::StatusCode::FORBIDDEN));
+    }
+
+    let result = calendar_feed_repo.rotate(user_id, Some(event_id)).await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_feed_panel(
+        &request,
+        result.expect("Should be valid."),
+        "Event Calendar".to_string(),
+        format!("/protected/event/{}/calendar-feed/{}", event_id, user_id),
+        format!("/protected/event/{}/staff-panel/{}", event_id, user_id),
+    )
+}
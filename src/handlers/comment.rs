@@ -3,15 +3,16 @@ use std::str::FromStr;
 use crate::{
     errors::handle_database_error,
     handlers::common::extract_path_tuple_ids,
-    models::AcceptanceStatus,
+    models::{AcceptanceStatus, EventRole, UserRole},
     repositories::{
         assigned_staff::assigned_staff_repo::AssignedStaffRepository,
         comment::models::{CommentData, NewComment},
         event_staff::event_staff_repo::StaffRepository,
+        user::user_repo::UserRepository,
     },
     templates::comment::{
-        CommentTemplate, CommentUpdateModeTemplate, EventCommentsContainerTemplate, SingleComment,
-        TaskCommentsContainerTemplate,
+        order_into_threads, CommentHistoryTemplate, CommentTemplate, CommentUpdateModeTemplate,
+        EventCommentsContainerTemplate, SingleComment, TaskCommentsContainerTemplate,
     },
 };
 use actix_web::{delete, get, http, patch, post, web, HttpResponse};
@@ -28,9 +29,39 @@ use crate::{
 #[derive(Deserialize)]
 pub struct NewCommentData {
     author_id: Uuid,
+    parent_id: Option<Uuid>,
     content: String,
 }
 
+/// Replies have to stay within the discussion their parent comment belongs to.
+async fn check_reply_parent(
+    parent_id: Option<Uuid>,
+    event_id: Option<Uuid>,
+    task_id: Option<Uuid>,
+    comment_repo: &web::Data<CommentRepository>,
+) -> Result<(), HttpResponse> {
+    if parent_id.is_none() {
+        return Ok(());
+    }
+
+    let parent_res = comment_repo
+        .read_one(parent_id.expect("Should be some."))
+        .await;
+    if parent_res.is_err() {
+        return Err(handle_database_error(
+            parent_res.expect_err("Should be an error."),
+        ));
+    }
+
+    let parent = parent_res.expect("Should be valid.");
+    if parent.event_id != event_id || parent.task_id != task_id {
+        return Err(HttpResponse::BadRequest()
+            .body("Replies have to stay in the same discussion.".to_string()));
+    }
+
+    Ok(())
+}
+
 #[get("/event/{event_id}/comment-panel/{user_id}")]
 pub async fn open_event_comments_for_user(
     path: web::Path<(String, String)>,
@@ -67,7 +98,7 @@ pub async fn open_event_comments_for_user(
         .await;
     if let Ok(comment) = result {
         let comments: Vec<SingleComment> =
-            comment.into_iter().map(|comment| comment.into()).collect();
+            order_into_threads(comment.into_iter().map(|comment| comment.into()).collect());
         let template = EventCommentsContainerTemplate {
             comments,
             requester_id: user_id,
@@ -103,10 +134,17 @@ pub async fn create_event_comment(
         return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
     }
 
+    if let Err(response) =
+        check_reply_parent(new_comment.parent_id, Some(parsed_id), None, &comment_repo).await
+    {
+        return response;
+    }
+
     let data = NewComment {
         author_id: new_comment.author_id,
         event_id: Some(parsed_id),
         task_id: None,
+        parent_id: new_comment.parent_id,
         content: new_comment.content.clone(),
     };
 
@@ -126,7 +164,7 @@ pub async fn create_event_comment(
         .await;
     if let Ok(comment) = comments_result {
         let comments: Vec<SingleComment> =
-            comment.into_iter().map(|comment| comment.into()).collect();
+            order_into_threads(comment.into_iter().map(|comment| comment.into()).collect());
         let template = CommentsTemplate {
             requester_id: new_comment.author_id,
             comments,
@@ -177,7 +215,7 @@ pub async fn open_task_comments_for_user(
         .await;
     if let Ok(comment) = result {
         let comments: Vec<SingleComment> =
-            comment.into_iter().map(|comment| comment.into()).collect();
+            order_into_threads(comment.into_iter().map(|comment| comment.into()).collect());
         let template = TaskCommentsContainerTemplate {
             comments,
             requester_id: staff_res.expect("Should be valid").staff.user.id,
@@ -213,10 +251,17 @@ pub async fn create_task_comment(
         return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
     }
 
+    if let Err(response) =
+        check_reply_parent(new_comment.parent_id, None, Some(parsed_id), &comment_repo).await
+    {
+        return response;
+    }
+
     let data = NewComment {
         author_id: new_comment.author_id,
         event_id: None,
         task_id: Some(parsed_id),
+        parent_id: new_comment.parent_id,
         content: new_comment.content.clone(),
     };
 
@@ -236,7 +281,7 @@ pub async fn create_task_comment(
         .await;
     if let Ok(comment) = comments_result {
         let comments: Vec<SingleComment> =
-            comment.into_iter().map(|comment| comment.into()).collect();
+            order_into_threads(comment.into_iter().map(|comment| comment.into()).collect());
         let template = CommentsTemplate {
             requester_id: new_comment.author_id,
             comments,
@@ -347,6 +392,61 @@ pub async fn get_comment(
     handle_database_error(result.expect_err("Should be an error here."))
 }
 
+/// Prior versions of a comment are shown to the organizers of its event
+/// and to administrators.
+#[get("/comment/{comment_id}/history/{user_id}")]
+pub async fn get_comment_history(
+    path: web::Path<(String, String)>,
+    comment_repo: web::Data<CommentRepository>,
+    staff_repo: web::Data<StaffRepository>,
+    user_repo: web::Data<UserRepository>,
+) -> HttpResponse {
+    let parsed_ids = extract_path_tuple_ids(path.into_inner());
+    if parsed_ids.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let (comment_id, user_id) = parsed_ids.unwrap();
+
+    let event_res = comment_repo.read_event_id(comment_id).await;
+    if event_res.is_err() {
+        return handle_database_error(event_res.expect_err("Should be an error."));
+    }
+
+    let user_res = user_repo.read_one(user_id).await;
+    if user_res.is_err() {
+        return handle_database_error(user_res.expect_err("Should be an error."));
+    }
+
+    let is_moderator = user_res.expect("Should be valid.").role == UserRole::Admin
+        || staff_repo
+            .read_by_event_and_user_id(event_res.expect("Should be valid."), user_id)
+            .await
+            .is_ok_and(|staff| {
+                staff.role == EventRole::Organizer && staff.status == AcceptanceStatus::Accepted
+            });
+    if !is_moderator {
+        return HttpResponse::Forbidden().body(parse_error(http::StatusCode::FORBIDDEN));
+    }
+
+    let result = comment_repo.read_revisions(comment_id).await;
+    if let Ok(revisions) = result {
+        let template = CommentHistoryTemplate {
+            comment_id,
+            revisions,
+        };
+        let body = template.render();
+        if body.is_err() {
+            return HttpResponse::InternalServerError()
+                .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+        }
+        return HttpResponse::Ok()
+            .content_type("text/html")
+            .body(body.expect("Should be valid now."));
+    }
+    handle_database_error(result.expect_err("Should be error."))
+}
+
 #[delete("/comment/{comment_id}")]
 pub async fn delete_comment(
     comment_id: web::Path<String>,
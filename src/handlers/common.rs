@@ -1,14 +1,32 @@
+use actix_web::{web, HttpResponse};
 use anyhow::Error;
+use chrono::{Months, NaiveDate};
 use serde::Deserialize;
 use std::str::FromStr;
 use uuid::Uuid;
 
+use crate::{
+    errors::handle_database_error,
+    models::{AcceptanceStatus, EmployeeLevel, EventRole, UserRole},
+    repositories::{
+        employment::employment_repo::EmploymentRepository, event::event_repo::EventRepository,
+        event_staff::event_staff_repo::StaffRepository, task::task_repo::TaskRepository,
+        user::user_repo::UserRepository,
+    },
+};
+
 #[derive(Deserialize)]
 pub struct QueryParams {
     pub limit: Option<i64>,
     pub offset: Option<i64>,
 }
 
+/// The user deleting a record, shown in the trash.
+#[derive(Deserialize)]
+pub struct DeletionQuery {
+    pub deleted_by: Option<Uuid>,
+}
+
 pub fn extract_path_tuple_ids(ids: (String, String)) -> Result<(Uuid, Uuid), Error> {
     Ok((
         Uuid::from_str(ids.0.as_str())?,
@@ -23,3 +41,80 @@ pub fn extract_path_triple_ids(ids: (String, String, String)) -> Result<(Uuid, U
         Uuid::from_str(ids.2.as_str())?,
     ))
 }
+
+/// Tasks of cancelled and completed events are kept the way they were when
+/// the event was closed.
+pub async fn check_event_tasks_editable(
+    event_id: Uuid,
+    event_repo: &web::Data<EventRepository>,
+) -> Result<(), HttpResponse> {
+    let event_res = event_repo.read_one(event_id).await;
+    if event_res.is_err() {
+        return Err(handle_database_error(
+            event_res.expect_err("Should be an error."),
+        ));
+    }
+
+    if event_res.expect("Should be valid.").status.is_closed() {
+        return Err(HttpResponse::BadRequest()
+            .body("Tasks of a cancelled or completed event can't be changed.".to_string()));
+    }
+
+    Ok(())
+}
+
+/// The task's part of the event, see `check_event_tasks_editable`.
+pub async fn check_task_editable(
+    task_id: Uuid,
+    event_repo: &web::Data<EventRepository>,
+    task_repo: &web::Data<TaskRepository>,
+) -> Result<(), HttpResponse> {
+    let task_res = task_repo.read_one(task_id).await;
+    if task_res.is_err() {
+        return Err(handle_database_error(
+            task_res.expect_err("Should be an error."),
+        ));
+    }
+
+    check_event_tasks_editable(task_res.expect("Should be valid.").event_id, event_repo).await
+}
+
+pub async fn is_event_organizer(
+    event_id: Uuid,
+    user_id: Uuid,
+    staff_repo: &web::Data<StaffRepository>,
+) -> bool {
+    staff_repo
+        .read_by_event_and_user_id(event_id, user_id)
+        .await
+        .is_ok_and(|staff| {
+            staff.role == EventRole::Organizer && staff.status == AcceptanceStatus::Accepted
+        })
+}
+
+pub async fn is_company_administrator(
+    company_id: Uuid,
+    user_id: Uuid,
+    employment_repo: &web::Data<EmploymentRepository>,
+) -> bool {
+    employment_repo
+        .read_one(user_id, company_id)
+        .await
+        .is_ok_and(|employment| employment.level == EmployeeLevel::CompanyAdministrator)
+}
+
+/// Administrators of the whole application, not of a company.
+pub async fn is_administrator(user_id: Uuid, user_repo: &web::Data<UserRepository>) -> bool {
+    user_repo
+        .read_one(user_id)
+        .await
+        .is_ok_and(|user| user.role == UserRole::Admin)
+}
+
+/// Parses a `YYYY-MM` month into its first and last day.
+pub fn parse_month(month: &str) -> Option<(NaiveDate, NaiveDate)> {
+    let first_day = NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d").ok()?;
+    let last_day = first_day.checked_add_months(Months::new(1))?.pred_opt()?;
+
+    Some((first_day, last_day))
+}
@@ -16,7 +16,7 @@ use uuid::Uuid;
 
 use crate::{
     errors::{handle_database_error, parse_error},
-    handlers::common::extract_path_tuple_ids,
+    handlers::common::{extract_path_tuple_ids, DeletionQuery},
     models::EmployeeLevel,
     repositories::company::{
         company_repo::CompanyRepository,
@@ -373,6 +373,7 @@ pub async fn update_company(
 #[delete("/company/{company_id}")]
 pub async fn delete_company(
     company_id: web::Path<String>,
+    query: web::Query<DeletionQuery>,
     company_repo: web::Data<CompanyRepository>,
 ) -> HttpResponse {
     let id_parse = Uuid::from_str(company_id.into_inner().as_str());
@@ -381,7 +382,7 @@ pub async fn delete_company(
     }
 
     let parsed_id = id_parse.expect("Should be valid.");
-    let result = company_repo.delete(parsed_id).await;
+    let result = company_repo.delete(parsed_id, query.deleted_by).await;
 
     if let Err(error) = result {
         return handle_database_error(error);
@@ -2,7 +2,7 @@ use std::str::FromStr;
 
 use crate::{
     errors::handle_database_error,
-    handlers::common::{extract_path_triple_ids, extract_path_tuple_ids},
+    handlers::common::{extract_path_triple_ids, extract_path_tuple_ids, DeletionQuery},
     models::{EmployeeLevel, EmploymentContract},
     repositories::employment::models::{EmploymentData, NewEmployment},
     templates::employment::{
@@ -373,6 +373,7 @@ pub async fn update_employment(
 #[delete("/user/{user_id}/employment/{company_id}")]
 pub async fn delete_employment(
     path: web::Path<(String, String)>,
+    query: web::Query<DeletionQuery>,
     employment_repo: web::Data<EmploymentRepository>,
 ) -> HttpResponse {
     let parsed_ids = extract_path_tuple_ids(path.into_inner());
@@ -382,7 +383,9 @@ pub async fn delete_employment(
 
     let (user_id, company_id) = parsed_ids.unwrap();
 
-    let result = employment_repo.delete(user_id, company_id).await;
+    let result = employment_repo
+        .delete(user_id, company_id, query.deleted_by)
+        .await;
     if let Err(error) = result {
         return handle_database_error(error);
     }
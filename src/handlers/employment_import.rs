@@ -0,0 +1,367 @@
+use std::collections::{HashMap, HashSet};
+
+use actix_multipart::form::MultipartForm;
+use actix_web::{get, post, web, HttpResponse};
+use askama::Template;
+use uuid::Uuid;
+
+use crate::{
+    errors::handle_database_error,
+    handlers::{common::extract_path_tuple_ids, user::validate_new_user},
+    models::{EmployeeLevel, UserRole},
+    repositories::{
+        employment::{
+            employment_repo::EmploymentRepository,
+            models::{ImportManager, ImportUser, ImportedEmployment},
+        },
+        user::models::NewUser,
+    },
+    templates::employment::{
+        EmploymentImportPreviewTemplate, EmploymentImportRow, EmploymentImportTemplate,
+    },
+    utils::{
+        image_storage::models::{UploadForm, MAX_FILE_SIZE},
+        import::{
+            models::{ImportFormat, ImportRecord, ImportRow},
+            parser::{parse_record, read_records},
+        },
+    },
+};
+
+/// Only managers and company administrators may add employees, the same as
+/// when creating a single employment.
+async fn read_importer_level(
+    importer_id: Uuid,
+    company_id: Uuid,
+    employment_repo: &web::Data<EmploymentRepository>,
+) -> Result<EmployeeLevel, HttpResponse> {
+    let result = employment_repo.read_one(importer_id, company_id).await;
+    if let Err(error) = result {
+        return Err(handle_database_error(error));
+    }
+
+    let employment = result.expect("Should be valid.");
+    if employment.level == EmployeeLevel::Basic {
+        return Err(HttpResponse::Forbidden()
+            .body("Your employee level is unable to create new employments.".to_string()));
+    }
+
+    Ok(employment.level)
+}
+
+async fn read_upload(form: UploadForm) -> Result<Vec<ImportRecord>, HttpResponse> {
+    if form.file.size == 0 || form.file.size > MAX_FILE_SIZE {
+        return Err(HttpResponse::BadRequest().body("Incorrect file size. The limit is 10MB."));
+    }
+
+    let format = form
+        .file
+        .file_name
+        .as_deref()
+        .and_then(ImportFormat::from_file_name);
+    if format.is_none() {
+        return Err(
+            HttpResponse::BadRequest().body("Invalid file type, only .csv and .xlsx are allowed.")
+        );
+    }
+
+    let bytes = tokio::fs::read(form.file.file.path()).await;
+    if bytes.is_err() {
+        return Err(HttpResponse::InternalServerError().body("Internal Server Error.".to_string()));
+    }
+
+    read_records(
+        format.expect("Should be some."),
+        &bytes.expect("Should be valid."),
+    )
+    .map_err(|error| HttpResponse::BadRequest().body(error.to_string()))
+}
+
+/// Checks every row the same way the handlers check a single user and
+/// employment, then resolves the emails against the database and the rest of
+/// the file. Returns the rows for the preview and the rows which can be imported.
+fn validate_rows(
+    importer_id: Uuid,
+    importer_level: &EmployeeLevel,
+    records: &[ImportRecord],
+    known_users: Vec<ImportUser>,
+) -> (Vec<EmploymentImportRow>, Vec<ImportedEmployment>) {
+    let known_users: HashMap<String, ImportUser> = known_users
+        .into_iter()
+        .map(|user| (user.email.to_lowercase(), user))
+        .collect();
+
+    let mut preview: Vec<EmploymentImportRow> =
+        records.iter().map(|record| record.into()).collect();
+    let mut parsed: Vec<Option<ImportRow>> = vec![];
+    let mut seen_emails = HashSet::new();
+
+    for (record, preview_row) in records.iter().zip(preview.iter_mut()) {
+        let row = match parse_record(record) {
+            Ok(row) => row,
+            Err(errors) => {
+                preview_row.errors = errors;
+                parsed.push(None);
+                continue;
+            }
+        };
+
+        let new_user = NewUser {
+            name: row.name.clone(),
+            email: row.email.clone(),
+            birth: row.birth,
+            gender: row.gender.clone(),
+            role: UserRole::User,
+        };
+        if let Err(error) = validate_new_user(new_user) {
+            preview_row.errors.push(error);
+        }
+        if row.hourly_wage < 0.0 {
+            preview_row
+                .errors
+                .push("Hourly wage can't be negative.".to_string());
+        }
+        if row.start_date > row.end_date {
+            preview_row
+                .errors
+                .push("Start date can't be after the end date.".to_string());
+        }
+        if row.level == EmployeeLevel::CompanyAdministrator
+            && *importer_level != EmployeeLevel::CompanyAdministrator
+        {
+            preview_row
+                .errors
+                .push("Only a company administrator can add company administrators.".to_string());
+        }
+
+        let email = row.email.to_lowercase();
+        if !seen_emails.insert(email.clone()) {
+            preview_row
+                .errors
+                .push("The email is listed more than once.".to_string());
+        }
+        if let Some(user) = known_users.get(&email) {
+            preview_row.existing_user = true;
+            if user.has_employment {
+                preview_row
+                    .errors
+                    .push("The user already has an employment in the company.".to_string());
+            }
+        }
+
+        parsed.push(Some(row));
+    }
+
+    let lines_by_email: HashMap<String, usize> = parsed
+        .iter()
+        .enumerate()
+        .filter_map(|(index, row)| row.as_ref().map(|row| (row.email.to_lowercase(), index)))
+        .collect();
+
+    let mut managers: Vec<Option<ImportManager>> = vec![None; parsed.len()];
+    for (index, row) in parsed.iter().enumerate() {
+        let Some(row) = row else {
+            continue;
+        };
+
+        let manager = match &row.manager_email {
+            None => Some(ImportManager::Existing(importer_id)),
+            Some(email) if email.eq_ignore_ascii_case(&row.email) => {
+                preview[index]
+                    .errors
+                    .push("An employee can't be their own manager.".to_string());
+                None
+            }
+            Some(email) if lines_by_email.contains_key(&email.to_lowercase()) => {
+                Some(ImportManager::Imported(email.clone()))
+            }
+            Some(email) => match known_users.get(&email.to_lowercase()) {
+                Some(user) if user.employed => Some(ImportManager::Existing(user.id)),
+                _ => {
+                    preview[index].errors.push(format!(
+                        "The manager {} isn't employed by the company.",
+                        email
+                    ));
+                    None
+                }
+            },
+        };
+        managers[index] = manager;
+    }
+
+    // An employee can't be imported without their manager, who may depend on
+    // another manager in the file.
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for index in 0..preview.len() {
+            let Some(ImportManager::Imported(email)) = &managers[index] else {
+                continue;
+            };
+            let manager_index = lines_by_email[&email.to_lowercase()];
+            if preview[index].errors.is_empty() && !preview[manager_index].errors.is_empty() {
+                preview[index]
+                    .errors
+                    .push(format!("The row of the manager {} is invalid.", email));
+                changed = true;
+            }
+        }
+    }
+
+    let mut valid = vec![];
+    for ((row, manager), preview_row) in parsed.into_iter().zip(managers).zip(preview.iter()) {
+        let (Some(row), Some(manager)) = (row, manager) else {
+            continue;
+        };
+        if !preview_row.errors.is_empty() {
+            continue;
+        }
+
+        let existing_user_id = known_users
+            .get(&row.email.to_lowercase())
+            .map(|user| user.id);
+        valid.push(ImportedEmployment {
+            existing_user_id,
+            user: NewUser {
+                name: row.name,
+                email: row.email,
+                birth: row.birth,
+                gender: row.gender,
+                role: UserRole::User,
+            },
+            manager,
+            hourly_wage: row.hourly_wage,
+            start_date: row.start_date,
+            end_date: row.end_date,
+            description: row.description,
+            employment_type: row.employment_type,
+            level: row.level,
+        });
+    }
+
+    (preview, valid)
+}
+
+async fn prepare_import(
+    path: (String, String),
+    form: UploadForm,
+    employment_repo: &web::Data<EmploymentRepository>,
+) -> Result<(Uuid, Vec<EmploymentImportRow>, Vec<ImportedEmployment>), HttpResponse> {
+    let parsed_ids = extract_path_tuple_ids(path);
+    if parsed_ids.is_err() {
+        return Err(HttpResponse::BadRequest().body("Invalid ID format.".to_string()));
+    }
+
+    let (importer_id, company_id) = parsed_ids.expect("Should be valid.");
+    let importer_level = read_importer_level(importer_id, company_id, employment_repo).await?;
+
+    let records = read_upload(form).await?;
+
+    let emails: Vec<String> = records
+        .iter()
+        .flat_map(|record| [record.email.clone(), record.manager_email.clone()])
+        .filter(|email| !email.is_empty())
+        .collect();
+    let known_users_res = employment_repo.read_import_users(company_id, emails).await;
+    if let Err(error) = known_users_res {
+        return Err(handle_database_error(error));
+    }
+
+    let (preview, valid) = validate_rows(
+        importer_id,
+        &importer_level,
+        &records,
+        known_users_res.expect("Should be valid."),
+    );
+
+    Ok((company_id, preview, valid))
+}
+
+fn render_preview(
+    rows: Vec<EmploymentImportRow>,
+    valid_count: usize,
+    committed: bool,
+) -> HttpResponse {
+    let template = EmploymentImportPreviewTemplate {
+        rows,
+        valid_count,
+        committed,
+    };
+
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError().body("Internal Server Error.".to_string());
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be valid now."))
+}
+
+#[get("/user/{user_id}/employment/{company_id}/import")]
+pub async fn toggle_employment_import(
+    path: web::Path<(String, String)>,
+    employment_repo: web::Data<EmploymentRepository>,
+) -> HttpResponse {
+    let parsed_ids = extract_path_tuple_ids(path.into_inner());
+    if parsed_ids.is_err() {
+        return HttpResponse::BadRequest().body("Invalid ID format.".to_string());
+    }
+
+    let (importer_id, company_id) = parsed_ids.expect("Should be valid.");
+    if let Err(response) = read_importer_level(importer_id, company_id, &employment_repo).await {
+        return response;
+    }
+
+    let template = EmploymentImportTemplate {
+        importer_id,
+        company_id,
+    };
+
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError().body("Internal Server Error.".to_string());
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be valid now."))
+}
+
+/// Dry run of the import, nothing is stored.
+#[post("/user/{user_id}/employment/{company_id}/import/preview")]
+pub async fn preview_employment_import(
+    path: web::Path<(String, String)>,
+    MultipartForm(form): MultipartForm<UploadForm>,
+    employment_repo: web::Data<EmploymentRepository>,
+) -> HttpResponse {
+    match prepare_import(path.into_inner(), form, &employment_repo).await {
+        Ok((_, preview, valid)) => render_preview(preview, valid.len(), false),
+        Err(response) => response,
+    }
+}
+
+/// Imports the valid rows of the file, the invalid ones are skipped.
+#[post("/user/{user_id}/employment/{company_id}/import")]
+pub async fn commit_employment_import(
+    path: web::Path<(String, String)>,
+    MultipartForm(form): MultipartForm<UploadForm>,
+    employment_repo: web::Data<EmploymentRepository>,
+) -> HttpResponse {
+    let (company_id, preview, valid) =
+        match prepare_import(path.into_inner(), form, &employment_repo).await {
+            Ok(prepared) => prepared,
+            Err(response) => return response,
+        };
+
+    if valid.is_empty() {
+        return HttpResponse::BadRequest().body("The file has no valid rows to import.");
+    }
+
+    let result = employment_repo.import(company_id, valid).await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_preview(preview, result.expect("Should be valid.").len(), true)
+}
@@ -3,23 +3,26 @@ use std::str::FromStr;
 use actix_multipart::form::MultipartForm;
 use actix_web::{delete, get, http, patch, post, put, web, HttpResponse};
 use askama::Template;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::{
     common::{calculate_new_offsets, PAGINATION_LIMIT},
     errors::{handle_database_error, parse_error},
-    handlers::common::extract_path_tuple_ids,
-    models::{EmployeeLevel, EventRole},
+    handlers::common::{extract_path_tuple_ids, DeletionQuery},
+    models::{EmployeeLevel, EventRole, EventStatus},
     repositories::{
         employment::employment_repo::EmploymentRepository,
         event::{
             event_repo::EventRepository,
-            models::{EventData, EventFilter, NewEvent},
+            models::{EventData, EventFilter, EventStatusData, NewEvent},
         },
         event_staff::event_staff_repo::StaffRepository,
+        timesheet::models::TimeRange,
     },
     templates::event::{
-        EventCreateTemplate, EventEditTemplate, EventLite, EventTemplate, EventsTemplate,
+        DateChangeImpactTemplate, EventCreateTemplate, EventEditTemplate, EventLite, EventTemplate,
+        EventsTemplate,
     },
     utils::image_storage::{
         img_manipulation::{remove_image, store_image},
@@ -27,6 +30,27 @@ use crate::{
     },
 };
 
+#[derive(Deserialize)]
+pub struct EventViewerData {
+    user_id: Option<Uuid>,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct EventUpdateData {
+    #[serde(flatten)]
+    pub data: EventData,
+    /// Set once the organizer has seen how the new dates affect the timesheets.
+    #[serde(default)]
+    pub confirmed: bool,
+}
+
+#[derive(Deserialize)]
+pub struct EventStatusChangeData {
+    editor_id: Uuid, // staff table ID
+    status: EventStatus,
+    cancellation_reason: Option<String>,
+}
+
 #[get("/event")]
 pub async fn get_events(
     params: web::Query<EventFilter>,
@@ -45,6 +69,7 @@ pub async fn get_events(
     // We read one ahead to determine if we have reached the end before we actually do.
     let modified_query_params = EventFilter {
         accepts_staff: query_params.accepts_staff,
+        user_id: query_params.user_id,
         limit: if query_params.limit.is_some() {
             Some(query_params.limit.expect("Should be some") + 1)
         } else {
@@ -92,7 +117,9 @@ pub async fn get_events(
 #[get("/event/{event_id}")]
 pub async fn get_event(
     event_id: web::Path<String>,
+    viewer: web::Query<EventViewerData>,
     event_repo: web::Data<EventRepository>,
+    staff_repo: web::Data<StaffRepository>,
 ) -> HttpResponse {
     let id_parse = Uuid::from_str(event_id.into_inner().as_str());
     if id_parse.is_err() {
@@ -104,6 +131,21 @@ pub async fn get_event(
     let result = event_repo.read_one(parsed_id).await;
 
     if let Ok(event) = result {
+        // Drafts are only shown to their organizers, everyone else
+        // shouldn't learn that the event exists at all.
+        if event.status == EventStatus::Draft {
+            let is_organizer = match viewer.user_id {
+                Some(user_id) => staff_repo
+                    .read_by_event_and_user_id(parsed_id, user_id)
+                    .await
+                    .is_ok_and(|staff| staff.role == EventRole::Organizer),
+                None => false,
+            };
+            if !is_organizer {
+                return HttpResponse::NotFound().body(parse_error(http::StatusCode::NOT_FOUND));
+            }
+        }
+
         let template: EventTemplate = event.into();
 
         let body = template.render();
@@ -200,10 +242,12 @@ fn is_update_data_empty(event_data: EventData) -> bool {
 #[patch("/event/{event_id}")]
 pub async fn update_event(
     event_id: web::Path<String>,
-    event_data: web::Json<EventData>,
+    update_data: web::Json<EventUpdateData>,
     event_repo: web::Data<EventRepository>,
+    staff_repo: web::Data<StaffRepository>,
 ) -> HttpResponse {
-    if is_update_data_empty(event_data.clone()) {
+    let update_data = update_data.into_inner();
+    if is_update_data_empty(update_data.data.clone()) {
         return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
     }
 
@@ -213,7 +257,82 @@ pub async fn update_event(
     }
 
     let parsed_id = id_parse.expect("Should be valid.");
-    let result = event_repo.update(parsed_id, event_data.into_inner()).await;
+
+    let event_res = event_repo.read_one(parsed_id).await;
+    if event_res.is_err() {
+        return handle_database_error(event_res.expect_err("Should be an error."));
+    }
+    let event = event_res.expect("Should be valid.");
+    if event.status.is_closed() {
+        return HttpResponse::BadRequest().body("Cancelled or completed events can't be edited.");
+    }
+
+    let new_range = TimeRange {
+        start_date: update_data.data.start_date.unwrap_or(event.start_date),
+        end_date: update_data.data.end_date.unwrap_or(event.end_date),
+    };
+    if new_range.start_date != event.start_date || new_range.end_date != event.end_date {
+        if new_range.start_date > new_range.end_date {
+            return HttpResponse::BadRequest().body("The event can't end before it starts.");
+        }
+
+        let impact_res = event_repo
+            .read_date_change_impact(parsed_id, &new_range)
+            .await;
+        if impact_res.is_err() {
+            return handle_database_error(impact_res.expect_err("Should be an error."));
+        }
+        let sheets = impact_res.expect("Should be valid.");
+
+        // Moving the dates of the staff's timesheets is up to the organizers,
+        // who have to see what changes first.
+        if !sheets.is_empty() {
+            let Some(editor_id) = update_data.data.editor_id else {
+                return HttpResponse::BadRequest()
+                    .body("Please state who is changing the dates of the event.");
+            };
+            let staff_res = staff_repo.read_one(editor_id).await;
+            if staff_res.is_err() {
+                return handle_database_error(staff_res.expect_err("Should be an error."));
+            }
+            let staff = staff_res.expect("Should be valid.");
+            if staff.role != EventRole::Organizer || staff.event_id != parsed_id {
+                return HttpResponse::Forbidden().body(parse_error(http::StatusCode::FORBIDDEN));
+            }
+
+            if !update_data.confirmed {
+                let confirmed_data = serde_json::to_string(&EventUpdateData {
+                    data: update_data.data,
+                    confirmed: true,
+                });
+                if confirmed_data.is_err() {
+                    return HttpResponse::InternalServerError()
+                        .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+                }
+
+                let template = DateChangeImpactTemplate {
+                    event: event.into(),
+                    editor_id,
+                    start_date: new_range.start_date,
+                    end_date: new_range.end_date,
+                    sheets,
+                    confirmed_data: confirmed_data.expect("Should be valid."),
+                };
+
+                let body = template.render();
+                if body.is_err() {
+                    return HttpResponse::InternalServerError()
+                        .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+                }
+
+                return HttpResponse::Ok()
+                    .content_type("text/html")
+                    .body(body.expect("Should be valid now."));
+            }
+        }
+    }
+
+    let result = event_repo.update(parsed_id, update_data.data).await;
 
     if let Ok(event) = result {
         let template: EventTemplate = event.into();
@@ -250,9 +369,83 @@ pub async fn switch_event_accepts_staff(
     handle_database_error(result.expect_err("Should be error."))
 }
 
+#[patch("/event/{event_id}/status")]
+pub async fn change_event_status(
+    event_id: web::Path<String>,
+    status_data: web::Json<EventStatusChangeData>,
+    event_repo: web::Data<EventRepository>,
+    staff_repo: web::Data<StaffRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(event_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let parsed_id = id_parse.expect("Should be valid.");
+
+    let cancellation_reason = status_data
+        .cancellation_reason
+        .as_ref()
+        .map(|reason| reason.trim().to_string())
+        .filter(|reason| !reason.is_empty());
+    if status_data.status == EventStatus::Cancelled && cancellation_reason.is_none() {
+        return HttpResponse::BadRequest().body("Please state why the event is cancelled.");
+    }
+
+    let staff_res = staff_repo.read_one(status_data.editor_id).await;
+    if staff_res.is_err() {
+        return handle_database_error(staff_res.expect_err("Should be an error."));
+    }
+    let staff = staff_res.expect("Should be valid.");
+    if staff.role != EventRole::Organizer || staff.event_id != parsed_id {
+        return HttpResponse::Forbidden().body(parse_error(http::StatusCode::FORBIDDEN));
+    }
+
+    let event_res = event_repo.read_one(parsed_id).await;
+    if event_res.is_err() {
+        return handle_database_error(event_res.expect_err("Should be an error."));
+    }
+    let event = event_res.expect("Should be valid.");
+    if !event.status.can_transition_to(&status_data.status) {
+        return HttpResponse::BadRequest().body(format!(
+            "A {} event can't be changed to {}.",
+            event.status.to_string().to_lowercase(),
+            status_data.status.to_string().to_lowercase()
+        ));
+    }
+
+    let data = EventStatusData {
+        status: status_data.status.clone(),
+        cancellation_reason: if status_data.status == EventStatus::Cancelled {
+            cancellation_reason
+        } else {
+            None
+        },
+        changed_by: staff.user.id,
+    };
+    let result = event_repo.change_status(parsed_id, data).await;
+
+    if let Ok(event) = result {
+        let template: EventTemplate = event.into();
+
+        let body = template.render();
+        if body.is_err() {
+            return HttpResponse::InternalServerError()
+                .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+        }
+
+        return HttpResponse::Ok()
+            .content_type("text/html")
+            .body(body.expect("Should be valid now."));
+    }
+
+    handle_database_error(result.expect_err("Should be error."))
+}
+
 #[delete("/event/{event_id}")]
 pub async fn delete_event(
     event_id: web::Path<String>,
+    query: web::Query<DeletionQuery>,
     event_repo: web::Data<EventRepository>,
 ) -> HttpResponse {
     let id_parse = Uuid::from_str(event_id.into_inner().as_str());
@@ -261,7 +454,7 @@ pub async fn delete_event(
     }
 
     let parsed_id = id_parse.expect("Should be valid.");
-    let result = event_repo.delete(parsed_id).await;
+    let result = event_repo.delete(parsed_id, query.deleted_by).await;
 
     if let Err(error) = result {
         return handle_database_error(error);
@@ -385,6 +578,7 @@ pub async fn upload_event_avatar(
         end_date: None,
         accepts_staff: None,
         avatar_url: Some(image_path),
+        editor_id: None,
     };
     let result = event_repo.update(parsed_id, data).await;
     if result.is_err() {
@@ -417,6 +611,7 @@ pub async fn remove_event_avatar(
         end_date: None,
         accepts_staff: None,
         avatar_url: Some(DEFAULT_EVENT_IMAGE.to_string()),
+        editor_id: None,
     };
 
     let result = event_repo.update(parsed_id, data).await;
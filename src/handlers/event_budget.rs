@@ -0,0 +1,295 @@
+use actix_web::{delete, get, http, post, web, HttpResponse};
+use askama::Template;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    errors::{handle_database_error, parse_error},
+    handlers::common::{extract_path_tuple_ids, is_event_organizer},
+    models::EventRole,
+    repositories::{
+        event::event_repo::EventRepository,
+        event_budget::{event_budget_repo::EventBudgetRepository, models::NewBudgetLine},
+        event_staff::event_staff_repo::StaffRepository,
+        timesheet::timesheet_repo::TimesheetRepository,
+        wage_preset::wage_preset_repo::WagePresetRepository,
+    },
+    templates::event::EventBudgetTemplate,
+    utils::budget::{
+        actual_costs::actual_costs,
+        report::{budget_costs, build_report, forecast_costs},
+    },
+};
+
+#[derive(Deserialize, Debug)]
+pub struct NewBudgetLineData {
+    /// Missing when the line is planned for any company.
+    pub company_id: Option<Uuid>,
+    pub role: EventRole,
+    /// Missing when the line is planned for every day of the event.
+    pub date: Option<NaiveDate>,
+    pub headcount: i32,
+    pub hours: f32,
+    pub hourly_rate: f64,
+}
+
+/// Repositories the budget panel is computed from.
+pub struct BudgetRepositories<'a> {
+    pub event_repo: &'a EventRepository,
+    pub budget_repo: &'a EventBudgetRepository,
+    pub timesheet_repo: &'a TimesheetRepository,
+    pub wage_preset_repo: &'a WagePresetRepository,
+}
+
+async fn render_budget_panel(
+    event_id: Uuid,
+    user_id: Uuid,
+    repos: BudgetRepositories<'_>,
+) -> HttpResponse {
+    let event_res = repos.event_repo.read_one(event_id).await;
+    if let Err(error) = event_res {
+        return handle_database_error(error);
+    }
+    let event = event_res.expect("Should be valid.");
+
+    let companies_res = repos.budget_repo.read_companies(event_id).await;
+    if let Err(error) = companies_res {
+        return handle_database_error(error);
+    }
+
+    let lines_res = repos.budget_repo.read_all_for_event(event_id).await;
+    if let Err(error) = lines_res {
+        return handle_database_error(error);
+    }
+    let lines = lines_res.expect("Should be valid.");
+
+    let staff_res = repos.budget_repo.read_forecast_staff(event_id).await;
+    if let Err(error) = staff_res {
+        return handle_database_error(error);
+    }
+
+    let presets_res = repos.wage_preset_repo._read_all().await;
+    if let Err(error) = presets_res {
+        return handle_database_error(error);
+    }
+
+    let timesheets_res = repos.budget_repo.read_actual_timesheets(event_id).await;
+    if let Err(error) = timesheets_res {
+        return handle_database_error(error);
+    }
+
+    let mut warnings = vec![];
+    let budget = budget_costs(&lines, event.start_date, event.end_date);
+    let forecast = forecast_costs(
+        &lines,
+        &staff_res.expect("Should be valid."),
+        &presets_res.expect("Should be valid."),
+        event.start_date,
+        event.end_date,
+        &mut warnings,
+    );
+    let actual_res = actual_costs(
+        repos.timesheet_repo,
+        &timesheets_res.expect("Should be valid."),
+        &mut warnings,
+    )
+    .await;
+    if let Err(error) = actual_res {
+        return handle_database_error(error);
+    }
+
+    let template = EventBudgetTemplate {
+        event_id,
+        user_id,
+        event_name: event.name,
+        start_date: event.start_date,
+        end_date: event.end_date,
+        companies: companies_res.expect("Should be valid."),
+        lines,
+        report: build_report(
+            &budget,
+            &forecast,
+            &actual_res.expect("Should be valid."),
+            warnings,
+        ),
+    };
+
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError()
+            .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be valid now."))
+}
+
+/// The budget lines of the event along with the budget vs. forecast vs.
+/// actual labour cost report.
+#[get("/event/{event_id}/budget/{user_id}")]
+pub async fn get_event_budget(
+    path: web::Path<(String, String)>,
+    event_repo: web::Data<EventRepository>,
+    budget_repo: web::Data<EventBudgetRepository>,
+    staff_repo: web::Data<StaffRepository>,
+    timesheet_repo: web::Data<TimesheetRepository>,
+    wage_preset_repo: web::Data<WagePresetRepository>,
+) -> HttpResponse {
+    let parsed_ids = extract_path_tuple_ids(path.into_inner());
+    if parsed_ids.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let (event_id, user_id) = parsed_ids.expect("Should be valid.");
+    if !is_event_organizer(event_id, user_id, &staff_repo).await {
+        return HttpResponse::Forbidden().body("Only an event organizer can see the budget.");
+    }
+
+    render_budget_panel(
+        event_id,
+        user_id,
+        BudgetRepositories {
+            event_repo: &event_repo,
+            budget_repo: &budget_repo,
+            timesheet_repo: &timesheet_repo,
+            wage_preset_repo: &wage_preset_repo,
+        },
+    )
+    .await
+}
+
+#[post("/event/{event_id}/budget/{user_id}")]
+pub async fn create_budget_line(
+    path: web::Path<(String, String)>,
+    new_line: web::Json<NewBudgetLineData>,
+    event_repo: web::Data<EventRepository>,
+    budget_repo: web::Data<EventBudgetRepository>,
+    staff_repo: web::Data<StaffRepository>,
+    timesheet_repo: web::Data<TimesheetRepository>,
+    wage_preset_repo: web::Data<WagePresetRepository>,
+) -> HttpResponse {
+    let parsed_ids = extract_path_tuple_ids(path.into_inner());
+    if parsed_ids.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let (event_id, user_id) = parsed_ids.expect("Should be valid.");
+    if !is_event_organizer(event_id, user_id, &staff_repo).await {
+        return HttpResponse::Forbidden().body("Only an event organizer can plan the budget.");
+    }
+
+    let data = new_line.into_inner();
+    if data.headcount <= 0 {
+        return HttpResponse::BadRequest().body("The headcount has to be positive.");
+    }
+    if data.hours <= 0.0 || data.hours > 24.0 {
+        return HttpResponse::BadRequest().body("The hours have to be between 0 and 24.");
+    }
+    if data.hourly_rate < 0.0 {
+        return HttpResponse::BadRequest().body("The hourly rate can't be negative.");
+    }
+
+    let event_res = event_repo.read_one(event_id).await;
+    if let Err(error) = event_res {
+        return handle_database_error(error);
+    }
+    let event = event_res.expect("Should be valid.");
+    if data
+        .date
+        .is_some_and(|date| date < event.start_date || date > event.end_date)
+    {
+        return HttpResponse::BadRequest().body("The date has to be within the event.");
+    }
+
+    if let Some(company_id) = data.company_id {
+        let companies_res = budget_repo.read_companies(event_id).await;
+        if let Err(error) = companies_res {
+            return handle_database_error(error);
+        }
+        if !companies_res
+            .expect("Should be valid.")
+            .iter()
+            .any(|company| company.id == company_id)
+        {
+            return HttpResponse::BadRequest()
+                .body("The company has to be associated with the event.");
+        }
+    }
+
+    let result = budget_repo
+        .create(NewBudgetLine {
+            event_id,
+            company_id: data.company_id,
+            role: data.role,
+            date: data.date,
+            headcount: data.headcount,
+            hours: data.hours,
+            hourly_rate: data.hourly_rate,
+        })
+        .await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_budget_panel(
+        event_id,
+        user_id,
+        BudgetRepositories {
+            event_repo: &event_repo,
+            budget_repo: &budget_repo,
+            timesheet_repo: &timesheet_repo,
+            wage_preset_repo: &wage_preset_repo,
+        },
+    )
+    .await
+}
+
+#[delete("/event/{event_id}/budget/{user_id}/{line_id}")]
+pub async fn delete_budget_line(
+    path: web::Path<(String, String, String)>,
+    event_repo: web::Data<EventRepository>,
+    budget_repo: web::Data<EventBudgetRepository>,
+    staff_repo: web::Data<StaffRepository>,
+    timesheet_repo: web::Data<TimesheetRepository>,
+    wage_preset_repo: web::Data<WagePresetRepository>,
+) -> HttpResponse {
+    let (event_id, user_id, line_id) = path.into_inner();
+    let parsed_ids = extract_path_tuple_ids((event_id, user_id));
+    let line_parse = Uuid::parse_str(line_id.as_str());
+    if parsed_ids.is_err() || line_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let (event_id, user_id) = parsed_ids.expect("Should be valid.");
+    let line_id = line_parse.expect("Should be valid.");
+    if !is_event_organizer(event_id, user_id, &staff_repo).await {
+        return HttpResponse::Forbidden().body("Only an event organizer can plan the budget.");
+    }
+
+    let line_res = budget_repo.read_one(line_id).await;
+    if let Err(error) = line_res {
+        return handle_database_error(error);
+    }
+    if line_res.expect("Should be valid.").event_id != event_id {
+        return HttpResponse::NotFound().body(parse_error(http::StatusCode::NOT_FOUND));
+    }
+
+    let result = budget_repo.delete(line_id).await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_budget_panel(
+        event_id,
+        user_id,
+        BudgetRepositories {
+            event_repo: &event_repo,
+            budget_repo: &budget_repo,
+            timesheet_repo: &timesheet_repo,
+            wage_preset_repo: &wage_preset_repo,
+        },
+    )
+    .await
+}
@@ -1,16 +1,14 @@
 use std::str::FromStr;
 
 use crate::repositories::associated_company::associated_company_repo::AssociatedCompanyRepository;
-use crate::repositories::timesheet::models::TimesheetCreateData;
 use crate::templates::staff::{EventStaffManagementTemplate, StaffRegisterTemplate};
 use crate::{
     common::DbResult,
     errors::handle_database_error,
     handlers::common::extract_path_tuple_ids,
-    models::{AcceptanceStatus, EventRole},
+    models::{AcceptanceStatus, ApprovalStatus, EventRole},
     repositories::{
-        event::event_repo::EventRepository,
-        event_staff::models::{NewStaff, StaffData, StaffFilter},
+        event_staff::models::{NewStaff, StaffData, StaffExtended, StaffFilter},
         timesheet::timesheet_repo::TimesheetRepository,
     },
     templates::staff::AllStaffTemplate,
@@ -28,12 +26,16 @@ async fn read_all_event_staff(
     event_id: Uuid,
     query: StaffFilter,
     event_staff_repo: web::Data<StaffRepository>,
+    warning: Option<String>,
 ) -> HttpResponse {
     let result = event_staff_repo.read_all_for_event(event_id, query).await;
     if let Ok(all_staff) = result {
         let staff_vec = all_staff.into_iter().map(|staff| staff.into()).collect();
 
-        let template = AllStaffTemplate { staff: staff_vec };
+        let template = AllStaffTemplate {
+            staff: staff_vec,
+            warning,
+        };
 
         let body = template.render();
         if body.is_err() {
@@ -68,7 +70,7 @@ pub async fn get_all_event_staff(
     }
 
     let parsed_id = id_parse.expect("Should be valid.");
-    read_all_event_staff(parsed_id, query_info, event_staff_repo).await
+    read_all_event_staff(parsed_id, query_info, event_staff_repo, None).await
 }
 
 #[get("/event/staff/{staff_id}")]
@@ -121,6 +123,16 @@ pub async fn create_event_staff(
         return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
     }
 
+    if associated_company
+        .expect("Should be valid.")
+        .event
+        .status
+        .is_closed()
+    {
+        return HttpResponse::BadRequest()
+            .body("Cancelled or completed events don't accept new staff.".to_string());
+    }
+
     let result = event_staff_repo
         .create(parsed_id, new_event_staff.into_inner())
         .await;
@@ -148,25 +160,34 @@ fn is_data_invalid(data: StaffData) -> bool {
         || (data.status.is_none() && data.decided_by.is_some())
 }
 
-async fn create_timesheet_for_user(
-    user_id: Uuid,
-    company_id: Uuid,
-    event_id: Uuid,
-    timesheet_repo: web::Data<TimesheetRepository>,
-    event_repo: web::Data<EventRepository>,
-) -> DbResult<()> {
-    let event = event_repo.read_one(event_id).await?;
-
-    let timesheet_data = TimesheetCreateData {
-        start_date: event.start_date,
-        end_date: event.end_date,
-        user_id,
-        company_id,
-        event_id,
-    };
-
-    timesheet_repo.create(timesheet_data).await?;
-    Ok(())
+/// Staff members keep their timesheet when they leave the event after they
+/// already submitted it or logged some work, the organizer is warned about it.
+async fn kept_timesheet_warning(
+    staff: &StaffExtended,
+    timesheet_repo: &TimesheetRepository,
+) -> DbResult<Option<String>> {
+    let timesheet = timesheet_repo
+        .read_one_for_staff(staff.user.id, staff.company.id, staff.event_id)
+        .await?;
+
+    Ok(timesheet.map(|sheet| match sheet.approval_status {
+        ApprovalStatus::Pending => format!(
+            "{} already submitted their timesheet for the event, so it was kept.",
+            staff.user.name
+        ),
+        ApprovalStatus::Accepted | ApprovalStatus::Rejected => format!(
+            "The timesheet of {} for the event was already reviewed, so it was kept.",
+            staff.user.name
+        ),
+        ApprovalStatus::NotRequested if sheet.total_hours > 0.0 => format!(
+            "{} already logged {} hours for the event, so their timesheet was kept.",
+            staff.user.name, sheet.total_hours
+        ),
+        ApprovalStatus::NotRequested => format!(
+            "{} already filled in their timesheet for the event, so it was kept.",
+            staff.user.name
+        ),
+    }))
 }
 
 #[patch("/event/{event_id}/staff/{staff_id}")]
@@ -175,7 +196,6 @@ pub async fn update_event_staff(
     event_staff_data: web::Json<StaffData>,
     event_staff_repo: web::Data<StaffRepository>,
     timesheet_repo: web::Data<TimesheetRepository>,
-    event_repo: web::Data<EventRepository>,
 ) -> HttpResponse {
     if is_data_invalid(event_staff_data.clone()) {
         return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
@@ -194,7 +214,6 @@ pub async fn update_event_staff(
         return HttpResponse::NotFound().body(parse_error(http::StatusCode::NOT_FOUND));
     }
     let old_staff = current_staff.expect("Should be valid now.");
-    let status_change = event_staff_data.status.clone();
 
     // Make sure the decider is a valid entity in the system.
     if event_staff_data.decided_by.is_some() {
@@ -217,23 +236,15 @@ pub async fn update_event_staff(
         .await;
 
     if let Ok(staff) = result {
-        // If a status change occured, and everything else went well, then we
-        // need to create the timesheet.
-        if status_change.is_some()
-            && status_change.unwrap() == AcceptanceStatus::Accepted
-            && old_staff.status != AcceptanceStatus::Accepted
+        let mut warning = None;
+        if old_staff.status == AcceptanceStatus::Accepted
+            && staff.status != AcceptanceStatus::Accepted
         {
-            let timesheet_res = create_timesheet_for_user(
-                staff.user.id,
-                staff.company.id,
-                event_id,
-                timesheet_repo,
-                event_repo,
-            )
-            .await;
-            if timesheet_res.is_err() {
-                return handle_database_error(timesheet_res.expect_err("Should be err."));
+            let warning_res = kept_timesheet_warning(&staff, &timesheet_repo).await;
+            if let Err(error) = warning_res {
+                return handle_database_error(error);
             }
+            warning = warning_res.expect("Should be valid.");
         }
 
         // Since changes are performed by the manager, we re-fetch all staff to refresh their view.
@@ -244,6 +255,7 @@ pub async fn update_event_staff(
                 offset: None,
             },
             event_staff_repo,
+            warning,
         )
         .await;
     }
@@ -275,6 +287,7 @@ pub async fn delete_all_rejected_event_staff(
             offset: None,
         },
         event_staff_repo,
+        None,
     )
     .await
 }
@@ -283,6 +296,7 @@ pub async fn delete_all_rejected_event_staff(
 pub async fn delete_event_staff(
     path: web::Path<(String, String)>,
     event_staff_repo: web::Data<StaffRepository>,
+    timesheet_repo: web::Data<TimesheetRepository>,
 ) -> HttpResponse {
     let parsed_ids = extract_path_tuple_ids(path.into_inner());
     if parsed_ids.is_err() {
@@ -290,12 +304,26 @@ pub async fn delete_event_staff(
     }
 
     let (event_id, staff_id) = parsed_ids.unwrap();
-    let result = event_staff_repo.delete(staff_id).await;
+    let staff_res = event_staff_repo.read_one(staff_id).await;
+    if let Err(error) = staff_res {
+        return handle_database_error(error);
+    }
+    let staff = staff_res.expect("Should be valid.");
 
+    let result = event_staff_repo.delete(staff_id).await;
     if let Err(error) = result {
         return handle_database_error(error);
     }
 
+    let mut warning = None;
+    if staff.status == AcceptanceStatus::Accepted {
+        let warning_res = kept_timesheet_warning(&staff, &timesheet_repo).await;
+        if let Err(error) = warning_res {
+            return handle_database_error(error);
+        }
+        warning = warning_res.expect("Should be valid.");
+    }
+
     read_all_event_staff(
         event_id,
         StaffFilter {
@@ -303,6 +331,7 @@ pub async fn delete_event_staff(
             offset: None,
         },
         event_staff_repo,
+        warning,
     )
     .await
 }
@@ -1,28 +1,34 @@
-use std::str::FromStr;
+use std::{collections::HashMap, str::FromStr};
 
 use actix_web::{delete, get, http, patch, post, web, HttpResponse};
 use askama::Template;
-use chrono::Utc;
+use chrono::{NaiveDateTime, Utc};
 use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::{
     common::{calculate_new_offsets, PAGINATION_LIMIT},
     errors::{handle_database_error, parse_error},
-    handlers::common::extract_path_tuple_ids,
-    models::{EventRole, TaskPriority},
+    handlers::common::{
+        check_event_tasks_editable, check_task_editable, extract_path_tuple_ids, DeletionQuery,
+    },
+    models::{EventRole, TaskPriority, TaskStatus},
     repositories::{
         assigned_staff::assigned_staff_repo::AssignedStaffRepository,
-        event_staff::event_staff_repo::StaffRepository,
+        event::event_repo::EventRepository,
+        event_staff::{event_staff_repo::StaffRepository, models::StaffLite},
         task::{
-            models::{NewTask, TaskData, TaskExtended, TaskFilter},
+            models::{NewTask, TaskData, TaskExtended, TaskFilter, TaskMoveData},
             task_repo::TaskRepository,
         },
+        task_dependency::task_dependency_repo::TaskDependencyRepository,
     },
     templates::task::{
-        EventTask, TaskCreationTemplate, TaskEditTemplate, TaskPanelTemplate, TasksPanelTemplate,
+        CriticalPathTemplate, EventTask, SubtasksTemplate, TaskBoardColumn, TaskBoardTemplate,
+        TaskCreationTemplate, TaskEditTemplate, TaskPanelTemplate, TasksPanelTemplate,
         TasksTemplate,
     },
+    utils::task_graph::critical_path::{find_critical_path, TaskNode},
 };
 
 #[derive(Deserialize)]
@@ -31,6 +37,21 @@ pub struct NewEventTaskData {
     title: String,
     description: Option<String>,
     priority: TaskPriority,
+    due_at: Option<NaiveDateTime>,
+    estimated_hours: Option<f32>,
+    parent_task_id: Option<Uuid>,
+}
+
+#[derive(Deserialize)]
+pub struct TaskCreationQuery {
+    parent_task_id: Option<Uuid>,
+}
+
+#[derive(Deserialize)]
+pub struct TaskBoardMoveData {
+    mover_id: Uuid, // staff table ID
+    status: TaskStatus,
+    position: i32,
 }
 
 async fn get_tasks_per_event(
@@ -108,6 +129,7 @@ pub async fn get_event_tasks(
 pub async fn create_task(
     event_id: web::Path<String>,
     new_task: web::Json<NewEventTaskData>,
+    event_repo: web::Data<EventRepository>,
     task_repo: web::Data<TaskRepository>,
     assigned_repo: web::Data<AssignedStaffRepository>,
 ) -> HttpResponse {
@@ -124,13 +146,26 @@ pub async fn create_task(
         return HttpResponse::BadRequest().body("The description can't be empty.".to_string());
     }
 
+    if new_task.estimated_hours.is_some() && new_task.estimated_hours.unwrap() < 0.0 {
+        return HttpResponse::BadRequest()
+            .body("The estimated effort can't be negative.".to_string());
+    }
+
     let parsed_id = id_parse.expect("Should be valid.");
+
+    if let Err(response) = check_event_tasks_editable(parsed_id, &event_repo).await {
+        return response;
+    }
+
     let data = NewTask {
         event_id: parsed_id,
         creator_id: new_task.creator_id,
         title: new_task.title.clone(),
         description: new_task.description.clone(),
         priority: new_task.priority.clone(),
+        due_at: new_task.due_at,
+        estimated_hours: new_task.estimated_hours,
+        parent_task_id: new_task.parent_task_id,
     };
     let result = task_repo.create(data).await;
     if let Ok(task) = result {
@@ -145,6 +180,10 @@ fn is_data_empty(data: &TaskData) -> bool {
         && data.finished_at.is_none()
         && data.priority.is_none()
         && data.accepts_staff.is_none()
+        && data.status.is_none()
+        && data.due_at.is_none()
+        && data.estimated_hours.is_none()
+        && data.parent_task_id.is_none()
 }
 
 fn validate_data(data: TaskData) -> Result<(), String> {
@@ -160,6 +199,30 @@ fn validate_data(data: TaskData) -> Result<(), String> {
         return Err("The description can't be empty.".to_string());
     }
 
+    if data.estimated_hours.is_some() && data.estimated_hours.expect("Should be some") < 0.0 {
+        return Err("The estimated effort can't be negative.".to_string());
+    }
+
+    Ok(())
+}
+
+/// Completing a task is refused while any of its blockers or subtasks is open.
+async fn check_task_not_blocked(
+    task_id: Uuid,
+    task_repo: &web::Data<TaskRepository>,
+) -> Result<(), HttpResponse> {
+    let blocked_res = task_repo.has_open_blockers(task_id).await;
+    if blocked_res.is_err() {
+        return Err(handle_database_error(
+            blocked_res.expect_err("Should be an error."),
+        ));
+    }
+
+    if blocked_res.expect("Should be valid.") {
+        return Err(HttpResponse::BadRequest()
+            .body("The task is blocked by unfinished tasks or subtasks.".to_string()));
+    }
+
     Ok(())
 }
 
@@ -167,6 +230,7 @@ fn validate_data(data: TaskData) -> Result<(), String> {
 pub async fn update_task(
     task_id: web::Path<String>,
     task_data: web::Json<TaskData>,
+    event_repo: web::Data<EventRepository>,
     task_repo: web::Data<TaskRepository>,
     assigned_repo: web::Data<AssignedStaffRepository>,
 ) -> HttpResponse {
@@ -181,6 +245,16 @@ pub async fn update_task(
     }
     let parsed_id = id_parse.expect("Should be valid.");
 
+    if let Err(response) = check_task_editable(parsed_id, &event_repo, &task_repo).await {
+        return response;
+    }
+
+    if task_data.finished_at.is_some() || task_data.status == Some(TaskStatus::Done) {
+        if let Err(response) = check_task_not_blocked(parsed_id, &task_repo).await {
+            return response;
+        }
+    }
+
     let result = task_repo.update(parsed_id, task_data.into_inner()).await;
     if result.is_err() {
         return handle_database_error(result.expect_err("Should be an error."));
@@ -193,6 +267,7 @@ pub async fn update_task(
 #[patch("/event/task/{task_id}/completion")]
 pub async fn update_task_completion(
     task_id: web::Path<String>,
+    event_repo: web::Data<EventRepository>,
     task_repo: web::Data<TaskRepository>,
     assigned_repo: web::Data<AssignedStaffRepository>,
 ) -> HttpResponse {
@@ -202,12 +277,24 @@ pub async fn update_task_completion(
     }
     let parsed_id = id_parse.expect("Should be valid.");
 
+    if let Err(response) = check_task_editable(parsed_id, &event_repo, &task_repo).await {
+        return response;
+    }
+
+    if let Err(response) = check_task_not_blocked(parsed_id, &task_repo).await {
+        return response;
+    }
+
     let task_data = TaskData {
         title: None,
         finished_at: Some(Utc::now().naive_local()),
         description: None,
         priority: None,
         accepts_staff: None,
+        status: Some(TaskStatus::Done),
+        due_at: None,
+        estimated_hours: None,
+        parent_task_id: None,
     };
 
     let result = task_repo.update(parsed_id, task_data).await;
@@ -221,6 +308,8 @@ pub async fn update_task_completion(
 #[delete("/event/task/{task_id}")]
 pub async fn delete_task(
     task_id: web::Path<String>,
+    query: web::Query<DeletionQuery>,
+    event_repo: web::Data<EventRepository>,
     task_repo: web::Data<TaskRepository>,
 ) -> HttpResponse {
     let id_parse = Uuid::from_str(task_id.into_inner().as_str());
@@ -229,7 +318,11 @@ pub async fn delete_task(
     }
     let parsed_id = id_parse.expect("Should be valid.");
 
-    let result = task_repo.delete(parsed_id).await;
+    if let Err(response) = check_task_editable(parsed_id, &event_repo, &task_repo).await {
+        return response;
+    }
+
+    let result = task_repo.delete(parsed_id, query.deleted_by).await;
     if let Err(error) = result {
         return handle_database_error(error);
     }
@@ -275,9 +368,124 @@ pub async fn open_tasks_panel(
     HttpResponse::Ok().body(body.expect("Should be valid."))
 }
 
+async fn render_task_board(
+    requester: StaffLite,
+    task_repo: web::Data<TaskRepository>,
+) -> HttpResponse {
+    let result = task_repo.read_board_for_event(requester.event_id).await;
+    if result.is_err() {
+        return handle_database_error(result.expect_err("Should be an error."));
+    }
+
+    // The tasks come ordered by status, so every column keeps its order.
+    let mut columns: Vec<TaskBoardColumn> = TaskStatus::all()
+        .into_iter()
+        .map(|status| TaskBoardColumn {
+            status,
+            tasks: vec![],
+        })
+        .collect();
+    for task in result.expect("Should be valid.") {
+        let column = columns
+            .iter_mut()
+            .find(|column| column.status == task.status)
+            .expect("Every status has a column.");
+        column.tasks.push(task.into());
+    }
+
+    let template = TaskBoardTemplate { requester, columns };
+
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError()
+            .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be valid."))
+}
+
+#[get("/event/staff/{staff_id}/task-board")]
+pub async fn get_task_board(
+    staff_id: web::Path<String>,
+    staff_repo: web::Data<StaffRepository>,
+    task_repo: web::Data<TaskRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(staff_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+    let parsed_id = id_parse.expect("Should be valid.");
+
+    let staff_res = staff_repo.read_one(parsed_id).await;
+    if staff_res.is_err() {
+        return handle_database_error(staff_res.expect_err("Should be an error."));
+    }
+
+    render_task_board(staff_res.expect("Should be valid.").into(), task_repo).await
+}
+
+#[patch("/event/task/{task_id}/position")]
+pub async fn move_task(
+    task_id: web::Path<String>,
+    move_data: web::Json<TaskBoardMoveData>,
+    event_repo: web::Data<EventRepository>,
+    staff_repo: web::Data<StaffRepository>,
+    task_repo: web::Data<TaskRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(task_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+    let parsed_id = id_parse.expect("Should be valid.");
+
+    if move_data.position < 0 {
+        return HttpResponse::BadRequest().body("The position can't be negative.".to_string());
+    }
+
+    let staff_res = staff_repo.read_one(move_data.mover_id).await;
+    if staff_res.is_err() {
+        return handle_database_error(staff_res.expect_err("Should be an error."));
+    }
+    let staff = staff_res.expect("Should be valid.");
+
+    let task_res = task_repo.read_one(parsed_id).await;
+    if task_res.is_err() {
+        return handle_database_error(task_res.expect_err("Should be an error."));
+    }
+    let task = task_res.expect("Should be valid.");
+
+    if staff.role != EventRole::Organizer || staff.event_id != task.event_id {
+        return HttpResponse::Forbidden().body(parse_error(http::StatusCode::FORBIDDEN));
+    }
+
+    if let Err(response) = check_event_tasks_editable(task.event_id, &event_repo).await {
+        return response;
+    }
+
+    if move_data.status == TaskStatus::Done && task.status != TaskStatus::Done {
+        if let Err(response) = check_task_not_blocked(parsed_id, &task_repo).await {
+            return response;
+        }
+    }
+
+    let data = TaskMoveData {
+        status: move_data.status.clone(),
+        position: move_data.position,
+    };
+    let result = task_repo.move_task(parsed_id, data).await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_task_board(staff.into(), task_repo).await
+}
+
 #[get("/event/staff/{staff_id}/task-creation")]
 pub async fn open_task_creation_panel(
     staff_id: web::Path<String>,
+    query: web::Query<TaskCreationQuery>,
     staff_repo: web::Data<StaffRepository>,
 ) -> HttpResponse {
     let id_parse = Uuid::from_str(staff_id.into_inner().as_str());
@@ -299,6 +507,7 @@ pub async fn open_task_creation_panel(
     let template = TaskCreationTemplate {
         creator_id: staff.id,
         event_id: staff.event_id,
+        parent_task_id: query.parent_task_id,
     };
 
     let body = template.render();
@@ -414,3 +623,118 @@ pub async fn open_task_edit_panel(
     }
     HttpResponse::Ok().body(body.expect("Should be valid."))
 }
+
+#[get("/event/staff/{staff_id}/task/{task_id}/subtasks")]
+pub async fn get_subtasks(
+    path: web::Path<(String, String)>,
+    staff_repo: web::Data<StaffRepository>,
+    task_repo: web::Data<TaskRepository>,
+) -> HttpResponse {
+    let parsed_ids = extract_path_tuple_ids(path.into_inner());
+    if parsed_ids.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let (staff_id, task_id) = parsed_ids.unwrap();
+
+    let staff_res = staff_repo.read_one(staff_id).await;
+    if staff_res.is_err() {
+        return handle_database_error(staff_res.expect_err("Should be an error."));
+    }
+
+    let result = task_repo.read_subtasks(task_id).await;
+    if result.is_err() {
+        return handle_database_error(result.expect_err("Should be an error."));
+    }
+
+    let template = SubtasksTemplate {
+        requester: staff_res.expect("Should be valid.").into(),
+        task_id,
+        subtasks: result
+            .expect("Should be valid.")
+            .into_iter()
+            .map(|task| task.into())
+            .collect(),
+    };
+
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError()
+            .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be valid."))
+}
+
+#[get("/event/{event_id}/critical-path")]
+pub async fn get_event_critical_path(
+    event_id: web::Path<String>,
+    task_repo: web::Data<TaskRepository>,
+    dependency_repo: web::Data<TaskDependencyRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(event_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+    let parsed_id = id_parse.expect("Should be valid.");
+
+    let tasks_res = task_repo.read_board_for_event(parsed_id).await;
+    if tasks_res.is_err() {
+        return handle_database_error(tasks_res.expect_err("Should be an error."));
+    }
+
+    let dependencies_res = dependency_repo.read_all_for_event(parsed_id).await;
+    if dependencies_res.is_err() {
+        return handle_database_error(dependencies_res.expect_err("Should be an error."));
+    }
+
+    // Finished work no longer holds anything up.
+    let open_tasks: Vec<TaskExtended> = tasks_res
+        .expect("Should be valid.")
+        .into_iter()
+        .filter(|task| task.status != TaskStatus::Done)
+        .collect();
+    let nodes: Vec<TaskNode> = open_tasks
+        .iter()
+        .map(|task| TaskNode {
+            id: task.task_id,
+            estimated_hours: task.estimated_hours.unwrap_or(0.0),
+        })
+        .collect();
+    let edges: Vec<(Uuid, Uuid)> = dependencies_res
+        .expect("Should be valid.")
+        .into_iter()
+        .map(|dependency| (dependency.blocker_id, dependency.task_id))
+        .collect();
+
+    let path = find_critical_path(&nodes, &edges);
+
+    let mut tasks_by_id: HashMap<Uuid, TaskExtended> = open_tasks
+        .into_iter()
+        .map(|task| (task.task_id, task))
+        .collect();
+    let tasks: Vec<EventTask> = path
+        .iter()
+        .filter_map(|task_id| tasks_by_id.remove(task_id))
+        .map(|task| task.into())
+        .collect();
+    let total_hours = nodes
+        .iter()
+        .filter(|node| path.contains(&node.id))
+        .map(|node| node.estimated_hours)
+        .sum();
+
+    let template = CriticalPathTemplate { tasks, total_hours };
+
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError()
+            .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be valid."))
+}
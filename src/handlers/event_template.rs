@@ -0,0 +1,193 @@
+use std::str::FromStr;
+
+use actix_web::{delete, get, http, post, web, HttpResponse};
+use askama::Template;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    errors::{handle_database_error, parse_error},
+    handlers::common::extract_path_tuple_ids,
+    models::{EmployeeLevel, EventRole},
+    repositories::{
+        employment::employment_repo::EmploymentRepository,
+        event_staff::event_staff_repo::StaffRepository,
+        event_template::{
+            event_template_repo::EventTemplateRepository,
+            models::{NewEventFromTemplate, NewEventTemplate},
+        },
+    },
+    templates::{event::EventTemplate, event_template::EventTemplatesTemplate},
+};
+
+#[derive(Deserialize)]
+pub struct NewEventTemplateData {
+    creator_id: Uuid, // staff table ID
+    name: String,
+}
+
+#[get("/user/{user_id}/employment/{company_id}/event-template")]
+pub async fn get_event_templates(
+    path: web::Path<(String, String)>,
+    employment_repo: web::Data<EmploymentRepository>,
+    template_repo: web::Data<EventTemplateRepository>,
+) -> HttpResponse {
+    let parsed_ids = extract_path_tuple_ids(path.into_inner());
+    if parsed_ids.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let (user_id, company_id) = parsed_ids.unwrap();
+    let employment_res = employment_repo.read_one(user_id, company_id).await;
+    if employment_res.is_err() {
+        return handle_database_error(employment_res.expect_err("Should be an error."));
+    }
+
+    if employment_res.expect("Should be valid.").level != EmployeeLevel::CompanyAdministrator {
+        return HttpResponse::Forbidden().body(parse_error(http::StatusCode::FORBIDDEN));
+    }
+
+    let result = template_repo.read_all_for_company(company_id).await;
+    if result.is_err() {
+        return handle_database_error(result.expect_err("Should be an error."));
+    }
+
+    let template = EventTemplatesTemplate {
+        requester_id: user_id,
+        company_id,
+        templates: result
+            .expect("Should be valid.")
+            .into_iter()
+            .map(|event_template| event_template.into())
+            .collect(),
+    };
+
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError()
+            .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be valid."))
+}
+
+#[post("/event/{event_id}/template")]
+pub async fn create_event_template(
+    event_id: web::Path<String>,
+    new_template: web::Json<NewEventTemplateData>,
+    staff_repo: web::Data<StaffRepository>,
+    template_repo: web::Data<EventTemplateRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(event_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+    let parsed_id = id_parse.expect("Should be valid.");
+
+    if new_template.name.trim().is_empty() {
+        return HttpResponse::BadRequest().body("Name can't be empty.");
+    }
+
+    let staff_res = staff_repo.read_one(new_template.creator_id).await;
+    if staff_res.is_err() {
+        return handle_database_error(staff_res.expect_err("Should be an error."));
+    }
+    let staff = staff_res.expect("Should be valid.");
+
+    if staff.role != EventRole::Organizer || staff.event_id != parsed_id {
+        return HttpResponse::Forbidden().body(parse_error(http::StatusCode::FORBIDDEN));
+    }
+
+    // The template belongs to the company the organizer represents.
+    let data = NewEventTemplate {
+        event_id: parsed_id,
+        company_id: staff.company.id,
+        name: new_template.name.trim().to_string(),
+    };
+    let result = template_repo.create(data).await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    HttpResponse::Created().body("Template saved!")
+}
+
+#[post("/event-template/{template_id}/event")]
+pub async fn create_event_from_template(
+    template_id: web::Path<String>,
+    new_event: web::Json<NewEventFromTemplate>,
+    employment_repo: web::Data<EmploymentRepository>,
+    template_repo: web::Data<EventTemplateRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(template_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+    let parsed_id = id_parse.expect("Should be valid.");
+
+    if new_event.name.trim().is_empty() {
+        return HttpResponse::BadRequest().body("Name can't be empty.");
+    }
+
+    let employee_res = employment_repo
+        .read_one(new_event.creator_id, new_event.company_id)
+        .await;
+    if employee_res.is_err() {
+        return handle_database_error(employee_res.expect_err("Should be error."));
+    }
+
+    if employee_res.expect("Should be OK").level != EmployeeLevel::CompanyAdministrator {
+        return HttpResponse::Forbidden().body(parse_error(http::StatusCode::FORBIDDEN));
+    }
+
+    let template_res = template_repo.read_one(parsed_id).await;
+    if template_res.is_err() {
+        return handle_database_error(template_res.expect_err("Should be an error."));
+    }
+
+    if template_res.expect("Should be valid.").company_id != new_event.company_id {
+        return HttpResponse::Forbidden().body(parse_error(http::StatusCode::FORBIDDEN));
+    }
+
+    let result = template_repo
+        .instantiate(parsed_id, new_event.into_inner())
+        .await;
+
+    if let Ok(event) = result {
+        let template: EventTemplate = event.into();
+
+        let body = template.render();
+        if body.is_err() {
+            return HttpResponse::InternalServerError()
+                .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+        }
+
+        return HttpResponse::Created()
+            .content_type("text/html")
+            .body(body.expect("Should be valid."));
+    }
+
+    handle_database_error(result.expect_err("Should be error."))
+}
+
+#[delete("/event-template/{template_id}")]
+pub async fn delete_event_template(
+    template_id: web::Path<String>,
+    template_repo: web::Data<EventTemplateRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(template_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let parsed_id = id_parse.expect("Should be valid.");
+    let result = template_repo.delete(parsed_id).await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    // Ok because of HTMX
+    HttpResponse::Ok().finish()
+}
@@ -0,0 +1,336 @@
+use actix_web::{get, http, web, HttpResponse};
+use futures::{Stream, TryStreamExt};
+use serde::Deserialize;
+
+use crate::{
+    errors::parse_error,
+    handlers::common::{
+        extract_path_tuple_ids, is_company_administrator, is_event_organizer, parse_month,
+    },
+    repositories::{
+        assigned_staff::assigned_staff_repo::AssignedStaffRepository,
+        employment::employment_repo::EmploymentRepository,
+        event_staff::event_staff_repo::StaffRepository,
+        timesheet::{
+            models::{TimesheetExport, Workday},
+            timesheet_repo::TimesheetRepository,
+        },
+    },
+    utils::{
+        export::{
+            export_stream::stream_export,
+            models::{ExportCell, ExportFormat},
+        },
+        wage_calc::{
+            calculate_wage::calculate_timesheet_wage,
+            models::{TimesheetWageDetailed, YearAndMonth},
+        },
+    },
+};
+
+#[derive(Deserialize, Debug)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TimesheetExportQuery {
+    /// `YYYY-MM`
+    pub month: String,
+    #[serde(default)]
+    pub format: ExportFormat,
+    #[serde(default)]
+    pub pink_paper_signed: bool,
+}
+
+pub const STAFF_EXPORT_COLUMNS: [&str; 6] = [
+    "Name",
+    "Email",
+    "Birth Date",
+    "Company",
+    "Role",
+    "Accepted At",
+];
+
+pub const TASK_EXPORT_COLUMNS: [&str; 9] = [
+    "Task",
+    "Status",
+    "Priority",
+    "Due At",
+    "Estimated Hours",
+    "Staff",
+    "Email",
+    "Company",
+    "Assignment Status",
+];
+
+pub const TIMESHEET_EXPORT_COLUMNS: [&str; 18] = [
+    "Employee",
+    "Email",
+    "Event",
+    "Timesheet Start",
+    "Timesheet End",
+    "Timesheet Status",
+    "Contract",
+    "Hourly Wage",
+    "Date",
+    "Hours",
+    "Comment",
+    "Gross Wage",
+    "Employee Social Insurance",
+    "Employee Health Insurance",
+    "Employer Social Insurance",
+    "Employer Health Insurance",
+    "Net Wage",
+    "Wage Error",
+];
+
+fn export_response<S>(format: ExportFormat, file_name: &str, stream: S) -> HttpResponse
+where
+    S: Stream<Item = Result<web::Bytes, std::io::Error>> + 'static,
+{
+    HttpResponse::Ok()
+        .content_type(format.content_type())
+        .insert_header((
+            http::header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"{}.{}\"",
+                file_name,
+                format.extension()
+            ),
+        ))
+        .streaming(stream)
+}
+
+fn round_money(value: f32) -> ExportCell {
+    ExportCell::Number((value as f64 * 100.0).round() / 100.0)
+}
+
+/// The wage is computed per month, every workday gets a share of its month
+/// by the hours worked, so the rows of a month add up to the month's wage.
+fn workday_wage_cells(
+    wage: &Result<TimesheetWageDetailed, String>,
+    workday: &Workday,
+) -> Vec<ExportCell> {
+    let error = match wage {
+        Err(error) => Some(error.clone()),
+        Ok(wage) => wage.error_option.clone(),
+    };
+    if let Some(error) = error {
+        let mut cells = vec![ExportCell::Empty; 6];
+        cells.push(error.into());
+        return cells;
+    }
+
+    let wage = wage.as_ref().expect("Should be valid.");
+    let month: YearAndMonth = workday.date.into();
+    match wage.month_to_detailed_wage.get(&month) {
+        Some(detailed) if detailed.worked_hours > 0.0 => {
+            let share = workday.total_hours / detailed.worked_hours;
+            vec![
+                round_money(detailed.tax_base * share),
+                round_money(detailed.employee_social_insurance * share),
+                round_money(detailed.employee_health_insurance * share),
+                round_money(detailed.employer_social_insurance * share),
+                round_money(detailed.employer_health_insurance * share),
+                round_money(detailed.net_wage * share),
+                ExportCell::Empty,
+            ]
+        }
+        _ => {
+            let mut cells = vec![ExportCell::Empty; 6];
+            cells.push("No wage was computed for the month.".into());
+            cells
+        }
+    }
+}
+
+fn timesheet_row(
+    timesheet: &TimesheetExport,
+    workday: &Workday,
+    wage: &Result<TimesheetWageDetailed, String>,
+) -> Vec<ExportCell> {
+    let mut cells = vec![
+        timesheet.user_name.clone().into(),
+        timesheet.user_email.clone().into(),
+        timesheet.event_name.clone().into(),
+        timesheet.start_date.into(),
+        timesheet.end_date.into(),
+        timesheet.approval_status.to_string().into(),
+        timesheet.employment_type.to_string().into(),
+        timesheet.hourly_wage.into(),
+        workday.date.into(),
+        workday.total_hours.into(),
+        workday.comment.clone().into(),
+    ];
+    cells.append(&mut workday_wage_cells(wage, workday));
+
+    cells
+}
+
+#[get("/event/{event_id}/export/{user_id}/staff")]
+pub async fn export_event_staff(
+    path: web::Path<(String, String)>,
+    query: web::Query<ExportQuery>,
+    staff_repo: web::Data<StaffRepository>,
+) -> HttpResponse {
+    let parsed_ids = extract_path_tuple_ids(path.into_inner());
+    if parsed_ids.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let (event_id, user_id) = parsed_ids.expect("Should be valid.");
+    if !is_event_organizer(event_id, user_id, &staff_repo).await {
+        return HttpResponse::Forbidden().body(parse_error(http::StatusCode::FORBIDDEN));
+    }
+
+    let staff_repo = staff_repo.into_inner();
+    let stream = stream_export(
+        query.format,
+        "Staff",
+        &STAFF_EXPORT_COLUMNS,
+        move |mut sink| async move {
+            let mut staff = staff_repo.stream_accepted_for_event(event_id);
+            while let Some(member) = staff.try_next().await? {
+                sink.push(vec![
+                    member.name.into(),
+                    member.email.into(),
+                    member.birth.into(),
+                    member.company_name.into(),
+                    member.role.to_string().into(),
+                    member.accepted_at.date().into(),
+                ])
+                .await?;
+            }
+
+            Ok(sink)
+        },
+    );
+
+    export_response(query.format, "event-staff", stream)
+}
+
+#[get("/event/{event_id}/export/{user_id}/tasks")]
+pub async fn export_event_tasks(
+    path: web::Path<(String, String)>,
+    query: web::Query<ExportQuery>,
+    staff_repo: web::Data<StaffRepository>,
+    assigned_staff_repo: web::Data<AssignedStaffRepository>,
+) -> HttpResponse {
+    let parsed_ids = extract_path_tuple_ids(path.into_inner());
+    if parsed_ids.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let (event_id, user_id) = parsed_ids.expect("Should be valid.");
+    if !is_event_organizer(event_id, user_id, &staff_repo).await {
+        return HttpResponse::Forbidden().body(parse_error(http::StatusCode::FORBIDDEN));
+    }
+
+    let assigned_staff_repo = assigned_staff_repo.into_inner();
+    let stream = stream_export(
+        query.format,
+        "Tasks",
+        &TASK_EXPORT_COLUMNS,
+        move |mut sink| async move {
+            let mut assignments = assigned_staff_repo.stream_all_for_event(event_id);
+            while let Some(assignment) = assignments.try_next().await? {
+                sink.push(vec![
+                    assignment.task_title.into(),
+                    assignment.task_status.to_string().into(),
+                    assignment.task_priority.to_string().into(),
+                    assignment
+                        .due_at
+                        .map(|due_at| due_at.format("%Y-%m-%d %H:%M").to_string())
+                        .into(),
+                    assignment.estimated_hours.into(),
+                    assignment.staff_name.into(),
+                    assignment.staff_email.into(),
+                    assignment.company_name.into(),
+                    assignment
+                        .assignment_status
+                        .map(|status| status.to_string())
+                        .into(),
+                ])
+                .await?;
+            }
+
+            Ok(sink)
+        },
+    );
+
+    export_response(query.format, "event-tasks", stream)
+}
+
+/// Every worked day of the company's timesheets in the month, along with
+/// its share of the computed wage.
+#[get("/company/{company_id}/export/{user_id}/timesheets")]
+pub async fn export_company_timesheets(
+    path: web::Path<(String, String)>,
+    query: web::Query<TimesheetExportQuery>,
+    employment_repo: web::Data<EmploymentRepository>,
+    timesheet_repo: web::Data<TimesheetRepository>,
+) -> HttpResponse {
+    let parsed_ids = extract_path_tuple_ids(path.into_inner());
+    if parsed_ids.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let month_bounds = parse_month(&query.month);
+    if month_bounds.is_none() {
+        return HttpResponse::BadRequest().body("The month has to be in the YYYY-MM format.");
+    }
+    let (month_start, month_end) = month_bounds.expect("Should be some.");
+
+    let (company_id, user_id) = parsed_ids.expect("Should be valid.");
+    if !is_company_administrator(company_id, user_id, &employment_repo).await {
+        return HttpResponse::Forbidden()
+            .body("Only a company administrator can export the company's timesheets.");
+    }
+
+    let pink_paper_signed = query.pink_paper_signed;
+    let timesheet_repo = timesheet_repo.into_inner();
+    let stream = stream_export(
+        query.format,
+        &query.month,
+        &TIMESHEET_EXPORT_COLUMNS,
+        move |mut sink| async move {
+            // Collected first, the wage computation reads from the pool too
+            // and the open stream would hold on to another connection.
+            let timesheets: Vec<TimesheetExport> = timesheet_repo
+                .stream_all_for_company(company_id, month_start, month_end)
+                .try_collect()
+                .await?;
+            for timesheet in timesheets {
+                let related = timesheet_repo
+                    .read_all_with_date_from_to_per_employment_extended_db(
+                        timesheet.user_id,
+                        company_id,
+                        timesheet.start_date,
+                        timesheet.end_date,
+                    )
+                    .await?;
+                let wage = calculate_timesheet_wage(pink_paper_signed, &related, timesheet.id);
+
+                let workdays = related
+                    .timesheets
+                    .iter()
+                    .find(|related| related.timesheet.id == timesheet.id)
+                    .map(|related| related.workdays.as_slice())
+                    .unwrap_or_default();
+                for workday in workdays.iter().filter(|workday| {
+                    workday.date >= month_start
+                        && workday.date <= month_end
+                        && workday.total_hours > 0.0
+                }) {
+                    sink.push(timesheet_row(&timesheet, workday, &wage)).await?;
+                }
+            }
+
+            Ok(sink)
+        },
+    );
+
+    export_response(query.format, &format!("timesheets-{}", query.month), stream)
+}
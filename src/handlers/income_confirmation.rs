@@ -0,0 +1,220 @@
+use actix_web::{get, http, web, HttpResponse};
+use askama::Template;
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::{
+    errors::{handle_database_error, parse_error},
+    handlers::common::{extract_path_tuple_ids, is_company_administrator},
+    models::EmployeeLevel,
+    repositories::{
+        company::company_repo::CompanyRepository,
+        employment::employment_repo::EmploymentRepository,
+        timesheet::timesheet_repo::TimesheetRepository,
+    },
+    templates::employment::IncomeConfirmationTemplate,
+    utils::{
+        export::{
+            export_stream::stream_export,
+            models::{ExportCell, ExportFormat},
+        },
+        payroll::{accepted_wages::collect_accepted_wages, models::IncomeConfirmation},
+    },
+};
+
+#[derive(Deserialize, Debug)]
+pub struct IncomeConfirmationQuery {
+    pub year: i32,
+    #[serde(default)]
+    pub pink_paper_signed: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct IncomeConfirmationExportQuery {
+    pub year: i32,
+    #[serde(default)]
+    pub format: ExportFormat,
+    #[serde(default)]
+    pub pink_paper_signed: bool,
+}
+
+pub const INCOME_CONFIRMATION_EXPORT_COLUMNS: [&str; 12] = [
+    "Employee",
+    "Email",
+    "Contract",
+    "Year",
+    "Months Worked",
+    "Hours",
+    "Gross Income",
+    "Insurance Withheld",
+    "Tax Withheld",
+    "Net Income",
+    "Currency",
+    "Wage Error",
+];
+
+fn year_bounds(year: i32) -> Option<(NaiveDate, NaiveDate)> {
+    Some((
+        NaiveDate::from_ymd_opt(year, 1, 1)?,
+        NaiveDate::from_ymd_opt(year, 12, 31)?,
+    ))
+}
+
+fn confirmation_row(confirmation: IncomeConfirmation) -> Vec<ExportCell> {
+    let months_worked = confirmation.months_worked() as f64;
+    let total = confirmation.total;
+    vec![
+        confirmation.name.into(),
+        confirmation.email.into(),
+        confirmation.employment_type.to_string().into(),
+        (confirmation.year as f64).into(),
+        months_worked.into(),
+        total.worked_hours.into(),
+        ((total.gross_income as f64 * 100.0).round() / 100.0).into(),
+        ((total.insurance_withheld as f64 * 100.0).round() / 100.0).into(),
+        ((total.tax_withheld as f64 * 100.0).round() / 100.0).into(),
+        ((total.net_income as f64 * 100.0).round() / 100.0).into(),
+        "CZK".into(),
+        confirmation.errors.join("; ").into(),
+    ]
+}
+
+/// Printable confirmations of the year's taxable income. A company
+/// administrator gets one for every employee, anyone else only their own.
+#[get("/company/{company_id}/income-confirmation/{user_id}")]
+pub async fn get_income_confirmations(
+    path: web::Path<(String, String)>,
+    query: web::Query<IncomeConfirmationQuery>,
+    company_repo: web::Data<CompanyRepository>,
+    employment_repo: web::Data<EmploymentRepository>,
+    timesheet_repo: web::Data<TimesheetRepository>,
+) -> HttpResponse {
+    let parsed_ids = extract_path_tuple_ids(path.into_inner());
+    if parsed_ids.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let year_bounds = year_bounds(query.year);
+    if year_bounds.is_none() {
+        return HttpResponse::BadRequest().body("The year is not valid.");
+    }
+    let (year_start, year_end) = year_bounds.expect("Should be some.");
+
+    let (company_id, user_id) = parsed_ids.expect("Should be valid.");
+    let employment_res = employment_repo.read_one(user_id, company_id).await;
+    if employment_res.is_err() {
+        return HttpResponse::Forbidden().body(parse_error(http::StatusCode::FORBIDDEN));
+    }
+    let is_administrator =
+        employment_res.expect("Should be valid.").level == EmployeeLevel::CompanyAdministrator;
+
+    let company_res = company_repo.read_one_extended(company_id).await;
+    if let Err(error) = company_res {
+        return handle_database_error(error);
+    }
+    let company = company_res.expect("Should be valid.");
+
+    let employees_res = collect_accepted_wages(
+        &timesheet_repo,
+        company_id,
+        year_start,
+        year_end,
+        query.pink_paper_signed,
+    )
+    .await;
+    if let Err(error) = employees_res {
+        return handle_database_error(error);
+    }
+
+    let confirmations = employees_res
+        .expect("Should be valid.")
+        .into_iter()
+        .filter(|employee| is_administrator || employee.user_id == user_id)
+        .map(|employee| IncomeConfirmation::new(query.year, &company.name, &company.crn, employee))
+        .collect();
+
+    let template = IncomeConfirmationTemplate {
+        year: query.year,
+        confirmations,
+    };
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError()
+            .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be valid now."))
+}
+
+/// The year's income of all of the company's employees, one row each.
+#[get("/company/{company_id}/income-confirmation/{user_id}/export")]
+pub async fn export_income_confirmations(
+    path: web::Path<(String, String)>,
+    query: web::Query<IncomeConfirmationExportQuery>,
+    company_repo: web::Data<CompanyRepository>,
+    employment_repo: web::Data<EmploymentRepository>,
+    timesheet_repo: web::Data<TimesheetRepository>,
+) -> HttpResponse {
+    let parsed_ids = extract_path_tuple_ids(path.into_inner());
+    if parsed_ids.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let year_bounds = year_bounds(query.year);
+    if year_bounds.is_none() {
+        return HttpResponse::BadRequest().body("The year is not valid.");
+    }
+    let (year_start, year_end) = year_bounds.expect("Should be some.");
+
+    let (company_id, user_id) = parsed_ids.expect("Should be valid.");
+    if !is_company_administrator(company_id, user_id, &employment_repo).await {
+        return HttpResponse::Forbidden()
+            .body("Only a company administrator can export the income confirmations.");
+    }
+
+    let company_res = company_repo.read_one_extended(company_id).await;
+    if let Err(error) = company_res {
+        return handle_database_error(error);
+    }
+    let company = company_res.expect("Should be valid.");
+
+    let year = query.year;
+    let pink_paper_signed = query.pink_paper_signed;
+    let timesheet_repo = timesheet_repo.into_inner();
+    let stream = stream_export(
+        query.format,
+        &format!("Income {}", year),
+        &INCOME_CONFIRMATION_EXPORT_COLUMNS,
+        move |mut sink| async move {
+            let employees = collect_accepted_wages(
+                &timesheet_repo,
+                company_id,
+                year_start,
+                year_end,
+                pink_paper_signed,
+            )
+            .await?;
+            for employee in employees {
+                let confirmation =
+                    IncomeConfirmation::new(year, &company.name, &company.crn, employee);
+                sink.push(confirmation_row(confirmation)).await?;
+            }
+
+            Ok(sink)
+        },
+    );
+
+    HttpResponse::Ok()
+        .content_type(query.format.content_type())
+        .insert_header((
+            http::header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"income-confirmations-{}.{}\"",
+                year,
+                query.format.extension()
+            ),
+        ))
+        .streaming(stream)
+}
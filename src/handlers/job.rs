@@ -0,0 +1,85 @@
+use actix_web::{get, http, post, web, HttpResponse};
+use askama::Template;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    errors::{handle_database_error, parse_error},
+    handlers::common::is_administrator,
+    repositories::{job::job_repo::JobRepository, user::user_repo::UserRepository},
+    templates::job::JobsTemplate,
+};
+
+/// How many of the latest job runs are listed.
+const JOB_RUNS_SHOWN: i64 = 50;
+
+#[derive(Deserialize, Debug)]
+pub struct JobQuery {
+    pub user_id: Uuid,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct JobRunRequest {
+    pub user_id: Uuid,
+}
+
+async fn render_jobs(user_id: Uuid, job_repo: &JobRepository) -> HttpResponse {
+    let jobs_res = job_repo.read_all().await;
+    if let Err(error) = jobs_res {
+        return handle_database_error(error);
+    }
+
+    let runs_res = job_repo.read_runs(JOB_RUNS_SHOWN).await;
+    if let Err(error) = runs_res {
+        return handle_database_error(error);
+    }
+
+    let template = JobsTemplate {
+        user_id,
+        jobs: jobs_res.expect("Should be valid."),
+        runs: runs_res.expect("Should be valid."),
+    };
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError()
+            .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be valid now."))
+}
+
+/// The background jobs and their latest runs, for application administrators.
+#[get("/admin/jobs")]
+pub async fn get_jobs(
+    query: web::Query<JobQuery>,
+    user_repo: web::Data<UserRepository>,
+    job_repo: web::Data<JobRepository>,
+) -> HttpResponse {
+    if !is_administrator(query.user_id, &user_repo).await {
+        return HttpResponse::Forbidden().body("Only an administrator can see the jobs.");
+    }
+
+    render_jobs(query.user_id, &job_repo).await
+}
+
+/// Makes the job due, one of the application instances runs it shortly.
+#[post("/admin/jobs/{name}/run")]
+pub async fn run_job(
+    name: web::Path<String>,
+    data: web::Json<JobRunRequest>,
+    user_repo: web::Data<UserRepository>,
+    job_repo: web::Data<JobRepository>,
+) -> HttpResponse {
+    if !is_administrator(data.user_id, &user_repo).await {
+        return HttpResponse::Forbidden().body("Only an administrator can run the jobs.");
+    }
+
+    let result = job_repo.schedule_now(name.as_str()).await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_jobs(data.user_id, &job_repo).await
+}
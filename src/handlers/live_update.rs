@@ -0,0 +1,39 @@
+use actix_web::{get, http, web, HttpResponse};
+
+use crate::{
+    errors::parse_error, handlers::common::extract_path_tuple_ids, models::AcceptanceStatus,
+    repositories::event_staff::event_staff_repo::StaffRepository,
+    utils::live_update::live_update_hub::LiveUpdateHub,
+};
+
+/// Server-Sent Events telling the open pages of the event which of their
+/// panels changed, the panels then refresh themselves.
+#[get("/event/{event_id}/live/{user_id}")]
+pub async fn get_event_live_updates(
+    path: web::Path<(String, String)>,
+    staff_repo: web::Data<StaffRepository>,
+    live_update_hub: web::Data<LiveUpdateHub>,
+) -> HttpResponse {
+    let parsed_ids = extract_path_tuple_ids(path.into_inner());
+    if parsed_ids.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let (event_id, user_id) = parsed_ids.unwrap();
+
+    let staff_res = staff_repo
+        .read_by_event_and_user_id(event_id, user_id)
+        .await;
+
+    // Not staff -> Can't follow the event.
+    if staff_res.is_err()
+        || staff_res.expect("Should be valid").status != AcceptanceStatus::Accepted
+    {
+        return HttpResponse::Forbidden().body(parse_error(http::StatusCode::FORBIDDEN));
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header((http::header::CACHE_CONTROL, "no-cache"))
+        .streaming(live_update_hub.subscribe(event_id))
+}
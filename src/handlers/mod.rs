@@ -1,13 +1,31 @@
 pub mod assigned_staff;
 pub mod associated_company;
 pub mod auth;
+pub mod calendar_feed;
 pub mod comment;
 pub mod common;
 pub mod company;
 pub mod employment;
+pub mod employment_import;
 pub mod event;
+pub mod event_budget;
 pub mod event_staff;
 pub mod event_task;
+pub mod event_template;
+pub mod export;
+pub mod income_confirmation;
 pub mod index;
+pub mod job;
+pub mod live_update;
+pub mod notification;
+pub mod payroll;
+pub mod report;
+pub mod review_queue;
+pub mod submission_policy;
+pub mod task_checklist;
+pub mod task_dependency;
 pub mod timesheet;
+pub mod timesheet_amendment;
+pub mod timesheet_consistency;
+pub mod trash;
 pub mod user;
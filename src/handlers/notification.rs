@@ -0,0 +1,287 @@
+use std::str::FromStr;
+
+use actix_web::{get, http, patch, web, HttpResponse};
+use askama::Template;
+use uuid::Uuid;
+
+use crate::{
+    common::{calculate_new_offsets, PAGINATION_LIMIT},
+    errors::{handle_database_error, parse_error},
+    repositories::{
+        mail_outbox::{mail_outbox_repo::MailOutboxRepository, models::MailSettingData},
+        notification::{
+            models::{NotificationFilter, NotificationPreferenceData},
+            notification_repo::NotificationRepository,
+        },
+    },
+    templates::notification::{
+        NotificationBadgeTemplate, NotificationPreferencesTemplate, NotificationsTemplate,
+    },
+};
+
+async fn render_notifications(
+    user_id: Uuid,
+    filter: NotificationFilter,
+    notification_repo: web::Data<NotificationRepository>,
+) -> HttpResponse {
+    let limit = filter.limit.unwrap_or(PAGINATION_LIMIT);
+    let offset = filter.offset.unwrap_or(0);
+    let (prev_offset, next_offset) = calculate_new_offsets(Some(offset));
+
+    // We read one ahead to determine if we have reached the end before we actually do.
+    let result = notification_repo
+        .read_all_for_user(
+            user_id,
+            NotificationFilter {
+                limit: Some(limit + 1),
+                offset: Some(offset),
+            },
+        )
+        .await;
+    if result.is_err() {
+        return handle_database_error(result.expect_err("Should be an error."));
+    }
+
+    let count_res = notification_repo.count_unread(user_id).await;
+    if count_res.is_err() {
+        return handle_database_error(count_res.expect_err("Should be an error."));
+    }
+
+    let mut notifications = result.expect("Should be valid.");
+    let next_offset = if notifications.len() as i64 > limit {
+        notifications.pop();
+        next_offset
+    } else {
+        None
+    };
+
+    let template = NotificationsTemplate {
+        user_id,
+        notifications,
+        unread_count: count_res.expect("Should be valid."),
+        next_offset,
+        prev_offset,
+        limit,
+    };
+
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError()
+            .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be valid now."))
+}
+
+#[get("/user/{user_id}/notification")]
+pub async fn get_notifications(
+    user_id: web::Path<String>,
+    query: web::Query<NotificationFilter>,
+    notification_repo: web::Data<NotificationRepository>,
+) -> HttpResponse {
+    let query_params = query.into_inner();
+    if query_params.limit.is_some_and(|limit| limit < 0)
+        || query_params.offset.is_some_and(|offset| offset < 0)
+    {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let id_parse = Uuid::from_str(user_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    render_notifications(
+        id_parse.expect("Should be valid."),
+        query_params,
+        notification_repo,
+    )
+    .await
+}
+
+#[get("/user/{user_id}/notification-badge")]
+pub async fn get_notification_badge(
+    user_id: web::Path<String>,
+    notification_repo: web::Data<NotificationRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(user_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let parsed_id = id_parse.expect("Should be valid.");
+    let result = notification_repo.count_unread(parsed_id).await;
+    if let Ok(unread_count) = result {
+        let template = NotificationBadgeTemplate {
+            user_id: parsed_id,
+            unread_count,
+            swap_oob: false,
+        };
+
+        let body = template.render();
+        if body.is_err() {
+            return HttpResponse::InternalServerError()
+                .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+        }
+
+        return HttpResponse::Ok()
+            .content_type("text/html")
+            .body(body.expect("Should be valid now."));
+    }
+
+    handle_database_error(result.expect_err("Should be error."))
+}
+
+#[patch("/notification/{notification_id}")]
+pub async fn mark_notification_read(
+    notification_id: web::Path<String>,
+    notification_repo: web::Data<NotificationRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(notification_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let result = notification_repo
+        .mark_read(id_parse.expect("Should be valid."))
+        .await;
+    if result.is_err() {
+        return handle_database_error(result.expect_err("Should be an error."));
+    }
+
+    render_notifications(
+        result.expect("Should be valid.").user_id,
+        NotificationFilter {
+            limit: None,
+            offset: None,
+        },
+        notification_repo,
+    )
+    .await
+}
+
+#[patch("/user/{user_id}/notification")]
+pub async fn mark_all_notifications_read(
+    user_id: web::Path<String>,
+    notification_repo: web::Data<NotificationRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(user_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let parsed_id = id_parse.expect("Should be valid.");
+    let result = notification_repo.mark_all_read(parsed_id).await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_notifications(
+        parsed_id,
+        NotificationFilter {
+            limit: None,
+            offset: None,
+        },
+        notification_repo,
+    )
+    .await
+}
+
+async fn render_preferences(
+    user_id: Uuid,
+    notification_repo: web::Data<NotificationRepository>,
+    mail_outbox_repo: web::Data<MailOutboxRepository>,
+) -> HttpResponse {
+    let setting_res = mail_outbox_repo.read_setting(user_id).await;
+    if let Err(error) = setting_res {
+        return handle_database_error(error);
+    }
+
+    let result = notification_repo.read_preferences(user_id).await;
+    if let Ok(preferences) = result {
+        let template = NotificationPreferencesTemplate {
+            user_id,
+            preferences,
+            mail_delivery: setting_res.expect("Should be valid.").delivery,
+        };
+
+        let body = template.render();
+        if body.is_err() {
+            return HttpResponse::InternalServerError()
+                .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+        }
+
+        return HttpResponse::Ok()
+            .content_type("text/html")
+            .body(body.expect("Should be valid now."));
+    }
+
+    handle_database_error(result.expect_err("Should be error."))
+}
+
+#[get("/user/{user_id}/notification-preference")]
+pub async fn get_notification_preferences(
+    user_id: web::Path<String>,
+    notification_repo: web::Data<NotificationRepository>,
+    mail_outbox_repo: web::Data<MailOutboxRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(user_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    render_preferences(
+        id_parse.expect("Should be valid."),
+        notification_repo,
+        mail_outbox_repo,
+    )
+    .await
+}
+
+#[patch("/user/{user_id}/notification-preference")]
+pub async fn update_notification_preference(
+    user_id: web::Path<String>,
+    preference_data: web::Json<NotificationPreferenceData>,
+    notification_repo: web::Data<NotificationRepository>,
+    mail_outbox_repo: web::Data<MailOutboxRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(user_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let parsed_id = id_parse.expect("Should be valid.");
+    let result = notification_repo
+        .update_preference(parsed_id, preference_data.into_inner())
+        .await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_preferences(parsed_id, notification_repo, mail_outbox_repo).await
+}
+
+#[patch("/user/{user_id}/mail-setting")]
+pub async fn update_mail_setting(
+    user_id: web::Path<String>,
+    setting_data: web::Json<MailSettingData>,
+    notification_repo: web::Data<NotificationRepository>,
+    mail_outbox_repo: web::Data<MailOutboxRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(user_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let parsed_id = id_parse.expect("Should be valid.");
+    let result = mail_outbox_repo
+        .update_setting(parsed_id, setting_data.into_inner())
+        .await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_preferences(parsed_id, notification_repo, mail_outbox_repo).await
+}
@@ -0,0 +1,145 @@
+use actix_web::{get, http, web, HttpResponse};
+use serde::Deserialize;
+
+use crate::{
+    errors::{handle_database_error, parse_error},
+    handlers::common::{extract_path_tuple_ids, is_company_administrator, parse_month},
+    repositories::{
+        company::company_repo::CompanyRepository,
+        employment::employment_repo::EmploymentRepository,
+        timesheet::timesheet_repo::TimesheetRepository,
+        timesheet_amendment::timesheet_amendment_repo::TimesheetAmendmentRepository,
+    },
+    utils::payroll::{
+        accepted_wages::{collect_accepted_wages, EmployeeWages},
+        amendment_wages::collect_amendment_corrections,
+        exporter::payroll_exporter,
+        models::{Payroll, PayrollEntry, PayrollFormat},
+    },
+};
+
+#[derive(Deserialize, Debug)]
+pub struct PayrollQuery {
+    /// `YYYY-MM`
+    pub month: String,
+    #[serde(default)]
+    pub format: PayrollFormat,
+    #[serde(default)]
+    pub pink_paper_signed: bool,
+}
+
+fn payroll_entries(employees: Vec<EmployeeWages>) -> Vec<PayrollEntry> {
+    employees
+        .into_iter()
+        .filter(|employee| !employee.months.is_empty())
+        .map(|employee| PayrollEntry {
+            wage: employee.total(),
+            user_id: employee.user_id,
+            name: employee.name,
+            email: employee.email,
+            employment_type: employee.employment_type,
+            hourly_wage: employee.hourly_wage,
+        })
+        .collect()
+}
+
+/// The company's payroll of the month in the import format of an accounting
+/// software. Only accepted timesheets are included, the approved amendments
+/// settled in the month are added as corrections.
+#[get("/company/{company_id}/payroll/{user_id}")]
+pub async fn export_company_payroll(
+    path: web::Path<(String, String)>,
+    query: web::Query<PayrollQuery>,
+    company_repo: web::Data<CompanyRepository>,
+    employment_repo: web::Data<EmploymentRepository>,
+    timesheet_repo: web::Data<TimesheetRepository>,
+    amendment_repo: web::Data<TimesheetAmendmentRepository>,
+) -> HttpResponse {
+    let parsed_ids = extract_path_tuple_ids(path.into_inner());
+    if parsed_ids.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let month_bounds = parse_month(&query.month);
+    if month_bounds.is_none() {
+        return HttpResponse::BadRequest().body("The month has to be in the YYYY-MM format.");
+    }
+    let (month_start, month_end) = month_bounds.expect("Should be some.");
+
+    let (company_id, user_id) = parsed_ids.expect("Should be valid.");
+    if !is_company_administrator(company_id, user_id, &employment_repo).await {
+        return HttpResponse::Forbidden()
+            .body("Only a company administrator can export the company's payroll.");
+    }
+
+    let company_res = company_repo.read_one_extended(company_id).await;
+    if let Err(error) = company_res {
+        return handle_database_error(error);
+    }
+    let company = company_res.expect("Should be valid.");
+
+    let employees_res = collect_accepted_wages(
+        &timesheet_repo,
+        company_id,
+        month_start,
+        month_end,
+        query.pink_paper_signed,
+    )
+    .await;
+    if let Err(error) = employees_res {
+        return handle_database_error(error);
+    }
+
+    let corrections_res = collect_amendment_corrections(
+        &timesheet_repo,
+        &amendment_repo,
+        company_id,
+        month_start,
+        query.pink_paper_signed,
+    )
+    .await;
+    if let Err(error) = corrections_res {
+        return handle_database_error(error);
+    }
+
+    let employees = employees_res.expect("Should be valid.");
+    let corrections = corrections_res.expect("Should be valid.");
+    let errors: Vec<String> = employees
+        .iter()
+        .chain(corrections.iter())
+        .flat_map(|employee| {
+            employee
+                .errors
+                .iter()
+                .map(|error| format!("{} ({}), {}", employee.name, employee.email, error))
+        })
+        .collect();
+    if !errors.is_empty() {
+        return HttpResponse::UnprocessableEntity().body(format!(
+            "The wage of some accepted timesheets couldn't be computed:\n{}",
+            errors.join("\n")
+        ));
+    }
+
+    let payroll = Payroll {
+        company_name: company.name,
+        company_crn: company.crn,
+        month: month_start,
+        currency: "CZK".to_string(),
+        entries: payroll_entries(employees),
+        corrections: payroll_entries(corrections),
+    };
+
+    let exporter = payroll_exporter(query.format);
+    HttpResponse::Ok()
+        .content_type(exporter.content_type())
+        .insert_header((
+            http::header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"payroll-{}.{}\"",
+                query.month,
+                exporter.extension()
+            ),
+        ))
+        .body(exporter.export(&payroll))
+}
@@ -0,0 +1,352 @@
+use std::collections::BTreeMap;
+
+use actix_web::{get, http, web, HttpResponse};
+use askama::Template;
+use chrono::{Datelike, Months, NaiveDate, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    errors::{handle_database_error, parse_error},
+    handlers::common::is_administrator,
+    repositories::{
+        company::{company_repo::CompanyRepository, models::CompanyFilter},
+        report::{models::ReportFilter, report_repo::ReportRepository},
+        user::user_repo::UserRepository,
+    },
+    templates::report::{ChartBar, ReportsTemplate},
+    utils::export::{
+        export_stream::stream_export,
+        models::{ExportCell, ExportFormat},
+    },
+};
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportKind {
+    Hours,
+    Utilization,
+    Approval,
+    Tasks,
+}
+
+impl ReportKind {
+    fn columns(&self) -> &'static [&'static str] {
+        match self {
+            ReportKind::Hours => &[
+                "Month",
+                "Event",
+                "Company",
+                "Reported Hours",
+                "Accepted Hours",
+                "Staff",
+            ],
+            ReportKind::Utilization => &[
+                "Company",
+                "Employees",
+                "Active Staff",
+                "Utilization %",
+                "Returning Staff",
+                "Retention %",
+                "Events",
+                "Worked Hours",
+                "Hours per Staff",
+            ],
+            ReportKind::Approval => &[
+                "Company",
+                "Submitted",
+                "Reviewed",
+                "Pending",
+                "Accepted",
+                "Rejected",
+                "Average Latency (h)",
+                "Max Latency (h)",
+            ],
+            ReportKind::Tasks => &[
+                "Event",
+                "Start Date",
+                "End Date",
+                "Tasks",
+                "Done",
+                "Overdue",
+                "Completion %",
+            ],
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            ReportKind::Hours => "hours",
+            ReportKind::Utilization => "utilization",
+            ReportKind::Approval => "approval-latency",
+            ReportKind::Tasks => "task-completion",
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ReportQuery {
+    pub user_id: Uuid,
+    /// The past twelve months when missing.
+    pub date_from: Option<NaiveDate>,
+    pub date_to: Option<NaiveDate>,
+    /// Empty for every company.
+    #[serde(default)]
+    pub company_id: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ReportExportQuery {
+    pub user_id: Uuid,
+    pub report: ReportKind,
+    pub date_from: Option<NaiveDate>,
+    pub date_to: Option<NaiveDate>,
+    #[serde(default)]
+    pub company_id: String,
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// The error is the reason the filter is invalid.
+fn report_filter(
+    date_from: Option<NaiveDate>,
+    date_to: Option<NaiveDate>,
+    company_id: &str,
+) -> Result<ReportFilter, &'static str> {
+    let date_to = date_to.unwrap_or(Utc::now().date_naive());
+    let date_from = date_from.unwrap_or(
+        date_to
+            .with_day(1)
+            .and_then(|first_day| first_day.checked_sub_months(Months::new(11)))
+            .unwrap_or(date_to),
+    );
+    if date_from > date_to {
+        return Err("The date range is empty.");
+    }
+
+    let company_id = match company_id {
+        "" => None,
+        company_id => Some(Uuid::parse_str(company_id).map_err(|_| "Invalid company.")?),
+    };
+
+    Ok(ReportFilter {
+        date_from,
+        date_to,
+        company_id,
+    })
+}
+
+fn round(value: f64) -> ExportCell {
+    ExportCell::Number((value * 100.0).round() / 100.0)
+}
+
+/// Hours worked, staff utilization, timesheet approval latency and task
+/// completion across all companies, for application administrators.
+#[get("/admin/reports")]
+pub async fn get_reports(
+    query: web::Query<ReportQuery>,
+    user_repo: web::Data<UserRepository>,
+    company_repo: web::Data<CompanyRepository>,
+    report_repo: web::Data<ReportRepository>,
+) -> HttpResponse {
+    if !is_administrator(query.user_id, &user_repo).await {
+        return HttpResponse::Forbidden().body("Only an administrator can see the reports.");
+    }
+
+    let filter_res = report_filter(query.date_from, query.date_to, &query.company_id);
+    if let Err(reason) = filter_res {
+        return HttpResponse::BadRequest().body(reason);
+    }
+    let filter = filter_res.expect("Should be valid.");
+
+    let companies_res = company_repo
+        .read_all(CompanyFilter {
+            limit: None,
+            offset: None,
+            name: None,
+        })
+        .await;
+    if let Err(error) = companies_res {
+        return handle_database_error(error);
+    }
+
+    let hours_res = report_repo.read_monthly_hours(&filter).await;
+    if let Err(error) = hours_res {
+        return handle_database_error(error);
+    }
+    let monthly_hours = hours_res.expect("Should be valid.");
+
+    let utilization_res = report_repo.read_staff_utilization(&filter).await;
+    if let Err(error) = utilization_res {
+        return handle_database_error(error);
+    }
+
+    let approval_res = report_repo.read_approval_latency(&filter).await;
+    if let Err(error) = approval_res {
+        return handle_database_error(error);
+    }
+
+    let tasks_res = report_repo.read_task_completion(&filter).await;
+    if let Err(error) = tasks_res {
+        return handle_database_error(error);
+    }
+
+    let mut hours_per_month = BTreeMap::<NaiveDate, f64>::new();
+    for hours in monthly_hours.iter() {
+        *hours_per_month.entry(hours.month).or_default() += hours.reported_hours;
+    }
+
+    let template = ReportsTemplate {
+        user_id: query.user_id,
+        date_from: filter.date_from,
+        date_to: filter.date_to,
+        company_id: filter
+            .company_id
+            .map(|company_id| company_id.to_string())
+            .unwrap_or_default(),
+        companies: companies_res.expect("Should be valid."),
+        hours_chart: ChartBar::scaled(
+            hours_per_month
+                .into_iter()
+                .map(|(month, hours)| (month.format("%m/%Y").to_string(), hours))
+                .collect(),
+        ),
+        reported_hours: monthly_hours.iter().map(|hours| hours.reported_hours).sum(),
+        accepted_hours: monthly_hours.iter().map(|hours| hours.accepted_hours).sum(),
+        monthly_hours,
+        utilization: utilization_res.expect("Should be valid."),
+        approval: approval_res.expect("Should be valid."),
+        tasks: tasks_res.expect("Should be valid."),
+    };
+
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError()
+            .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be valid now."))
+}
+
+#[get("/admin/reports/export")]
+pub async fn export_report(
+    query: web::Query<ReportExportQuery>,
+    user_repo: web::Data<UserRepository>,
+    report_repo: web::Data<ReportRepository>,
+) -> HttpResponse {
+    if !is_administrator(query.user_id, &user_repo).await {
+        return HttpResponse::Forbidden().body("Only an administrator can export the reports.");
+    }
+
+    let filter_res = report_filter(query.date_from, query.date_to, &query.company_id);
+    if let Err(reason) = filter_res {
+        return HttpResponse::BadRequest().body(reason);
+    }
+    let filter = filter_res.expect("Should be valid.");
+
+    let report = query.report;
+    let file_name = format!(
+        "{}-{}-{}",
+        report.name(),
+        filter.date_from.format("%Y%m%d"),
+        filter.date_to.format("%Y%m%d")
+    );
+    let report_repo = report_repo.into_inner();
+    let stream = stream_export(
+        query.format,
+        report.name(),
+        report.columns(),
+        move |mut sink| async move {
+            let rows: Vec<Vec<ExportCell>> = match report {
+                ReportKind::Hours => report_repo
+                    .read_monthly_hours(&filter)
+                    .await?
+                    .into_iter()
+                    .map(|hours| {
+                        vec![
+                            hours.month.format("%Y-%m").to_string().into(),
+                            hours.event_name.into(),
+                            hours.company_name.into(),
+                            round(hours.reported_hours),
+                            round(hours.accepted_hours),
+                            ExportCell::Number(hours.staff_count as f64),
+                        ]
+                    })
+                    .collect(),
+                ReportKind::Utilization => report_repo
+                    .read_staff_utilization(&filter)
+                    .await?
+                    .into_iter()
+                    .map(|utilization| {
+                        vec![
+                            utilization.company_name.clone().into(),
+                            ExportCell::Number(utilization.employees as f64),
+                            ExportCell::Number(utilization.active_staff as f64),
+                            round(utilization.utilization_pct()),
+                            ExportCell::Number(utilization.returning_staff as f64),
+                            round(utilization.retention_pct()),
+                            ExportCell::Number(utilization.events as f64),
+                            round(utilization.worked_hours),
+                            round(utilization.hours_per_staff()),
+                        ]
+                    })
+                    .collect(),
+                ReportKind::Approval => report_repo
+                    .read_approval_latency(&filter)
+                    .await?
+                    .into_iter()
+                    .map(|latency| {
+                        vec![
+                            latency.company_name.into(),
+                            ExportCell::Number(latency.submitted as f64),
+                            ExportCell::Number(latency.reviewed as f64),
+                            ExportCell::Number(latency.pending as f64),
+                            ExportCell::Number(latency.accepted as f64),
+                            ExportCell::Number(latency.rejected as f64),
+                            latency
+                                .average_latency_hours
+                                .map_or(ExportCell::Empty, round),
+                            latency.max_latency_hours.map_or(ExportCell::Empty, round),
+                        ]
+                    })
+                    .collect(),
+                ReportKind::Tasks => report_repo
+                    .read_task_completion(&filter)
+                    .await?
+                    .into_iter()
+                    .map(|completion| {
+                        vec![
+                            completion.event_name.clone().into(),
+                            completion.start_date.into(),
+                            completion.end_date.into(),
+                            ExportCell::Number(completion.tasks as f64),
+                            ExportCell::Number(completion.done_tasks as f64),
+                            ExportCell::Number(completion.overdue_tasks as f64),
+                            round(completion.completion_pct()),
+                        ]
+                    })
+                    .collect(),
+            };
+
+            for row in rows {
+                sink.push(row).await?;
+            }
+
+            Ok(sink)
+        },
+    );
+
+    HttpResponse::Ok()
+        .content_type(query.format.content_type())
+        .insert_header((
+            http::header::CONTENT_DISPOSITION,
+            format!(
+                "attachment; filename=\"{}.{}\"",
+                file_name,
+                query.format.extension()
+            ),
+        ))
+        .streaming(stream)
+}
@@ -0,0 +1,256 @@
+use std::{collections::HashMap, str::FromStr};
+
+use actix_web::{get, http, post, web, HttpResponse};
+use askama::Template;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    errors::{handle_database_error, parse_error},
+    models::{ApprovalStatus, TimesheetTransition},
+    repositories::timesheet::{
+        models::{ReviewQueueFilter, TimesheetTransitionData},
+        timesheet_repo::TimesheetRepository,
+    },
+    templates::timesheet::ReviewQueueTemplate,
+};
+
+/// How many sheets can be decided at once.
+const BULK_REVIEW_LIMIT: usize = 200;
+
+fn default_status() -> String {
+    "pending".to_string()
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ReviewQueueQuery {
+    /// Empty for every event.
+    #[serde(default)]
+    pub event_id: String,
+    /// `YYYY-MM`, empty for every month.
+    #[serde(default)]
+    pub month: String,
+    /// The submitted sheets when missing, `all` for every status.
+    #[serde(default = "default_status")]
+    pub status: String,
+    /// Empty for any number of hours.
+    #[serde(default)]
+    pub min_hours: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BulkReviewData {
+    pub timesheet_ids: Vec<Uuid>,
+    pub transition: TimesheetTransition,
+    pub note: Option<String>,
+    /// The queue is rendered again with the same filter.
+    #[serde(flatten)]
+    pub query: ReviewQueueQuery,
+}
+
+/// The error is the reason the filter is invalid.
+fn review_queue_filter(query: &ReviewQueueQuery) -> Result<ReviewQueueFilter, &'static str> {
+    let event_id = match query.event_id.as_str() {
+        "" => None,
+        event_id => Some(Uuid::parse_str(event_id).map_err(|_| "Invalid event.")?),
+    };
+
+    let month = match query.month.as_str() {
+        "" => None,
+        month => Some(
+            NaiveDate::parse_from_str(&format!("{}-01", month), "%Y-%m-%d")
+                .map_err(|_| "Invalid month.")?,
+        ),
+    };
+
+    let status = match query.status.as_str() {
+        "all" => None,
+        "pending" => Some(ApprovalStatus::Pending),
+        "accepted" => Some(ApprovalStatus::Accepted),
+        "rejected" => Some(ApprovalStatus::Rejected),
+        "not_requested" => Some(ApprovalStatus::NotRequested),
+        _ => return Err("Invalid status."),
+    };
+
+    let min_hours = match query.min_hours.trim() {
+        "" => None,
+        min_hours => Some(
+            min_hours
+                .parse::<f32>()
+                .ok()
+                .filter(|hours| hours.is_finite() && *hours >= 0.0)
+                .ok_or("The hours have to be a non-negative number.")?,
+        ),
+    };
+
+    Ok(ReviewQueueFilter {
+        event_id,
+        month,
+        status,
+        min_hours,
+    })
+}
+
+async fn render_review_queue(
+    user_id: Uuid,
+    query: ReviewQueueQuery,
+    filter: &ReviewQueueFilter,
+    timesheet_repo: &TimesheetRepository,
+) -> HttpResponse {
+    let entries_res = timesheet_repo.read_review_queue(user_id, filter).await;
+    if let Err(error) = entries_res {
+        return handle_database_error(error);
+    }
+
+    let events_res = timesheet_repo.read_review_queue_events(user_id).await;
+    if let Err(error) = events_res {
+        return handle_database_error(error);
+    }
+
+    let template = ReviewQueueTemplate {
+        user_id,
+        event_id: query.event_id,
+        month: query.month,
+        status: query.status,
+        min_hours: query.min_hours,
+        events: events_res.expect("Should be valid."),
+        entries: entries_res.expect("Should be valid."),
+    };
+
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError()
+            .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be valid now."))
+}
+
+/// The timesheets of every direct and delegated subordinate of the manager,
+/// across all the companies they manage in.
+#[get("/user/{user_id}/sheets-review-queue")]
+pub async fn get_review_queue(
+    user_id: web::Path<String>,
+    query: web::Query<ReviewQueueQuery>,
+    timesheet_repo: web::Data<TimesheetRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(user_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let query = query.into_inner();
+    let filter_res = review_queue_filter(&query);
+    if let Err(reason) = filter_res {
+        return HttpResponse::BadRequest().body(reason);
+    }
+
+    render_review_queue(
+        id_parse.expect("Should be valid."),
+        query,
+        &filter_res.expect("Should be valid."),
+        &timesheet_repo,
+    )
+    .await
+}
+
+/// Approves or rejects all the selected sheets with the same note, either
+/// every sheet is decided or none is.
+#[post("/user/{user_id}/sheets-review-queue")]
+pub async fn review_queued_timesheets(
+    user_id: web::Path<String>,
+    data: web::Json<BulkReviewData>,
+    timesheet_repo: web::Data<TimesheetRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(user_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+    let user_id = id_parse.expect("Should be valid.");
+
+    let data = data.into_inner();
+    let filter_res = review_queue_filter(&data.query);
+    if let Err(reason) = filter_res {
+        return HttpResponse::BadRequest().body(reason);
+    }
+
+    if !matches!(
+        data.transition,
+        TimesheetTransition::Approve | TimesheetTransition::Reject
+    ) {
+        return HttpResponse::BadRequest().body("The sheets can only be approved or rejected.");
+    }
+
+    let mut timesheet_ids = data.timesheet_ids;
+    timesheet_ids.sort();
+    timesheet_ids.dedup();
+    if timesheet_ids.is_empty() {
+        return HttpResponse::BadRequest().body("Please select the sheets to review.");
+    }
+    if timesheet_ids.len() > BULK_REVIEW_LIMIT {
+        return HttpResponse::BadRequest().body(format!(
+            "At most {} sheets can be reviewed at once.",
+            BULK_REVIEW_LIMIT
+        ));
+    }
+
+    let note = data
+        .note
+        .map(|note| note.trim().to_string())
+        .filter(|note| !note.is_empty());
+    if note.as_ref().is_some_and(|note| note.chars().count() > 255) {
+        return HttpResponse::BadRequest().body("The note can be at most 255 characters long.");
+    }
+    if data.transition == TimesheetTransition::Reject && note.is_none() {
+        return HttpResponse::BadRequest().body("Please state why the sheets are rejected.");
+    }
+
+    let queue_res = timesheet_repo
+        .read_review_queue(user_id, &ReviewQueueFilter::default())
+        .await;
+    if let Err(error) = queue_res {
+        return handle_database_error(error);
+    }
+    let statuses: HashMap<Uuid, ApprovalStatus> = queue_res
+        .expect("Should be valid.")
+        .into_iter()
+        .map(|entry| (entry.id, entry.approval_status))
+        .collect();
+
+    if timesheet_ids.iter().any(|id| !statuses.contains_key(id)) {
+        return HttpResponse::Forbidden()
+            .body("Only the manager of the employees can review their sheets.");
+    }
+    if timesheet_ids
+        .iter()
+        .any(|id| statuses[id] != ApprovalStatus::Pending)
+    {
+        return HttpResponse::BadRequest()
+            .body("Only the submitted sheets can be approved or rejected.");
+    }
+
+    let result = timesheet_repo
+        .transition_all(
+            &timesheet_ids,
+            TimesheetTransitionData {
+                actor_id: user_id,
+                transition: data.transition,
+                note,
+            },
+        )
+        .await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_review_queue(
+        user_id,
+        data.query,
+        &filter_res.expect("Should be valid."),
+        &timesheet_repo,
+    )
+    .await
+}
@@ -0,0 +1,203 @@
+use std::str::FromStr;
+
+use actix_web::{delete, get, http, put, web, HttpResponse};
+use askama::Template;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    errors::{handle_database_error, parse_error},
+    handlers::common::{extract_path_tuple_ids, is_company_administrator},
+    models::OverduePolicy,
+    repositories::{
+        employment::employment_repo::EmploymentRepository,
+        submission_policy::{
+            models::SubmissionPolicyData, submission_policy_repo::SubmissionPolicyRepository,
+        },
+    },
+    templates::{company::SubmissionPolicyTemplate, timesheet::OverdueTimesheetsTemplate},
+};
+
+/// The fields are kept as they were typed into the form.
+#[derive(Deserialize, Debug)]
+pub struct SubmissionPolicyUpdateData {
+    pub deadline_day: String,
+    /// Comma separated, e.g. `7, 3, 1`.
+    #[serde(default)]
+    pub reminder_days: String,
+    pub overdue_policy: OverduePolicy,
+}
+
+/// The error is the reason the policy is invalid.
+fn submission_policy_data(
+    data: &SubmissionPolicyUpdateData,
+) -> Result<SubmissionPolicyData, &'static str> {
+    let deadline_day = data
+        .deadline_day
+        .trim()
+        .parse::<i32>()
+        .ok()
+        .filter(|day| (1..=28).contains(day))
+        .ok_or("The deadline has to be a day between 1 and 28.")?;
+
+    let mut reminder_days = data
+        .reminder_days
+        .split(',')
+        .map(str::trim)
+        .filter(|days| !days.is_empty())
+        .map(|days| {
+            days.parse::<i32>()
+                .ok()
+                .filter(|days| (0..=28).contains(days))
+                .ok_or("The reminders have to be between 0 and 28 days before the deadline.")
+        })
+        .collect::<Result<Vec<i32>, &'static str>>()?;
+    reminder_days.sort_by(|a, b| b.cmp(a));
+    reminder_days.dedup();
+
+    Ok(SubmissionPolicyData {
+        deadline_day,
+        reminder_days,
+        overdue_policy: data.overdue_policy.clone(),
+    })
+}
+
+async fn render_submission_policy(
+    company_id: Uuid,
+    user_id: Uuid,
+    submission_policy_repo: &SubmissionPolicyRepository,
+) -> HttpResponse {
+    let policy_res = submission_policy_repo.read_one(company_id).await;
+    if let Err(error) = policy_res {
+        return handle_database_error(error);
+    }
+
+    let template = SubmissionPolicyTemplate {
+        company_id,
+        user_id,
+        policy: policy_res.expect("Should be valid."),
+    };
+
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError()
+            .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be valid now."))
+}
+
+#[get("/company/{company_id}/submission-policy/{user_id}")]
+pub async fn get_submission_policy(
+    path: web::Path<(String, String)>,
+    employment_repo: web::Data<EmploymentRepository>,
+    submission_policy_repo: web::Data<SubmissionPolicyRepository>,
+) -> HttpResponse {
+    let parsed_ids = extract_path_tuple_ids(path.into_inner());
+    if parsed_ids.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let (company_id, user_id) = parsed_ids.expect("Should be valid.");
+    if !is_company_administrator(company_id, user_id, &employment_repo).await {
+        return HttpResponse::Forbidden()
+            .body("Only a company administrator can see the submission deadline.");
+    }
+
+    render_submission_policy(company_id, user_id, &submission_policy_repo).await
+}
+
+#[put("/company/{company_id}/submission-policy/{user_id}")]
+pub async fn update_submission_policy(
+    path: web::Path<(String, String)>,
+    data: web::Json<SubmissionPolicyUpdateData>,
+    employment_repo: web::Data<EmploymentRepository>,
+    submission_policy_repo: web::Data<SubmissionPolicyRepository>,
+) -> HttpResponse {
+    let parsed_ids = extract_path_tuple_ids(path.into_inner());
+    if parsed_ids.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let (company_id, user_id) = parsed_ids.expect("Should be valid.");
+    if !is_company_administrator(company_id, user_id, &employment_repo).await {
+        return HttpResponse::Forbidden()
+            .body("Only a company administrator can set the submission deadline.");
+    }
+
+    let data_res = submission_policy_data(&data.into_inner());
+    if let Err(reason) = data_res {
+        return HttpResponse::BadRequest().body(reason);
+    }
+
+    let result = submission_policy_repo
+        .upsert(company_id, data_res.expect("Should be valid."))
+        .await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_submission_policy(company_id, user_id, &submission_policy_repo).await
+}
+
+/// The timesheets of the company have no deadline afterwards.
+#[delete("/company/{company_id}/submission-policy/{user_id}")]
+pub async fn delete_submission_policy(
+    path: web::Path<(String, String)>,
+    employment_repo: web::Data<EmploymentRepository>,
+    submission_policy_repo: web::Data<SubmissionPolicyRepository>,
+) -> HttpResponse {
+    let parsed_ids = extract_path_tuple_ids(path.into_inner());
+    if parsed_ids.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let (company_id, user_id) = parsed_ids.expect("Should be valid.");
+    if !is_company_administrator(company_id, user_id, &employment_repo).await {
+        return HttpResponse::Forbidden()
+            .body("Only a company administrator can remove the submission deadline.");
+    }
+
+    let result = submission_policy_repo.delete(company_id).await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_submission_policy(company_id, user_id, &submission_policy_repo).await
+}
+
+/// The sheets of the manager's direct and delegated subordinates which missed
+/// their deadline and still aren't accepted.
+#[get("/user/{user_id}/sheets-overdue")]
+pub async fn get_overdue_timesheets(
+    user_id: web::Path<String>,
+    submission_policy_repo: web::Data<SubmissionPolicyRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(user_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let timesheets_res = submission_policy_repo
+        .read_overdue(id_parse.expect("Should be valid."))
+        .await;
+    if let Err(error) = timesheets_res {
+        return handle_database_error(error);
+    }
+
+    let template = OverdueTimesheetsTemplate {
+        timesheets: timesheets_res.expect("Should be valid."),
+    };
+
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError()
+            .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be valid now."))
+}
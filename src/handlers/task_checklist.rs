@@ -0,0 +1,262 @@
+use std::str::FromStr;
+
+use actix_web::{delete, get, http, patch, post, web, HttpResponse};
+use askama::Template;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    errors::{handle_database_error, parse_error},
+    handlers::common::{check_event_tasks_editable, check_task_editable, extract_path_tuple_ids},
+    models::{AcceptanceStatus, EventRole},
+    repositories::{
+        event::event_repo::EventRepository,
+        event_staff::{event_staff_repo::StaffRepository, models::StaffLite},
+        task::task_repo::TaskRepository,
+        task_checklist::{
+            models::{ChecklistItemData, NewChecklistItem},
+            task_checklist_repo::TaskChecklistRepository,
+        },
+    },
+    templates::task::ChecklistTemplate,
+};
+
+#[derive(Deserialize)]
+pub struct NewChecklistItemData {
+    editor_id: Uuid, // staff table ID
+    content: String,
+}
+
+#[derive(Deserialize)]
+pub struct ChecklistItemUpdateData {
+    editor_id: Uuid, // staff table ID
+    content: Option<String>,
+    is_finished: Option<bool>,
+}
+
+fn is_content_valid(content: &str) -> bool {
+    !content.trim().is_empty() && content.len() <= 255
+}
+
+async fn render_checklist(
+    requester: StaffLite,
+    task_id: Uuid,
+    checklist_repo: web::Data<TaskChecklistRepository>,
+) -> HttpResponse {
+    let result = checklist_repo.read_all_for_task(task_id).await;
+    if result.is_err() {
+        return handle_database_error(result.expect_err("Should be an error."));
+    }
+
+    let items = result.expect("Should be valid.");
+    let finished_count = items
+        .iter()
+        .filter(|item| item.finished_at.is_some())
+        .count();
+    let template = ChecklistTemplate {
+        requester,
+        task_id,
+        items,
+        finished_count,
+    };
+
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError()
+            .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be valid."))
+}
+
+#[get("/task/{task_id}/checklist/{staff_id}")]
+pub async fn open_task_checklist(
+    path: web::Path<(String, String)>,
+    staff_repo: web::Data<StaffRepository>,
+    task_repo: web::Data<TaskRepository>,
+    checklist_repo: web::Data<TaskChecklistRepository>,
+) -> HttpResponse {
+    let parsed_ids = extract_path_tuple_ids(path.into_inner());
+    if parsed_ids.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let (task_id, staff_id) = parsed_ids.unwrap();
+
+    let staff_res = staff_repo.read_one(staff_id).await;
+    if staff_res.is_err() {
+        return handle_database_error(staff_res.expect_err("Should be an error."));
+    }
+    let staff = staff_res.expect("Should be valid.");
+
+    let task_res = task_repo.read_one(task_id).await;
+    if task_res.is_err() {
+        return handle_database_error(task_res.expect_err("Should be an error."));
+    }
+
+    if staff.event_id != task_res.expect("Should be valid.").event_id {
+        return HttpResponse::Forbidden().body(parse_error(http::StatusCode::FORBIDDEN));
+    }
+
+    render_checklist(staff.into(), task_id, checklist_repo).await
+}
+
+#[post("/task/{task_id}/checklist")]
+pub async fn create_checklist_item(
+    task_id: web::Path<String>,
+    new_item: web::Json<NewChecklistItemData>,
+    event_repo: web::Data<EventRepository>,
+    staff_repo: web::Data<StaffRepository>,
+    task_repo: web::Data<TaskRepository>,
+    checklist_repo: web::Data<TaskChecklistRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(task_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+    let parsed_id = id_parse.expect("Should be valid.");
+
+    if !is_content_valid(&new_item.content) {
+        return HttpResponse::BadRequest()
+            .body("The checklist item must have between 1 and 255 characters.".to_string());
+    }
+
+    let staff_res = staff_repo.read_one(new_item.editor_id).await;
+    if staff_res.is_err() {
+        return handle_database_error(staff_res.expect_err("Should be an error."));
+    }
+    let staff = staff_res.expect("Should be valid.");
+
+    let task_res = task_repo.read_one(parsed_id).await;
+    if task_res.is_err() {
+        return handle_database_error(task_res.expect_err("Should be an error."));
+    }
+
+    let event_id = task_res.expect("Should be valid.").event_id;
+    if staff.role != EventRole::Organizer || staff.event_id != event_id {
+        return HttpResponse::Forbidden().body(parse_error(http::StatusCode::FORBIDDEN));
+    }
+
+    if let Err(response) = check_event_tasks_editable(event_id, &event_repo).await {
+        return response;
+    }
+
+    let data = NewChecklistItem {
+        task_id: parsed_id,
+        content: new_item.content.trim().to_string(),
+    };
+    let result = checklist_repo.create(data).await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_checklist(staff.into(), parsed_id, checklist_repo).await
+}
+
+#[patch("/task/checklist/{item_id}")]
+pub async fn update_checklist_item(
+    item_id: web::Path<String>,
+    item_data: web::Json<ChecklistItemUpdateData>,
+    event_repo: web::Data<EventRepository>,
+    staff_repo: web::Data<StaffRepository>,
+    task_repo: web::Data<TaskRepository>,
+    checklist_repo: web::Data<TaskChecklistRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(item_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+    let parsed_id = id_parse.expect("Should be valid.");
+
+    if item_data.content.is_none() && item_data.is_finished.is_none() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    if item_data
+        .content
+        .as_ref()
+        .is_some_and(|content| !is_content_valid(content))
+    {
+        return HttpResponse::BadRequest()
+            .body("The checklist item must have between 1 and 255 characters.".to_string());
+    }
+
+    let staff_res = staff_repo.read_one(item_data.editor_id).await;
+    if staff_res.is_err() {
+        return handle_database_error(staff_res.expect_err("Should be an error."));
+    }
+    let staff = staff_res.expect("Should be valid.");
+
+    let item_res = checklist_repo.read_one(parsed_id).await;
+    if item_res.is_err() {
+        return handle_database_error(item_res.expect_err("Should be an error."));
+    }
+    let item = item_res.expect("Should be valid.");
+
+    let task_res = task_repo.read_one(item.task_id).await;
+    if task_res.is_err() {
+        return handle_database_error(task_res.expect_err("Should be an error."));
+    }
+
+    // Any accepted staff member of the event may tick items off,
+    // rewording them is left to the organizers.
+    let event_id = task_res.expect("Should be valid.").event_id;
+    if staff.event_id != event_id
+        || staff.status != AcceptanceStatus::Accepted
+        || (item_data.content.is_some() && staff.role != EventRole::Organizer)
+    {
+        return HttpResponse::Forbidden().body(parse_error(http::StatusCode::FORBIDDEN));
+    }
+
+    if let Err(response) = check_event_tasks_editable(event_id, &event_repo).await {
+        return response;
+    }
+
+    let data = ChecklistItemData {
+        content: item_data
+            .content
+            .as_ref()
+            .map(|content| content.trim().to_string()),
+        is_finished: item_data.is_finished,
+    };
+    let result = checklist_repo.update(parsed_id, data).await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_checklist(staff.into(), item.task_id, checklist_repo).await
+}
+
+#[delete("/task/checklist/{item_id}")]
+pub async fn delete_checklist_item(
+    item_id: web::Path<String>,
+    event_repo: web::Data<EventRepository>,
+    task_repo: web::Data<TaskRepository>,
+    checklist_repo: web::Data<TaskChecklistRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(item_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let parsed_id = id_parse.expect("Should be valid.");
+    let item_res = checklist_repo.read_one(parsed_id).await;
+    if item_res.is_err() {
+        return handle_database_error(item_res.expect_err("Should be an error."));
+    }
+
+    let task_id = item_res.expect("Should be valid.").task_id;
+    if let Err(response) = check_task_editable(task_id, &event_repo, &task_repo).await {
+        return response;
+    }
+
+    let result = checklist_repo.delete(parsed_id).await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    // Ok because of HTMX
+    HttpResponse::Ok().finish()
+}
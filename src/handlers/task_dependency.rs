@@ -0,0 +1,209 @@
+use std::str::FromStr;
+
+use actix_web::{delete, get, http, post, web, HttpResponse};
+use askama::Template;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    errors::{handle_database_error, parse_error},
+    handlers::common::{check_event_tasks_editable, check_task_editable, extract_path_tuple_ids},
+    models::EventRole,
+    repositories::{
+        event::event_repo::EventRepository,
+        event_staff::{event_staff_repo::StaffRepository, models::StaffLite},
+        task::task_repo::TaskRepository,
+        task_dependency::{
+            models::NewTaskDependency, task_dependency_repo::TaskDependencyRepository,
+        },
+    },
+    templates::task::{EventTask, TaskDependenciesTemplate},
+};
+
+#[derive(Deserialize)]
+pub struct NewTaskDependencyData {
+    editor_id: Uuid, // staff table ID
+    blocker_id: Uuid,
+}
+
+async fn render_dependencies(
+    requester: StaffLite,
+    task_id: Uuid,
+    task_repo: web::Data<TaskRepository>,
+    dependency_repo: web::Data<TaskDependencyRepository>,
+) -> HttpResponse {
+    let blockers_res = dependency_repo.read_blockers(task_id).await;
+    if blockers_res.is_err() {
+        return handle_database_error(blockers_res.expect_err("Should be an error."));
+    }
+    let blockers = blockers_res.expect("Should be valid.");
+
+    let blocked_res = dependency_repo.read_blocked(task_id).await;
+    if blocked_res.is_err() {
+        return handle_database_error(blocked_res.expect_err("Should be an error."));
+    }
+
+    let tasks_res = task_repo.read_board_for_event(requester.event_id).await;
+    if tasks_res.is_err() {
+        return handle_database_error(tasks_res.expect_err("Should be an error."));
+    }
+    let candidates: Vec<EventTask> = tasks_res
+        .expect("Should be valid.")
+        .into_iter()
+        .filter(|task| {
+            task.task_id != task_id
+                && !blockers
+                    .iter()
+                    .any(|blocker| blocker.task_id == task.task_id)
+        })
+        .map(|task| task.into())
+        .collect();
+
+    let template = TaskDependenciesTemplate {
+        requester,
+        task_id,
+        blockers,
+        blocked: blocked_res.expect("Should be valid."),
+        candidates,
+    };
+
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError()
+            .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be valid."))
+}
+
+#[get("/task/{task_id}/dependencies/{staff_id}")]
+pub async fn open_task_dependencies(
+    path: web::Path<(String, String)>,
+    staff_repo: web::Data<StaffRepository>,
+    task_repo: web::Data<TaskRepository>,
+    dependency_repo: web::Data<TaskDependencyRepository>,
+) -> HttpResponse {
+    let parsed_ids = extract_path_tuple_ids(path.into_inner());
+    if parsed_ids.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let (task_id, staff_id) = parsed_ids.unwrap();
+
+    let staff_res = staff_repo.read_one(staff_id).await;
+    if staff_res.is_err() {
+        return handle_database_error(staff_res.expect_err("Should be an error."));
+    }
+    let staff = staff_res.expect("Should be valid.");
+
+    let task_res = task_repo.read_one(task_id).await;
+    if task_res.is_err() {
+        return handle_database_error(task_res.expect_err("Should be an error."));
+    }
+
+    if staff.event_id != task_res.expect("Should be valid.").event_id {
+        return HttpResponse::Forbidden().body(parse_error(http::StatusCode::FORBIDDEN));
+    }
+
+    render_dependencies(staff.into(), task_id, task_repo, dependency_repo).await
+}
+
+#[post("/task/{task_id}/dependency")]
+pub async fn create_task_dependency(
+    task_id: web::Path<String>,
+    new_dependency: web::Json<NewTaskDependencyData>,
+    event_repo: web::Data<EventRepository>,
+    staff_repo: web::Data<StaffRepository>,
+    task_repo: web::Data<TaskRepository>,
+    dependency_repo: web::Data<TaskDependencyRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(task_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+    let parsed_id = id_parse.expect("Should be valid.");
+
+    if parsed_id == new_dependency.blocker_id {
+        return HttpResponse::BadRequest().body("A task can't block itself.".to_string());
+    }
+
+    let staff_res = staff_repo.read_one(new_dependency.editor_id).await;
+    if staff_res.is_err() {
+        return handle_database_error(staff_res.expect_err("Should be an error."));
+    }
+    let staff = staff_res.expect("Should be valid.");
+
+    let task_res = task_repo.read_one(parsed_id).await;
+    if task_res.is_err() {
+        return handle_database_error(task_res.expect_err("Should be an error."));
+    }
+
+    let blocker_res = task_repo.read_one(new_dependency.blocker_id).await;
+    if blocker_res.is_err() {
+        return handle_database_error(blocker_res.expect_err("Should be an error."));
+    }
+
+    let event_id = task_res.expect("Should be valid.").event_id;
+    if staff.role != EventRole::Organizer || staff.event_id != event_id {
+        return HttpResponse::Forbidden().body(parse_error(http::StatusCode::FORBIDDEN));
+    }
+
+    if blocker_res.expect("Should be valid.").event_id != event_id {
+        return HttpResponse::BadRequest()
+            .body("Only tasks of the same event can depend on each other.".to_string());
+    }
+
+    if let Err(response) = check_event_tasks_editable(event_id, &event_repo).await {
+        return response;
+    }
+
+    let cycle_res = dependency_repo
+        .creates_cycle(parsed_id, new_dependency.blocker_id)
+        .await;
+    if cycle_res.is_err() {
+        return handle_database_error(cycle_res.expect_err("Should be an error."));
+    }
+    if cycle_res.expect("Should be valid.") {
+        return HttpResponse::BadRequest()
+            .body("This dependency would create a cycle between tasks.".to_string());
+    }
+
+    let data = NewTaskDependency {
+        task_id: parsed_id,
+        blocker_id: new_dependency.blocker_id,
+    };
+    let result = dependency_repo.create(data).await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_dependencies(staff.into(), parsed_id, task_repo, dependency_repo).await
+}
+
+#[delete("/task/{task_id}/dependency/{blocker_id}")]
+pub async fn delete_task_dependency(
+    path: web::Path<(String, String)>,
+    event_repo: web::Data<EventRepository>,
+    task_repo: web::Data<TaskRepository>,
+    dependency_repo: web::Data<TaskDependencyRepository>,
+) -> HttpResponse {
+    let parsed_ids = extract_path_tuple_ids(path.into_inner());
+    if parsed_ids.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let (task_id, blocker_id) = parsed_ids.unwrap();
+    if let Err(response) = check_task_editable(task_id, &event_repo, &task_repo).await {
+        return response;
+    }
+
+    let result = dependency_repo.delete(task_id, blocker_id).await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    // Ok because of HTMX
+    HttpResponse::Ok().finish()
+}
@@ -9,20 +9,22 @@ use uuid::Uuid;
 use crate::{
     errors::{handle_database_error, parse_error},
     handlers::common::extract_path_tuple_ids,
-    models::ApprovalStatus,
+    models::{ApprovalStatus, TimesheetTransition},
     repositories::{
         employment::employment_repo::EmploymentRepository,
         timesheet::{
             models::{
-                TimesheetCreateData, TimesheetReadAllData, TimesheetUpdateData,
-                TimesheetWithWorkdays, WorkdayUpdateData,
+                TimesheetCreateData, TimesheetReadAllData, TimesheetTransitionData,
+                TimesheetUpdateData, TimesheetWithEvent, TimesheetWithWorkdays, WorkdayFlagData,
+                WorkdayUpdateData,
             },
             timesheet_repo::TimesheetRepository,
         },
     },
     templates::timesheet::{
-        TimesheetCalculateTemplate, TimesheetReviewTemplate, TimesheetTemplate,
-        TimesheetsReviewTemplate, TimesheetsTemplate, WorkdayEditTemplate, WorkdayTemplate,
+        TimesheetCalculateTemplate, TimesheetPreviewTemplate, TimesheetReviewTemplate,
+        TimesheetTemplate, TimesheetsReviewTemplate, TimesheetsTemplate, WorkdayEditTemplate,
+        WorkdayTemplate,
     },
     utils::wage_calc::{calculate_wage::calculate_timesheet_wage, models::TimesheetWageDetailed},
 };
@@ -125,21 +127,38 @@ pub async fn get_timesheet(
     let result = timesheet_repo._read_one(parsed_id).await;
 
     if let Ok(full_timesheet) = result {
-        let template: TimesheetTemplate = full_timesheet.into();
-        let body = template.render();
-        if body.is_err() {
-            return HttpResponse::InternalServerError()
-                .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
-        }
-
-        return HttpResponse::Ok()
-            .content_type("text/html")
-            .body(body.expect("Should be valid now."));
+        return render_timesheet(full_timesheet, &timesheet_repo).await;
     }
 
     handle_database_error(result.expect_err("Should be error."))
 }
 
+/// Renders the timesheet of the employee along with the days flagged by
+/// their manager.
+async fn render_timesheet(
+    full_timesheet: TimesheetWithWorkdays,
+    timesheet_repo: &TimesheetRepository,
+) -> HttpResponse {
+    let review_res = timesheet_repo
+        .read_review(full_timesheet.timesheet.id)
+        .await;
+    if let Err(error) = review_res {
+        return handle_database_error(error);
+    }
+
+    let template =
+        TimesheetTemplate::from(full_timesheet).with_review(review_res.expect("Should be valid."));
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError()
+            .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be valid now."))
+}
+
 async fn get_calculated_wage(
     target_sheet: &TimesheetWithWorkdays,
     pink_paper_signed: bool,
@@ -263,10 +282,8 @@ pub async fn open_sheet_submit_page(
 }
 
 fn is_data_empty(data: TimesheetUpdateData) -> bool {
-    data.is_editable.is_none()
-        && data.status.is_none()
-        && (data.manager_note.is_none()
-            || (data.manager_note.is_some() && data.manager_note.unwrap().is_empty()))
+    data.manager_note.is_none()
+        || (data.manager_note.is_some() && data.manager_note.unwrap().is_empty())
 }
 
 #[patch("/timesheet/{timesheet_id}")]
@@ -285,7 +302,6 @@ pub async fn update_timesheet(
         return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
     }
 
-    let status_change = timesheet_data.status.clone();
     let parsed_id = id_parse.expect("Should be valid.");
     let result = timesheet_repo
         .update(parsed_id, timesheet_data.into_inner())
@@ -297,20 +313,6 @@ pub async fn update_timesheet(
 
     let timesheet = result.expect("Should be valid");
 
-    if status_change.is_some() && status_change.expect("Should be some.") == ApprovalStatus::Pending
-    {
-        let template: TimesheetTemplate = timesheet.into();
-        let body = template.render();
-        if body.is_err() {
-            return HttpResponse::InternalServerError()
-                .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
-        }
-
-        return HttpResponse::Ok()
-            .content_type("text/html")
-            .body(body.expect("Should be valid now."));
-    }
-
     get_review_sheets(
         timesheet.timesheet.user_id,
         timesheet.timesheet.company_id,
@@ -320,6 +322,260 @@ pub async fn update_timesheet(
     .await
 }
 
+#[derive(Deserialize, Debug)]
+pub struct TimesheetTransitionRequest {
+    pub user_id: Uuid,
+    pub transition: TimesheetTransition,
+    pub note: Option<String>,
+}
+
+/// Submits, withdraws, approves, rejects or reopens the timesheet. The first
+/// two are up to the employee, the rest to the manager of their employment.
+#[post("/timesheet/{timesheet_id}/transition")]
+pub async fn transition_timesheet(
+    timesheet_id: web::Path<String>,
+    transition_data: web::Json<TimesheetTransitionRequest>,
+    employment_repo: web::Data<EmploymentRepository>,
+    timesheet_repo: web::Data<TimesheetRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(timesheet_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let parsed_id = id_parse.expect("Should be valid.");
+    let data = transition_data.into_inner();
+    let note = data
+        .note
+        .map(|note| note.trim().to_string())
+        .filter(|note| !note.is_empty());
+    if note.as_ref().is_some_and(|note| note.chars().count() > 255) {
+        return HttpResponse::BadRequest().body("The note can't be longer than 255 characters.");
+    }
+
+    let sheet_res = timesheet_repo._read_one(parsed_id).await;
+    if let Err(error) = sheet_res {
+        return handle_database_error(error);
+    }
+    let sheet = sheet_res.expect("Should be valid.").timesheet;
+
+    if data.transition.is_made_by_manager() {
+        if !is_manager_of(data.user_id, &sheet, &employment_repo).await {
+            return HttpResponse::Forbidden()
+                .body("Only the manager of the employee can review the sheet.");
+        }
+    } else if sheet.user_id != data.user_id {
+        return HttpResponse::Forbidden().body("Only the employee can submit the sheet.");
+    }
+
+    if !data.transition.is_allowed_from(&sheet.approval_status) {
+        return HttpResponse::BadRequest().body(format!(
+            "A sheet that is {} can't be {}.",
+            sheet.approval_status.to_string().to_lowercase(),
+            data.transition.to_string().to_lowercase()
+        ));
+    }
+
+    let review_res = timesheet_repo.read_review(parsed_id).await;
+    if let Err(error) = review_res {
+        return handle_database_error(error);
+    }
+    let review = review_res.expect("Should be valid.");
+    match data.transition {
+        TimesheetTransition::Reject if note.is_none() && review.flags.is_empty() => {
+            return HttpResponse::BadRequest()
+                .body("Please state why the sheet is rejected or flag the days to correct.");
+        }
+        TimesheetTransition::Adjust if !review.flags.iter().any(|flag| flag.has_suggestion()) => {
+            return HttpResponse::BadRequest()
+                .body("Please suggest a correction of the flagged days first.");
+        }
+        TimesheetTransition::Acknowledge if !review.awaits_acknowledgement() => {
+            return HttpResponse::BadRequest().body("There are no adjustments to acknowledge.");
+        }
+        _ => {}
+    }
+
+    let result = timesheet_repo
+        .transition(
+            parsed_id,
+            TimesheetTransitionData {
+                actor_id: data.user_id,
+                transition: data.transition,
+                note,
+            },
+        )
+        .await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    if data.transition.is_made_by_manager() {
+        return get_review_sheets(
+            sheet.user_id,
+            sheet.company_id,
+            employment_repo,
+            timesheet_repo,
+        )
+        .await;
+    }
+
+    render_timesheet(result.expect("Should be valid."), &timesheet_repo).await
+}
+
+pub async fn is_manager_of(
+    user_id: Uuid,
+    sheet: &TimesheetWithEvent,
+    employment_repo: &EmploymentRepository,
+) -> bool {
+    employment_repo
+        .is_superior(user_id, sheet.user_id, sheet.company_id)
+        .await
+        .unwrap_or(false)
+}
+
+#[derive(Deserialize, Debug)]
+pub struct WorkdayFlagRequest {
+    pub user_id: Uuid,
+    pub reason: String,
+    pub suggested_hours: Option<f32>,
+    pub suggested_comment: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ReviewerQuery {
+    pub user_id: Uuid,
+}
+
+/// Checks that the user manages the employee of a submitted timesheet,
+/// returns the response to send otherwise.
+async fn check_reviewer(
+    timesheet_id: Uuid,
+    user_id: Uuid,
+    timesheet_repo: &TimesheetRepository,
+    employment_repo: &EmploymentRepository,
+) -> Option<HttpResponse> {
+    let sheet_res = timesheet_repo._read_one(timesheet_id).await;
+    if let Err(error) = sheet_res {
+        return Some(handle_database_error(error));
+    }
+    let sheet = sheet_res.expect("Should be valid.").timesheet;
+
+    if !is_manager_of(user_id, &sheet, employment_repo).await {
+        return Some(
+            HttpResponse::Forbidden()
+                .body("Only the manager of the employee can review the sheet."),
+        );
+    }
+    if sheet.approval_status != ApprovalStatus::Pending {
+        return Some(HttpResponse::BadRequest().body("Only a submitted sheet can be reviewed."));
+    }
+
+    None
+}
+
+/// Flags a workday of a submitted timesheet with the reason it needs
+/// a change and, optionally, the correction the manager suggests.
+#[post("/timesheet/{timesheet_id}/day/{date}/flag")]
+pub async fn flag_work_day(
+    path: web::Path<(String, String)>,
+    flag_data: web::Json<WorkdayFlagRequest>,
+    employment_repo: web::Data<EmploymentRepository>,
+    timesheet_repo: web::Data<TimesheetRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(path.0.as_str());
+    let date_parse = NaiveDate::parse_from_str(path.1.as_str(), "%Y-%m-%d");
+    if id_parse.is_err() || date_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let timesheet_id = id_parse.expect("Should be valid");
+    let date = date_parse.expect("Should be valid");
+    let data = flag_data.into_inner();
+    let reason = data.reason.trim().to_string();
+    let suggested_comment = data
+        .suggested_comment
+        .map(|comment| comment.trim().to_string())
+        .filter(|comment| !comment.is_empty());
+    if reason.is_empty() || reason.chars().count() > 255 {
+        return HttpResponse::BadRequest()
+            .body("The reason has to be between 1 and 255 characters long.");
+    }
+    if data
+        .suggested_hours
+        .is_some_and(|hours| !(0.0..=24.0).contains(&hours))
+    {
+        return HttpResponse::BadRequest().body("The hours have to be between 0 and 24.");
+    }
+    if suggested_comment
+        .as_ref()
+        .is_some_and(|comment| comment.chars().count() > 255)
+    {
+        return HttpResponse::BadRequest().body("The comment can't be longer than 255 characters.");
+    }
+
+    if let Some(response) = check_reviewer(
+        timesheet_id,
+        data.user_id,
+        &timesheet_repo,
+        &employment_repo,
+    )
+    .await
+    {
+        return response;
+    }
+
+    let result = timesheet_repo
+        .flag_workday(WorkdayFlagData {
+            timesheet_id,
+            date,
+            flagged_by: data.user_id,
+            reason,
+            suggested_hours: data.suggested_hours,
+            suggested_comment,
+        })
+        .await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_review_page(timesheet_id, &timesheet_repo).await
+}
+
+#[delete("/timesheet/{timesheet_id}/day/{date}/flag")]
+pub async fn remove_work_day_flag(
+    path: web::Path<(String, String)>,
+    query: web::Query<ReviewerQuery>,
+    employment_repo: web::Data<EmploymentRepository>,
+    timesheet_repo: web::Data<TimesheetRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(path.0.as_str());
+    let date_parse = NaiveDate::parse_from_str(path.1.as_str(), "%Y-%m-%d");
+    if id_parse.is_err() || date_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let timesheet_id = id_parse.expect("Should be valid");
+    let date = date_parse.expect("Should be valid");
+    if let Some(response) = check_reviewer(
+        timesheet_id,
+        query.user_id,
+        &timesheet_repo,
+        &employment_repo,
+    )
+    .await
+    {
+        return response;
+    }
+
+    let result = timesheet_repo.remove_workday_flag(timesheet_id, date).await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_review_page(timesheet_id, &timesheet_repo).await
+}
+
 /*
 * Reset every workday for a corresponding timesheet, as well as worked_hours and comments in the timesheet record.
 */
@@ -336,17 +592,7 @@ pub async fn reset_timesheet_data(
     let parsed_id = id_parse.expect("Should be valid.");
     let result = timesheet_repo.reset_timesheet(parsed_id).await;
     if let Ok(full_timesheet) = result {
-        let template: TimesheetTemplate = full_timesheet.into();
-
-        let body = template.render();
-        if body.is_err() {
-            return HttpResponse::InternalServerError()
-                .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
-        }
-
-        return HttpResponse::Ok()
-            .content_type("text/html")
-            .body(body.expect("Should be valid now."));
+        return render_timesheet(full_timesheet, &timesheet_repo).await;
     }
 
     handle_database_error(result.expect_err("Should be error."))
@@ -525,18 +771,84 @@ pub async fn open_timesheet_for_review(
         return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
     }
 
+    render_review_page(id_parse.expect("Should be valid."), &timesheet_repo).await
+}
+
+/// The workdays and the computed wage of the sheet, shown next to the review
+/// queue so the manager doesn't have to open every sheet.
+#[get("/timesheet/{timesheet_id}/review-preview")]
+pub async fn get_timesheet_review_preview(
+    timesheet_id: web::Path<String>,
+    timesheet_repo: web::Data<TimesheetRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(timesheet_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
     let parsed_id = id_parse.expect("Should be valid.");
+
     let result = timesheet_repo._read_one(parsed_id).await;
-    if let Ok(sheet) = result {
-        let template = TimesheetReviewTemplate {
-            sheet: sheet.into(),
-        };
-        let body = template.render();
-        if body.is_err() {
-            return HttpResponse::InternalServerError()
-                .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
-        }
-        return HttpResponse::Ok().body(body.expect("Should be okay"));
+    if let Err(error) = result {
+        return handle_database_error(error);
     }
-    handle_database_error(result.expect_err("Should be an error."))
+    let sheet = result.expect("Should be valid.");
+
+    let review_res = timesheet_repo.read_review(parsed_id).await;
+    if let Err(error) = review_res {
+        return handle_database_error(error);
+    }
+
+    let wage = get_calculated_wage(&sheet, false, timesheet_repo).await;
+    let template = TimesheetPreviewTemplate {
+        sheet: TimesheetTemplate::from(sheet).with_review(review_res.expect("Should be valid.")),
+        wage_error: wage.as_ref().err().cloned(),
+        wage: wage.ok(),
+    };
+
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError()
+            .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be valid now."))
+}
+
+/// Renders the timesheet as its employee's manager reviews it, with the
+/// flagged workdays and the history of its transitions.
+async fn render_review_page(
+    timesheet_id: Uuid,
+    timesheet_repo: &TimesheetRepository,
+) -> HttpResponse {
+    let result = timesheet_repo._read_one(timesheet_id).await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    let history_res = timesheet_repo.read_history(timesheet_id).await;
+    if let Err(error) = history_res {
+        return handle_database_error(error);
+    }
+
+    let review_res = timesheet_repo.read_review(timesheet_id).await;
+    if let Err(error) = review_res {
+        return handle_database_error(error);
+    }
+
+    let template = TimesheetReviewTemplate {
+        sheet: TimesheetTemplate::from(result.expect("Should be valid."))
+            .with_review(review_res.expect("Should be valid.")),
+        history: history_res.expect("Should be valid."),
+    };
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError()
+            .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be okay"))
 }
@@ -0,0 +1,346 @@
+use std::{collections::HashSet, str::FromStr};
+
+use actix_web::{get, http, post, web, HttpResponse};
+use askama::Template;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    errors::{handle_database_error, parse_error},
+    handlers::{common::extract_path_tuple_ids, timesheet::is_manager_of},
+    models::{AmendmentStatus, ApprovalStatus},
+    repositories::{
+        employment::employment_repo::EmploymentRepository,
+        timesheet::{
+            models::{TimesheetWithWorkdays, Workday},
+            timesheet_repo::TimesheetRepository,
+        },
+        timesheet_amendment::{
+            models::{AmendmentDayData, AmendmentDecisionData, NewTimesheetAmendment},
+            timesheet_amendment_repo::TimesheetAmendmentRepository,
+        },
+    },
+    templates::timesheet::{AmendmentView, AmendmentsTemplate},
+    utils::payroll::amendment_wages::amendment_wage_difference,
+};
+
+#[derive(Deserialize, Debug)]
+pub struct AmendmentsQuery {
+    pub user_id: Uuid,
+    #[serde(default)]
+    pub pink_paper_signed: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AmendmentProposal {
+    pub user_id: Uuid,
+    pub reason: String,
+    pub days: Vec<AmendmentDayData>,
+    #[serde(default)]
+    pub pink_paper_signed: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct AmendmentDecision {
+    pub user_id: Uuid,
+    pub approve: bool,
+    pub note: Option<String>,
+    #[serde(default)]
+    pub pink_paper_signed: bool,
+}
+
+fn validate_proposal(
+    proposal: &AmendmentProposal,
+    workdays: &[Workday],
+) -> Result<(), &'static str> {
+    let reason = proposal.reason.trim();
+    if reason.is_empty() || reason.chars().count() > 255 {
+        return Err("The reason has to be between 1 and 255 characters long.");
+    }
+    if proposal.days.is_empty() {
+        return Err("At least one workday has to be corrected.");
+    }
+
+    let mut dates = HashSet::new();
+    for day in proposal.days.iter() {
+        if !dates.insert(day.date) {
+            return Err("Every workday can be corrected only once.");
+        }
+        if !workdays.iter().any(|workday| workday.date == day.date) {
+            return Err("Only the workdays of the sheet can be corrected.");
+        }
+        if !(0.0..=24.0).contains(&day.total_hours) {
+            return Err("The hours have to be between 0 and 24.");
+        }
+        if day
+            .comment
+            .as_ref()
+            .is_some_and(|comment| comment.chars().count() > 255)
+        {
+            return Err("The comment can't be longer than 255 characters.");
+        }
+    }
+
+    Ok(())
+}
+
+fn access_denied() -> HttpResponse {
+    HttpResponse::Forbidden()
+        .body("Only the employee or their manager can see the amendments of the sheet.")
+}
+
+async fn render_amendments(
+    sheet: TimesheetWithWorkdays,
+    can_decide: bool,
+    pink_paper_signed: bool,
+    timesheet_repo: &TimesheetRepository,
+    amendment_repo: &TimesheetAmendmentRepository,
+) -> HttpResponse {
+    let amendments_res = amendment_repo
+        .read_all_for_timesheet(sheet.timesheet.id)
+        .await;
+    if let Err(error) = amendments_res {
+        return handle_database_error(error);
+    }
+    let amendments = amendments_res.expect("Should be valid.");
+
+    let mut views = vec![];
+    for amendment in amendments.iter() {
+        let mut view = AmendmentView {
+            amendment: amendment.amendment.clone(),
+            days: amendment.days.clone(),
+            difference: None,
+            difference_error: None,
+        };
+        if amendment.amendment.status != AmendmentStatus::Rejected {
+            let difference_res = amendment_wage_difference(
+                timesheet_repo,
+                amendment_repo,
+                amendment,
+                pink_paper_signed,
+            )
+            .await;
+            match difference_res {
+                Err(error) => return handle_database_error(error),
+                Ok(Ok(difference)) => view.difference = Some(difference),
+                Ok(Err(error)) => view.difference_error = Some(error),
+            }
+        }
+        views.push(view);
+    }
+
+    // The newest approved amendment of a day holds its paid values.
+    let mut workdays = sheet.workdays;
+    for workday in workdays.iter_mut() {
+        let latest = amendments
+            .iter()
+            .filter(|amendment| amendment.amendment.status == AmendmentStatus::Approved)
+            .filter_map(|amendment| {
+                amendment
+                    .days
+                    .iter()
+                    .find(|day| day.date == workday.date)
+                    .map(|day| (amendment.amendment.decided_at, day))
+            })
+            .max_by_key(|(decided_at, _)| *decided_at);
+        if let Some((_, day)) = latest {
+            workday.total_hours = day.total_hours;
+            workday.comment = day.comment.clone();
+        }
+    }
+
+    let template = AmendmentsTemplate {
+        timesheet_id: sheet.timesheet.id,
+        workdays,
+        amendments: views,
+        can_decide,
+    };
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError()
+            .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be valid now."))
+}
+
+#[get("/timesheet/{timesheet_id}/amendments")]
+pub async fn get_timesheet_amendments(
+    timesheet_id: web::Path<String>,
+    query: web::Query<AmendmentsQuery>,
+    employment_repo: web::Data<EmploymentRepository>,
+    timesheet_repo: web::Data<TimesheetRepository>,
+    amendment_repo: web::Data<TimesheetAmendmentRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(timesheet_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let sheet_res = timesheet_repo
+        ._read_one(id_parse.expect("Should be valid."))
+        .await;
+    if let Err(error) = sheet_res {
+        return handle_database_error(error);
+    }
+    let sheet = sheet_res.expect("Should be valid.");
+    let is_manager = is_manager_of(query.user_id, &sheet.timesheet, &employment_repo).await;
+    if !is_manager && sheet.timesheet.user_id != query.user_id {
+        return access_denied();
+    }
+
+    render_amendments(
+        sheet,
+        is_manager,
+        query.pink_paper_signed,
+        &timesheet_repo,
+        &amendment_repo,
+    )
+    .await
+}
+
+/// Proposes corrected workday values of an accepted sheet. The sheet itself
+/// stays as it was accepted, the correction is paid once a manager approves it.
+#[post("/timesheet/{timesheet_id}/amendments")]
+pub async fn propose_timesheet_amendment(
+    timesheet_id: web::Path<String>,
+    proposal: web::Json<AmendmentProposal>,
+    employment_repo: web::Data<EmploymentRepository>,
+    timesheet_repo: web::Data<TimesheetRepository>,
+    amendment_repo: web::Data<TimesheetAmendmentRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(timesheet_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let timesheet_id = id_parse.expect("Should be valid.");
+    let proposal = proposal.into_inner();
+    let sheet_res = timesheet_repo._read_one(timesheet_id).await;
+    if let Err(error) = sheet_res {
+        return handle_database_error(error);
+    }
+    let sheet = sheet_res.expect("Should be valid.");
+    let is_manager = is_manager_of(proposal.user_id, &sheet.timesheet, &employment_repo).await;
+    if !is_manager && sheet.timesheet.user_id != proposal.user_id {
+        return access_denied();
+    }
+
+    if sheet.timesheet.approval_status != ApprovalStatus::Accepted {
+        return HttpResponse::BadRequest().body("Only an accepted sheet can be amended.");
+    }
+    if let Err(message) = validate_proposal(&proposal, &sheet.workdays) {
+        return HttpResponse::BadRequest().body(message);
+    }
+
+    let result = amendment_repo
+        .create(NewTimesheetAmendment {
+            timesheet_id,
+            proposed_by: proposal.user_id,
+            reason: proposal.reason.trim().to_string(),
+            days: proposal
+                .days
+                .into_iter()
+                .map(|day| AmendmentDayData {
+                    comment: day
+                        .comment
+                        .map(|comment| comment.trim().to_string())
+                        .filter(|comment| !comment.is_empty()),
+                    ..day
+                })
+                .collect(),
+        })
+        .await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_amendments(
+        sheet,
+        is_manager,
+        proposal.pink_paper_signed,
+        &timesheet_repo,
+        &amendment_repo,
+    )
+    .await
+}
+
+/// Approves or rejects a pending amendment, rejecting needs a reason.
+#[post("/timesheet/{timesheet_id}/amendments/{amendment_id}/decision")]
+pub async fn decide_timesheet_amendment(
+    path: web::Path<(String, String)>,
+    decision: web::Json<AmendmentDecision>,
+    employment_repo: web::Data<EmploymentRepository>,
+    timesheet_repo: web::Data<TimesheetRepository>,
+    amendment_repo: web::Data<TimesheetAmendmentRepository>,
+) -> HttpResponse {
+    let parsed_ids = extract_path_tuple_ids(path.into_inner());
+    if parsed_ids.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    let (timesheet_id, amendment_id) = parsed_ids.expect("Should be valid.");
+    let decision = decision.into_inner();
+    let note = decision
+        .note
+        .map(|note| note.trim().to_string())
+        .filter(|note| !note.is_empty());
+    if note.as_ref().is_some_and(|note| note.chars().count() > 255) {
+        return HttpResponse::BadRequest().body("The note can't be longer than 255 characters.");
+    }
+    if !decision.approve && note.is_none() {
+        return HttpResponse::BadRequest().body("A rejected amendment needs a reason.");
+    }
+
+    let sheet_res = timesheet_repo._read_one(timesheet_id).await;
+    if let Err(error) = sheet_res {
+        return handle_database_error(error);
+    }
+    let sheet = sheet_res.expect("Should be valid.");
+    let is_manager = is_manager_of(decision.user_id, &sheet.timesheet, &employment_repo).await;
+    if !is_manager && sheet.timesheet.user_id != decision.user_id {
+        return access_denied();
+    }
+    if !is_manager {
+        return HttpResponse::Forbidden()
+            .body("Only the manager of the employee can decide an amendment.");
+    }
+
+    let amendment_res = amendment_repo.read_one(amendment_id).await;
+    if let Err(error) = amendment_res {
+        return handle_database_error(error);
+    }
+    if amendment_res
+        .expect("Should be valid.")
+        .amendment
+        .timesheet_id
+        != timesheet_id
+    {
+        return HttpResponse::NotFound().body(parse_error(http::StatusCode::NOT_FOUND));
+    }
+
+    let result = amendment_repo
+        .decide(
+            amendment_id,
+            AmendmentDecisionData {
+                decided_by: decision.user_id,
+                approve: decision.approve,
+                note,
+            },
+        )
+        .await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_amendments(
+        sheet,
+        is_manager,
+        decision.pink_paper_signed,
+        &timesheet_repo,
+        &amendment_repo,
+    )
+    .await
+}
@@ -0,0 +1,120 @@
+use std::str::FromStr;
+
+use actix_web::{get, http, post, web, HttpResponse};
+use askama::Template;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    errors::{handle_database_error, parse_error},
+    handlers::common::is_administrator,
+    models::ApprovalStatus,
+    repositories::{
+        timesheet::timesheet_repo::TimesheetRepository,
+        timesheet_consistency::{
+            models::ConsistencyIssue, timesheet_consistency_repo::TimesheetConsistencyRepository,
+        },
+        user::user_repo::UserRepository,
+    },
+    templates::timesheet::TimesheetConsistencyTemplate,
+};
+
+#[derive(Deserialize, Debug)]
+pub struct ConsistencyQuery {
+    pub user_id: Uuid,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ConsistencyRepair {
+    pub user_id: Uuid,
+    pub issue: ConsistencyIssue,
+}
+
+async fn render_consistency(
+    user_id: Uuid,
+    consistency_repo: &TimesheetConsistencyRepository,
+) -> HttpResponse {
+    let report_res = consistency_repo.check().await;
+    if let Err(error) = report_res {
+        return handle_database_error(error);
+    }
+
+    let template = TimesheetConsistencyTemplate {
+        user_id,
+        report: report_res.expect("Should be valid."),
+    };
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError()
+            .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be valid now."))
+}
+
+/// The timesheets whose totals, workdays or dates disagree with each other.
+#[get("/admin/timesheet-consistency")]
+pub async fn get_timesheet_consistency(
+    query: web::Query<ConsistencyQuery>,
+    user_repo: web::Data<UserRepository>,
+    consistency_repo: web::Data<TimesheetConsistencyRepository>,
+) -> HttpResponse {
+    if !is_administrator(query.user_id, &user_repo).await {
+        return HttpResponse::Forbidden()
+            .body("Only an administrator can check the consistency of the timesheets.");
+    }
+
+    render_consistency(query.user_id, &consistency_repo).await
+}
+
+/// Repairs one reported issue of the timesheet and checks the timesheets again.
+#[post("/admin/timesheet-consistency/{timesheet_id}/repair")]
+pub async fn repair_timesheet_consistency(
+    timesheet_id: web::Path<String>,
+    repair: web::Json<ConsistencyRepair>,
+    user_repo: web::Data<UserRepository>,
+    timesheet_repo: web::Data<TimesheetRepository>,
+    consistency_repo: web::Data<TimesheetConsistencyRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(timesheet_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    if !is_administrator(repair.user_id, &user_repo).await {
+        return HttpResponse::Forbidden().body("Only an administrator can repair the timesheets.");
+    }
+
+    let timesheet_id = id_parse.expect("Should be valid.");
+    let result = match repair.issue {
+        ConsistencyIssue::Total => consistency_repo.repair_total(timesheet_id).await,
+        ConsistencyIssue::Workdays => {
+            consistency_repo
+                .repair_workdays(timesheet_id, repair.user_id)
+                .await
+        }
+        ConsistencyIssue::Range => {
+            let sheet_res = timesheet_repo._read_one(timesheet_id).await;
+            if let Err(error) = sheet_res {
+                return handle_database_error(error);
+            }
+            let sheet = sheet_res.expect("Should be valid.").timesheet;
+            if !sheet.is_editable || sheet.approval_status == ApprovalStatus::Accepted {
+                return HttpResponse::BadRequest().body(
+                    "Only an editable timesheet which wasn't accepted can be moved to the dates of its event.",
+                );
+            }
+
+            consistency_repo
+                .repair_range(timesheet_id, repair.user_id)
+                .await
+        }
+    };
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_consistency(repair.user_id, &consistency_repo).await
+}
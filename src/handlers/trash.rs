@@ -0,0 +1,104 @@
+use std::str::FromStr;
+
+use actix_web::{get, http, post, web, HttpResponse};
+use askama::Template;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    errors::{handle_database_error, parse_error},
+    handlers::common::is_administrator,
+    repositories::{trash::trash_repo::TrashRepository, user::user_repo::UserRepository},
+    templates::trash::TrashTemplate,
+    utils::jobs::trash_purge::TRASH_RETENTION_DAYS,
+};
+
+#[derive(Deserialize, Debug)]
+pub struct TrashQuery {
+    pub user_id: Uuid,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TrashRestore {
+    pub user_id: Uuid,
+}
+
+async fn render_trash(user_id: Uuid, trash_repo: &TrashRepository) -> HttpResponse {
+    let deletions_res = trash_repo.read_all().await;
+    if let Err(error) = deletions_res {
+        return handle_database_error(error);
+    }
+
+    let template = TrashTemplate {
+        user_id,
+        deletions: deletions_res.expect("Should be valid."),
+        retention_days: TRASH_RETENTION_DAYS,
+    };
+    let body = template.render();
+    if body.is_err() {
+        return HttpResponse::InternalServerError()
+            .body(parse_error(http::StatusCode::INTERNAL_SERVER_ERROR));
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/html")
+        .body(body.expect("Should be valid now."))
+}
+
+/// The deleted records which can still be restored, for administrators.
+#[get("/admin/trash")]
+pub async fn get_trash(
+    query: web::Query<TrashQuery>,
+    user_repo: web::Data<UserRepository>,
+    trash_repo: web::Data<TrashRepository>,
+) -> HttpResponse {
+    if !is_administrator(query.user_id, &user_repo).await {
+        return HttpResponse::Forbidden().body("Only an administrator can see the trash.");
+    }
+
+    render_trash(query.user_id, &trash_repo).await
+}
+
+/// Restores the deleted record along with the rows deleted with it.
+#[post("/admin/trash/{deletion_id}/restore")]
+pub async fn restore_deletion(
+    deletion_id: web::Path<String>,
+    data: web::Json<TrashRestore>,
+    user_repo: web::Data<UserRepository>,
+    trash_repo: web::Data<TrashRepository>,
+) -> HttpResponse {
+    let id_parse = Uuid::from_str(deletion_id.into_inner().as_str());
+    if id_parse.is_err() {
+        return HttpResponse::BadRequest().body(parse_error(http::StatusCode::BAD_REQUEST));
+    }
+
+    if !is_administrator(data.user_id, &user_repo).await {
+        return HttpResponse::Forbidden().body("Only an administrator can restore from the trash.");
+    }
+
+    let deletion_res = trash_repo
+        .read_one(id_parse.expect("Should be valid."))
+        .await;
+    if let Err(error) = deletion_res {
+        return handle_database_error(error);
+    }
+    let deletion = deletion_res.expect("Should be valid.");
+
+    let can_restore = trash_repo.can_restore(&deletion).await;
+    if let Err(error) = can_restore {
+        return handle_database_error(error);
+    }
+    if !can_restore.expect("Should be valid.") {
+        return HttpResponse::BadRequest().body(format!(
+            "{} {} belongs to a deleted record, restore that one first.",
+            deletion.entity, deletion.label
+        ));
+    }
+
+    let result = trash_repo.restore(deletion.id).await;
+    if let Err(error) = result {
+        return handle_database_error(error);
+    }
+
+    render_trash(data.user_id, &trash_repo).await
+}
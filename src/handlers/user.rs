@@ -2,6 +2,7 @@ use std::str::FromStr;
 
 use crate::{
     errors::{handle_database_error, parse_error},
+    handlers::common::DeletionQuery,
     repositories::user::models::{NewUser, UserData, UsersQuery},
     templates::user::{AdminTemplate, UserEditTemplate, UserInfo, UserInfoTemplate, UserTemplate},
     utils::{
@@ -108,7 +109,7 @@ pub async fn toggle_user_edit(
     handle_database_error(result.expect_err("Should be error."))
 }
 
-fn validate_new_user(new_user: NewUser) -> Result<(), String> {
+pub fn validate_new_user(new_user: NewUser) -> Result<(), String> {
     if new_user.name.trim().is_empty() || new_user.email.trim().is_empty() {
         return Err("Username or Email empty.".to_string());
     }
@@ -224,6 +225,7 @@ pub async fn update_user(
 #[delete("/user/{user_id}")]
 pub async fn delete_user(
     user_id: web::Path<String>,
+    query: web::Query<DeletionQuery>,
     user_repo: web::Data<UserRepository>,
 ) -> HttpResponse {
     let id_parse = Uuid::from_str(user_id.into_inner().as_str());
@@ -233,7 +235,7 @@ pub async fn delete_user(
 
     let parsed_id = id_parse.expect("Should be valid.");
 
-    let result = user_repo.delete_user(parsed_id).await;
+    let result = user_repo.delete_user(parsed_id, query.deleted_by).await;
 
     if let Err(error) = result {
         return handle_database_error(error);
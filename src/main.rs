@@ -23,27 +23,62 @@ use actix_web_middleware_keycloak_auth::{DecodingKey, KeycloakAuth};
 
 use crate::configs::assigned_staff_config::configure_assigned_staff_endpoints;
 use crate::configs::associated_company_config::configure_associated_company_endpoints;
+use crate::configs::calendar_feed_config::configure_calendar_feed_endpoints;
 use crate::configs::comment_config::configure_comment_endpoints;
 use crate::configs::company_config::configure_company_endpoints;
 use crate::configs::employment_config::configure_employment_endpoints;
+use crate::configs::event_budget_config::configure_event_budget_endpoints;
 use crate::configs::event_config::configure_event_endpoints;
+use crate::configs::event_template_config::configure_event_template_endpoints;
+use crate::configs::export_config::configure_export_endpoints;
+use crate::configs::job_config::configure_job_endpoints;
+use crate::configs::live_update_config::configure_live_update_endpoints;
+use crate::configs::notification_config::configure_notification_endpoints;
+use crate::configs::report_config::configure_report_endpoints;
 use crate::configs::staff_config::configure_staff_endpoints;
+use crate::configs::submission_policy_config::configure_submission_policy_endpoints;
+use crate::configs::task_checklist_config::configure_task_checklist_endpoints;
 use crate::configs::task_config::configure_task_endpoints;
+use crate::configs::task_dependency_config::configure_task_dependency_endpoints;
 use crate::configs::timesheet_config::configure_timesheet_endpoints;
+use crate::configs::trash_config::configure_trash_endpoints;
 use crate::configs::user_config::configure_user_endpoints;
 
+use crate::handlers::calendar_feed::get_calendar_feed;
 use crate::handlers::index::{index, login_page, registration_page};
 use crate::repositories::assigned_staff::assigned_staff_repo::AssignedStaffRepository;
 use crate::repositories::associated_company::associated_company_repo::AssociatedCompanyRepository;
+use crate::repositories::calendar_feed::calendar_feed_repo::CalendarFeedRepository;
 use crate::repositories::comment::comment_repo::CommentRepository;
 use crate::repositories::company::company_repo::CompanyRepository;
 use crate::repositories::employment::employment_repo::EmploymentRepository;
 use crate::repositories::event::event_repo::EventRepository;
+use crate::repositories::event_budget::event_budget_repo::EventBudgetRepository;
 use crate::repositories::event_staff::event_staff_repo::StaffRepository;
+use crate::repositories::event_template::event_template_repo::EventTemplateRepository;
+use crate::repositories::job::job_repo::JobRepository;
+use crate::repositories::mail_outbox::mail_outbox_repo::MailOutboxRepository;
+use crate::repositories::notification::notification_repo::NotificationRepository;
+use crate::repositories::report::report_repo::ReportRepository;
 use crate::repositories::repository::DbRepository;
+use crate::repositories::submission_policy::submission_policy_repo::SubmissionPolicyRepository;
 use crate::repositories::task::task_repo::TaskRepository;
+use crate::repositories::task_checklist::task_checklist_repo::TaskChecklistRepository;
+use crate::repositories::task_dependency::task_dependency_repo::TaskDependencyRepository;
 use crate::repositories::timesheet::timesheet_repo::TimesheetRepository;
+use crate::repositories::timesheet_amendment::timesheet_amendment_repo::TimesheetAmendmentRepository;
+use crate::repositories::timesheet_consistency::timesheet_consistency_repo::TimesheetConsistencyRepository;
+use crate::repositories::trash::trash_repo::TrashRepository;
 use crate::repositories::user::user_repo::UserRepository;
+use crate::repositories::wage_preset::wage_preset_repo::WagePresetRepository;
+use crate::utils::deadline::deadline_worker::DeadlineJob;
+use crate::utils::jobs::job_scheduler::JobScheduler;
+use crate::utils::jobs::run_history::RunHistoryCleanupJob;
+use crate::utils::jobs::timesheet_consistency::TimesheetConsistencyJob;
+use crate::utils::jobs::trash_purge::TrashPurgeJob;
+use crate::utils::live_update::live_update_hub::LiveUpdateHub;
+use crate::utils::mail::mail_sender::{MailConfig, MailSender};
+use crate::utils::mail::mail_worker::MailDeliveryJob;
 
 use actix_web::web;
 use serde::Deserialize;
@@ -69,6 +104,11 @@ async fn main() -> std::io::Result<()> {
     let config = Config::default();
 
     let pool = setup_db_pool().await;
+
+    // Changes made through any instance reach the open pages via Postgres.
+    let live_update_hub = LiveUpdateHub::new();
+    live_update_hub.spawn_listener(pool.clone());
+
     let arc_pool = Arc::new(pool);
     let user_repository = UserRepository::new(arc_pool.clone());
     let company_repository = CompanyRepository::new(arc_pool.clone());
@@ -80,6 +120,47 @@ async fn main() -> std::io::Result<()> {
     let associated_company_repository = AssociatedCompanyRepository::new(arc_pool.clone());
     let timesheet_repository = TimesheetRepository::new(arc_pool.clone());
     let comment_repository = CommentRepository::new(arc_pool.clone());
+    let task_checklist_repository = TaskChecklistRepository::new(arc_pool.clone());
+    let task_dependency_repository = TaskDependencyRepository::new(arc_pool.clone());
+    let event_template_repository = EventTemplateRepository::new(arc_pool.clone());
+    let notification_repository = NotificationRepository::new(arc_pool.clone());
+    let mail_outbox_repository = MailOutboxRepository::new(arc_pool.clone());
+    let calendar_feed_repository = CalendarFeedRepository::new(arc_pool.clone());
+    let event_budget_repository = EventBudgetRepository::new(arc_pool.clone());
+    let wage_preset_repository = WagePresetRepository::new(arc_pool.clone());
+    let report_repository = ReportRepository::new(arc_pool.clone());
+    let submission_policy_repository = SubmissionPolicyRepository::new(arc_pool.clone());
+    let timesheet_amendment_repository = TimesheetAmendmentRepository::new(arc_pool.clone());
+    let timesheet_consistency_repository = TimesheetConsistencyRepository::new(arc_pool.clone());
+    let trash_repository = TrashRepository::new(arc_pool.clone());
+
+    let job_repository = JobRepository::new(arc_pool.clone());
+
+    let mut job_scheduler = JobScheduler::new(job_repository.clone())
+        .with_job(DeadlineJob {
+            submission_policy_repo: submission_policy_repository.clone(),
+        })
+        .with_job(RunHistoryCleanupJob {
+            job_repo: job_repository.clone(),
+        })
+        .with_job(TimesheetConsistencyJob {
+            consistency_repo: timesheet_consistency_repository.clone(),
+        })
+        .with_job(TrashPurgeJob {
+            trash_repo: trash_repository.clone(),
+        });
+    // The outbox keeps filling up without SMTP, mail goes out once it is configured.
+    match MailConfig::from_env().map(|mail_config| MailSender::new(&mail_config)) {
+        Ok(Ok(sender)) => {
+            job_scheduler = job_scheduler.with_job(MailDeliveryJob {
+                mail_outbox_repo: mail_outbox_repository.clone(),
+                sender,
+            });
+        }
+        Ok(Err(error)) => log::warn!("Mail delivery is disabled: {}", error),
+        Err(error) => log::warn!("Mail delivery is disabled: {}", error),
+    }
+    job_scheduler.spawn();
 
     let user_repo = web::Data::new(user_repository);
     let company_repo = web::Data::new(company_repository);
@@ -91,6 +172,21 @@ async fn main() -> std::io::Result<()> {
     let associated_company_repo = web::Data::new(associated_company_repository);
     let timesheet_repo = web::Data::new(timesheet_repository);
     let comment_repo = web::Data::new(comment_repository);
+    let task_checklist_repo = web::Data::new(task_checklist_repository);
+    let task_dependency_repo = web::Data::new(task_dependency_repository);
+    let event_template_repo = web::Data::new(event_template_repository);
+    let notification_repo = web::Data::new(notification_repository);
+    let mail_outbox_repo = web::Data::new(mail_outbox_repository);
+    let calendar_feed_repo = web::Data::new(calendar_feed_repository);
+    let event_budget_repo = web::Data::new(event_budget_repository);
+    let wage_preset_repo = web::Data::new(wage_preset_repository);
+    let report_repo = web::Data::new(report_repository);
+    let submission_policy_repo = web::Data::new(submission_policy_repository);
+    let timesheet_amendment_repo = web::Data::new(timesheet_amendment_repository);
+    let timesheet_consistency_repo = web::Data::new(timesheet_consistency_repository);
+    let trash_repo = web::Data::new(trash_repository);
+    let job_repo = web::Data::new(job_repository);
+    let live_update_hub = web::Data::new(live_update_hub);
 
     println!("Starting server on http://{}:{}", config.host, config.port);
 
@@ -112,6 +208,21 @@ async fn main() -> std::io::Result<()> {
             .app_data(associated_company_repo.clone())
             .app_data(timesheet_repo.clone())
             .app_data(comment_repo.clone())
+            .app_data(task_checklist_repo.clone())
+            .app_data(task_dependency_repo.clone())
+            .app_data(event_template_repo.clone())
+            .app_data(notification_repo.clone())
+            .app_data(mail_outbox_repo.clone())
+            .app_data(calendar_feed_repo.clone())
+            .app_data(event_budget_repo.clone())
+            .app_data(wage_preset_repo.clone())
+            .app_data(report_repo.clone())
+            .app_data(submission_policy_repo.clone())
+            .app_data(timesheet_amendment_repo.clone())
+            .app_data(timesheet_consistency_repo.clone())
+            .app_data(trash_repo.clone())
+            .app_data(job_repo.clone())
+            .app_data(live_update_hub.clone())
             .wrap(Logger::default())
             .wrap(Logger::new("%a %{User-Agent}i"))
             .service(index)
@@ -119,6 +230,8 @@ async fn main() -> std::io::Result<()> {
             .service(login_page)
             .service(login)
             .service(register)
+            // Calendar applications can't log in, the feed token authorizes them.
+            .service(get_calendar_feed)
             .service(
                 web::scope("/protected")
                     .wrap(keycloak_auth)
@@ -126,12 +239,24 @@ async fn main() -> std::io::Result<()> {
                     .configure(configure_user_endpoints)
                     .configure(configure_company_endpoints)
                     .configure(configure_event_endpoints)
+                    .configure(configure_event_template_endpoints)
                     .configure(configure_employment_endpoints)
                     .configure(configure_assigned_staff_endpoints)
                     .configure(configure_task_endpoints)
+                    .configure(configure_task_checklist_endpoints)
+                    .configure(configure_task_dependency_endpoints)
                     .configure(configure_staff_endpoints)
                     .configure(configure_associated_company_endpoints)
                     .configure(configure_comment_endpoints)
+                    .configure(configure_notification_endpoints)
+                    .configure(configure_live_update_endpoints)
+                    .configure(configure_calendar_feed_endpoints)
+                    .configure(configure_export_endpoints)
+                    .configure(configure_event_budget_endpoints)
+                    .configure(configure_report_endpoints)
+                    .configure(configure_job_endpoints)
+                    .configure(configure_trash_endpoints)
+                    .configure(configure_submission_policy_endpoints)
                     .configure(configure_timesheet_endpoints),
             )
             // For serving css and static files overall
@@ -68,6 +68,44 @@ impl fmt::Display for EventRole {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, sqlx::Type, PartialEq, Clone)]
+#[sqlx(type_name = "event_status", rename_all = "lowercase")]
+pub enum EventStatus {
+    Draft,
+    Published,
+    Cancelled,
+    Completed,
+}
+
+impl EventStatus {
+    /// Checks whether the lifecycle allows moving from this state to `next`.
+    pub fn can_transition_to(&self, next: &EventStatus) -> bool {
+        matches!(
+            (self, next),
+            (EventStatus::Draft, EventStatus::Published)
+                | (EventStatus::Draft, EventStatus::Cancelled)
+                | (EventStatus::Published, EventStatus::Cancelled)
+                | (EventStatus::Published, EventStatus::Completed)
+        )
+    }
+
+    /// Cancelled and completed events are final and can no longer be changed.
+    pub fn is_closed(&self) -> bool {
+        matches!(self, EventStatus::Cancelled | EventStatus::Completed)
+    }
+}
+
+impl fmt::Display for EventStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventStatus::Draft => write!(f, "Draft"),
+            EventStatus::Published => write!(f, "Published"),
+            EventStatus::Cancelled => write!(f, "Cancelled"),
+            EventStatus::Completed => write!(f, "Completed"),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, sqlx::Type, PartialEq, Clone)]
 #[sqlx(type_name = "acceptance_status", rename_all = "snake_case")]
 pub enum AcceptanceStatus {
@@ -104,6 +142,41 @@ impl fmt::Display for TaskPriority {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, sqlx::Type, PartialEq, Clone)]
+#[sqlx(type_name = "task_status", rename_all = "snake_case")]
+pub enum TaskStatus {
+    Todo,
+    InProgress,
+    Blocked,
+    Review,
+    Done,
+}
+
+impl TaskStatus {
+    /// Board columns in the order they are displayed.
+    pub fn all() -> Vec<TaskStatus> {
+        vec![
+            TaskStatus::Todo,
+            TaskStatus::InProgress,
+            TaskStatus::Blocked,
+            TaskStatus::Review,
+            TaskStatus::Done,
+        ]
+    }
+}
+
+impl fmt::Display for TaskStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaskStatus::Todo => write!(f, "To Do"),
+            TaskStatus::InProgress => write!(f, "In Progress"),
+            TaskStatus::Blocked => write!(f, "Blocked"),
+            TaskStatus::Review => write!(f, "Review"),
+            TaskStatus::Done => write!(f, "Done"),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, sqlx::Type, Clone, PartialEq)]
 #[sqlx(type_name = "association", rename_all = "lowercase")]
 pub enum Association {
@@ -179,3 +252,238 @@ impl fmt::Display for ApprovalStatus {
         }
     }
 }
+
+#[derive(Serialize, Deserialize, Debug, sqlx::Type, Clone, Copy, PartialEq)]
+#[sqlx(type_name = "timesheet_transition", rename_all = "lowercase")]
+pub enum TimesheetTransition {
+    Submit,
+    Withdraw,
+    Approve,
+    Reject,
+    Reopen,
+    Adjust,
+    Acknowledge,
+}
+
+impl TimesheetTransition {
+    /// Checks whether a timesheet in `status` can go through the transition.
+    pub fn is_allowed_from(&self, status: &ApprovalStatus) -> bool {
+        matches!(
+            (self, status),
+            (TimesheetTransition::Submit, ApprovalStatus::NotRequested)
+                | (TimesheetTransition::Submit, ApprovalStatus::Rejected)
+                | (TimesheetTransition::Withdraw, ApprovalStatus::Pending)
+                | (TimesheetTransition::Approve, ApprovalStatus::Pending)
+                | (TimesheetTransition::Reject, ApprovalStatus::Pending)
+                | (TimesheetTransition::Reopen, ApprovalStatus::Accepted)
+                | (TimesheetTransition::Adjust, ApprovalStatus::Pending)
+                | (TimesheetTransition::Acknowledge, ApprovalStatus::Accepted)
+        )
+    }
+
+    /// The status the timesheet ends up in.
+    pub fn target(&self) -> ApprovalStatus {
+        match self {
+            TimesheetTransition::Submit => ApprovalStatus::Pending,
+            TimesheetTransition::Approve
+            | TimesheetTransition::Adjust
+            | TimesheetTransition::Acknowledge => ApprovalStatus::Accepted,
+            TimesheetTransition::Reject => ApprovalStatus::Rejected,
+            TimesheetTransition::Withdraw | TimesheetTransition::Reopen => {
+                ApprovalStatus::NotRequested
+            }
+        }
+    }
+
+    /// Submitting, withdrawing and acknowledging adjustments is up to the
+    /// employee, the rest is decided by their manager.
+    pub fn is_made_by_manager(&self) -> bool {
+        !matches!(
+            self,
+            TimesheetTransition::Submit
+                | TimesheetTransition::Withdraw
+                | TimesheetTransition::Acknowledge
+        )
+    }
+
+    /// Approving, with or without adjustments, and rejecting decides the review.
+    pub fn is_decision(&self) -> bool {
+        matches!(
+            self,
+            TimesheetTransition::Approve
+                | TimesheetTransition::Adjust
+                | TimesheetTransition::Reject
+        )
+    }
+}
+
+impl fmt::Display for TimesheetTransition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimesheetTransition::Submit => write!(f, "Submitted"),
+            TimesheetTransition::Withdraw => write!(f, "Withdrawn"),
+            TimesheetTransition::Approve => write!(f, "Approved"),
+            TimesheetTransition::Reject => write!(f, "Rejected"),
+            TimesheetTransition::Reopen => write!(f, "Reopened"),
+            TimesheetTransition::Adjust => write!(f, "Approved with Adjustments"),
+            TimesheetTransition::Acknowledge => write!(f, "Adjustments Acknowledged"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, sqlx::Type, Clone, PartialEq)]
+#[sqlx(type_name = "notification_kind", rename_all = "snake_case")]
+pub enum NotificationKind {
+    StaffDecision,
+    AssignmentDecision,
+    TimesheetApproval,
+    TimesheetRejection,
+    CommentMention,
+    EventStatusChange,
+    SubmissionReminder,
+}
+
+impl NotificationKind {
+    /// Preference rows in the order they are displayed.
+    pub fn all() -> Vec<NotificationKind> {
+        vec![
+            NotificationKind::StaffDecision,
+            NotificationKind::AssignmentDecision,
+            NotificationKind::TimesheetApproval,
+            NotificationKind::TimesheetRejection,
+            NotificationKind::CommentMention,
+            NotificationKind::EventStatusChange,
+            NotificationKind::SubmissionReminder,
+        ]
+    }
+}
+
+impl fmt::Display for NotificationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotificationKind::StaffDecision => write!(f, "Event Application Decisions"),
+            NotificationKind::AssignmentDecision => write!(f, "Task Assignment Decisions"),
+            NotificationKind::TimesheetApproval => write!(f, "Timesheet Approvals"),
+            NotificationKind::TimesheetRejection => write!(f, "Timesheet Rejections"),
+            NotificationKind::CommentMention => write!(f, "Comment Mentions"),
+            NotificationKind::EventStatusChange => write!(f, "Event Cancellations and Completions"),
+            NotificationKind::SubmissionReminder => write!(f, "Timesheet Deadline Reminders"),
+        }
+    }
+}
+
+/// The manager's decision on a correction of an accepted timesheet.
+#[derive(Serialize, Deserialize, Debug, sqlx::Type, Clone, Copy, PartialEq)]
+#[sqlx(type_name = "amendment_status", rename_all = "lowercase")]
+pub enum AmendmentStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl fmt::Display for AmendmentStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AmendmentStatus::Pending => write!(f, "Pending"),
+            AmendmentStatus::Approved => write!(f, "Approved"),
+            AmendmentStatus::Rejected => write!(f, "Rejected"),
+        }
+    }
+}
+
+/// What happens to the timesheets which weren't submitted by the deadline.
+#[derive(Serialize, Deserialize, Debug, sqlx::Type, Clone, PartialEq)]
+#[sqlx(type_name = "overdue_policy", rename_all = "lowercase")]
+pub enum OverduePolicy {
+    Submit,
+    Lock,
+}
+
+impl fmt::Display for OverduePolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OverduePolicy::Submit => write!(f, "Submit as Is"),
+            OverduePolicy::Lock => write!(f, "Lock"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, sqlx::Type, Clone, PartialEq)]
+#[sqlx(type_name = "mail_delivery", rename_all = "snake_case")]
+pub enum MailDelivery {
+    Immediate,
+    DailyDigest,
+}
+
+impl fmt::Display for MailDelivery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MailDelivery::Immediate => write!(f, "Immediately"),
+            MailDelivery::DailyDigest => write!(f, "Daily Digest"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, sqlx::Type, Clone, PartialEq)]
+#[sqlx(type_name = "mail_status", rename_all = "lowercase")]
+pub enum MailStatus {
+    Pending,
+    Digest,
+    Bundled,
+    Sent,
+    Failed,
+}
+
+impl fmt::Display for MailStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MailStatus::Pending => write!(f, "Pending"),
+            MailStatus::Digest => write!(f, "Waiting for Digest"),
+            MailStatus::Bundled => write!(f, "Bundled into Digest"),
+            MailStatus::Sent => write!(f, "Sent"),
+            MailStatus::Failed => write!(f, "Failed"),
+        }
+    }
+}
+
+/// The outcome of a background job's run.
+#[derive(Serialize, Deserialize, Debug, sqlx::Type, Clone, Copy, PartialEq)]
+#[sqlx(type_name = "job_run_status", rename_all = "lowercase")]
+pub enum JobRunStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+impl fmt::Display for JobRunStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobRunStatus::Running => write!(f, "Running"),
+            JobRunStatus::Succeeded => write!(f, "Succeeded"),
+            JobRunStatus::Failed => write!(f, "Failed"),
+        }
+    }
+}
+
+/// The record a deletion started from, its dependent rows go along with it.
+#[derive(Serialize, Deserialize, Debug, sqlx::Type, Clone, Copy, PartialEq)]
+#[sqlx(type_name = "deleted_entity", rename_all = "lowercase")]
+pub enum DeletedEntity {
+    User,
+    Company,
+    Event,
+    Employment,
+    Task,
+}
+
+impl fmt::Display for DeletedEntity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeletedEntity::User => write!(f, "User"),
+            DeletedEntity::Company => write!(f, "Company"),
+            DeletedEntity::Event => write!(f, "Event"),
+            DeletedEntity::Employment => write!(f, "Employment"),
+            DeletedEntity::Task => write!(f, "Task"),
+        }
+    }
+}
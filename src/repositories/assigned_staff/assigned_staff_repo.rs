@@ -1,12 +1,19 @@
 use crate::{
-    common::DbResult, repositories::assigned_staff::models::AssignedStaffStaffUserCompanyFlattened,
+    common::DbResult,
+    repositories::{
+        assigned_staff::models::{AssignedStaffStaffUserCompanyFlattened, TaskAssignmentExport},
+        notification::{models::NotificationEvent, notification_repo},
+    },
 };
 use async_trait::async_trait;
+use futures::stream::BoxStream;
 use sqlx::postgres::PgPool;
 use std::{ops::DerefMut, sync::Arc};
 use uuid::Uuid;
 
-use crate::models::{AcceptanceStatus, EventRole, Gender, UserRole, UserStatus};
+use crate::models::{
+    AcceptanceStatus, EventRole, Gender, TaskPriority, TaskStatus, UserRole, UserStatus,
+};
 
 use super::models::{
     AssignedStaff, AssignedStaffData, AssignedStaffExtended, AssignedStaffFilter, NewAssignedStaff,
@@ -251,6 +258,23 @@ impl AssignedStaffRepository {
     ) -> DbResult<AssignedStaffExtended> {
         let mut tx = self.pool.begin().await?;
 
+        let previous_status = sqlx::query_scalar!(
+            r#"
+            SELECT status AS "status!: AcceptanceStatus"
+            FROM assigned_staff
+            WHERE staff_id = $1
+              AND task_id = $2
+              AND deleted_at IS NULL
+            FOR UPDATE;
+            "#,
+            staff_id,
+            task_id,
+        )
+        .fetch_optional(tx.deref_mut())
+        .await?;
+
+        let status_change = data.status.clone();
+
         let _ = sqlx::query_as!(
             AssignedStaff,
             r#"
@@ -278,6 +302,17 @@ impl AssignedStaffRepository {
         .fetch_one(tx.deref_mut())
         .await?;
 
+        // Only an actual decision is worth telling the assignee about.
+        if Some(&status_change) != previous_status.as_ref()
+            && status_change != AcceptanceStatus::Pending
+        {
+            notification_repo::emit_db_using_tx(
+                &mut tx,
+                NotificationEvent::AssignmentDecided { task_id, staff_id },
+            )
+            .await?;
+        }
+
         let updated_staff = self.read_one_db(tx.deref_mut(), task_id, staff_id).await?;
         tx.commit().await?;
         Ok(updated_staff)
@@ -333,4 +368,37 @@ impl AssignedStaffRepository {
 
         Ok(())
     }
+
+    /// Tasks of the event with their assigned staff, read row by row for exports.
+    pub fn stream_all_for_event(
+        &self,
+        event_id: Uuid,
+    ) -> BoxStream<'_, DbResult<TaskAssignmentExport>> {
+        sqlx::query_as!(
+            TaskAssignmentExport,
+            r#"
+            SELECT task.title AS task_title,
+                   task.status AS "task_status!: TaskStatus",
+                   task.priority AS "task_priority!: TaskPriority",
+                   task.due_at,
+                   task.estimated_hours,
+                   user_record.name AS "staff_name?",
+                   user_record.email AS "staff_email?",
+                   company.name AS "company_name?",
+                   assigned_staff.status AS "assignment_status?: AcceptanceStatus"
+            FROM task
+                LEFT OUTER JOIN (assigned_staff
+                    INNER JOIN event_staff ON assigned_staff.staff_id = event_staff.id
+                    INNER JOIN user_record ON event_staff.user_id = user_record.id
+                    INNER JOIN company ON event_staff.company_id = company.id)
+                ON assigned_staff.task_id = task.id
+                   AND assigned_staff.deleted_at IS NULL
+            WHERE task.event_id = $1
+              AND task.deleted_at IS NULL
+            ORDER BY task.title, task.id, user_record.name;
+            "#,
+            event_id,
+        )
+        .fetch(self.pool.as_ref())
+    }
 }
@@ -4,7 +4,7 @@ use sqlx::{types::chrono::NaiveDateTime, FromRow};
 use uuid::Uuid;
 
 use crate::{
-    models::{AcceptanceStatus, EventRole, Gender, UserRole, UserStatus},
+    models::{AcceptanceStatus, EventRole, Gender, TaskPriority, TaskStatus, UserRole, UserStatus},
     repositories::{company::models::Company, event_staff::models::StaffLite, user::models::User},
 };
 
@@ -184,3 +184,18 @@ impl From<AssignedStaffStaffUserCompanyFlattened> for AssignedStaffExtended {
         }
     }
 }
+
+/// A task of an event with one of its assigned staff members, tasks without
+/// any staff have the staff columns empty.
+#[derive(Debug, FromRow, Clone)]
+pub struct TaskAssignmentExport {
+    pub task_title: String,
+    pub task_status: TaskStatus,
+    pub task_priority: TaskPriority,
+    pub due_at: Option<NaiveDateTime>,
+    pub estimated_hours: Option<f32>,
+    pub staff_name: Option<String>,
+    pub staff_email: Option<String>,
+    pub company_name: Option<String>,
+    pub assignment_status: Option<AcceptanceStatus>,
+}
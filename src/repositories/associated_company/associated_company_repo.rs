@@ -4,7 +4,7 @@ use sqlx::postgres::PgPool;
 use std::{ops::DerefMut, sync::Arc};
 use uuid::Uuid;
 
-use crate::models::{EmployeeLevel, EmploymentContract};
+use crate::models::{EmployeeLevel, EmploymentContract, EventStatus};
 
 use super::models::{
     AssociatedCompany, AssociatedCompanyData, AssociatedCompanyExtended, AssociatedCompanyFilter,
@@ -111,6 +111,8 @@ impl AssociatedCompanyRepository {
                 event.start_date as "event_start_date!", 
                 event.end_date as "event_end_date!", 
                 event.avatar_url as "event_avatar_url", 
+                event.status as "event_status!: EventStatus", 
+                event.cancellation_reason as "event_cancellation_reason", 
                 event.created_at as "event_created_at!", 
                 event.edited_at as "event_edited_at!", 
                 event.deleted_at as "event_deleted_at", 
@@ -172,6 +174,8 @@ impl AssociatedCompanyRepository {
                 event.start_date as "event_start_date!", 
                 event.end_date as "event_end_date!", 
                 event.avatar_url as "event_avatar_url", 
+                event.status as "event_status!: EventStatus", 
+                event.cancellation_reason as "event_cancellation_reason", 
                 event.created_at as "event_created_at!", 
                 event.edited_at as "event_edited_at!", 
                 event.deleted_at as "event_deleted_at", 
@@ -236,6 +240,8 @@ impl AssociatedCompanyRepository {
                 event.start_date as "event_start_date!", 
                 event.end_date as "event_end_date!", 
                 event.avatar_url as "event_avatar_url", 
+                event.status as "event_status!: EventStatus", 
+                event.cancellation_reason as "event_cancellation_reason", 
                 event.created_at as "event_created_at!", 
                 event.edited_at as "event_edited_at!", 
                 event.deleted_at as "event_deleted_at", 
@@ -305,6 +311,8 @@ impl AssociatedCompanyRepository {
                 event.start_date as "event_start_date!", 
                 event.end_date as "event_end_date!", 
                 event.avatar_url as "event_avatar_url", 
+                event.status as "event_status!: EventStatus", 
+                event.cancellation_reason as "event_cancellation_reason", 
                 event.created_at as "event_created_at!", 
                 event.edited_at as "event_edited_at!", 
                 event.deleted_at as "event_deleted_at", 
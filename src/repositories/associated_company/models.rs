@@ -4,7 +4,7 @@ use sqlx::{types::chrono::NaiveDateTime, FromRow};
 use uuid::Uuid;
 
 use crate::{
-    models::{Association, EmployeeLevel, EmploymentContract},
+    models::{Association, EmployeeLevel, EmploymentContract, EventStatus},
     repositories::{company::models::Company, event::models::Event},
 };
 
@@ -63,6 +63,8 @@ pub struct AssociatedCompanyFlattened {
     pub event_start_date: NaiveDate,
     pub event_end_date: NaiveDate,
     pub event_avatar_url: String,
+    pub event_status: EventStatus,
+    pub event_cancellation_reason: Option<String>,
     pub event_created_at: NaiveDateTime,
     pub event_edited_at: NaiveDateTime,
     pub event_deleted_at: Option<NaiveDateTime>,
@@ -105,6 +107,8 @@ impl From<AssociatedCompanyFlattened> for AssociatedCompanyExtended {
             start_date: value.event_start_date,
             end_date: value.event_end_date,
             avatar_url: value.event_avatar_url,
+            status: value.event_status,
+            cancellation_reason: value.event_cancellation_reason,
             created_at: value.event_created_at,
             edited_at: value.event_edited_at,
             deleted_at: value.event_deleted_at,
@@ -0,0 +1,277 @@
+use crate::common::DbResult;
+use crate::models::EventStatus;
+use async_trait::async_trait;
+use sqlx::postgres::PgPool;
+use std::ops::DerefMut;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::models::{CalendarEvent, CalendarFeed, CalendarTask};
+
+#[derive(Clone)]
+pub struct CalendarFeedRepository {
+    pub pool: Arc<PgPool>,
+}
+
+#[async_trait]
+impl crate::repositories::repository::DbRepository for CalendarFeedRepository {
+    /// Database repository constructor
+    fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Method allowing the database repository to disconnect from the database pool gracefully
+    async fn disconnect(&mut self) -> () {
+        self.pool.close().await;
+    }
+}
+
+impl CalendarFeedRepository {
+    /// Feeds are created the first time the user asks for them.
+    pub async fn read_or_create(
+        &self,
+        user_id: Uuid,
+        event_id: Option<Uuid>,
+    ) -> DbResult<CalendarFeed> {
+        let mut tx = self.pool.begin().await?;
+
+        let existing = sqlx::query_as!(
+            CalendarFeed,
+            r#"
+            SELECT token,
+                   user_id,
+                   event_id,
+                   created_at,
+                   edited_at,
+                   deleted_at
+            FROM calendar_feed
+            WHERE user_id = $1
+              AND event_id IS NOT DISTINCT FROM $2
+              AND deleted_at IS NULL;
+            "#,
+            user_id,
+            event_id,
+        )
+        .fetch_optional(tx.deref_mut())
+        .await?;
+
+        if let Some(feed) = existing {
+            tx.commit().await?;
+            return Ok(feed);
+        }
+
+        let feed = sqlx::query_as!(
+            CalendarFeed,
+            r#"
+            INSERT INTO calendar_feed (user_id, event_id)
+            VALUES ($1, $2)
+            RETURNING token,
+                      user_id,
+                      event_id,
+                      created_at,
+                      edited_at,
+                      deleted_at;
+            "#,
+            user_id,
+            event_id,
+        )
+        .fetch_one(tx.deref_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(feed)
+    }
+
+    /// Replaces the token of the feed, the old URL stops working.
+    pub async fn rotate(&self, user_id: Uuid, event_id: Option<Uuid>) -> DbResult<CalendarFeed> {
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE calendar_feed
+            SET deleted_at = NOW(),
+                edited_at = NOW()
+            WHERE user_id = $1
+              AND event_id IS NOT DISTINCT FROM $2
+              AND deleted_at IS NULL;
+            "#,
+            user_id,
+            event_id,
+        )
+        .execute(tx.deref_mut())
+        .await?;
+
+        let feed = sqlx::query_as!(
+            CalendarFeed,
+            r#"
+            INSERT INTO calendar_feed (user_id, event_id)
+            VALUES ($1, $2)
+            RETURNING token,
+                      user_id,
+                      event_id,
+                      created_at,
+                      edited_at,
+                      deleted_at;
+            "#,
+            user_id,
+            event_id,
+        )
+        .fetch_one(tx.deref_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(feed)
+    }
+
+    pub async fn read_by_token(&self, token: Uuid) -> DbResult<CalendarFeed> {
+        let executor = self.pool.as_ref();
+
+        let feed = sqlx::query_as!(
+            CalendarFeed,
+            r#"
+            SELECT token,
+                   user_id,
+                   event_id,
+                   created_at,
+                   edited_at,
+                   deleted_at
+            FROM calendar_feed
+            WHERE token = $1
+              AND deleted_at IS NULL;
+            "#,
+            token,
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(feed)
+    }
+
+    /// Events the user was accepted to as staff.
+    pub async fn read_events_for_user(&self, user_id: Uuid) -> DbResult<Vec<CalendarEvent>> {
+        let executor = self.pool.as_ref();
+
+        let events = sqlx::query_as!(
+            CalendarEvent,
+            r#"
+            SELECT event.id,
+                   event.name,
+                   event.description,
+                   event.start_date,
+                   event.end_date,
+                   event.status AS "status!: EventStatus",
+                   event.cancellation_reason,
+                   timezone('UTC', event.created_at::TIMESTAMPTZ) AS "created_at!",
+                   timezone('UTC', event.edited_at::TIMESTAMPTZ) AS "edited_at!"
+            FROM event_staff
+            INNER JOIN event ON event_staff.event_id = event.id
+            WHERE event_staff.user_id = $1
+              AND event_staff.status = 'accepted'
+              AND event_staff.deleted_at IS NULL
+              AND event.deleted_at IS NULL
+            ORDER BY event.start_date, event.id;
+            "#,
+            user_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(events)
+    }
+
+    /// Tasks with a due date the user was accepted to work on.
+    pub async fn read_tasks_for_user(&self, user_id: Uuid) -> DbResult<Vec<CalendarTask>> {
+        let executor = self.pool.as_ref();
+
+        let tasks = sqlx::query_as!(
+            CalendarTask,
+            r#"
+            SELECT task.id,
+                   task.title,
+                   task.description,
+                   task.due_at AS "due_at!",
+                   event.name AS event_name,
+                   event.status AS "event_status!: EventStatus",
+                   timezone('UTC', task.created_at::TIMESTAMPTZ) AS "created_at!",
+                   timezone('UTC', task.edited_at::TIMESTAMPTZ) AS "edited_at!"
+            FROM assigned_staff
+            INNER JOIN event_staff ON assigned_staff.staff_id = event_staff.id
+            INNER JOIN task ON assigned_staff.task_id = task.id
+            INNER JOIN event ON task.event_id = event.id
+            WHERE event_staff.user_id = $1
+              AND assigned_staff.status = 'accepted'
+              AND task.due_at IS NOT NULL
+              AND assigned_staff.deleted_at IS NULL
+              AND event_staff.deleted_at IS NULL
+              AND task.deleted_at IS NULL
+              AND event.deleted_at IS NULL
+            ORDER BY task.due_at, task.id;
+            "#,
+            user_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(tasks)
+    }
+
+    pub async fn read_event(&self, event_id: Uuid) -> DbResult<CalendarEvent> {
+        let executor = self.pool.as_ref();
+
+        let event = sqlx::query_as!(
+            CalendarEvent,
+            r#"
+            SELECT id,
+                   name,
+                   description,
+                   start_date,
+                   end_date,
+                   status AS "status!: EventStatus",
+                   cancellation_reason,
+                   timezone('UTC', created_at::TIMESTAMPTZ) AS "created_at!",
+                   timezone('UTC', edited_at::TIMESTAMPTZ) AS "edited_at!"
+            FROM event
+            WHERE id = $1
+              AND deleted_at IS NULL;
+            "#,
+            event_id,
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(event)
+    }
+
+    /// Every task of the event with a due date.
+    pub async fn read_tasks_for_event(&self, event_id: Uuid) -> DbResult<Vec<CalendarTask>> {
+        let executor = self.pool.as_ref();
+
+        let tasks = sqlx::query_as!(
+            CalendarTask,
+            r#"
+            SELECT task.id,
+                   task.title,
+                   task.description,
+                   task.due_at AS "due_at!",
+                   event.name AS event_name,
+                   event.status AS "event_status!: EventStatus",
+                   timezone('UTC', task.created_at::TIMESTAMPTZ) AS "created_at!",
+                   timezone('UTC', task.edited_at::TIMESTAMPTZ) AS "edited_at!"
+            FROM task
+            INNER JOIN event ON task.event_id = event.id
+            WHERE task.event_id = $1
+              AND task.due_at IS NOT NULL
+              AND task.deleted_at IS NULL
+              AND event.deleted_at IS NULL
+            ORDER BY task.due_at, task.id;
+            "#,
+            event_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(tasks)
+    }
+}
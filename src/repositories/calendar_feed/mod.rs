@@ -0,0 +1,2 @@
+pub mod calendar_feed_repo;
+pub mod models;
@@ -0,0 +1,43 @@
+use chrono::NaiveDate;
+use sqlx::{types::chrono::NaiveDateTime, FromRow};
+use uuid::Uuid;
+
+use crate::models::EventStatus;
+
+#[derive(Debug, FromRow, Clone)]
+pub struct CalendarFeed {
+    pub token: Uuid,
+    pub user_id: Uuid,
+    /// `None` for the personal feed of the user.
+    pub event_id: Option<Uuid>,
+    pub created_at: NaiveDateTime,
+    pub edited_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, FromRow, Clone)]
+pub struct CalendarEvent {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub status: EventStatus,
+    pub cancellation_reason: Option<String>,
+    /// In UTC, as well as `edited_at`.
+    pub created_at: NaiveDateTime,
+    pub edited_at: NaiveDateTime,
+}
+
+#[derive(Debug, FromRow, Clone)]
+pub struct CalendarTask {
+    pub id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub due_at: NaiveDateTime,
+    pub event_name: String,
+    pub event_status: EventStatus,
+    /// In UTC, as well as `edited_at`.
+    pub created_at: NaiveDateTime,
+    pub edited_at: NaiveDateTime,
+}
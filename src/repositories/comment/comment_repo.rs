@@ -1,15 +1,96 @@
 use crate::common::DbResult;
 use async_trait::async_trait;
-use sqlx::postgres::PgPool;
+use sqlx::{postgres::PgPool, Postgres, Transaction};
 use std::ops::DerefMut;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use super::models::{
-    Comment, CommentData, CommentExtended, CommentFilter, CommentUserFlattened, NewComment,
+    Comment, CommentData, CommentExtended, CommentFilter, CommentRevision, CommentUserFlattened,
+    NewComment,
 };
 
-use crate::models::{Gender, UserRole, UserStatus};
+use crate::{
+    models::{Gender, UserRole, UserStatus},
+    repositories::notification::{models::NotificationEvent, notification_repo},
+    utils::comment_format::mentions::{find_mentions, MentionCandidate},
+};
+
+/// Resolves the `@Name` mentions of accepted event staff within the comment.
+/// Mentions which are no longer present in the content are removed, the newly
+/// mentioned staff get notified.
+pub async fn sync_mentions_db_using_tx(
+    tx: &mut Transaction<'_, Postgres>,
+    comment_id: Uuid,
+    content: &str,
+) -> DbResult<()> {
+    let candidates: Vec<MentionCandidate> = sqlx::query_as!(
+        MentionCandidate,
+        r#"
+        SELECT event_staff.id AS staff_id,
+               user_record.name
+        FROM comment
+        LEFT JOIN task ON comment.task_id = task.id
+        INNER JOIN event_staff
+            ON event_staff.event_id = COALESCE(comment.event_id, task.event_id)
+        INNER JOIN user_record ON event_staff.user_id = user_record.id
+        WHERE comment.id = $1
+          AND event_staff.status = 'accepted'
+          AND event_staff.deleted_at IS NULL
+          AND user_record.deleted_at IS NULL;
+        "#,
+        comment_id,
+    )
+    .fetch_all(tx.deref_mut())
+    .await?;
+
+    let mentioned = find_mentions(content, &candidates);
+
+    sqlx::query!(
+        r#"
+        UPDATE comment_mention
+        SET deleted_at = NOW(),
+            edited_at = NOW()
+        WHERE comment_id = $1
+          AND staff_id <> ALL($2)
+          AND deleted_at IS NULL;
+        "#,
+        comment_id,
+        &mentioned,
+    )
+    .execute(tx.deref_mut())
+    .await?;
+
+    let newly_mentioned: Vec<Uuid> = sqlx::query_scalar!(
+        r#"
+        INSERT INTO comment_mention (comment_id, staff_id)
+        SELECT $1, mentioned.staff_id
+        FROM UNNEST($2::UUID[]) AS mentioned(staff_id)
+        ON CONFLICT (comment_id, staff_id) DO UPDATE
+        SET deleted_at = NULL,
+            edited_at = NOW()
+        WHERE comment_mention.deleted_at IS NOT NULL
+        RETURNING staff_id;
+        "#,
+        comment_id,
+        &mentioned,
+    )
+    .fetch_all(tx.deref_mut())
+    .await?;
+
+    for staff_id in newly_mentioned {
+        notification_repo::emit_db_using_tx(
+            tx,
+            NotificationEvent::Mentioned {
+                comment_id,
+                staff_id,
+            },
+        )
+        .await?;
+    }
+
+    Ok(())
+}
 
 #[derive(Clone)]
 pub struct CommentRepository {
@@ -50,21 +131,51 @@ impl CommentRepository {
 
         let mut tx = self.pool.begin().await?;
 
+        if let Some(parent_id) = data.parent_id {
+            // Replies stay within the discussion of their parent.
+            let parent = sqlx::query!(
+                r#"
+                SELECT event_id, task_id
+                FROM comment
+                WHERE id = $1
+                  AND deleted_at IS NULL;
+                "#,
+                parent_id,
+            )
+            .fetch_optional(tx.deref_mut())
+            .await?;
+
+            if parent.is_none() {
+                return Err(sqlx::Error::RowNotFound);
+            }
+
+            let parent = parent.expect("Should be some.");
+            if parent.event_id != data.event_id || parent.task_id != data.task_id {
+                return Err(sqlx::Error::TypeNotFound {
+                    type_name: "User Error".to_string(),
+                });
+            }
+        }
+
         let comment: Comment = sqlx::query_as!(
             Comment,
             r#"
-            INSERT INTO comment (author_id, event_id, task_id, content)
-            VALUES ($1, $2, $3, $4)
-            RETURNING *
+            INSERT INTO comment (author_id, event_id, task_id, parent_id, content)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, author_id, event_id, task_id, content, created_at, edited_at, deleted_at,
+                      parent_id
             "#,
             data.author_id,
             data.event_id,
             data.task_id,
+            data.parent_id,
             data.content,
         )
         .fetch_one(tx.deref_mut())
         .await?;
 
+        sync_mentions_db_using_tx(&mut tx, comment.id, &comment.content).await?;
+
         let full_comment = self.read_one_db(tx.deref_mut(), comment.id).await?;
         tx.commit().await?;
 
@@ -89,6 +200,7 @@ impl CommentRepository {
                 comment.author_id AS comment_author_id, 
                 comment.event_id AS comment_event_id, 
                 comment.task_id AS comment_task_id, 
+                comment.parent_id AS comment_parent_id, 
                 comment.content AS comment_content, 
                 comment.created_at AS comment_created_at, 
                 comment.edited_at AS comment_edited_at, 
@@ -134,6 +246,7 @@ impl CommentRepository {
                 comment.author_id AS comment_author_id, 
                 comment.event_id AS comment_event_id, 
                 comment.task_id AS comment_task_id, 
+                comment.parent_id AS comment_parent_id, 
                 comment.content AS comment_content, 
                 comment.created_at AS comment_created_at, 
                 comment.edited_at AS comment_edited_at, 
@@ -183,6 +296,7 @@ impl CommentRepository {
                 comment.author_id AS comment_author_id, 
                 comment.event_id AS comment_event_id, 
                 comment.task_id AS comment_task_id, 
+                comment.parent_id AS comment_parent_id, 
                 comment.content AS comment_content, 
                 comment.created_at AS comment_created_at, 
                 comment.edited_at AS comment_edited_at, 
@@ -227,6 +341,22 @@ impl CommentRepository {
 
         let mut tx = self.pool.begin().await?;
 
+        // Keep the replaced version around for the moderators.
+        sqlx::query!(
+            r#"
+            INSERT INTO comment_revision (comment_id, content)
+            SELECT id, content
+            FROM comment
+            WHERE id = $1
+              AND deleted_at IS NULL
+              AND content <> $2;
+            "#,
+            comment_id,
+            data.content,
+        )
+        .execute(tx.deref_mut())
+        .await?;
+
         let comment: Comment = sqlx::query_as!(
             Comment,
             r#"
@@ -235,7 +365,8 @@ impl CommentRepository {
             edited_at = NOW()
             WHERE id = $2
               AND deleted_at IS NULL
-            RETURNING *
+            RETURNING id, author_id, event_id, task_id, content, created_at, edited_at, deleted_at,
+                      parent_id
             "#,
             data.content,
             comment_id,
@@ -243,12 +374,78 @@ impl CommentRepository {
         .fetch_one(tx.deref_mut())
         .await?;
 
+        sync_mentions_db_using_tx(&mut tx, comment.id, &comment.content).await?;
+
         let full_comment = self.read_one_db(tx.deref_mut(), comment.id).await?;
         tx.commit().await?;
 
         Ok(full_comment)
     }
 
+    /// Prior versions of the comment, the most recent first.
+    pub async fn read_revisions(&self, comment_id: Uuid) -> DbResult<Vec<CommentRevision>> {
+        let executor = self.pool.as_ref();
+
+        let revisions: Vec<CommentRevision> = sqlx::query_as!(
+            CommentRevision,
+            r#"
+            SELECT *
+            FROM comment_revision
+            WHERE comment_id = $1
+              AND deleted_at IS NULL
+            ORDER BY created_at DESC;
+            "#,
+            comment_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(revisions)
+    }
+
+    /// The event whose discussion the comment belongs to, directly or through its task.
+    pub async fn read_event_id(&self, comment_id: Uuid) -> DbResult<Uuid> {
+        let executor = self.pool.as_ref();
+
+        let event = sqlx::query!(
+            r#"
+            SELECT COALESCE(comment.event_id, task.event_id) AS "event_id!"
+            FROM comment
+            LEFT JOIN task ON comment.task_id = task.id
+            WHERE comment.id = $1
+              AND comment.deleted_at IS NULL;
+            "#,
+            comment_id,
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(event.event_id)
+    }
+
+    /// Staff members mentioned in the comment.
+    pub async fn read_mentioned_staff(&self, comment_id: Uuid) -> DbResult<Vec<Uuid>> {
+        let executor = self.pool.as_ref();
+
+        let mentions = sqlx::query!(
+            r#"
+            SELECT staff_id
+            FROM comment_mention
+            WHERE comment_id = $1
+              AND deleted_at IS NULL
+            ORDER BY created_at;
+            "#,
+            comment_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(mentions
+            .into_iter()
+            .map(|mention| mention.staff_id)
+            .collect())
+    }
+
     pub async fn delete(&self, comment_id: Uuid) -> DbResult<()> {
         let executor = self.pool.as_ref();
 
@@ -263,7 +460,8 @@ impl CommentRepository {
             edited_at = NOW()
             WHERE id = $1
               AND deleted_at IS NULL
-            RETURNING *;
+            RETURNING id, author_id, event_id, task_id, content, created_at, edited_at, deleted_at,
+                      parent_id;
             "#,
             comment_id,
         )
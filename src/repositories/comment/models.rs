@@ -13,6 +13,7 @@ pub struct NewComment {
     pub author_id: Uuid,
     pub event_id: Option<Uuid>,
     pub task_id: Option<Uuid>,
+    pub parent_id: Option<Uuid>,
     pub content: String,
 }
 
@@ -26,6 +27,7 @@ pub struct Comment {
     pub created_at: NaiveDateTime,
     pub edited_at: NaiveDateTime,
     pub deleted_at: Option<NaiveDateTime>,
+    pub parent_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone)]
@@ -34,6 +36,7 @@ pub struct CommentExtended {
     pub author: User,
     pub event_id: Option<Uuid>,
     pub task_id: Option<Uuid>,
+    pub parent_id: Option<Uuid>,
     pub content: String,
     pub created_at: NaiveDateTime,
     pub edited_at: NaiveDateTime,
@@ -45,6 +48,16 @@ pub struct CommentData {
     pub content: String,
 }
 
+#[derive(Debug, FromRow, Clone)]
+pub struct CommentRevision {
+    pub id: Uuid,
+    pub comment_id: Uuid,
+    pub content: String,
+    pub created_at: NaiveDateTime,
+    pub edited_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct CommentFilter {
     pub limit: Option<i64>,
@@ -61,6 +74,7 @@ pub struct CommentUserFlattened {
     pub comment_author_id: Uuid,
     pub comment_event_id: Option<Uuid>,
     pub comment_task_id: Option<Uuid>,
+    pub comment_parent_id: Option<Uuid>,
     pub comment_content: String,
     pub comment_created_at: NaiveDateTime,
     pub comment_edited_at: NaiveDateTime,
@@ -100,6 +114,7 @@ impl From<CommentUserFlattened> for CommentExtended {
             author: tmp_user,
             event_id: value.comment_event_id,
             task_id: value.comment_task_id,
+            parent_id: value.comment_parent_id,
             content: value.comment_content,
             created_at: value.comment_created_at,
             edited_at: value.comment_edited_at,
@@ -5,7 +5,8 @@ use sqlx::postgres::PgPool;
 use std::{ops::DerefMut, sync::Arc};
 use uuid::Uuid;
 
-use crate::models::{EmployeeLevel, EmploymentContract};
+use crate::models::{DeletedEntity, EmployeeLevel, EmploymentContract};
+use crate::repositories::trash::trash_repo::delete_db_using_tx;
 
 use super::models::{
     Address, AddressData, AddressUpdateData, Company, CompanyData, CompanyExtended, CompanyFilter,
@@ -45,7 +46,9 @@ impl CompanyRepository {
         let company = sqlx::query_as!(
             Company,
             "INSERT INTO company (name, description, phone, email, website, crn, vatin) 
-             VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING *;",
+             VALUES ($1, $2, $3, $4, $5, $6, $7)
+             RETURNING id, name, description, phone, email, avatar_url, website, crn, vatin,
+                       created_at, edited_at, deleted_at;",
             data.name,
             if let Some(description) = data.description {
                 description
@@ -136,7 +139,8 @@ impl CompanyRepository {
 
         let company = sqlx::query_as!(
             Company,
-            "SELECT * 
+            "SELECT id, name, description, phone, email, avatar_url, website, crn, vatin,
+                    created_at, edited_at, deleted_at
              FROM company 
              WHERE id = $1
                AND deleted_at IS NULL;",
@@ -201,7 +205,9 @@ impl CompanyRepository {
 
         let companies = sqlx::query_as!(
             Company,
-            "SELECT * FROM company 
+            "SELECT id, name, description, phone, email, avatar_url, website, crn, vatin,
+                    created_at, edited_at, deleted_at
+             FROM company 
              WHERE deleted_at IS NULL 
                AND name LIKE $3
              ORDER BY name
@@ -417,36 +423,19 @@ impl CompanyRepository {
         Ok(company)
     }
 
-    pub async fn delete(&self, company_id: Uuid) -> DbResult<()> {
-        let executor = self.pool.as_ref();
+    pub async fn delete(&self, company_id: Uuid, deleted_by: Option<Uuid>) -> DbResult<()> {
+        let mut tx = self.pool.begin().await?;
 
-        let result = sqlx::query_as!(
-            Company,
-            "UPDATE company 
-            SET deleted_at = NOW(), 
-                edited_at = NOW() 
-            WHERE id = $1
-              AND deleted_at IS NULL
-            RETURNING id,
-                      name,
-                      description,
-                      phone,
-                      email,
-                      avatar_url,
-                      website,
-                      crn,
-                      vatin,
-                      company.created_at,
-                      company.edited_at,
-                      company.deleted_at;",
-            company_id
+        delete_db_using_tx(
+            &mut tx,
+            DeletedEntity::Company,
+            company_id,
+            None,
+            deleted_by,
         )
-        .fetch_optional(executor)
         .await?;
 
-        if result.is_none() {
-            return Err(sqlx::Error::RowNotFound);
-        }
+        tx.commit().await?;
 
         Ok(())
     }
@@ -2,6 +2,7 @@ use crate::common::DbResult;
 use async_trait::async_trait;
 use sqlx::postgres::PgPool;
 use sqlx::Transaction;
+use std::collections::HashMap;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -9,10 +10,14 @@ use std::ops::DerefMut;
 
 use super::models::{
     Employment, EmploymentContractAndHourlyWage, EmploymentData, EmploymentExtended,
-    EmploymentFilter, EmploymentUserCompanyFlattened, NewEmployment, Subordinate,
+    EmploymentFilter, EmploymentUserCompanyFlattened, ImportManager, ImportUser,
+    ImportedEmployment, NewEmployment, Subordinate,
 };
 
-use crate::models::{EmployeeLevel, EmploymentContract, Gender, UserRole, UserStatus};
+use crate::models::{
+    DeletedEntity, EmployeeLevel, EmploymentContract, Gender, UserRole, UserStatus,
+};
+use crate::repositories::trash::trash_repo::delete_db_using_tx;
 
 #[derive(Clone)]
 pub struct EmploymentRepository {
@@ -96,6 +101,136 @@ impl EmploymentRepository {
         Ok(new_employment)
     }
 
+    /// Users with the given emails (case-insensitive) and whether they work
+    /// for the company.
+    pub async fn read_import_users(
+        &self,
+        company_id: Uuid,
+        emails: Vec<String>,
+    ) -> DbResult<Vec<ImportUser>> {
+        let executor = self.pool.as_ref();
+
+        let emails: Vec<String> = emails.iter().map(|email| email.to_lowercase()).collect();
+        let users = sqlx::query_as!(
+            ImportUser,
+            r#"
+            SELECT user_record.id,
+                   user_record.email,
+                   EXISTS (
+                       SELECT 1
+                       FROM employment
+                       WHERE employment.user_id = user_record.id
+                         AND employment.company_id = $1
+                         AND employment.deleted_at IS NULL
+                   ) AS "employed!",
+                   EXISTS (
+                       SELECT 1
+                       FROM employment
+                       WHERE employment.user_id = user_record.id
+                         AND employment.company_id = $1
+                   ) AS "has_employment!"
+            FROM user_record
+            WHERE LOWER(user_record.email) = ANY($2)
+              AND user_record.deleted_at IS NULL;
+            "#,
+            company_id,
+            &emails,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(users)
+    }
+
+    /// Creates the users and their employments in a single transaction, either
+    /// all of the rows are imported or none.
+    pub async fn import(
+        &self,
+        company_id: Uuid,
+        rows: Vec<ImportedEmployment>,
+    ) -> DbResult<Vec<Employment>> {
+        let mut tx = self.pool.begin().await?;
+
+        let mut user_ids: HashMap<String, Uuid> = HashMap::new();
+        for row in rows.iter() {
+            let user_id = match row.existing_user_id {
+                Some(user_id) => user_id,
+                None => {
+                    sqlx::query_scalar!(
+                        r#"
+                        INSERT INTO user_record (name, email, birth, gender, role)
+                        VALUES ($1, $2, $3, $4, $5)
+                        RETURNING id;
+                        "#,
+                        row.user.name,
+                        row.user.email,
+                        row.user.birth,
+                        row.user.gender.clone() as Gender,
+                        row.user.role.clone() as UserRole,
+                    )
+                    .fetch_one(tx.deref_mut())
+                    .await?
+                }
+            };
+            user_ids.insert(row.user.email.to_lowercase(), user_id);
+        }
+
+        let mut employments = vec![];
+        for row in rows {
+            let manager_id = match row.manager {
+                ImportManager::Existing(manager_id) => manager_id,
+                ImportManager::Imported(email) => {
+                    *user_ids
+                        .get(&email.to_lowercase())
+                        .ok_or(sqlx::Error::TypeNotFound {
+                            type_name: "User Error".to_string(),
+                        })?
+                }
+            };
+
+            let employment = sqlx::query_as!(
+                Employment,
+                r#"
+                INSERT INTO employment (
+                    user_id, company_id, manager_id, hourly_wage,
+                    start_date, end_date, description,
+                    type, level
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                RETURNING user_id,
+                    company_id,
+                    manager_id,
+                    hourly_wage,
+                    start_date,
+                    end_date,
+                    description,
+                    type AS "employment_type!: EmploymentContract",
+                    level AS "level!: EmployeeLevel",
+                    created_at,
+                    edited_at,
+                    deleted_at;
+                "#,
+                user_ids[&row.user.email.to_lowercase()],
+                company_id,
+                manager_id,
+                row.hourly_wage,
+                row.start_date,
+                row.end_date,
+                row.description,
+                row.employment_type as EmploymentContract,
+                row.level as EmployeeLevel,
+            )
+            .fetch_one(tx.deref_mut())
+            .await?;
+
+            employments.push(employment);
+        }
+
+        tx.commit().await?;
+
+        Ok(employments)
+    }
+
     pub async fn read_one(
         &self,
         _user_uuid: Uuid,
@@ -358,6 +493,33 @@ impl EmploymentRepository {
         Ok(employment)
     }
 
+    /// Whether the user is a subordinate of the manager in the company, either
+    /// directly or delegated through the managers below them.
+    pub async fn is_superior(
+        &self,
+        manager_uuid: Uuid,
+        user_uuid: Uuid,
+        company_uuid: Uuid,
+    ) -> DbResult<bool> {
+        let executor = self.pool.as_ref();
+
+        let result = sqlx::query!(
+            r#"
+            SELECT EXISTS (SELECT 1
+                           FROM employment_subordinates($1) AS subordinate
+                           WHERE subordinate.user_id = $2
+                             AND subordinate.company_id = $3) AS "is_superior!";
+            "#,
+            manager_uuid,
+            user_uuid,
+            company_uuid,
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(result.is_superior)
+    }
+
     pub async fn update(
         &self,
         user_uuid: Uuid,
@@ -424,37 +586,24 @@ impl EmploymentRepository {
         Ok(updated_employment.expect("Should be some."))
     }
 
-    pub async fn delete(&self, user_uuid: Uuid, company_uuid: Uuid) -> DbResult<()> {
-        let executor = self.pool.as_ref();
+    pub async fn delete(
+        &self,
+        user_uuid: Uuid,
+        company_uuid: Uuid,
+        deleted_by: Option<Uuid>,
+    ) -> DbResult<()> {
+        let mut tx = self.pool.begin().await?;
 
-        let result = sqlx::query_as!(
-            Employment,
-            r#"UPDATE employment
-            SET deleted_at = NOW(), edited_at = NOW()
-            WHERE user_id = $1 AND company_id = $2
-            AND deleted_at IS NULL
-            RETURNING 
-                user_id, 
-                company_id, 
-                manager_id, 
-                hourly_wage, 
-                start_date, 
-                end_date, 
-                description, 
-                type AS "employment_type!: EmploymentContract", 
-                level AS "level!: EmployeeLevel", 
-                created_at, 
-                edited_at, 
-                deleted_at;"#,
+        delete_db_using_tx(
+            &mut tx,
+            DeletedEntity::Employment,
             user_uuid,
-            company_uuid,
+            Some(company_uuid),
+            deleted_by,
         )
-        .fetch_optional(executor)
         .await?;
 
-        if result.is_none() {
-            return Err(sqlx::Error::RowNotFound);
-        }
+        tx.commit().await?;
 
         Ok(())
     }
@@ -5,7 +5,10 @@ use uuid::Uuid;
 
 use crate::{
     models::{EmployeeLevel, EmploymentContract, Gender, UserRole, UserStatus},
-    repositories::{company::models::Company, user::models::User},
+    repositories::{
+        company::models::Company,
+        user::models::{NewUser, User},
+    },
 };
 
 #[derive(Debug, Deserialize, Clone)]
@@ -185,3 +188,35 @@ impl From<EmploymentUserCompanyFlattened> for EmploymentExtended {
         }
     }
 }
+
+/// A user the import refers to by email, who is already in the database.
+#[derive(Debug, FromRow, Clone)]
+pub struct ImportUser {
+    pub id: Uuid,
+    pub email: String,
+    /// The user has an active employment in the company.
+    pub employed: bool,
+    /// The user has an employment in the company, even a deleted one.
+    pub has_employment: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportManager {
+    Existing(Uuid),
+    /// Email of an employee created by the same import.
+    Imported(String),
+}
+
+/// A validated row of the import, the user is created unless they already exist.
+#[derive(Debug, Clone)]
+pub struct ImportedEmployment {
+    pub existing_user_id: Option<Uuid>,
+    pub user: NewUser,
+    pub manager: ImportManager,
+    pub hourly_wage: f64,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub description: Option<String>,
+    pub employment_type: EmploymentContract,
+    pub level: EmployeeLevel,
+}
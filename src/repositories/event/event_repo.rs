@@ -1,19 +1,94 @@
 use crate::{
     common::DbResult,
-    models::{Association, EventRole},
+    models::{ApprovalStatus, Association, DeletedEntity, EventRole, EventStatus},
     repositories::{
         associated_company::models::AssociatedCompanyMinimal,
         event_staff::models::StaffInfo,
-        timesheet::models::{TimeRange, TimesheetStructureData},
+        notification::{models::NotificationEvent, notification_repo},
+        timesheet::{
+            models::{TimeRange, TimesheetStructureData},
+            timesheet_repo::realign_workdays_db_using_tx,
+        },
+        trash::trash_repo::delete_db_using_tx,
     },
 };
 use async_trait::async_trait;
-use chrono::{DateTime, Datelike, TimeZone, Utc};
 use sqlx::{postgres::PgPool, Postgres, Transaction};
 use std::{ops::DerefMut, sync::Arc};
 use uuid::Uuid;
 
-use super::models::{Event, EventData, EventFilter, NewEvent};
+use super::models::{DateChangeImpact, Event, EventData, EventFilter, EventStatusData, NewEvent};
+
+/// Creates the event together with its organizing company and creator.
+/// Returns the event along with the staff ID of its creator.
+pub async fn create_event_db_using_tx(
+    tx: &mut Transaction<'_, Postgres>,
+    data: NewEvent,
+) -> DbResult<(Event, Uuid)> {
+    let new_event: Event = sqlx::query_as!(
+        Event,
+        r#" INSERT INTO event (
+            name, description, website, start_date, end_date, status
+            ) VALUES 
+            ($1, $2, $3, $4, $5, $6) RETURNING id, 
+            name, 
+            description, 
+            website, 
+            accepts_staff, 
+            start_date, 
+            end_date, 
+            avatar_url, 
+            status AS "status!: EventStatus", 
+            cancellation_reason, 
+            created_at, 
+            edited_at, 
+            deleted_at;
+        "#,
+        data.name,
+        data.description,
+        data.website,
+        data.start_date,
+        data.end_date,
+        EventStatus::Draft as EventStatus,
+    )
+    .fetch_one(tx.deref_mut())
+    .await?;
+
+    let _: AssociatedCompanyMinimal = sqlx::query_as!(
+        AssociatedCompanyMinimal,
+        r#"
+        INSERT INTO associated_company
+            (company_id, event_id, type)
+        VALUES ($1, $2, $3)
+        RETURNING company_id,
+                  event_id;
+        "#,
+        data.company_id,
+        new_event.id,
+        Association::Organizer as Association,
+    )
+    .fetch_one(tx.deref_mut())
+    .await?;
+
+    let creator: StaffInfo = sqlx::query_as!(
+        StaffInfo,
+        r#"
+        INSERT INTO event_staff
+            ( user_id, company_id, event_id, role )
+        VALUES
+            ( $1, $2, $3, $4 )
+        RETURNING id;
+        "#,
+        data.creator_id,
+        data.company_id,
+        new_event.id,
+        EventRole::Organizer as EventRole,
+    )
+    .fetch_one(tx.deref_mut())
+    .await?;
+
+    Ok((new_event, creator.id))
+}
 
 #[derive(Clone)]
 pub struct EventRepository {
@@ -38,64 +113,7 @@ impl EventRepository {
     pub async fn create(&self, data: NewEvent) -> DbResult<Event> {
         let mut tx = self.pool.begin().await?;
 
-        let new_event: Event = sqlx::query_as!(
-            Event,
-            r#" INSERT INTO event (
-                name, description, website, start_date, end_date
-                ) VALUES 
-                ($1, $2, $3, $4, $5) RETURNING id, 
-                name, 
-                description, 
-                website, 
-                accepts_staff, 
-                start_date, 
-                end_date, 
-                avatar_url, 
-                created_at, 
-                edited_at, 
-                deleted_at;
-            "#,
-            data.name,
-            data.description,
-            data.website,
-            data.start_date,
-            data.end_date,
-        )
-        .fetch_one(tx.deref_mut())
-        .await?;
-
-        let _: AssociatedCompanyMinimal = sqlx::query_as!(
-            AssociatedCompanyMinimal,
-            r#"
-            INSERT INTO associated_company
-                (company_id, event_id, type)
-            VALUES ($1, $2, $3)
-            RETURNING company_id,
-                      event_id;
-            "#,
-            data.company_id,
-            new_event.id,
-            Association::Organizer as Association,
-        )
-        .fetch_one(tx.deref_mut())
-        .await?;
-
-        let _: StaffInfo = sqlx::query_as!(
-            StaffInfo,
-            r#"
-            INSERT INTO event_staff
-                ( user_id, company_id, event_id, role )
-            VALUES
-                ( $1, $2, $3, $4 )
-            RETURNING id;
-            "#,
-            data.creator_id,
-            data.company_id,
-            new_event.id,
-            EventRole::Organizer as EventRole,
-        )
-        .fetch_one(tx.deref_mut())
-        .await?;
+        let (new_event, _) = create_event_db_using_tx(&mut tx, data).await?;
 
         tx.commit().await?;
 
@@ -112,7 +130,19 @@ impl EventRepository {
 
         let event: Event = sqlx::query_as!(
             Event,
-            r#"SELECT * 
+            r#"SELECT id,
+                      name,
+                      description,
+                      website,
+                      accepts_staff,
+                      start_date,
+                      end_date,
+                      avatar_url,
+                      status AS "status!: EventStatus",
+                      cancellation_reason,
+                      created_at,
+                      edited_at,
+                      deleted_at
                    FROM event 
                    WHERE id = $1
                      AND deleted_at IS NULL;"#,
@@ -132,14 +162,34 @@ impl EventRepository {
             Some(accepts_staff) => {
                 sqlx::query_as!(
                     Event,
-                    r#" SELECT * 
+                    r#" SELECT id,
+                               name,
+                               description,
+                               website,
+                               accepts_staff,
+                               start_date,
+                               end_date,
+                               avatar_url,
+                               status AS "status!: EventStatus",
+                               cancellation_reason,
+                               created_at,
+                               edited_at,
+                               deleted_at
                         FROM event 
                         WHERE accepts_staff = $1 
                           AND deleted_at IS NULL
+                          AND (status <> 'draft'
+                               OR EXISTS (SELECT 1
+                                          FROM event_staff
+                                          WHERE event_staff.event_id = event.id
+                                            AND event_staff.user_id = $2
+                                            AND event_staff.role = 'organizer'
+                                            AND event_staff.deleted_at IS NULL))
                         ORDER BY name
-                        LIMIT $2 
-                        OFFSET $3;"#,
+                        LIMIT $3 
+                        OFFSET $4;"#,
                     accepts_staff,
+                    filter.user_id,
                     filter.limit,
                     filter.offset,
                 )
@@ -149,12 +199,32 @@ impl EventRepository {
             None => {
                 sqlx::query_as!(
                     Event,
-                    r#" SELECT * 
+                    r#" SELECT id,
+                               name,
+                               description,
+                               website,
+                               accepts_staff,
+                               start_date,
+                               end_date,
+                               avatar_url,
+                               status AS "status!: EventStatus",
+                               cancellation_reason,
+                               created_at,
+                               edited_at,
+                               deleted_at
                         FROM event
                         WHERE deleted_at IS NULL 
+                          AND (status <> 'draft'
+                               OR EXISTS (SELECT 1
+                                          FROM event_staff
+                                          WHERE event_staff.event_id = event.id
+                                            AND event_staff.user_id = $1
+                                            AND event_staff.role = 'organizer'
+                                            AND event_staff.deleted_at IS NULL))
                         ORDER BY name
-                        LIMIT $1 
-                        OFFSET $2;"#,
+                        LIMIT $2 
+                        OFFSET $3;"#,
+                    filter.user_id,
                     filter.limit,
                     filter.offset,
                 )
@@ -166,10 +236,59 @@ impl EventRepository {
         Ok(events)
     }
 
+    /// The timesheets of the event that would change with the new dates.
+    pub async fn read_date_change_impact(
+        &self,
+        event_id: Uuid,
+        data: &TimeRange,
+    ) -> DbResult<Vec<DateChangeImpact>> {
+        let executor = self.pool.as_ref();
+
+        let impact = sqlx::query_as!(
+            DateChangeImpact,
+            r#"
+            SELECT timesheet.id AS timesheet_id,
+                   user_record.name AS user_name,
+                   timesheet.status AS "approval_status!: ApprovalStatus",
+                   timesheet.is_editable,
+                   timesheet.total_hours,
+                   COALESCE(SUM(workday.total_hours)
+                            FILTER (WHERE workday.date < $2 OR workday.date > $3),
+                            0) AS "out_of_range_hours!",
+                   COUNT(workday.date)
+                       FILTER (WHERE (workday.date < $2 OR workday.date > $3)
+                                 AND (workday.total_hours > 0
+                                      OR COALESCE(workday.comment, '') <> ''))
+                       AS "out_of_range_days!"
+            FROM timesheet
+                JOIN user_record ON user_record.id = timesheet.user_id
+                LEFT JOIN workday ON workday.timesheet_id = timesheet.id
+                                 AND workday.deleted_at IS NULL
+            WHERE timesheet.event_id = $1
+              AND timesheet.deleted_at IS NULL
+              AND (timesheet.start_date <> $2 OR timesheet.end_date <> $3)
+            GROUP BY timesheet.id, user_record.name
+            ORDER BY user_record.name;
+            "#,
+            event_id,
+            data.start_date,
+            data.end_date,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(impact)
+    }
+
+    /// Moves the editable timesheets of the event to its new dates. Submitted
+    /// and accepted sheets are left as they are. The workdays outside of the
+    /// new dates are only dropped when nothing was logged in them, the rest is
+    /// kept and flagged for the manager to sort out.
     pub async fn update_timesheet_range_for_event(
         &self,
         event_id: Uuid,
         data: TimeRange,
+        editor_id: Option<Uuid>,
         mut tx: Transaction<'_, Postgres>,
     ) -> DbResult<()> {
         let updated_sheets = sqlx::query_as!(
@@ -181,6 +300,8 @@ impl EventRepository {
                 edited_at = NOW()
             WHERE event_id = $3
               AND deleted_at IS NULL
+              AND is_editable
+              AND status <> 'accepted'
             RETURNING id,
                       start_date,
                       end_date;
@@ -192,61 +313,26 @@ impl EventRepository {
         .fetch_all(tx.deref_mut())
         .await?;
 
-        for sheet in updated_sheets.into_iter() {
-            let start_date_time: DateTime<Utc> = Utc
-                .with_ymd_and_hms(
-                    sheet.start_date.year(),
-                    sheet.start_date.month(),
-                    sheet.start_date.day(),
-                    0,
-                    0,
-                    0,
+        let flag_reason = format!(
+            "Logged outside of the event's new dates ({} - {}).",
+            data.start_date.format("%d.%m.%Y"),
+            data.end_date.format("%d.%m.%Y")
+        );
+
+        let flagged_by = match editor_id {
+            Some(editor_id) => {
+                sqlx::query_scalar!(
+                    r#"SELECT user_id FROM event_staff WHERE id = $1;"#,
+                    editor_id,
                 )
-                .unwrap();
-            let end_date_time: DateTime<Utc> = Utc
-                .with_ymd_and_hms(
-                    sheet.end_date.year(),
-                    sheet.end_date.month(),
-                    sheet.end_date.day(),
-                    0,
-                    0,
-                    0,
-                )
-                .unwrap();
-            sqlx::query!(
-                r#"DELETE FROM workday
-                   WHERE timesheet_id = $1 AND (date < $2 OR date > $3);"#,
-                sheet.id,
-                sheet.start_date,
-                sheet.end_date,
-            )
-            .execute(tx.deref_mut())
-            .await?;
-
-            sqlx::query!(
-                r#"INSERT INTO workday (timesheet_id, date)
-                 SELECT $1, curr_date
-                 FROM generate_series($2, $3, interval '1 day') as curr_date
-                 ON CONFLICT DO NOTHING;"#,
-                sheet.id,
-                start_date_time,
-                end_date_time,
-            )
-            .execute(tx.deref_mut())
-            .await?;
+                .fetch_optional(tx.deref_mut())
+                .await?
+            }
+            None => None,
+        };
 
-            sqlx::query!(
-                r#"
-                UPDATE timesheet 
-                SET total_hours = (SELECT SUM(total_hours) 
-                                  FROM workday 
-                                  WHERE workday.timesheet_id = $1
-                                  GROUP BY timesheet_id)
-                WHERE id = $1 AND deleted_at IS NULL;"#,
-                sheet.id,
-            )
-            .execute(tx.deref_mut())
-            .await?;
+        for sheet in updated_sheets.iter() {
+            realign_workdays_db_using_tx(&mut tx, sheet, &flag_reason, flagged_by).await?;
         }
 
         tx.commit().await?;
@@ -291,6 +377,8 @@ impl EventRepository {
                 start_date, 
                 end_date, 
                 avatar_url, 
+                status AS "status!: EventStatus", 
+                cancellation_reason, 
                 created_at, 
                 edited_at, 
                 deleted_at;
@@ -319,7 +407,7 @@ impl EventRepository {
                 end_date: data.end_date.unwrap_or(result_event.end_date),
             };
 
-            self.update_timesheet_range_for_event(event_id, time_range, tx)
+            self.update_timesheet_range_for_event(event_id, time_range, data.editor_id, tx)
                 .await?;
         } else {
             tx.commit().await?;
@@ -346,6 +434,8 @@ impl EventRepository {
                          start_date, 
                          end_date, 
                          avatar_url, 
+                         status AS "status!: EventStatus", 
+                         cancellation_reason, 
                          created_at, 
                          edited_at, 
                          deleted_at;"#,
@@ -357,35 +447,150 @@ impl EventRepository {
         Ok(())
     }
 
-    pub async fn delete(&self, event_id: Uuid) -> DbResult<()> {
-        let executor = self.pool.as_ref();
+    /// Moves the event along its lifecycle. Cancelling closes the timesheets
+    /// which were not approved yet and rejects the submitted ones, both
+    /// cancelling and completing leave a comment on the event and notify its
+    /// accepted staff about the change.
+    pub async fn change_status(&self, event_id: Uuid, data: EventStatusData) -> DbResult<Event> {
+        let mut tx = self.pool.begin().await?;
 
-        let result = sqlx::query_as!(
+        let current = sqlx::query!(
+            r#"SELECT status AS "status!: EventStatus"
+               FROM event
+               WHERE id = $1
+                 AND deleted_at IS NULL
+               FOR UPDATE;"#,
+            event_id,
+        )
+        .fetch_optional(tx.deref_mut())
+        .await?;
+
+        if current.is_none() {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        let is_cancellation = data.status == EventStatus::Cancelled;
+        if !current
+            .expect("Should be some.")
+            .status
+            .can_transition_to(&data.status)
+            || is_cancellation != data.cancellation_reason.is_some()
+        {
+            return Err(sqlx::Error::TypeNotFound {
+                type_name: "User Error".to_string(),
+            });
+        }
+
+        let event = sqlx::query_as!(
             Event,
             r#"UPDATE event
-            SET deleted_at = NOW(), edited_at = NOW()
-            WHERE id = $1
-            AND deleted_at IS NULL
-            RETURNING id, 
-                name, 
-                description, 
-                website, 
-                accepts_staff, 
-                start_date, 
-                end_date, 
-                avatar_url, 
-                created_at, 
-                edited_at, 
-                deleted_at;"#,
+               SET status = $1,
+                   cancellation_reason = $2,
+                   accepts_staff = accepts_staff AND NOT $3,
+                   edited_at = NOW()
+               WHERE id = $4
+               RETURNING id, 
+                         name, 
+                         description, 
+                         website, 
+                         accepts_staff, 
+                         start_date, 
+                         end_date, 
+                         avatar_url, 
+                         status AS "status!: EventStatus", 
+                         cancellation_reason, 
+                         created_at, 
+                         edited_at, 
+                         deleted_at;"#,
+            data.status.clone() as EventStatus,
+            data.cancellation_reason,
+            data.status.is_closed(),
             event_id,
         )
-        .fetch_optional(executor)
+        .fetch_one(tx.deref_mut())
         .await?;
 
-        if result.is_none() {
-            return Err(sqlx::Error::RowNotFound);
+        let announcement = match event.status {
+            EventStatus::Cancelled => Some(format!(
+                "This event has been cancelled: {}",
+                event.cancellation_reason.clone().unwrap_or_default()
+            )),
+            EventStatus::Completed => Some(
+                "This event has been completed. Please submit your final timesheets.".to_string(),
+            ),
+            _ => None,
+        };
+
+        // Submitted sheets are rejected so that they don't wait for a review.
+        if is_cancellation {
+            sqlx::query!(
+                r#"UPDATE timesheet
+                   SET is_editable = false,
+                       status = CASE WHEN status = 'pending'
+                                     THEN 'rejected'
+                                     ELSE status
+                                END,
+                       manager_note = CASE WHEN status = 'pending'
+                                           THEN 'The event has been cancelled.'
+                                           ELSE manager_note
+                                      END,
+                       edited_at = NOW()
+                   WHERE event_id = $1
+                     AND status <> 'accepted'
+                     AND (is_editable OR status = 'pending')
+                     AND deleted_at IS NULL;"#,
+                event_id,
+            )
+            .execute(tx.deref_mut())
+            .await?;
         }
 
+        if let Some(content) = announcement {
+            sqlx::query!(
+                r#"INSERT INTO comment (author_id, event_id, content)
+                   VALUES ($1, $2, $3);"#,
+                data.changed_by,
+                event_id,
+                content,
+            )
+            .execute(tx.deref_mut())
+            .await?;
+
+            // The one who changed the status doesn't need to be told about it.
+            let staff_ids = sqlx::query_scalar!(
+                r#"SELECT id
+                   FROM event_staff
+                   WHERE event_id = $1
+                     AND status = 'accepted'
+                     AND user_id <> $2
+                     AND deleted_at IS NULL;"#,
+                event_id,
+                data.changed_by,
+            )
+            .fetch_all(tx.deref_mut())
+            .await?;
+
+            for staff_id in staff_ids {
+                notification_repo::emit_db_using_tx(
+                    &mut tx,
+                    NotificationEvent::EventStatusChanged { event_id, staff_id },
+                )
+                .await?;
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(event)
+    }
+
+    pub async fn delete(&self, event_id: Uuid, deleted_by: Option<Uuid>) -> DbResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        delete_db_using_tx(&mut tx, DeletedEntity::Event, event_id, None, deleted_by).await?;
+
+        tx.commit().await?;
+
         Ok(())
     }
 }
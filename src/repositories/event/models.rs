@@ -1,8 +1,10 @@
 use chrono::NaiveDate;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use sqlx::{types::chrono::NaiveDateTime, FromRow};
 use uuid::Uuid;
 
+use crate::models::{ApprovalStatus, EventStatus};
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct NewEvent {
     pub name: String,
@@ -24,12 +26,14 @@ pub struct Event {
     pub start_date: NaiveDate,
     pub end_date: NaiveDate,
     pub avatar_url: String,
+    pub status: EventStatus,
+    pub cancellation_reason: Option<String>,
     pub created_at: NaiveDateTime,
     pub edited_at: NaiveDateTime,
     pub deleted_at: Option<NaiveDateTime>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct EventData {
     pub name: Option<String>,
     pub description: Option<String>,
@@ -38,11 +42,44 @@ pub struct EventData {
     pub end_date: Option<NaiveDate>,
     pub accepts_staff: Option<bool>,
     pub avatar_url: Option<String>,
+    // The organizer moving the event, the logged workdays outside of
+    // the new dates are flagged in their name.
+    pub editor_id: Option<Uuid>, // staff table ID
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct EventFilter {
     pub accepts_staff: Option<bool>,
+    // Drafts are only listed for the user organizing them.
+    pub user_id: Option<Uuid>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
 }
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct EventStatusData {
+    pub status: EventStatus,
+    pub cancellation_reason: Option<String>,
+    pub changed_by: Uuid, // user table ID
+}
+
+/// How moving the event to new dates affects one of its timesheets.
+#[derive(Debug, FromRow, Clone)]
+pub struct DateChangeImpact {
+    pub timesheet_id: Uuid,
+    pub user_name: String,
+    pub approval_status: ApprovalStatus,
+    pub is_editable: bool,
+    pub total_hours: f32,
+    /// The hours logged on the days outside of the new dates.
+    pub out_of_range_hours: f32,
+    /// The days outside of the new dates with some hours or a comment.
+    pub out_of_range_days: i64,
+}
+
+impl DateChangeImpact {
+    /// Submitted and accepted sheets keep their dates.
+    pub fn is_locked(&self) -> bool {
+        !self.is_editable || self.approval_status == ApprovalStatus::Accepted
+    }
+}
@@ -0,0 +1,227 @@
+use crate::common::DbResult;
+use crate::models::{ApprovalStatus, EmploymentContract, EventRole};
+use async_trait::async_trait;
+use sqlx::postgres::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::models::{BudgetCompany, BudgetLine, BudgetTimesheet, ForecastStaff, NewBudgetLine};
+
+#[derive(Clone)]
+pub struct EventBudgetRepository {
+    pub pool: Arc<PgPool>,
+}
+
+#[async_trait]
+impl crate::repositories::repository::DbRepository for EventBudgetRepository {
+    /// Database repository constructor
+    fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Method allowing the database repository to disconnect from the database pool gracefully
+    async fn disconnect(&mut self) -> () {
+        self.pool.close().await;
+    }
+}
+
+impl EventBudgetRepository {
+    pub async fn create(&self, data: NewBudgetLine) -> DbResult<BudgetLine> {
+        let executor = self.pool.as_ref();
+
+        let line_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO event_budget_line
+                (event_id, company_id, role, date, headcount, hours, hourly_rate)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING id;
+            "#,
+            data.event_id,
+            data.company_id,
+            data.role as EventRole,
+            data.date,
+            data.headcount,
+            data.hours,
+            data.hourly_rate,
+        )
+        .fetch_one(executor)
+        .await?;
+
+        self.read_one(line_id).await
+    }
+
+    pub async fn read_one(&self, line_id: Uuid) -> DbResult<BudgetLine> {
+        let executor = self.pool.as_ref();
+
+        let line = sqlx::query_as!(
+            BudgetLine,
+            r#"
+            SELECT event_budget_line.id,
+                   event_budget_line.event_id,
+                   event_budget_line.company_id,
+                   company.name AS "company_name?",
+                   event_budget_line.role AS "role!: EventRole",
+                   event_budget_line.date,
+                   event_budget_line.headcount,
+                   event_budget_line.hours,
+                   event_budget_line.hourly_rate,
+                   event_budget_line.created_at,
+                   event_budget_line.edited_at,
+                   event_budget_line.deleted_at
+            FROM event_budget_line
+                LEFT JOIN company ON event_budget_line.company_id = company.id
+            WHERE event_budget_line.id = $1
+              AND event_budget_line.deleted_at IS NULL;
+            "#,
+            line_id,
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(line)
+    }
+
+    pub async fn read_all_for_event(&self, event_id: Uuid) -> DbResult<Vec<BudgetLine>> {
+        let executor = self.pool.as_ref();
+
+        let lines = sqlx::query_as!(
+            BudgetLine,
+            r#"
+            SELECT event_budget_line.id,
+                   event_budget_line.event_id,
+                   event_budget_line.company_id,
+                   company.name AS "company_name?",
+                   event_budget_line.role AS "role!: EventRole",
+                   event_budget_line.date,
+                   event_budget_line.headcount,
+                   event_budget_line.hours,
+                   event_budget_line.hourly_rate,
+                   event_budget_line.created_at,
+                   event_budget_line.edited_at,
+                   event_budget_line.deleted_at
+            FROM event_budget_line
+                LEFT JOIN company ON event_budget_line.company_id = company.id
+            WHERE event_budget_line.event_id = $1
+              AND event_budget_line.deleted_at IS NULL
+            ORDER BY event_budget_line.date NULLS FIRST,
+                     company.name NULLS FIRST,
+                     event_budget_line.role,
+                     event_budget_line.created_at;
+            "#,
+            event_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(lines)
+    }
+
+    pub async fn delete(&self, line_id: Uuid) -> DbResult<()> {
+        let executor = self.pool.as_ref();
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE event_budget_line
+            SET deleted_at = NOW(),
+                edited_at = NOW()
+            WHERE id = $1
+              AND deleted_at IS NULL;
+            "#,
+            line_id,
+        )
+        .execute(executor)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        Ok(())
+    }
+
+    pub async fn read_companies(&self, event_id: Uuid) -> DbResult<Vec<BudgetCompany>> {
+        let executor = self.pool.as_ref();
+
+        let companies = sqlx::query_as!(
+            BudgetCompany,
+            r#"
+            SELECT company.id, company.name
+            FROM associated_company
+                INNER JOIN company ON associated_company.company_id = company.id
+            WHERE associated_company.event_id = $1
+              AND associated_company.deleted_at IS NULL
+              AND company.deleted_at IS NULL
+            ORDER BY company.name;
+            "#,
+            event_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(companies)
+    }
+
+    pub async fn read_forecast_staff(&self, event_id: Uuid) -> DbResult<Vec<ForecastStaff>> {
+        let executor = self.pool.as_ref();
+
+        let staff = sqlx::query_as!(
+            ForecastStaff,
+            r#"
+            SELECT event_staff.user_id,
+                   event_staff.company_id,
+                   company.name AS company_name,
+                   event_staff.role AS "role!: EventRole",
+                   employment.type AS "employment_type!: EmploymentContract",
+                   employment.hourly_wage
+            FROM event_staff
+                INNER JOIN company ON event_staff.company_id = company.id
+                INNER JOIN employment ON event_staff.user_id = employment.user_id
+                                     AND event_staff.company_id = employment.company_id
+            WHERE event_staff.event_id = $1
+              AND event_staff.status = 'accepted'
+              AND event_staff.deleted_at IS NULL
+              AND employment.deleted_at IS NULL
+            ORDER BY company.name, event_staff.user_id;
+            "#,
+            event_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(staff)
+    }
+
+    /// Timesheets which were submitted for an approval or accepted.
+    pub async fn read_actual_timesheets(&self, event_id: Uuid) -> DbResult<Vec<BudgetTimesheet>> {
+        let executor = self.pool.as_ref();
+
+        let timesheets = sqlx::query_as!(
+            BudgetTimesheet,
+            r#"
+            SELECT timesheet.id,
+                   timesheet.user_id,
+                   timesheet.company_id,
+                   company.name AS company_name,
+                   COALESCE(event_staff.role, 'staff') AS "role!: EventRole",
+                   timesheet.status AS "status!: ApprovalStatus",
+                   timesheet.start_date,
+                   timesheet.end_date
+            FROM timesheet
+                INNER JOIN company ON timesheet.company_id = company.id
+                LEFT JOIN event_staff ON timesheet.event_id = event_staff.event_id
+                                     AND timesheet.user_id = event_staff.user_id
+                                     AND timesheet.company_id = event_staff.company_id
+                                     AND event_staff.deleted_at IS NULL
+            WHERE timesheet.event_id = $1
+              AND timesheet.status IN ('pending', 'accepted')
+              AND timesheet.deleted_at IS NULL
+            ORDER BY company.name, timesheet.user_id;
+            "#,
+            event_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(timesheets)
+    }
+}
@@ -0,0 +1,2 @@
+pub mod event_budget_repo;
+pub mod models;
@@ -0,0 +1,68 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::Deserialize;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::models::{ApprovalStatus, EmploymentContract, EventRole};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewBudgetLine {
+    pub event_id: Uuid,
+    pub company_id: Option<Uuid>,
+    pub role: EventRole,
+    pub date: Option<NaiveDate>,
+    pub headcount: i32,
+    pub hours: f32,
+    pub hourly_rate: f64,
+}
+
+/// Planned staffing, `headcount` people of the role working `hours` a day.
+/// A line without a date is planned for every day of the event, a line
+/// without a company for any company.
+#[derive(Debug, FromRow, Clone)]
+pub struct BudgetLine {
+    pub id: Uuid,
+    pub event_id: Uuid,
+    pub company_id: Option<Uuid>,
+    pub company_name: Option<String>,
+    pub role: EventRole,
+    pub date: Option<NaiveDate>,
+    pub headcount: i32,
+    pub hours: f32,
+    pub hourly_rate: f64,
+    pub created_at: NaiveDateTime,
+    pub edited_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+/// An accepted staff member of the event along with their employment.
+#[derive(Debug, FromRow, Clone)]
+pub struct ForecastStaff {
+    pub user_id: Uuid,
+    pub company_id: Uuid,
+    pub company_name: String,
+    pub role: EventRole,
+    pub employment_type: EmploymentContract,
+    pub hourly_wage: f64,
+}
+
+/// A submitted or accepted timesheet of the event, the role is the one the
+/// user has at the event.
+#[derive(Debug, FromRow, Clone)]
+pub struct BudgetTimesheet {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub company_id: Uuid,
+    pub company_name: String,
+    pub role: EventRole,
+    pub status: ApprovalStatus,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+}
+
+/// A company associated with the event, budget lines can be planned for it.
+#[derive(Debug, FromRow, Clone)]
+pub struct BudgetCompany {
+    pub id: Uuid,
+    pub name: String,
+}
@@ -1,12 +1,17 @@
 use crate::common::DbResult;
 use async_trait::async_trait;
+use futures::stream::BoxStream;
 use sqlx::postgres::PgPool;
 use std::{ops::DerefMut, sync::Arc};
 use uuid::Uuid;
 
 use crate::repositories::event_staff::models::StaffInfo;
+use crate::repositories::notification::{models::NotificationEvent, notification_repo};
+use crate::repositories::timesheet::timesheet_repo;
 
-use super::models::{NewStaff, StaffData, StaffExtended, StaffFilter, StaffUserCompanyFlattened};
+use super::models::{
+    NewStaff, StaffData, StaffExport, StaffExtended, StaffFilter, StaffUserCompanyFlattened,
+};
 
 use crate::models::{AcceptanceStatus, EventRole, Gender, UserRole, UserStatus};
 
@@ -283,6 +288,33 @@ impl StaffRepository {
         Ok(staff.into_iter().map(|s| s.into()).collect())
     }
 
+    /// Accepted staff of the event, read row by row for exports.
+    pub fn stream_accepted_for_event(
+        &self,
+        event_id: Uuid,
+    ) -> BoxStream<'_, DbResult<StaffExport>> {
+        sqlx::query_as!(
+            StaffExport,
+            r#"
+            SELECT user_record.name,
+                   user_record.email,
+                   user_record.birth,
+                   company.name AS company_name,
+                   event_staff.role AS "role!: EventRole",
+                   event_staff.edited_at AS accepted_at
+            FROM event_staff
+                INNER JOIN user_record ON event_staff.user_id = user_record.id
+                INNER JOIN company ON event_staff.company_id = company.id
+            WHERE event_staff.event_id = $1
+              AND event_staff.status = 'accepted'
+              AND event_staff.deleted_at IS NULL
+            ORDER BY user_record.name;
+            "#,
+            event_id,
+        )
+        .fetch(self.pool.as_ref())
+    }
+
     pub async fn update(&self, event_staff_id: Uuid, data: StaffData) -> DbResult<StaffExtended> {
         if data.role.is_none() && data.status.is_none() {
             // TODO - better error
@@ -293,8 +325,20 @@ impl StaffRepository {
 
         let mut tx = self.pool.begin().await?;
 
-        let staff_info: Option<StaffInfo> = sqlx::query_as!(
-            StaffInfo,
+        let previous_status = sqlx::query_scalar!(
+            r#"SELECT status AS "status!: AcceptanceStatus"
+            FROM event_staff
+            WHERE id = $1
+              AND deleted_at IS NULL
+            FOR UPDATE;"#,
+            event_staff_id,
+        )
+        .fetch_optional(tx.deref_mut())
+        .await?;
+
+        let status_change = data.status.clone();
+
+        let staff_info = sqlx::query!(
             r#" UPDATE event_staff SET 
                 role = COALESCE($1, role), 
                 status = COALESCE($2, status), 
@@ -302,7 +346,7 @@ impl StaffRepository {
                 edited_at = now() 
             WHERE id = $4 
               AND deleted_at IS NULL
-            RETURNING id;
+            RETURNING id, user_id, company_id, event_id;
             "#,
             data.role as Option<EventRole>,
             data.status as Option<AcceptanceStatus>,
@@ -315,35 +359,78 @@ impl StaffRepository {
         if staff_info.is_none() {
             return Err(sqlx::Error::RowNotFound);
         }
+        let staff_info = staff_info.expect("Should be some.");
+
+        // The timesheet follows the staff member in and out of the event.
+        if status_change == Some(AcceptanceStatus::Accepted) {
+            timesheet_repo::provision_db_using_tx(
+                &mut tx,
+                staff_info.user_id,
+                staff_info.company_id,
+                staff_info.event_id,
+            )
+            .await?;
+        } else if status_change.is_some() && previous_status == Some(AcceptanceStatus::Accepted) {
+            timesheet_repo::release_db_using_tx(
+                &mut tx,
+                staff_info.user_id,
+                staff_info.company_id,
+                staff_info.event_id,
+            )
+            .await?;
+        }
 
-        let updated_staff = self
-            .read_one_db(tx.deref_mut(), staff_info.unwrap().id)
+        // Only an actual decision is worth telling the applicant about.
+        if status_change.is_some()
+            && status_change != previous_status
+            && status_change != Some(AcceptanceStatus::Pending)
+        {
+            notification_repo::emit_db_using_tx(
+                &mut tx,
+                NotificationEvent::StaffDecided {
+                    staff_id: event_staff_id,
+                },
+            )
             .await?;
+        }
+
+        let updated_staff = self.read_one_db(tx.deref_mut(), staff_info.id).await?;
         tx.commit().await?;
         Ok(updated_staff)
     }
 
+    /// Removes the staff member from the event along with their timesheet,
+    /// unless they already logged some work in it.
     pub async fn delete(&self, event_staff_id: Uuid) -> DbResult<()> {
-        let executor = self.pool.as_ref();
+        let mut tx = self.pool.begin().await?;
 
-        let result: Option<StaffInfo> = sqlx::query_as!(
-            StaffInfo,
+        let result = sqlx::query!(
             r#" UPDATE event_staff SET 
                 deleted_at = now(), 
                 edited_at = now() 
             WHERE id = $1 
               AND deleted_at IS NULL
-            RETURNING id;
+            RETURNING id, user_id, company_id, event_id;
             "#,
             event_staff_id,
         )
-        .fetch_optional(executor)
+        .fetch_optional(tx.deref_mut())
         .await?;
 
         if result.is_none() {
             return Err(sqlx::Error::RowNotFound);
         }
 
+        let staff_info = result.expect("Should be some.");
+        timesheet_repo::release_db_using_tx(
+            &mut tx,
+            staff_info.user_id,
+            staff_info.company_id,
+            staff_info.event_id,
+        )
+        .await?;
+        tx.commit().await?;
+
         Ok(())
     }
 
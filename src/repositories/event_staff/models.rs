@@ -197,3 +197,14 @@ impl From<StaffUserCompanyFlattened> for StaffExtended {
         }
     }
 }
+
+/// Accepted staff member of an event as exported to a spreadsheet.
+#[derive(Debug, FromRow, Clone)]
+pub struct StaffExport {
+    pub name: String,
+    pub email: String,
+    pub birth: NaiveDate,
+    pub company_name: String,
+    pub role: EventRole,
+    pub accepted_at: NaiveDateTime,
+}
@@ -0,0 +1,497 @@
+use crate::{
+    common::DbResult,
+    models::{EventStatus, TaskPriority},
+    repositories::event::{
+        event_repo::create_event_db_using_tx,
+        models::{Event, NewEvent},
+    },
+};
+use async_trait::async_trait;
+use chrono::Duration;
+use sqlx::postgres::PgPool;
+use std::{collections::HashMap, ops::DerefMut, sync::Arc};
+use uuid::Uuid;
+
+use super::models::{EventTemplate, EventTemplateTask, NewEventFromTemplate, NewEventTemplate};
+
+#[derive(Clone)]
+pub struct EventTemplateRepository {
+    pub pool: Arc<PgPool>,
+}
+
+#[async_trait]
+impl crate::repositories::repository::DbRepository for EventTemplateRepository {
+    /// Database repository constructor
+    fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Method allowing the database repository to disconnect from the database pool gracefully
+    async fn disconnect(&mut self) -> () {
+        self.pool.close().await;
+    }
+}
+
+impl EventTemplateRepository {
+    /// Takes a snapshot of the event, its tasks, associated companies and staff.
+    pub async fn create(&self, data: NewEventTemplate) -> DbResult<EventTemplate> {
+        let mut tx = self.pool.begin().await?;
+
+        let template = sqlx::query_as!(
+            EventTemplate,
+            r#"INSERT INTO event_template (
+                company_id, source_event_id, name, description,
+                website, accepts_staff, duration_days
+            )
+            SELECT $1, id, $2, description, website, accepts_staff, end_date - start_date
+            FROM event
+            WHERE id = $3
+              AND deleted_at IS NULL
+            RETURNING id,
+                      company_id,
+                      source_event_id,
+                      name,
+                      description,
+                      website,
+                      accepts_staff,
+                      duration_days,
+                      created_at,
+                      edited_at,
+                      deleted_at;"#,
+            data.company_id,
+            data.name,
+            data.event_id,
+        )
+        .fetch_optional(tx.deref_mut())
+        .await?;
+
+        if template.is_none() {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        let template = template.expect("Should be some.");
+
+        let tasks = sqlx::query!(
+            r#"SELECT task.id,
+                      task.parent_task_id,
+                      task.title,
+                      task.description,
+                      task.priority AS "priority!: TaskPriority",
+                      task.accepts_staff,
+                      task.estimated_hours,
+                      (EXTRACT(EPOCH FROM task.due_at - event.start_date::timestamp) / 60)::integer
+                          AS due_offset_minutes,
+                      (ROW_NUMBER() OVER (ORDER BY task.status, task.position, task.title) - 1)::integer
+                          AS "position!"
+            FROM task
+            INNER JOIN event ON task.event_id = event.id
+            WHERE task.event_id = $1
+              AND task.deleted_at IS NULL;"#,
+            data.event_id,
+        )
+        .fetch_all(tx.deref_mut())
+        .await?;
+
+        // Maps the IDs of event tasks to the IDs of their template copies.
+        let mut task_ids: HashMap<Uuid, Uuid> = HashMap::new();
+        for task in tasks.iter() {
+            let template_task_id = sqlx::query_scalar!(
+                r#"INSERT INTO event_template_task (
+                    template_id, title, description, priority, accepts_staff,
+                    estimated_hours, due_offset_minutes, position
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                RETURNING id;"#,
+                template.id,
+                task.title,
+                task.description,
+                task.priority.clone() as TaskPriority,
+                task.accepts_staff,
+                task.estimated_hours,
+                task.due_offset_minutes,
+                task.position,
+            )
+            .fetch_one(tx.deref_mut())
+            .await?;
+
+            task_ids.insert(task.id, template_task_id);
+
+            sqlx::query!(
+                r#"INSERT INTO event_template_checklist_item (template_task_id, content, position)
+                SELECT $1, content, position
+                FROM task_checklist_item
+                WHERE task_id = $2
+                  AND deleted_at IS NULL;"#,
+                template_task_id,
+                task.id,
+            )
+            .execute(tx.deref_mut())
+            .await?;
+        }
+
+        // Parents are linked once all the tasks have their copies.
+        for task in tasks.iter() {
+            let parent_id = task
+                .parent_task_id
+                .and_then(|parent_task_id| task_ids.get(&parent_task_id));
+            if let Some(parent_id) = parent_id {
+                sqlx::query!(
+                    r#"UPDATE event_template_task
+                    SET parent_id = $1
+                    WHERE id = $2;"#,
+                    parent_id,
+                    task_ids[&task.id],
+                )
+                .execute(tx.deref_mut())
+                .await?;
+            }
+        }
+
+        let dependencies = sqlx::query!(
+            r#"SELECT task_dependency.task_id,
+                      task_dependency.blocker_id
+            FROM task_dependency
+            INNER JOIN task ON task_dependency.task_id = task.id
+            WHERE task.event_id = $1
+              AND task_dependency.deleted_at IS NULL;"#,
+            data.event_id,
+        )
+        .fetch_all(tx.deref_mut())
+        .await?;
+
+        for dependency in dependencies.iter() {
+            if let (Some(task_id), Some(blocker_id)) = (
+                task_ids.get(&dependency.task_id),
+                task_ids.get(&dependency.blocker_id),
+            ) {
+                sqlx::query!(
+                    r#"INSERT INTO event_template_task_dependency (template_task_id, blocker_id)
+                    VALUES ($1, $2);"#,
+                    task_id,
+                    blocker_id,
+                )
+                .execute(tx.deref_mut())
+                .await?;
+            }
+        }
+
+        sqlx::query!(
+            r#"INSERT INTO event_template_company (template_id, company_id, type)
+            SELECT $1, company_id, type
+            FROM associated_company
+            WHERE event_id = $2
+              AND deleted_at IS NULL;"#,
+            template.id,
+            data.event_id,
+        )
+        .execute(tx.deref_mut())
+        .await?;
+
+        // Organizers are kept even before they get accepted, as the creator
+        // of an event never goes through the acceptance.
+        sqlx::query!(
+            r#"INSERT INTO event_template_staff (template_id, user_id, company_id, role)
+            SELECT DISTINCT ON (user_id, company_id) $1, user_id, company_id, role
+            FROM event_staff
+            WHERE event_id = $2
+              AND deleted_at IS NULL
+              AND (status = 'accepted' OR role = 'organizer')
+            ORDER BY user_id, company_id, role DESC;"#,
+            template.id,
+            data.event_id,
+        )
+        .execute(tx.deref_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(template)
+    }
+
+    pub async fn read_one(&self, template_id: Uuid) -> DbResult<EventTemplate> {
+        let executor = self.pool.as_ref();
+
+        let template = sqlx::query_as!(
+            EventTemplate,
+            r#"SELECT id,
+                      company_id,
+                      source_event_id,
+                      name,
+                      description,
+                      website,
+                      accepts_staff,
+                      duration_days,
+                      created_at,
+                      edited_at,
+                      deleted_at
+            FROM event_template
+            WHERE id = $1
+              AND deleted_at IS NULL;"#,
+            template_id,
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(template)
+    }
+
+    pub async fn read_all_for_company(&self, company_id: Uuid) -> DbResult<Vec<EventTemplate>> {
+        let executor = self.pool.as_ref();
+
+        let templates = sqlx::query_as!(
+            EventTemplate,
+            r#"SELECT id,
+                      company_id,
+                      source_event_id,
+                      name,
+                      description,
+                      website,
+                      accepts_staff,
+                      duration_days,
+                      created_at,
+                      edited_at,
+                      deleted_at
+            FROM event_template
+            WHERE company_id = $1
+              AND deleted_at IS NULL
+            ORDER BY name;"#,
+            company_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(templates)
+    }
+
+    pub async fn read_tasks(&self, template_id: Uuid) -> DbResult<Vec<EventTemplateTask>> {
+        let executor = self.pool.as_ref();
+
+        let tasks = sqlx::query_as!(
+            EventTemplateTask,
+            r#"SELECT id,
+                      template_id,
+                      parent_id,
+                      title,
+                      description,
+                      priority AS "priority!: TaskPriority",
+                      accepts_staff,
+                      estimated_hours,
+                      due_offset_minutes,
+                      position,
+                      created_at,
+                      edited_at,
+                      deleted_at
+            FROM event_template_task
+            WHERE template_id = $1
+              AND deleted_at IS NULL
+            ORDER BY position;"#,
+            template_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(tasks)
+    }
+
+    /// Opens a new edition of the event. All task deadlines keep their
+    /// distance from the start of the event.
+    pub async fn instantiate(
+        &self,
+        template_id: Uuid,
+        data: NewEventFromTemplate,
+    ) -> DbResult<Event> {
+        let template = self.read_one(template_id).await?;
+        let tasks = self.read_tasks(template_id).await?;
+
+        let mut tx = self.pool.begin().await?;
+
+        let new_event = NewEvent {
+            name: data.name,
+            description: template.description,
+            website: template.website,
+            start_date: data.start_date,
+            end_date: data.start_date + Duration::days(i64::from(template.duration_days)),
+            creator_id: data.creator_id,
+            company_id: data.company_id,
+        };
+        let (event, creator_id) = create_event_db_using_tx(&mut tx, new_event).await?;
+
+        let event = sqlx::query_as!(
+            Event,
+            r#"UPDATE event
+            SET accepts_staff = $1
+            WHERE id = $2
+            RETURNING id,
+                      name,
+                      description,
+                      website,
+                      accepts_staff,
+                      start_date,
+                      end_date,
+                      avatar_url,
+                      status AS "status!: EventStatus",
+                      cancellation_reason,
+                      created_at,
+                      edited_at,
+                      deleted_at;"#,
+            template.accepts_staff,
+            event.id,
+        )
+        .fetch_one(tx.deref_mut())
+        .await?;
+
+        // The company creating the edition is already its organizer.
+        sqlx::query!(
+            r#"INSERT INTO associated_company (company_id, event_id, type)
+            SELECT event_template_company.company_id, $1, event_template_company.type
+            FROM event_template_company
+            INNER JOIN company ON event_template_company.company_id = company.id
+            WHERE event_template_company.template_id = $2
+              AND event_template_company.deleted_at IS NULL
+              AND company.deleted_at IS NULL
+            ON CONFLICT DO NOTHING;"#,
+            event.id,
+            template_id,
+        )
+        .execute(tx.deref_mut())
+        .await?;
+
+        sqlx::query!(
+            r#"INSERT INTO event_staff (user_id, company_id, event_id, role)
+            SELECT event_template_staff.user_id,
+                   event_template_staff.company_id,
+                   $1,
+                   event_template_staff.role
+            FROM event_template_staff
+            INNER JOIN employment
+                ON event_template_staff.user_id = employment.user_id
+               AND event_template_staff.company_id = employment.company_id
+            WHERE event_template_staff.template_id = $2
+              AND event_template_staff.user_id != $3
+              AND event_template_staff.deleted_at IS NULL
+              AND employment.deleted_at IS NULL;"#,
+            event.id,
+            template_id,
+            data.creator_id,
+        )
+        .execute(tx.deref_mut())
+        .await?;
+
+        let start = event
+            .start_date
+            .and_hms_opt(0, 0, 0)
+            .expect("Should be valid.");
+
+        // Maps the IDs of template tasks to the IDs of the new event tasks.
+        let mut task_ids: HashMap<Uuid, Uuid> = HashMap::new();
+        for task in tasks.iter() {
+            let due_at = task
+                .due_offset_minutes
+                .map(|minutes| start + Duration::minutes(i64::from(minutes)));
+            let task_id = sqlx::query_scalar!(
+                r#"INSERT INTO task (
+                    event_id, creator_id, title, description, priority,
+                    accepts_staff, estimated_hours, due_at, position
+                )
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                RETURNING id;"#,
+                event.id,
+                creator_id,
+                task.title,
+                task.description,
+                task.priority.clone() as TaskPriority,
+                task.accepts_staff,
+                task.estimated_hours,
+                due_at,
+                task.position,
+            )
+            .fetch_one(tx.deref_mut())
+            .await?;
+
+            task_ids.insert(task.id, task_id);
+
+            sqlx::query!(
+                r#"INSERT INTO task_checklist_item (task_id, content, position)
+                SELECT $1, content, position
+                FROM event_template_checklist_item
+                WHERE template_task_id = $2
+                  AND deleted_at IS NULL;"#,
+                task_id,
+                task.id,
+            )
+            .execute(tx.deref_mut())
+            .await?;
+        }
+
+        for task in tasks.iter() {
+            let parent_task_id = task
+                .parent_id
+                .and_then(|parent_id| task_ids.get(&parent_id));
+            if let Some(parent_task_id) = parent_task_id {
+                sqlx::query!(
+                    r#"UPDATE task
+                    SET parent_task_id = $1
+                    WHERE id = $2;"#,
+                    parent_task_id,
+                    task_ids[&task.id],
+                )
+                .execute(tx.deref_mut())
+                .await?;
+            }
+        }
+
+        let dependencies = sqlx::query!(
+            r#"SELECT event_template_task_dependency.template_task_id,
+                      event_template_task_dependency.blocker_id
+            FROM event_template_task_dependency
+            INNER JOIN event_template_task
+                ON event_template_task_dependency.template_task_id = event_template_task.id
+            WHERE event_template_task.template_id = $1
+              AND event_template_task_dependency.deleted_at IS NULL;"#,
+            template_id,
+        )
+        .fetch_all(tx.deref_mut())
+        .await?;
+
+        for dependency in dependencies.iter() {
+            if let (Some(task_id), Some(blocker_id)) = (
+                task_ids.get(&dependency.template_task_id),
+                task_ids.get(&dependency.blocker_id),
+            ) {
+                sqlx::query!(
+                    r#"INSERT INTO task_dependency (task_id, blocker_id)
+                    VALUES ($1, $2);"#,
+                    task_id,
+                    blocker_id,
+                )
+                .execute(tx.deref_mut())
+                .await?;
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(event)
+    }
+
+    pub async fn delete(&self, template_id: Uuid) -> DbResult<()> {
+        let executor = self.pool.as_ref();
+
+        let deleted = sqlx::query_scalar!(
+            r#"UPDATE event_template
+            SET deleted_at = NOW(),
+                edited_at = NOW()
+            WHERE id = $1
+              AND deleted_at IS NULL
+            RETURNING id;"#,
+            template_id,
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        if deleted.is_none() {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,2 @@
+pub mod event_template_repo;
+pub mod models;
@@ -0,0 +1,54 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+use sqlx::{types::chrono::NaiveDateTime, FromRow};
+use uuid::Uuid;
+
+use crate::models::TaskPriority;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewEventTemplate {
+    pub event_id: Uuid,
+    pub company_id: Uuid,
+    pub name: String,
+}
+
+#[derive(Debug, FromRow, Clone)]
+pub struct EventTemplate {
+    pub id: Uuid,
+    pub company_id: Uuid,
+    pub source_event_id: Option<Uuid>,
+    pub name: String,
+    pub description: Option<String>,
+    pub website: Option<String>,
+    pub accepts_staff: bool,
+    pub duration_days: i32,
+    pub created_at: NaiveDateTime,
+    pub edited_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, FromRow, Clone)]
+pub struct EventTemplateTask {
+    pub id: Uuid,
+    pub template_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: TaskPriority,
+    pub accepts_staff: bool,
+    pub estimated_hours: Option<f32>,
+    pub due_offset_minutes: Option<i32>,
+    pub position: i32,
+    pub created_at: NaiveDateTime,
+    pub edited_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+/// Everything needed to open a new edition of an event from its template.
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewEventFromTemplate {
+    pub name: String,
+    pub start_date: NaiveDate,
+    pub creator_id: Uuid, // user ID
+    pub company_id: Uuid,
+}
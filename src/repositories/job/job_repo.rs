@@ -0,0 +1,319 @@
+use crate::common::DbResult;
+use crate::models::JobRunStatus;
+use async_trait::async_trait;
+use sqlx::postgres::PgPool;
+use std::ops::DerefMut;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use super::models::{Job, JobRun};
+
+#[derive(Clone)]
+pub struct JobRepository {
+    pub pool: Arc<PgPool>,
+}
+
+#[async_trait]
+impl crate::repositories::repository::DbRepository for JobRepository {
+    /// Database repository constructor
+    fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Method allowing the database repository to disconnect from the database pool gracefully
+    async fn disconnect(&mut self) -> () {
+        self.pool.close().await;
+    }
+}
+
+impl JobRepository {
+    /// Adds the job, or updates its period. A new job is due right away.
+    pub async fn register(&self, name: &str, period: Duration) -> DbResult<()> {
+        let executor = self.pool.as_ref();
+
+        sqlx::query!(
+            r#"
+            INSERT INTO job (name, period_seconds)
+            VALUES ($1, $2)
+            ON CONFLICT (name) DO UPDATE
+            SET period_seconds = EXCLUDED.period_seconds,
+                edited_at = NOW()
+            WHERE job.period_seconds <> EXCLUDED.period_seconds;
+            "#,
+            name,
+            period.as_secs() as i32,
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Starts a run of one of the given jobs which is due. The job is leased
+    /// to the caller, other instances skip it until the lease expires. Runs
+    /// still running once the lease expired are marked as failed.
+    pub async fn claim_due(&self, names: &[String], lease: Duration) -> DbResult<Option<JobRun>> {
+        let mut tx = self.pool.begin().await?;
+
+        let job = sqlx::query!(
+            r#"
+            SELECT name, failed_attempts
+            FROM job
+            WHERE name = ANY($1)
+              AND next_run_at <= NOW()
+            ORDER BY next_run_at
+            LIMIT 1
+            FOR UPDATE SKIP LOCKED;
+            "#,
+            names,
+        )
+        .fetch_optional(tx.deref_mut())
+        .await?;
+        let Some(job) = job else {
+            return Ok(None);
+        };
+
+        sqlx::query!(
+            r#"
+            UPDATE job_run
+            SET status = 'failed',
+                output = 'The run was interrupted.',
+                finished_at = NOW()
+            WHERE job_name = $1
+              AND status = 'running';
+            "#,
+            job.name,
+        )
+        .execute(tx.deref_mut())
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE job
+            SET next_run_at = NOW() + make_interval(secs => $2),
+                edited_at = NOW()
+            WHERE name = $1;
+            "#,
+            job.name,
+            lease.as_secs_f64(),
+        )
+        .execute(tx.deref_mut())
+        .await?;
+
+        let run = sqlx::query_as!(
+            JobRun,
+            r#"
+            INSERT INTO job_run (job_name, attempt)
+            VALUES ($1, $2)
+            RETURNING id,
+                      job_name,
+                      attempt,
+                      status AS "status!: JobRunStatus",
+                      output,
+                      started_at,
+                      finished_at;
+            "#,
+            job.name,
+            job.failed_attempts + 1,
+        )
+        .fetch_one(tx.deref_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(Some(run))
+    }
+
+    /// Finishes the run, the job is due again a period after the run started.
+    pub async fn succeed(&self, run_id: Uuid, output: &str) -> DbResult<JobRun> {
+        let mut tx = self.pool.begin().await?;
+
+        let run = sqlx::query_as!(
+            JobRun,
+            r#"
+            UPDATE job_run
+            SET status = 'succeeded',
+                output = $2,
+                finished_at = NOW()
+            WHERE id = $1
+              AND status = 'running'
+            RETURNING id,
+                      job_name,
+                      attempt,
+                      status AS "status!: JobRunStatus",
+                      output,
+                      started_at,
+                      finished_at;
+            "#,
+            run_id,
+            output,
+        )
+        .fetch_one(tx.deref_mut())
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE job
+            SET failed_attempts = 0,
+                next_run_at = $2::TIMESTAMP + make_interval(secs => period_seconds),
+                edited_at = NOW()
+            WHERE name = $1;
+            "#,
+            run.job_name,
+            run.started_at,
+        )
+        .execute(tx.deref_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(run)
+    }
+
+    /// Finishes the run as failed, the job is retried after the given delay.
+    pub async fn fail(&self, run_id: Uuid, error: &str, retry_in: Duration) -> DbResult<JobRun> {
+        let mut tx = self.pool.begin().await?;
+
+        let run = sqlx::query_as!(
+            JobRun,
+            r#"
+            UPDATE job_run
+            SET status = 'failed',
+                output = $2,
+                finished_at = NOW()
+            WHERE id = $1
+              AND status = 'running'
+            RETURNING id,
+                      job_name,
+                      attempt,
+                      status AS "status!: JobRunStatus",
+                      output,
+                      started_at,
+                      finished_at;
+            "#,
+            run_id,
+            error,
+        )
+        .fetch_one(tx.deref_mut())
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE job
+            SET failed_attempts = failed_attempts + 1,
+                next_run_at = NOW() + make_interval(secs => $2),
+                edited_at = NOW()
+            WHERE name = $1;
+            "#,
+            run.job_name,
+            retry_in.as_secs_f64(),
+        )
+        .execute(tx.deref_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(run)
+    }
+
+    /// Makes the job due right away, unless it is running.
+    pub async fn schedule_now(&self, name: &str) -> DbResult<()> {
+        let executor = self.pool.as_ref();
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE job
+            SET next_run_at = NOW(),
+                edited_at = NOW()
+            WHERE name = $1
+              AND NOT EXISTS (SELECT 1
+                              FROM job_run
+                              WHERE job_run.job_name = job.name
+                                AND job_run.status = 'running');
+            "#,
+            name,
+        )
+        .execute(executor)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        Ok(())
+    }
+
+    pub async fn read_all(&self) -> DbResult<Vec<Job>> {
+        let executor = self.pool.as_ref();
+
+        let jobs = sqlx::query_as!(
+            Job,
+            r#"
+            SELECT job.name,
+                   job.period_seconds,
+                   job.next_run_at,
+                   job.failed_attempts,
+                   last_run.status AS "last_status?: JobRunStatus",
+                   last_run.started_at AS "last_started_at?"
+            FROM job
+                LEFT JOIN LATERAL (
+                    SELECT job_run.status, job_run.started_at
+                    FROM job_run
+                    WHERE job_run.job_name = job.name
+                    ORDER BY job_run.started_at DESC
+                    LIMIT 1
+                ) AS last_run ON true
+            ORDER BY job.name;
+            "#,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(jobs)
+    }
+
+    /// The latest runs of all jobs, the newest first.
+    pub async fn read_runs(&self, limit: i64) -> DbResult<Vec<JobRun>> {
+        let executor = self.pool.as_ref();
+
+        let runs = sqlx::query_as!(
+            JobRun,
+            r#"
+            SELECT id,
+                   job_name,
+                   attempt,
+                   status AS "status!: JobRunStatus",
+                   output,
+                   started_at,
+                   finished_at
+            FROM job_run
+            ORDER BY started_at DESC, id
+            LIMIT $1;
+            "#,
+            limit,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(runs)
+    }
+
+    /// Removes the finished runs which started more than `days` ago.
+    pub async fn delete_runs_older_than(&self, days: i32) -> DbResult<u64> {
+        let executor = self.pool.as_ref();
+
+        let result = sqlx::query!(
+            r#"
+            DELETE FROM job_run
+            WHERE status <> 'running'
+              AND started_at < NOW() - make_interval(days => $1);
+            "#,
+            days,
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+}
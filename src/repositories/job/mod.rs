@@ -0,0 +1,2 @@
+pub mod job_repo;
+pub mod models;
@@ -0,0 +1,28 @@
+use chrono::NaiveDateTime;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::models::JobRunStatus;
+
+/// A registered background job along with its latest run.
+#[derive(Debug, Clone, FromRow)]
+pub struct Job {
+    pub name: String,
+    pub period_seconds: i32,
+    pub next_run_at: NaiveDateTime,
+    /// Failed runs since the last successful one.
+    pub failed_attempts: i32,
+    pub last_status: Option<JobRunStatus>,
+    pub last_started_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct JobRun {
+    pub id: Uuid,
+    pub job_name: String,
+    pub attempt: i32,
+    pub status: JobRunStatus,
+    pub output: Option<String>,
+    pub started_at: NaiveDateTime,
+    pub finished_at: Option<NaiveDateTime>,
+}
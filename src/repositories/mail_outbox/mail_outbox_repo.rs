@@ -0,0 +1,438 @@
+use crate::common::DbResult;
+use crate::models::{MailDelivery, MailStatus};
+use crate::templates::mail::{DigestItem, MailContent};
+use async_trait::async_trait;
+use sqlx::{postgres::PgPool, Postgres, Transaction};
+use std::ops::DerefMut;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::models::{MailRecipient, MailSetting, MailSettingData, OutboxMail, MAX_MAIL_ATTEMPTS};
+
+async fn insert_db_using_tx(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    recipient: &MailRecipient,
+    content: &MailContent,
+    status: MailStatus,
+) -> DbResult<OutboxMail> {
+    let mail = content
+        .render(&recipient.name)
+        .map_err(|error| sqlx::Error::Protocol(format!("Mail rendering failed: {}", error)))?;
+
+    let outbox_mail = sqlx::query_as!(
+        OutboxMail,
+        r#"
+        INSERT INTO mail_outbox (user_id, recipient, subject, body_text, body_html, summary, status)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id,
+                  user_id,
+                  recipient,
+                  subject,
+                  body_text,
+                  body_html,
+                  summary,
+                  status AS "status!: MailStatus",
+                  attempts,
+                  last_error,
+                  next_attempt_at,
+                  sent_at,
+                  created_at,
+                  edited_at,
+                  deleted_at;
+        "#,
+        user_id,
+        recipient.email,
+        mail.subject,
+        mail.body_text,
+        mail.body_html,
+        mail.summary,
+        status as MailStatus,
+    )
+    .fetch_one(tx.deref_mut())
+    .await?;
+
+    Ok(outbox_mail)
+}
+
+async fn read_recipient_db_using_tx(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+) -> DbResult<MailRecipient> {
+    let recipient = sqlx::query_as!(
+        MailRecipient,
+        r#"
+        SELECT user_record.name,
+               user_record.email,
+               COALESCE(mail_setting.delivery, 'immediate') AS "delivery!: MailDelivery"
+        FROM user_record
+        LEFT JOIN mail_setting
+            ON mail_setting.user_id = user_record.id
+           AND mail_setting.deleted_at IS NULL
+        WHERE user_record.id = $1
+          AND user_record.deleted_at IS NULL;
+        "#,
+        user_id,
+    )
+    .fetch_one(tx.deref_mut())
+    .await?;
+
+    Ok(recipient)
+}
+
+/// Queues the mail for the user, it waits for the user's next daily digest
+/// if they asked for one.
+pub async fn enqueue_db_using_tx(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    content: &MailContent,
+) -> DbResult<OutboxMail> {
+    let recipient = read_recipient_db_using_tx(tx, user_id).await?;
+    let status = match recipient.delivery {
+        MailDelivery::Immediate => MailStatus::Pending,
+        MailDelivery::DailyDigest => MailStatus::Digest,
+    };
+
+    insert_db_using_tx(tx, user_id, &recipient, content, status).await
+}
+
+#[derive(Clone)]
+pub struct MailOutboxRepository {
+    pub pool: Arc<PgPool>,
+}
+
+#[async_trait]
+impl crate::repositories::repository::DbRepository for MailOutboxRepository {
+    /// Database repository constructor
+    fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Method allowing the database repository to disconnect from the database pool gracefully
+    async fn disconnect(&mut self) -> () {
+        self.pool.close().await;
+    }
+}
+
+impl MailOutboxRepository {
+    pub async fn enqueue(&self, user_id: Uuid, content: &MailContent) -> DbResult<OutboxMail> {
+        let mut tx = self.pool.begin().await?;
+        let mail = enqueue_db_using_tx(&mut tx, user_id, content).await?;
+        tx.commit().await?;
+
+        Ok(mail)
+    }
+
+    pub async fn read_one(&self, mail_id: Uuid) -> DbResult<OutboxMail> {
+        let executor = self.pool.as_ref();
+
+        let mail = sqlx::query_as!(
+            OutboxMail,
+            r#"
+            SELECT id,
+                   user_id,
+                   recipient,
+                   subject,
+                   body_text,
+                   body_html,
+                   summary,
+                   status AS "status!: MailStatus",
+                   attempts,
+                   last_error,
+                   next_attempt_at,
+                   sent_at,
+                   created_at,
+                   edited_at,
+                   deleted_at
+            FROM mail_outbox
+            WHERE id = $1
+              AND deleted_at IS NULL;
+            "#,
+            mail_id,
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(mail)
+    }
+
+    /// Pending mails whose (next) attempt is due, the oldest first.
+    pub async fn read_due(&self, limit: i64) -> DbResult<Vec<OutboxMail>> {
+        let executor = self.pool.as_ref();
+
+        let mails = sqlx::query_as!(
+            OutboxMail,
+            r#"
+            SELECT id,
+                   user_id,
+                   recipient,
+                   subject,
+                   body_text,
+                   body_html,
+                   summary,
+                   status AS "status!: MailStatus",
+                   attempts,
+                   last_error,
+                   next_attempt_at,
+                   sent_at,
+                   created_at,
+                   edited_at,
+                   deleted_at
+            FROM mail_outbox
+            WHERE status = 'pending'
+              AND next_attempt_at <= NOW()
+              AND deleted_at IS NULL
+            ORDER BY next_attempt_at, created_at
+            LIMIT $1;
+            "#,
+            limit,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(mails)
+    }
+
+    pub async fn mark_sent(&self, mail_id: Uuid) -> DbResult<OutboxMail> {
+        let executor = self.pool.as_ref();
+
+        let mail = sqlx::query_as!(
+            OutboxMail,
+            r#"
+            UPDATE mail_outbox
+            SET status = 'sent',
+                attempts = attempts + 1,
+                last_error = NULL,
+                sent_at = NOW(),
+                edited_at = NOW()
+            WHERE id = $1
+              AND status = 'pending'
+              AND deleted_at IS NULL
+            RETURNING id,
+                      user_id,
+                      recipient,
+                      subject,
+                      body_text,
+                      body_html,
+                      summary,
+                      status AS "status!: MailStatus",
+                      attempts,
+                      last_error,
+                      next_attempt_at,
+                      sent_at,
+                      created_at,
+                      edited_at,
+                      deleted_at;
+            "#,
+            mail_id,
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        if mail.is_none() {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        Ok(mail.expect("Should be some."))
+    }
+
+    /// Reschedules the mail with an exponential backoff, after
+    /// `MAX_MAIL_ATTEMPTS` the mail is marked as failed.
+    pub async fn mark_failed_attempt(&self, mail_id: Uuid, error: &str) -> DbResult<OutboxMail> {
+        let executor = self.pool.as_ref();
+
+        let mail = sqlx::query_as!(
+            OutboxMail,
+            r#"
+            UPDATE mail_outbox
+            SET status = CASE
+                    WHEN attempts + 1 >= $3 THEN 'failed'::mail_status
+                    ELSE 'pending'::mail_status
+                END,
+                attempts = attempts + 1,
+                last_error = $2,
+                next_attempt_at = NOW() + make_interval(mins => POWER(2, attempts)::INT),
+                edited_at = NOW()
+            WHERE id = $1
+              AND status = 'pending'
+              AND deleted_at IS NULL
+            RETURNING id,
+                      user_id,
+                      recipient,
+                      subject,
+                      body_text,
+                      body_html,
+                      summary,
+                      status AS "status!: MailStatus",
+                      attempts,
+                      last_error,
+                      next_attempt_at,
+                      sent_at,
+                      created_at,
+                      edited_at,
+                      deleted_at;
+            "#,
+            mail_id,
+            error,
+            MAX_MAIL_ATTEMPTS,
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        if mail.is_none() {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        Ok(mail.expect("Should be some."))
+    }
+
+    /// Bundles the waiting mails of every user whose daily digest is due into
+    /// a single pending digest mail. Returns the queued digests.
+    pub async fn bundle_due_digests(&self) -> DbResult<Vec<OutboxMail>> {
+        let mut tx = self.pool.begin().await?;
+
+        let user_ids: Vec<Uuid> = sqlx::query_scalar!(
+            r#"
+            SELECT mail_setting.user_id
+            FROM mail_setting
+            WHERE mail_setting.delivery = 'daily_digest'
+              AND COALESCE(mail_setting.last_digest_at, mail_setting.created_at)
+                  <= NOW() - INTERVAL '1 day'
+              AND mail_setting.deleted_at IS NULL
+              AND EXISTS (
+                  SELECT 1
+                  FROM mail_outbox
+                  WHERE mail_outbox.user_id = mail_setting.user_id
+                    AND mail_outbox.status = 'digest'
+                    AND mail_outbox.deleted_at IS NULL
+              )
+            FOR UPDATE OF mail_setting SKIP LOCKED;
+            "#,
+        )
+        .fetch_all(tx.deref_mut())
+        .await?;
+
+        let mut digests = vec![];
+        for user_id in user_ids {
+            let items: Vec<DigestItem> = sqlx::query_as!(
+                DigestItem,
+                r#"
+                UPDATE mail_outbox
+                SET status = 'bundled',
+                    edited_at = NOW()
+                WHERE user_id = $1
+                  AND status = 'digest'
+                  AND deleted_at IS NULL
+                RETURNING subject,
+                          summary AS body_text;
+                "#,
+                user_id,
+            )
+            .fetch_all(tx.deref_mut())
+            .await?;
+
+            let recipient = read_recipient_db_using_tx(&mut tx, user_id).await?;
+            let digest = insert_db_using_tx(
+                &mut tx,
+                user_id,
+                &recipient,
+                &MailContent::Digest { items },
+                MailStatus::Pending,
+            )
+            .await?;
+
+            sqlx::query!(
+                r#"
+                UPDATE mail_setting
+                SET last_digest_at = NOW(),
+                    edited_at = NOW()
+                WHERE user_id = $1;
+                "#,
+                user_id,
+            )
+            .execute(tx.deref_mut())
+            .await?;
+
+            digests.push(digest);
+        }
+
+        tx.commit().await?;
+
+        Ok(digests)
+    }
+
+    pub async fn read_setting(&self, user_id: Uuid) -> DbResult<MailSetting> {
+        let executor = self.pool.as_ref();
+
+        let setting = sqlx::query_as!(
+            MailSetting,
+            r#"
+            SELECT user_record.id AS user_id,
+                   COALESCE(mail_setting.delivery, 'immediate') AS "delivery!: MailDelivery",
+                   mail_setting.last_digest_at AS "last_digest_at?"
+            FROM user_record
+            LEFT JOIN mail_setting
+                ON mail_setting.user_id = user_record.id
+               AND mail_setting.deleted_at IS NULL
+            WHERE user_record.id = $1
+              AND user_record.deleted_at IS NULL;
+            "#,
+            user_id,
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(setting)
+    }
+
+    /// Switching back to immediate delivery releases the mails which were
+    /// waiting for the digest.
+    pub async fn update_setting(
+        &self,
+        user_id: Uuid,
+        data: MailSettingData,
+    ) -> DbResult<MailSetting> {
+        let mut tx = self.pool.begin().await?;
+
+        let setting = sqlx::query_as!(
+            MailSetting,
+            r#"
+            INSERT INTO mail_setting (user_id, delivery, last_digest_at)
+            VALUES ($1, $2, NOW())
+            ON CONFLICT (user_id) DO UPDATE
+            SET delivery = EXCLUDED.delivery,
+                last_digest_at = COALESCE(mail_setting.last_digest_at, NOW()),
+                deleted_at = NULL,
+                edited_at = NOW()
+            RETURNING user_id,
+                      delivery AS "delivery!: MailDelivery",
+                      last_digest_at;
+            "#,
+            user_id,
+            data.delivery as MailDelivery,
+        )
+        .fetch_one(tx.deref_mut())
+        .await?;
+
+        if setting.delivery == MailDelivery::Immediate {
+            sqlx::query!(
+                r#"
+                UPDATE mail_outbox
+                SET status = 'pending',
+                    next_attempt_at = NOW(),
+                    edited_at = NOW()
+                WHERE user_id = $1
+                  AND status = 'digest'
+                  AND deleted_at IS NULL;
+                "#,
+                user_id,
+            )
+            .execute(tx.deref_mut())
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(setting)
+    }
+}
@@ -0,0 +1,2 @@
+pub mod mail_outbox_repo;
+pub mod models;
@@ -0,0 +1,46 @@
+use serde::Deserialize;
+use sqlx::{types::chrono::NaiveDateTime, FromRow};
+use uuid::Uuid;
+
+use crate::models::{MailDelivery, MailStatus};
+
+/// Mails which failed this many times are given up on.
+pub const MAX_MAIL_ATTEMPTS: i32 = 5;
+
+#[derive(Debug, FromRow, Clone)]
+pub struct OutboxMail {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub recipient: String,
+    pub subject: String,
+    pub body_text: String,
+    pub body_html: String,
+    pub summary: String,
+    pub status: MailStatus,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: NaiveDateTime,
+    pub sent_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub edited_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, FromRow, Clone)]
+pub struct MailSetting {
+    pub user_id: Uuid,
+    pub delivery: MailDelivery,
+    pub last_digest_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct MailSettingData {
+    pub delivery: MailDelivery,
+}
+
+#[derive(Debug, FromRow)]
+pub struct MailRecipient {
+    pub name: String,
+    pub email: String,
+    pub delivery: MailDelivery,
+}
@@ -1,12 +1,25 @@
 pub mod assigned_staff;
 pub mod associated_company;
+pub mod calendar_feed;
 pub mod comment;
 pub mod company;
 pub mod employment;
 pub mod event;
+pub mod event_budget;
 pub mod event_staff;
+pub mod event_template;
+pub mod job;
+pub mod mail_outbox;
+pub mod notification;
+pub mod report;
 pub mod repository;
+pub mod submission_policy;
 pub mod task;
+pub mod task_checklist;
+pub mod task_dependency;
 pub mod timesheet;
+pub mod timesheet_amendment;
+pub mod timesheet_consistency;
+pub mod trash;
 pub mod user;
 pub mod wage_preset;
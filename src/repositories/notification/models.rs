@@ -0,0 +1,65 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+use sqlx::{types::chrono::NaiveDateTime, FromRow};
+use uuid::Uuid;
+
+use crate::models::NotificationKind;
+
+#[derive(Debug, Clone)]
+pub struct NewNotification {
+    pub user_id: Uuid,
+    pub kind: NotificationKind,
+    pub message: String,
+    pub link: Option<String>,
+}
+
+#[derive(Debug, FromRow, Clone)]
+pub struct Notification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: NotificationKind,
+    pub message: String,
+    pub link: Option<String>,
+    pub read_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub edited_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct NotificationFilter {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, FromRow, Clone)]
+pub struct NotificationPreference {
+    pub kind: NotificationKind,
+    pub is_enabled: bool,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct NotificationPreferenceData {
+    pub kind: NotificationKind,
+    pub is_enabled: bool,
+}
+
+/// Domain events which may result in a notification of the affected user.
+#[derive(Debug, Clone)]
+pub enum NotificationEvent {
+    /// An organizer decided on the application of event staff.
+    StaffDecided { staff_id: Uuid },
+    /// An organizer decided on the assignment of event staff to a task.
+    AssignmentDecided { task_id: Uuid, staff_id: Uuid },
+    /// A manager approved or rejected a timesheet.
+    TimesheetDecided { timesheet_id: Uuid },
+    /// Event staff got mentioned in a comment.
+    Mentioned { comment_id: Uuid, staff_id: Uuid },
+    /// The event the staff takes part in was cancelled or completed.
+    EventStatusChanged { event_id: Uuid, staff_id: Uuid },
+    /// The deadline for submitting a timesheet is coming up.
+    SubmissionReminder {
+        timesheet_id: Uuid,
+        deadline: NaiveDate,
+    },
+}
@@ -0,0 +1,555 @@
+use crate::common::DbResult;
+use crate::models::{AcceptanceStatus, ApprovalStatus, EventStatus, NotificationKind};
+use crate::repositories::mail_outbox::mail_outbox_repo;
+use crate::templates::mail::MailContent;
+use async_trait::async_trait;
+use sqlx::{postgres::PgPool, Postgres, Transaction};
+use std::ops::DerefMut;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::models::{
+    NewNotification, Notification, NotificationEvent, NotificationFilter, NotificationPreference,
+    NotificationPreferenceData,
+};
+
+fn decision_word(status: &AcceptanceStatus) -> &'static str {
+    match status {
+        AcceptanceStatus::Accepted => "accepted",
+        AcceptanceStatus::Rejected => "rejected",
+        AcceptanceStatus::Pending => "reset to pending",
+    }
+}
+
+/// Turns the domain event into a notification of the affected user, the event
+/// is dropped if it concerns nobody or the user opted out of its kind. Decisions
+/// are also queued to be mailed to the user.
+pub async fn emit_db_using_tx(
+    tx: &mut Transaction<'_, Postgres>,
+    event: NotificationEvent,
+) -> DbResult<Option<Notification>> {
+    let (notification, mail) = match event {
+        NotificationEvent::StaffDecided { staff_id } => {
+            let staff = sqlx::query!(
+                r#"
+                SELECT event_staff.user_id,
+                       event_staff.event_id,
+                       event_staff.status AS "status!: AcceptanceStatus",
+                       event.name AS event_name
+                FROM event_staff
+                INNER JOIN event ON event_staff.event_id = event.id
+                WHERE event_staff.id = $1;
+                "#,
+                staff_id,
+            )
+            .fetch_one(tx.deref_mut())
+            .await?;
+
+            let notification = NewNotification {
+                user_id: staff.user_id,
+                kind: NotificationKind::StaffDecision,
+                message: format!(
+                    "Your application to {} was {}.",
+                    staff.event_name,
+                    decision_word(&staff.status)
+                ),
+                link: Some(format!(
+                    "/protected/event/{}?user_id={}",
+                    staff.event_id, staff.user_id
+                )),
+            };
+            let mail =
+                (staff.status != AcceptanceStatus::Pending).then(|| MailContent::StaffDecision {
+                    event_name: staff.event_name,
+                    accepted: staff.status == AcceptanceStatus::Accepted,
+                });
+
+            (Some(notification), mail)
+        }
+        NotificationEvent::AssignmentDecided { task_id, staff_id } => {
+            let assignment = sqlx::query!(
+                r#"
+                SELECT event_staff.user_id,
+                       assigned_staff.status AS "status!: AcceptanceStatus",
+                       task.title AS task_title,
+                       event.name AS event_name
+                FROM assigned_staff
+                INNER JOIN event_staff ON assigned_staff.staff_id = event_staff.id
+                INNER JOIN task ON assigned_staff.task_id = task.id
+                INNER JOIN event ON task.event_id = event.id
+                WHERE assigned_staff.task_id = $1
+                  AND assigned_staff.staff_id = $2;
+                "#,
+                task_id,
+                staff_id,
+            )
+            .fetch_one(tx.deref_mut())
+            .await?;
+
+            let notification = NewNotification {
+                user_id: assignment.user_id,
+                kind: NotificationKind::AssignmentDecision,
+                message: format!(
+                    "Your assignment to the task \"{}\" was {}.",
+                    assignment.task_title,
+                    decision_word(&assignment.status)
+                ),
+                link: Some(format!(
+                    "/protected/event/staff/{}/task/{}",
+                    staff_id, task_id
+                )),
+            };
+            let mail = (assignment.status != AcceptanceStatus::Pending).then(|| {
+                MailContent::AssignmentDecision {
+                    task_title: assignment.task_title,
+                    event_name: assignment.event_name,
+                    accepted: assignment.status == AcceptanceStatus::Accepted,
+                }
+            });
+
+            (Some(notification), mail)
+        }
+        NotificationEvent::TimesheetDecided { timesheet_id } => {
+            let timesheet = sqlx::query!(
+                r#"
+                SELECT timesheet.user_id,
+                       timesheet.status AS "status!: ApprovalStatus",
+                       timesheet.manager_note,
+                       event.name AS event_name
+                FROM timesheet
+                INNER JOIN event ON timesheet.event_id = event.id
+                WHERE timesheet.id = $1;
+                "#,
+                timesheet_id,
+            )
+            .fetch_one(tx.deref_mut())
+            .await?;
+
+            let approved = timesheet.status == ApprovalStatus::Accepted;
+            let (kind, decision) = if approved {
+                (NotificationKind::TimesheetApproval, "approved")
+            } else {
+                (NotificationKind::TimesheetRejection, "rejected")
+            };
+            let manager_note = timesheet
+                .manager_note
+                .filter(|note| !note.trim().is_empty());
+            let message = match &manager_note {
+                Some(note) => format!(
+                    "Your timesheet for {} was {}: {}",
+                    timesheet.event_name, decision, note
+                ),
+                None => format!(
+                    "Your timesheet for {} was {}.",
+                    timesheet.event_name, decision
+                ),
+            };
+
+            let notification = NewNotification {
+                user_id: timesheet.user_id,
+                kind,
+                message,
+                link: Some(format!("/protected/timesheet/{}", timesheet_id)),
+            };
+            let mail = MailContent::TimesheetDecision {
+                event_name: timesheet.event_name,
+                approved,
+                manager_note,
+            };
+
+            (Some(notification), Some(mail))
+        }
+        NotificationEvent::Mentioned {
+            comment_id,
+            staff_id,
+        } => {
+            // Authors mentioning themselves don't need to be told about it.
+            let mention = sqlx::query!(
+                r#"
+                SELECT event_staff.user_id,
+                       event_staff.event_id,
+                       author.name AS author_name
+                FROM comment
+                INNER JOIN user_record AS author ON comment.author_id = author.id
+                INNER JOIN event_staff ON event_staff.id = $2
+                WHERE comment.id = $1
+                  AND event_staff.user_id <> comment.author_id;
+                "#,
+                comment_id,
+                staff_id,
+            )
+            .fetch_optional(tx.deref_mut())
+            .await?;
+
+            let notification = mention.map(|mention| NewNotification {
+                user_id: mention.user_id,
+                kind: NotificationKind::CommentMention,
+                message: format!("{} mentioned you in a comment.", mention.author_name),
+                link: Some(format!(
+                    "/protected/event/{}?user_id={}",
+                    mention.event_id, mention.user_id
+                )),
+            });
+
+            (notification, None)
+        }
+        NotificationEvent::EventStatusChanged { event_id, staff_id } => {
+            let staff = sqlx::query!(
+                r#"
+                SELECT event_staff.user_id,
+                       event.name AS event_name,
+                       event.status AS "status!: EventStatus",
+                       event.cancellation_reason
+                FROM event_staff
+                INNER JOIN event ON event_staff.event_id = event.id
+                WHERE event_staff.id = $2
+                  AND event.id = $1;
+                "#,
+                event_id,
+                staff_id,
+            )
+            .fetch_one(tx.deref_mut())
+            .await?;
+
+            let cancellation_reason = match staff.status {
+                EventStatus::Cancelled => Some(staff.cancellation_reason.unwrap_or_default()),
+                EventStatus::Completed => None,
+                _ => return Ok(None),
+            };
+            let message = match &cancellation_reason {
+                Some(reason) => format!("{} has been cancelled: {}", staff.event_name, reason),
+                None => format!(
+                    "{} has been completed. Please submit your final timesheets.",
+                    staff.event_name
+                ),
+            };
+
+            let notification = NewNotification {
+                user_id: staff.user_id,
+                kind: NotificationKind::EventStatusChange,
+                message,
+                link: Some(format!(
+                    "/protected/event/{}?user_id={}",
+                    event_id, staff.user_id
+                )),
+            };
+            let mail = MailContent::EventStatusChange {
+                event_name: staff.event_name,
+                cancellation_reason,
+            };
+
+            (Some(notification), Some(mail))
+        }
+        NotificationEvent::SubmissionReminder {
+            timesheet_id,
+            deadline,
+        } => {
+            let timesheet = sqlx::query!(
+                r#"
+                SELECT timesheet.user_id,
+                       event.name AS event_name
+                FROM timesheet
+                INNER JOIN event ON timesheet.event_id = event.id
+                WHERE timesheet.id = $1;
+                "#,
+                timesheet_id,
+            )
+            .fetch_one(tx.deref_mut())
+            .await?;
+
+            let notification = NewNotification {
+                user_id: timesheet.user_id,
+                kind: NotificationKind::SubmissionReminder,
+                message: format!(
+                    "Your timesheet for {} has to be submitted by {}.",
+                    timesheet.event_name,
+                    deadline.format("%d.%m.%Y")
+                ),
+                link: Some(format!("/protected/timesheet/{}", timesheet_id)),
+            };
+            let mail = MailContent::SubmissionDeadline {
+                event_name: timesheet.event_name,
+                deadline,
+            };
+
+            (Some(notification), Some(mail))
+        }
+    };
+
+    let Some(data) = notification else {
+        return Ok(None);
+    };
+
+    // Users who opted out of the kind are not mailed about it either.
+    let created = create_db(tx.deref_mut(), data).await?;
+    if let (Some(created), Some(mail)) = (&created, mail) {
+        mail_outbox_repo::enqueue_db_using_tx(tx, created.user_id, &mail).await?;
+    }
+
+    Ok(created)
+}
+
+async fn create_db<'e, E>(db: E, data: NewNotification) -> DbResult<Option<Notification>>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let notification = sqlx::query_as!(
+        Notification,
+        r#"
+        INSERT INTO notification (user_id, kind, message, link)
+        SELECT $1, $2, $3, $4
+        WHERE NOT EXISTS (
+            SELECT 1
+            FROM notification_preference
+            WHERE user_id = $1
+              AND kind = $2
+              AND NOT is_enabled
+              AND deleted_at IS NULL
+        )
+        RETURNING id,
+                  user_id,
+                  kind AS "kind!: NotificationKind",
+                  message,
+                  link,
+                  read_at,
+                  created_at,
+                  edited_at,
+                  deleted_at;
+        "#,
+        data.user_id,
+        data.kind as NotificationKind,
+        data.message,
+        data.link,
+    )
+    .fetch_optional(db)
+    .await?;
+
+    Ok(notification)
+}
+
+#[derive(Clone)]
+pub struct NotificationRepository {
+    pub pool: Arc<PgPool>,
+}
+
+#[async_trait]
+impl crate::repositories::repository::DbRepository for NotificationRepository {
+    /// Database repository constructor
+    fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Method allowing the database repository to disconnect from the database pool gracefully
+    async fn disconnect(&mut self) -> () {
+        self.pool.close().await;
+    }
+}
+
+impl NotificationRepository {
+    /// Returns `None` when the user opted out of the notification's kind.
+    pub async fn create(&self, data: NewNotification) -> DbResult<Option<Notification>> {
+        if data.message.trim().is_empty() {
+            return Err(sqlx::Error::TypeNotFound {
+                type_name: "User Error".to_string(),
+            });
+        }
+
+        let executor = self.pool.as_ref();
+        create_db(executor, data).await
+    }
+
+    pub async fn emit(&self, event: NotificationEvent) -> DbResult<Option<Notification>> {
+        let mut tx = self.pool.begin().await?;
+        let notification = emit_db_using_tx(&mut tx, event).await?;
+        tx.commit().await?;
+
+        Ok(notification)
+    }
+
+    pub async fn read_one(&self, notification_id: Uuid) -> DbResult<Notification> {
+        let executor = self.pool.as_ref();
+
+        let notification = sqlx::query_as!(
+            Notification,
+            r#"
+            SELECT id,
+                   user_id,
+                   kind AS "kind!: NotificationKind",
+                   message,
+                   link,
+                   read_at,
+                   created_at,
+                   edited_at,
+                   deleted_at
+            FROM notification
+            WHERE id = $1
+              AND deleted_at IS NULL;
+            "#,
+            notification_id,
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(notification)
+    }
+
+    pub async fn read_all_for_user(
+        &self,
+        user_id: Uuid,
+        filter: NotificationFilter,
+    ) -> DbResult<Vec<Notification>> {
+        let executor = self.pool.as_ref();
+
+        let notifications = sqlx::query_as!(
+            Notification,
+            r#"
+            SELECT id,
+                   user_id,
+                   kind AS "kind!: NotificationKind",
+                   message,
+                   link,
+                   read_at,
+                   created_at,
+                   edited_at,
+                   deleted_at
+            FROM notification
+            WHERE user_id = $1
+              AND deleted_at IS NULL
+            ORDER BY created_at DESC, id
+            LIMIT $2 OFFSET $3;
+            "#,
+            user_id,
+            filter.limit,
+            filter.offset,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(notifications)
+    }
+
+    pub async fn count_unread(&self, user_id: Uuid) -> DbResult<i64> {
+        let executor = self.pool.as_ref();
+
+        let count = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM notification
+            WHERE user_id = $1
+              AND read_at IS NULL
+              AND deleted_at IS NULL;
+            "#,
+            user_id,
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(count)
+    }
+
+    pub async fn mark_read(&self, notification_id: Uuid) -> DbResult<Notification> {
+        let executor = self.pool.as_ref();
+
+        let notification = sqlx::query_as!(
+            Notification,
+            r#"
+            UPDATE notification
+            SET read_at = COALESCE(read_at, NOW()),
+                edited_at = NOW()
+            WHERE id = $1
+              AND deleted_at IS NULL
+            RETURNING id,
+                      user_id,
+                      kind AS "kind!: NotificationKind",
+                      message,
+                      link,
+                      read_at,
+                      created_at,
+                      edited_at,
+                      deleted_at;
+            "#,
+            notification_id,
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        if notification.is_none() {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        Ok(notification.expect("Should be some."))
+    }
+
+    pub async fn mark_all_read(&self, user_id: Uuid) -> DbResult<()> {
+        let executor = self.pool.as_ref();
+
+        sqlx::query!(
+            r#"
+            UPDATE notification
+            SET read_at = NOW(),
+                edited_at = NOW()
+            WHERE user_id = $1
+              AND read_at IS NULL
+              AND deleted_at IS NULL;
+            "#,
+            user_id,
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Every kind is listed, the ones the user never changed are enabled.
+    pub async fn read_preferences(&self, user_id: Uuid) -> DbResult<Vec<NotificationPreference>> {
+        let executor = self.pool.as_ref();
+
+        let preferences = sqlx::query_as!(
+            NotificationPreference,
+            r#"
+            SELECT kinds.kind AS "kind!: NotificationKind",
+                   COALESCE(notification_preference.is_enabled, TRUE) AS "is_enabled!"
+            FROM UNNEST(enum_range(NULL::notification_kind)) AS kinds(kind)
+            LEFT JOIN notification_preference
+                ON notification_preference.kind = kinds.kind
+               AND notification_preference.user_id = $1
+               AND notification_preference.deleted_at IS NULL
+            ORDER BY kinds.kind;
+            "#,
+            user_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(preferences)
+    }
+
+    pub async fn update_preference(
+        &self,
+        user_id: Uuid,
+        data: NotificationPreferenceData,
+    ) -> DbResult<NotificationPreference> {
+        let executor = self.pool.as_ref();
+
+        let preference = sqlx::query_as!(
+            NotificationPreference,
+            r#"
+            INSERT INTO notification_preference (user_id, kind, is_enabled)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, kind) DO UPDATE
+            SET is_enabled = EXCLUDED.is_enabled,
+                deleted_at = NULL,
+                edited_at = NOW()
+            RETURNING kind AS "kind!: NotificationKind",
+                      is_enabled;
+            "#,
+            user_id,
+            data.kind as NotificationKind,
+            data.is_enabled,
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(preference)
+    }
+}
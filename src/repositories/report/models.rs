@@ -0,0 +1,94 @@
+use chrono::NaiveDate;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Restricts the reports to a date range and optionally a single company.
+#[derive(Debug, Clone)]
+pub struct ReportFilter {
+    pub date_from: NaiveDate,
+    pub date_to: NaiveDate,
+    pub company_id: Option<Uuid>,
+}
+
+#[derive(Debug, FromRow, Clone)]
+pub struct MonthlyHours {
+    pub event_id: Uuid,
+    pub event_name: String,
+    pub company_id: Uuid,
+    pub company_name: String,
+    /// The first day of the month.
+    pub month: NaiveDate,
+    pub reported_hours: f64,
+    pub accepted_hours: f64,
+    pub staff_count: i64,
+}
+
+/// How much of a company's workforce staffed the events of the date range
+/// and how many came back for another event.
+#[derive(Debug, FromRow, Clone)]
+pub struct StaffUtilization {
+    pub company_id: Uuid,
+    pub company_name: String,
+    pub employees: i64,
+    pub active_staff: i64,
+    pub returning_staff: i64,
+    pub events: i64,
+    pub worked_hours: f64,
+}
+
+impl StaffUtilization {
+    /// Share of the employees who staffed an event, in percent.
+    pub fn utilization_pct(&self) -> f64 {
+        percentage(self.active_staff, self.employees)
+    }
+
+    /// Share of the active staff who staffed more than one event, in percent.
+    pub fn retention_pct(&self) -> f64 {
+        percentage(self.returning_staff, self.active_staff)
+    }
+
+    pub fn hours_per_staff(&self) -> f64 {
+        if self.active_staff == 0 {
+            return 0.0;
+        }
+        self.worked_hours / self.active_staff as f64
+    }
+}
+
+/// How long the timesheets submitted in the date range waited for a review.
+#[derive(Debug, FromRow, Clone)]
+pub struct ApprovalLatency {
+    pub company_id: Uuid,
+    pub company_name: String,
+    pub submitted: i64,
+    pub reviewed: i64,
+    pub pending: i64,
+    pub accepted: i64,
+    pub rejected: i64,
+    pub average_latency_hours: Option<f64>,
+    pub max_latency_hours: Option<f64>,
+}
+
+#[derive(Debug, FromRow, Clone)]
+pub struct TaskCompletion {
+    pub event_id: Uuid,
+    pub event_name: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub tasks: i64,
+    pub done_tasks: i64,
+    pub overdue_tasks: i64,
+}
+
+impl TaskCompletion {
+    pub fn completion_pct(&self) -> f64 {
+        percentage(self.done_tasks, self.tasks)
+    }
+}
+
+fn percentage(part: i64, whole: i64) -> f64 {
+    if whole == 0 {
+        return 0.0;
+    }
+    part as f64 * 100.0 / whole as f64
+}
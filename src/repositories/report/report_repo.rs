@@ -0,0 +1,189 @@
+use crate::common::DbResult;
+use async_trait::async_trait;
+use sqlx::postgres::PgPool;
+use std::sync::Arc;
+
+use super::models::{
+    ApprovalLatency, MonthlyHours, ReportFilter, StaffUtilization, TaskCompletion,
+};
+
+/// Reads the aggregate reporting views, see the `reporting` migration.
+#[derive(Clone)]
+pub struct ReportRepository {
+    pub pool: Arc<PgPool>,
+}
+
+#[async_trait]
+impl crate::repositories::repository::DbRepository for ReportRepository {
+    /// Database repository constructor
+    fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Method allowing the database repository to disconnect from the database pool gracefully
+    async fn disconnect(&mut self) -> () {
+        self.pool.close().await;
+    }
+}
+
+impl ReportRepository {
+    /// Months overlapping the date range.
+    pub async fn read_monthly_hours(&self, filter: &ReportFilter) -> DbResult<Vec<MonthlyHours>> {
+        let executor = self.pool.as_ref();
+
+        let hours = sqlx::query_as!(
+            MonthlyHours,
+            r#"
+            SELECT event_id AS "event_id!",
+                   event_name AS "event_name!",
+                   company_id AS "company_id!",
+                   company_name AS "company_name!",
+                   month AS "month!",
+                   reported_hours AS "reported_hours!",
+                   accepted_hours AS "accepted_hours!",
+                   staff_count AS "staff_count!"
+            FROM report_monthly_hours
+            WHERE month BETWEEN date_trunc('month', $1::DATE)::DATE AND $2
+              AND ($3::UUID IS NULL OR company_id = $3)
+            ORDER BY month, event_name, company_name;
+            "#,
+            filter.date_from,
+            filter.date_to,
+            filter.company_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(hours)
+    }
+
+    /// Counts the staff of events taking place in the date range and the
+    /// employees employed at some point of it.
+    pub async fn read_staff_utilization(
+        &self,
+        filter: &ReportFilter,
+    ) -> DbResult<Vec<StaffUtilization>> {
+        let executor = self.pool.as_ref();
+
+        let utilization = sqlx::query_as!(
+            StaffUtilization,
+            r#"
+            WITH participation AS (
+                SELECT user_id, company_id, event_id, worked_hours
+                FROM report_staff_participation
+                WHERE start_date <= $2
+                  AND end_date >= $1
+            ), per_user AS (
+                SELECT company_id,
+                       user_id,
+                       COUNT(DISTINCT event_id) AS events,
+                       SUM(worked_hours) AS hours
+                FROM participation
+                GROUP BY company_id, user_id
+            )
+            SELECT company.id AS "company_id!",
+                   company.name AS "company_name!",
+                   (SELECT COUNT(*)
+                    FROM employment
+                    WHERE employment.company_id = company.id
+                      AND employment.start_date <= $2
+                      AND employment.end_date >= $1
+                      AND employment.deleted_at IS NULL) AS "employees!",
+                   COUNT(per_user.user_id) AS "active_staff!",
+                   COUNT(per_user.user_id) FILTER (WHERE per_user.events > 1)
+                       AS "returning_staff!",
+                   (SELECT COUNT(DISTINCT participation.event_id)
+                    FROM participation
+                    WHERE participation.company_id = company.id) AS "events!",
+                   COALESCE(SUM(per_user.hours), 0)::FLOAT8 AS "worked_hours!"
+            FROM company
+                LEFT JOIN per_user ON per_user.company_id = company.id
+            WHERE company.deleted_at IS NULL
+              AND ($3::UUID IS NULL OR company.id = $3)
+            GROUP BY company.id, company.name
+            ORDER BY company.name;
+            "#,
+            filter.date_from,
+            filter.date_to,
+            filter.company_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(utilization)
+    }
+
+    /// Timesheets submitted in the date range.
+    pub async fn read_approval_latency(
+        &self,
+        filter: &ReportFilter,
+    ) -> DbResult<Vec<ApprovalLatency>> {
+        let executor = self.pool.as_ref();
+
+        let latency = sqlx::query_as!(
+            ApprovalLatency,
+            r#"
+            SELECT company_id AS "company_id!",
+                   company_name AS "company_name!",
+                   COUNT(*) AS "submitted!",
+                   COUNT(reviewed_at) AS "reviewed!",
+                   COUNT(*) FILTER (WHERE status = 'pending') AS "pending!",
+                   COUNT(*) FILTER (WHERE status = 'accepted') AS "accepted!",
+                   COUNT(*) FILTER (WHERE status = 'rejected') AS "rejected!",
+                   AVG(latency_hours) AS average_latency_hours,
+                   MAX(latency_hours) AS max_latency_hours
+            FROM report_timesheet_review
+            WHERE submitted_at::DATE BETWEEN $1 AND $2
+              AND ($3::UUID IS NULL OR company_id = $3)
+            GROUP BY company_id, company_name
+            ORDER BY company_name;
+            "#,
+            filter.date_from,
+            filter.date_to,
+            filter.company_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(latency)
+    }
+
+    /// Events taking place in the date range, with a company only the events
+    /// it is associated with.
+    pub async fn read_task_completion(
+        &self,
+        filter: &ReportFilter,
+    ) -> DbResult<Vec<TaskCompletion>> {
+        let executor = self.pool.as_ref();
+
+        let completion = sqlx::query_as!(
+            TaskCompletion,
+            r#"
+            SELECT event_id AS "event_id!",
+                   event_name AS "event_name!",
+                   start_date AS "start_date!",
+                   end_date AS "end_date!",
+                   tasks AS "tasks!",
+                   done_tasks AS "done_tasks!",
+                   overdue_tasks AS "overdue_tasks!"
+            FROM report_task_completion
+            WHERE start_date <= $2
+              AND end_date >= $1
+              AND ($3::UUID IS NULL
+                   OR EXISTS (SELECT 1
+                              FROM associated_company
+                              WHERE associated_company.event_id = report_task_completion.event_id
+                                AND associated_company.company_id = $3
+                                AND associated_company.deleted_at IS NULL))
+            ORDER BY start_date, event_name;
+            "#,
+            filter.date_from,
+            filter.date_to,
+            filter.company_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(completion)
+    }
+}
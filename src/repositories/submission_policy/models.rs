@@ -0,0 +1,38 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::models::{ApprovalStatus, OverduePolicy};
+
+/// The deadline the timesheets of a company have to be submitted by.
+#[derive(Debug, FromRow, Clone)]
+pub struct SubmissionPolicy {
+    /// The day of the month after the one a timesheet ends in.
+    pub deadline_day: i32,
+    /// How many days before the deadline the employees are reminded.
+    pub reminder_days: Vec<i32>,
+    pub overdue_policy: OverduePolicy,
+    pub edited_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct SubmissionPolicyData {
+    pub deadline_day: i32,
+    pub reminder_days: Vec<i32>,
+    pub overdue_policy: OverduePolicy,
+}
+
+/// A timesheet which missed its deadline and is still not accepted.
+#[derive(Debug, FromRow, Clone)]
+pub struct OverdueTimesheet {
+    pub id: Uuid,
+    pub user_name: String,
+    pub company_name: String,
+    pub event_name: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub total_hours: f32,
+    pub approval_status: ApprovalStatus,
+    pub deadline: NaiveDate,
+    pub is_locked: bool,
+}
@@ -0,0 +1,289 @@
+use crate::common::DbResult;
+use crate::models::{ApprovalStatus, OverduePolicy, TimesheetTransition};
+use crate::repositories::notification::{models::NotificationEvent, notification_repo};
+use crate::repositories::timesheet::{models::TimesheetTransitionData, timesheet_repo};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::postgres::PgPool;
+use std::ops::DerefMut;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::models::{OverdueTimesheet, SubmissionPolicy, SubmissionPolicyData};
+
+#[derive(Clone)]
+pub struct SubmissionPolicyRepository {
+    pub pool: Arc<PgPool>,
+}
+
+#[async_trait]
+impl crate::repositories::repository::DbRepository for SubmissionPolicyRepository {
+    /// Database repository constructor
+    fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Method allowing the database repository to disconnect from the database pool gracefully
+    async fn disconnect(&mut self) -> () {
+        self.pool.close().await;
+    }
+}
+
+impl SubmissionPolicyRepository {
+    /// Returns `None` when the company has no deadline.
+    pub async fn read_one(&self, company_id: Uuid) -> DbResult<Option<SubmissionPolicy>> {
+        let executor = self.pool.as_ref();
+
+        let policy = sqlx::query_as!(
+            SubmissionPolicy,
+            r#"
+            SELECT deadline_day,
+                   reminder_days,
+                   overdue_policy AS "overdue_policy!: OverduePolicy",
+                   edited_at
+            FROM submission_policy
+            WHERE company_id = $1
+              AND deleted_at IS NULL;
+            "#,
+            company_id,
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(policy)
+    }
+
+    /// Sets the deadline of the company, replacing the previous one.
+    pub async fn upsert(
+        &self,
+        company_id: Uuid,
+        data: SubmissionPolicyData,
+    ) -> DbResult<SubmissionPolicy> {
+        let executor = self.pool.as_ref();
+
+        let policy = sqlx::query_as!(
+            SubmissionPolicy,
+            r#"
+            INSERT INTO submission_policy (company_id, deadline_day, reminder_days, overdue_policy)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (company_id) DO UPDATE
+            SET deadline_day = EXCLUDED.deadline_day,
+                reminder_days = EXCLUDED.reminder_days,
+                overdue_policy = EXCLUDED.overdue_policy,
+                edited_at = NOW(),
+                deleted_at = NULL,
+                deletion_id = NULL
+            RETURNING deadline_day,
+                      reminder_days,
+                      overdue_policy AS "overdue_policy!: OverduePolicy",
+                      edited_at;
+            "#,
+            company_id,
+            data.deadline_day,
+            &data.reminder_days,
+            data.overdue_policy as OverduePolicy,
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(policy)
+    }
+
+    pub async fn delete(&self, company_id: Uuid) -> DbResult<()> {
+        let executor = self.pool.as_ref();
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE submission_policy
+            SET deleted_at = NOW(),
+                edited_at = NOW()
+            WHERE company_id = $1
+              AND deleted_at IS NULL;
+            "#,
+            company_id,
+        )
+        .execute(executor)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Reminds the employees of the sheets they still have to submit. Only the
+    /// closest reminder is sent, so the ones missed while the application was
+    /// down don't pile up. Returns the number of reminders sent.
+    pub async fn send_due_reminders(&self, today: NaiveDate) -> DbResult<usize> {
+        let mut tx = self.pool.begin().await?;
+
+        let reminders = sqlx::query!(
+            r#"
+            SELECT timesheet.id AS timesheet_id,
+                   due.deadline AS "deadline!",
+                   reminder.days_before AS "days_before!"
+            FROM timesheet
+                INNER JOIN submission_policy
+                    ON submission_policy.company_id = timesheet.company_id
+                   AND submission_policy.deleted_at IS NULL
+                CROSS JOIN LATERAL (
+                    SELECT timesheet_deadline(timesheet.id,
+                                              timesheet.end_date,
+                                              submission_policy.deadline_day) AS deadline
+                ) AS due
+                CROSS JOIN LATERAL (
+                    SELECT MIN(days) AS days_before
+                    FROM UNNEST(submission_policy.reminder_days) AS days
+                    WHERE due.deadline - $1 <= days
+                ) AS reminder
+            WHERE timesheet.deleted_at IS NULL
+              AND timesheet.status IN ('not_requested', 'rejected')
+              AND timesheet.overdue_at IS NULL
+              AND due.deadline >= $1
+              AND reminder.days_before IS NOT NULL
+              AND NOT EXISTS (SELECT 1
+                              FROM submission_reminder
+                              WHERE submission_reminder.timesheet_id = timesheet.id
+                                AND submission_reminder.deadline = due.deadline
+                                AND submission_reminder.days_before <= reminder.days_before)
+            ORDER BY timesheet.id;
+            "#,
+            today,
+        )
+        .fetch_all(tx.deref_mut())
+        .await?;
+
+        for reminder in reminders.iter() {
+            notification_repo::emit_db_using_tx(
+                &mut tx,
+                NotificationEvent::SubmissionReminder {
+                    timesheet_id: reminder.timesheet_id,
+                    deadline: reminder.deadline,
+                },
+            )
+            .await?;
+
+            sqlx::query!(
+                r#"
+                INSERT INTO submission_reminder (timesheet_id, deadline, days_before)
+                VALUES ($1, $2, $3);
+                "#,
+                reminder.timesheet_id,
+                reminder.deadline,
+                reminder.days_before,
+            )
+            .execute(tx.deref_mut())
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(reminders.len())
+    }
+
+    /// Submits or locks the sheets which missed their deadline, as their
+    /// company's policy says. Returns the number of sheets it was applied to.
+    pub async fn apply_overdue_policies(&self, today: NaiveDate) -> DbResult<usize> {
+        let mut tx = self.pool.begin().await?;
+
+        let timesheets = sqlx::query!(
+            r#"
+            SELECT timesheet.id,
+                   timesheet.user_id,
+                   submission_policy.overdue_policy AS "overdue_policy!: OverduePolicy"
+            FROM timesheet
+                INNER JOIN submission_policy
+                    ON submission_policy.company_id = timesheet.company_id
+                   AND submission_policy.deleted_at IS NULL
+            WHERE timesheet.deleted_at IS NULL
+              AND timesheet.status IN ('not_requested', 'rejected')
+              AND timesheet.overdue_at IS NULL
+              AND timesheet_deadline(timesheet.id,
+                                     timesheet.end_date,
+                                     submission_policy.deadline_day) < $1
+            ORDER BY timesheet.id
+            FOR UPDATE OF timesheet;
+            "#,
+            today,
+        )
+        .fetch_all(tx.deref_mut())
+        .await?;
+
+        for timesheet in timesheets.iter() {
+            if timesheet.overdue_policy == OverduePolicy::Submit {
+                timesheet_repo::transition_db_using_tx(
+                    &mut tx,
+                    timesheet.id,
+                    TimesheetTransitionData {
+                        actor_id: timesheet.user_id,
+                        transition: TimesheetTransition::Submit,
+                        note: Some("Submitted automatically after the deadline.".to_string()),
+                    },
+                )
+                .await?;
+            }
+
+            sqlx::query!(
+                r#"
+                UPDATE timesheet
+                SET overdue_at = NOW(),
+                    is_editable = is_editable AND NOT $2,
+                    locked_at = CASE WHEN $2 THEN NOW() END,
+                    edited_at = NOW()
+                WHERE id = $1;
+                "#,
+                timesheet.id,
+                timesheet.overdue_policy == OverduePolicy::Lock,
+            )
+            .execute(tx.deref_mut())
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(timesheets.len())
+    }
+
+    /// The sheets of the manager's subordinates which missed their deadline
+    /// and still aren't accepted.
+    pub async fn read_overdue(&self, manager_id: Uuid) -> DbResult<Vec<OverdueTimesheet>> {
+        let executor = self.pool.as_ref();
+
+        let timesheets = sqlx::query_as!(
+            OverdueTimesheet,
+            r#"
+            SELECT timesheet.id,
+                   user_record.name AS user_name,
+                   company.name AS company_name,
+                   event.name AS event_name,
+                   timesheet.start_date,
+                   timesheet.end_date,
+                   timesheet.total_hours,
+                   timesheet.status AS "approval_status!: ApprovalStatus",
+                   timesheet_deadline(timesheet.id,
+                                      timesheet.end_date,
+                                      submission_policy.deadline_day) AS "deadline!",
+                   (timesheet.locked_at IS NOT NULL) AS "is_locked!"
+            FROM timesheet
+                INNER JOIN employment_subordinates($1) AS subordinate
+                    ON subordinate.user_id = timesheet.user_id
+                   AND subordinate.company_id = timesheet.company_id
+                INNER JOIN submission_policy
+                    ON submission_policy.company_id = timesheet.company_id
+                INNER JOIN user_record ON user_record.id = timesheet.user_id
+                INNER JOIN company ON company.id = timesheet.company_id
+                INNER JOIN event ON event.id = timesheet.event_id
+            WHERE timesheet.deleted_at IS NULL
+              AND timesheet.overdue_at IS NOT NULL
+              AND timesheet.status <> 'accepted'
+            ORDER BY timesheet.end_date, user_record.name;
+            "#,
+            manager_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(timesheets)
+    }
+}
@@ -4,7 +4,7 @@ use sqlx::{types::chrono::NaiveDateTime, FromRow};
 use uuid::Uuid;
 
 use crate::{
-    models::{Gender, TaskPriority, UserRole, UserStatus},
+    models::{Gender, TaskPriority, TaskStatus, UserRole, UserStatus},
     repositories::user::models::User,
 };
 
@@ -15,6 +15,9 @@ pub struct NewTask {
     pub title: String,
     pub description: Option<String>,
     pub priority: TaskPriority,
+    pub due_at: Option<NaiveDateTime>,
+    pub estimated_hours: Option<f32>,
+    pub parent_task_id: Option<Uuid>,
 }
 
 // TODO needs to be kept the same as in task/models.rs => TaskUserFlattened
@@ -28,6 +31,11 @@ pub struct Task {
     pub finished_at: Option<NaiveDateTime>,
     pub priority: TaskPriority,
     pub accepts_staff: bool,
+    pub status: TaskStatus,
+    pub due_at: Option<NaiveDateTime>,
+    pub estimated_hours: Option<f32>,
+    pub position: i32,
+    pub parent_task_id: Option<Uuid>,
     pub created_at: NaiveDateTime,
     pub edited_at: NaiveDateTime,
     pub deleted_at: Option<NaiveDateTime>,
@@ -44,6 +52,11 @@ pub struct TaskExtended {
     pub finished_at: Option<NaiveDateTime>,
     pub priority: TaskPriority,
     pub accepts_staff: bool,
+    pub status: TaskStatus,
+    pub due_at: Option<NaiveDateTime>,
+    pub estimated_hours: Option<f32>,
+    pub position: i32,
+    pub parent_task_id: Option<Uuid>,
     pub created_at: NaiveDateTime,
     pub edited_at: NaiveDateTime,
     pub deleted_at: Option<NaiveDateTime>,
@@ -56,6 +69,16 @@ pub struct TaskData {
     pub finished_at: Option<NaiveDateTime>,
     pub priority: Option<TaskPriority>,
     pub accepts_staff: Option<bool>,
+    pub status: Option<TaskStatus>,
+    pub due_at: Option<NaiveDateTime>,
+    pub estimated_hours: Option<f32>,
+    pub parent_task_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct TaskMoveData {
+    pub status: TaskStatus,
+    pub position: i32,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -78,6 +101,11 @@ pub struct TaskUserFlattened {
     pub task_finished_at: Option<NaiveDateTime>,
     pub task_priority: TaskPriority,
     pub task_accepts_staff: bool,
+    pub task_status: TaskStatus,
+    pub task_due_at: Option<NaiveDateTime>,
+    pub task_estimated_hours: Option<f32>,
+    pub task_position: i32,
+    pub task_parent_task_id: Option<Uuid>,
     pub task_created_at: NaiveDateTime,
     pub task_edited_at: NaiveDateTime,
     pub task_deleted_at: Option<NaiveDateTime>,
@@ -121,6 +149,11 @@ impl From<TaskUserFlattened> for TaskExtended {
             finished_at: value.task_finished_at,
             priority: value.task_priority,
             accepts_staff: value.task_accepts_staff,
+            status: value.task_status,
+            due_at: value.task_due_at,
+            estimated_hours: value.task_estimated_hours,
+            position: value.task_position,
+            parent_task_id: value.task_parent_task_id,
             created_at: value.task_created_at,
             edited_at: value.task_edited_at,
             deleted_at: value.task_deleted_at,
@@ -1,16 +1,70 @@
 use crate::{
     common::DbResult,
-    models::AcceptanceStatus,
-    repositories::{assigned_staff::models::AssignedStaffData, task::models::TaskUserFlattened},
+    models::{AcceptanceStatus, DeletedEntity},
+    repositories::{
+        assigned_staff::models::AssignedStaffData, task::models::TaskUserFlattened,
+        trash::trash_repo::delete_db_using_tx,
+    },
 };
 use async_trait::async_trait;
-use sqlx::postgres::PgPool;
+use sqlx::{postgres::PgPool, Postgres, Transaction};
 use std::{ops::DerefMut, sync::Arc};
 use uuid::Uuid;
 
-use super::models::{NewTask, Task, TaskData, TaskExtended, TaskFilter};
+use super::models::{NewTask, Task, TaskData, TaskExtended, TaskFilter, TaskMoveData};
 
-use crate::models::{Gender, TaskPriority, UserRole, UserStatus};
+use crate::models::{Gender, TaskPriority, TaskStatus, UserRole, UserStatus};
+
+/// A parent has to be a live task of the same event, and must not be
+/// the task itself or one of its descendants.
+async fn is_valid_parent_db_using_tx(
+    tx: &mut Transaction<'_, Postgres>,
+    task_id: Option<Uuid>,
+    event_id: Uuid,
+    parent_task_id: Uuid,
+) -> DbResult<bool> {
+    let parent_exists = sqlx::query_scalar!(
+        r#"SELECT EXISTS (
+            SELECT 1
+            FROM task
+            WHERE id = $1
+              AND event_id = $2
+              AND deleted_at IS NULL
+        ) AS "parent_exists!";"#,
+        parent_task_id,
+        event_id,
+    )
+    .fetch_one(tx.deref_mut())
+    .await?;
+
+    if !parent_exists {
+        return Ok(false);
+    }
+
+    if task_id.is_none() {
+        return Ok(true);
+    }
+
+    let creates_cycle = sqlx::query_scalar!(
+        r#"WITH RECURSIVE ancestor(id) AS (
+            SELECT $1::uuid
+            UNION
+            SELECT task.parent_task_id
+            FROM task
+            INNER JOIN ancestor ON task.id = ancestor.id
+            WHERE task.parent_task_id IS NOT NULL
+        )
+        SELECT EXISTS (
+            SELECT 1 FROM ancestor WHERE id = $2
+        ) AS "creates_cycle!";"#,
+        parent_task_id,
+        task_id.expect("Should be some."),
+    )
+    .fetch_one(tx.deref_mut())
+    .await?;
+
+    Ok(!creates_cycle)
+}
 
 #[derive(Clone)]
 pub struct TaskRepository {
@@ -35,12 +89,26 @@ impl TaskRepository {
     pub async fn create(&self, data: NewTask) -> DbResult<TaskExtended> {
         let mut tx = self.pool.begin().await?;
 
+        if let Some(parent_task_id) = data.parent_task_id {
+            if !is_valid_parent_db_using_tx(&mut tx, None, data.event_id, parent_task_id).await? {
+                return Err(sqlx::Error::TypeNotFound {
+                    type_name: "User Error".to_string(),
+                });
+            }
+        }
+
         let new_task: Task = sqlx::query_as!(
             Task,
             r#" INSERT INTO task (
-                event_id, creator_id, title, description, priority
+                event_id, creator_id, title, description, priority,
+                due_at, estimated_hours, parent_task_id, position
                 ) VALUES 
-                ($1, $2, $3, $4, $5) RETURNING id, 
+                ($1, $2, $3, $4, $5, $6, $7, $8,
+                 (SELECT COALESCE(MAX(position) + 1, 0)
+                  FROM task
+                  WHERE event_id = $1
+                    AND status = 'todo'
+                    AND deleted_at IS NULL)) RETURNING id, 
                 event_id, 
                 creator_id, 
                 title, 
@@ -48,6 +116,11 @@ impl TaskRepository {
                 finished_at, 
                 priority AS "priority!: TaskPriority", 
                 accepts_staff, 
+                status AS "status!: TaskStatus", 
+                due_at, 
+                estimated_hours, 
+                position, 
+                parent_task_id, 
                 created_at, 
                 edited_at, 
                 deleted_at;"#,
@@ -56,6 +129,9 @@ impl TaskRepository {
             data.title,
             data.description,
             data.priority as TaskPriority,
+            data.due_at,
+            data.estimated_hours,
+            data.parent_task_id,
         )
         .fetch_one(tx.deref_mut())
         .await?;
@@ -106,6 +182,11 @@ impl TaskRepository {
                 task.finished_at AS task_finished_at, 
                 task.priority AS "task_priority!: TaskPriority", 
                 task.accepts_staff AS task_accepts_staff, 
+                task.status AS "task_status!: TaskStatus", 
+                task.due_at AS task_due_at, 
+                task.estimated_hours AS task_estimated_hours, 
+                task.position AS task_position, 
+                task.parent_task_id AS task_parent_task_id, 
                 task.created_at AS task_created_at, 
                 task.edited_at AS task_edited_at, 
                 task.deleted_at AS task_deleted_at, 
@@ -147,6 +228,11 @@ impl TaskRepository {
                 task.finished_at AS task_finished_at, 
                 task.priority AS "task_priority!: TaskPriority", 
                 task.accepts_staff AS task_accepts_staff, 
+                task.status AS "task_status!: TaskStatus", 
+                task.due_at AS task_due_at, 
+                task.estimated_hours AS task_estimated_hours, 
+                task.position AS task_position, 
+                task.parent_task_id AS task_parent_task_id, 
                 task.created_at AS task_created_at, 
                 task.edited_at AS task_edited_at, 
                 task.deleted_at AS task_deleted_at, 
@@ -192,6 +278,11 @@ impl TaskRepository {
                 task.finished_at AS task_finished_at, 
                 task.priority AS "task_priority!: TaskPriority", 
                 task.accepts_staff AS task_accepts_staff, 
+                task.status AS "task_status!: TaskStatus", 
+                task.due_at AS task_due_at, 
+                task.estimated_hours AS task_estimated_hours, 
+                task.position AS task_position, 
+                task.parent_task_id AS task_parent_task_id, 
                 task.created_at AS task_created_at, 
                 task.edited_at AS task_edited_at, 
                 task.deleted_at AS task_deleted_at, 
@@ -223,12 +314,223 @@ impl TaskRepository {
         Ok(tasks.into_iter().map(|t| t.into()).collect())
     }
 
+    /// A task is blocked while any of its blockers or subtasks is not done.
+    pub async fn has_open_blockers(&self, task_id: Uuid) -> DbResult<bool> {
+        let executor = self.pool.as_ref();
+        self.has_open_blockers_db(executor, task_id).await
+    }
+
+    async fn has_open_blockers_db<'e, E>(&self, db: E, task_id: Uuid) -> DbResult<bool>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+    {
+        let is_blocked = sqlx::query_scalar!(
+            r#"SELECT EXISTS (
+                SELECT 1
+                FROM task_dependency
+                INNER JOIN task AS blocker ON task_dependency.blocker_id = blocker.id
+                WHERE task_dependency.task_id = $1
+                  AND task_dependency.deleted_at IS NULL
+                  AND blocker.deleted_at IS NULL
+                  AND blocker.status != 'done'
+                UNION ALL
+                SELECT 1
+                FROM task AS subtask
+                WHERE subtask.parent_task_id = $1
+                  AND subtask.deleted_at IS NULL
+                  AND subtask.status != 'done'
+            ) AS "is_blocked!";"#,
+            task_id,
+        )
+        .fetch_one(db)
+        .await?;
+
+        Ok(is_blocked)
+    }
+
+    pub async fn read_subtasks(&self, task_id: Uuid) -> DbResult<Vec<TaskExtended>> {
+        let executor = self.pool.as_ref();
+
+        let tasks: Vec<TaskUserFlattened> = sqlx::query_as!(
+            TaskUserFlattened,
+            r#"SELECT 
+                task.id AS task_id, 
+                task.event_id AS task_event_id, 
+                task.creator_id AS task_creator_id, 
+                task.title AS task_title, 
+                task.description AS task_description, 
+                task.finished_at AS task_finished_at, 
+                task.priority AS "task_priority!: TaskPriority", 
+                task.accepts_staff AS task_accepts_staff, 
+                task.status AS "task_status!: TaskStatus", 
+                task.due_at AS task_due_at, 
+                task.estimated_hours AS task_estimated_hours, 
+                task.position AS task_position, 
+                task.parent_task_id AS task_parent_task_id, 
+                task.created_at AS task_created_at, 
+                task.edited_at AS task_edited_at, 
+                task.deleted_at AS task_deleted_at, 
+                user_record.id AS user_id, 
+                user_record.name AS user_name, 
+                user_record.email AS user_email, 
+                user_record.birth AS user_birth, 
+                user_record.avatar_url AS user_avatar_url, 
+                user_record.gender AS "user_gender!: Gender", 
+                user_record.role AS "user_role!: UserRole",
+                user_record.status AS "user_status!: UserStatus", 
+                user_record.created_at AS user_created_at, 
+                user_record.edited_at AS user_edited_at, 
+                user_record.deleted_at AS user_deleted_at
+            FROM task 
+            INNER JOIN event_staff ON task.creator_id=event_staff.id
+            INNER JOIN user_record ON event_staff.user_id=user_record.id 
+            WHERE task.parent_task_id=$1
+              AND task.deleted_at IS NULL
+            ORDER BY task.title"#,
+            task_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(tasks.into_iter().map(|t| t.into()).collect())
+    }
+
+    pub async fn read_board_for_event(&self, event_id: Uuid) -> DbResult<Vec<TaskExtended>> {
+        let executor = self.pool.as_ref();
+
+        let tasks: Vec<TaskUserFlattened> = sqlx::query_as!(
+            TaskUserFlattened,
+            r#"SELECT 
+                task.id AS task_id, 
+                task.event_id AS task_event_id, 
+                task.creator_id AS task_creator_id, 
+                task.title AS task_title, 
+                task.description AS task_description, 
+                task.finished_at AS task_finished_at, 
+                task.priority AS "task_priority!: TaskPriority", 
+                task.accepts_staff AS task_accepts_staff, 
+                task.status AS "task_status!: TaskStatus", 
+                task.due_at AS task_due_at, 
+                task.estimated_hours AS task_estimated_hours, 
+                task.position AS task_position, 
+                task.parent_task_id AS task_parent_task_id, 
+                task.created_at AS task_created_at, 
+                task.edited_at AS task_edited_at, 
+                task.deleted_at AS task_deleted_at, 
+                user_record.id AS user_id, 
+                user_record.name AS user_name, 
+                user_record.email AS user_email, 
+                user_record.birth AS user_birth, 
+                user_record.avatar_url AS user_avatar_url, 
+                user_record.gender AS "user_gender!: Gender", 
+                user_record.role AS "user_role!: UserRole",
+                user_record.status AS "user_status!: UserStatus", 
+                user_record.created_at AS user_created_at, 
+                user_record.edited_at AS user_edited_at, 
+                user_record.deleted_at AS user_deleted_at
+            FROM task 
+            INNER JOIN event_staff ON task.creator_id=event_staff.id
+            INNER JOIN user_record ON event_staff.user_id=user_record.id 
+            WHERE task.event_id=$1
+              AND task.deleted_at IS NULL
+            ORDER BY task.status, task.position, task.title"#,
+            event_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(tasks.into_iter().map(|t| t.into()).collect())
+    }
+
+    /// Moves a task into the given board column and renumbers that column,
+    /// so that the task ends up at `data.position` (clamped to the column size).
+    pub async fn move_task(&self, task_id: Uuid, data: TaskMoveData) -> DbResult<TaskExtended> {
+        if data.position < 0 {
+            return Err(sqlx::Error::TypeNotFound {
+                type_name: "User Error".to_string(),
+            });
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        if data.status == TaskStatus::Done
+            && self.has_open_blockers_db(tx.deref_mut(), task_id).await?
+        {
+            return Err(sqlx::Error::TypeNotFound {
+                type_name: "User Error".to_string(),
+            });
+        }
+
+        let moved = sqlx::query!(
+            r#"UPDATE task
+            SET status = $1,
+                finished_at = CASE
+                    WHEN $1::task_status = 'done' THEN COALESCE(finished_at, NOW())
+                    ELSE NULL
+                END,
+                edited_at = NOW()
+            WHERE id = $2
+              AND deleted_at IS NULL
+            RETURNING event_id;
+            "#,
+            data.status.clone() as TaskStatus,
+            task_id,
+        )
+        .fetch_optional(tx.deref_mut())
+        .await?;
+
+        if moved.is_none() {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        let event_id = moved.expect("Should be some.").event_id;
+
+        let mut column: Vec<Uuid> = sqlx::query_scalar!(
+            r#"SELECT id
+            FROM task
+            WHERE event_id = $1
+              AND status = $2
+              AND id <> $3
+              AND deleted_at IS NULL
+            ORDER BY position, title
+            FOR UPDATE;
+            "#,
+            event_id,
+            data.status as TaskStatus,
+            task_id,
+        )
+        .fetch_all(tx.deref_mut())
+        .await?;
+
+        let position = (data.position as usize).min(column.len());
+        column.insert(position, task_id);
+
+        sqlx::query!(
+            r#"UPDATE task
+            SET position = ordered.idx - 1
+            FROM UNNEST($1::uuid[]) WITH ORDINALITY AS ordered(id, idx)
+            WHERE task.id = ordered.id;
+            "#,
+            &column,
+        )
+        .execute(tx.deref_mut())
+        .await?;
+
+        let task = self.read_one_db(tx.deref_mut(), task_id).await?;
+        tx.commit().await?;
+
+        Ok(task)
+    }
+
     pub async fn update(&self, task_id: Uuid, data: TaskData) -> DbResult<TaskExtended> {
         if data.description.is_none()
             && data.finished_at.is_none()
             && data.priority.is_none()
             && data.title.is_none()
             && data.accepts_staff.is_none()
+            && data.status.is_none()
+            && data.due_at.is_none()
+            && data.estimated_hours.is_none()
+            && data.parent_task_id.is_none()
         {
             // TODO - add better error
             return Err(sqlx::Error::TypeNotFound {
@@ -238,6 +540,43 @@ impl TaskRepository {
 
         let mut tx = self.pool.begin().await?;
 
+        let is_completion = data.finished_at.is_some() || data.status == Some(TaskStatus::Done);
+        if is_completion && self.has_open_blockers_db(tx.deref_mut(), task_id).await? {
+            return Err(sqlx::Error::TypeNotFound {
+                type_name: "User Error".to_string(),
+            });
+        }
+
+        if let Some(parent_task_id) = data.parent_task_id {
+            let event_id = sqlx::query_scalar!(
+                r#"SELECT event_id
+                FROM task
+                WHERE id = $1
+                  AND deleted_at IS NULL
+                FOR UPDATE;"#,
+                task_id,
+            )
+            .fetch_optional(tx.deref_mut())
+            .await?;
+
+            if event_id.is_none() {
+                return Err(sqlx::Error::RowNotFound);
+            }
+
+            let is_valid = is_valid_parent_db_using_tx(
+                &mut tx,
+                Some(task_id),
+                event_id.expect("Should be some."),
+                parent_task_id,
+            )
+            .await?;
+            if !is_valid {
+                return Err(sqlx::Error::TypeNotFound {
+                    type_name: "User Error".to_string(),
+                });
+            }
+        }
+
         let task_res: Option<Task> = sqlx::query_as!(
             Task,
             r#"UPDATE 
@@ -245,12 +584,29 @@ impl TaskRepository {
             SET 
                 title = COALESCE($1, title), 
                 description = COALESCE($2, description), 
-                finished_at = COALESCE($3, finished_at), 
+                finished_at = CASE
+                    WHEN $6::task_status = 'done' THEN COALESCE($3::timestamp, finished_at, NOW()::timestamp)
+                    WHEN $6::task_status IS NOT NULL THEN NULL
+                    ELSE COALESCE($3, finished_at)
+                END, 
                 priority = COALESCE($4, priority),
                 accepts_staff = COALESCE($5, accepts_staff),
+                position = CASE
+                    WHEN $6::task_status IS NOT NULL AND $6::task_status <> status THEN
+                        (SELECT COALESCE(MAX(column_task.position) + 1, 0)
+                         FROM task AS column_task
+                         WHERE column_task.event_id = task.event_id
+                           AND column_task.status = $6::task_status
+                           AND column_task.deleted_at IS NULL)
+                    ELSE position
+                END,
+                status = COALESCE($6::task_status, status),
+                due_at = COALESCE($7, due_at),
+                estimated_hours = COALESCE($8, estimated_hours),
+                parent_task_id = COALESCE($9, parent_task_id),
                 edited_at = NOW() 
             WHERE 
-                id = $6 
+                id = $10 
                 AND deleted_at IS NULL 
             RETURNING id, 
                 event_id, 
@@ -260,6 +616,11 @@ impl TaskRepository {
                 finished_at, 
                 priority as "priority!: TaskPriority", 
                 accepts_staff, 
+                status AS "status!: TaskStatus", 
+                due_at, 
+                estimated_hours, 
+                position, 
+                parent_task_id, 
                 created_at, 
                 deleted_at, 
                 edited_at;
@@ -269,6 +630,10 @@ impl TaskRepository {
             data.finished_at,
             data.priority as Option<TaskPriority>,
             data.accepts_staff,
+            data.status as Option<TaskStatus>,
+            data.due_at,
+            data.estimated_hours,
+            data.parent_task_id,
             task_id,
         )
         .fetch_optional(tx.deref_mut())
@@ -286,36 +651,12 @@ impl TaskRepository {
         Ok(task)
     }
 
-    pub async fn delete(&self, task_id: Uuid) -> DbResult<()> {
-        let executor = self.pool.as_ref();
+    pub async fn delete(&self, task_id: Uuid, deleted_by: Option<Uuid>) -> DbResult<()> {
+        let mut tx = self.pool.begin().await?;
 
-        let result = sqlx::query_as!(
-            Task,
-            r#"UPDATE task
-            SET deleted_at = NOW(), 
-                edited_at = NOW()
-            WHERE id = $1
-            AND deleted_at IS NULL
-            RETURNING id, 
-                event_id, 
-                creator_id, 
-                title, 
-                description, 
-                finished_at, 
-                priority as "priority!: TaskPriority", 
-                accepts_staff, 
-                created_at, 
-                deleted_at, 
-                edited_at;
-            "#,
-            task_id,
-        )
-        .fetch_optional(executor)
-        .await?;
+        delete_db_using_tx(&mut tx, DeletedEntity::Task, task_id, None, deleted_by).await?;
 
-        if result.is_none() {
-            return Err(sqlx::Error::RowNotFound);
-        }
+        tx.commit().await?;
 
         Ok(())
     }
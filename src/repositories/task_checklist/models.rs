@@ -0,0 +1,27 @@
+use serde::Deserialize;
+use sqlx::{types::chrono::NaiveDateTime, FromRow};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewChecklistItem {
+    pub task_id: Uuid,
+    pub content: String,
+}
+
+#[derive(Debug, FromRow)]
+pub struct ChecklistItem {
+    pub id: Uuid,
+    pub task_id: Uuid,
+    pub content: String,
+    pub position: i32,
+    pub finished_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub edited_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChecklistItemData {
+    pub content: Option<String>,
+    pub is_finished: Option<bool>,
+}
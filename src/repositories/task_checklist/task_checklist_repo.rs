@@ -0,0 +1,176 @@
+use crate::common::DbResult;
+use async_trait::async_trait;
+use sqlx::postgres::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::models::{ChecklistItem, ChecklistItemData, NewChecklistItem};
+
+#[derive(Clone)]
+pub struct TaskChecklistRepository {
+    pub pool: Arc<PgPool>,
+}
+
+#[async_trait]
+impl crate::repositories::repository::DbRepository for TaskChecklistRepository {
+    /// Database repository constructor
+    fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Method allowing the database repository to disconnect from the database pool gracefully
+    async fn disconnect(&mut self) -> () {
+        self.pool.close().await;
+    }
+}
+
+impl TaskChecklistRepository {
+    pub async fn create(&self, data: NewChecklistItem) -> DbResult<ChecklistItem> {
+        let executor = self.pool.as_ref();
+
+        let item = sqlx::query_as!(
+            ChecklistItem,
+            r#"INSERT INTO task_checklist_item (task_id, content, position)
+            SELECT $1, $2, COALESCE(MAX(position) + 1, 0)
+            FROM task_checklist_item
+            WHERE task_id = $1
+              AND deleted_at IS NULL
+            RETURNING id,
+                      task_id,
+                      content,
+                      position,
+                      finished_at,
+                      created_at,
+                      edited_at,
+                      deleted_at;"#,
+            data.task_id,
+            data.content,
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(item)
+    }
+
+    pub async fn read_one(&self, item_id: Uuid) -> DbResult<ChecklistItem> {
+        let executor = self.pool.as_ref();
+
+        let item = sqlx::query_as!(
+            ChecklistItem,
+            r#"SELECT id,
+                      task_id,
+                      content,
+                      position,
+                      finished_at,
+                      created_at,
+                      edited_at,
+                      deleted_at
+            FROM task_checklist_item
+            WHERE id = $1
+              AND deleted_at IS NULL;"#,
+            item_id,
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(item)
+    }
+
+    pub async fn read_all_for_task(&self, task_id: Uuid) -> DbResult<Vec<ChecklistItem>> {
+        let executor = self.pool.as_ref();
+
+        let items = sqlx::query_as!(
+            ChecklistItem,
+            r#"SELECT id,
+                      task_id,
+                      content,
+                      position,
+                      finished_at,
+                      created_at,
+                      edited_at,
+                      deleted_at
+            FROM task_checklist_item
+            WHERE task_id = $1
+              AND deleted_at IS NULL
+            ORDER BY position;"#,
+            task_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(items)
+    }
+
+    pub async fn update(&self, item_id: Uuid, data: ChecklistItemData) -> DbResult<ChecklistItem> {
+        if data.content.is_none() && data.is_finished.is_none() {
+            return Err(sqlx::Error::TypeNotFound {
+                type_name: "User Error".to_string(),
+            });
+        }
+
+        let executor = self.pool.as_ref();
+
+        let item = sqlx::query_as!(
+            ChecklistItem,
+            r#"UPDATE task_checklist_item
+            SET content = COALESCE($1, content),
+                finished_at = CASE
+                    WHEN $2::boolean IS NULL THEN finished_at
+                    WHEN $2::boolean THEN COALESCE(finished_at, NOW())
+                    ELSE NULL
+                END,
+                edited_at = NOW()
+            WHERE id = $3
+              AND deleted_at IS NULL
+            RETURNING id,
+                      task_id,
+                      content,
+                      position,
+                      finished_at,
+                      created_at,
+                      edited_at,
+                      deleted_at;"#,
+            data.content,
+            data.is_finished,
+            item_id,
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        if item.is_none() {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        Ok(item.expect("Should be some."))
+    }
+
+    pub async fn delete(&self, item_id: Uuid) -> DbResult<ChecklistItem> {
+        let executor = self.pool.as_ref();
+
+        let item = sqlx::query_as!(
+            ChecklistItem,
+            r#"UPDATE task_checklist_item
+            SET deleted_at = NOW(),
+                edited_at = NOW()
+            WHERE id = $1
+              AND deleted_at IS NULL
+            RETURNING id,
+                      task_id,
+                      content,
+                      position,
+                      finished_at,
+                      created_at,
+                      edited_at,
+                      deleted_at;"#,
+            item_id,
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        if item.is_none() {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        Ok(item.expect("Should be some."))
+    }
+}
@@ -0,0 +1,28 @@
+use serde::Deserialize;
+use sqlx::{types::chrono::NaiveDateTime, FromRow};
+use uuid::Uuid;
+
+use crate::models::TaskStatus;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NewTaskDependency {
+    pub task_id: Uuid,
+    pub blocker_id: Uuid, // task which has to be done before `task_id`
+}
+
+#[derive(Debug, FromRow)]
+pub struct TaskDependency {
+    pub task_id: Uuid,
+    pub blocker_id: Uuid,
+    pub created_at: NaiveDateTime,
+    pub edited_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+}
+
+/// The other end of a dependency edge, as shown next to a task.
+#[derive(Debug, FromRow)]
+pub struct TaskDependencyLite {
+    pub task_id: Uuid,
+    pub title: String,
+    pub status: TaskStatus,
+}
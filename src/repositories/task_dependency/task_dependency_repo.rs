@@ -0,0 +1,234 @@
+use crate::{common::DbResult, models::TaskStatus};
+use async_trait::async_trait;
+use sqlx::postgres::PgPool;
+use std::{ops::DerefMut, sync::Arc};
+use uuid::Uuid;
+
+use super::models::{NewTaskDependency, TaskDependency, TaskDependencyLite};
+
+#[derive(Clone)]
+pub struct TaskDependencyRepository {
+    pub pool: Arc<PgPool>,
+}
+
+#[async_trait]
+impl crate::repositories::repository::DbRepository for TaskDependencyRepository {
+    /// Database repository constructor
+    fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Method allowing the database repository to disconnect from the database pool gracefully
+    async fn disconnect(&mut self) -> () {
+        self.pool.close().await;
+    }
+}
+
+impl TaskDependencyRepository {
+    pub async fn create(&self, data: NewTaskDependency) -> DbResult<TaskDependency> {
+        let mut tx = self.pool.begin().await?;
+
+        let event_ids: Vec<Uuid> = sqlx::query_scalar!(
+            r#"SELECT event_id
+            FROM task
+            WHERE id IN ($1, $2)
+              AND deleted_at IS NULL;"#,
+            data.task_id,
+            data.blocker_id,
+        )
+        .fetch_all(tx.deref_mut())
+        .await?;
+
+        // Both tasks have to exist and belong to the same event.
+        if data.task_id == data.blocker_id || event_ids.len() != 2 || event_ids[0] != event_ids[1] {
+            return Err(sqlx::Error::TypeNotFound {
+                type_name: "User Error".to_string(),
+            });
+        }
+
+        // Serialize dependency changes per event, so that two concurrent
+        // inserts can't close a cycle together.
+        sqlx::query!(
+            "SELECT pg_advisory_xact_lock(hashtext($1::text));",
+            event_ids[0].to_string(),
+        )
+        .execute(tx.deref_mut())
+        .await?;
+
+        if self
+            .creates_cycle_db(tx.deref_mut(), data.task_id, data.blocker_id)
+            .await?
+        {
+            return Err(sqlx::Error::TypeNotFound {
+                type_name: "User Error".to_string(),
+            });
+        }
+
+        let dependency = sqlx::query_as!(
+            TaskDependency,
+            r#"INSERT INTO task_dependency (task_id, blocker_id)
+            VALUES ($1, $2)
+            ON CONFLICT (task_id, blocker_id) DO UPDATE
+            SET deleted_at = NULL,
+                deletion_id = NULL,
+                edited_at = NOW()
+            RETURNING task_id,
+                      blocker_id,
+                      created_at,
+                      edited_at,
+                      deleted_at;"#,
+            data.task_id,
+            data.blocker_id,
+        )
+        .fetch_one(tx.deref_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(dependency)
+    }
+
+    /// Checks whether `blocker_id` already (transitively) waits for `task_id`,
+    /// in which case the new edge would close a cycle. A task waits for its
+    /// blockers as well as for its subtasks.
+    pub async fn creates_cycle(&self, task_id: Uuid, blocker_id: Uuid) -> DbResult<bool> {
+        let executor = self.pool.as_ref();
+        self.creates_cycle_db(executor, task_id, blocker_id).await
+    }
+
+    async fn creates_cycle_db<'e, E>(
+        &self,
+        db: E,
+        task_id: Uuid,
+        blocker_id: Uuid,
+    ) -> DbResult<bool>
+    where
+        E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+    {
+        let creates_cycle = sqlx::query_scalar!(
+            r#"WITH RECURSIVE upstream(id) AS (
+                SELECT $1::uuid
+                UNION
+                SELECT edge.waits_for
+                FROM (
+                    SELECT task_id AS waiting, blocker_id AS waits_for
+                    FROM task_dependency
+                    WHERE deleted_at IS NULL
+                    UNION ALL
+                    SELECT parent_task_id, id
+                    FROM task
+                    WHERE parent_task_id IS NOT NULL
+                      AND deleted_at IS NULL
+                ) AS edge
+                INNER JOIN upstream ON edge.waiting = upstream.id
+            )
+            SELECT EXISTS (
+                SELECT 1 FROM upstream WHERE id = $2
+            ) AS "creates_cycle!";"#,
+            blocker_id,
+            task_id,
+        )
+        .fetch_one(db)
+        .await?;
+
+        Ok(creates_cycle)
+    }
+
+    pub async fn read_blockers(&self, task_id: Uuid) -> DbResult<Vec<TaskDependencyLite>> {
+        let executor = self.pool.as_ref();
+
+        let blockers = sqlx::query_as!(
+            TaskDependencyLite,
+            r#"SELECT task.id AS task_id,
+                      task.title,
+                      task.status AS "status!: TaskStatus"
+            FROM task_dependency
+            INNER JOIN task ON task_dependency.blocker_id = task.id
+            WHERE task_dependency.task_id = $1
+              AND task_dependency.deleted_at IS NULL
+              AND task.deleted_at IS NULL
+            ORDER BY task.title;"#,
+            task_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(blockers)
+    }
+
+    pub async fn read_blocked(&self, blocker_id: Uuid) -> DbResult<Vec<TaskDependencyLite>> {
+        let executor = self.pool.as_ref();
+
+        let blocked = sqlx::query_as!(
+            TaskDependencyLite,
+            r#"SELECT task.id AS task_id,
+                      task.title,
+                      task.status AS "status!: TaskStatus"
+            FROM task_dependency
+            INNER JOIN task ON task_dependency.task_id = task.id
+            WHERE task_dependency.blocker_id = $1
+              AND task_dependency.deleted_at IS NULL
+              AND task.deleted_at IS NULL
+            ORDER BY task.title;"#,
+            blocker_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(blocked)
+    }
+
+    pub async fn read_all_for_event(&self, event_id: Uuid) -> DbResult<Vec<TaskDependency>> {
+        let executor = self.pool.as_ref();
+
+        let dependencies = sqlx::query_as!(
+            TaskDependency,
+            r#"SELECT task_dependency.task_id,
+                      task_dependency.blocker_id,
+                      task_dependency.created_at,
+                      task_dependency.edited_at,
+                      task_dependency.deleted_at
+            FROM task_dependency
+            INNER JOIN task ON task_dependency.task_id = task.id
+            INNER JOIN task AS blocker ON task_dependency.blocker_id = blocker.id
+            WHERE task.event_id = $1
+              AND task_dependency.deleted_at IS NULL
+              AND task.deleted_at IS NULL
+              AND blocker.deleted_at IS NULL;"#,
+            event_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(dependencies)
+    }
+
+    pub async fn delete(&self, task_id: Uuid, blocker_id: Uuid) -> DbResult<()> {
+        let executor = self.pool.as_ref();
+
+        let result = sqlx::query_as!(
+            TaskDependency,
+            r#"UPDATE task_dependency
+            SET deleted_at = NOW(),
+                edited_at = NOW()
+            WHERE task_id = $1
+              AND blocker_id = $2
+              AND deleted_at IS NULL
+            RETURNING task_id,
+                      blocker_id,
+                      created_at,
+                      edited_at,
+                      deleted_at;"#,
+            task_id,
+            blocker_id,
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        if result.is_none() {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use crate::models::{ApprovalStatus, EmploymentContract};
+use crate::models::{ApprovalStatus, EmploymentContract, TimesheetTransition};
 use crate::repositories::wage_preset::models::WagePreset;
 use crate::utils::wage_calc::models::YearAndMonth;
 use chrono::{NaiveDate, NaiveDateTime};
@@ -56,13 +56,88 @@ pub struct WorkdayUpdateData {
     pub comment: Option<String>,
 }
 
+/// The status of a timesheet is only changed through a `TimesheetTransition`.
 #[derive(Debug, Clone, Deserialize, FromRow)]
 pub struct TimesheetUpdateData {
-    pub is_editable: Option<bool>,
-    pub status: Option<ApprovalStatus>,
     pub manager_note: Option<String>,
 }
 
+#[derive(Debug, Clone)]
+pub struct TimesheetTransitionData {
+    pub actor_id: Uuid,
+    pub transition: TimesheetTransition,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct TimesheetHistoryEntry {
+    pub id: Uuid,
+    pub timesheet_id: Uuid,
+    pub actor_id: Uuid,
+    pub actor_name: String,
+    pub transition: TimesheetTransition,
+    pub from_status: ApprovalStatus,
+    pub to_status: ApprovalStatus,
+    pub note: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct WorkdayFlagData {
+    pub timesheet_id: Uuid,
+    pub date: NaiveDate,
+    pub flagged_by: Uuid,
+    pub reason: String,
+    pub suggested_hours: Option<f32>,
+    pub suggested_comment: Option<String>,
+}
+
+/// A workday flagged by the manager, along with the values it had when it was
+/// flagged and the values it has now.
+#[derive(Debug, Clone, FromRow)]
+pub struct WorkdayFlag {
+    pub id: Uuid,
+    pub timesheet_id: Uuid,
+    pub date: NaiveDate,
+    pub flagged_by: Uuid,
+    pub flagged_by_name: String,
+    pub reason: String,
+    pub suggested_hours: Option<f32>,
+    pub suggested_comment: Option<String>,
+    pub original_hours: f32,
+    pub original_comment: Option<String>,
+    pub current_hours: f32,
+    pub current_comment: Option<String>,
+    pub applied: bool,
+    pub created_at: NaiveDateTime,
+}
+
+impl WorkdayFlag {
+    pub fn has_suggestion(&self) -> bool {
+        self.suggested_hours.is_some() || self.suggested_comment.is_some()
+    }
+
+    /// Whether the workday changed since it was flagged.
+    pub fn is_changed(&self) -> bool {
+        self.current_hours != self.original_hours || self.current_comment != self.original_comment
+    }
+}
+
+/// The open flags of a timesheet, or the applied ones when it was approved
+/// with adjustments.
+#[derive(Debug, Clone, Default)]
+pub struct TimesheetReview {
+    pub flags: Vec<WorkdayFlag>,
+    pub adjusted_at: Option<NaiveDateTime>,
+    pub acknowledged_at: Option<NaiveDateTime>,
+}
+
+impl TimesheetReview {
+    pub fn awaits_acknowledgement(&self) -> bool {
+        self.adjusted_at.is_some() && self.acknowledged_at.is_none()
+    }
+}
+
 #[derive(Debug, Deserialize, FromRow)]
 pub struct TimesheetReadAllData {
     pub limit: Option<i64>,
@@ -98,3 +173,51 @@ pub struct TimesheetsWithWorkdaysExtended {
     //
     pub date_to_wage_presets: HashMap<YearAndMonth, Option<WagePreset>>,
 }
+
+/// A timesheet with the employee's details, as exported to a spreadsheet.
+#[derive(Debug, Clone, FromRow)]
+pub struct TimesheetExport {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub user_name: String,
+    pub user_email: String,
+    pub event_name: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub approval_status: ApprovalStatus,
+    pub employment_type: EmploymentContract,
+    pub hourly_wage: f64,
+}
+
+/// Narrows the timesheets a manager has to review, every field is optional.
+#[derive(Debug, Clone, Default)]
+pub struct ReviewQueueFilter {
+    pub event_id: Option<Uuid>,
+    /// Any day of the month the sheets overlap.
+    pub month: Option<NaiveDate>,
+    pub status: Option<ApprovalStatus>,
+    /// Only the sheets with more hours than this.
+    pub min_hours: Option<f32>,
+}
+
+/// A timesheet of a direct or delegated subordinate of the manager.
+#[derive(Debug, Clone, FromRow)]
+pub struct ReviewQueueEntry {
+    pub id: Uuid,
+    pub user_name: String,
+    pub company_name: String,
+    pub event_name: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub total_hours: f32,
+    pub approval_status: ApprovalStatus,
+    pub is_direct: bool,
+    pub flagged_days: i64,
+    pub submitted_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct ReviewQueueEvent {
+    pub id: Uuid,
+    pub name: String,
+}
@@ -1,16 +1,20 @@
 use crate::common::DbResult;
-use crate::models::ApprovalStatus;
+use crate::models::{ApprovalStatus, EmploymentContract, TimesheetTransition};
 use crate::repositories::timesheet::models::{
-    TimesheetCreateData, TimesheetReadAllData, TimesheetStructureData, TimesheetUpdateData,
-    TimesheetWithEvent, TimesheetWithWorkdays, TimesheetsWithWorkdaysExtended, Workday,
+    ReviewQueueEntry, ReviewQueueEvent, ReviewQueueFilter, TimesheetCreateData, TimesheetExport,
+    TimesheetHistoryEntry, TimesheetReadAllData, TimesheetReview, TimesheetStructureData,
+    TimesheetTransitionData, TimesheetUpdateData, TimesheetWithEvent, TimesheetWithWorkdays,
+    TimesheetsWithWorkdaysExtended, Workday, WorkdayFlag, WorkdayFlagData,
 };
 
 use crate::repositories::wage_preset::{models::WagePreset, wage_preset_repo};
 
 use crate::repositories::employment::employment_repo;
+use crate::repositories::notification::{models::NotificationEvent, notification_repo};
 use crate::utils::wage_calc::models::YearAndMonth;
 
 use chrono::{Datelike, Duration, Months, NaiveDate};
+use futures::stream::BoxStream;
 use sqlx::postgres::PgPool;
 use sqlx::{Postgres, Transaction};
 use std::collections::HashMap;
@@ -51,21 +55,28 @@ async fn read_some_timesheet_workdays_db_using_tx(
     .await
 }
 
-/// Reads all workdays of a specific timesheet.
+/// Reads all workdays of a specific timesheet, including the ones kept
+/// outside of its dates when its event was moved.
 async fn read_all_timesheet_workdays_db_using_tx(
     tx: &mut Transaction<'_, sqlx::Postgres>,
     timesheet: &TimesheetWithEvent,
 ) -> DbResult<Vec<Workday>> {
-    //
-    // Pass timesheet's start and end date respectively
-    // in order to get all its workdays.
-    //
-    read_some_timesheet_workdays_db_using_tx(
-        tx,
+    sqlx::query_as!(
+        Workday,
+        r#"
+        SELECT timesheet_id,
+               date,
+               total_hours,
+               comment AS "comment?",
+               created_at,
+               edited_at
+        FROM workday
+        WHERE timesheet_id = $1
+        ORDER BY date;
+        "#,
         timesheet.id,
-        timesheet.start_date,
-        timesheet.end_date,
     )
+    .fetch_all(tx.deref_mut())
     .await
 }
 
@@ -98,7 +109,7 @@ pub async fn read_all_with_date_from_to_per_employment_db_using_tx(
                    timesheet.end_date,
                    total_hours,
                    is_editable,
-                   status AS "approval_status!: ApprovalStatus",
+                   timesheet.status AS "approval_status!: ApprovalStatus",
                    manager_note AS "manager_note?",
                    user_id,
                    company_id,
@@ -148,6 +159,307 @@ pub async fn read_all_with_date_from_to_per_employment_db_using_tx(
     Ok(timesheets_with_workdays)
 }
 
+/// Moves the timesheet through its approval process and records who did so in
+/// its history. Whether the actor may make the transition is up to the caller,
+/// the state the sheet is in is checked here.
+pub async fn transition_db_using_tx(
+    tx: &mut Transaction<'_, Postgres>,
+    timesheet_id: Uuid,
+    data: TimesheetTransitionData,
+) -> DbResult<()> {
+    let previous = sqlx::query!(
+        r#"
+        SELECT status AS "status!: ApprovalStatus",
+               (adjusted_at IS NOT NULL
+                AND acknowledged_at IS NULL) AS "awaits_acknowledgement!"
+        FROM timesheet
+        WHERE id = $1
+          AND deleted_at IS NULL
+        FOR UPDATE;
+        "#,
+        timesheet_id
+    )
+    .fetch_optional(tx.deref_mut())
+    .await?;
+
+    if previous.is_none() {
+        return Err(sqlx::Error::RowNotFound);
+    }
+
+    let previous = previous.expect("Should be some.");
+    let previous_status = previous.status;
+    if !data.transition.is_allowed_from(&previous_status)
+        || (data.transition == TimesheetTransition::Acknowledge && !previous.awaits_acknowledgement)
+    {
+        return Err(sqlx::Error::TypeNotFound {
+            type_name: "User Error".to_string(),
+        });
+    }
+
+    if data.transition == TimesheetTransition::Adjust {
+        let adjusted_days = TimesheetRepository::apply_flag_suggestions(tx, timesheet_id).await?;
+        if adjusted_days == 0 {
+            return Err(sqlx::Error::TypeNotFound {
+                type_name: "User Error".to_string(),
+            });
+        }
+    }
+
+    let status = data.transition.target();
+    let note = data
+        .note
+        .map(|note| note.trim().to_string())
+        .filter(|note| !note.is_empty());
+
+    // The employee can only edit the workdays while the sheet is
+    // waiting to be submitted again. A sheet locked after its deadline
+    // stays locked until the manager sends it back, which gives it a new
+    // deadline.
+    sqlx::query!(
+        r#"
+        UPDATE timesheet
+        SET
+            is_editable = $1 AND (locked_at IS NULL OR $3),
+            locked_at = CASE WHEN $3 THEN NULL ELSE locked_at END,
+            overdue_at = CASE WHEN $1 AND $3 THEN NULL ELSE overdue_at END,
+            status = $2,
+            manager_note = CASE WHEN $3 THEN COALESCE($4, manager_note)
+                                ELSE manager_note END,
+            adjusted_at = CASE $5
+                              WHEN 'adjust' THEN NOW()
+                              WHEN 'acknowledge' THEN adjusted_at
+                          END,
+            acknowledged_at = CASE WHEN $5 = 'acknowledge' THEN NOW() END,
+            edited_at = NOW()
+        WHERE id = $6
+        "#,
+        matches!(
+            status,
+            ApprovalStatus::NotRequested | ApprovalStatus::Rejected
+        ),
+        status.clone() as ApprovalStatus,
+        data.transition.is_made_by_manager(),
+        note,
+        data.transition as TimesheetTransition,
+        timesheet_id
+    )
+    .execute(tx.deref_mut())
+    .await?;
+
+    // The flags are settled once the sheet is approved.
+    if status == ApprovalStatus::Accepted {
+        sqlx::query!(
+            r#"
+            UPDATE workday_flag
+            SET resolved_at = NOW()
+            WHERE timesheet_id = $1
+              AND resolved_at IS NULL;
+            "#,
+            timesheet_id
+        )
+        .execute(tx.deref_mut())
+        .await?;
+    }
+
+    sqlx::query!(
+        r#"
+        INSERT INTO timesheet_history
+            (timesheet_id, actor_id, transition, from_status, to_status, note)
+        VALUES ($1, $2, $3, $4, $5, $6);
+        "#,
+        timesheet_id,
+        data.actor_id,
+        data.transition as TimesheetTransition,
+        previous_status as ApprovalStatus,
+        status.clone() as ApprovalStatus,
+        note,
+    )
+    .execute(tx.deref_mut())
+    .await?;
+
+    if data.transition.is_decision() {
+        notification_repo::emit_db_using_tx(
+            tx,
+            NotificationEvent::TimesheetDecided { timesheet_id },
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Creates the timesheet of an accepted staff member covering the event, with
+/// one workday per day, unless they already have one for the event. Returns
+/// the id of the created timesheet.
+pub async fn provision_db_using_tx(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    company_id: Uuid,
+    event_id: Uuid,
+) -> DbResult<Option<Uuid>> {
+    let timesheet_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO timesheet (start_date, end_date, user_id, company_id, event_id)
+        SELECT event.start_date, event.end_date, $1, $2, event.id
+        FROM event
+        WHERE event.id = $3
+          AND NOT EXISTS (SELECT 1
+                          FROM timesheet
+                          WHERE timesheet.user_id = $1
+                            AND timesheet.company_id = $2
+                            AND timesheet.event_id = $3
+                            AND timesheet.deleted_at IS NULL)
+        RETURNING id;
+        "#,
+        user_id,
+        company_id,
+        event_id,
+    )
+    .fetch_optional(tx.deref_mut())
+    .await?;
+
+    if let Some(timesheet_id) = timesheet_id {
+        sqlx::query!(
+            r#"
+            INSERT INTO workday (timesheet_id, date)
+            SELECT timesheet.id, curr_date::DATE
+            FROM timesheet,
+                 generate_series(timesheet.start_date, timesheet.end_date,
+                                 interval '1 day') AS curr_date
+            WHERE timesheet.id = $1;
+            "#,
+            timesheet_id,
+        )
+        .execute(tx.deref_mut())
+        .await?;
+    }
+
+    Ok(timesheet_id)
+}
+
+/// Deletes the timesheet of a staff member who left the event, unless they
+/// already logged some work in it or submitted it. Returns the id of the
+/// deleted timesheet.
+pub async fn release_db_using_tx(
+    tx: &mut Transaction<'_, Postgres>,
+    user_id: Uuid,
+    company_id: Uuid,
+    event_id: Uuid,
+) -> DbResult<Option<Uuid>> {
+    let timesheet_id = sqlx::query_scalar!(
+        r#"
+        UPDATE timesheet
+        SET edited_at = NOW(),
+            deleted_at = NOW()
+        WHERE user_id = $1
+          AND company_id = $2
+          AND event_id = $3
+          AND deleted_at IS NULL
+          AND status = 'not_requested'
+          AND NOT EXISTS (SELECT 1
+                          FROM workday
+                          WHERE workday.timesheet_id = timesheet.id
+                            AND workday.deleted_at IS NULL
+                            AND (workday.total_hours > 0
+                                 OR COALESCE(workday.comment, '') <> ''))
+        RETURNING id;
+        "#,
+        user_id,
+        company_id,
+        event_id,
+    )
+    .fetch_optional(tx.deref_mut())
+    .await?;
+
+    if let Some(timesheet_id) = timesheet_id {
+        sqlx::query!(
+            r#"
+            UPDATE workday
+            SET edited_at = NOW(),
+                deleted_at = NOW()
+            WHERE timesheet_id = $1;
+            "#,
+            timesheet_id,
+        )
+        .execute(tx.deref_mut())
+        .await?;
+    }
+
+    Ok(timesheet_id)
+}
+
+/// Fits the workdays of the timesheet to its dates. The workdays outside of
+/// them are only dropped when nothing was logged in them, the rest is kept
+/// and flagged with the reason, if there is someone to flag them. The missing
+/// workdays are added and the total hours are recalculated.
+pub async fn realign_workdays_db_using_tx(
+    tx: &mut Transaction<'_, Postgres>,
+    sheet: &TimesheetStructureData,
+    flag_reason: &str,
+    flagged_by: Option<Uuid>,
+) -> DbResult<()> {
+    sqlx::query!(
+        r#"DELETE FROM workday
+           WHERE timesheet_id = $1
+             AND (date < $2 OR date > $3)
+             AND (deleted_at IS NOT NULL
+                  OR (total_hours = 0 AND COALESCE(comment, '') = ''));"#,
+        sheet.id,
+        sheet.start_date,
+        sheet.end_date,
+    )
+    .execute(tx.deref_mut())
+    .await?;
+
+    if let Some(flagged_by) = flagged_by {
+        sqlx::query!(
+            r#"INSERT INTO workday_flag
+                   (timesheet_id, date, flagged_by, reason,
+                    original_hours, original_comment)
+               SELECT workday.timesheet_id, workday.date, $4, $5,
+                      workday.total_hours, workday.comment
+               FROM workday
+               WHERE workday.timesheet_id = $1
+                 AND (workday.date < $2 OR workday.date > $3)
+               ON CONFLICT (timesheet_id, date) WHERE resolved_at IS NULL DO NOTHING;"#,
+            sheet.id,
+            sheet.start_date,
+            sheet.end_date,
+            flagged_by,
+            flag_reason,
+        )
+        .execute(tx.deref_mut())
+        .await?;
+    }
+
+    sqlx::query!(
+        r#"INSERT INTO workday (timesheet_id, date)
+           SELECT $1, curr_date::DATE
+           FROM generate_series($2::DATE, $3::DATE, interval '1 day') AS curr_date
+           ON CONFLICT DO NOTHING;"#,
+        sheet.id,
+        sheet.start_date,
+        sheet.end_date,
+    )
+    .execute(tx.deref_mut())
+    .await?;
+
+    sqlx::query!(
+        r#"
+        UPDATE timesheet
+        SET total_hours = COALESCE((SELECT SUM(total_hours)
+                                    FROM workday
+                                    WHERE workday.timesheet_id = $1
+                                      AND workday.deleted_at IS NULL), 0)
+        WHERE id = $1 AND deleted_at IS NULL;"#,
+        sheet.id,
+    )
+    .execute(tx.deref_mut())
+    .await?;
+
+    Ok(())
+}
+
 #[derive(Clone)]
 pub struct TimesheetRepository {
     pub pool: Arc<PgPool>,
@@ -221,7 +533,7 @@ impl TimesheetRepository {
                    timesheet.end_date, 
                    total_hours, 
                    is_editable, 
-                   status AS "approval_status!:ApprovalStatus", 
+                   timesheet.status AS "approval_status!:ApprovalStatus", 
                    manager_note AS "manager_note?", 
                    user_id, 
                    company_id,
@@ -270,7 +582,7 @@ impl TimesheetRepository {
                    timesheet.end_date, 
                    total_hours, 
                    is_editable, 
-                   status AS "approval_status!: ApprovalStatus", 
+                   timesheet.status AS "approval_status!: ApprovalStatus", 
                    manager_note AS "manager_note?", 
                    user_id, 
                    company_id,
@@ -321,7 +633,7 @@ impl TimesheetRepository {
                    timesheet.end_date, 
                    total_hours, 
                    is_editable, 
-                   status AS "approval_status!: ApprovalStatus", 
+                   timesheet.status AS "approval_status!: ApprovalStatus", 
                    manager_note AS "manager_note?", 
                    user_id, 
                    company_id,
@@ -377,7 +689,7 @@ impl TimesheetRepository {
                    timesheet.end_date, 
                    total_hours, 
                    is_editable, 
-                   status AS "approval_status!: ApprovalStatus", 
+                   timesheet.status AS "approval_status!: ApprovalStatus", 
                    manager_note AS "manager_note?", 
                    user_id, 
                    company_id,
@@ -406,12 +718,55 @@ impl TimesheetRepository {
     }
 
     fn _is_data_empty(data: TimesheetUpdateData) -> bool {
-        data.is_editable.is_none() && data.status.is_none() && data.manager_note.is_none()
+        data.manager_note.is_none()
     }
 
     /* Methods for workday are kept in timesheet_repo because
      * workdays are semantically bound to timesheets.
      */
+    /// The timesheet the staff member has for the event, if any.
+    pub async fn read_one_for_staff(
+        &self,
+        user_id: Uuid,
+        company_id: Uuid,
+        event_id: Uuid,
+    ) -> DbResult<Option<TimesheetWithEvent>> {
+        let executor = self.pool.as_ref();
+
+        let timesheet = sqlx::query_as!(
+            TimesheetWithEvent,
+            r#"
+            SELECT timesheet.id,
+                   timesheet.start_date,
+                   timesheet.end_date,
+                   total_hours,
+                   is_editable,
+                   timesheet.status AS "approval_status!:ApprovalStatus",
+                   manager_note AS "manager_note?",
+                   user_id,
+                   company_id,
+                   event_id,
+                   event.avatar_url AS event_avatar_url,
+                   event.name AS event_name,
+                   timesheet.created_at,
+                   timesheet.edited_at
+            FROM timesheet
+            JOIN event ON timesheet.event_id = event.id
+            WHERE timesheet.user_id = $1
+              AND timesheet.company_id = $2
+              AND timesheet.event_id = $3
+              AND timesheet.deleted_at IS NULL;
+            "#,
+            user_id,
+            company_id,
+            event_id,
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        Ok(timesheet)
+    }
+
     pub async fn read_one_workday(&self, timesheet_id: Uuid, date: NaiveDate) -> DbResult<Workday> {
         let executor = self.pool.as_ref();
 
@@ -502,21 +857,26 @@ impl TimesheetRepository {
             r#"
             UPDATE timesheet
             SET
-                is_editable = COALESCE($1, is_editable),
-                status = COALESCE($2, status),
-                manager_note = COALESCE($3, manager_note),
+                manager_note = COALESCE($1, manager_note),
                 edited_at = NOW()
-            WHERE id = $4
+            WHERE id = $2
               AND deleted_at IS NULL
             "#,
-            data.is_editable,
-            data.status as Option<ApprovalStatus>,
             data.manager_note,
             timesheet_id
         )
         .execute(tx.deref_mut())
         .await?;
 
+        self.read_with_workdays_tx(timesheet_id, tx).await
+    }
+
+    // Warning!! The tx will be commited, use this as the last call in a transaction.
+    async fn read_with_workdays_tx(
+        &self,
+        timesheet_id: Uuid,
+        mut tx: Transaction<'_, Postgres>,
+    ) -> DbResult<TimesheetWithWorkdays> {
         let workdays = sqlx::query_as!(
             Workday,
             r#"
@@ -545,6 +905,369 @@ impl TimesheetRepository {
         Ok(result)
     }
 
+    /// Moves the timesheet through its approval process and records who did
+    /// so in its history. Whether the actor may make the transition is up to
+    /// the caller, the state the sheet is in is checked here.
+    pub async fn transition(
+        &self,
+        timesheet_id: Uuid,
+        data: TimesheetTransitionData,
+    ) -> DbResult<TimesheetWithWorkdays> {
+        let mut tx = self.pool.begin().await?;
+        transition_db_using_tx(&mut tx, timesheet_id, data).await?;
+
+        self.read_with_workdays_tx(timesheet_id, tx).await
+    }
+
+    /// Makes the same transition with every timesheet, either all of them
+    /// are moved or none is.
+    pub async fn transition_all(
+        &self,
+        timesheet_ids: &[Uuid],
+        data: TimesheetTransitionData,
+    ) -> DbResult<()> {
+        let mut tx = self.pool.begin().await?;
+        for timesheet_id in timesheet_ids {
+            transition_db_using_tx(&mut tx, *timesheet_id, data.clone()).await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Applies the corrections suggested by the open flags to their workdays,
+    /// returns how many workdays were adjusted.
+    async fn apply_flag_suggestions(
+        tx: &mut Transaction<'_, Postgres>,
+        timesheet_id: Uuid,
+    ) -> DbResult<u64> {
+        let adjusted = sqlx::query!(
+            r#"
+            UPDATE workday
+            SET total_hours = COALESCE(workday_flag.suggested_hours, workday.total_hours),
+                comment = COALESCE(workday_flag.suggested_comment, workday.comment),
+                edited_at = NOW()
+            FROM workday_flag
+            WHERE workday_flag.timesheet_id = $1
+              AND workday_flag.resolved_at IS NULL
+              AND (workday_flag.suggested_hours IS NOT NULL
+                   OR workday_flag.suggested_comment IS NOT NULL)
+              AND workday.timesheet_id = workday_flag.timesheet_id
+              AND workday.date = workday_flag.date
+              AND workday.deleted_at IS NULL;
+            "#,
+            timesheet_id
+        )
+        .execute(tx.deref_mut())
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE workday_flag
+            SET applied = true,
+                resolved_at = NOW()
+            WHERE timesheet_id = $1
+              AND resolved_at IS NULL
+              AND (suggested_hours IS NOT NULL OR suggested_comment IS NOT NULL);
+            "#,
+            timesheet_id
+        )
+        .execute(tx.deref_mut())
+        .await?;
+
+        sqlx::query!(
+            r#"
+            UPDATE timesheet
+            SET total_hours = (SELECT COALESCE(SUM(total_hours), 0)
+                               FROM workday
+                               WHERE workday.timesheet_id = $1
+                                 AND workday.deleted_at IS NULL)
+            WHERE id = $1;
+            "#,
+            timesheet_id
+        )
+        .execute(tx.deref_mut())
+        .await?;
+
+        Ok(adjusted.rows_affected())
+    }
+
+    /// Flags the workday of a submitted timesheet, replacing its previous
+    /// flag. The workday is remembered as it is now.
+    pub async fn flag_workday(&self, data: WorkdayFlagData) -> DbResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let status = sqlx::query_scalar!(
+            r#"
+            SELECT status AS "status!: ApprovalStatus"
+            FROM timesheet
+            WHERE id = $1
+              AND deleted_at IS NULL
+            FOR UPDATE;
+            "#,
+            data.timesheet_id
+        )
+        .fetch_optional(tx.deref_mut())
+        .await?;
+
+        if status.is_none() {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        if status.expect("Should be some.") != ApprovalStatus::Pending {
+            return Err(sqlx::Error::TypeNotFound {
+                type_name: "User Error".to_string(),
+            });
+        }
+
+        sqlx::query!(
+            r#"
+            UPDATE workday_flag
+            SET resolved_at = NOW()
+            WHERE timesheet_id = $1
+              AND date = $2
+              AND resolved_at IS NULL;
+            "#,
+            data.timesheet_id,
+            data.date
+        )
+        .execute(tx.deref_mut())
+        .await?;
+
+        let flagged = sqlx::query!(
+            r#"
+            INSERT INTO workday_flag
+                (timesheet_id, date, flagged_by, reason, suggested_hours,
+                 suggested_comment, original_hours, original_comment)
+            SELECT timesheet_id, date, $3, $4, $5, $6, total_hours, comment
+            FROM workday
+            WHERE timesheet_id = $1
+              AND date = $2
+              AND deleted_at IS NULL;
+            "#,
+            data.timesheet_id,
+            data.date,
+            data.flagged_by,
+            data.reason,
+            data.suggested_hours,
+            data.suggested_comment
+        )
+        .execute(tx.deref_mut())
+        .await?;
+
+        if flagged.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn remove_workday_flag(&self, timesheet_id: Uuid, date: NaiveDate) -> DbResult<()> {
+        let executor = self.pool.as_ref();
+
+        let removed = sqlx::query!(
+            r#"
+            UPDATE workday_flag
+            SET resolved_at = NOW()
+            WHERE timesheet_id = $1
+              AND date = $2
+              AND resolved_at IS NULL;
+            "#,
+            timesheet_id,
+            date
+        )
+        .execute(executor)
+        .await?;
+
+        if removed.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// The open flags of the timesheet, or the flags applied when it was
+    /// last approved with adjustments.
+    pub async fn read_review(&self, timesheet_id: Uuid) -> DbResult<TimesheetReview> {
+        let mut tx = self.pool.begin().await?;
+
+        let timesheet = sqlx::query!(
+            r#"
+            SELECT adjusted_at, acknowledged_at
+            FROM timesheet
+            WHERE id = $1
+              AND deleted_at IS NULL;
+            "#,
+            timesheet_id
+        )
+        .fetch_optional(tx.deref_mut())
+        .await?;
+
+        if timesheet.is_none() {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        let timesheet = timesheet.expect("Should be some.");
+
+        let flags = sqlx::query_as!(
+            WorkdayFlag,
+            r#"
+            SELECT workday_flag.id,
+                   workday_flag.timesheet_id,
+                   workday_flag.date,
+                   workday_flag.flagged_by,
+                   user_record.name AS flagged_by_name,
+                   workday_flag.reason,
+                   workday_flag.suggested_hours,
+                   workday_flag.suggested_comment,
+                   workday_flag.original_hours,
+                   workday_flag.original_comment,
+                   workday.total_hours AS current_hours,
+                   workday.comment AS current_comment,
+                   workday_flag.applied,
+                   workday_flag.created_at
+            FROM workday_flag
+            JOIN workday ON workday_flag.timesheet_id = workday.timesheet_id
+                        AND workday_flag.date = workday.date
+            JOIN user_record ON workday_flag.flagged_by = user_record.id
+            WHERE workday_flag.timesheet_id = $1
+              AND (workday_flag.resolved_at IS NULL
+                   OR (workday_flag.applied
+                       AND workday_flag.resolved_at >= $2))
+            ORDER BY workday_flag.date;
+            "#,
+            timesheet_id,
+            timesheet.adjusted_at
+        )
+        .fetch_all(tx.deref_mut())
+        .await?;
+
+        tx.commit().await?;
+
+        Ok(TimesheetReview {
+            flags,
+            adjusted_at: timesheet.adjusted_at,
+            acknowledged_at: timesheet.acknowledged_at,
+        })
+    }
+
+    /// Transitions of the timesheet, the most recent first.
+    pub async fn read_history(&self, timesheet_id: Uuid) -> DbResult<Vec<TimesheetHistoryEntry>> {
+        let executor = self.pool.as_ref();
+
+        let history = sqlx::query_as!(
+            TimesheetHistoryEntry,
+            r#"
+            SELECT timesheet_history.id,
+                   timesheet_history.timesheet_id,
+                   timesheet_history.actor_id,
+                   user_record.name AS actor_name,
+                   timesheet_history.transition AS "transition!: TimesheetTransition",
+                   timesheet_history.from_status AS "from_status!: ApprovalStatus",
+                   timesheet_history.to_status AS "to_status!: ApprovalStatus",
+                   timesheet_history.note,
+                   timesheet_history.created_at
+            FROM timesheet_history
+            JOIN user_record ON timesheet_history.actor_id = user_record.id
+            WHERE timesheet_history.timesheet_id = $1
+            ORDER BY timesheet_history.created_at DESC;
+            "#,
+            timesheet_id
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(history)
+    }
+
+    /// The timesheets of the direct and delegated subordinates of the manager,
+    /// the submitted ones waiting the longest first.
+    pub async fn read_review_queue(
+        &self,
+        manager_id: Uuid,
+        filter: &ReviewQueueFilter,
+    ) -> DbResult<Vec<ReviewQueueEntry>> {
+        let executor = self.pool.as_ref();
+
+        let month_start = filter.month.and_then(|month| month.with_day(1));
+        let month_end = month_start.and_then(|month| month.checked_add_months(Months::new(1)));
+
+        let entries = sqlx::query_as!(
+            ReviewQueueEntry,
+            r#"
+            SELECT timesheet.id,
+                   user_record.name AS user_name,
+                   company.name AS company_name,
+                   event.name AS event_name,
+                   timesheet.start_date,
+                   timesheet.end_date,
+                   timesheet.total_hours,
+                   timesheet.status AS "approval_status!: ApprovalStatus",
+                   subordinate.is_direct AS "is_direct!",
+                   (SELECT COUNT(*)
+                    FROM workday_flag
+                    WHERE workday_flag.timesheet_id = timesheet.id
+                      AND workday_flag.resolved_at IS NULL) AS "flagged_days!",
+                   timesheet.submitted_at
+            FROM employment_subordinates($1) AS subordinate
+                INNER JOIN timesheet ON timesheet.user_id = subordinate.user_id
+                                    AND timesheet.company_id = subordinate.company_id
+                INNER JOIN user_record ON user_record.id = timesheet.user_id
+                INNER JOIN company ON company.id = timesheet.company_id
+                INNER JOIN event ON event.id = timesheet.event_id
+            WHERE timesheet.deleted_at IS NULL
+              AND event.deleted_at IS NULL
+              AND ($2::UUID IS NULL OR timesheet.event_id = $2)
+              AND ($3::DATE IS NULL OR (timesheet.start_date < $4 AND timesheet.end_date >= $3))
+              AND ($5::approval_status IS NULL OR timesheet.status = $5)
+              AND ($6::REAL IS NULL OR timesheet.total_hours > $6)
+            ORDER BY timesheet.status = 'pending' DESC,
+                     timesheet.submitted_at NULLS LAST,
+                     user_record.name,
+                     timesheet.start_date;
+            "#,
+            manager_id,
+            filter.event_id,
+            month_start,
+            month_end,
+            filter.status.clone() as Option<ApprovalStatus>,
+            filter.min_hours,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(entries)
+    }
+
+    /// The events the subordinates of the manager have timesheets for.
+    pub async fn read_review_queue_events(
+        &self,
+        manager_id: Uuid,
+    ) -> DbResult<Vec<ReviewQueueEvent>> {
+        let executor = self.pool.as_ref();
+
+        let events = sqlx::query_as!(
+            ReviewQueueEvent,
+            r#"
+            SELECT event.id, event.name
+            FROM event
+            WHERE event.deleted_at IS NULL
+              AND event.id IN (SELECT timesheet.event_id
+                               FROM employment_subordinates($1) AS subordinate
+                                   INNER JOIN timesheet
+                                       ON timesheet.user_id = subordinate.user_id
+                                      AND timesheet.company_id = subordinate.company_id
+                               WHERE timesheet.deleted_at IS NULL)
+            ORDER BY event.start_date DESC, event.name;
+            "#,
+            manager_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(events)
+    }
+
     pub async fn _delete(&self, timesheet_id: Uuid) -> DbResult<()> {
         let executor = self.pool.as_ref();
 
@@ -776,4 +1499,43 @@ impl TimesheetRepository {
             date_to_wage_presets,
         })
     }
+
+    /// Timesheets of the company's employees which overlap the date range,
+    /// read row by row for exports.
+    pub fn stream_all_for_company(
+        &self,
+        company_id: Uuid,
+        date_from: NaiveDate,
+        date_to: NaiveDate,
+    ) -> BoxStream<'_, DbResult<TimesheetExport>> {
+        sqlx::query_as!(
+            TimesheetExport,
+            r#"
+            SELECT timesheet.id,
+                   timesheet.user_id,
+                   user_record.name AS user_name,
+                   user_record.email AS user_email,
+                   event.name AS event_name,
+                   timesheet.start_date,
+                   timesheet.end_date,
+                   timesheet.status AS "approval_status!: ApprovalStatus",
+                   employment.type AS "employment_type!: EmploymentContract",
+                   employment.hourly_wage
+            FROM timesheet
+                INNER JOIN user_record ON timesheet.user_id = user_record.id
+                INNER JOIN event ON timesheet.event_id = event.id
+                INNER JOIN employment ON timesheet.user_id = employment.user_id
+                                     AND timesheet.company_id = employment.company_id
+            WHERE timesheet.company_id = $1
+              AND timesheet.start_date <= $3
+              AND timesheet.end_date >= $2
+              AND timesheet.deleted_at IS NULL
+            ORDER BY user_record.name, timesheet.start_date, timesheet.id;
+            "#,
+            company_id,
+            date_from,
+            date_to,
+        )
+        .fetch(self.pool.as_ref())
+    }
 }
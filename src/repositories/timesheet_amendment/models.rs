@@ -0,0 +1,73 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::Deserialize;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::models::{AmendmentStatus, EmploymentContract};
+
+#[derive(Debug, Clone, FromRow)]
+pub struct TimesheetAmendment {
+    pub id: Uuid,
+    pub timesheet_id: Uuid,
+    pub proposed_by_name: String,
+    pub reason: String,
+    pub status: AmendmentStatus,
+    pub decided_by_name: Option<String>,
+    pub decision_note: Option<String>,
+    pub decided_at: Option<NaiveDateTime>,
+    /// The first day of the month whose payroll settles the difference.
+    pub settlement_month: Option<NaiveDate>,
+    pub created_at: NaiveDateTime,
+}
+
+/// A corrected workday, along with the values it was paid with.
+#[derive(Debug, Clone, FromRow)]
+pub struct AmendmentDay {
+    pub amendment_id: Uuid,
+    pub date: NaiveDate,
+    pub original_hours: f32,
+    pub original_comment: Option<String>,
+    pub total_hours: f32,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TimesheetAmendmentWithDays {
+    pub amendment: TimesheetAmendment,
+    pub days: Vec<AmendmentDay>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AmendmentDayData {
+    pub date: NaiveDate,
+    pub total_hours: f32,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NewTimesheetAmendment {
+    pub timesheet_id: Uuid,
+    pub proposed_by: Uuid,
+    pub reason: String,
+    pub days: Vec<AmendmentDayData>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AmendmentDecisionData {
+    pub decided_by: Uuid,
+    pub approve: bool,
+    pub note: Option<String>,
+}
+
+/// An approved amendment settled in a company's payroll, with the employee's
+/// details.
+#[derive(Debug, Clone, FromRow)]
+pub struct AmendmentSettlement {
+    pub amendment_id: Uuid,
+    pub user_id: Uuid,
+    pub user_name: String,
+    pub user_email: String,
+    pub event_name: String,
+    pub employment_type: EmploymentContract,
+    pub hourly_wage: f64,
+}
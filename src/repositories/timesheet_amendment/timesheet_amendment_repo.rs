@@ -0,0 +1,324 @@
+use crate::common::DbResult;
+use crate::models::{AmendmentStatus, ApprovalStatus, EmploymentContract};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::postgres::PgPool;
+use std::ops::DerefMut;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::models::{
+    AmendmentDay, AmendmentDecisionData, AmendmentSettlement, NewTimesheetAmendment,
+    TimesheetAmendment, TimesheetAmendmentWithDays,
+};
+
+#[derive(Clone)]
+pub struct TimesheetAmendmentRepository {
+    pub pool: Arc<PgPool>,
+}
+
+#[async_trait]
+impl crate::repositories::repository::DbRepository for TimesheetAmendmentRepository {
+    /// Database repository constructor
+    fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Method allowing the database repository to disconnect from the database pool gracefully
+    async fn disconnect(&mut self) -> () {
+        self.pool.close().await;
+    }
+}
+
+impl TimesheetAmendmentRepository {
+    /// Proposes a correction of an accepted timesheet. The corrected days
+    /// remember the values they were paid with, including the amendments
+    /// approved before.
+    pub async fn create(
+        &self,
+        data: NewTimesheetAmendment,
+    ) -> DbResult<TimesheetAmendmentWithDays> {
+        let mut tx = self.pool.begin().await?;
+
+        let status = sqlx::query_scalar!(
+            r#"
+            SELECT status AS "status!: ApprovalStatus"
+            FROM timesheet
+            WHERE id = $1
+              AND deleted_at IS NULL
+            FOR UPDATE;
+            "#,
+            data.timesheet_id,
+        )
+        .fetch_optional(tx.deref_mut())
+        .await?;
+        match status {
+            None => return Err(sqlx::Error::RowNotFound),
+            Some(ApprovalStatus::Accepted) => {}
+            Some(_) => {
+                return Err(sqlx::Error::TypeNotFound {
+                    type_name: "User Error".to_string(),
+                })
+            }
+        }
+
+        let amendment_id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO timesheet_amendment (timesheet_id, proposed_by, reason)
+            VALUES ($1, $2, $3)
+            RETURNING id;
+            "#,
+            data.timesheet_id,
+            data.proposed_by,
+            data.reason,
+        )
+        .fetch_one(tx.deref_mut())
+        .await?;
+
+        for day in data.days.iter() {
+            let result = sqlx::query!(
+                r#"
+                INSERT INTO timesheet_amendment_day
+                    (amendment_id, timesheet_id, date, original_hours, original_comment,
+                     total_hours, comment)
+                SELECT $1,
+                       workday.timesheet_id,
+                       workday.date,
+                       COALESCE(amended.total_hours, workday.total_hours),
+                       CASE WHEN amended.date IS NULL
+                            THEN workday.comment
+                            ELSE amended.comment
+                       END,
+                       $4,
+                       $5
+                FROM workday
+                    LEFT JOIN LATERAL (
+                        SELECT amendment_day.date,
+                               amendment_day.total_hours,
+                               amendment_day.comment
+                        FROM timesheet_amendment_day AS amendment_day
+                            INNER JOIN timesheet_amendment
+                                ON timesheet_amendment.id = amendment_day.amendment_id
+                        WHERE amendment_day.timesheet_id = workday.timesheet_id
+                          AND amendment_day.date = workday.date
+                          AND timesheet_amendment.status = 'approved'
+                          AND timesheet_amendment.deleted_at IS NULL
+                        ORDER BY timesheet_amendment.decided_at DESC
+                        LIMIT 1
+                    ) AS amended ON true
+                WHERE workday.timesheet_id = $2
+                  AND workday.date = $3
+                  AND workday.deleted_at IS NULL;
+                "#,
+                amendment_id,
+                data.timesheet_id,
+                day.date,
+                day.total_hours,
+                day.comment,
+            )
+            .execute(tx.deref_mut())
+            .await?;
+
+            if result.rows_affected() == 0 {
+                return Err(sqlx::Error::RowNotFound);
+            }
+        }
+
+        tx.commit().await?;
+
+        self.read_one(amendment_id).await
+    }
+
+    pub async fn read_one(&self, amendment_id: Uuid) -> DbResult<TimesheetAmendmentWithDays> {
+        let executor = self.pool.as_ref();
+
+        let amendment = sqlx::query_as!(
+            TimesheetAmendment,
+            r#"
+            SELECT timesheet_amendment.id,
+                   timesheet_amendment.timesheet_id,
+                   proposer.name AS proposed_by_name,
+                   timesheet_amendment.reason,
+                   timesheet_amendment.status AS "status!: AmendmentStatus",
+                   decider.name AS "decided_by_name?",
+                   timesheet_amendment.decision_note,
+                   timesheet_amendment.decided_at,
+                   timesheet_amendment.settlement_month,
+                   timesheet_amendment.created_at
+            FROM timesheet_amendment
+                INNER JOIN user_record AS proposer
+                    ON proposer.id = timesheet_amendment.proposed_by
+                LEFT JOIN user_record AS decider
+                    ON decider.id = timesheet_amendment.decided_by
+            WHERE timesheet_amendment.id = $1
+              AND timesheet_amendment.deleted_at IS NULL;
+            "#,
+            amendment_id,
+        )
+        .fetch_one(executor)
+        .await?;
+
+        let mut amendments = self.with_days(vec![amendment]).await?;
+
+        Ok(amendments.remove(0))
+    }
+
+    /// The newest amendment first.
+    pub async fn read_all_for_timesheet(
+        &self,
+        timesheet_id: Uuid,
+    ) -> DbResult<Vec<TimesheetAmendmentWithDays>> {
+        let executor = self.pool.as_ref();
+
+        let amendments = sqlx::query_as!(
+            TimesheetAmendment,
+            r#"
+            SELECT timesheet_amendment.id,
+                   timesheet_amendment.timesheet_id,
+                   proposer.name AS proposed_by_name,
+                   timesheet_amendment.reason,
+                   timesheet_amendment.status AS "status!: AmendmentStatus",
+                   decider.name AS "decided_by_name?",
+                   timesheet_amendment.decision_note,
+                   timesheet_amendment.decided_at,
+                   timesheet_amendment.settlement_month,
+                   timesheet_amendment.created_at
+            FROM timesheet_amendment
+                INNER JOIN user_record AS proposer
+                    ON proposer.id = timesheet_amendment.proposed_by
+                LEFT JOIN user_record AS decider
+                    ON decider.id = timesheet_amendment.decided_by
+            WHERE timesheet_amendment.timesheet_id = $1
+              AND timesheet_amendment.deleted_at IS NULL
+            ORDER BY timesheet_amendment.created_at DESC, timesheet_amendment.id;
+            "#,
+            timesheet_id,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        self.with_days(amendments).await
+    }
+
+    async fn with_days(
+        &self,
+        amendments: Vec<TimesheetAmendment>,
+    ) -> DbResult<Vec<TimesheetAmendmentWithDays>> {
+        let executor = self.pool.as_ref();
+
+        let amendment_ids: Vec<Uuid> = amendments.iter().map(|amendment| amendment.id).collect();
+        let days = sqlx::query_as!(
+            AmendmentDay,
+            r#"
+            SELECT amendment_id,
+                   date,
+                   original_hours,
+                   original_comment,
+                   total_hours,
+                   comment
+            FROM timesheet_amendment_day
+            WHERE amendment_id = ANY($1)
+            ORDER BY date;
+            "#,
+            &amendment_ids,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(amendments
+            .into_iter()
+            .map(|amendment| TimesheetAmendmentWithDays {
+                days: days
+                    .iter()
+                    .filter(|day| day.amendment_id == amendment.id)
+                    .cloned()
+                    .collect(),
+                amendment,
+            })
+            .collect())
+    }
+
+    /// An approved amendment is settled in the payroll of the next month.
+    pub async fn decide(
+        &self,
+        amendment_id: Uuid,
+        data: AmendmentDecisionData,
+    ) -> DbResult<TimesheetAmendmentWithDays> {
+        let executor = self.pool.as_ref();
+
+        let status = match data.approve {
+            true => AmendmentStatus::Approved,
+            false => AmendmentStatus::Rejected,
+        };
+        let result = sqlx::query!(
+            r#"
+            UPDATE timesheet_amendment
+            SET status = $2,
+                decided_by = $3,
+                decision_note = $4,
+                decided_at = NOW(),
+                settlement_month = CASE
+                    WHEN $5 THEN (date_trunc('month', NOW()) + interval '1 month')::DATE
+                END,
+                edited_at = NOW()
+            WHERE id = $1
+              AND status = 'pending'
+              AND deleted_at IS NULL;
+            "#,
+            amendment_id,
+            status as AmendmentStatus,
+            data.decided_by,
+            data.note,
+            data.approve,
+        )
+        .execute(executor)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        self.read_one(amendment_id).await
+    }
+
+    /// The approved amendments of the company's timesheets settled in the
+    /// payroll of the month.
+    pub async fn read_settled_in(
+        &self,
+        company_id: Uuid,
+        month: NaiveDate,
+    ) -> DbResult<Vec<AmendmentSettlement>> {
+        let executor = self.pool.as_ref();
+
+        let settlements = sqlx::query_as!(
+            AmendmentSettlement,
+            r#"
+            SELECT timesheet_amendment.id AS amendment_id,
+                   timesheet.user_id,
+                   user_record.name AS user_name,
+                   user_record.email AS user_email,
+                   event.name AS event_name,
+                   employment.type AS "employment_type!: EmploymentContract",
+                   employment.hourly_wage
+            FROM timesheet_amendment
+                INNER JOIN timesheet ON timesheet.id = timesheet_amendment.timesheet_id
+                INNER JOIN user_record ON user_record.id = timesheet.user_id
+                INNER JOIN event ON event.id = timesheet.event_id
+                INNER JOIN employment ON employment.user_id = timesheet.user_id
+                                     AND employment.company_id = timesheet.company_id
+            WHERE timesheet.company_id = $1
+              AND timesheet_amendment.settlement_month = date_trunc('month', $2::DATE)::DATE
+              AND timesheet_amendment.status = 'approved'
+              AND timesheet_amendment.deleted_at IS NULL
+              AND timesheet.deleted_at IS NULL
+            ORDER BY user_record.name, timesheet_amendment.decided_at;
+            "#,
+            company_id,
+            month,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(settlements)
+    }
+}
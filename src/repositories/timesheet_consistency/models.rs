@@ -0,0 +1,66 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::models::ApprovalStatus;
+
+/// A timesheet whose total hours differ from the sum of its workdays.
+#[derive(Debug, Clone, FromRow)]
+pub struct TotalMismatch {
+    pub timesheet_id: Uuid,
+    pub user_name: String,
+    pub event_name: String,
+    pub total_hours: f32,
+    pub workday_hours: f32,
+}
+
+/// A timesheet with workdays outside of its dates. The logged ones are only
+/// reported until a manager is asked to sort them out by a flag.
+#[derive(Debug, Clone, FromRow)]
+pub struct WorkdaysOutOfRange {
+    pub timesheet_id: Uuid,
+    pub user_name: String,
+    pub event_name: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub empty_days: i64,
+    pub logged_days: i64,
+}
+
+/// A timesheet which wasn't accepted yet, but doesn't cover its event anymore.
+#[derive(Debug, Clone, FromRow)]
+pub struct RangeMismatch {
+    pub timesheet_id: Uuid,
+    pub user_name: String,
+    pub event_name: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub event_start_date: NaiveDate,
+    pub event_end_date: NaiveDate,
+    pub approval_status: ApprovalStatus,
+    pub is_editable: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ConsistencyReport {
+    pub total_mismatches: Vec<TotalMismatch>,
+    pub workdays_out_of_range: Vec<WorkdaysOutOfRange>,
+    pub range_mismatches: Vec<RangeMismatch>,
+}
+
+impl ConsistencyReport {
+    pub fn is_consistent(&self) -> bool {
+        self.total_mismatches.is_empty()
+            && self.workdays_out_of_range.is_empty()
+            && self.range_mismatches.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConsistencyIssue {
+    Total,
+    Workdays,
+    Range,
+}
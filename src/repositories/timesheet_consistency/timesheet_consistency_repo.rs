@@ -0,0 +1,282 @@
+use crate::common::DbResult;
+use crate::models::ApprovalStatus;
+use crate::repositories::timesheet::{
+    models::TimesheetStructureData, timesheet_repo::realign_workdays_db_using_tx,
+};
+use async_trait::async_trait;
+use sqlx::postgres::PgPool;
+use std::ops::DerefMut;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::models::{ConsistencyReport, RangeMismatch, TotalMismatch, WorkdaysOutOfRange};
+
+/// The difference of the total hours which is still put down to rounding.
+const TOTAL_HOURS_TOLERANCE: f32 = 0.01;
+
+#[derive(Clone)]
+pub struct TimesheetConsistencyRepository {
+    pub pool: Arc<PgPool>,
+}
+
+#[async_trait]
+impl crate::repositories::repository::DbRepository for TimesheetConsistencyRepository {
+    /// Database repository constructor
+    fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Method allowing the database repository to disconnect from the database pool gracefully
+    async fn disconnect(&mut self) -> () {
+        self.pool.close().await;
+    }
+}
+
+impl TimesheetConsistencyRepository {
+    /// Looks for the timesheets whose stored values disagree with each other.
+    pub async fn check(&self) -> DbResult<ConsistencyReport> {
+        let executor = self.pool.as_ref();
+
+        let total_mismatches = sqlx::query_as!(
+            TotalMismatch,
+            r#"
+            SELECT timesheet.id AS timesheet_id,
+                   user_record.name AS user_name,
+                   event.name AS event_name,
+                   timesheet.total_hours,
+                   COALESCE(SUM(workday.total_hours), 0) AS "workday_hours!"
+            FROM timesheet
+                JOIN user_record ON user_record.id = timesheet.user_id
+                JOIN event ON event.id = timesheet.event_id
+                LEFT JOIN workday ON workday.timesheet_id = timesheet.id
+                                 AND workday.deleted_at IS NULL
+            WHERE timesheet.deleted_at IS NULL
+            GROUP BY timesheet.id, user_record.name, event.name
+            HAVING ABS(timesheet.total_hours - COALESCE(SUM(workday.total_hours), 0)) > $1
+            ORDER BY event.name, user_record.name;
+            "#,
+            TOTAL_HOURS_TOLERANCE,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        let workdays_out_of_range = sqlx::query_as!(
+            WorkdaysOutOfRange,
+            r#"
+            WITH stray_workday AS (
+                SELECT workday.timesheet_id,
+                       (workday.total_hours = 0
+                        AND COALESCE(workday.comment, '') = '') AS is_empty
+                FROM workday
+                    JOIN timesheet ON timesheet.id = workday.timesheet_id
+                WHERE workday.deleted_at IS NULL
+                  AND timesheet.deleted_at IS NULL
+                  AND (workday.date < timesheet.start_date
+                       OR workday.date > timesheet.end_date)
+                  AND NOT EXISTS (SELECT 1
+                                  FROM workday_flag
+                                  WHERE workday_flag.timesheet_id = workday.timesheet_id
+                                    AND workday_flag.date = workday.date
+                                    AND workday_flag.resolved_at IS NULL)
+            )
+            SELECT timesheet.id AS timesheet_id,
+                   user_record.name AS user_name,
+                   event.name AS event_name,
+                   timesheet.start_date,
+                   timesheet.end_date,
+                   COUNT(*) FILTER (WHERE stray_workday.is_empty) AS "empty_days!",
+                   COUNT(*) FILTER (WHERE NOT stray_workday.is_empty) AS "logged_days!"
+            FROM stray_workday
+                JOIN timesheet ON timesheet.id = stray_workday.timesheet_id
+                JOIN user_record ON user_record.id = timesheet.user_id
+                JOIN event ON event.id = timesheet.event_id
+            GROUP BY timesheet.id, user_record.name, event.name
+            ORDER BY event.name, user_record.name;
+            "#,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        let range_mismatches = sqlx::query_as!(
+            RangeMismatch,
+            r#"
+            SELECT timesheet.id AS timesheet_id,
+                   user_record.name AS user_name,
+                   event.name AS event_name,
+                   timesheet.start_date,
+                   timesheet.end_date,
+                   event.start_date AS event_start_date,
+                   event.end_date AS event_end_date,
+                   timesheet.status AS "approval_status!: ApprovalStatus",
+                   timesheet.is_editable
+            FROM timesheet
+                JOIN user_record ON user_record.id = timesheet.user_id
+                JOIN event ON event.id = timesheet.event_id
+            WHERE timesheet.deleted_at IS NULL
+              AND event.deleted_at IS NULL
+              AND timesheet.status <> 'accepted'
+              AND (timesheet.start_date <> event.start_date
+                   OR timesheet.end_date <> event.end_date)
+            ORDER BY event.name, user_record.name;
+            "#,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(ConsistencyReport {
+            total_mismatches,
+            workdays_out_of_range,
+            range_mismatches,
+        })
+    }
+
+    /// Sets the total hours of the timesheet to the sum of its workdays.
+    pub async fn repair_total(&self, timesheet_id: Uuid) -> DbResult<()> {
+        let executor = self.pool.as_ref();
+
+        let repaired = sqlx::query_scalar!(
+            r#"
+            UPDATE timesheet
+            SET total_hours = COALESCE((SELECT SUM(total_hours)
+                                        FROM workday
+                                        WHERE workday.timesheet_id = $1
+                                          AND workday.deleted_at IS NULL), 0),
+                edited_at = NOW()
+            WHERE id = $1
+              AND deleted_at IS NULL
+            RETURNING id;
+            "#,
+            timesheet_id,
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        if repaired.is_none() {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Recalculates the total hours of every timesheet which doesn't match its
+    /// workdays. Returns the number of repaired timesheets.
+    pub async fn repair_all_totals(&self) -> DbResult<u64> {
+        let executor = self.pool.as_ref();
+
+        let result = sqlx::query!(
+            r#"
+            UPDATE timesheet
+            SET total_hours = workday_sum.hours,
+                edited_at = NOW()
+            FROM (SELECT timesheet.id,
+                         COALESCE(SUM(workday.total_hours), 0) AS hours
+                  FROM timesheet
+                      LEFT JOIN workday ON workday.timesheet_id = timesheet.id
+                                       AND workday.deleted_at IS NULL
+                  WHERE timesheet.deleted_at IS NULL
+                  GROUP BY timesheet.id) AS workday_sum
+            WHERE timesheet.id = workday_sum.id
+              AND ABS(timesheet.total_hours - workday_sum.hours) > $1;
+            "#,
+            TOTAL_HOURS_TOLERANCE,
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Fits the workdays to the timesheet's dates. The empty workdays outside
+    /// of them are dropped, the logged ones are flagged for the manager.
+    pub async fn repair_workdays(&self, timesheet_id: Uuid, flagged_by: Uuid) -> DbResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let sheet = sqlx::query_as!(
+            TimesheetStructureData,
+            r#"
+            SELECT id,
+                   start_date,
+                   end_date
+            FROM timesheet
+            WHERE id = $1
+              AND deleted_at IS NULL
+            FOR UPDATE;
+            "#,
+            timesheet_id,
+        )
+        .fetch_optional(tx.deref_mut())
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+
+        let flag_reason = format!(
+            "Logged outside of the sheet's dates ({} - {}).",
+            sheet.start_date.format("%d.%m.%Y"),
+            sheet.end_date.format("%d.%m.%Y")
+        );
+        realign_workdays_db_using_tx(&mut tx, &sheet, &flag_reason, Some(flagged_by)).await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Moves the timesheet to the dates of its event, the same way as moving
+    /// the event does. Only an editable timesheet which wasn't accepted can be
+    /// moved.
+    pub async fn repair_range(&self, timesheet_id: Uuid, flagged_by: Uuid) -> DbResult<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let sheet = sqlx::query!(
+            r#"
+            SELECT timesheet.status AS "approval_status!: ApprovalStatus",
+                   timesheet.is_editable
+            FROM timesheet
+            WHERE timesheet.id = $1
+              AND timesheet.deleted_at IS NULL
+            FOR UPDATE;
+            "#,
+            timesheet_id,
+        )
+        .fetch_optional(tx.deref_mut())
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+
+        if !sheet.is_editable || sheet.approval_status == ApprovalStatus::Accepted {
+            return Err(sqlx::Error::TypeNotFound {
+                type_name: "User Error".to_string(),
+            });
+        }
+
+        let moved_sheet = sqlx::query_as!(
+            TimesheetStructureData,
+            r#"
+            UPDATE timesheet
+            SET start_date = event.start_date,
+                end_date = event.end_date,
+                edited_at = NOW()
+            FROM event
+            WHERE timesheet.id = $1
+              AND event.id = timesheet.event_id
+              AND event.deleted_at IS NULL
+            RETURNING timesheet.id,
+                      timesheet.start_date,
+                      timesheet.end_date;
+            "#,
+            timesheet_id,
+        )
+        .fetch_optional(tx.deref_mut())
+        .await?
+        .ok_or(sqlx::Error::RowNotFound)?;
+
+        let flag_reason = format!(
+            "Logged outside of the event's dates ({} - {}).",
+            moved_sheet.start_date.format("%d.%m.%Y"),
+            moved_sheet.end_date.format("%d.%m.%Y")
+        );
+        realign_workdays_db_using_tx(&mut tx, &moved_sheet, &flag_reason, Some(flagged_by)).await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+}
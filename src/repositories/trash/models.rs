@@ -0,0 +1,25 @@
+use chrono::NaiveDateTime;
+use sqlx::FromRow;
+use uuid::Uuid;
+
+use crate::models::DeletedEntity;
+
+/// A deletion in the trash, the rows it soft-deleted can still be restored.
+#[derive(Debug, Clone, FromRow)]
+pub struct Deletion {
+    pub id: Uuid,
+    pub entity: DeletedEntity,
+    pub record_id: Uuid,
+    /// The company of a deleted employment.
+    pub related_id: Option<Uuid>,
+    pub label: String,
+    pub deleted_by_name: Option<String>,
+    pub deleted_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PurgeResult {
+    pub purged: u64,
+    /// The deletions with rows still referenced by records which were kept.
+    pub kept: u64,
+}
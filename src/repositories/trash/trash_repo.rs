@@ -0,0 +1,684 @@
+use crate::common::DbResult;
+use crate::models::DeletedEntity;
+use async_trait::async_trait;
+use sqlx::postgres::PgPool;
+use sqlx::{Postgres, Transaction};
+use std::ops::DerefMut;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::models::{Deletion, PurgeResult};
+
+/// Soft-deletes the record along with the rows depending on it as a single
+/// deletion, which can be restored from the trash. An employment is keyed by
+/// the user and the related company. Returns the id of the deletion.
+pub async fn delete_db_using_tx(
+    tx: &mut Transaction<'_, Postgres>,
+    entity: DeletedEntity,
+    record_id: Uuid,
+    related_id: Option<Uuid>,
+    deleted_by: Option<Uuid>,
+) -> DbResult<Uuid> {
+    let label = match entity {
+        DeletedEntity::User => {
+            sqlx::query_scalar!(
+                r#"SELECT name
+                   FROM user_record
+                   WHERE id = $1
+                     AND deleted_at IS NULL
+                   FOR UPDATE;"#,
+                record_id,
+            )
+            .fetch_optional(tx.deref_mut())
+            .await?
+        }
+        DeletedEntity::Company => {
+            sqlx::query_scalar!(
+                r#"SELECT name
+                   FROM company
+                   WHERE id = $1
+                     AND deleted_at IS NULL
+                   FOR UPDATE;"#,
+                record_id,
+            )
+            .fetch_optional(tx.deref_mut())
+            .await?
+        }
+        DeletedEntity::Event => {
+            sqlx::query_scalar!(
+                r#"SELECT name
+                   FROM event
+                   WHERE id = $1
+                     AND deleted_at IS NULL
+                   FOR UPDATE;"#,
+                record_id,
+            )
+            .fetch_optional(tx.deref_mut())
+            .await?
+        }
+        DeletedEntity::Employment => {
+            sqlx::query_scalar!(
+                r#"SELECT user_record.name || ' at ' || company.name AS "label!"
+                   FROM employment
+                       JOIN user_record ON user_record.id = employment.user_id
+                       JOIN company ON company.id = employment.company_id
+                   WHERE employment.user_id = $1
+                     AND employment.company_id = $2
+                     AND employment.deleted_at IS NULL
+                   FOR UPDATE OF employment;"#,
+                record_id,
+                related_id,
+            )
+            .fetch_optional(tx.deref_mut())
+            .await?
+        }
+        DeletedEntity::Task => {
+            sqlx::query_scalar!(
+                r#"SELECT title
+                   FROM task
+                   WHERE id = $1
+                     AND deleted_at IS NULL
+                   FOR UPDATE;"#,
+                record_id,
+            )
+            .fetch_optional(tx.deref_mut())
+            .await?
+        }
+    };
+    let label = label.ok_or(sqlx::Error::RowNotFound)?;
+
+    let deletion_id = sqlx::query_scalar!(
+        r#"INSERT INTO deletion (entity, record_id, related_id, label, deleted_by)
+           VALUES ($1, $2, $3, $4, $5)
+           RETURNING id;"#,
+        entity as DeletedEntity,
+        record_id,
+        related_id,
+        label.chars().take(255).collect::<String>(),
+        deleted_by,
+    )
+    .fetch_one(tx.deref_mut())
+    .await?;
+
+    match entity {
+        DeletedEntity::User => {
+            sqlx::query!(
+                r#"UPDATE user_record
+                   SET deleted_at = NOW(), edited_at = NOW(), deletion_id = $1
+                   WHERE id = $2;"#,
+                deletion_id,
+                record_id,
+            )
+            .execute(tx.deref_mut())
+            .await?;
+        }
+        DeletedEntity::Company => {
+            sqlx::query!(
+                r#"UPDATE company
+                   SET deleted_at = NOW(), edited_at = NOW(), deletion_id = $1
+                   WHERE id = $2;"#,
+                deletion_id,
+                record_id,
+            )
+            .execute(tx.deref_mut())
+            .await?;
+        }
+        DeletedEntity::Event => {
+            sqlx::query!(
+                r#"UPDATE event
+                   SET deleted_at = NOW(), edited_at = NOW(), deletion_id = $1
+                   WHERE id = $2;"#,
+                deletion_id,
+                record_id,
+            )
+            .execute(tx.deref_mut())
+            .await?;
+        }
+        DeletedEntity::Employment => {
+            sqlx::query!(
+                r#"UPDATE employment
+                   SET deleted_at = NOW(), edited_at = NOW(), deletion_id = $1
+                   WHERE user_id = $2
+                     AND company_id = $3;"#,
+                deletion_id,
+                record_id,
+                related_id,
+            )
+            .execute(tx.deref_mut())
+            .await?;
+        }
+        DeletedEntity::Task => {
+            sqlx::query!(
+                r#"UPDATE task
+                   SET deleted_at = NOW(), edited_at = NOW(), deletion_id = $1
+                   WHERE id = $2;"#,
+                deletion_id,
+                record_id,
+            )
+            .execute(tx.deref_mut())
+            .await?;
+        }
+    }
+
+    cascade_db_using_tx(tx, deletion_id).await?;
+
+    Ok(deletion_id)
+}
+
+/// The cascade policy. Every step soft-deletes the rows depending on the rows
+/// deleted so far, so the order matters:
+/// - a company takes the events no other company takes part in,
+/// - a user or a company takes their employments,
+/// - a company or an event takes the associations between them,
+/// - an event or an employment takes the staff,
+/// - an event takes its tasks, a task takes its subtasks,
+/// - a task or a staff member takes the assignments,
+/// - a task takes its checklist and dependencies,
+/// - an event or a task takes its comments,
+/// - an event or an employment takes the timesheets with their workdays,
+/// - an event or a company takes the budget lines,
+/// - an event or a user takes the calendar feeds,
+/// - a company takes its submission policy.
+///
+/// The rows which were deleted before are left out, so that they are not
+/// restored with this deletion.
+async fn cascade_db_using_tx(
+    tx: &mut Transaction<'_, Postgres>,
+    deletion_id: Uuid,
+) -> DbResult<()> {
+    sqlx::query!(
+        r#"UPDATE event
+           SET deleted_at = NOW(), edited_at = NOW(), deletion_id = $1
+           WHERE deleted_at IS NULL
+             AND id IN (SELECT associated_company.event_id
+                        FROM associated_company
+                        WHERE associated_company.deleted_at IS NULL
+                        GROUP BY associated_company.event_id
+                        HAVING bool_and(associated_company.company_id IN
+                                        (SELECT company.id
+                                         FROM company
+                                         WHERE company.deletion_id = $1)));"#,
+        deletion_id,
+    )
+    .execute(tx.deref_mut())
+    .await?;
+
+    sqlx::query!(
+        r#"UPDATE employment
+           SET deleted_at = NOW(), edited_at = NOW(), deletion_id = $1
+           WHERE deleted_at IS NULL
+             AND (user_id IN (SELECT id FROM user_record WHERE deletion_id = $1)
+                  OR company_id IN (SELECT id FROM company WHERE deletion_id = $1));"#,
+        deletion_id,
+    )
+    .execute(tx.deref_mut())
+    .await?;
+
+    sqlx::query!(
+        r#"UPDATE associated_company
+           SET deleted_at = NOW(), edited_at = NOW(), deletion_id = $1
+           WHERE deleted_at IS NULL
+             AND (company_id IN (SELECT id FROM company WHERE deletion_id = $1)
+                  OR event_id IN (SELECT id FROM event WHERE deletion_id = $1));"#,
+        deletion_id,
+    )
+    .execute(tx.deref_mut())
+    .await?;
+
+    sqlx::query!(
+        r#"UPDATE event_staff
+           SET deleted_at = NOW(), edited_at = NOW(), deletion_id = $1
+           WHERE deleted_at IS NULL
+             AND (event_id IN (SELECT id FROM event WHERE deletion_id = $1)
+                  OR (user_id, company_id) IN (SELECT user_id, company_id
+                                               FROM employment
+                                               WHERE deletion_id = $1));"#,
+        deletion_id,
+    )
+    .execute(tx.deref_mut())
+    .await?;
+
+    sqlx::query!(
+        r#"WITH RECURSIVE deleted_task AS (
+               SELECT task.id
+               FROM task
+               WHERE task.deletion_id = $1
+                  OR task.event_id IN (SELECT id FROM event WHERE deletion_id = $1)
+               UNION
+               SELECT task.id
+               FROM task
+                   JOIN deleted_task ON task.parent_task_id = deleted_task.id
+           )
+           UPDATE task
+           SET deleted_at = NOW(), edited_at = NOW(), deletion_id = $1
+           WHERE deleted_at IS NULL
+             AND id IN (SELECT id FROM deleted_task);"#,
+        deletion_id,
+    )
+    .execute(tx.deref_mut())
+    .await?;
+
+    sqlx::query!(
+        r#"UPDATE assigned_staff
+           SET deleted_at = NOW(), edited_at = NOW(), deletion_id = $1
+           WHERE deleted_at IS NULL
+             AND (task_id IN (SELECT id FROM task WHERE deletion_id = $1)
+                  OR staff_id IN (SELECT id FROM event_staff WHERE deletion_id = $1));"#,
+        deletion_id,
+    )
+    .execute(tx.deref_mut())
+    .await?;
+
+    sqlx::query!(
+        r#"UPDATE task_checklist_item
+           SET deleted_at = NOW(), edited_at = NOW(), deletion_id = $1
+           WHERE deleted_at IS NULL
+             AND task_id IN (SELECT id FROM task WHERE deletion_id = $1);"#,
+        deletion_id,
+    )
+    .execute(tx.deref_mut())
+    .await?;
+
+    sqlx::query!(
+        r#"UPDATE task_dependency
+           SET deleted_at = NOW(), edited_at = NOW(), deletion_id = $1
+           WHERE deleted_at IS NULL
+             AND (task_id IN (SELECT id FROM task WHERE deletion_id = $1)
+                  OR blocker_id IN (SELECT id FROM task WHERE deletion_id = $1));"#,
+        deletion_id,
+    )
+    .execute(tx.deref_mut())
+    .await?;
+
+    sqlx::query!(
+        r#"UPDATE comment
+           SET deleted_at = NOW(), edited_at = NOW(), deletion_id = $1
+           WHERE deleted_at IS NULL
+             AND (event_id IN (SELECT id FROM event WHERE deletion_id = $1)
+                  OR task_id IN (SELECT id FROM task WHERE deletion_id = $1));"#,
+        deletion_id,
+    )
+    .execute(tx.deref_mut())
+    .await?;
+
+    sqlx::query!(
+        r#"UPDATE timesheet
+           SET deleted_at = NOW(), edited_at = NOW(), deletion_id = $1
+           WHERE deleted_at IS NULL
+             AND (event_id IN (SELECT id FROM event WHERE deletion_id = $1)
+                  OR (user_id, company_id) IN (SELECT user_id, company_id
+                                               FROM employment
+                                               WHERE deletion_id = $1));"#,
+        deletion_id,
+    )
+    .execute(tx.deref_mut())
+    .await?;
+
+    sqlx::query!(
+        r#"UPDATE workday
+           SET deleted_at = NOW(), edited_at = NOW(), deletion_id = $1
+           WHERE deleted_at IS NULL
+             AND timesheet_id IN (SELECT id FROM timesheet WHERE deletion_id = $1);"#,
+        deletion_id,
+    )
+    .execute(tx.deref_mut())
+    .await?;
+
+    sqlx::query!(
+        r#"UPDATE event_budget_line
+           SET deleted_at = NOW(), edited_at = NOW(), deletion_id = $1
+           WHERE deleted_at IS NULL
+             AND (event_id IN (SELECT id FROM event WHERE deletion_id = $1)
+                  OR company_id IN (SELECT id FROM company WHERE deletion_id = $1));"#,
+        deletion_id,
+    )
+    .execute(tx.deref_mut())
+    .await?;
+
+    sqlx::query!(
+        r#"UPDATE calendar_feed
+           SET deleted_at = NOW(), edited_at = NOW(), deletion_id = $1
+           WHERE deleted_at IS NULL
+             AND (event_id IN (SELECT id FROM event WHERE deletion_id = $1)
+                  OR user_id IN (SELECT id FROM user_record WHERE deletion_id = $1));"#,
+        deletion_id,
+    )
+    .execute(tx.deref_mut())
+    .await?;
+
+    sqlx::query!(
+        r#"UPDATE submission_policy
+           SET deleted_at = NOW(), edited_at = NOW(), deletion_id = $1
+           WHERE deleted_at IS NULL
+             AND company_id IN (SELECT id FROM company WHERE deletion_id = $1);"#,
+        deletion_id,
+    )
+    .execute(tx.deref_mut())
+    .await?;
+
+    Ok(())
+}
+
+#[derive(Clone)]
+pub struct TrashRepository {
+    pub pool: Arc<PgPool>,
+}
+
+#[async_trait]
+impl crate::repositories::repository::DbRepository for TrashRepository {
+    /// Database repository constructor
+    fn new(pool: Arc<PgPool>) -> Self {
+        Self { pool }
+    }
+
+    /// Method allowing the database repository to disconnect from the database pool gracefully
+    async fn disconnect(&mut self) -> () {
+        self.pool.close().await;
+    }
+}
+
+impl TrashRepository {
+    /// The deletions in the trash, the latest first.
+    pub async fn read_all(&self) -> DbResult<Vec<Deletion>> {
+        let executor = self.pool.as_ref();
+
+        let deletions = sqlx::query_as!(
+            Deletion,
+            r#"
+            SELECT deletion.id,
+                   deletion.entity AS "entity!: DeletedEntity",
+                   deletion.record_id,
+                   deletion.related_id,
+                   deletion.label,
+                   user_record.name AS "deleted_by_name?",
+                   deletion.deleted_at
+            FROM deletion
+                LEFT JOIN user_record ON user_record.id = deletion.deleted_by
+            ORDER BY deletion.deleted_at DESC;
+            "#,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        Ok(deletions)
+    }
+
+    pub async fn read_one(&self, deletion_id: Uuid) -> DbResult<Deletion> {
+        let executor = self.pool.as_ref();
+
+        let deletion = sqlx::query_as!(
+            Deletion,
+            r#"
+            SELECT deletion.id,
+                   deletion.entity AS "entity!: DeletedEntity",
+                   deletion.record_id,
+                   deletion.related_id,
+                   deletion.label,
+                   user_record.name AS "deleted_by_name?",
+                   deletion.deleted_at
+            FROM deletion
+                LEFT JOIN user_record ON user_record.id = deletion.deleted_by
+            WHERE deletion.id = $1;
+            "#,
+            deletion_id,
+        )
+        .fetch_one(executor)
+        .await?;
+
+        Ok(deletion)
+    }
+
+    /// Whether the records the deleted one belongs to still exist. An
+    /// employment needs its user and company, a task its event and parent task.
+    pub async fn can_restore(&self, deletion: &Deletion) -> DbResult<bool> {
+        let executor = self.pool.as_ref();
+
+        let can_restore = match deletion.entity {
+            DeletedEntity::User | DeletedEntity::Company | DeletedEntity::Event => true,
+            DeletedEntity::Employment => {
+                sqlx::query_scalar!(
+                    r#"SELECT EXISTS (SELECT 1
+                                  FROM user_record
+                                  WHERE id = $1
+                                    AND deleted_at IS NULL)
+                      AND EXISTS (SELECT 1
+                                  FROM company
+                                  WHERE id = $2
+                                    AND deleted_at IS NULL) AS "can_restore!";"#,
+                    deletion.record_id,
+                    deletion.related_id,
+                )
+                .fetch_one(executor)
+                .await?
+            }
+            DeletedEntity::Task => {
+                sqlx::query_scalar!(
+                    r#"SELECT event.deleted_at IS NULL
+                          AND (parent_task.id IS NULL
+                               OR parent_task.deleted_at IS NULL) AS "can_restore!"
+                   FROM task
+                       JOIN event ON event.id = task.event_id
+                       LEFT JOIN task AS parent_task ON parent_task.id = task.parent_task_id
+                   WHERE task.id = $1;"#,
+                    deletion.record_id,
+                )
+                .fetch_one(executor)
+                .await?
+            }
+        };
+
+        Ok(can_restore)
+    }
+
+    /// Brings back every row soft-deleted by the deletion.
+    pub async fn restore(&self, deletion_id: Uuid) -> DbResult<()> {
+        let executor = self.pool.as_ref();
+
+        let restored = sqlx::query_scalar!(
+            r#"
+            WITH restored_user AS (
+                UPDATE user_record
+                SET deleted_at = NULL, edited_at = NOW(), deletion_id = NULL
+                WHERE deletion_id = $1
+            ), restored_company AS (
+                UPDATE company
+                SET deleted_at = NULL, edited_at = NOW(), deletion_id = NULL
+                WHERE deletion_id = $1
+            ), restored_event AS (
+                UPDATE event
+                SET deleted_at = NULL, edited_at = NOW(), deletion_id = NULL
+                WHERE deletion_id = $1
+            ), restored_employment AS (
+                UPDATE employment
+                SET deleted_at = NULL, edited_at = NOW(), deletion_id = NULL
+                WHERE deletion_id = $1
+            ), restored_associated_company AS (
+                UPDATE associated_company
+                SET deleted_at = NULL, edited_at = NOW(), deletion_id = NULL
+                WHERE deletion_id = $1
+            ), restored_event_staff AS (
+                UPDATE event_staff
+                SET deleted_at = NULL, edited_at = NOW(), deletion_id = NULL
+                WHERE deletion_id = $1
+            ), restored_task AS (
+                UPDATE task
+                SET deleted_at = NULL, edited_at = NOW(), deletion_id = NULL
+                WHERE deletion_id = $1
+            ), restored_assigned_staff AS (
+                UPDATE assigned_staff
+                SET deleted_at = NULL, edited_at = NOW(), deletion_id = NULL
+                WHERE deletion_id = $1
+            ), restored_task_checklist_item AS (
+                UPDATE task_checklist_item
+                SET deleted_at = NULL, edited_at = NOW(), deletion_id = NULL
+                WHERE deletion_id = $1
+            ), restored_task_dependency AS (
+                UPDATE task_dependency
+                SET deleted_at = NULL, edited_at = NOW(), deletion_id = NULL
+                WHERE deletion_id = $1
+            ), restored_comment AS (
+                UPDATE comment
+                SET deleted_at = NULL, edited_at = NOW(), deletion_id = NULL
+                WHERE deletion_id = $1
+            ), restored_timesheet AS (
+                UPDATE timesheet
+                SET deleted_at = NULL, edited_at = NOW(), deletion_id = NULL
+                WHERE deletion_id = $1
+            ), restored_workday AS (
+                UPDATE workday
+                SET deleted_at = NULL, edited_at = NOW(), deletion_id = NULL
+                WHERE deletion_id = $1
+            ), restored_event_budget_line AS (
+                UPDATE event_budget_line
+                SET deleted_at = NULL, edited_at = NOW(), deletion_id = NULL
+                WHERE deletion_id = $1
+            ), restored_calendar_feed AS (
+                UPDATE calendar_feed
+                SET deleted_at = NULL, edited_at = NOW(), deletion_id = NULL
+                WHERE deletion_id = $1
+            ), restored_submission_policy AS (
+                UPDATE submission_policy
+                SET deleted_at = NULL, edited_at = NOW(), deletion_id = NULL
+                WHERE deletion_id = $1
+            )
+            DELETE FROM deletion
+            WHERE id = $1
+            RETURNING id;
+            "#,
+            deletion_id,
+        )
+        .fetch_optional(executor)
+        .await?;
+
+        if restored.is_none() {
+            return Err(sqlx::Error::RowNotFound);
+        }
+
+        Ok(())
+    }
+
+    /// Deletes the rows of the deletion for good, together with the records
+    /// that only make sense with them, like the history of a timesheet or
+    /// the notifications of a user.
+    async fn purge(&self, deletion_id: Uuid) -> DbResult<()> {
+        let executor = self.pool.as_ref();
+
+        sqlx::query!(
+            r#"
+            WITH purged_comment_mention AS (
+                DELETE FROM comment_mention
+                WHERE comment_id IN (SELECT id FROM comment WHERE deletion_id = $1)
+                   OR staff_id IN (SELECT id FROM event_staff WHERE deletion_id = $1)
+            ), purged_comment_revision AS (
+                DELETE FROM comment_revision
+                WHERE comment_id IN (SELECT id FROM comment WHERE deletion_id = $1)
+            ), purged_comment AS (
+                DELETE FROM comment WHERE deletion_id = $1
+            ), purged_assigned_staff AS (
+                DELETE FROM assigned_staff WHERE deletion_id = $1
+            ), purged_task_checklist_item AS (
+                DELETE FROM task_checklist_item WHERE deletion_id = $1
+            ), purged_task_dependency AS (
+                DELETE FROM task_dependency WHERE deletion_id = $1
+            ), purged_task AS (
+                DELETE FROM task WHERE deletion_id = $1
+            ), purged_timesheet_amendment_day AS (
+                DELETE FROM timesheet_amendment_day
+                WHERE amendment_id IN (SELECT timesheet_amendment.id
+                                       FROM timesheet_amendment
+                                           JOIN timesheet
+                                               ON timesheet.id = timesheet_amendment.timesheet_id
+                                       WHERE timesheet.deletion_id = $1)
+            ), purged_timesheet_amendment AS (
+                DELETE FROM timesheet_amendment
+                WHERE timesheet_id IN (SELECT id FROM timesheet WHERE deletion_id = $1)
+            ), purged_timesheet_history AS (
+                DELETE FROM timesheet_history
+                WHERE timesheet_id IN (SELECT id FROM timesheet WHERE deletion_id = $1)
+            ), purged_submission_reminder AS (
+                DELETE FROM submission_reminder
+                WHERE timesheet_id IN (SELECT id FROM timesheet WHERE deletion_id = $1)
+            ), purged_workday_flag AS (
+                DELETE FROM workday_flag
+                WHERE timesheet_id IN (SELECT id FROM timesheet WHERE deletion_id = $1)
+            ), purged_workday AS (
+                DELETE FROM workday WHERE deletion_id = $1
+            ), purged_timesheet AS (
+                DELETE FROM timesheet WHERE deletion_id = $1
+            ), purged_event_budget_line AS (
+                DELETE FROM event_budget_line WHERE deletion_id = $1
+            ), purged_calendar_feed AS (
+                DELETE FROM calendar_feed WHERE deletion_id = $1
+            ), purged_submission_policy AS (
+                DELETE FROM submission_policy WHERE deletion_id = $1
+            ), purged_event_staff AS (
+                DELETE FROM event_staff WHERE deletion_id = $1
+            ), purged_associated_company AS (
+                DELETE FROM associated_company WHERE deletion_id = $1
+            ), purged_employment AS (
+                DELETE FROM employment WHERE deletion_id = $1
+            ), purged_event AS (
+                DELETE FROM event WHERE deletion_id = $1
+            ), purged_address AS (
+                DELETE FROM address
+                WHERE company_id IN (SELECT id FROM company WHERE deletion_id = $1)
+            ), purged_company AS (
+                DELETE FROM company WHERE deletion_id = $1
+            ), purged_notification AS (
+                DELETE FROM notification
+                WHERE user_id IN (SELECT id FROM user_record WHERE deletion_id = $1)
+            ), purged_notification_preference AS (
+                DELETE FROM notification_preference
+                WHERE user_id IN (SELECT id FROM user_record WHERE deletion_id = $1)
+            ), purged_mail_outbox AS (
+                DELETE FROM mail_outbox
+                WHERE user_id IN (SELECT id FROM user_record WHERE deletion_id = $1)
+            ), purged_mail_setting AS (
+                DELETE FROM mail_setting
+                WHERE user_id IN (SELECT id FROM user_record WHERE deletion_id = $1)
+            ), purged_user AS (
+                DELETE FROM user_record WHERE deletion_id = $1
+            )
+            DELETE FROM deletion
+            WHERE id = $1;
+            "#,
+            deletion_id,
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Purges the deletions older than the retention period. A deletion whose
+    /// rows are still referenced by the kept records, like a timesheet
+    /// history entry of another employee, stays in the trash.
+    pub async fn purge_older_than(&self, days: i32) -> DbResult<PurgeResult> {
+        let executor = self.pool.as_ref();
+
+        let expired = sqlx::query_scalar!(
+            r#"
+            SELECT id
+            FROM deletion
+            WHERE deleted_at < NOW() - make_interval(days => $1)
+            ORDER BY deleted_at;
+            "#,
+            days,
+        )
+        .fetch_all(executor)
+        .await?;
+
+        let mut result = PurgeResult { purged: 0, kept: 0 };
+        for deletion_id in expired.into_iter() {
+            match self.purge(deletion_id).await {
+                Ok(()) => result.purged += 1,
+                Err(sqlx::Error::Database(error)) if error.is_foreign_key_violation() => {
+                    result.kept += 1
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(result)
+    }
+}
@@ -3,7 +3,8 @@ use sqlx::postgres::PgPool;
 use std::sync::Arc;
 use uuid::Uuid;
 
-use crate::models::{Gender, UserRole, UserStatus};
+use crate::models::{DeletedEntity, Gender, UserRole, UserStatus};
+use crate::repositories::trash::trash_repo::delete_db_using_tx;
 
 use super::models::{NewUser, User, UserData, UsersQuery};
 
@@ -253,25 +254,18 @@ impl UserRepository {
     }
 
     // Remove a user in the DB if they exist.
-    pub async fn delete_user(&self, user_id: Uuid) -> DbResult<()> {
-        let executor = self.pool.as_ref();
-
+    pub async fn delete_user(&self, user_id: Uuid, deleted_by: Option<Uuid>) -> DbResult<()> {
         let user = self.read_one(user_id).await?;
 
         if user.deleted_at.is_some() {
             return Err(sqlx::Error::RowNotFound);
         }
 
-        sqlx::query!(
-            r#"UPDATE user_record
-            SET deleted_at = NOW(), edited_at = NOW()
-            WHERE id = $1
-            AND deleted_at IS NULL
-            "#,
-            user_id,
-        )
-        .execute(executor)
-        .await?;
+        let mut tx = self.pool.begin().await?;
+
+        delete_db_using_tx(&mut tx, DeletedEntity::User, user_id, None, deleted_by).await?;
+
+        tx.commit().await?;
 
         Ok(())
     }
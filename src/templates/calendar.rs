@@ -0,0 +1,11 @@
+use askama::Template;
+
+#[derive(Template, Debug)]
+#[template(path = "calendar/calendar-feed.html")]
+pub struct CalendarFeedTemplate {
+    pub title: String,
+    pub feed_url: String,
+    pub webcal_url: String,
+    pub rotate_url: String,
+    pub back_url: String,
+}
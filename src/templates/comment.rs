@@ -1,4 +1,12 @@
-use crate::repositories::{comment::models::CommentExtended, user::models::UserLite};
+use std::collections::HashMap;
+
+use crate::{
+    repositories::{
+        comment::models::{CommentExtended, CommentRevision},
+        user::models::UserLite,
+    },
+    utils::comment_format::markdown::render_markdown,
+};
 use askama::Template;
 use chrono::NaiveDateTime;
 use serde::Deserialize;
@@ -25,8 +33,15 @@ pub struct CommentTemplate {
 pub struct SingleComment {
     pub id: Uuid,
     pub parent_category_id: Uuid,
+    pub parent_id: Option<Uuid>,
     pub author: UserLiteTemplate,
     pub content: String,
+    pub content_html: String,
+    pub reply_url: String,
+    // Set when the comments get ordered into threads.
+    pub thread_id: Uuid,
+    pub is_reply: bool,
+    pub closes_thread: bool,
     pub created_at: NaiveDateTime,
     pub edited_at: NaiveDateTime,
 }
@@ -34,6 +49,14 @@ pub struct SingleComment {
 impl From<CommentExtended> for SingleComment {
     fn from(value: CommentExtended) -> Self {
         let author_lite: UserLite = value.author.into();
+        let reply_url = if let Some(event_id) = value.event_id {
+            format!("/protected/event/{}/comment", event_id)
+        } else {
+            format!(
+                "/protected/task/{}/comment",
+                value.task_id.expect("Should be set.")
+            )
+        };
         SingleComment {
             id: value.comment_id,
             parent_category_id: if value.event_id.is_some() {
@@ -41,14 +64,54 @@ impl From<CommentExtended> for SingleComment {
             } else {
                 value.task_id.expect("Should be set.")
             },
+            parent_id: value.parent_id,
             author: author_lite.into(),
+            content_html: render_markdown(&value.content),
             content: value.content,
+            reply_url,
+            thread_id: value.parent_id.unwrap_or(value.comment_id),
+            is_reply: value.parent_id.is_some(),
+            closes_thread: false,
             created_at: value.created_at,
-            edited_at: value.created_at,
+            edited_at: value.edited_at,
         }
     }
 }
 
+/// Orders chronologically sorted comments into threads, each top-level
+/// comment followed by all of its replies. Replies to replies are kept in
+/// the thread of the top-level comment they belong to. A reply whose
+/// parent isn't among the comments starts a thread of its own.
+pub fn order_into_threads(comments: Vec<SingleComment>) -> Vec<SingleComment> {
+    let mut thread_of: HashMap<Uuid, Uuid> = HashMap::new();
+    let mut threads: Vec<(Uuid, Vec<SingleComment>)> = vec![];
+
+    for mut comment in comments {
+        let thread_id = comment
+            .parent_id
+            .and_then(|parent_id| thread_of.get(&parent_id).copied())
+            .unwrap_or(comment.id);
+        thread_of.insert(comment.id, thread_id);
+        comment.thread_id = thread_id;
+        comment.is_reply = thread_id != comment.id;
+
+        match threads.iter_mut().find(|(id, _)| *id == thread_id) {
+            Some((_, thread)) => thread.push(comment),
+            None => threads.push((thread_id, vec![comment])),
+        }
+    }
+
+    threads
+        .into_iter()
+        .flat_map(|(_, mut thread)| {
+            if let Some(last) = thread.last_mut() {
+                last.closes_thread = true;
+            }
+            thread
+        })
+        .collect()
+}
+
 #[derive(Template, Debug, Deserialize)]
 #[template(path = "comment/comments.html")]
 pub struct CommentsTemplate {
@@ -71,3 +134,10 @@ pub struct TaskCommentsContainerTemplate {
     pub requester_id: Uuid,
     pub task_id: Uuid,
 }
+
+#[derive(Template, Debug)]
+#[template(path = "comment/comment-history.html")]
+pub struct CommentHistoryTemplate {
+    pub comment_id: Uuid,
+    pub revisions: Vec<CommentRevision>,
+}
@@ -5,10 +5,11 @@ use sqlx::types::uuid;
 use uuid::Uuid;
 
 use crate::{
-    models::Association,
+    models::{Association, OverduePolicy},
     repositories::{
         associated_company::models::AssociatedCompanyExtended,
         company::models::{Company, CompanyExtended},
+        submission_policy::models::SubmissionPolicy,
     },
 };
 
@@ -169,3 +170,37 @@ pub struct EditableAssociatedCompanyTemplate {
 pub struct CompaniesInfoTemplate {
     pub companies: Vec<CompanyLite>,
 }
+
+/// The monthly timesheet submission deadline of the company, `None` when it
+/// has none.
+#[derive(Template, Debug)]
+#[template(path = "company/submission-policy.html")]
+pub struct SubmissionPolicyTemplate {
+    pub company_id: Uuid,
+    pub user_id: Uuid,
+    pub policy: Option<SubmissionPolicy>,
+}
+
+impl SubmissionPolicyTemplate {
+    pub fn reminder_days(&self) -> String {
+        self.policy
+            .as_ref()
+            .map(|policy| {
+                policy
+                    .reminder_days
+                    .iter()
+                    .map(|days| days.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            })
+            .unwrap_or_else(|| "3, 1".to_string())
+    }
+
+    pub fn is_policy(&self, overdue_policy: OverduePolicy) -> bool {
+        self.policy
+            .as_ref()
+            .map_or(overdue_policy == OverduePolicy::Lock, |policy| {
+                policy.overdue_policy == overdue_policy
+            })
+    }
+}
@@ -7,6 +7,7 @@ use uuid::Uuid;
 use crate::{
     models::{EmployeeLevel, EmploymentContract},
     repositories::employment::models::{EmploymentExtended, Subordinate},
+    utils::{import::models::ImportRecord, payroll::models::IncomeConfirmation},
 };
 
 use super::{company::CompanyLite, user::UserLiteTemplate};
@@ -104,3 +105,60 @@ pub struct EmploymentCreateTemplate {
     pub creator_id: Uuid,
     pub creator_level: EmployeeLevel,
 }
+
+#[derive(Template, Debug)]
+#[template(path = "employment/import/employment-import.html")]
+pub struct EmploymentImportTemplate {
+    pub importer_id: Uuid,
+    pub company_id: Uuid,
+}
+
+/// A row of the import as it is in the file, along with what is wrong with it.
+#[derive(Debug, Clone)]
+pub struct EmploymentImportRow {
+    pub line: usize,
+    pub name: String,
+    pub email: String,
+    pub employment_type: String,
+    pub hourly_wage: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub manager_email: String,
+    pub level: String,
+    pub existing_user: bool,
+    pub errors: Vec<String>,
+}
+
+impl From<&ImportRecord> for EmploymentImportRow {
+    fn from(record: &ImportRecord) -> Self {
+        EmploymentImportRow {
+            line: record.line,
+            name: record.name.clone(),
+            email: record.email.clone(),
+            employment_type: record.employment_type.clone(),
+            hourly_wage: record.hourly_wage.clone(),
+            start_date: record.start_date.clone(),
+            end_date: record.end_date.clone(),
+            manager_email: record.manager_email.clone(),
+            level: record.level.clone(),
+            existing_user: false,
+            errors: vec![],
+        }
+    }
+}
+
+#[derive(Template, Debug)]
+#[template(path = "employment/import/employment-import-preview.html")]
+pub struct EmploymentImportPreviewTemplate {
+    pub rows: Vec<EmploymentImportRow>,
+    pub valid_count: usize,
+    pub committed: bool,
+}
+
+/// A standalone page meant to be printed, one confirmation per page.
+#[derive(Template, Debug)]
+#[template(path = "employment/income/income-confirmation.html")]
+pub struct IncomeConfirmationTemplate {
+    pub year: i32,
+    pub confirmations: Vec<IncomeConfirmation>,
+}
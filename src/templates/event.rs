@@ -4,7 +4,14 @@ use serde::Deserialize;
 use sqlx::types::uuid;
 use uuid::Uuid;
 
-use crate::repositories::event::models::Event;
+use crate::{
+    models::{ApprovalStatus, EventStatus},
+    repositories::{
+        event::models::{DateChangeImpact, Event},
+        event_budget::models::{BudgetCompany, BudgetLine},
+    },
+    utils::budget::models::BudgetReport,
+};
 
 use super::staff::StaffTemplate;
 
@@ -19,6 +26,8 @@ pub struct EventTemplate {
     pub accepts_staff: bool,
     pub start_date: NaiveDate,
     pub end_date: NaiveDate,
+    pub status: EventStatus,
+    pub cancellation_reason: Option<String>,
     pub created_at: NaiveDateTime,
     pub edited_at: NaiveDateTime,
 }
@@ -36,6 +45,8 @@ impl From<Event> for EventTemplate {
             accepts_staff: event.accepts_staff,
             start_date: event.start_date,
             end_date: event.end_date,
+            status: event.status,
+            cancellation_reason: event.cancellation_reason,
             created_at: event.created_at,
             edited_at: event.edited_at,
         }
@@ -50,6 +61,7 @@ pub struct EventLite {
     pub accepts_staff: bool,
     pub start_date: NaiveDate,
     pub end_date: NaiveDate,
+    pub status: EventStatus,
 }
 
 impl From<Event> for EventLite {
@@ -61,6 +73,7 @@ impl From<Event> for EventLite {
             accepts_staff: event.accepts_staff,
             start_date: event.start_date,
             end_date: event.end_date,
+            status: event.status,
         }
     }
 }
@@ -81,9 +94,50 @@ pub struct EventEditTemplate {
     pub editor: StaffTemplate,
 }
 
+/// What moving the event to new dates does to its timesheets, shown before
+/// the organizer confirms the change.
+#[derive(Template, Debug)]
+#[template(path = "event/date-change-impact.html")]
+pub struct DateChangeImpactTemplate {
+    pub event: EventTemplate,
+    pub editor_id: Uuid,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub sheets: Vec<DateChangeImpact>,
+    /// The submitted changes, sent again once confirmed.
+    pub confirmed_data: String,
+}
+
+impl DateChangeImpactTemplate {
+    pub fn out_of_range_hours(&self) -> f32 {
+        self.sheets
+            .iter()
+            .filter(|sheet| !sheet.is_locked())
+            .map(|sheet| sheet.out_of_range_hours)
+            .sum()
+    }
+
+    pub fn locked_count(&self) -> usize {
+        self.sheets.iter().filter(|sheet| sheet.is_locked()).count()
+    }
+}
+
 #[derive(Template, Debug)]
 #[template(path = "event/event-create.html")]
 pub struct EventCreateTemplate {
     pub creator_id: Uuid,
     pub company_id: Uuid,
 }
+
+#[derive(Template, Debug)]
+#[template(path = "event/budget/budget.html")]
+pub struct EventBudgetTemplate {
+    pub event_id: Uuid,
+    pub user_id: Uuid,
+    pub event_name: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub companies: Vec<BudgetCompany>,
+    pub lines: Vec<BudgetLine>,
+    pub report: BudgetReport,
+}
@@ -0,0 +1,36 @@
+use askama::Template;
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+use crate::repositories::event_template::models;
+
+#[derive(Debug)]
+pub struct EventTemplateLite {
+    pub id: Uuid,
+    pub name: String,
+    pub description: String,
+    pub duration_days: i32,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<models::EventTemplate> for EventTemplateLite {
+    fn from(template: models::EventTemplate) -> Self {
+        EventTemplateLite {
+            id: template.id,
+            name: template.name,
+            description: template
+                .description
+                .unwrap_or("No description set.".to_string()),
+            duration_days: template.duration_days,
+            created_at: template.created_at,
+        }
+    }
+}
+
+#[derive(Template, Debug)]
+#[template(path = "event/template/event-templates.html")]
+pub struct EventTemplatesTemplate {
+    pub requester_id: Uuid, // user ID
+    pub company_id: Uuid,
+    pub templates: Vec<EventTemplateLite>,
+}
@@ -0,0 +1,21 @@
+use askama::Template;
+use uuid::Uuid;
+
+use crate::{
+    models::JobRunStatus,
+    repositories::job::models::{Job, JobRun},
+};
+
+#[derive(Template, Debug)]
+#[template(path = "job/jobs.html")]
+pub struct JobsTemplate {
+    pub user_id: Uuid,
+    pub jobs: Vec<Job>,
+    pub runs: Vec<JobRun>,
+}
+
+impl JobsTemplate {
+    pub fn is_running(&self, job: &Job) -> bool {
+        job.last_status == Some(JobRunStatus::Running)
+    }
+}
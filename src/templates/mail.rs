@@ -0,0 +1,141 @@
+use askama::Template;
+use chrono::NaiveDate;
+
+/// Everything the users are told about over email.
+#[derive(Debug, Clone)]
+pub enum MailContent {
+    StaffDecision {
+        event_name: String,
+        accepted: bool,
+    },
+    AssignmentDecision {
+        task_title: String,
+        event_name: String,
+        accepted: bool,
+    },
+    TimesheetDecision {
+        event_name: String,
+        approved: bool,
+        manager_note: Option<String>,
+    },
+    SubmissionDeadline {
+        event_name: String,
+        deadline: NaiveDate,
+    },
+    /// The reason is missing when the event was completed.
+    EventStatusChange {
+        event_name: String,
+        cancellation_reason: Option<String>,
+    },
+    Digest {
+        items: Vec<DigestItem>,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct DigestItem {
+    pub subject: String,
+    pub body_text: String,
+}
+
+impl MailContent {
+    pub fn subject(&self) -> String {
+        match self {
+            MailContent::StaffDecision {
+                event_name,
+                accepted,
+            } => format!(
+                "Your application to {} was {}",
+                event_name,
+                if *accepted { "accepted" } else { "rejected" }
+            ),
+            MailContent::AssignmentDecision {
+                task_title,
+                accepted,
+                ..
+            } => format!(
+                "Your assignment to \"{}\" was {}",
+                task_title,
+                if *accepted { "accepted" } else { "rejected" }
+            ),
+            MailContent::TimesheetDecision {
+                event_name,
+                approved,
+                ..
+            } => format!(
+                "Your timesheet for {} was {}",
+                event_name,
+                if *approved { "approved" } else { "rejected" }
+            ),
+            MailContent::SubmissionDeadline { event_name, .. } => {
+                format!("Submit your timesheet for {}", event_name)
+            }
+            MailContent::EventStatusChange {
+                event_name,
+                cancellation_reason,
+            } => format!(
+                "{} was {}",
+                event_name,
+                if cancellation_reason.is_some() {
+                    "cancelled"
+                } else {
+                    "completed"
+                }
+            ),
+            MailContent::Digest { items } => format!("Your daily digest ({} updates)", items.len()),
+        }
+    }
+
+    pub fn render(&self, user_name: &str) -> askama::Result<RenderedMail> {
+        let subject = self.subject();
+        let body_text = TextMailTemplate {
+            user_name,
+            content: self,
+        }
+        .render()?;
+        let body_html = HtmlMailTemplate {
+            subject: &subject,
+            user_name,
+            content: self,
+        }
+        .render()?;
+        let summary = MailSummaryTemplate { content: self }.render()?;
+
+        Ok(RenderedMail {
+            subject,
+            body_text,
+            body_html,
+            summary,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RenderedMail {
+    pub subject: String,
+    pub body_text: String,
+    pub body_html: String,
+    pub summary: String,
+}
+
+#[derive(Template)]
+#[template(path = "mail/mail.html")]
+pub struct HtmlMailTemplate<'a> {
+    pub subject: &'a str,
+    pub user_name: &'a str,
+    pub content: &'a MailContent,
+}
+
+#[derive(Template)]
+#[template(path = "mail/mail.txt")]
+pub struct TextMailTemplate<'a> {
+    pub user_name: &'a str,
+    pub content: &'a MailContent,
+}
+
+// The plain text without the greeting, digests are put together from these.
+#[derive(Template)]
+#[template(path = "mail/summary.txt")]
+pub struct MailSummaryTemplate<'a> {
+    pub content: &'a MailContent,
+}
@@ -1,9 +1,16 @@
+pub mod calendar;
 pub mod comment;
 pub mod common;
 pub mod company;
 pub mod employment;
 pub mod event;
+pub mod event_template;
+pub mod job;
+pub mod mail;
+pub mod notification;
+pub mod report;
 pub mod staff;
 pub mod task;
 pub mod timesheet;
+pub mod trash;
 pub mod user;
@@ -0,0 +1,34 @@
+use askama::Template;
+use sqlx::types::uuid;
+use uuid::Uuid;
+
+use crate::models::MailDelivery;
+use crate::repositories::notification::models::{Notification, NotificationPreference};
+
+#[derive(Template, Debug)]
+#[template(path = "notification/notifications.html")]
+pub struct NotificationsTemplate {
+    pub user_id: Uuid,
+    pub notifications: Vec<Notification>,
+    pub unread_count: i64,
+    pub next_offset: Option<i64>,
+    pub prev_offset: Option<i64>,
+    pub limit: i64,
+}
+
+#[derive(Template, Debug)]
+#[template(path = "notification/notification-badge.html")]
+pub struct NotificationBadgeTemplate {
+    pub user_id: Uuid,
+    pub unread_count: i64,
+    // Set when the badge gets refreshed alongside the notification list.
+    pub swap_oob: bool,
+}
+
+#[derive(Template, Debug)]
+#[template(path = "notification/notification-preferences.html")]
+pub struct NotificationPreferencesTemplate {
+    pub user_id: Uuid,
+    pub preferences: Vec<NotificationPreference>,
+    pub mail_delivery: MailDelivery,
+}
@@ -0,0 +1,52 @@
+use askama::Template;
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::repositories::{
+    company::models::Company,
+    report::models::{ApprovalLatency, MonthlyHours, StaffUtilization, TaskCompletion},
+};
+
+#[derive(Debug)]
+pub struct ChartBar {
+    pub label: String,
+    pub value: f64,
+    /// Width of the bar, the highest value fills the chart.
+    pub width_pct: f64,
+}
+
+impl ChartBar {
+    pub fn scaled(values: Vec<(String, f64)>) -> Vec<ChartBar> {
+        let max = values
+            .iter()
+            .map(|(_, value)| *value)
+            .fold(0.0_f64, f64::max);
+
+        values
+            .into_iter()
+            .map(|(label, value)| ChartBar {
+                label,
+                value,
+                width_pct: if max > 0.0 { value * 100.0 / max } else { 0.0 },
+            })
+            .collect()
+    }
+}
+
+#[derive(Template, Debug)]
+#[template(path = "report/reports.html")]
+pub struct ReportsTemplate {
+    pub user_id: Uuid,
+    pub date_from: NaiveDate,
+    pub date_to: NaiveDate,
+    /// Empty when the reports aren't restricted to a company.
+    pub company_id: String,
+    pub companies: Vec<Company>,
+    pub reported_hours: f64,
+    pub accepted_hours: f64,
+    pub hours_chart: Vec<ChartBar>,
+    pub monthly_hours: Vec<MonthlyHours>,
+    pub utilization: Vec<StaffUtilization>,
+    pub approval: Vec<ApprovalLatency>,
+    pub tasks: Vec<TaskCompletion>,
+}
@@ -86,6 +86,8 @@ pub struct StaffRegisterTemplate {
 #[template(path = "event/staff/all-staff.html")]
 pub struct AllStaffTemplate {
     pub staff: Vec<StaffTemplate>,
+    /// Shown above the staff, e.g. when a timesheet was kept.
+    pub warning: Option<String>,
 }
 
 #[derive(Template, Debug, Deserialize)]
@@ -1,14 +1,16 @@
 use crate::templates::staff::AssignedStaff;
 use askama::Template;
-use chrono::NaiveDateTime;
+use chrono::{NaiveDateTime, Utc};
 use serde::Deserialize;
 use sqlx::types::uuid;
 use uuid::Uuid;
 
 use crate::{
-    models::{AcceptanceStatus, EventRole, TaskPriority},
+    models::{AcceptanceStatus, EventRole, TaskPriority, TaskStatus},
     repositories::{
-        event_staff::models::StaffLite, task::models::TaskExtended, user::models::UserLite,
+        event_staff::models::StaffLite, task::models::TaskExtended,
+        task_checklist::models::ChecklistItem, task_dependency::models::TaskDependencyLite,
+        user::models::UserLite,
     },
 };
 
@@ -25,6 +27,11 @@ pub struct EventTask {
     pub finished_at: Option<NaiveDateTime>,
     pub priority: TaskPriority,
     pub accepts_staff: bool,
+    pub status: TaskStatus,
+    pub due_at: Option<NaiveDateTime>,
+    pub estimated_hours: Option<f32>,
+    pub position: i32,
+    pub is_overdue: bool,
     pub created_at: NaiveDateTime,
     pub edited_at: NaiveDateTime,
 }
@@ -33,6 +40,10 @@ impl From<TaskExtended> for EventTask {
     fn from(task: TaskExtended) -> Self {
         let creator_lite: UserLite = task.creator.into();
         let creator = creator_lite.into();
+        let is_overdue = task.status != TaskStatus::Done
+            && task
+                .due_at
+                .is_some_and(|due_at| due_at < Utc::now().naive_local());
 
         EventTask {
             id: task.task_id,
@@ -48,6 +59,11 @@ impl From<TaskExtended> for EventTask {
             finished_at: task.finished_at,
             priority: task.priority,
             accepts_staff: task.accepts_staff,
+            status: task.status,
+            due_at: task.due_at,
+            estimated_hours: task.estimated_hours,
+            position: task.position,
+            is_overdue,
             created_at: task.created_at,
             edited_at: task.edited_at,
         }
@@ -70,6 +86,19 @@ pub struct TasksPanelTemplate {
     pub requester: StaffLite,
 }
 
+#[derive(Deserialize, Debug)]
+pub struct TaskBoardColumn {
+    pub status: TaskStatus,
+    pub tasks: Vec<EventTask>,
+}
+
+#[derive(Template, Deserialize)]
+#[template(path = "event/task/task-board.html")]
+pub struct TaskBoardTemplate {
+    pub requester: StaffLite,
+    pub columns: Vec<TaskBoardColumn>,
+}
+
 #[derive(Template, Deserialize)]
 #[template(path = "event/task/task-panel.html")]
 pub struct TaskPanelTemplate {
@@ -83,6 +112,42 @@ pub struct TaskPanelTemplate {
 pub struct TaskCreationTemplate {
     pub creator_id: Uuid,
     pub event_id: Uuid,
+    pub parent_task_id: Option<Uuid>,
+}
+
+#[derive(Template)]
+#[template(path = "event/task/subtasks.html")]
+pub struct SubtasksTemplate {
+    pub requester: StaffLite,
+    pub task_id: Uuid,
+    pub subtasks: Vec<EventTask>,
+}
+
+#[derive(Template)]
+#[template(path = "event/task/checklist.html")]
+pub struct ChecklistTemplate {
+    pub requester: StaffLite,
+    pub task_id: Uuid,
+    pub items: Vec<ChecklistItem>,
+    pub finished_count: usize,
+}
+
+#[derive(Template)]
+#[template(path = "event/task/dependencies.html")]
+pub struct TaskDependenciesTemplate {
+    pub requester: StaffLite,
+    pub task_id: Uuid,
+    pub blockers: Vec<TaskDependencyLite>,
+    pub blocked: Vec<TaskDependencyLite>,
+    // Tasks of the same event which may still be added as blockers.
+    pub candidates: Vec<EventTask>,
+}
+
+#[derive(Template)]
+#[template(path = "event/task/critical-path.html")]
+pub struct CriticalPathTemplate {
+    pub tasks: Vec<EventTask>,
+    pub total_hours: f32,
 }
 
 #[derive(Template, Deserialize)]
@@ -1,6 +1,12 @@
 use crate::{
-    repositories::timesheet::models::{TimesheetWithEvent, Workday},
-    utils::wage_calc::models::TimesheetWageDetailed,
+    repositories::submission_policy::models::OverdueTimesheet,
+    repositories::timesheet::models::{
+        ReviewQueueEntry, ReviewQueueEvent, TimesheetHistoryEntry, TimesheetReview,
+        TimesheetWithEvent, Workday, WorkdayFlag,
+    },
+    repositories::timesheet_amendment::models::{AmendmentDay, TimesheetAmendment},
+    repositories::timesheet_consistency::models::ConsistencyReport,
+    utils::wage_calc::models::{DetailedWage, TimesheetWageDetailed},
 };
 use askama::Template;
 use chrono::{NaiveDate, NaiveDateTime};
@@ -8,7 +14,10 @@ use serde::Deserialize;
 use sqlx::types::uuid;
 use uuid::Uuid;
 
-use crate::{models::ApprovalStatus, repositories::timesheet::models::TimesheetWithWorkdays};
+use crate::{
+    models::{AmendmentStatus, ApprovalStatus, TimesheetTransition},
+    repositories::timesheet::models::TimesheetWithWorkdays,
+};
 
 #[derive(Template, Debug, Deserialize)]
 #[template(path = "employment/timesheet/workday.html")]
@@ -54,7 +63,7 @@ impl From<Workday> for WorkdayEditTemplate {
     }
 }
 
-#[derive(Template, Debug, Deserialize)]
+#[derive(Template, Debug)]
 #[template(path = "employment/timesheet/timesheet.html")]
 //
 // Note: We deliberately don't supply `calculated_wage`, this value will be
@@ -75,10 +84,26 @@ pub struct TimesheetTemplate {
     pub is_editable: bool,
     pub status: ApprovalStatus,
     pub manager_note: Option<String>,
+    pub flags: Vec<WorkdayFlag>,
+    pub awaits_acknowledgement: bool,
     pub created_at: NaiveDateTime,
     pub edited_at: NaiveDateTime,
 }
 
+impl TimesheetTemplate {
+    pub fn with_review(self, review: TimesheetReview) -> Self {
+        TimesheetTemplate {
+            awaits_acknowledgement: review.awaits_acknowledgement(),
+            flags: review.flags,
+            ..self
+        }
+    }
+
+    pub fn flag_for(&self, date: &NaiveDate) -> Option<&WorkdayFlag> {
+        self.flags.iter().find(|flag| flag.date == *date)
+    }
+}
+
 impl From<TimesheetWithWorkdays> for TimesheetTemplate {
     fn from(full_timesheet: TimesheetWithWorkdays) -> Self {
         let workdays = full_timesheet
@@ -108,6 +133,8 @@ impl From<TimesheetWithWorkdays> for TimesheetTemplate {
             is_editable: full_timesheet.timesheet.is_editable,
             status: full_timesheet.timesheet.approval_status,
             manager_note: full_timesheet.timesheet.manager_note,
+            flags: vec![],
+            awaits_acknowledgement: false,
             created_at: full_timesheet.timesheet.created_at,
             edited_at: full_timesheet.timesheet.edited_at,
         }
@@ -176,8 +203,92 @@ pub struct TimesheetsReviewTemplate {
     pub company_id: Uuid,
 }
 
-#[derive(Template, Debug, Deserialize)]
+#[derive(Template, Debug)]
 #[template(path = "employment/timesheet/timesheet-review.html")]
 pub struct TimesheetReviewTemplate {
     pub sheet: TimesheetTemplate,
+    pub history: Vec<TimesheetHistoryEntry>,
+}
+
+impl TimesheetReviewTemplate {
+    pub fn can_adjust(&self) -> bool {
+        self.sheet.flags.iter().any(|flag| flag.has_suggestion())
+    }
+}
+
+/// The timesheets of all direct and delegated subordinates of the manager.
+/// The filter fields are kept as submitted so the form can be filled again.
+#[derive(Template, Debug)]
+#[template(path = "employment/timesheet/review-queue.html")]
+pub struct ReviewQueueTemplate {
+    pub user_id: Uuid,
+    pub event_id: String,
+    pub month: String,
+    pub status: String,
+    pub min_hours: String,
+    pub events: Vec<ReviewQueueEvent>,
+    pub entries: Vec<ReviewQueueEntry>,
+}
+
+impl ReviewQueueTemplate {
+    pub fn pending_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.approval_status == ApprovalStatus::Pending)
+            .count()
+    }
+}
+
+/// The workdays and the computed wage of a timesheet, shown next to the
+/// review queue.
+#[derive(Template, Debug)]
+#[template(path = "employment/timesheet/timesheet-preview.html")]
+pub struct TimesheetPreviewTemplate {
+    pub sheet: TimesheetTemplate,
+    pub wage: Option<TimesheetWageDetailed>,
+    pub wage_error: Option<String>,
+}
+
+/// The sheets of the manager's subordinates which missed their deadline.
+#[derive(Template, Debug)]
+#[template(path = "employment/timesheet/overdue.html")]
+pub struct OverdueTimesheetsTemplate {
+    pub timesheets: Vec<OverdueTimesheet>,
+}
+
+/// An amendment with the wage difference it settles, rejected amendments
+/// settle nothing.
+#[derive(Debug)]
+pub struct AmendmentView {
+    pub amendment: TimesheetAmendment,
+    pub days: Vec<AmendmentDay>,
+    pub difference: Option<DetailedWage>,
+    pub difference_error: Option<String>,
+}
+
+/// Corrections of an accepted timesheet. The workdays show the values
+/// the employee is paid for, the approved amendments included.
+#[derive(Template, Debug)]
+#[template(path = "employment/timesheet/amendments.html")]
+pub struct AmendmentsTemplate {
+    pub timesheet_id: Uuid,
+    pub workdays: Vec<Workday>,
+    pub amendments: Vec<AmendmentView>,
+    pub can_decide: bool,
+}
+
+impl AmendmentsTemplate {
+    pub fn has_pending(&self) -> bool {
+        self.amendments
+            .iter()
+            .any(|view| view.amendment.status == AmendmentStatus::Pending)
+    }
+}
+
+/// The inconsistent timesheets found by the check, for administrators.
+#[derive(Template, Debug)]
+#[template(path = "employment/timesheet/consistency.html")]
+pub struct TimesheetConsistencyTemplate {
+    pub user_id: Uuid,
+    pub report: ConsistencyReport,
 }
@@ -0,0 +1,21 @@
+use askama::Template;
+use uuid::Uuid;
+
+use crate::repositories::trash::models::Deletion;
+
+#[derive(Template, Debug)]
+#[template(path = "trash/trash.html")]
+pub struct TrashTemplate {
+    pub user_id: Uuid,
+    pub deletions: Vec<Deletion>,
+    pub retention_days: i32,
+}
+
+impl TrashTemplate {
+    /// The date the deletion is purged on by the retention job.
+    pub fn purged_on(&self, deletion: &Deletion) -> String {
+        (deletion.deleted_at + chrono::Duration::days(self.retention_days.into()))
+            .format("%d.%m.%Y")
+            .to_string()
+    }
+}
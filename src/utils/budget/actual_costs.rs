@@ -0,0 +1,75 @@
+use crate::{
+    common::DbResult,
+    repositories::{
+        event_budget::models::BudgetTimesheet, timesheet::timesheet_repo::TimesheetRepository,
+    },
+    utils::wage_calc::{calculate_wage::calculate_timesheet_wage, models::YearAndMonth},
+};
+
+use super::models::DayCost;
+
+/// What the submitted and accepted timesheets cost per worked day. The
+/// employer's cost of a month (the gross wage and the employer's insurance)
+/// is split among its days by the hours worked.
+pub async fn actual_costs(
+    timesheet_repo: &TimesheetRepository,
+    timesheets: &[BudgetTimesheet],
+    warnings: &mut Vec<String>,
+) -> DbResult<Vec<DayCost>> {
+    let mut costs = vec![];
+
+    for timesheet in timesheets {
+        let related = timesheet_repo
+            .read_all_with_date_from_to_per_employment_extended_db(
+                timesheet.user_id,
+                timesheet.company_id,
+                timesheet.start_date,
+                timesheet.end_date,
+            )
+            .await?;
+        // A signed pink paper only changes the net wage, not the employer's cost.
+        let wage = calculate_timesheet_wage(false, &related, timesheet.id).and_then(|wage| {
+            match wage.error_option {
+                Some(error) => Err(error),
+                None => Ok(wage),
+            }
+        });
+        if let Err(error) = &wage {
+            warnings.push(format!(
+                "The wage of a timesheet from {} couldn't be computed, its cost is left out: {}",
+                timesheet.company_name, error
+            ));
+        }
+
+        let workdays = related
+            .timesheets
+            .iter()
+            .find(|related| related.timesheet.id == timesheet.id)
+            .map(|related| related.workdays.as_slice())
+            .unwrap_or_default();
+        for workday in workdays.iter().filter(|workday| workday.total_hours > 0.0) {
+            let month: YearAndMonth = workday.date.into();
+            let cost = wage
+                .as_ref()
+                .ok()
+                .and_then(|wage| wage.month_to_detailed_wage.get(&month))
+                .filter(|detailed| detailed.worked_hours > 0.0)
+                .map_or(0.0, |detailed| {
+                    let employer_cost = detailed.tax_base
+                        + detailed.employer_social_insurance
+                        + detailed.employer_health_insurance;
+                    (employer_cost * workday.total_hours / detailed.worked_hours) as f64
+                });
+
+            costs.push(DayCost {
+                company_name: timesheet.company_name.clone(),
+                date: workday.date,
+                role: timesheet.role.clone(),
+                hours: workday.total_hours as f64,
+                cost,
+            });
+        }
+    }
+
+    Ok(costs)
+}
@@ -0,0 +1,3 @@
+pub mod actual_costs;
+pub mod models;
+pub mod report;
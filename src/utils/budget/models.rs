@@ -0,0 +1,49 @@
+use chrono::NaiveDate;
+
+use crate::models::EventRole;
+
+/// Budget lines without a company are reported under this name.
+pub const ANY_COMPANY: &str = "Any company";
+
+/// Hours worked on a day and what they cost the employer, including the
+/// employer's insurance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayCost {
+    pub company_name: String,
+    pub date: NaiveDate,
+    pub role: EventRole,
+    pub hours: f64,
+    pub cost: f64,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct CostFigures {
+    pub budget_hours: f64,
+    pub budget_cost: f64,
+    pub forecast_hours: f64,
+    pub forecast_cost: f64,
+    pub actual_hours: f64,
+    pub actual_cost: f64,
+}
+
+impl CostFigures {
+    /// Positive when the forecast is over the budget.
+    pub fn forecast_variance(&self) -> f64 {
+        self.forecast_cost - self.budget_cost
+    }
+
+    /// Positive when the actual cost is over the budget.
+    pub fn actual_variance(&self) -> f64 {
+        self.actual_cost - self.budget_cost
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BudgetReport {
+    pub total: CostFigures,
+    pub by_company: Vec<(String, CostFigures)>,
+    pub by_day: Vec<(NaiveDate, CostFigures)>,
+    pub by_role: Vec<(String, CostFigures)>,
+    /// Costs which couldn't be computed and are left out of the report.
+    pub warnings: Vec<String>,
+}
@@ -0,0 +1,208 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::{
+    models::EmploymentContract,
+    repositories::{
+        event_budget::models::{BudgetLine, ForecastStaff},
+        wage_preset::models::WagePreset,
+    },
+};
+
+use super::models::{BudgetReport, CostFigures, DayCost, ANY_COMPANY};
+
+fn event_days(start_date: NaiveDate, end_date: NaiveDate) -> Vec<NaiveDate> {
+    start_date
+        .iter_days()
+        .take_while(|day| *day <= end_date)
+        .collect()
+}
+
+/// Every line spread into the days it is planned for.
+pub fn budget_costs(
+    lines: &[BudgetLine],
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Vec<DayCost> {
+    let days = event_days(start_date, end_date);
+
+    lines
+        .iter()
+        .flat_map(|line| {
+            let line_days = match line.date {
+                Some(date) => vec![date],
+                None => days.clone(),
+            };
+            line_days.into_iter().map(move |date| {
+                let hours = line.headcount as f64 * line.hours as f64;
+                DayCost {
+                    company_name: line.company_name.clone().unwrap_or(ANY_COMPANY.to_string()),
+                    date,
+                    role: line.role.clone(),
+                    hours,
+                    cost: hours * line.hourly_rate,
+                }
+            })
+        })
+        .collect()
+}
+
+/// The most specific line planned for the staff member on the day, a line
+/// of their company beats a line for any company, a line of the day beats
+/// a line for every day.
+fn planned_line<'a>(
+    lines: &'a [BudgetLine],
+    staff: &ForecastStaff,
+    date: NaiveDate,
+) -> Option<&'a BudgetLine> {
+    lines
+        .iter()
+        .filter(|line| {
+            line.role == staff.role
+                && line.date.is_none_or(|line_date| line_date == date)
+                && line
+                    .company_id
+                    .is_none_or(|company_id| company_id == staff.company_id)
+        })
+        .max_by_key(|line| (line.company_id.is_some(), line.date.is_some()))
+}
+
+fn matching_preset(presets: &[WagePreset], date: NaiveDate) -> Option<&WagePreset> {
+    presets.iter().find(|preset| {
+        preset.valid_from <= date && preset.valid_to.is_none_or(|valid_to| valid_to >= date)
+    })
+}
+
+/// Share of the wage the employer pays as insurance. Part-time agreements
+/// are only insured above the monthly limit of the preset.
+fn employer_insurance_rate(
+    preset: &WagePreset,
+    employment_type: &EmploymentContract,
+    monthly_tax_base: f64,
+) -> f64 {
+    let limit = match employment_type {
+        EmploymentContract::Dpp => preset.monthly_dpp_employer_no_tax_limit,
+        EmploymentContract::Dpc => preset.monthly_dpc_employer_no_tax_limit,
+        EmploymentContract::Hpp => 0.0,
+    };
+    if monthly_tax_base < limit as f64 {
+        return 0.0;
+    }
+
+    (preset.health_insurance_employer_tax_pct + preset.social_insurance_employer_tax_pct) as f64
+        / 100.0
+}
+
+/// What the accepted staff costs if everyone works the planned hours of
+/// their role. Only the work at this event counts towards the monthly
+/// insurance limits.
+pub fn forecast_costs(
+    lines: &[BudgetLine],
+    staff: &[ForecastStaff],
+    presets: &[WagePreset],
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+    warnings: &mut Vec<String>,
+) -> Vec<DayCost> {
+    let days = event_days(start_date, end_date);
+    let mut costs = vec![];
+
+    for member in staff {
+        let planned_days: Vec<(NaiveDate, f64)> = days
+            .iter()
+            .filter_map(|day| {
+                planned_line(lines, member, *day).map(|line| (*day, line.hours as f64))
+            })
+            .collect();
+
+        let mut monthly_tax_base = HashMap::<(i32, u32), f64>::new();
+        for (day, hours) in planned_days.iter() {
+            *monthly_tax_base
+                .entry((day.year(), day.month()))
+                .or_default() += hours * member.hourly_wage;
+        }
+
+        for (day, hours) in planned_days {
+            let wage = hours * member.hourly_wage;
+            let insurance_rate = match matching_preset(presets, day) {
+                Some(preset) => employer_insurance_rate(
+                    preset,
+                    &member.employment_type,
+                    monthly_tax_base[&(day.year(), day.month())],
+                ),
+                None => {
+                    let warning = format!(
+                        "No wage preset is valid for {}, the forecast leaves out the employer's insurance.",
+                        day.format("%m/%Y")
+                    );
+                    if !warnings.contains(&warning) {
+                        warnings.push(warning);
+                    }
+                    0.0
+                }
+            };
+
+            costs.push(DayCost {
+                company_name: member.company_name.clone(),
+                date: day,
+                role: member.role.clone(),
+                hours,
+                cost: wage * (1.0 + insurance_rate),
+            });
+        }
+    }
+
+    costs
+}
+
+/// Adds a cost to the budget, forecast or actual figures.
+type AddCost = fn(&mut CostFigures, &DayCost);
+
+fn add_costs<K: Ord>(figures: &mut BTreeMap<K, CostFigures>, key: K, cost: &DayCost, add: AddCost) {
+    add(figures.entry(key).or_default(), cost);
+}
+
+/// Compares the budget, forecast and actual costs by company, day and role.
+pub fn build_report(
+    budget: &[DayCost],
+    forecast: &[DayCost],
+    actual: &[DayCost],
+    warnings: Vec<String>,
+) -> BudgetReport {
+    let mut total = CostFigures::default();
+    let mut by_company = BTreeMap::<String, CostFigures>::new();
+    let mut by_day = BTreeMap::<NaiveDate, CostFigures>::new();
+    let mut by_role = BTreeMap::<String, CostFigures>::new();
+
+    let sources: [(&[DayCost], AddCost); 3] = [
+        (budget, |figures, cost| {
+            figures.budget_hours += cost.hours;
+            figures.budget_cost += cost.cost;
+        }),
+        (forecast, |figures, cost| {
+            figures.forecast_hours += cost.hours;
+            figures.forecast_cost += cost.cost;
+        }),
+        (actual, |figures, cost| {
+            figures.actual_hours += cost.hours;
+            figures.actual_cost += cost.cost;
+        }),
+    ];
+    for (costs, add) in sources {
+        for cost in costs {
+            add(&mut total, cost);
+            add_costs(&mut by_company, cost.company_name.clone(), cost, add);
+            add_costs(&mut by_day, cost.date, cost, add);
+            add_costs(&mut by_role, cost.role.to_string(), cost, add);
+        }
+    }
+
+    BudgetReport {
+        total,
+        by_company: by_company.into_iter().collect(),
+        by_day: by_day.into_iter().collect(),
+        by_role: by_role.into_iter().collect(),
+        warnings,
+    }
+}
@@ -0,0 +1,19 @@
+use pulldown_cmark::{html, Options, Parser};
+
+/// Renders comment Markdown into HTML which is safe to embed in a page.
+/// Raw HTML written by the author is sanitised away, links open outside
+/// of the application.
+pub fn render_markdown(content: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TABLES);
+
+    let parser = Parser::new_ext(content, options);
+    let mut unsafe_html = String::new();
+    html::push_html(&mut unsafe_html, parser);
+
+    ammonia::Builder::default()
+        .link_rel(Some("noopener noreferrer nofollow"))
+        .clean(&unsafe_html)
+        .to_string()
+}
@@ -0,0 +1,47 @@
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct MentionCandidate {
+    pub staff_id: Uuid,
+    pub name: String,
+}
+
+/// Finds the staff members mentioned as `@Name` in the comment.
+/// Names may contain spaces, so the candidates are looked up in the content
+/// rather than parsed out of it. The longest name wins when several of them
+/// start the same way, `@Joe Cocker` doesn't mention a plain `Joe` as well.
+pub fn find_mentions(content: &str, candidates: &[MentionCandidate]) -> Vec<Uuid> {
+    let lowered = content.to_lowercase();
+    let mut sorted: Vec<&MentionCandidate> = candidates.iter().collect();
+    sorted.sort_by_key(|candidate| std::cmp::Reverse(candidate.name.chars().count()));
+
+    let mut taken: Vec<(usize, usize)> = vec![];
+    let mut mentioned: Vec<Uuid> = vec![];
+    for candidate in sorted {
+        let needle = format!("@{}", candidate.name.to_lowercase());
+        for (start, _) in lowered.match_indices(needle.as_str()) {
+            let end = start + needle.len();
+            let at_word_start = lowered[..start]
+                .chars()
+                .next_back()
+                .is_none_or(|previous| !previous.is_alphanumeric());
+            let at_word_end = lowered[end..]
+                .chars()
+                .next()
+                .is_none_or(|next| !next.is_alphanumeric());
+            let overlaps = taken
+                .iter()
+                .any(|(taken_start, taken_end)| start < *taken_end && *taken_start < end);
+            if !at_word_start || !at_word_end || overlaps {
+                continue;
+            }
+
+            taken.push((start, end));
+            if !mentioned.contains(&candidate.staff_id) {
+                mentioned.push(candidate.staff_id);
+            }
+        }
+    }
+
+    mentioned
+}
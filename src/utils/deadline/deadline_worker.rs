@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+
+use crate::{
+    common::DbResult,
+    repositories::submission_policy::submission_policy_repo::SubmissionPolicyRepository,
+    utils::jobs::job_scheduler::Job,
+};
+
+pub const DEADLINE_WORKER_PERIOD: Duration = Duration::from_secs(60 * 60);
+
+/// Reminds the employees of the upcoming deadlines and applies the overdue
+/// policies to the sheets which missed theirs. Returns the number of reminders
+/// sent and the number of overdue sheets.
+pub async fn enforce_deadlines(
+    submission_policy_repo: &SubmissionPolicyRepository,
+    today: NaiveDate,
+) -> DbResult<(usize, usize)> {
+    let reminded = submission_policy_repo.send_due_reminders(today).await?;
+    let overdue = submission_policy_repo.apply_overdue_policies(today).await?;
+
+    Ok((reminded, overdue))
+}
+
+/// Enforces the deadlines of the day.
+pub struct DeadlineJob {
+    pub submission_policy_repo: SubmissionPolicyRepository,
+}
+
+#[async_trait]
+impl Job for DeadlineJob {
+    fn name(&self) -> &'static str {
+        "submission_deadlines"
+    }
+
+    fn period(&self) -> Duration {
+        DEADLINE_WORKER_PERIOD
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let today = Utc::now().naive_local().date();
+        let (reminded, overdue) = enforce_deadlines(&self.submission_policy_repo, today)
+            .await
+            .map_err(|error| error.to_string())?;
+
+        Ok(format!(
+            "Sent {} deadline reminders, {} timesheets are overdue.",
+            reminded, overdue
+        ))
+    }
+}
@@ -0,0 +1 @@
+pub mod deadline_worker;
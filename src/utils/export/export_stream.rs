@@ -0,0 +1,93 @@
+use std::future::Future;
+
+use actix_web::web::Bytes;
+use futures::{channel::mpsc, SinkExt, Stream};
+
+use super::{
+    models::{ExportCell, ExportError, ExportFormat},
+    table_writer::{table_writer, TableWriter},
+};
+
+/// Rows are sent out in chunks of about this size.
+pub const EXPORT_CHUNK_SIZE: usize = 64 * 1024;
+/// Chunks waiting for a slow client, the export pauses when they are full.
+const EXPORT_BUFFERED_CHUNKS: usize = 4;
+
+/// Receives the rows of an export and passes them to the client.
+pub struct ExportSink {
+    writer: Box<dyn TableWriter + Send>,
+    buffer: Vec<u8>,
+    sender: mpsc::Sender<Result<Bytes, std::io::Error>>,
+}
+
+impl ExportSink {
+    pub async fn push(&mut self, cells: Vec<ExportCell>) -> Result<(), ExportError> {
+        self.writer.write_row(&cells, &mut self.buffer);
+        if self.buffer.len() >= EXPORT_CHUNK_SIZE {
+            self.flush().await?;
+        }
+
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<(), ExportError> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+
+        let chunk = Bytes::from(std::mem::take(&mut self.buffer));
+        self.sender
+            .send(Ok(chunk))
+            .await
+            .map_err(|_| ExportError::Closed)
+    }
+
+    async fn finish(mut self) -> Result<(), ExportError> {
+        self.writer.finish(&mut self.buffer);
+        self.flush().await
+    }
+}
+
+/// Runs `produce` in the background and streams the table it pushes into the
+/// sink, at most a few chunks are held in memory. A failure halfway through
+/// aborts the download, so the client doesn't end up with a partial file.
+pub fn stream_export<F, Fut>(
+    format: ExportFormat,
+    sheet_name: &str,
+    columns: &[&str],
+    produce: F,
+) -> impl Stream<Item = Result<Bytes, std::io::Error>>
+where
+    F: FnOnce(ExportSink) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<ExportSink, ExportError>> + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel(EXPORT_BUFFERED_CHUNKS);
+    let mut writer = table_writer(format, sheet_name);
+
+    let mut buffer = vec![];
+    let header: Vec<ExportCell> = columns.iter().map(|column| (*column).into()).collect();
+    writer.write_row(&header, &mut buffer);
+
+    let sink = ExportSink {
+        writer,
+        buffer,
+        sender: sender.clone(),
+    };
+
+    tokio::spawn(async move {
+        let result = match produce(sink).await {
+            Ok(sink) => sink.finish().await,
+            Err(error) => Err(error),
+        };
+
+        if let Err(ExportError::Database(error)) = result {
+            log::error!("Export failed: {}", error);
+            let mut sender = sender;
+            let _ = sender
+                .send(Err(std::io::Error::other("Export failed.")))
+                .await;
+        }
+    });
+
+    receiver
+}
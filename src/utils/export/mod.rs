@@ -0,0 +1,4 @@
+pub mod export_stream;
+pub mod models;
+pub mod table_writer;
+pub mod zip_stream;
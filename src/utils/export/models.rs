@@ -0,0 +1,108 @@
+use std::fmt;
+
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Xlsx,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Xlsx => {
+                "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+            }
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Xlsx => "xlsx",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportCell {
+    Text(String),
+    Number(f64),
+    Date(NaiveDate),
+    Empty,
+}
+
+impl fmt::Display for ExportCell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportCell::Text(text) => write!(f, "{}", text),
+            ExportCell::Number(number) => write!(f, "{}", number),
+            ExportCell::Date(date) => write!(f, "{}", date.format("%Y-%m-%d")),
+            ExportCell::Empty => Ok(()),
+        }
+    }
+}
+
+impl From<String> for ExportCell {
+    fn from(text: String) -> Self {
+        ExportCell::Text(text)
+    }
+}
+
+impl From<&str> for ExportCell {
+    fn from(text: &str) -> Self {
+        ExportCell::Text(text.to_string())
+    }
+}
+
+impl From<f64> for ExportCell {
+    fn from(number: f64) -> Self {
+        ExportCell::Number(number)
+    }
+}
+
+impl From<f32> for ExportCell {
+    fn from(number: f32) -> Self {
+        // Through the decimal representation, so 0.1 doesn't become 0.10000000149.
+        ExportCell::Number(number.to_string().parse().unwrap_or(number as f64))
+    }
+}
+
+impl From<NaiveDate> for ExportCell {
+    fn from(date: NaiveDate) -> Self {
+        ExportCell::Date(date)
+    }
+}
+
+impl<T: Into<ExportCell>> From<Option<T>> for ExportCell {
+    fn from(value: Option<T>) -> Self {
+        value.map(|value| value.into()).unwrap_or(ExportCell::Empty)
+    }
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    Database(sqlx::Error),
+    /// The client stopped downloading the export.
+    Closed,
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::Database(error) => write!(f, "Database error: {}", error),
+            ExportError::Closed => write!(f, "The download was cancelled."),
+        }
+    }
+}
+
+impl From<sqlx::Error> for ExportError {
+    fn from(error: sqlx::Error) -> Self {
+        ExportError::Database(error)
+    }
+}
@@ -0,0 +1,207 @@
+use chrono::NaiveDate;
+
+use super::{
+    models::{ExportCell, ExportFormat},
+    zip_stream::ZipStream,
+};
+
+/// Writes a single table, the output of every call is appended to `output`
+/// and can be sent out right away.
+pub trait TableWriter {
+    fn write_row(&mut self, cells: &[ExportCell], output: &mut Vec<u8>);
+    fn finish(&mut self, output: &mut Vec<u8>);
+}
+
+pub fn table_writer(format: ExportFormat, sheet_name: &str) -> Box<dyn TableWriter + Send> {
+    match format {
+        ExportFormat::Csv => Box::new(CsvTableWriter),
+        ExportFormat::Xlsx => Box::new(XlsxTableWriter::new(sheet_name)),
+    }
+}
+
+pub struct CsvTableWriter;
+
+impl TableWriter for CsvTableWriter {
+    fn write_row(&mut self, cells: &[ExportCell], output: &mut Vec<u8>) {
+        let mut writer = csv::Writer::from_writer(output);
+        writer
+            .write_record(cells.iter().map(|cell| cell.to_string()))
+            .expect("Writing to memory can't fail.");
+        writer.flush().expect("Writing to memory can't fail.");
+    }
+
+    fn finish(&mut self, _output: &mut Vec<u8>) {}
+}
+
+const CONTENT_TYPES: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">"#,
+    r#"<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>"#,
+    r#"<Default Extension="xml" ContentType="application/xml"/>"#,
+    r#"<Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/>"#,
+    r#"<Override PartName="/xl/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.styles+xml"/>"#,
+    r#"<Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/>"#,
+    r#"</Types>"#,
+);
+
+const ROOT_RELATIONSHIPS: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+    r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/>"#,
+    r#"</Relationships>"#,
+);
+
+const WORKBOOK_RELATIONSHIPS: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+    r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/>"#,
+    r#"<Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/>"#,
+    r#"</Relationships>"#,
+);
+
+/// Style 1 formats dates, style 2 is the bold header.
+const STYLES: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<styleSheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">"#,
+    r#"<fonts count="2"><font><sz val="11"/><name val="Calibri"/></font>"#,
+    r#"<font><b/><sz val="11"/><name val="Calibri"/></font></fonts>"#,
+    r#"<fills count="2"><fill><patternFill patternType="none"/></fill>"#,
+    r#"<fill><patternFill patternType="gray125"/></fill></fills>"#,
+    r#"<borders count="1"><border><left/><right/><top/><bottom/><diagonal/></border></borders>"#,
+    r#"<cellStyleXfs count="1"><xf numFmtId="0" fontId="0" fillId="0" borderId="0"/></cellStyleXfs>"#,
+    r#"<cellXfs count="3"><xf numFmtId="0" fontId="0" fillId="0" borderId="0" xfId="0"/>"#,
+    r#"<xf numFmtId="14" fontId="0" fillId="0" borderId="0" xfId="0" applyNumberFormat="1"/>"#,
+    r#"<xf numFmtId="0" fontId="1" fillId="0" borderId="0" xfId="0" applyFont="1"/></cellXfs>"#,
+    r#"<cellStyles count="1"><cellStyle name="Normal" xfId="0" builtinId="0"/></cellStyles>"#,
+    r#"</styleSheet>"#,
+);
+
+const SHEET_START: &str = concat!(
+    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+    r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">"#,
+    r#"<sheetData>"#,
+);
+
+const SHEET_END: &str = "</sheetData></worksheet>";
+
+/// Spreadsheet dates count days from 1899-12-30.
+const SPREADSHEET_EPOCH: NaiveDate = match NaiveDate::from_ymd_opt(1899, 12, 30) {
+    Some(date) => date,
+    None => panic!("Should be a valid date."),
+};
+
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            // XML 1.0 doesn't allow the other control characters.
+            '\t' | '\n' | '\r' => escaped.push(character),
+            _ if character.is_control() => {}
+            _ => escaped.push(character),
+        }
+    }
+
+    escaped
+}
+
+/// Name of the spreadsheet column, 0 is `A`, 26 is `AA`.
+pub fn column_name(index: usize) -> String {
+    let mut name = vec![];
+    let mut index = index + 1;
+    while index > 0 {
+        let remainder = (index - 1) % 26;
+        name.push(b'A' + remainder as u8);
+        index = (index - 1) / 26;
+    }
+    name.reverse();
+
+    String::from_utf8(name).expect("Should be ASCII.")
+}
+
+/// Single sheet workbook, the first row is the bold header. Strings are
+/// inline so the sheet can be written before all of the rows are known.
+pub struct XlsxTableWriter {
+    zip: ZipStream,
+    row: usize,
+}
+
+impl XlsxTableWriter {
+    pub fn new(sheet_name: &str) -> Self {
+        // Sheet names are limited to 31 characters and some characters.
+        let sheet_name: String = sheet_name
+            .chars()
+            .filter(|character| !"[]:*?/\\".contains(*character))
+            .take(31)
+            .collect();
+
+        let mut zip = ZipStream::new();
+        zip.start_file("[Content_Types].xml");
+        zip.write(CONTENT_TYPES.as_bytes());
+        zip.start_file("_rels/.rels");
+        zip.write(ROOT_RELATIONSHIPS.as_bytes());
+        zip.start_file("xl/workbook.xml");
+        zip.write(
+            format!(
+                concat!(
+                    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+                    r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" "#,
+                    r#"xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">"#,
+                    r#"<sheets><sheet name="{}" sheetId="1" r:id="rId1"/></sheets></workbook>"#,
+                ),
+                escape_xml(&sheet_name)
+            )
+            .as_bytes(),
+        );
+        zip.start_file("xl/_rels/workbook.xml.rels");
+        zip.write(WORKBOOK_RELATIONSHIPS.as_bytes());
+        zip.start_file("xl/styles.xml");
+        zip.write(STYLES.as_bytes());
+        zip.start_file("xl/worksheets/sheet1.xml");
+        zip.write(SHEET_START.as_bytes());
+
+        XlsxTableWriter { zip, row: 0 }
+    }
+}
+
+impl TableWriter for XlsxTableWriter {
+    fn write_row(&mut self, cells: &[ExportCell], output: &mut Vec<u8>) {
+        self.row += 1;
+        let is_header = self.row == 1;
+
+        let mut xml = format!(r#"<row r="{}">"#, self.row);
+        for (index, cell) in cells.iter().enumerate() {
+            let reference = format!("{}{}", column_name(index), self.row);
+            match cell {
+                ExportCell::Text(text) => xml.push_str(&format!(
+                    r#"<c r="{}" t="inlineStr"{}><is><t xml:space="preserve">{}</t></is></c>"#,
+                    reference,
+                    if is_header { r#" s="2""# } else { "" },
+                    escape_xml(text)
+                )),
+                ExportCell::Number(number) if number.is_finite() => {
+                    xml.push_str(&format!(r#"<c r="{}"><v>{}</v></c>"#, reference, number))
+                }
+                ExportCell::Date(date) => xml.push_str(&format!(
+                    r#"<c r="{}" s="1"><v>{}</v></c>"#,
+                    reference,
+                    (*date - SPREADSHEET_EPOCH).num_days()
+                )),
+                ExportCell::Number(_) | ExportCell::Empty => {}
+            }
+        }
+        xml.push_str("</row>");
+
+        self.zip.write(xml.as_bytes());
+        output.append(&mut self.zip.take_output());
+    }
+
+    fn finish(&mut self, output: &mut Vec<u8>) {
+        self.zip.write(SHEET_END.as_bytes());
+        self.zip.finish();
+        output.append(&mut self.zip.take_output());
+    }
+}
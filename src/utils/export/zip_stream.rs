@@ -0,0 +1,191 @@
+use std::io::Write;
+
+use crc32fast::Hasher;
+use flate2::{write::DeflateEncoder, Compression};
+
+const LOCAL_FILE_HEADER_SIGNATURE: u32 = 0x04034b50;
+const DATA_DESCRIPTOR_SIGNATURE: u32 = 0x08074b50;
+const CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x02014b50;
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x06054b50;
+
+const VERSION: u16 = 20;
+/// Sizes and the checksum follow the data, names are UTF-8.
+const FLAGS: u16 = 0x0008 | 0x0800;
+const METHOD_DEFLATE: u16 = 8;
+/// 1980-01-01 00:00, the earliest date a zip entry can carry.
+const DOS_TIME: u16 = 0;
+const DOS_DATE: u16 = (1 << 5) | 1;
+
+struct ZipEntry {
+    name: String,
+    offset: u32,
+    crc: u32,
+    compressed_size: u32,
+    size: u32,
+}
+
+struct OpenEntry {
+    name: String,
+    offset: u32,
+    hasher: Hasher,
+    size: u32,
+    compressed_size: u32,
+    encoder: DeflateEncoder<Vec<u8>>,
+}
+
+/// Zip archive written front to back, the entries are compressed as they are
+/// written and the output can be sent out right away. Sizes are limited to 4 GB.
+pub struct ZipStream {
+    output: Vec<u8>,
+    written: u32,
+    entries: Vec<ZipEntry>,
+    open_entry: Option<OpenEntry>,
+}
+
+impl Default for ZipStream {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ZipStream {
+    pub fn new() -> Self {
+        ZipStream {
+            output: vec![],
+            written: 0,
+            entries: vec![],
+            open_entry: None,
+        }
+    }
+
+    fn emit(&mut self, bytes: &[u8]) {
+        self.output.extend_from_slice(bytes);
+        self.written = self.written.wrapping_add(bytes.len() as u32);
+    }
+
+    fn emit_u16(&mut self, value: u16) {
+        self.emit(&value.to_le_bytes());
+    }
+
+    fn emit_u32(&mut self, value: u32) {
+        self.emit(&value.to_le_bytes());
+    }
+
+    pub fn start_file(&mut self, name: &str) {
+        self.finish_file();
+
+        let offset = self.written;
+        self.emit_u32(LOCAL_FILE_HEADER_SIGNATURE);
+        self.emit_u16(VERSION);
+        self.emit_u16(FLAGS);
+        self.emit_u16(METHOD_DEFLATE);
+        self.emit_u16(DOS_TIME);
+        self.emit_u16(DOS_DATE);
+        // The checksum and the sizes are in the data descriptor.
+        self.emit_u32(0);
+        self.emit_u32(0);
+        self.emit_u32(0);
+        self.emit_u16(name.len() as u16);
+        self.emit_u16(0);
+        self.emit(name.as_bytes());
+
+        self.open_entry = Some(OpenEntry {
+            name: name.to_string(),
+            offset,
+            hasher: Hasher::new(),
+            size: 0,
+            compressed_size: 0,
+            encoder: DeflateEncoder::new(vec![], Compression::default()),
+        });
+    }
+
+    pub fn write(&mut self, data: &[u8]) {
+        let Some(mut entry) = self.open_entry.take() else {
+            return;
+        };
+
+        entry.hasher.update(data);
+        entry.size = entry.size.wrapping_add(data.len() as u32);
+        entry
+            .encoder
+            .write_all(data)
+            .expect("Writing to memory can't fail.");
+
+        let compressed = std::mem::take(entry.encoder.get_mut());
+        entry.compressed_size = entry.compressed_size.wrapping_add(compressed.len() as u32);
+        self.emit(&compressed);
+
+        self.open_entry = Some(entry);
+    }
+
+    fn finish_file(&mut self) {
+        let Some(entry) = self.open_entry.take() else {
+            return;
+        };
+
+        let compressed = entry
+            .encoder
+            .finish()
+            .expect("Writing to memory can't fail.");
+        let compressed_size = entry.compressed_size.wrapping_add(compressed.len() as u32);
+        self.emit(&compressed);
+
+        let crc = entry.hasher.finalize();
+        self.emit_u32(DATA_DESCRIPTOR_SIGNATURE);
+        self.emit_u32(crc);
+        self.emit_u32(compressed_size);
+        self.emit_u32(entry.size);
+
+        self.entries.push(ZipEntry {
+            name: entry.name,
+            offset: entry.offset,
+            crc,
+            compressed_size,
+            size: entry.size,
+        });
+    }
+
+    /// Writes the central directory, nothing can be added afterwards.
+    pub fn finish(&mut self) {
+        self.finish_file();
+
+        let directory_offset = self.written;
+        let entries = std::mem::take(&mut self.entries);
+        for entry in entries.iter() {
+            self.emit_u32(CENTRAL_DIRECTORY_SIGNATURE);
+            self.emit_u16(VERSION);
+            self.emit_u16(VERSION);
+            self.emit_u16(FLAGS);
+            self.emit_u16(METHOD_DEFLATE);
+            self.emit_u16(DOS_TIME);
+            self.emit_u16(DOS_DATE);
+            self.emit_u32(entry.crc);
+            self.emit_u32(entry.compressed_size);
+            self.emit_u32(entry.size);
+            self.emit_u16(entry.name.len() as u16);
+            // Extra field, comment, disk number, internal and external attributes.
+            self.emit_u16(0);
+            self.emit_u16(0);
+            self.emit_u16(0);
+            self.emit_u16(0);
+            self.emit_u32(0);
+            self.emit_u32(entry.offset);
+            self.emit(entry.name.as_bytes());
+        }
+        let directory_size = self.written.wrapping_sub(directory_offset);
+
+        self.emit_u32(END_OF_CENTRAL_DIRECTORY_SIGNATURE);
+        self.emit_u16(0);
+        self.emit_u16(0);
+        self.emit_u16(entries.len() as u16);
+        self.emit_u16(entries.len() as u16);
+        self.emit_u32(directory_size);
+        self.emit_u32(directory_offset);
+        self.emit_u16(0);
+    }
+
+    /// The bytes produced since the last call.
+    pub fn take_output(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.output)
+    }
+}
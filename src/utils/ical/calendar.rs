@@ -0,0 +1,95 @@
+use super::models::{CalendarEntry, CalendarEntryStatus, CalendarTime};
+
+const MAX_LINE_OCTETS: usize = 75;
+
+/// Escapes the TEXT value of a property (RFC 5545, section 3.3.11).
+pub fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(character),
+        }
+    }
+
+    escaped
+}
+
+/// Splits the content line into lines of at most 75 octets, the continuation
+/// lines start with a space (RFC 5545, section 3.1). Characters are never split.
+pub fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 2);
+    let mut line_octets = 0;
+    for character in line.chars() {
+        let octets = character.len_utf8();
+        if line_octets + octets > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            // The leading space counts towards the limit.
+            line_octets = 1;
+        }
+        folded.push(character);
+        line_octets += octets;
+    }
+    folded.push_str("\r\n");
+
+    folded
+}
+
+fn format_time(name: &str, time: &CalendarTime) -> String {
+    match time {
+        CalendarTime::Date(date) => format!("{};VALUE=DATE:{}", name, date.format("%Y%m%d")),
+        CalendarTime::DateTime(date_time) => {
+            format!("{}:{}", name, date_time.format("%Y%m%dT%H%M%S"))
+        }
+    }
+}
+
+fn format_status(status: &CalendarEntryStatus) -> &'static str {
+    match status {
+        CalendarEntryStatus::Tentative => "TENTATIVE",
+        CalendarEntryStatus::Confirmed => "CONFIRMED",
+        CalendarEntryStatus::Cancelled => "CANCELLED",
+    }
+}
+
+/// Renders the entries as an iCalendar (RFC 5545) object. The output only
+/// depends on the entries, so unchanged feeds stay byte for byte the same.
+pub fn render_calendar(name: &str, entries: &[CalendarEntry]) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Orchestrate//Calendar Feed//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(name)),
+    ];
+
+    for entry in entries {
+        let last_modified = entry.last_modified.format("%Y%m%dT%H%M%SZ");
+        // Every edit moves the sequence forward, so clients take the update.
+        let sequence = (entry.last_modified - entry.created_at)
+            .num_seconds()
+            .clamp(0, i32::MAX as i64);
+
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", entry.uid));
+        lines.push(format!("DTSTAMP:{}", last_modified));
+        lines.push(format!("LAST-MODIFIED:{}", last_modified));
+        lines.push(format!("SEQUENCE:{}", sequence));
+        lines.push(format_time("DTSTART", &entry.start));
+        lines.push(format_time("DTEND", &entry.end));
+        lines.push(format!("SUMMARY:{}", escape_text(&entry.summary)));
+        if let Some(description) = &entry.description {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        lines.push(format!("STATUS:{}", format_status(&entry.status)));
+        lines.push("END:VEVENT".to_string());
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold_line(line)).collect()
+}
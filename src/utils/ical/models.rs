@@ -0,0 +1,82 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+
+use crate::{
+    models::EventStatus,
+    repositories::calendar_feed::models::{CalendarEvent, CalendarTask},
+};
+
+/// Domain of the calendar entry UIDs, they have to be globally unique.
+pub const CALENDAR_UID_DOMAIN: &str = "orchestrate";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CalendarTime {
+    /// The whole day.
+    Date(NaiveDate),
+    /// The local time, shown the same in every time zone (floating time).
+    DateTime(NaiveDateTime),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CalendarEntryStatus {
+    Tentative,
+    Confirmed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone)]
+pub struct CalendarEntry {
+    pub uid: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub start: CalendarTime,
+    /// Exclusive.
+    pub end: CalendarTime,
+    pub status: CalendarEntryStatus,
+    /// In UTC, as well as `last_modified`.
+    pub created_at: NaiveDateTime,
+    pub last_modified: NaiveDateTime,
+}
+
+fn entry_status(status: &EventStatus) -> CalendarEntryStatus {
+    match status {
+        EventStatus::Draft => CalendarEntryStatus::Tentative,
+        EventStatus::Published | EventStatus::Completed => CalendarEntryStatus::Confirmed,
+        EventStatus::Cancelled => CalendarEntryStatus::Cancelled,
+    }
+}
+
+impl From<CalendarEvent> for CalendarEntry {
+    fn from(event: CalendarEvent) -> Self {
+        let description = match event.cancellation_reason {
+            Some(reason) => Some(format!("Cancelled: {}", reason)),
+            None => event.description,
+        };
+
+        CalendarEntry {
+            uid: format!("event-{}@{}", event.id, CALENDAR_UID_DOMAIN),
+            summary: event.name,
+            description,
+            start: CalendarTime::Date(event.start_date),
+            end: CalendarTime::Date(event.end_date + Duration::days(1)),
+            status: entry_status(&event.status),
+            created_at: event.created_at,
+            last_modified: event.edited_at,
+        }
+    }
+}
+
+impl From<CalendarTask> for CalendarEntry {
+    fn from(task: CalendarTask) -> Self {
+        CalendarEntry {
+            uid: format!("task-{}@{}", task.id, CALENDAR_UID_DOMAIN),
+            summary: format!("{} ({})", task.title, task.event_name),
+            description: task.description,
+            // The due date is a point in time, calendars need a duration.
+            start: CalendarTime::DateTime(task.due_at - Duration::hours(1)),
+            end: CalendarTime::DateTime(task.due_at),
+            status: entry_status(&task.event_status),
+            created_at: task.created_at,
+            last_modified: task.edited_at,
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use std::fmt;
+
+use chrono::NaiveDate;
+
+use crate::models::{EmployeeLevel, EmploymentContract, Gender};
+
+/// Upper bound of rows in a single import, larger crews are split into more files.
+pub const MAX_IMPORT_ROWS: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportFormat {
+    Csv,
+    Xlsx,
+}
+
+impl ImportFormat {
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        let extension = file_name.rsplit_once('.')?.1.to_lowercase();
+        match extension.as_str() {
+            "csv" => Some(ImportFormat::Csv),
+            "xlsx" => Some(ImportFormat::Xlsx),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImportColumn {
+    Name,
+    Email,
+    Birth,
+    Gender,
+    EmploymentType,
+    HourlyWage,
+    StartDate,
+    EndDate,
+    ManagerEmail,
+    Level,
+    Description,
+}
+
+impl ImportColumn {
+    pub const REQUIRED: [ImportColumn; 7] = [
+        ImportColumn::Name,
+        ImportColumn::Email,
+        ImportColumn::Birth,
+        ImportColumn::Gender,
+        ImportColumn::EmploymentType,
+        ImportColumn::HourlyWage,
+        ImportColumn::StartDate,
+    ];
+
+    /// Matches the header of a column, case, spaces and underscores don't matter.
+    pub fn from_header(header: &str) -> Option<Self> {
+        let normalized = header
+            .trim()
+            .to_lowercase()
+            .replace(['_', '-'], " ")
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+
+        match normalized.as_str() {
+            "name" | "full name" => Some(ImportColumn::Name),
+            "email" | "e mail" => Some(ImportColumn::Email),
+            "birth" | "birth date" | "date of birth" => Some(ImportColumn::Birth),
+            "gender" => Some(ImportColumn::Gender),
+            "contract" | "contract type" | "employment type" => Some(ImportColumn::EmploymentType),
+            "wage" | "hourly wage" => Some(ImportColumn::HourlyWage),
+            "start" | "start date" => Some(ImportColumn::StartDate),
+            "end" | "end date" => Some(ImportColumn::EndDate),
+            "manager" | "manager email" => Some(ImportColumn::ManagerEmail),
+            "level" | "employee level" => Some(ImportColumn::Level),
+            "description" | "job description" => Some(ImportColumn::Description),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ImportColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportColumn::Name => write!(f, "Name"),
+            ImportColumn::Email => write!(f, "Email"),
+            ImportColumn::Birth => write!(f, "Birth Date"),
+            ImportColumn::Gender => write!(f, "Gender"),
+            ImportColumn::EmploymentType => write!(f, "Contract Type"),
+            ImportColumn::HourlyWage => write!(f, "Hourly Wage"),
+            ImportColumn::StartDate => write!(f, "Start Date"),
+            ImportColumn::EndDate => write!(f, "End Date"),
+            ImportColumn::ManagerEmail => write!(f, "Manager Email"),
+            ImportColumn::Level => write!(f, "Level"),
+            ImportColumn::Description => write!(f, "Description"),
+        }
+    }
+}
+
+/// A row of the uploaded file as text, `line` is the line (CSV) or the row
+/// (XLSX) the user sees in their spreadsheet.
+#[derive(Debug, Clone, Default)]
+pub struct ImportRecord {
+    pub line: usize,
+    pub name: String,
+    pub email: String,
+    pub birth: String,
+    pub gender: String,
+    pub employment_type: String,
+    pub hourly_wage: String,
+    pub start_date: String,
+    pub end_date: String,
+    pub manager_email: String,
+    pub level: String,
+    pub description: String,
+}
+
+impl ImportRecord {
+    pub fn set(&mut self, column: ImportColumn, value: String) {
+        let field = match column {
+            ImportColumn::Name => &mut self.name,
+            ImportColumn::Email => &mut self.email,
+            ImportColumn::Birth => &mut self.birth,
+            ImportColumn::Gender => &mut self.gender,
+            ImportColumn::EmploymentType => &mut self.employment_type,
+            ImportColumn::HourlyWage => &mut self.hourly_wage,
+            ImportColumn::StartDate => &mut self.start_date,
+            ImportColumn::EndDate => &mut self.end_date,
+            ImportColumn::ManagerEmail => &mut self.manager_email,
+            ImportColumn::Level => &mut self.level,
+            ImportColumn::Description => &mut self.description,
+        };
+        *field = value.trim().to_string();
+    }
+}
+
+/// A row whose every value has the right type, it still has to be checked
+/// against the rules of the handlers and the database.
+#[derive(Debug, Clone)]
+pub struct ImportRow {
+    pub line: usize,
+    pub name: String,
+    pub email: String,
+    pub birth: NaiveDate,
+    pub gender: Gender,
+    pub employment_type: EmploymentContract,
+    pub hourly_wage: f64,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub manager_email: Option<String>,
+    pub level: EmployeeLevel,
+    pub description: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum ImportError {
+    Csv(csv::Error),
+    Xlsx(calamine::XlsxError),
+    EmptyFile,
+    MissingColumns(Vec<ImportColumn>),
+    TooManyRows,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Csv(error) => write!(f, "Invalid CSV file: {}", error),
+            ImportError::Xlsx(error) => write!(f, "Invalid XLSX file: {}", error),
+            ImportError::EmptyFile => write!(f, "The file has no rows."),
+            ImportError::MissingColumns(columns) => write!(
+                f,
+                "Missing columns: {}.",
+                columns
+                    .iter()
+                    .map(|column| column.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            ImportError::TooManyRows => write!(
+                f,
+                "The file has more than {} rows, split it into more files.",
+                MAX_IMPORT_ROWS
+            ),
+        }
+    }
+}
+
+impl From<csv::Error> for ImportError {
+    fn from(error: csv::Error) -> Self {
+        ImportError::Csv(error)
+    }
+}
+
+impl From<calamine::XlsxError> for ImportError {
+    fn from(error: calamine::XlsxError) -> Self {
+        ImportError::Xlsx(error)
+    }
+}
@@ -0,0 +1,257 @@
+use std::io::Cursor;
+
+use calamine::{Data, Reader, Xlsx};
+use chrono::NaiveDate;
+
+use crate::models::{EmployeeLevel, EmploymentContract, Gender};
+
+use super::models::{
+    ImportColumn, ImportError, ImportFormat, ImportRecord, ImportRow, MAX_IMPORT_ROWS,
+};
+
+/// Date formats accepted in the import, ISO and the Czech `15.8.1969`.
+const DATE_FORMATS: [&str; 2] = ["%Y-%m-%d", "%d.%m.%Y"];
+
+/// Employment without an end date is open-ended, same as in the database.
+pub const OPEN_END_DATE: NaiveDate = match NaiveDate::from_ymd_opt(9999, 12, 31) {
+    Some(date) => date,
+    None => panic!("Should be a valid date."),
+};
+
+/// Reads the rows of the uploaded file, the first row holds the column headers.
+pub fn read_records(format: ImportFormat, bytes: &[u8]) -> Result<Vec<ImportRecord>, ImportError> {
+    let rows = match format {
+        ImportFormat::Csv => read_csv_rows(bytes)?,
+        ImportFormat::Xlsx => read_xlsx_rows(bytes)?,
+    };
+
+    let mut rows = rows.into_iter();
+    let (_, header) = rows.next().ok_or(ImportError::EmptyFile)?;
+    let columns: Vec<Option<ImportColumn>> = header
+        .iter()
+        .map(|title| ImportColumn::from_header(title))
+        .collect();
+
+    let missing: Vec<ImportColumn> = ImportColumn::REQUIRED
+        .into_iter()
+        .filter(|required| !columns.contains(&Some(*required)))
+        .collect();
+    if !missing.is_empty() {
+        return Err(ImportError::MissingColumns(missing));
+    }
+
+    let mut records = vec![];
+    for (line, values) in rows {
+        if values.iter().all(|value| value.trim().is_empty()) {
+            continue;
+        }
+        if records.len() == MAX_IMPORT_ROWS {
+            return Err(ImportError::TooManyRows);
+        }
+
+        let mut record = ImportRecord {
+            line,
+            ..Default::default()
+        };
+        for (column, value) in columns.iter().zip(values) {
+            if let Some(column) = column {
+                record.set(*column, value);
+            }
+        }
+        records.push(record);
+    }
+
+    if records.is_empty() {
+        return Err(ImportError::EmptyFile);
+    }
+
+    Ok(records)
+}
+
+/// Spreadsheets set to the Czech locale export CSV separated by semicolons.
+fn detect_delimiter(bytes: &[u8]) -> u8 {
+    let header = bytes
+        .split(|byte| *byte == b'\n')
+        .next()
+        .unwrap_or_default();
+    let semicolons = header.iter().filter(|byte| **byte == b';').count();
+    let commas = header.iter().filter(|byte| **byte == b',').count();
+
+    if semicolons > commas {
+        b';'
+    } else {
+        b','
+    }
+}
+
+fn read_csv_rows(bytes: &[u8]) -> Result<Vec<(usize, Vec<String>)>, ImportError> {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(detect_delimiter(bytes))
+        .from_reader(bytes);
+
+    let mut rows = vec![];
+    for result in reader.records() {
+        let record = result?;
+        let offset = record
+            .position()
+            .map(|position| position.byte() as usize)
+            .unwrap_or_default();
+        // With CRLF line ends the offset points at the `\n` of the previous line.
+        let line = bytes[..(offset + 1).min(bytes.len())]
+            .iter()
+            .filter(|byte| **byte == b'\n')
+            .count()
+            + 1;
+        rows.push((line, record.iter().map(str::to_string).collect()));
+    }
+
+    Ok(rows)
+}
+
+fn read_xlsx_rows(bytes: &[u8]) -> Result<Vec<(usize, Vec<String>)>, ImportError> {
+    let mut workbook = Xlsx::new(Cursor::new(bytes))?;
+    let range = match workbook.worksheet_range_at(0) {
+        Some(range) => range?,
+        None => return Err(ImportError::EmptyFile),
+    };
+
+    let first_row = range
+        .start()
+        .map(|(row, _)| row as usize)
+        .unwrap_or_default();
+    let rows = range
+        .rows()
+        .enumerate()
+        .map(|(index, cells)| {
+            (
+                first_row + index + 1,
+                cells.iter().map(cell_to_string).collect(),
+            )
+        })
+        .collect();
+
+    Ok(rows)
+}
+
+fn cell_to_string(cell: &Data) -> String {
+    match cell {
+        Data::String(value) | Data::DateTimeIso(value) | Data::DurationIso(value) => value.clone(),
+        Data::Int(value) => value.to_string(),
+        Data::Float(value) if value.fract() == 0.0 && value.abs() < 1e15 => {
+            (*value as i64).to_string()
+        }
+        Data::Float(value) => value.to_string(),
+        Data::Bool(value) => value.to_string(),
+        Data::DateTime(value) => value
+            .as_datetime()
+            .map(|datetime| datetime.date().format("%Y-%m-%d").to_string())
+            .unwrap_or_default(),
+        Data::Error(_) | Data::Empty => String::new(),
+    }
+}
+
+fn parse_date(value: &str, column: ImportColumn) -> Result<NaiveDate, String> {
+    DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+        .ok_or(format!(
+            "{} '{}' is not a date (YYYY-MM-DD or DD.MM.YYYY).",
+            column, value
+        ))
+}
+
+fn parse_gender(value: &str) -> Result<Gender, String> {
+    match value.to_lowercase().as_str() {
+        "male" => Ok(Gender::Male),
+        "female" => Ok(Gender::Female),
+        "other" => Ok(Gender::Other),
+        _ => Err(format!(
+            "Gender '{}' is not one of Male, Female, Other.",
+            value
+        )),
+    }
+}
+
+fn parse_employment_type(value: &str) -> Result<EmploymentContract, String> {
+    match value.to_lowercase().as_str() {
+        "dpp" => Ok(EmploymentContract::Dpp),
+        "dpc" => Ok(EmploymentContract::Dpc),
+        "hpp" => Ok(EmploymentContract::Hpp),
+        _ => Err(format!(
+            "Contract type '{}' is not one of DPP, DPC, HPP.",
+            value
+        )),
+    }
+}
+
+fn parse_level(value: &str) -> Result<EmployeeLevel, String> {
+    let normalized = value.to_lowercase().replace('_', " ");
+    match normalized.as_str() {
+        "" | "basic" => Ok(EmployeeLevel::Basic),
+        "manager" => Ok(EmployeeLevel::Manager),
+        "company administrator" => Ok(EmployeeLevel::CompanyAdministrator),
+        _ => Err(format!(
+            "Level '{}' is not one of Basic, Manager, Company Administrator.",
+            value
+        )),
+    }
+}
+
+fn parse_wage(value: &str) -> Result<f64, String> {
+    value
+        .replace(' ', "")
+        .replace(',', ".")
+        .parse::<f64>()
+        .ok()
+        .filter(|wage| wage.is_finite())
+        .ok_or(format!("Hourly wage '{}' is not a number.", value))
+}
+
+fn optional(value: &str) -> Option<String> {
+    if value.is_empty() {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+/// Converts the values of the row, all of the invalid values are reported at once.
+pub fn parse_record(record: &ImportRecord) -> Result<ImportRow, Vec<String>> {
+    let mut errors = vec![];
+
+    let birth = parse_date(&record.birth, ImportColumn::Birth).map_err(|e| errors.push(e));
+    let gender = parse_gender(&record.gender).map_err(|e| errors.push(e));
+    let employment_type =
+        parse_employment_type(&record.employment_type).map_err(|e| errors.push(e));
+    let hourly_wage = parse_wage(&record.hourly_wage).map_err(|e| errors.push(e));
+    let start_date =
+        parse_date(&record.start_date, ImportColumn::StartDate).map_err(|e| errors.push(e));
+    let end_date = if record.end_date.is_empty() {
+        Ok(OPEN_END_DATE)
+    } else {
+        parse_date(&record.end_date, ImportColumn::EndDate).map_err(|e| errors.push(e))
+    };
+    let level = parse_level(&record.level).map_err(|e| errors.push(e));
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(ImportRow {
+        line: record.line,
+        name: record.name.clone(),
+        email: record.email.clone(),
+        birth: birth.expect("Should be valid."),
+        gender: gender.expect("Should be valid."),
+        employment_type: employment_type.expect("Should be valid."),
+        hourly_wage: hourly_wage.expect("Should be valid."),
+        start_date: start_date.expect("Should be valid."),
+        end_date: end_date.expect("Should be valid."),
+        manager_email: optional(&record.manager_email),
+        level: level.expect("Should be valid."),
+        description: optional(&record.description),
+    })
+}
@@ -0,0 +1,113 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+
+use crate::{common::DbResult, repositories::job::job_repo::JobRepository};
+
+/// How often the scheduler looks for due jobs.
+pub const SCHEDULER_TICK: Duration = Duration::from_secs(15);
+/// How long a claimed job is reserved for its run. A run taking longer is
+/// considered interrupted and the job may be claimed again.
+pub const JOB_LEASE: Duration = Duration::from_secs(15 * 60);
+/// The delay before the first retry, doubled with every further failure.
+pub const RETRY_BASE_DELAY: Duration = Duration::from_secs(60);
+
+/// Periodic work run by the scheduler.
+#[async_trait]
+pub trait Job: Send + Sync {
+    /// Identifies the job across the application instances.
+    fn name(&self) -> &'static str;
+    fn period(&self) -> Duration;
+    /// Returns a summary of the work done, or why it failed.
+    async fn run(&self) -> Result<String, String>;
+}
+
+/// The delay before retrying a job whose run failed, never longer than
+/// the job's period.
+pub fn retry_delay(attempt: i32, period: Duration) -> Duration {
+    let exponent = attempt.clamp(1, 16) as u32 - 1;
+
+    RETRY_BASE_DELAY
+        .saturating_mul(2u32.pow(exponent))
+        .min(period)
+}
+
+/// Runs the registered jobs when they are due. The job records are kept in
+/// the database, so any number of application instances can share them and
+/// every run happens in a single instance only.
+pub struct JobScheduler {
+    job_repo: JobRepository,
+    jobs: Vec<Arc<dyn Job>>,
+}
+
+impl JobScheduler {
+    pub fn new(job_repo: JobRepository) -> Self {
+        Self {
+            job_repo,
+            jobs: vec![],
+        }
+    }
+
+    pub fn with_job(mut self, job: impl Job + 'static) -> Self {
+        self.jobs.push(Arc::new(job));
+        self
+    }
+
+    pub async fn register(&self) -> DbResult<()> {
+        for job in self.jobs.iter() {
+            self.job_repo.register(job.name(), job.period()).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Runs the due jobs one after another. Returns the number of runs.
+    pub async fn run_due(&self) -> DbResult<usize> {
+        let names: Vec<String> = self.jobs.iter().map(|job| job.name().to_string()).collect();
+
+        let mut runs = 0;
+        while let Some(run) = self.job_repo.claim_due(&names, JOB_LEASE).await? {
+            let job = self
+                .jobs
+                .iter()
+                .find(|job| job.name() == run.job_name)
+                .expect("Only the registered jobs are claimed.");
+
+            match job.run().await {
+                Ok(output) => {
+                    self.job_repo.succeed(run.id, &output).await?;
+                }
+                Err(error) => {
+                    log::warn!(
+                        "Job {} failed (attempt {}): {}",
+                        run.job_name,
+                        run.attempt,
+                        error
+                    );
+                    self.job_repo
+                        .fail(run.id, &error, retry_delay(run.attempt, job.period()))
+                        .await?;
+                }
+            }
+            runs += 1;
+        }
+
+        Ok(runs)
+    }
+
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            if let Err(error) = self.register().await {
+                log::error!("Registering the background jobs failed: {}", error);
+            }
+
+            let mut interval = tokio::time::interval(SCHEDULER_TICK);
+            loop {
+                interval.tick().await;
+                if let Err(error) = self.run_due().await {
+                    log::error!("Background job round failed: {}", error);
+                }
+            }
+        })
+    }
+}
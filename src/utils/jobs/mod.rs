@@ -0,0 +1,4 @@
+pub mod job_scheduler;
+pub mod run_history;
+pub mod timesheet_consistency;
+pub mod trash_purge;
@@ -0,0 +1,36 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::repositories::job::job_repo::JobRepository;
+
+use super::job_scheduler::Job;
+
+/// How long the finished job runs are kept.
+pub const JOB_RUN_RETENTION_DAYS: i32 = 30;
+
+/// Removes the records of old job runs.
+pub struct RunHistoryCleanupJob {
+    pub job_repo: JobRepository,
+}
+
+#[async_trait]
+impl Job for RunHistoryCleanupJob {
+    fn name(&self) -> &'static str {
+        "job_run_cleanup"
+    }
+
+    fn period(&self) -> Duration {
+        Duration::from_secs(24 * 60 * 60)
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let deleted = self
+            .job_repo
+            .delete_runs_older_than(JOB_RUN_RETENTION_DAYS)
+            .await
+            .map_err(|error| error.to_string())?;
+
+        Ok(format!("Removed {} job runs.", deleted))
+    }
+}
@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::repositories::timesheet_consistency::timesheet_consistency_repo::TimesheetConsistencyRepository;
+
+use super::job_scheduler::Job;
+
+/// Recalculates the totals of the timesheets which drifted from their
+/// workdays. The other inconsistencies are only counted, an administrator
+/// repairs them from the admin panel.
+pub struct TimesheetConsistencyJob {
+    pub consistency_repo: TimesheetConsistencyRepository,
+}
+
+#[async_trait]
+impl Job for TimesheetConsistencyJob {
+    fn name(&self) -> &'static str {
+        "timesheet_consistency"
+    }
+
+    fn period(&self) -> Duration {
+        Duration::from_secs(24 * 60 * 60)
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let repaired = self
+            .consistency_repo
+            .repair_all_totals()
+            .await
+            .map_err(|error| error.to_string())?;
+        let report = self
+            .consistency_repo
+            .check()
+            .await
+            .map_err(|error| error.to_string())?;
+
+        Ok(format!(
+            "Recalculated {} timesheet totals, {} timesheets have workdays outside of their dates, {} timesheets don't match their event.",
+            repaired,
+            report.workdays_out_of_range.len(),
+            report.range_mismatches.len()
+        ))
+    }
+}
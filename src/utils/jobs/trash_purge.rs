@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::repositories::trash::trash_repo::TrashRepository;
+
+use super::job_scheduler::Job;
+
+/// How many days the deleted records stay in the trash.
+pub const TRASH_RETENTION_DAYS: i32 = 30;
+
+/// Deletes the records which stayed in the trash longer than the retention
+/// period for good.
+pub struct TrashPurgeJob {
+    pub trash_repo: TrashRepository,
+}
+
+#[async_trait]
+impl Job for TrashPurgeJob {
+    fn name(&self) -> &'static str {
+        "trash_purge"
+    }
+
+    fn period(&self) -> Duration {
+        Duration::from_secs(24 * 60 * 60)
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let result = self
+            .trash_repo
+            .purge_older_than(TRASH_RETENTION_DAYS)
+            .await
+            .map_err(|error| error.to_string())?;
+
+        Ok(format!(
+            "Purged {} deletions, {} are still referenced by kept records.",
+            result.purged, result.kept
+        ))
+    }
+}
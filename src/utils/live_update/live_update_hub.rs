@@ -0,0 +1,105 @@
+use std::time::Duration;
+
+use actix_web::web::Bytes;
+use futures::{stream, Stream};
+use sqlx::postgres::{PgListener, PgPool};
+use tokio::sync::broadcast::{self, error::RecvError};
+use uuid::Uuid;
+
+use super::models::{EventChange, EVENT_CHANGE_CHANNEL, EVENT_CHANGE_TOPICS};
+
+const HUB_CAPACITY: usize = 1024;
+pub const KEEP_ALIVE_PERIOD: Duration = Duration::from_secs(15);
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Fans out the changes of events to the open SSE connections of this
+/// application instance. The changes come from Postgres, so the instances
+/// see each other's changes as well.
+#[derive(Clone)]
+pub struct LiveUpdateHub {
+    sender: broadcast::Sender<EventChange>,
+}
+
+impl Default for LiveUpdateHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn format_sse_event(topic: &str) -> String {
+    format!("event: {}\ndata: {}\n\n", topic, topic)
+}
+
+impl LiveUpdateHub {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(HUB_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn publish(&self, change: EventChange) {
+        // Nobody watching the event is fine.
+        let _ = self.sender.send(change);
+    }
+
+    /// SSE stream of the topics changed within the event.
+    pub fn subscribe(&self, event_id: Uuid) -> impl Stream<Item = Result<Bytes, actix_web::Error>> {
+        let receiver = self.sender.subscribe();
+        let keep_alive = tokio::time::interval_at(
+            tokio::time::Instant::now() + KEEP_ALIVE_PERIOD,
+            KEEP_ALIVE_PERIOD,
+        );
+
+        stream::unfold(
+            (receiver, keep_alive),
+            move |(mut receiver, mut keep_alive)| async move {
+                loop {
+                    let message = tokio::select! {
+                        change = receiver.recv() => match change {
+                            Ok(change) if change.event_id == event_id => {
+                                Bytes::from(format_sse_event(&change.topic))
+                            }
+                            Ok(_) => continue,
+                            // Some changes were missed, so everything gets refreshed.
+                            Err(RecvError::Lagged(_)) => Bytes::from(
+                                EVENT_CHANGE_TOPICS
+                                    .iter()
+                                    .map(|topic| format_sse_event(topic))
+                                    .collect::<String>(),
+                            ),
+                            Err(RecvError::Closed) => return None,
+                        },
+                        _ = keep_alive.tick() => Bytes::from_static(b": keep-alive\n\n"),
+                    };
+
+                    return Some((Ok(message), (receiver, keep_alive)));
+                }
+            },
+        )
+    }
+
+    /// Forwards the notifications of the database until the listener fails.
+    pub async fn listen(&self, pool: &PgPool) -> Result<(), sqlx::Error> {
+        let mut listener = PgListener::connect_with(pool).await?;
+        listener.listen(EVENT_CHANGE_CHANNEL).await?;
+
+        loop {
+            let notification = listener.recv().await?;
+            match serde_json::from_str::<EventChange>(notification.payload()) {
+                Ok(change) => self.publish(change),
+                Err(error) => log::warn!("Malformed event change: {}", error),
+            }
+        }
+    }
+
+    pub fn spawn_listener(&self, pool: PgPool) -> tokio::task::JoinHandle<()> {
+        let hub = self.clone();
+        tokio::spawn(async move {
+            loop {
+                if let Err(error) = hub.listen(&pool).await {
+                    log::error!("Listening for event changes failed: {}", error);
+                }
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        })
+    }
+}
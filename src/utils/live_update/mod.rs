@@ -0,0 +1,2 @@
+pub mod live_update_hub;
+pub mod models;
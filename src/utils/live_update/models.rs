@@ -0,0 +1,20 @@
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// Channel the database triggers publish the changes on.
+pub const EVENT_CHANGE_CHANNEL: &str = "event_change";
+
+/// Everything the open pages of an event may refresh on.
+pub const EVENT_CHANGE_TOPICS: [&str; 5] = [
+    "comments",
+    "task-comments",
+    "tasks",
+    "assigned-staff",
+    "staff",
+];
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct EventChange {
+    pub event_id: Uuid,
+    pub topic: String,
+}
@@ -0,0 +1,107 @@
+use std::fmt;
+
+use lettre::{
+    address::AddressError,
+    message::{Mailbox, MultiPart},
+    transport::smtp::authentication::Credentials,
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use serde::Deserialize;
+
+use crate::repositories::mail_outbox::models::OutboxMail;
+
+/// SMTP server the mail is delivered through, read from `SMTP_*` and
+/// `MAIL_FROM` environment variables.
+#[derive(Deserialize, Debug, Clone)]
+pub struct MailConfig {
+    pub smtp_host: String,
+    pub smtp_port: u16,
+    pub smtp_username: Option<String>,
+    pub smtp_password: Option<String>,
+    #[serde(default)]
+    pub smtp_tls: bool,
+    pub mail_from: String,
+}
+
+impl MailConfig {
+    pub fn from_env() -> Result<Self, envy::Error> {
+        envy::from_env::<MailConfig>()
+    }
+}
+
+#[derive(Debug)]
+pub enum MailError {
+    Address(AddressError),
+    Message(lettre::error::Error),
+    Transport(lettre::transport::smtp::Error),
+}
+
+impl fmt::Display for MailError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MailError::Address(error) => write!(f, "Invalid address: {}", error),
+            MailError::Message(error) => write!(f, "Invalid message: {}", error),
+            MailError::Transport(error) => write!(f, "SMTP error: {}", error),
+        }
+    }
+}
+
+impl From<AddressError> for MailError {
+    fn from(error: AddressError) -> Self {
+        MailError::Address(error)
+    }
+}
+
+impl From<lettre::error::Error> for MailError {
+    fn from(error: lettre::error::Error) -> Self {
+        MailError::Message(error)
+    }
+}
+
+impl From<lettre::transport::smtp::Error> for MailError {
+    fn from(error: lettre::transport::smtp::Error) -> Self {
+        MailError::Transport(error)
+    }
+}
+
+#[derive(Clone)]
+pub struct MailSender {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl MailSender {
+    pub fn new(config: &MailConfig) -> Result<Self, MailError> {
+        let mut builder = if config.smtp_tls {
+            AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.smtp_host)?
+        } else {
+            // Local sinks (and relays on a private network) talk plain SMTP.
+            AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.smtp_host)
+        }
+        .port(config.smtp_port);
+
+        if let (Some(username), Some(password)) = (&config.smtp_username, &config.smtp_password) {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from: config.mail_from.parse()?,
+        })
+    }
+
+    pub async fn send(&self, mail: &OutboxMail) -> Result<(), MailError> {
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(mail.recipient.parse()?)
+            .subject(mail.subject.clone())
+            .multipart(MultiPart::alternative_plain_html(
+                mail.body_text.clone(),
+                mail.body_html.clone(),
+            ))?;
+
+        self.transport.send(message).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use crate::{
+    common::DbResult, repositories::mail_outbox::mail_outbox_repo::MailOutboxRepository,
+    utils::jobs::job_scheduler::Job,
+};
+
+use super::mail_sender::MailSender;
+
+/// How many mails are sent at most in one round.
+pub const MAIL_BATCH_SIZE: i64 = 50;
+pub const MAIL_WORKER_PERIOD: Duration = Duration::from_secs(30);
+
+/// Bundles the due digests and tries to send every due mail once.
+/// Returns the number of mails which were sent.
+pub async fn deliver_due_mail(
+    mail_outbox_repo: &MailOutboxRepository,
+    sender: &MailSender,
+) -> DbResult<usize> {
+    mail_outbox_repo.bundle_due_digests().await?;
+
+    let mails = mail_outbox_repo.read_due(MAIL_BATCH_SIZE).await?;
+    let mut sent = 0;
+    for mail in mails {
+        match sender.send(&mail).await {
+            Ok(()) => {
+                mail_outbox_repo.mark_sent(mail.id).await?;
+                sent += 1;
+            }
+            Err(error) => {
+                log::warn!("Failed to send mail {}: {}", mail.id, error);
+                mail_outbox_repo
+                    .mark_failed_attempt(mail.id, &error.to_string())
+                    .await?;
+            }
+        }
+    }
+
+    Ok(sent)
+}
+
+/// Sends the mail outbox.
+pub struct MailDeliveryJob {
+    pub mail_outbox_repo: MailOutboxRepository,
+    pub sender: MailSender,
+}
+
+#[async_trait]
+impl Job for MailDeliveryJob {
+    fn name(&self) -> &'static str {
+        "mail_delivery"
+    }
+
+    fn period(&self) -> Duration {
+        MAIL_WORKER_PERIOD
+    }
+
+    async fn run(&self) -> Result<String, String> {
+        let sent = deliver_due_mail(&self.mail_outbox_repo, &self.sender)
+            .await
+            .map_err(|error| error.to_string())?;
+
+        Ok(format!("Sent {} mails.", sent))
+    }
+}
@@ -0,0 +1,2 @@
+pub mod mail_sender;
+pub mod mail_worker;
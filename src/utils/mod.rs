@@ -1,3 +1,14 @@
+pub mod budget;
+pub mod comment_format;
+pub mod deadline;
+pub mod export;
 pub mod format_check;
+pub mod ical;
 pub mod image_storage;
+pub mod import;
+pub mod jobs;
+pub mod live_update;
+pub mod mail;
+pub mod payroll;
+pub mod task_graph;
 pub mod wage_calc;
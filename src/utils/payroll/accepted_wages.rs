@@ -0,0 +1,120 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use futures::TryStreamExt;
+use uuid::Uuid;
+
+use crate::{
+    common::DbResult,
+    models::{ApprovalStatus, EmploymentContract},
+    repositories::timesheet::{models::TimesheetExport, timesheet_repo::TimesheetRepository},
+    utils::wage_calc::{calculate_wage::calculate_timesheet_wage, models::DetailedWage},
+};
+
+/// The accepted work of an employee within a company.
+#[derive(Debug)]
+pub struct EmployeeWages {
+    pub user_id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub employment_type: EmploymentContract,
+    pub hourly_wage: f32,
+    /// Wages summed per month, keyed by the first day of the month.
+    pub months: BTreeMap<NaiveDate, DetailedWage>,
+    /// Timesheets whose wage couldn't be computed.
+    pub errors: Vec<String>,
+}
+
+impl EmployeeWages {
+    fn new(timesheet: &TimesheetExport) -> Self {
+        Self {
+            user_id: timesheet.user_id,
+            name: timesheet.user_name.clone(),
+            email: timesheet.user_email.clone(),
+            employment_type: timesheet.employment_type.clone(),
+            hourly_wage: timesheet.hourly_wage as f32,
+            months: BTreeMap::new(),
+            errors: vec![],
+        }
+    }
+
+    pub fn total(&self) -> DetailedWage {
+        let mut total = DetailedWage::default();
+        for wage in self.months.values() {
+            total += wage;
+        }
+
+        total
+    }
+}
+
+/// Recomputes the wages of the company's accepted timesheets and sums them
+/// per employee and month. Only the months between `date_from` and `date_to`
+/// are kept, the employees are ordered by name.
+pub async fn collect_accepted_wages(
+    timesheet_repo: &TimesheetRepository,
+    company_id: Uuid,
+    date_from: NaiveDate,
+    date_to: NaiveDate,
+    pink_paper_signed: bool,
+) -> DbResult<Vec<EmployeeWages>> {
+    let timesheets: Vec<TimesheetExport> = timesheet_repo
+        .stream_all_for_company(company_id, date_from, date_to)
+        .try_filter(|timesheet| {
+            futures::future::ready(timesheet.approval_status == ApprovalStatus::Accepted)
+        })
+        .try_collect()
+        .await?;
+
+    let mut employees: Vec<EmployeeWages> = vec![];
+    for timesheet in timesheets {
+        let related = timesheet_repo
+            .read_all_with_date_from_to_per_employment_extended_db(
+                timesheet.user_id,
+                company_id,
+                timesheet.start_date,
+                timesheet.end_date,
+            )
+            .await?;
+        let wage =
+            calculate_timesheet_wage(pink_paper_signed, &related, timesheet.id).and_then(|wage| {
+                match wage.error_option {
+                    Some(error) => Err(error),
+                    None => Ok(wage),
+                }
+            });
+
+        let position = employees
+            .iter()
+            .position(|employee| employee.user_id == timesheet.user_id);
+        let employee = match position {
+            Some(position) => &mut employees[position],
+            None => {
+                employees.push(EmployeeWages::new(&timesheet));
+                employees.last_mut().expect("Should be some.")
+            }
+        };
+
+        match wage {
+            Err(error) => employee
+                .errors
+                .push(format!("{}: {}", timesheet.event_name, error)),
+            Ok(wage) => {
+                for (year_month, monthly_wage) in wage.month_to_detailed_wage.iter() {
+                    let month =
+                        NaiveDate::from_ymd_opt(year_month.year as i32, year_month.month as u32, 1)
+                            .expect("Should be a valid month.");
+                    if month > date_to
+                        || month.checked_add_months(chrono::Months::new(1)) <= Some(date_from)
+                    {
+                        continue;
+                    }
+
+                    *employee.months.entry(month).or_default() += monthly_wage;
+                }
+            }
+        }
+    }
+
+    Ok(employees)
+}
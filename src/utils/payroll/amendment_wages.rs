@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::{
+    common::DbResult,
+    models::AmendmentStatus,
+    repositories::{
+        timesheet::timesheet_repo::TimesheetRepository,
+        timesheet_amendment::{
+            models::{AmendmentDay, TimesheetAmendmentWithDays},
+            timesheet_amendment_repo::TimesheetAmendmentRepository,
+        },
+    },
+    utils::wage_calc::{calculate_wage::calculate_amendment_difference, models::DetailedWage},
+};
+
+use super::accepted_wages::EmployeeWages;
+
+/// Days of the amendments approved before the given one, in the order they
+/// were approved.
+fn approved_before(
+    amendments: &[TimesheetAmendmentWithDays],
+    amendment: &TimesheetAmendmentWithDays,
+) -> Vec<AmendmentDay> {
+    let mut approved: Vec<&TimesheetAmendmentWithDays> = amendments
+        .iter()
+        .filter(|other| {
+            other.amendment.status == AmendmentStatus::Approved
+                && other.amendment.id != amendment.amendment.id
+                && (amendment.amendment.decided_at.is_none()
+                    || other.amendment.decided_at < amendment.amendment.decided_at)
+        })
+        .collect();
+    approved.sort_by_key(|other| other.amendment.decided_at);
+
+    approved
+        .into_iter()
+        .flat_map(|other| other.days.iter().cloned())
+        .collect()
+}
+
+/// The wage to pay (positive) or recover (negative) when the amendment is
+/// settled. The inner error describes why the wage couldn't be computed.
+pub async fn amendment_wage_difference(
+    timesheet_repo: &TimesheetRepository,
+    amendment_repo: &TimesheetAmendmentRepository,
+    amendment: &TimesheetAmendmentWithDays,
+    pink_paper_signed: bool,
+) -> DbResult<Result<DetailedWage, String>> {
+    let timesheet = timesheet_repo
+        ._read_one(amendment.amendment.timesheet_id)
+        .await?
+        .timesheet;
+    let amendments = amendment_repo.read_all_for_timesheet(timesheet.id).await?;
+    let related = timesheet_repo
+        .read_all_with_date_from_to_per_employment_extended_db(
+            timesheet.user_id,
+            timesheet.company_id,
+            timesheet.start_date,
+            timesheet.end_date,
+        )
+        .await?;
+
+    Ok(calculate_amendment_difference(
+        pink_paper_signed,
+        related,
+        timesheet.id,
+        &approved_before(&amendments, amendment),
+        &amendment.days,
+    ))
+}
+
+/// Sums the wage differences of the company's amendments settled in the
+/// month per employee, the employees are ordered by name.
+pub async fn collect_amendment_corrections(
+    timesheet_repo: &TimesheetRepository,
+    amendment_repo: &TimesheetAmendmentRepository,
+    company_id: Uuid,
+    month: NaiveDate,
+    pink_paper_signed: bool,
+) -> DbResult<Vec<EmployeeWages>> {
+    let settlements = amendment_repo.read_settled_in(company_id, month).await?;
+
+    let mut employees: Vec<EmployeeWages> = vec![];
+    for settlement in settlements {
+        let amendment = amendment_repo.read_one(settlement.amendment_id).await?;
+        let difference = amendment_wage_difference(
+            timesheet_repo,
+            amendment_repo,
+            &amendment,
+            pink_paper_signed,
+        )
+        .await?;
+
+        let position = employees
+            .iter()
+            .position(|employee| employee.user_id == settlement.user_id);
+        let employee = match position {
+            Some(position) => &mut employees[position],
+            None => {
+                employees.push(EmployeeWages {
+                    user_id: settlement.user_id,
+                    name: settlement.user_name.clone(),
+                    email: settlement.user_email.clone(),
+                    employment_type: settlement.employment_type.clone(),
+                    hourly_wage: settlement.hourly_wage as f32,
+                    months: BTreeMap::new(),
+                    errors: vec![],
+                });
+                employees.last_mut().expect("Should be some.")
+            }
+        };
+
+        match difference {
+            Err(error) => employee
+                .errors
+                .push(format!("{} (amendment): {}", settlement.event_name, error)),
+            Ok(difference) => *employee.months.entry(month).or_default() += &difference,
+        }
+    }
+
+    Ok(employees)
+}
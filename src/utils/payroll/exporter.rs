@@ -0,0 +1,17 @@
+use super::{
+    models::{Payroll, PayrollFormat},
+    pohoda::PohodaExporter,
+};
+
+/// Turns a payroll into the import file of an accounting software.
+pub trait PayrollExporter {
+    fn content_type(&self) -> &'static str;
+    fn extension(&self) -> &'static str;
+    fn export(&self, payroll: &Payroll) -> Vec<u8>;
+}
+
+pub fn payroll_exporter(format: PayrollFormat) -> Box<dyn PayrollExporter> {
+    match format {
+        PayrollFormat::Pohoda => Box::new(PohodaExporter),
+    }
+}
@@ -0,0 +1,5 @@
+pub mod accepted_wages;
+pub mod amendment_wages;
+pub mod exporter;
+pub mod models;
+pub mod pohoda;
@@ -0,0 +1,118 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{models::EmploymentContract, utils::wage_calc::models::DetailedWage};
+
+use super::accepted_wages::EmployeeWages;
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PayrollFormat {
+    #[default]
+    Pohoda,
+}
+
+/// A month's payroll of a company, built from the accepted timesheets.
+#[derive(Debug)]
+pub struct Payroll {
+    pub company_name: String,
+    /// Company registration number (IČO).
+    pub company_crn: String,
+    /// The first day of the month.
+    pub month: NaiveDate,
+    pub currency: String,
+    pub entries: Vec<PayrollEntry>,
+    /// Wage differences of the amendments settled in the month.
+    pub corrections: Vec<PayrollEntry>,
+}
+
+/// Summed wage of a single employee for the month.
+#[derive(Debug)]
+pub struct PayrollEntry {
+    pub user_id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub employment_type: EmploymentContract,
+    pub hourly_wage: f32,
+    pub wage: DetailedWage,
+}
+
+/// Income of a period split into what was withheld and what was paid out.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct WithheldIncome {
+    pub worked_hours: f32,
+    pub gross_income: f32,
+    pub insurance_withheld: f32,
+    pub tax_withheld: f32,
+    pub net_income: f32,
+}
+
+impl From<&DetailedWage> for WithheldIncome {
+    /// The employee's insurance isn't withheld with a signed pink paper,
+    /// so only the difference between the gross and net wage was withheld.
+    /// Anything withheld on top of the insurance is the tax.
+    fn from(wage: &DetailedWage) -> Self {
+        let withheld = (wage.tax_base - wage.net_wage).max(0.0);
+        let insurance_withheld =
+            (wage.employee_social_insurance + wage.employee_health_insurance).min(withheld);
+
+        WithheldIncome {
+            worked_hours: wage.worked_hours,
+            gross_income: wage.tax_base,
+            insurance_withheld,
+            tax_withheld: withheld - insurance_withheld,
+            net_income: wage.net_wage,
+        }
+    }
+}
+
+/// Yearly confirmation of an employee's taxable income from a company
+/// (potvrzení o zdanitelných příjmech).
+#[derive(Debug)]
+pub struct IncomeConfirmation {
+    pub year: i32,
+    pub company_name: String,
+    pub company_crn: String,
+    pub user_id: Uuid,
+    pub name: String,
+    pub email: String,
+    pub employment_type: EmploymentContract,
+    /// Keyed by the first day of the month.
+    pub months: Vec<(NaiveDate, WithheldIncome)>,
+    pub total: WithheldIncome,
+    /// Timesheets whose wage couldn't be computed, the confirmation is
+    /// incomplete without them.
+    pub errors: Vec<String>,
+}
+
+impl IncomeConfirmation {
+    pub fn new(year: i32, company_name: &str, company_crn: &str, employee: EmployeeWages) -> Self {
+        let months: Vec<(NaiveDate, WithheldIncome)> = employee
+            .months
+            .iter()
+            .map(|(month, wage)| (*month, wage.into()))
+            .collect();
+
+        IncomeConfirmation {
+            year,
+            company_name: company_name.to_string(),
+            company_crn: company_crn.to_string(),
+            total: (&employee.total()).into(),
+            user_id: employee.user_id,
+            name: employee.name,
+            email: employee.email,
+            employment_type: employee.employment_type,
+            months,
+            errors: employee.errors,
+        }
+    }
+
+    /// Months with any taxable income.
+    pub fn months_worked(&self) -> usize {
+        self.months
+            .iter()
+            .filter(|(_, income)| income.gross_income > 0.0)
+            .count()
+    }
+}
@@ -0,0 +1,251 @@
+use std::fmt::Write;
+
+use crate::{models::EmploymentContract, utils::export::table_writer::escape_xml};
+
+use super::{
+    exporter::PayrollExporter,
+    models::{Payroll, PayrollEntry},
+};
+
+pub const POHODA_DATA_NAMESPACE: &str = "http://www.stormware.cz/schema/version_2/data.xsd";
+pub const POHODA_INT_DOC_NAMESPACE: &str = "http://www.stormware.cz/schema/version_2/intDoc.xsd";
+pub const POHODA_TYPE_NAMESPACE: &str = "http://www.stormware.cz/schema/version_2/type.xsd";
+
+/// Pohoda XML import (data pack version 2.0). Every employee's monthly wage
+/// becomes an internal document (`int:intDoc`) with one item per amount, so
+/// the accountant only has to post it, the employer's insurance another one.
+/// Wage corrections of earlier months are separate documents.
+pub struct PohodaExporter;
+
+fn contract_name(employment_type: &EmploymentContract) -> &'static str {
+    match employment_type {
+        EmploymentContract::Dpp => "DPP",
+        EmploymentContract::Dpc => "DPČ",
+        EmploymentContract::Hpp => "HPP",
+    }
+}
+
+/// Pohoda limits the length of most texts.
+fn truncate(text: &str, max_length: usize) -> String {
+    escape_xml(&text.chars().take(max_length).collect::<String>())
+}
+
+fn write_item(
+    output: &mut String,
+    text: &str,
+    quantity: f32,
+    unit: Option<&str>,
+    unit_price: f32,
+) -> std::fmt::Result {
+    write!(
+        output,
+        "<int:intDocItem><int:text>{}</int:text><int:quantity>{:.2}</int:quantity>",
+        truncate(text, 90),
+        quantity,
+    )?;
+    if let Some(unit) = unit {
+        write!(output, "<int:unit>{}</int:unit>", escape_xml(unit))?;
+    }
+    write!(
+        output,
+        concat!(
+            "<int:rateVAT>none</int:rateVAT>",
+            "<int:homeCurrency><typ:unitPrice>{:.2}</typ:unitPrice></int:homeCurrency>",
+            "</int:intDocItem>",
+        ),
+        unit_price,
+    )
+}
+
+/// The titles of the documents an entry is split into.
+struct EntryTitles {
+    wage: &'static str,
+    employer_insurance: &'static str,
+}
+
+const WAGE_TITLES: EntryTitles = EntryTitles {
+    wage: "Mzda",
+    employer_insurance: "Pojištění zaměstnavatele",
+};
+
+const CORRECTION_TITLES: EntryTitles = EntryTitles {
+    wage: "Oprava mzdy",
+    employer_insurance: "Oprava pojištění zaměstnavatele",
+};
+
+/// Rounds the amount to cents, without a negative zero.
+fn round_cents(amount: f32) -> f32 {
+    (amount * 100.0).round() / 100.0 + 0.0
+}
+
+fn write_document_header(
+    output: &mut String,
+    payroll: &Payroll,
+    entry: &PayrollEntry,
+    title: &str,
+    id: usize,
+) -> std::fmt::Result {
+    let contract = contract_name(&entry.employment_type);
+    let last_day = payroll
+        .month
+        .checked_add_months(chrono::Months::new(1))
+        .and_then(|next_month| next_month.pred_opt())
+        .unwrap_or(payroll.month);
+    let text = format!(
+        "{} {} {} - {}",
+        title,
+        payroll.month.format("%m/%Y"),
+        contract,
+        entry.name
+    );
+
+    write!(
+        output,
+        concat!(
+            r#"<dat:dataPackItem id="{}-{}" version="2.0">"#,
+            r#"<int:intDoc version="2.0">"#,
+            "<int:intDocHeader>",
+            "<int:date>{}</int:date>",
+            "<int:text>{}</int:text>",
+            "<int:partnerIdentity><typ:address>",
+            "<typ:name>{}</typ:name>",
+            "<typ:email>{}</typ:email>",
+            "</typ:address></int:partnerIdentity>",
+            "<int:note>{}, {:.2} h, {:.2} {}/h</int:note>",
+            "</int:intDocHeader>",
+            "<int:intDocDetail>",
+        ),
+        payroll.month.format("%Y-%m"),
+        id,
+        last_day.format("%Y-%m-%d"),
+        truncate(&text, 240),
+        truncate(&entry.name, 255),
+        truncate(&entry.email, 98),
+        contract,
+        entry.wage.worked_hours,
+        entry.hourly_wage,
+        escape_xml(&payroll.currency),
+    )
+}
+
+fn write_document_footer(output: &mut String) -> std::fmt::Result {
+    write!(
+        output,
+        "</int:intDocDetail></int:intDoc></dat:dataPackItem>"
+    )
+}
+
+/// Writes the wage document of the entry, whose total is the net wage: the
+/// gross wage minus the employee's insurance and income tax. The employer's
+/// insurance is a cost on top of the gross wage, so it gets a document of its
+/// own. Returns the number of documents written.
+fn write_entry(
+    output: &mut String,
+    payroll: &Payroll,
+    entry: &PayrollEntry,
+    titles: &EntryTitles,
+    first_id: usize,
+) -> Result<usize, std::fmt::Error> {
+    let wage = &entry.wage;
+    let income_tax = round_cents(
+        wage.tax_base
+            - wage.net_wage
+            - wage.employee_social_insurance
+            - wage.employee_health_insurance,
+    );
+
+    write_document_header(output, payroll, entry, titles.wage, first_id)?;
+    write_item(
+        output,
+        "Hrubá mzda",
+        wage.worked_hours,
+        Some("h"),
+        entry.hourly_wage,
+    )?;
+    for (text, amount) in [
+        (
+            "Sociální pojištění zaměstnanec",
+            wage.employee_social_insurance,
+        ),
+        (
+            "Zdravotní pojištění zaměstnanec",
+            wage.employee_health_insurance,
+        ),
+        ("Daň z příjmů", income_tax),
+    ] {
+        write_item(output, text, 1.0, None, -amount)?;
+    }
+    write_document_footer(output)?;
+
+    if wage.employer_social_insurance == 0.0 && wage.employer_health_insurance == 0.0 {
+        return Ok(1);
+    }
+
+    write_document_header(
+        output,
+        payroll,
+        entry,
+        titles.employer_insurance,
+        first_id + 1,
+    )?;
+    for (text, amount) in [
+        (
+            "Sociální pojištění zaměstnavatel",
+            wage.employer_social_insurance,
+        ),
+        (
+            "Zdravotní pojištění zaměstnavatel",
+            wage.employer_health_insurance,
+        ),
+    ] {
+        write_item(output, text, 1.0, None, amount)?;
+    }
+    write_document_footer(output)?;
+
+    Ok(2)
+}
+
+impl PayrollExporter for PohodaExporter {
+    fn content_type(&self) -> &'static str {
+        "application/xml; charset=utf-8"
+    }
+
+    fn extension(&self) -> &'static str {
+        "xml"
+    }
+
+    fn export(&self, payroll: &Payroll) -> Vec<u8> {
+        let mut output = String::new();
+        write!(
+            output,
+            concat!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+                r#"<dat:dataPack xmlns:dat="{}" xmlns:int="{}" xmlns:typ="{}" "#,
+                r#"id="payroll-{}" ico="{}" application="Orchestrate" version="2.0" "#,
+                r#"note="Mzdy {} - {}">"#,
+            ),
+            POHODA_DATA_NAMESPACE,
+            POHODA_INT_DOC_NAMESPACE,
+            POHODA_TYPE_NAMESPACE,
+            payroll.month.format("%Y-%m"),
+            escape_xml(&payroll.company_crn),
+            payroll.month.format("%m/%Y"),
+            escape_xml(&payroll.company_name),
+        )
+        .expect("Writing to memory can't fail.");
+
+        let mut next_id = 1;
+        for entry in payroll.entries.iter() {
+            next_id += write_entry(&mut output, payroll, entry, &WAGE_TITLES, next_id)
+                .expect("Writing to memory can't fail.");
+        }
+        // Corrections of the wages settled before, from approved amendments.
+        for entry in payroll.corrections.iter() {
+            next_id += write_entry(&mut output, payroll, entry, &CORRECTION_TITLES, next_id)
+                .expect("Writing to memory can't fail.");
+        }
+        output.push_str("</dat:dataPack>");
+
+        output.into_bytes()
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::{HashMap, VecDeque};
+
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct TaskNode {
+    pub id: Uuid,
+    pub estimated_hours: f32,
+}
+
+/// Length of the longest chain ending in a task. Ties in hours are broken
+/// by the number of tasks, so the path is meaningful even without estimates.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+struct ChainLength {
+    hours: f32,
+    tasks: usize,
+}
+
+/// Finds the longest chain of dependent tasks, weighted by their estimates.
+///
+/// `dependencies` holds `(blocker_id, task_id)` edges. Edges touching tasks
+/// not present in `tasks` are ignored, as are tasks caught in a cycle.
+/// The returned IDs are ordered from the first task to be done to the last.
+pub fn find_critical_path(tasks: &[TaskNode], dependencies: &[(Uuid, Uuid)]) -> Vec<Uuid> {
+    let weights: HashMap<Uuid, f32> = tasks
+        .iter()
+        .map(|task| (task.id, task.estimated_hours.max(0.0)))
+        .collect();
+
+    let mut successors: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    let mut blocker_count: HashMap<Uuid, usize> = tasks.iter().map(|task| (task.id, 0)).collect();
+    for (blocker_id, task_id) in dependencies {
+        if !weights.contains_key(blocker_id) || !weights.contains_key(task_id) {
+            continue;
+        }
+        successors.entry(*blocker_id).or_default().push(*task_id);
+        *blocker_count.get_mut(task_id).expect("Should be present.") += 1;
+    }
+
+    let mut longest: HashMap<Uuid, ChainLength> = HashMap::new();
+    let mut predecessor: HashMap<Uuid, Uuid> = HashMap::new();
+    let mut ready: VecDeque<Uuid> = tasks
+        .iter()
+        .filter(|task| blocker_count[&task.id] == 0)
+        .map(|task| task.id)
+        .collect();
+
+    for task_id in ready.iter() {
+        longest.insert(
+            *task_id,
+            ChainLength {
+                hours: weights[task_id],
+                tasks: 1,
+            },
+        );
+    }
+
+    // Kahn's algorithm - a task is relaxed once all of its blockers are.
+    while let Some(task_id) = ready.pop_front() {
+        let current = longest[&task_id];
+        for next_id in successors.get(&task_id).cloned().unwrap_or_default() {
+            let candidate = ChainLength {
+                hours: current.hours + weights[&next_id],
+                tasks: current.tasks + 1,
+            };
+            let is_longer = match longest.get(&next_id) {
+                Some(existing) => candidate > *existing,
+                None => true,
+            };
+            if is_longer {
+                longest.insert(next_id, candidate);
+                predecessor.insert(next_id, task_id);
+            }
+
+            let count = blocker_count.get_mut(&next_id).expect("Should be present.");
+            *count -= 1;
+            if *count == 0 {
+                ready.push_back(next_id);
+            }
+        }
+    }
+
+    let last = longest
+        .iter()
+        .filter(|(task_id, _)| blocker_count[*task_id] == 0)
+        .max_by(|a, b| {
+            a.1.partial_cmp(b.1)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                // Keep the result deterministic for equally long chains.
+                .then_with(|| b.0.cmp(a.0))
+        })
+        .map(|(task_id, _)| *task_id);
+
+    let mut path = vec![];
+    let mut current = last;
+    while let Some(task_id) = current {
+        path.push(task_id);
+        current = predecessor.get(&task_id).copied();
+    }
+    path.reverse();
+
+    path
+}
@@ -0,0 +1 @@
+pub mod critical_path;
@@ -4,6 +4,7 @@
 #![allow(dead_code)]
 #![allow(unused_variables)]
 
+use crate::repositories::timesheet_amendment::models::AmendmentDay;
 use crate::repositories::wage_preset::models::WagePreset;
 
 use crate::models::EmploymentContract;
@@ -372,3 +373,70 @@ pub fn calculate_timesheet_wage(
 
     Ok(detailed_wage_output)
 }
+
+//
+// Overwrites the wanted timesheet's workdays with corrected values.
+//
+fn apply_amendment_days(
+    timesheets_extended: &mut TimesheetsWithWorkdaysExtended,
+    wanted_timesheet_id: Uuid,
+    amendment_days: &[AmendmentDay],
+) {
+    let wanted_timesheet = timesheets_extended
+        .timesheets
+        .iter_mut()
+        .find(|timesheet| timesheet.timesheet.id == wanted_timesheet_id);
+
+    if let Some(wanted_timesheet) = wanted_timesheet {
+        for day in amendment_days.iter() {
+            if let Some(workday) = wanted_timesheet
+                .workdays
+                .iter_mut()
+                .find(|workday| workday.date == day.date)
+            {
+                workday.total_hours = day.total_hours;
+            }
+        }
+    }
+}
+
+//
+// Get the wage difference an amendment of an accepted timesheet causes.
+// A positive value is to be paid out, a negative one recovered.
+//
+// Note: `amended_before` are the days of amendments approved earlier,
+//       in the order they were approved. The wage was already settled
+//       with them, so they are the base of the difference.
+//
+pub fn calculate_amendment_difference(
+    pink_paper_signed: bool,
+    mut timesheets_extended: TimesheetsWithWorkdaysExtended,
+    wanted_timesheet_id: Uuid,
+    amended_before: &[AmendmentDay],
+    amendment_days: &[AmendmentDay],
+) -> Result<DetailedWage, String> {
+    apply_amendment_days(
+        &mut timesheets_extended,
+        wanted_timesheet_id,
+        amended_before,
+    );
+    let original_wage =
+        calculate_timesheet_wage(pink_paper_signed, &timesheets_extended, wanted_timesheet_id)?;
+
+    apply_amendment_days(
+        &mut timesheets_extended,
+        wanted_timesheet_id,
+        amendment_days,
+    );
+    let amended_wage =
+        calculate_timesheet_wage(pink_paper_signed, &timesheets_extended, wanted_timesheet_id)?;
+
+    if let Some(error) = original_wage.error_option.or(amended_wage.error_option) {
+        return Err(error);
+    }
+
+    let mut difference = amended_wage.total_wage;
+    difference -= &original_wage.total_wage;
+
+    Ok(difference)
+}
@@ -1,4 +1,7 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    ops::{AddAssign, SubAssign},
+};
 
 use chrono::{Datelike, NaiveDate};
 use serde::Deserialize;
@@ -53,6 +56,30 @@ impl Default for DetailedWage {
     }
 }
 
+impl AddAssign<&DetailedWage> for DetailedWage {
+    fn add_assign(&mut self, other: &DetailedWage) {
+        self.tax_base += other.tax_base;
+        self.net_wage += other.net_wage;
+        self.worked_hours += other.worked_hours;
+        self.employee_social_insurance += other.employee_social_insurance;
+        self.employee_health_insurance += other.employee_health_insurance;
+        self.employer_social_insurance += other.employer_social_insurance;
+        self.employer_health_insurance += other.employer_health_insurance;
+    }
+}
+
+impl SubAssign<&DetailedWage> for DetailedWage {
+    fn sub_assign(&mut self, other: &DetailedWage) {
+        self.tax_base -= other.tax_base;
+        self.net_wage -= other.net_wage;
+        self.worked_hours -= other.worked_hours;
+        self.employee_social_insurance -= other.employee_social_insurance;
+        self.employee_health_insurance -= other.employee_health_insurance;
+        self.employer_social_insurance -= other.employer_social_insurance;
+        self.employer_health_insurance -= other.employer_health_insurance;
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct TimesheetWageDetailed {
     // A total wage data for selected timesheet's work.
@@ -9,19 +9,35 @@ mod api_tests {
     use chrono::NaiveDate;
     use organization::common::DbResult;
     use organization::handlers::associated_company::get_all_associated_companies_per_event_and_user;
-    use organization::models::{EmployeeLevel, EmploymentContract};
+    use organization::models::{
+        EmployeeLevel, EmploymentContract, EventStatus, TimesheetTransition,
+    };
     use organization::repositories::assigned_staff::assigned_staff_repo::AssignedStaffRepository;
     use organization::repositories::associated_company::associated_company_repo::AssociatedCompanyRepository;
+    use organization::repositories::calendar_feed::calendar_feed_repo::CalendarFeedRepository;
     use organization::repositories::comment::comment_repo::CommentRepository;
     use organization::repositories::company::company_repo::CompanyRepository;
     use organization::repositories::employment::employment_repo::EmploymentRepository;
     use organization::repositories::event::event_repo::EventRepository;
+    use organization::repositories::event::models::EventStatusData;
+    use organization::repositories::event_budget::event_budget_repo::EventBudgetRepository;
     use organization::repositories::event_staff::event_staff_repo::StaffRepository;
+    use organization::repositories::job::job_repo::JobRepository;
+    use organization::repositories::report::report_repo::ReportRepository;
     use organization::repositories::repository::DbRepository;
+    use organization::repositories::submission_policy::submission_policy_repo::SubmissionPolicyRepository;
     use organization::repositories::task::models::TaskFilter;
     use organization::repositories::task::task_repo::TaskRepository;
+    use organization::repositories::timesheet::models::{
+        TimesheetTransitionData, WorkdayUpdateData,
+    };
     use organization::repositories::timesheet::timesheet_repo::TimesheetRepository;
+    use organization::repositories::timesheet_amendment::timesheet_amendment_repo::TimesheetAmendmentRepository;
+    use organization::repositories::timesheet_consistency::timesheet_consistency_repo::TimesheetConsistencyRepository;
+    use organization::repositories::trash::trash_repo::TrashRepository;
     use organization::repositories::user::user_repo::UserRepository;
+    use organization::repositories::wage_preset::wage_preset_repo::WagePresetRepository;
+    use organization::utils::live_update::live_update_hub::LiveUpdateHub;
 
     use organization::handlers::{
         assigned_staff::{
@@ -32,6 +48,7 @@ mod api_tests {
             create_associated_company, delete_associated_company, get_all_associated_companies,
             update_associated_company,
         },
+        calendar_feed::get_calendar_feed,
         comment::{
             create_event_comment, create_task_comment, delete_comment,
             open_event_comments_for_user, open_task_comments_for_user, update_comment,
@@ -41,16 +58,36 @@ mod api_tests {
             create_employment, delete_employment, get_employment, get_employments_per_user,
             get_subordinates, update_employment,
         },
+        employment_import::{commit_employment_import, preview_employment_import},
         event::{create_event, delete_event, get_event, get_events, update_event},
+        event_budget::{create_budget_line, delete_budget_line, get_event_budget},
         event_staff::{
             create_event_staff, delete_event_staff, get_all_event_staff, get_event_staff,
             update_event_staff,
         },
         event_task::{create_task, delete_task, get_event_tasks, update_task},
+        export::{export_company_timesheets, export_event_staff, export_event_tasks},
+        income_confirmation::{export_income_confirmations, get_income_confirmations},
         index::index,
+        job::{get_jobs, run_job},
+        live_update::get_event_live_updates,
+        payroll::export_company_payroll,
+        report::{export_report, get_reports},
+        review_queue::{get_review_queue, review_queued_timesheets},
+        submission_policy::{
+            delete_submission_policy, get_overdue_timesheets, get_submission_policy,
+            update_submission_policy,
+        },
         timesheet::{
-            create_timesheet, get_all_timesheets_for_employment, get_timesheet, update_timesheet,
+            create_timesheet, flag_work_day, get_all_timesheets_for_employment, get_timesheet,
+            get_timesheet_review_preview, open_timesheet_for_review, remove_work_day_flag,
+            transition_timesheet, update_timesheet,
+        },
+        timesheet_amendment::{
+            decide_timesheet_amendment, get_timesheet_amendments, propose_timesheet_amendment,
         },
+        timesheet_consistency::{get_timesheet_consistency, repair_timesheet_consistency},
+        trash::{get_trash, restore_deletion},
         user::{create_user, delete_user, get_user, update_user},
     };
 
@@ -592,9 +629,15 @@ mod api_tests {
         let arc_pool = Arc::new(pool);
         let event_repository = EventRepository::new(arc_pool.clone());
         let event_repo = web::Data::new(event_repository);
+        let staff_repo = web::Data::new(StaffRepository::new(arc_pool.clone()));
 
-        let app =
-            test::init_service(App::new().app_data(event_repo.clone()).service(get_event)).await;
+        let app = test::init_service(
+            App::new()
+                .app_data(event_repo.clone())
+                .app_data(staff_repo.clone())
+                .service(get_event),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/event/b71fd7ce-c891-410a-9bb4-70fc5c7748f8")
@@ -615,9 +658,15 @@ mod api_tests {
         let arc_pool = Arc::new(pool);
         let event_repository = EventRepository::new(arc_pool.clone());
         let event_repo = web::Data::new(event_repository);
+        let staff_repo = web::Data::new(StaffRepository::new(arc_pool.clone()));
 
-        let app =
-            test::init_service(App::new().app_data(event_repo.clone()).service(get_event)).await;
+        let app = test::init_service(
+            App::new()
+                .app_data(event_repo.clone())
+                .app_data(staff_repo.clone())
+                .service(get_event),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/event/a71cd75e-a811-410a-9bb4-70fc5c7748f8")
@@ -632,9 +681,15 @@ mod api_tests {
         let arc_pool = Arc::new(pool);
         let event_repository = EventRepository::new(arc_pool.clone());
         let event_repo = web::Data::new(event_repository);
+        let staff_repo = web::Data::new(StaffRepository::new(arc_pool.clone()));
 
-        let app =
-            test::init_service(App::new().app_data(event_repo.clone()).service(get_event)).await;
+        let app = test::init_service(
+            App::new()
+                .app_data(event_repo.clone())
+                .app_data(staff_repo.clone())
+                .service(get_event),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/event/a71cd75e-sleepy-head-111z3zz")
@@ -653,6 +708,7 @@ mod api_tests {
         let app = test::init_service(
             App::new()
                 .app_data(event_repo.clone())
+                .app_data(web::Data::new(StaffRepository::new(arc_pool.clone())))
                 .app_data(employment_repo.clone())
                 .service(create_event)
                 .service(update_event)
@@ -732,6 +788,7 @@ mod api_tests {
         let app = test::init_service(
             App::new()
                 .app_data(event_repo.clone())
+                .app_data(web::Data::new(StaffRepository::new(arc_pool.clone())))
                 .service(update_event),
         )
         .await;
@@ -758,6 +815,7 @@ mod api_tests {
         let app = test::init_service(
             App::new()
                 .app_data(event_repo.clone())
+                .app_data(web::Data::new(StaffRepository::new(arc_pool.clone())))
                 .service(update_event),
         )
         .await;
@@ -782,6 +840,7 @@ mod api_tests {
         let app = test::init_service(
             App::new()
                 .app_data(event_repo.clone())
+                .app_data(web::Data::new(StaffRepository::new(arc_pool.clone())))
                 .service(update_event),
         )
         .await;
@@ -797,6 +856,97 @@ mod api_tests {
         assert_eq!(res.status(), http::StatusCode::BAD_REQUEST);
     }
 
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn patch_event_dates_with_timesheets(pool: PgPool) {
+        let arc_pool = Arc::new(pool);
+        let event_repo = web::Data::new(EventRepository::new(arc_pool.clone()));
+        let timesheet_repo = TimesheetRepository::new(arc_pool.clone());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(event_repo.clone())
+                .app_data(web::Data::new(StaffRepository::new(arc_pool.clone())))
+                .service(update_event),
+        )
+        .await;
+
+        // The organizer has to say who they are.
+        let req = test::TestRequest::patch()
+            .uri("/event/b71fd7ce-c891-410a-9bb4-70fc5c7748f8")
+            .set_json(json!({
+                "start_date": "1969-08-16",
+                "end_date": "1969-08-18"
+            }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::BAD_REQUEST);
+
+        // Only an organizer of the event can move it.
+        let req = test::TestRequest::patch()
+            .uri("/event/b71fd7ce-c891-410a-9bb4-70fc5c7748f8")
+            .set_json(json!({
+                "start_date": "1969-08-16",
+                "end_date": "1969-08-18",
+                "editor_id": "a96d1d99-93b5-469b-ac62-654b0cf7ebd3"
+            }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::FORBIDDEN);
+
+        // The impact is shown first and nothing changes.
+        let req = test::TestRequest::patch()
+            .uri("/event/b71fd7ce-c891-410a-9bb4-70fc5c7748f8")
+            .set_json(json!({
+                "start_date": "1969-08-16",
+                "end_date": "1969-08-18",
+                "editor_id": "9281b570-4d02-4096-9136-338a613c71cd"
+            }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("2 timesheets are affected."));
+        assert!(body.contains("12 logged hours fall outside of the"));
+        assert!(body.contains("Confirm New Dates"));
+
+        let event = event_repo
+            .read_one(Uuid::from_str("b71fd7ce-c891-410a-9bb4-70fc5c7748f8").unwrap())
+            .await
+            .expect("Read should succeed");
+        assert_eq!(
+            event.start_date,
+            NaiveDate::from_ymd_opt(1969, 8, 15).unwrap()
+        );
+
+        // Once confirmed, the event and its editable sheet move.
+        let req = test::TestRequest::patch()
+            .uri("/event/b71fd7ce-c891-410a-9bb4-70fc5c7748f8")
+            .set_json(json!({
+                "start_date": "1969-08-16",
+                "end_date": "1969-08-18",
+                "editor_id": "9281b570-4d02-4096-9136-338a613c71cd",
+                "confirmed": true
+            }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("Woodstock"));
+        assert!(!body.contains("Confirm New Dates"));
+
+        let timesheet = timesheet_repo
+            ._read_one(Uuid::from_str("d47e8141-a77e-4d55-a2d5-4a77de24b6d0").unwrap())
+            .await
+            .expect("Read should succeed");
+        assert_eq!(
+            timesheet.timesheet.start_date,
+            NaiveDate::from_ymd_opt(1969, 8, 16).unwrap()
+        );
+        assert_eq!(timesheet.timesheet.total_hours, 22.0);
+    }
+
     #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
     async fn delete_event_invalid_uuid_format(pool: PgPool) {
         let arc_pool = Arc::new(pool);
@@ -862,10 +1012,12 @@ mod api_tests {
         let repository = TaskRepository::new(arc_pool.clone());
         let repo = web::Data::new(repository);
         let assigned_repo = web::Data::new(AssignedStaffRepository::new(arc_pool.clone()));
+        let event_repo = web::Data::new(EventRepository::new(arc_pool.clone()));
         let app = test::init_service(
             App::new()
                 .app_data(repo.clone())
                 .app_data(assigned_repo.clone())
+                .app_data(event_repo.clone())
                 .service(create_task)
                 .service(delete_task)
                 .service(update_task),
@@ -966,10 +1118,12 @@ mod api_tests {
         let repository = TaskRepository::new(arc_pool.clone());
         let repo = web::Data::new(repository);
         let assigned_repo = web::Data::new(AssignedStaffRepository::new(arc_pool.clone()));
+        let event_repo = web::Data::new(EventRepository::new(arc_pool.clone()));
         let app = test::init_service(
             App::new()
                 .app_data(repo.clone())
                 .app_data(assigned_repo.clone())
+                .app_data(event_repo.clone())
                 .service(update_task),
         )
         .await;
@@ -993,10 +1147,12 @@ mod api_tests {
         let repository = TaskRepository::new(arc_pool.clone());
         let repo = web::Data::new(repository);
         let assigned_repo = web::Data::new(AssignedStaffRepository::new(arc_pool.clone()));
+        let event_repo = web::Data::new(EventRepository::new(arc_pool.clone()));
         let app = test::init_service(
             App::new()
                 .app_data(repo.clone())
                 .app_data(assigned_repo.clone())
+                .app_data(event_repo.clone())
                 .service(update_task),
         )
         .await;
@@ -1019,8 +1175,15 @@ mod api_tests {
         let arc_pool = Arc::new(pool);
         let repository = TaskRepository::new(arc_pool.clone());
         let repo = web::Data::new(repository);
+        let event_repo = web::Data::new(EventRepository::new(arc_pool.clone()));
 
-        let app = test::init_service(App::new().app_data(repo.clone()).service(delete_task)).await;
+        let app = test::init_service(
+            App::new()
+                .app_data(repo.clone())
+                .app_data(event_repo.clone())
+                .service(delete_task),
+        )
+        .await;
 
         let req = test::TestRequest::delete()
             .uri("/event/task/yesofficerIamanUUID.")
@@ -2030,6 +2193,28 @@ mod api_tests {
         let body = str::from_utf8(body_bytes.borrow()).unwrap();
         assert!(body.contains("Organizer"));
 
+        // The accepted staff got a timesheet, log some work in it.
+        let company_id =
+            Uuid::from_str("71fa27d6-6f00-4ad0-8902-778e298aaed2").expect("Valid uuid");
+        let timesheet = timesheet_repo
+            .read_one_for_staff(user_id, company_id, event_id)
+            .await
+            .expect("Read should succeed")
+            .expect("The sheet should be provisioned");
+        timesheet_repo
+            .update_workday(
+                timesheet.id,
+                timesheet.start_date,
+                WorkdayUpdateData {
+                    timesheet_id: timesheet.id,
+                    date: timesheet.start_date,
+                    total_hours: Some(6.0),
+                    comment: None,
+                },
+            )
+            .await
+            .expect("Update should succeed");
+
         let req = test::TestRequest::delete()
             .uri(
                 format!(
@@ -2042,6 +2227,9 @@ mod api_tests {
         let res = test::call_service(&app, req).await;
         assert!(res.status().is_success());
         assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("so their timesheet was kept"));
 
         let req = test::TestRequest::delete()
             .uri(
@@ -2059,6 +2247,87 @@ mod api_tests {
         Ok(())
     }
 
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn delete_event_staff_with_submitted_timesheet(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+        let staff_repo = web::Data::new(StaffRepository::new(arc_pool.clone()));
+        let timesheet_repo = web::Data::new(TimesheetRepository::new(arc_pool.clone()));
+        let event_repo = web::Data::new(EventRepository::new(arc_pool.clone()));
+        let associated_repo = web::Data::new(AssociatedCompanyRepository::new(arc_pool));
+        let app = test::init_service(
+            App::new()
+                .app_data(staff_repo.clone())
+                .app_data(timesheet_repo.clone())
+                .app_data(event_repo.clone())
+                .app_data(associated_repo.clone())
+                .service(create_event_staff)
+                .service(update_event_staff)
+                .service(delete_event_staff),
+        )
+        .await;
+
+        let event_id = Uuid::from_str("b71fd7ce-c891-410a-9bb4-70fc5c7748f8").expect("Valid uuid");
+        let user_id = Uuid::from_str("51a01dbf-dcd5-43a0-809c-94ed8e61d420").expect("Valid uuid");
+        let company_id =
+            Uuid::from_str("71fa27d6-6f00-4ad0-8902-778e298aaed2").expect("Valid uuid");
+
+        let req = test::TestRequest::post()
+            .uri(format!("/event/{}/staff", event_id).as_str())
+            .set_json(json!({
+                "user_id": user_id,
+                "company_id": company_id,
+                "role": "Staff"
+            }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::CREATED);
+
+        let staff_id = staff_repo
+            .read_by_event_and_user_id(event_id, user_id)
+            .await
+            .expect("Read should succeed")
+            .id;
+
+        let req = test::TestRequest::patch()
+            .uri(format!("/event/{}/staff/{}", event_id, staff_id).as_str())
+            .set_json(json!({
+                "status": "Accepted",
+                "decided_by": "9281b570-4d02-4096-9136-338a613c71cd"
+            }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+
+        // The provisioned sheet is submitted without any hours.
+        let timesheet = timesheet_repo
+            .read_one_for_staff(user_id, company_id, event_id)
+            .await
+            .expect("Read should succeed")
+            .expect("The sheet should be provisioned");
+        timesheet_repo
+            .transition(
+                timesheet.id,
+                TimesheetTransitionData {
+                    actor_id: user_id,
+                    transition: TimesheetTransition::Submit,
+                    note: None,
+                },
+            )
+            .await
+            .expect("Submit should succeed");
+
+        let req = test::TestRequest::delete()
+            .uri(format!("/event/{}/staff/{}", event_id, staff_id).as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("already submitted their timesheet for the event, so it was kept"));
+        assert!(!body.contains("logged"));
+
+        Ok(())
+    }
     #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
     async fn get_all_assigned_staff_test(pool: PgPool) -> DbResult<()> {
         let arc_pool = Arc::new(pool);
@@ -2174,10 +2443,14 @@ mod api_tests {
         let repository = AssignedStaffRepository::new(arc_pool.clone());
         let repo = web::Data::new(repository);
         let staff_repo = web::Data::new(StaffRepository::new(arc_pool.clone()));
+        let event_repo = web::Data::new(EventRepository::new(arc_pool.clone()));
+        let task_repo = web::Data::new(TaskRepository::new(arc_pool.clone()));
         let app = test::init_service(
             App::new()
                 .app_data(repo.clone())
                 .app_data(staff_repo.clone())
+                .app_data(event_repo.clone())
+                .app_data(task_repo.clone())
                 .service(create_assigned_staff)
                 .service(update_assigned_staff)
                 .service(delete_assigned_staff),
@@ -2259,10 +2532,14 @@ mod api_tests {
         let arc_pool = Arc::new(pool);
         let repository = AssignedStaffRepository::new(arc_pool.clone());
         let repo = web::Data::new(repository);
+        let event_repo = web::Data::new(EventRepository::new(arc_pool.clone()));
+        let task_repo = web::Data::new(TaskRepository::new(arc_pool.clone()));
 
         let app = test::init_service(
             App::new()
                 .app_data(repo.clone())
+                .app_data(event_repo.clone())
+                .app_data(task_repo.clone())
                 .service(create_assigned_staff)
                 .service(update_assigned_staff)
                 .service(delete_assigned_staff),
@@ -2299,6 +2576,57 @@ mod api_tests {
         Ok(())
     }
 
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn assigned_staff_of_cancelled_event(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+        let repo = web::Data::new(AssignedStaffRepository::new(arc_pool.clone()));
+        let event_repo = web::Data::new(EventRepository::new(arc_pool.clone()));
+        let task_repo = web::Data::new(TaskRepository::new(arc_pool.clone()));
+
+        EventRepository::new(arc_pool.clone())
+            .change_status(
+                Uuid::from_str("b71fd7ce-c891-410a-9bb4-70fc5c7748f8").unwrap(),
+                EventStatusData {
+                    status: EventStatus::Cancelled,
+                    cancellation_reason: Some("Heavy rain.".to_string()),
+                    changed_by: Uuid::from_str("35341253-da20-40b6-96d8-ce069b1ba5d4").unwrap(),
+                },
+            )
+            .await
+            .expect("Should be cancelled");
+
+        let app = test::init_service(
+            App::new()
+                .app_data(repo.clone())
+                .app_data(event_repo.clone())
+                .app_data(task_repo.clone())
+                .service(create_assigned_staff)
+                .service(delete_assigned_staff),
+        )
+        .await;
+
+        let data = json!({
+            "staff_id": "a96d1d99-93b5-469b-ac62-654b0cf7ebd3"
+        });
+
+        let req = test::TestRequest::post()
+            .uri("/task/7ae0c017-fe31-4aac-b767-100d18a8877b/staff")
+            .set_json(data)
+            .to_request();
+
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::delete()
+            .uri("/task/7ae0c017-fe31-4aac-b767-100d18a8877b/staff/a96d1d99-93b5-469b-ac62-654b0cf7ebd3")
+            .to_request();
+
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::BAD_REQUEST);
+
+        Ok(())
+    }
+
     #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
     async fn get_all_associated_companies_per_event(pool: PgPool) -> DbResult<()> {
         let arc_pool = Arc::new(pool);
@@ -2691,4 +3019,1520 @@ mod api_tests {
         assert_eq!(res.status(), http::StatusCode::BAD_REQUEST);
         Ok(())
     }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn timesheet_transitions(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+        let repo = web::Data::new(TimesheetRepository::new(arc_pool.clone()));
+        let employment_repo = web::Data::new(EmploymentRepository::new(arc_pool.clone()));
+        let app = test::init_service(
+            App::new()
+                .app_data(repo.clone())
+                .app_data(employment_repo.clone())
+                .service(transition_timesheet)
+                .service(open_timesheet_for_review),
+        )
+        .await;
+
+        // Tana Smith's accepted sheet, managed by Dave Null.
+        let uri = "/timesheet/0f0f0ff5-0073-47cc-bd1f-540a04fee9ea/transition";
+        let employee = "0465041f-fe64-461f-9f71-71e3b97ca85f";
+        let manager = "35341253-da20-40b6-96d8-ce069b1ba5d4";
+        let stranger = "ac9bf689-a713-4b66-a3d0-41faaf0f8d0c";
+
+        for (user_id, transition, note, expected) in [
+            (employee, "Reopen", None, http::StatusCode::FORBIDDEN),
+            (stranger, "Reopen", None, http::StatusCode::FORBIDDEN),
+            (employee, "Submit", None, http::StatusCode::BAD_REQUEST),
+            (
+                manager,
+                "Reopen",
+                Some("Wrong dates."),
+                http::StatusCode::OK,
+            ),
+            (manager, "Submit", None, http::StatusCode::FORBIDDEN),
+            (employee, "Approve", None, http::StatusCode::FORBIDDEN),
+            (employee, "Submit", None, http::StatusCode::OK),
+            (employee, "Submit", None, http::StatusCode::BAD_REQUEST),
+            (manager, "Reject", None, http::StatusCode::BAD_REQUEST),
+            (manager, "Approve", None, http::StatusCode::OK),
+        ] {
+            let req = test::TestRequest::post()
+                .uri(uri)
+                .set_json(json!({
+                    "user_id": user_id,
+                    "transition": transition,
+                    "note": note,
+                }))
+                .to_request();
+            let res = test::call_service(&app, req).await;
+            assert_eq!(res.status(), expected, "{} by {}", transition, user_id);
+        }
+
+        let req = test::TestRequest::get()
+            .uri("/timesheet/0f0f0ff5-0073-47cc-bd1f-540a04fee9ea/review-mode")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("Approved"));
+        assert!(body.contains("by Tana Smith"));
+        assert!(body.contains("Wrong dates."));
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn timesheet_workday_flags(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+        let repo = web::Data::new(TimesheetRepository::new(arc_pool.clone()));
+        let employment_repo = web::Data::new(EmploymentRepository::new(arc_pool.clone()));
+        let app = test::init_service(
+            App::new()
+                .app_data(repo.clone())
+                .app_data(employment_repo.clone())
+                .service(get_timesheet)
+                .service(transition_timesheet)
+                .service(flag_work_day)
+                .service(remove_work_day_flag),
+        )
+        .await;
+
+        let sheet_uri = "/timesheet/0f0f0ff5-0073-47cc-bd1f-540a04fee9ea";
+        let flag_uri = format!("{}/day/1969-08-15/flag", sheet_uri);
+        let employee = "0465041f-fe64-461f-9f71-71e3b97ca85f";
+        let manager = "35341253-da20-40b6-96d8-ce069b1ba5d4";
+
+        for (user_id, transition) in [(manager, "Reopen"), (employee, "Submit")] {
+            let req = test::TestRequest::post()
+                .uri(format!("{}/transition", sheet_uri).as_str())
+                .set_json(json!({ "user_id": user_id, "transition": transition }))
+                .to_request();
+            let res = test::call_service(&app, req).await;
+            assert_eq!(res.status(), http::StatusCode::OK);
+        }
+
+        for (user_id, hours, expected) in [
+            (employee, 4.0, http::StatusCode::FORBIDDEN),
+            (manager, 30.0, http::StatusCode::BAD_REQUEST),
+            (manager, 4.0, http::StatusCode::OK),
+        ] {
+            let req = test::TestRequest::post()
+                .uri(flag_uri.as_str())
+                .set_json(json!({
+                    "user_id": user_id,
+                    "reason": "The stage opened at noon.",
+                    "suggested_hours": hours,
+                }))
+                .to_request();
+            let res = test::call_service(&app, req).await;
+            assert_eq!(res.status(), expected);
+        }
+
+        // The flags are reason enough to reject the sheet.
+        let req = test::TestRequest::post()
+            .uri(format!("{}/transition", sheet_uri).as_str())
+            .set_json(json!({ "user_id": manager, "transition": "Reject" }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+
+        let req = test::TestRequest::get().uri(sheet_uri).to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("Manager Review"));
+        assert!(body.contains("The stage opened at noon."));
+        assert!(body.contains("Needs a correction"));
+
+        // A rejected sheet is no longer under review.
+        let req = test::TestRequest::delete()
+            .uri(format!("{}?user_id={}", flag_uri, manager).as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::post()
+            .uri(format!("{}/transition", sheet_uri).as_str())
+            .set_json(json!({ "user_id": employee, "transition": "Submit" }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+
+        let req = test::TestRequest::delete()
+            .uri(format!("{}?user_id={}", flag_uri, manager).as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+
+        // Nothing is left to adjust.
+        let req = test::TestRequest::post()
+            .uri(format!("{}/transition", sheet_uri).as_str())
+            .set_json(json!({ "user_id": manager, "transition": "Adjust" }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::BAD_REQUEST);
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn timesheet_review_queue(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+        let repo = web::Data::new(TimesheetRepository::new(arc_pool.clone()));
+        let employment_repo = web::Data::new(EmploymentRepository::new(arc_pool.clone()));
+        let app = test::init_service(
+            App::new()
+                .app_data(repo.clone())
+                .app_data(employment_repo.clone())
+                .service(transition_timesheet)
+                .service(get_review_queue)
+                .service(review_queued_timesheets)
+                .service(get_timesheet_review_preview),
+        )
+        .await;
+
+        // Tana Smith's sheets, managed by Dave Null.
+        let submitted = "0f0f0ff5-0073-47cc-bd1f-540a04fee9ea";
+        let accepted = "8446b2ba-8223-4388-be5f-9efdfc4ea265";
+        let employee = "0465041f-fe64-461f-9f71-71e3b97ca85f";
+        let manager = "35341253-da20-40b6-96d8-ce069b1ba5d4";
+        let queue_uri = format!("/user/{}/sheets-review-queue", manager);
+
+        for (user_id, transition) in [(manager, "Reopen"), (employee, "Submit")] {
+            let req = test::TestRequest::post()
+                .uri(format!("/timesheet/{}/transition", submitted).as_str())
+                .set_json(json!({ "user_id": user_id, "transition": transition }))
+                .to_request();
+            let res = test::call_service(&app, req).await;
+            assert_eq!(res.status(), http::StatusCode::OK);
+        }
+
+        let req = test::TestRequest::get()
+            .uri(queue_uri.as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("Tana Smith"));
+        assert!(body.contains(submitted));
+        assert!(!body.contains(accepted));
+
+        for (query, expected) in [
+            ("status=all&month=1969-07", http::StatusCode::OK),
+            ("status=closed", http::StatusCode::BAD_REQUEST),
+            ("month=July", http::StatusCode::BAD_REQUEST),
+            ("min_hours=-1", http::StatusCode::BAD_REQUEST),
+        ] {
+            let req = test::TestRequest::get()
+                .uri(format!("{}?{}", queue_uri, query).as_str())
+                .to_request();
+            let res = test::call_service(&app, req).await;
+            assert_eq!(res.status(), expected, "{}", query);
+        }
+
+        for (user_id, timesheet_ids, transition, note, expected) in [
+            (
+                employee,
+                vec![submitted],
+                "Approve",
+                None,
+                http::StatusCode::FORBIDDEN,
+            ),
+            (
+                manager,
+                vec![],
+                "Approve",
+                None,
+                http::StatusCode::BAD_REQUEST,
+            ),
+            (
+                manager,
+                vec![submitted],
+                "Reject",
+                None,
+                http::StatusCode::BAD_REQUEST,
+            ),
+            (
+                manager,
+                vec![submitted],
+                "Reopen",
+                None,
+                http::StatusCode::BAD_REQUEST,
+            ),
+            (
+                manager,
+                vec![submitted, accepted],
+                "Approve",
+                None,
+                http::StatusCode::BAD_REQUEST,
+            ),
+            (
+                manager,
+                vec![submitted],
+                "Approve",
+                Some("Good job."),
+                http::StatusCode::OK,
+            ),
+        ] {
+            let req = test::TestRequest::post()
+                .uri(format!("/user/{}/sheets-review-queue", user_id).as_str())
+                .set_json(json!({
+                    "timesheet_ids": timesheet_ids,
+                    "transition": transition,
+                    "note": note,
+                }))
+                .to_request();
+            let res = test::call_service(&app, req).await;
+            assert_eq!(res.status(), expected, "{} by {}", transition, user_id);
+        }
+
+        // The approved sheet left the queue.
+        let req = test::TestRequest::get()
+            .uri(queue_uri.as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("No sheets found."));
+
+        let req = test::TestRequest::get()
+            .uri(format!("/timesheet/{}/review-preview", submitted).as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("Computed Wage"));
+        assert!(body.contains("I was overworked as a mule!"));
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn calendar_feed(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let calendar_feed_repo = CalendarFeedRepository::new(arc_pool.clone());
+        let staff_repo = StaffRepository::new(arc_pool.clone());
+
+        let user_feed = calendar_feed_repo
+            .read_or_create(
+                Uuid::from_str("35341253-da20-40b6-96d8-ce069b1ba5d4").unwrap(),
+                None,
+            )
+            .await
+            .unwrap();
+        // User1 is not an organizer of the event.
+        let staff_feed = calendar_feed_repo
+            .read_or_create(
+                Uuid::from_str("0465041f-fe64-461f-9f71-71e3b97ca85f").unwrap(),
+                Some(Uuid::from_str("b71fd7ce-c891-410a-9bb4-70fc5c7748f8").unwrap()),
+            )
+            .await
+            .unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(calendar_feed_repo))
+                .app_data(web::Data::new(staff_repo))
+                .service(get_calendar_feed),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri(format!("/calendar/{}.ics", user_feed.token).as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status(), http::StatusCode::OK);
+        assert_eq!(
+            res.headers().get(http::header::CONTENT_TYPE).unwrap(),
+            "text/calendar; charset=utf-8"
+        );
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.starts_with("BEGIN:VCALENDAR"));
+        assert!(body.contains("UID:event-b71fd7ce-c891-410a-9bb4-70fc5c7748f8@orchestrate"));
+
+        let req = test::TestRequest::get()
+            .uri(format!("/calendar/{}.ics", staff_feed.token).as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::NOT_FOUND);
+
+        let req = test::TestRequest::get()
+            .uri("/calendar/9d7e4f6a-2b1c-4e8d-a5f3-0c6b7a8d9e10.ics")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::NOT_FOUND);
+
+        let req = test::TestRequest::get()
+            .uri("/calendar/not-a-token.ics")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::NOT_FOUND);
+        Ok(())
+    }
+
+    fn multipart_file(file_name: &str, content: &str) -> (String, String) {
+        let boundary = "----import-boundary";
+        let body = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{file_name}\"\r\nContent-Type: text/csv\r\n\r\n{content}\r\n--{boundary}--\r\n"
+        );
+        (format!("multipart/form-data; boundary={}", boundary), body)
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn event_live_updates(pool: PgPool) -> DbResult<()> {
+        let staff_repo = StaffRepository::new(Arc::new(pool));
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(staff_repo))
+                .app_data(web::Data::new(LiveUpdateHub::new()))
+                .service(get_event_live_updates),
+        )
+        .await;
+
+        // User1 is accepted staff of the event.
+        let req = test::TestRequest::get()
+            .uri("/event/b71fd7ce-c891-410a-9bb4-70fc5c7748f8/live/0465041f-fe64-461f-9f71-71e3b97ca85f")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status(), http::StatusCode::OK);
+        assert_eq!(
+            res.headers().get(http::header::CONTENT_TYPE).unwrap(),
+            "text/event-stream"
+        );
+
+        let req = test::TestRequest::get()
+            .uri(
+                format!(
+                    "/event/b71fd7ce-c891-410a-9bb4-70fc5c7748f8/live/{}",
+                    Uuid::nil()
+                )
+                .as_str(),
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status(), http::StatusCode::FORBIDDEN);
+
+        let req = test::TestRequest::get()
+            .uri("/event/b71fd7ce-c891-410a-9bb4-70fc5c7748f8/live/not-a-uuid")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status(), http::StatusCode::BAD_REQUEST);
+
+        Ok(())
+    }
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn employment_import(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let employment_repo = web::Data::new(EmploymentRepository::new(arc_pool.clone()));
+
+        let app = test::init_service(
+            App::new()
+                .app_data(employment_repo.clone())
+                .service(preview_employment_import)
+                .service(commit_employment_import),
+        )
+        .await;
+
+        let csv = "Name,Email,Birth Date,Gender,Contract Type,Hourly Wage,Start Date,End Date,Manager Email,Level\n\
+            Jan Novak,jan.novak@seznam.cz,1990-02-01,Male,HPP,250,2024-06-01,2024-09-01,t.smith@seznam.cz,Basic\n\
+            Eva Nova,eva.nova@seznam.cz,1.3.1992,female,dpp,\"180,5\",2024-06-01,,jan.novak@seznam.cz,\n\
+            Bad Row,not-an-email,1990-01-01,Robot,hpp,abc,2024-06-01,,,\n\
+            Dee Scord,dee@lmao.com,1990-01-01,Female,dpc,150,2024-06-01,,,manager\n\
+            Anna Smeth,a.smeth@sezam.cz,1990-01-01,Female,dpc,150,2024-06-01,,,\n";
+        let uri = "/user/35341253-da20-40b6-96d8-ce069b1ba5d4/employment/134d5286-5f55-4637-9b98-223a5820a464/import";
+
+        let (content_type, body) = multipart_file("crew.csv", csv);
+        let req = test::TestRequest::post()
+            .uri(format!("{}/preview", uri).as_str())
+            .insert_header((http::header::CONTENT_TYPE, content_type))
+            .set_payload(body)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("3 of 5 rows can be imported."));
+        assert!(body.contains("Gender &#x27;Robot&#x27; is not one of Male, Female, Other."));
+        assert!(body.contains("The user already has an employment in the company."));
+
+        // Nothing is stored by the preview.
+        let users = employment_repo
+            .read_import_users(
+                Uuid::from_str("134d5286-5f55-4637-9b98-223a5820a464").unwrap(),
+                vec!["jan.novak@seznam.cz".to_string()],
+            )
+            .await
+            .unwrap();
+        assert!(users.is_empty());
+
+        let (content_type, body) = multipart_file("crew.csv", csv);
+        let req = test::TestRequest::post()
+            .uri(uri)
+            .insert_header((http::header::CONTENT_TYPE, content_type))
+            .set_payload(body)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("Imported 3 of 5 rows."));
+
+        let users = employment_repo
+            .read_import_users(
+                Uuid::from_str("134d5286-5f55-4637-9b98-223a5820a464").unwrap(),
+                vec![
+                    "jan.novak@seznam.cz".to_string(),
+                    "eva.nova@seznam.cz".to_string(),
+                    "dee@lmao.com".to_string(),
+                ],
+            )
+            .await
+            .unwrap();
+        assert_eq!(users.len(), 3);
+        assert!(users.iter().all(|user| user.employed));
+
+        let jan = users
+            .iter()
+            .find(|user| user.email == "jan.novak@seznam.cz")
+            .unwrap();
+        let eva = employment_repo
+            .read_one(
+                users
+                    .iter()
+                    .find(|user| user.email == "eva.nova@seznam.cz")
+                    .unwrap()
+                    .id,
+                Uuid::from_str("134d5286-5f55-4637-9b98-223a5820a464").unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(eva.manager.unwrap().id, jan.id);
+        assert_eq!(eva.hourly_wage, 180.5);
+
+        // The imported employees are already employed now.
+        let (content_type, body) = multipart_file("crew.csv", csv);
+        let req = test::TestRequest::post()
+            .uri(uri)
+            .insert_header((http::header::CONTENT_TYPE, content_type))
+            .set_payload(body)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::BAD_REQUEST);
+
+        let (content_type, body) = multipart_file("crew.txt", csv);
+        let req = test::TestRequest::post()
+            .uri(format!("{}/preview", uri).as_str())
+            .insert_header((http::header::CONTENT_TYPE, content_type))
+            .set_payload(body)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::BAD_REQUEST);
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn event_exports(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let staff_repo = web::Data::new(StaffRepository::new(arc_pool.clone()));
+        let assigned_staff_repo = web::Data::new(AssignedStaffRepository::new(arc_pool.clone()));
+
+        let app = test::init_service(
+            App::new()
+                .app_data(staff_repo.clone())
+                .app_data(assigned_staff_repo.clone())
+                .service(export_event_staff)
+                .service(export_event_tasks),
+        )
+        .await;
+
+        let event_id = "b71fd7ce-c891-410a-9bb4-70fc5c7748f8";
+        let organizer_id = "35341253-da20-40b6-96d8-ce069b1ba5d4";
+        let req = test::TestRequest::get()
+            .uri(format!("/event/{}/export/{}/staff", event_id, organizer_id).as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status(), http::StatusCode::OK);
+        assert_eq!(
+            res.headers().get(http::header::CONTENT_TYPE).unwrap(),
+            "text/csv; charset=utf-8"
+        );
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("Name,Email,Birth Date,Company,Role,Accepted At"));
+        assert!(body.contains("Tana Smith,t.smith@seznam.cz,1994-02-10"));
+
+        let req = test::TestRequest::get()
+            .uri(
+                format!(
+                    "/event/{}/export/{}/tasks?format=xlsx",
+                    event_id, organizer_id
+                )
+                .as_str(),
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        assert!(body_bytes.starts_with(b"PK"));
+
+        // User1 is only a staff member of the event.
+        let req = test::TestRequest::get()
+            .uri(
+                format!(
+                    "/event/{}/export/0465041f-fe64-461f-9f71-71e3b97ca85f/staff",
+                    event_id
+                )
+                .as_str(),
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::FORBIDDEN);
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn timesheet_export(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let employment_repo = web::Data::new(EmploymentRepository::new(arc_pool.clone()));
+        let timesheet_repo = web::Data::new(TimesheetRepository::new(arc_pool.clone()));
+
+        let app = test::init_service(
+            App::new()
+                .app_data(employment_repo.clone())
+                .app_data(timesheet_repo.clone())
+                .service(export_company_timesheets),
+        )
+        .await;
+
+        let company_id = "134d5286-5f55-4637-9b98-223a5820a464";
+        let req = test::TestRequest::get()
+            .uri(
+                format!(
+                    "/company/{}/export/35341253-da20-40b6-96d8-ce069b1ba5d4/timesheets?month=1969-08",
+                    company_id
+                )
+                .as_str(),
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        // Timesheet workdays of July are left out.
+        assert_eq!(body.lines().count(), 1 + 2 + 3 + 4);
+        assert!(body.contains("I was overworked as a mule!"));
+        assert!(!body.contains("1969-07-30,"));
+
+        let req = test::TestRequest::get()
+            .uri(
+                format!(
+                    "/company/{}/export/35341253-da20-40b6-96d8-ce069b1ba5d4/timesheets?month=August",
+                    company_id
+                )
+                .as_str(),
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::BAD_REQUEST);
+
+        // User1 is only a manager of the company.
+        let req = test::TestRequest::get()
+            .uri(
+                format!(
+                    "/company/{}/export/0465041f-fe64-461f-9f71-71e3b97ca85f/timesheets?month=1969-08",
+                    company_id
+                )
+                .as_str(),
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::FORBIDDEN);
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn payroll_export(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let company_repo = web::Data::new(CompanyRepository::new(arc_pool.clone()));
+        let employment_repo = web::Data::new(EmploymentRepository::new(arc_pool.clone()));
+        let timesheet_repo = web::Data::new(TimesheetRepository::new(arc_pool.clone()));
+        let amendment_repo = web::Data::new(TimesheetAmendmentRepository::new(arc_pool.clone()));
+
+        let app = test::init_service(
+            App::new()
+                .app_data(company_repo.clone())
+                .app_data(employment_repo.clone())
+                .app_data(timesheet_repo.clone())
+                .app_data(amendment_repo.clone())
+                .service(export_company_payroll),
+        )
+        .await;
+
+        let company_id = "134d5286-5f55-4637-9b98-223a5820a464";
+        let req = test::TestRequest::get()
+            .uri(
+                format!(
+                    "/company/{}/payroll/35341253-da20-40b6-96d8-ce069b1ba5d4?month=1969-08&format=pohoda",
+                    company_id
+                )
+                .as_str(),
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status(), http::StatusCode::OK);
+        assert_eq!(
+            res.headers().get(http::header::CONTENT_TYPE).unwrap(),
+            "application/xml; charset=utf-8"
+        );
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        // Both accepted timesheets of Tana Smith are summed up, the timesheet
+        // of Anna Smeth wasn't accepted yet.
+        // The wage and the employer's insurance.
+        assert_eq!(body.matches("<int:intDoc ").count(), 2);
+        assert!(body.contains("<int:text>Mzda 08/1969 DPČ - Tana Smith</int:text>"));
+        assert!(
+            body.contains("<int:text>Pojištění zaměstnavatele 08/1969 DPČ - Tana Smith</int:text>")
+        );
+        assert!(body.contains("<int:quantity>76.00</int:quantity>"));
+        assert!(!body.contains("Anna Smeth"));
+
+        // User1 is only a manager of the company.
+        let req = test::TestRequest::get()
+            .uri(
+                format!(
+                    "/company/{}/payroll/0465041f-fe64-461f-9f71-71e3b97ca85f?month=1969-08",
+                    company_id
+                )
+                .as_str(),
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::FORBIDDEN);
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn income_confirmations(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let company_repo = web::Data::new(CompanyRepository::new(arc_pool.clone()));
+        let employment_repo = web::Data::new(EmploymentRepository::new(arc_pool.clone()));
+        let timesheet_repo = web::Data::new(TimesheetRepository::new(arc_pool.clone()));
+
+        let app = test::init_service(
+            App::new()
+                .app_data(company_repo.clone())
+                .app_data(employment_repo.clone())
+                .app_data(timesheet_repo.clone())
+                .service(get_income_confirmations)
+                .service(export_income_confirmations),
+        )
+        .await;
+
+        let company_id = "134d5286-5f55-4637-9b98-223a5820a464";
+        let admin_id = "35341253-da20-40b6-96d8-ce069b1ba5d4";
+        let req = test::TestRequest::get()
+            .uri(
+                format!(
+                    "/company/{}/income-confirmation/{}?year=1969",
+                    company_id, admin_id
+                )
+                .as_str(),
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("Employee: Tana Smith"));
+        assert!(body.contains("Months worked: 2"));
+        assert!(body.contains("<td>07/1969</td>"));
+        assert!(!body.contains("Anna Smeth"));
+
+        // Anna Smeth is a manager without any accepted work.
+        let req = test::TestRequest::get()
+            .uri(
+                format!(
+                    "/company/{}/income-confirmation/ac9bf689-a713-4b66-a3d0-41faaf0f8d0c?year=1969",
+                    company_id
+                )
+                .as_str(),
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(!body.contains("Tana Smith"));
+        assert!(body.contains("There is no accepted work in 1969."));
+
+        let req = test::TestRequest::get()
+            .uri(
+                format!(
+                    "/company/{}/income-confirmation/{}/export?year=1969",
+                    company_id, admin_id
+                )
+                .as_str(),
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert_eq!(body.lines().count(), 2);
+        assert!(body.contains("Tana Smith,t.smith@seznam.cz,Dpc,1969,2,99,"));
+
+        let req = test::TestRequest::get()
+            .uri(
+                format!(
+                    "/company/{}/income-confirmation/0465041f-fe64-461f-9f71-71e3b97ca85f/export?year=1969",
+                    company_id
+                )
+                .as_str(),
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::FORBIDDEN);
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn event_budget(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let event_repo = web::Data::new(EventRepository::new(arc_pool.clone()));
+        let budget_repo = web::Data::new(EventBudgetRepository::new(arc_pool.clone()));
+        let staff_repo = web::Data::new(StaffRepository::new(arc_pool.clone()));
+        let timesheet_repo = web::Data::new(TimesheetRepository::new(arc_pool.clone()));
+        let wage_preset_repo = web::Data::new(WagePresetRepository::new(arc_pool.clone()));
+
+        let app = test::init_service(
+            App::new()
+                .app_data(event_repo.clone())
+                .app_data(budget_repo.clone())
+                .app_data(staff_repo.clone())
+                .app_data(timesheet_repo.clone())
+                .app_data(wage_preset_repo.clone())
+                .service(get_event_budget)
+                .service(create_budget_line)
+                .service(delete_budget_line),
+        )
+        .await;
+
+        let event_id = "b71fd7ce-c891-410a-9bb4-70fc5c7748f8";
+        let organizer_id = "35341253-da20-40b6-96d8-ce069b1ba5d4";
+        let uri = format!("/event/{}/budget/{}", event_id, organizer_id);
+
+        let req = test::TestRequest::post()
+            .uri(uri.as_str())
+            .set_json(json!({
+                "company_id": null,
+                "role": "Staff",
+                "date": null,
+                "headcount": 1,
+                "hours": 8.0,
+                "hourly_rate": 250.0,
+            }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("Budget of Woodstock"));
+        // Four days of eight hours, Tana Smith is the only accepted staff.
+        // Her 6400 exceed the DPC limit, the employer pays 33.8 % insurance.
+        assert!(body.contains("8000.00"));
+        assert!(body.contains("8563.20"));
+        assert!(body.contains("Every day"));
+
+        let lines = budget_repo
+            .read_all_for_event(Uuid::parse_str(event_id).unwrap())
+            .await?;
+        assert_eq!(lines.len(), 1);
+
+        // The day has to be within the event.
+        let req = test::TestRequest::post()
+            .uri(uri.as_str())
+            .set_json(json!({
+                "company_id": null,
+                "role": "Staff",
+                "date": "1969-08-20",
+                "headcount": 1,
+                "hours": 8.0,
+                "hourly_rate": 250.0,
+            }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::BAD_REQUEST);
+
+        // Company 1 isn't associated with Woodstock.
+        let req = test::TestRequest::post()
+            .uri(uri.as_str())
+            .set_json(json!({
+                "company_id": "134d5286-5f55-4637-9b98-223a5820a464",
+                "role": "Staff",
+                "date": null,
+                "headcount": 1,
+                "hours": 8.0,
+                "hourly_rate": 250.0,
+            }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::get()
+            .uri(
+                format!(
+                    "/event/{}/budget/0465041f-fe64-461f-9f71-71e3b97ca85f",
+                    event_id
+                )
+                .as_str(),
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::FORBIDDEN);
+
+        let req = test::TestRequest::delete()
+            .uri(format!("{}/{}", uri, lines[0].id).as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(!body.contains("Every day"));
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn reports(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let user_repo = web::Data::new(UserRepository::new(arc_pool.clone()));
+        let company_repo = web::Data::new(CompanyRepository::new(arc_pool.clone()));
+        let report_repo = web::Data::new(ReportRepository::new(arc_pool.clone()));
+
+        let app = test::init_service(
+            App::new()
+                .app_data(user_repo.clone())
+                .app_data(company_repo.clone())
+                .app_data(report_repo.clone())
+                .service(get_reports)
+                .service(export_report),
+        )
+        .await;
+
+        let admin_id = "35341253-da20-40b6-96d8-ce069b1ba5d4";
+        let req = test::TestRequest::get()
+            .uri(
+                format!(
+                    "/admin/reports?user_id={}&date_from=1969-07-01&date_to=1969-08-31&company_id=",
+                    admin_id
+                )
+                .as_str(),
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("121.0 hours reported"));
+        assert!(body.contains("Woodstock"));
+        assert!(body.contains("No timesheets were submitted in the period."));
+
+        let req = test::TestRequest::get()
+            .uri(
+                format!(
+                    "/admin/reports/export?user_id={}&report=hours&date_from=1969-07-01&date_to=1969-08-31&company_id=134d5286-5f55-4637-9b98-223a5820a464&format=csv",
+                    admin_id
+                )
+                .as_str(),
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert_eq!(body.lines().count(), 4);
+        assert!(body.contains("1969-08,Woodstock,ReportLab,46,24,2"));
+
+        let req = test::TestRequest::get()
+            .uri(
+                format!(
+                    "/admin/reports?user_id={}&date_from=1969-08-31&date_to=1969-07-01",
+                    admin_id
+                )
+                .as_str(),
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::BAD_REQUEST);
+
+        // Tana Smith isn't an administrator.
+        let req = test::TestRequest::get()
+            .uri("/admin/reports?user_id=0465041f-fe64-461f-9f71-71e3b97ca85f")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::FORBIDDEN);
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn submission_policy_and_overdue_timesheets(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+        let submission_policy_repository = SubmissionPolicyRepository::new(arc_pool.clone());
+        let timesheet_repository = TimesheetRepository::new(arc_pool.clone());
+        let employment_repo = web::Data::new(EmploymentRepository::new(arc_pool.clone()));
+        let submission_policy_repo = web::Data::new(submission_policy_repository.clone());
+        let app = test::init_service(
+            App::new()
+                .app_data(employment_repo.clone())
+                .app_data(submission_policy_repo.clone())
+                .service(get_submission_policy)
+                .service(update_submission_policy)
+                .service(delete_submission_policy)
+                .service(get_overdue_timesheets),
+        )
+        .await;
+
+        let company_id = "134d5286-5f55-4637-9b98-223a5820a464";
+        let admin_id = "35341253-da20-40b6-96d8-ce069b1ba5d4";
+        let employee_id = "0465041f-fe64-461f-9f71-71e3b97ca85f";
+        let policy_uri = format!("/company/{}/submission-policy/{}", company_id, admin_id);
+
+        let req = test::TestRequest::get()
+            .uri(policy_uri.as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("The timesheets have no deadline."));
+
+        let req = test::TestRequest::get()
+            .uri(format!("/company/{}/submission-policy/{}", company_id, employee_id).as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::FORBIDDEN);
+
+        for (deadline_day, reminder_days) in [("29", "3"), ("five", "3"), ("5", "1, -1")] {
+            let req = test::TestRequest::put()
+                .uri(policy_uri.as_str())
+                .set_json(json!({
+                    "deadline_day": deadline_day,
+                    "reminder_days": reminder_days,
+                    "overdue_policy": "Lock"
+                }))
+                .to_request();
+            let res = test::call_service(&app, req).await;
+            assert_eq!(res.status(), http::StatusCode::BAD_REQUEST);
+        }
+
+        let req = test::TestRequest::put()
+            .uri(policy_uri.as_str())
+            .set_json(json!({
+                "deadline_day": "5",
+                "reminder_days": "1, 7, 3",
+                "overdue_policy": "Lock"
+            }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("due on day 5 of the next month"));
+        assert!(body.contains("7, 3, 1"));
+
+        // Tana Smith's accepted sheet is sent back long after its deadline,
+        // which gives it a new one, and then misses that as well.
+        let timesheet_id = Uuid::from_str("0f0f0ff5-0073-47cc-bd1f-540a04fee9ea").unwrap();
+        timesheet_repository
+            .transition(
+                timesheet_id,
+                TimesheetTransitionData {
+                    actor_id: Uuid::from_str(admin_id).unwrap(),
+                    transition: TimesheetTransition::Reopen,
+                    note: None,
+                },
+            )
+            .await?;
+        submission_policy_repository
+            .apply_overdue_policies(NaiveDate::from_ymd_opt(1969, 9, 6).unwrap())
+            .await?;
+        assert!(!submission_policy_repository
+            .read_overdue(Uuid::from_str(admin_id).unwrap())
+            .await?
+            .iter()
+            .any(|sheet| sheet.id == timesheet_id));
+        submission_policy_repository
+            .apply_overdue_policies(NaiveDate::from_ymd_opt(2100, 1, 1).unwrap())
+            .await?;
+        let deadline = submission_policy_repository
+            .read_overdue(Uuid::from_str(admin_id).unwrap())
+            .await?
+            .into_iter()
+            .find(|sheet| sheet.id == timesheet_id)
+            .expect("The sheet should be overdue")
+            .deadline;
+        assert!(deadline > NaiveDate::from_ymd_opt(1969, 9, 5).unwrap());
+
+        let req = test::TestRequest::get()
+            .uri(format!("/user/{}/sheets-overdue", admin_id).as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains(&format!("overdue-{}", timesheet_id)));
+        assert!(body.contains("Tana Smith"));
+        assert!(body.contains(&deadline.format("%d.%m.%Y").to_string()));
+        assert!(body.contains("Locked"));
+
+        let req = test::TestRequest::get()
+            .uri(format!("/user/{}/sheets-overdue", employee_id).as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("Every sheet was submitted on time."));
+
+        let req = test::TestRequest::delete()
+            .uri(policy_uri.as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("The timesheets have no deadline."));
+
+        let req = test::TestRequest::delete()
+            .uri(policy_uri.as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::NOT_FOUND);
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn timesheet_amendments(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+        let amendment_repository = TimesheetAmendmentRepository::new(arc_pool.clone());
+        let company_repo = web::Data::new(CompanyRepository::new(arc_pool.clone()));
+        let employment_repo = web::Data::new(EmploymentRepository::new(arc_pool.clone()));
+        let timesheet_repo = web::Data::new(TimesheetRepository::new(arc_pool.clone()));
+        let amendment_repo = web::Data::new(amendment_repository.clone());
+        let app = test::init_service(
+            App::new()
+                .app_data(company_repo.clone())
+                .app_data(employment_repo.clone())
+                .app_data(timesheet_repo.clone())
+                .app_data(amendment_repo.clone())
+                .service(get_timesheet_amendments)
+                .service(propose_timesheet_amendment)
+                .service(decide_timesheet_amendment)
+                .service(export_company_payroll),
+        )
+        .await;
+
+        let company_id = "134d5286-5f55-4637-9b98-223a5820a464";
+        let manager_id = "35341253-da20-40b6-96d8-ce069b1ba5d4";
+        let employee_id = "0465041f-fe64-461f-9f71-71e3b97ca85f";
+        let stranger_id = "ac9bf689-a713-4b66-a3d0-41faaf0f8d0c";
+        let timesheet_id = "0f0f0ff5-0073-47cc-bd1f-540a04fee9ea";
+        let amendments_uri = format!("/timesheet/{}/amendments", timesheet_id);
+        let proposal = |user_id: &str, date: &str, total_hours: f32| {
+            json!({
+                "user_id": user_id,
+                "reason": "Forgot the teardown.",
+                "days": [{"date": date, "total_hours": total_hours, "comment": "Teardown"}]
+            })
+        };
+
+        let req = test::TestRequest::get()
+            .uri(format!("{}?user_id={}", amendments_uri, employee_id).as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("The sheet wasn't corrected yet."));
+
+        let req = test::TestRequest::post()
+            .uri(amendments_uri.as_str())
+            .set_json(proposal(stranger_id, "1969-08-15", 6.5))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::FORBIDDEN);
+
+        // The sheet has no workday on 08-17.
+        for (date, total_hours) in [("1969-08-17", 6.5), ("1969-08-15", 25.0)] {
+            let req = test::TestRequest::post()
+                .uri(amendments_uri.as_str())
+                .set_json(proposal(employee_id, date, total_hours))
+                .to_request();
+            let res = test::call_service(&app, req).await;
+            assert_eq!(res.status(), http::StatusCode::BAD_REQUEST);
+        }
+
+        let req = test::TestRequest::post()
+            .uri(amendments_uri.as_str())
+            .set_json(proposal(employee_id, "1969-08-15", 6.5))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("Pending"));
+        assert!(body.contains("- 4.5 hours"));
+        assert!(body.contains("+ 6.5 hours, Teardown"));
+        assert!(body.contains("To pay:"));
+        // The employee can't decide their own amendment.
+        assert!(!body.contains("/decision"));
+
+        let amendment_id = amendment_repository
+            .read_all_for_timesheet(Uuid::from_str(timesheet_id).unwrap())
+            .await?[0]
+            .amendment
+            .id;
+        let decision_uri = format!("{}/{}/decision", amendments_uri, amendment_id);
+
+        let req = test::TestRequest::post()
+            .uri(decision_uri.as_str())
+            .set_json(json!({"user_id": employee_id, "approve": true}))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::FORBIDDEN);
+
+        // Rejecting needs a reason.
+        let req = test::TestRequest::post()
+            .uri(decision_uri.as_str())
+            .set_json(json!({"user_id": manager_id, "approve": false, "note": " "}))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::post()
+            .uri(decision_uri.as_str())
+            .set_json(json!({"user_id": manager_id, "approve": true}))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("Approved by Dave Null"));
+        assert!(body.contains("in the payroll of"));
+        assert!(body.contains("(6.5 hours)"));
+
+        let settlement_month = amendment_repository
+            .read_one(amendment_id)
+            .await?
+            .amendment
+            .settlement_month
+            .unwrap();
+        let req = test::TestRequest::get()
+            .uri(
+                format!(
+                    "/company/{}/payroll/{}?month={}",
+                    company_id,
+                    manager_id,
+                    settlement_month.format("%Y-%m")
+                )
+                .as_str(),
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert_eq!(body.matches("<int:intDoc ").count(), 2);
+        assert!(body.contains(&format!(
+            "<int:text>Oprava mzdy {} DPČ - Tana Smith</int:text>",
+            settlement_month.format("%m/%Y")
+        )));
+        assert!(body.contains(&format!(
+            "<int:text>Oprava pojištění zaměstnavatele {} DPČ - Tana Smith</int:text>",
+            settlement_month.format("%m/%Y")
+        )));
+        assert!(body.contains("<int:quantity>2.00</int:quantity>"));
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn background_jobs(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let user_repo = web::Data::new(UserRepository::new(arc_pool.clone()));
+        let job_repository = JobRepository::new(arc_pool.clone());
+        let job_repo = web::Data::new(job_repository.clone());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(user_repo.clone())
+                .app_data(job_repo.clone())
+                .service(get_jobs)
+                .service(run_job),
+        )
+        .await;
+
+        let admin_id = "35341253-da20-40b6-96d8-ce069b1ba5d4";
+        let req = test::TestRequest::get()
+            .uri(format!("/admin/jobs?user_id={}", admin_id).as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("No job was registered yet."));
+
+        job_repository
+            .register("mail_delivery", std::time::Duration::from_secs(60))
+            .await?;
+        let run = job_repository
+            .claim_due(&["mail_delivery".to_string()], std::time::Duration::ZERO)
+            .await?
+            .unwrap();
+        job_repository.succeed(run.id, "Sent 2 mails.").await?;
+
+        let req = test::TestRequest::post()
+            .uri("/admin/jobs/mail_delivery/run")
+            .set_json(json!({ "user_id": admin_id }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("mail_delivery"));
+        assert!(body.contains("Sent 2 mails."));
+
+        let req = test::TestRequest::post()
+            .uri("/admin/jobs/unknown_job/run")
+            .set_json(json!({ "user_id": admin_id }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::NOT_FOUND);
+
+        // Tana Smith isn't an administrator.
+        let tana_id = "0465041f-fe64-461f-9f71-71e3b97ca85f";
+        let req = test::TestRequest::get()
+            .uri(format!("/admin/jobs?user_id={}", tana_id).as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::FORBIDDEN);
+
+        let req = test::TestRequest::post()
+            .uri("/admin/jobs/mail_delivery/run")
+            .set_json(json!({ "user_id": tana_id }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::FORBIDDEN);
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn timesheet_consistency(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let user_repo = web::Data::new(UserRepository::new(arc_pool.clone()));
+        let timesheet_repo = web::Data::new(TimesheetRepository::new(arc_pool.clone()));
+        let consistency_repo =
+            web::Data::new(TimesheetConsistencyRepository::new(arc_pool.clone()));
+
+        let app = test::init_service(
+            App::new()
+                .app_data(user_repo.clone())
+                .app_data(timesheet_repo.clone())
+                .app_data(consistency_repo.clone())
+                .service(get_timesheet_consistency)
+                .service(repair_timesheet_consistency),
+        )
+        .await;
+
+        let admin_id = "35341253-da20-40b6-96d8-ce069b1ba5d4";
+        let req = test::TestRequest::get()
+            .uri(format!("/admin/timesheet-consistency?user_id={}", admin_id).as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("total-mismatch-8446b2ba-8223-4388-be5f-9efdfc4ea265"));
+        assert!(body.contains("range-mismatch-d47e8141-a77e-4d55-a2d5-4a77de24b6d0"));
+
+        let req = test::TestRequest::post()
+            .uri("/admin/timesheet-consistency/8446b2ba-8223-4388-be5f-9efdfc4ea265/repair")
+            .set_json(json!({ "user_id": admin_id, "issue": "total" }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(!body.contains("Totals Not Matching the Workdays"));
+
+        let req = test::TestRequest::post()
+            .uri("/admin/timesheet-consistency/d47e8141-a77e-4d55-a2d5-4a77de24b6d0/repair")
+            .set_json(json!({ "user_id": admin_id, "issue": "range" }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(!body.contains("range-mismatch-d47e8141-a77e-4d55-a2d5-4a77de24b6d0"));
+
+        // The submitted sheet is still under review.
+        let req = test::TestRequest::post()
+            .uri("/admin/timesheet-consistency/c51e77aa-bd80-42c7-8b8a-003f018328f6/repair")
+            .set_json(json!({ "user_id": admin_id, "issue": "range" }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::post()
+            .uri("/admin/timesheet-consistency/00000000-0000-0000-0000-000000000000/repair")
+            .set_json(json!({ "user_id": admin_id, "issue": "workdays" }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::NOT_FOUND);
+
+        // Tana Smith isn't an administrator.
+        let tana_id = "0465041f-fe64-461f-9f71-71e3b97ca85f";
+        let req = test::TestRequest::get()
+            .uri(format!("/admin/timesheet-consistency?user_id={}", tana_id).as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::FORBIDDEN);
+
+        let req = test::TestRequest::post()
+            .uri("/admin/timesheet-consistency/8446b2ba-8223-4388-be5f-9efdfc4ea265/repair")
+            .set_json(json!({ "user_id": tana_id, "issue": "total" }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::FORBIDDEN);
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn trash(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let user_repo = web::Data::new(UserRepository::new(arc_pool.clone()));
+        let event_repo = web::Data::new(EventRepository::new(arc_pool.clone()));
+        let task_repo = web::Data::new(TaskRepository::new(arc_pool.clone()));
+        let trash_repository = TrashRepository::new(arc_pool.clone());
+        let trash_repo = web::Data::new(trash_repository.clone());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(user_repo.clone())
+                .app_data(event_repo.clone())
+                .app_data(task_repo.clone())
+                .app_data(trash_repo.clone())
+                .service(delete_event)
+                .service(delete_task)
+                .service(get_trash)
+                .service(restore_deletion),
+        )
+        .await;
+
+        let admin_id = "35341253-da20-40b6-96d8-ce069b1ba5d4";
+        let req = test::TestRequest::delete()
+            .uri("/event/task/7ae0c017-fe31-4aac-b767-100d18a8877b")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+
+        let req = test::TestRequest::delete()
+            .uri(
+                format!(
+                    "/event/b71fd7ce-c891-410a-9bb4-70fc5c7748f8?deleted_by={}",
+                    admin_id
+                )
+                .as_str(),
+            )
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert!(res.status().is_success());
+
+        let req = test::TestRequest::get()
+            .uri(format!("/admin/trash?user_id={}", admin_id).as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(body.contains("Woodstock"));
+        assert!(body.contains("Dave Null"));
+        assert!(body.contains("Prepare stage for Joe Cocker"));
+
+        let deletions = trash_repository.read_all().await?;
+        let (event_deletion, task_deletion) = (&deletions[0], &deletions[1]);
+
+        // The task can't come back while its event is in the trash.
+        let req = test::TestRequest::post()
+            .uri(format!("/admin/trash/{}/restore", task_deletion.id).as_str())
+            .set_json(json!({ "user_id": admin_id }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::post()
+            .uri(format!("/admin/trash/{}/restore", event_deletion.id).as_str())
+            .set_json(json!({ "user_id": admin_id }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::OK);
+        let body_bytes = test::read_body(res).await;
+        let body = str::from_utf8(body_bytes.borrow()).unwrap();
+        assert!(!body.contains(event_deletion.id.to_string().as_str()));
+        assert!(body.contains(task_deletion.id.to_string().as_str()));
+
+        let req = test::TestRequest::post()
+            .uri(format!("/admin/trash/{}/restore", event_deletion.id).as_str())
+            .set_json(json!({ "user_id": admin_id }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::NOT_FOUND);
+
+        // Tana Smith isn't an administrator.
+        let tana_id = "0465041f-fe64-461f-9f71-71e3b97ca85f";
+        let req = test::TestRequest::get()
+            .uri(format!("/admin/trash?user_id={}", tana_id).as_str())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::FORBIDDEN);
+
+        let req = test::TestRequest::post()
+            .uri(format!("/admin/trash/{}/restore", task_deletion.id).as_str())
+            .set_json(json!({ "user_id": tana_id }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), http::StatusCode::FORBIDDEN);
+        Ok(())
+    }
 }
@@ -0,0 +1,23 @@
+#!/bin/sh
+# Downloads the published Pohoda XML schemas (version 2), together with the
+# schemas they import, which the payroll export is validated against.
+set -eu
+
+base="${POHODA_SCHEMA_URL:-https://www.stormware.cz/xml/schema/version_2}"
+dir="$(dirname "$0")"
+queue="data.xsd"
+
+while [ -n "$queue" ]; do
+    set -- $queue
+    file="$1"
+    shift
+    queue="$*"
+    if [ -f "$dir/$file" ]; then
+        continue
+    fi
+
+    curl -fsSL "$base/$file" -o "$dir/$file"
+    for location in $(sed -n 's/.*schemaLocation="\([^"]*\)".*/\1/p' "$dir/$file"); do
+        queue="$queue ${location##*/}"
+    done
+done
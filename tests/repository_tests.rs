@@ -252,7 +252,7 @@ pub mod user_repo_tests {
             assert!(user.deleted_at.is_none());
 
             user_repo
-                .delete_user(user_id)
+                .delete_user(user_id, None)
                 .await
                 .expect("Delete should succeed");
 
@@ -293,7 +293,7 @@ pub mod user_repo_tests {
 
             assert!(user.deleted_at.is_none());
 
-            user_repo.delete_user(user_id).await.unwrap();
+            user_repo.delete_user(user_id, None).await.unwrap();
 
             let _ = user_repo.read_one(user_id).await.expect_err("Should fail.");
         }
@@ -304,7 +304,7 @@ pub mod user_repo_tests {
             let user_id = test_constants::USER0_ID;
 
             user_repo
-                .delete_user(user_id)
+                .delete_user(user_id, None)
                 .await
                 .expect_err("Repository should return error on deleting an already deleted user");
         }
@@ -315,7 +315,7 @@ pub mod user_repo_tests {
             let user_id = uuid!("35341253-da20-40b6-96d8-ce069b1ba5d9");
 
             user_repo
-                .delete_user(user_id)
+                .delete_user(user_id, None)
                 .await
                 .expect_err("Repository should return error on deleting a non-existing user");
         }
@@ -711,7 +711,7 @@ pub mod company_repo_tests {
             assert!(company.deleted_at.is_none());
 
             company_repo
-                .delete(company_id)
+                .delete(company_id, None)
                 .await
                 .expect("Delete should succeed");
 
@@ -767,7 +767,7 @@ pub mod company_repo_tests {
 
             assert!(company.deleted_at.is_none());
 
-            company_repo.delete(company_id).await.unwrap();
+            company_repo.delete(company_id, None).await.unwrap();
 
             let _ = company_repo
                 .read_one_extended(company_id)
@@ -785,7 +785,7 @@ pub mod company_repo_tests {
                 .await
                 .expect_err("Read should not succeed");
 
-            company_repo.delete(company_id).await.expect_err(
+            company_repo.delete(company_id, None).await.expect_err(
                 "Repository should return error on deleting an already deleted company",
             );
         }
@@ -796,7 +796,7 @@ pub mod company_repo_tests {
             let company_id = uuid!("b5188eda-528d-48d4-8cee-498e0971f9f9");
 
             company_repo
-                .delete(company_id)
+                .delete(company_id, None)
                 .await
                 .expect_err("Repository should return error on deleting a non-existing company");
         }
@@ -814,12 +814,14 @@ pub mod event_repo_tests {
     use chrono::{NaiveDate, NaiveDateTime, Utc};
     use organization::{
         common::DbResult,
+        models::{ApprovalStatus, EventStatus},
         repositories::{
             event::{
                 event_repo::EventRepository,
-                models::{EventData, EventFilter, NewEvent},
+                models::{EventData, EventFilter, EventStatusData, NewEvent},
             },
             repository::DbRepository,
+            timesheet::{models::TimeRange, timesheet_repo::TimesheetRepository},
         },
     };
     use sqlx::PgPool;
@@ -857,6 +859,8 @@ pub mod event_repo_tests {
         assert_eq!(new_event.avatar_url, "img/default/event.jpg".to_string());
 
         assert!(new_event.accepts_staff);
+        assert_eq!(new_event.status, EventStatus::Draft);
+        assert!(new_event.cancellation_reason.is_none());
 
         let time = NaiveDateTime::from_timestamp_opt(Utc::now().timestamp(), 0).unwrap();
         let time_difference_created = time - new_event.created_at;
@@ -918,6 +922,7 @@ pub mod event_repo_tests {
                 limit: None,
                 offset: None,
                 accepts_staff: None,
+                user_id: None,
             };
 
             let events = event_repo
@@ -941,6 +946,7 @@ pub mod event_repo_tests {
                 limit: None,
                 offset: None,
                 accepts_staff: Some(false),
+                user_id: None,
             };
 
             let events = event_repo
@@ -984,6 +990,7 @@ pub mod event_repo_tests {
                 end_date: Some(NaiveDate::from_ymd_opt(2025, 9, 16).unwrap()),
                 accepts_staff: None,
                 avatar_url: None,
+                editor_id: None,
             };
 
             let updated_event = event_repo
@@ -1016,6 +1023,7 @@ pub mod event_repo_tests {
                 end_date: None,
                 accepts_staff: None,
                 avatar_url: None,
+                editor_id: None,
             };
 
             let _updated_event = event_repo
@@ -1037,6 +1045,7 @@ pub mod event_repo_tests {
                 end_date: None,
                 accepts_staff: None,
                 avatar_url: None,
+                editor_id: None,
             };
 
             let _updated_event = event_repo
@@ -1056,7 +1065,7 @@ pub mod event_repo_tests {
             assert!(event.deleted_at.is_none());
 
             event_repo
-                .delete(event_id)
+                .delete(event_id, None)
                 .await
                 .expect("Delete should succeed");
 
@@ -1073,6 +1082,7 @@ pub mod event_repo_tests {
                 end_date: None,
                 accepts_staff: None,
                 avatar_url: None,
+                editor_id: None,
             };
 
             let _updated_event = event_repo
@@ -1102,7 +1112,7 @@ pub mod event_repo_tests {
 
             assert!(event.deleted_at.is_none());
 
-            event_repo.delete(event_id).await.unwrap();
+            event_repo.delete(event_id, None).await.unwrap();
 
             let _ = event_repo
                 .read_one(event_id)
@@ -1121,7 +1131,7 @@ pub mod event_repo_tests {
                 .expect_err("Read should not succeed");
 
             event_repo
-                .delete(event_id)
+                .delete(event_id, None)
                 .await
                 .expect_err("Repository should return error on deleting an already deleted event");
         }
@@ -1132,7 +1142,7 @@ pub mod event_repo_tests {
             let event_id = uuid!("b71fd7ce-c891-410a-9bb4-70fc5c7748f9");
 
             event_repo
-                .delete(event_id)
+                .delete(event_id, None)
                 .await
                 .expect_err("Repository should return error on deleting a non-existing event");
         }
@@ -1141,6 +1151,300 @@ pub mod event_repo_tests {
 
         Ok(())
     }
+
+    #[sqlx::test(fixtures("events"), migrations = "migrations/no_seed")]
+    async fn read_all_drafts(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut event_repo = EventRepository::new(arc_pool);
+
+        let new_event_data = NewEvent {
+            creator_id: USER0_ID,
+            company_id: COMPANY0_ID,
+            name: "Draft Event".to_string(),
+            description: None,
+            website: None,
+            start_date: NaiveDate::from_ymd_opt(2021, 9, 15).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2021, 9, 16).unwrap(),
+        };
+
+        let draft = event_repo
+            .create(new_event_data)
+            .await
+            .expect("Create should succeed");
+
+        // Hidden from the public listing
+
+        {
+            let filter = EventFilter {
+                limit: None,
+                offset: None,
+                accepts_staff: None,
+                user_id: None,
+            };
+
+            let events = event_repo
+                .read_all(filter)
+                .await
+                .expect("Read all should succeed");
+
+            assert_eq!(events.len(), 2);
+            assert!(events.iter().all(|event| event.id != draft.id));
+        }
+
+        // Listed for its organizer
+
+        {
+            let filter = EventFilter {
+                limit: None,
+                offset: None,
+                accepts_staff: None,
+                user_id: Some(USER0_ID),
+            };
+
+            let events = event_repo
+                .read_all(filter)
+                .await
+                .expect("Read all should succeed");
+
+            assert_eq!(events.len(), 3);
+            assert!(events.iter().any(|event| event.id == draft.id));
+        }
+
+        event_repo.disconnect().await;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("events"), migrations = "migrations/no_seed")]
+    async fn change_status(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut event_repo = EventRepository::new(arc_pool);
+
+        let event_id = test_constants::EVENT0_ID;
+
+        // Published events can't go back to drafts
+
+        {
+            let data = EventStatusData {
+                status: EventStatus::Draft,
+                cancellation_reason: None,
+                changed_by: USER0_ID,
+            };
+
+            event_repo
+                .change_status(event_id, data)
+                .await
+                .expect_err("Change should fail - invalid transition");
+        }
+
+        // Cancelling requires a reason
+
+        {
+            let data = EventStatusData {
+                status: EventStatus::Cancelled,
+                cancellation_reason: None,
+                changed_by: USER0_ID,
+            };
+
+            event_repo
+                .change_status(event_id, data)
+                .await
+                .expect_err("Change should fail - missing cancellation reason");
+        }
+
+        // Correct cancellation
+
+        {
+            let data = EventStatusData {
+                status: EventStatus::Cancelled,
+                cancellation_reason: Some("Heavy rain.".to_string()),
+                changed_by: USER0_ID,
+            };
+
+            let event = event_repo
+                .change_status(event_id, data)
+                .await
+                .expect("Change should succeed");
+
+            assert_eq!(event.status, EventStatus::Cancelled);
+            assert_eq!(event.cancellation_reason, Some("Heavy rain.".to_string()));
+            assert!(!event.accepts_staff);
+        }
+
+        // Cancelled events are final
+
+        {
+            let data = EventStatusData {
+                status: EventStatus::Completed,
+                cancellation_reason: None,
+                changed_by: USER0_ID,
+            };
+
+            event_repo
+                .change_status(event_id, data)
+                .await
+                .expect_err("Change should fail - the event is cancelled");
+        }
+
+        // Non existent
+
+        {
+            let data = EventStatusData {
+                status: EventStatus::Completed,
+                cancellation_reason: None,
+                changed_by: USER0_ID,
+            };
+
+            event_repo
+                .change_status(uuid!("b71fd7ce-c891-410a-9bb4-70fc5c7748f9"), data)
+                .await
+                .expect_err("Change should fail - non existent event");
+        }
+
+        event_repo.disconnect().await;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn cancellation_closes_timesheets(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut event_repo = EventRepository::new(arc_pool.clone());
+        let timesheet_repo = TimesheetRepository::new(arc_pool);
+
+        // Event 1 has a submitted sheet waiting for a review.
+        let event_id = uuid!("3f152d12-0bbd-429a-a9c5-28967d6370cc");
+        let timesheet_id = uuid!("c51e77aa-bd80-42c7-8b8a-003f018328f6");
+
+        event_repo
+            .change_status(
+                event_id,
+                EventStatusData {
+                    status: EventStatus::Cancelled,
+                    cancellation_reason: Some("Heavy rain.".to_string()),
+                    changed_by: USER0_ID,
+                },
+            )
+            .await
+            .expect("Change should succeed");
+
+        let timesheet = timesheet_repo
+            ._read_one(timesheet_id)
+            .await
+            .expect("Read should succeed")
+            .timesheet;
+
+        assert_eq!(timesheet.approval_status, ApprovalStatus::Rejected);
+        assert!(!timesheet.is_editable);
+        assert!(timesheet.manager_note.is_some());
+
+        event_repo.disconnect().await;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn date_change(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut event_repo = EventRepository::new(arc_pool.clone());
+        let timesheet_repo = TimesheetRepository::new(arc_pool);
+
+        let event_id = test_constants::EVENT0_ID;
+        let new_range = TimeRange {
+            start_date: NaiveDate::from_ymd_opt(1969, 8, 16).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(1969, 8, 18).unwrap(),
+        };
+
+        // The impact lists the logged hours outside of the new dates.
+
+        {
+            let impact = event_repo
+                .read_date_change_impact(event_id, &new_range)
+                .await
+                .expect("Read should succeed");
+
+            assert_eq!(impact.len(), 2);
+
+            let editable = impact
+                .iter()
+                .find(|sheet| sheet.timesheet_id == test_constants::TIMESHEET0_ID)
+                .expect("The editable sheet should be affected");
+            assert!(!editable.is_locked());
+            assert_eq!(editable.out_of_range_hours, 12.0);
+            assert_eq!(editable.out_of_range_days, 1);
+
+            let accepted = impact
+                .iter()
+                .find(|sheet| sheet.timesheet_id == test_constants::TIMESHEET1_ID)
+                .expect("The accepted sheet should be affected");
+            assert!(accepted.is_locked());
+            assert_eq!(accepted.out_of_range_hours, 14.5);
+            assert_eq!(accepted.out_of_range_days, 2);
+        }
+
+        // Moving the event keeps the logged hours and flags them.
+
+        {
+            let data = EventData {
+                name: None,
+                description: None,
+                website: None,
+                start_date: Some(new_range.start_date),
+                end_date: Some(new_range.end_date),
+                accepts_staff: None,
+                avatar_url: None,
+                editor_id: Some(test_constants::EVENT_STAFF0_ID),
+            };
+
+            event_repo
+                .update(event_id, data)
+                .await
+                .expect("Update should succeed");
+
+            let editable = timesheet_repo
+                ._read_one(test_constants::TIMESHEET0_ID)
+                .await
+                .expect("Read should succeed");
+            assert_eq!(editable.timesheet.start_date, new_range.start_date);
+            assert_eq!(editable.timesheet.end_date, new_range.end_date);
+            assert_eq!(editable.timesheet.total_hours, 22.0);
+            let kept_day = editable
+                .workdays
+                .iter()
+                .find(|workday| workday.date == NaiveDate::from_ymd_opt(1969, 8, 15).unwrap())
+                .expect("The logged day should be kept");
+            assert_eq!(kept_day.total_hours, 12.0);
+
+            let review = timesheet_repo
+                .read_review(test_constants::TIMESHEET0_ID)
+                .await
+                .expect("Read should succeed");
+            assert_eq!(review.flags.len(), 1);
+            assert_eq!(
+                review.flags[0].date,
+                NaiveDate::from_ymd_opt(1969, 8, 15).unwrap()
+            );
+            assert_eq!(review.flags[0].flagged_by, USER0_ID);
+
+            let accepted = timesheet_repo
+                ._read_one(test_constants::TIMESHEET1_ID)
+                .await
+                .expect("Read should succeed");
+            assert_eq!(
+                accepted.timesheet.start_date,
+                NaiveDate::from_ymd_opt(1969, 8, 14).unwrap()
+            );
+            assert_eq!(accepted.workdays.len(), 3);
+        }
+
+        event_repo.disconnect().await;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -1840,7 +2144,7 @@ pub mod employment_repo_tests {
             assert!(employment.deleted_at.is_none());
 
             employment_repo
-                .delete(user_id, company_id)
+                .delete(user_id, company_id, None)
                 .await
                 .expect("Delete should succeed");
 
@@ -1882,7 +2186,10 @@ pub mod employment_repo_tests {
 
             assert!(employment.deleted_at.is_none());
 
-            employment_repo.delete(user_id, company_id).await.unwrap();
+            employment_repo
+                .delete(user_id, company_id, None)
+                .await
+                .unwrap();
 
             let _new_employment = employment_repo
                 .read_one(user_id, company_id)
@@ -1902,7 +2209,7 @@ pub mod employment_repo_tests {
                 .expect_err("Read should not succeed");
 
             employment_repo
-                .delete(user_id, company_id)
+                .delete(user_id, company_id, None)
                 .await
                 .expect_err(
                     "Repository should return error on deleting an already deleted employment",
@@ -1916,7 +2223,7 @@ pub mod employment_repo_tests {
             let user_id = test_constants::USER1_ID;
 
             employment_repo
-                .delete(user_id, company_id)
+                .delete(user_id, company_id, None)
                 .await
                 .expect_err("Repository should return error on deleting a non-existing employment");
         }
@@ -1932,16 +2239,17 @@ pub mod employment_repo_tests {
 pub mod event_staff_repo_tests {
     use std::sync::Arc;
 
-    use chrono::{NaiveDateTime, Utc};
+    use chrono::{NaiveDate, NaiveDateTime, Utc};
     use organization::{
         common::DbResult,
-        models::{AcceptanceStatus, EventRole},
+        models::{AcceptanceStatus, ApprovalStatus, EventRole},
         repositories::{
             event_staff::{
                 event_staff_repo::StaffRepository,
                 models::{NewStaff, StaffData, StaffFilter},
             },
             repository::DbRepository,
+            timesheet::{models::WorkdayUpdateData, timesheet_repo::TimesheetRepository},
         },
     };
     use sqlx::PgPool;
@@ -2227,6 +2535,148 @@ pub mod event_staff_repo_tests {
 
         Ok(())
     }
+
+    #[sqlx::test(fixtures("event_staff"), migrations = "migrations/no_seed")]
+    async fn timesheet_provisioning(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut event_staff_repo = StaffRepository::new(arc_pool.clone());
+        let timesheet_repo = TimesheetRepository::new(arc_pool);
+
+        let event_staff_id = test_constants::EVENT_STAFF1_ID;
+        let decide = |status| StaffData {
+            role: None,
+            status: Some(status),
+            decided_by: Some(test_constants::EVENT_STAFF0_ID),
+        };
+
+        // Accepting the staff gives them a sheet with a workday for every day of the event.
+
+        event_staff_repo
+            .update(event_staff_id, decide(AcceptanceStatus::Accepted))
+            .await
+            .expect("Update should succeed");
+
+        let timesheet = timesheet_repo
+            .read_one_for_staff(
+                test_constants::USER1_ID,
+                test_constants::COMPANY1_ID,
+                test_constants::EVENT0_ID,
+            )
+            .await
+            .expect("Read should succeed")
+            .expect("The sheet should be provisioned");
+        assert_eq!(
+            timesheet.start_date,
+            NaiveDate::from_ymd_opt(1969, 8, 15).unwrap()
+        );
+        assert_eq!(
+            timesheet.end_date,
+            NaiveDate::from_ymd_opt(1969, 8, 18).unwrap()
+        );
+        assert_eq!(timesheet.approval_status, ApprovalStatus::NotRequested);
+
+        let workdays = timesheet_repo
+            ._read_one(timesheet.id)
+            .await
+            .expect("Read should succeed")
+            .workdays;
+        assert_eq!(workdays.len(), 4);
+
+        // Accepting them again keeps the same sheet.
+
+        event_staff_repo
+            .update(event_staff_id, decide(AcceptanceStatus::Accepted))
+            .await
+            .expect("Update should succeed");
+
+        let same_timesheet = timesheet_repo
+            .read_one_for_staff(
+                test_constants::USER1_ID,
+                test_constants::COMPANY1_ID,
+                test_constants::EVENT0_ID,
+            )
+            .await
+            .expect("Read should succeed")
+            .expect("The sheet should still exist");
+        assert_eq!(same_timesheet.id, timesheet.id);
+
+        // Rejecting them removes the untouched sheet.
+
+        event_staff_repo
+            .update(event_staff_id, decide(AcceptanceStatus::Rejected))
+            .await
+            .expect("Update should succeed");
+
+        let removed_timesheet = timesheet_repo
+            .read_one_for_staff(
+                test_constants::USER1_ID,
+                test_constants::COMPANY1_ID,
+                test_constants::EVENT0_ID,
+            )
+            .await
+            .expect("Read should succeed");
+        assert!(removed_timesheet.is_none());
+
+        // A sheet with logged hours is kept when the staff is withdrawn.
+
+        event_staff_repo
+            .update(event_staff_id, decide(AcceptanceStatus::Accepted))
+            .await
+            .expect("Update should succeed");
+
+        let timesheet = timesheet_repo
+            .read_one_for_staff(
+                test_constants::USER1_ID,
+                test_constants::COMPANY1_ID,
+                test_constants::EVENT0_ID,
+            )
+            .await
+            .expect("Read should succeed")
+            .expect("The sheet should be provisioned");
+        assert_ne!(timesheet.id, same_timesheet.id);
+
+        let date = NaiveDate::from_ymd_opt(1969, 8, 16).unwrap();
+        timesheet_repo
+            .update_workday(
+                timesheet.id,
+                date,
+                WorkdayUpdateData {
+                    timesheet_id: timesheet.id,
+                    date,
+                    total_hours: Some(8.0),
+                    comment: None,
+                },
+            )
+            .await
+            .expect("Update should succeed");
+
+        event_staff_repo
+            .update(event_staff_id, decide(AcceptanceStatus::Pending))
+            .await
+            .expect("Update should succeed");
+
+        event_staff_repo
+            .delete(event_staff_id)
+            .await
+            .expect("Delete should succeed");
+
+        let kept_timesheet = timesheet_repo
+            .read_one_for_staff(
+                test_constants::USER1_ID,
+                test_constants::COMPANY1_ID,
+                test_constants::EVENT0_ID,
+            )
+            .await
+            .expect("Read should succeed")
+            .expect("The sheet with logged hours should be kept");
+        assert_eq!(kept_timesheet.id, timesheet.id);
+        assert_eq!(kept_timesheet.total_hours, 8.0);
+
+        event_staff_repo.disconnect().await;
+
+        Ok(())
+    }
 }
 
 // event, event_staff
@@ -2235,11 +2685,11 @@ pub mod task_repo_tests {
     use chrono::{NaiveDateTime, Utc};
     use organization::{
         common::DbResult,
-        models::TaskPriority,
+        models::{TaskPriority, TaskStatus},
         repositories::{
             repository::DbRepository,
             task::{
-                models::{NewTask, TaskData, TaskFilter},
+                models::{NewTask, TaskData, TaskFilter, TaskMoveData},
                 task_repo::TaskRepository,
             },
         },
@@ -2262,6 +2712,9 @@ pub mod task_repo_tests {
             description: Some("Test Description".to_string()),
             title: "Test Title".to_string(),
             priority: TaskPriority::High,
+            due_at: None,
+            estimated_hours: None,
+            parent_task_id: None,
         };
 
         let new_task = task_repo
@@ -2391,6 +2844,10 @@ pub mod task_repo_tests {
                 ),
                 priority: Some(TaskPriority::Low),
                 accepts_staff: Some(false),
+                status: None,
+                due_at: None,
+                estimated_hours: None,
+                parent_task_id: None,
             };
 
             let updated_task = task_repo
@@ -2426,6 +2883,10 @@ pub mod task_repo_tests {
                 finished_at: None,
                 priority: None,
                 accepts_staff: None,
+                status: None,
+                due_at: None,
+                estimated_hours: None,
+                parent_task_id: None,
             };
 
             let _updated_task = task_repo
@@ -2445,6 +2906,10 @@ pub mod task_repo_tests {
                 finished_at: None,
                 priority: Some(TaskPriority::Low),
                 accepts_staff: Some(false),
+                status: None,
+                due_at: None,
+                estimated_hours: None,
+                parent_task_id: None,
             };
 
             let _updated_task = task_repo
@@ -2466,7 +2931,7 @@ pub mod task_repo_tests {
             assert!(task.deleted_at.is_none());
 
             task_repo
-                .delete(task_id)
+                .delete(task_id, None)
                 .await
                 .expect("Delete should succeed");
 
@@ -2483,6 +2948,10 @@ pub mod task_repo_tests {
                 finished_at: None,
                 priority: Some(TaskPriority::Low),
                 accepts_staff: Some(false),
+                status: None,
+                due_at: None,
+                estimated_hours: None,
+                parent_task_id: None,
             };
 
             let _updated_task = task_repo
@@ -2497,40 +2966,279 @@ pub mod task_repo_tests {
     }
 
     #[sqlx::test(fixtures("task"), migrations = "migrations/no_seed")]
-    async fn delete(pool: PgPool) -> DbResult<()> {
+    async fn read_board_for_event(pool: PgPool) -> DbResult<()> {
         let arc_pool = Arc::new(pool);
 
         let mut task_repo = TaskRepository::new(arc_pool);
 
-        {
-            let task_id = test_constants::TASK0_ID;
+        let board = task_repo
+            .read_board_for_event(test_constants::EVENT0_ID)
+            .await
+            .expect("Read should succeed");
 
-            let task = task_repo
-                .read_one(task_id)
-                .await
-                .expect("Read should succeed");
+        assert_eq!(board.len(), 2);
+        assert!(board.iter().all(|task| task.status == TaskStatus::Todo));
+
+        let updated_task = task_repo
+            .update(
+                test_constants::TASK1_ID,
+                TaskData {
+                    title: None,
+                    description: None,
+                    finished_at: None,
+                    priority: None,
+                    accepts_staff: None,
+                    status: Some(TaskStatus::Done),
+                    due_at: None,
+                    estimated_hours: Some(4.5),
+                    parent_task_id: None,
+                },
+            )
+            .await
+            .expect("Update should succeed");
 
-            assert!(task.deleted_at.is_none());
+        assert_eq!(updated_task.status, TaskStatus::Done);
+        assert_eq!(updated_task.estimated_hours, Some(4.5));
+        assert!(updated_task.finished_at.is_some());
 
-            task_repo.delete(task_id).await.unwrap();
+        let board = task_repo
+            .read_board_for_event(test_constants::EVENT0_ID)
+            .await
+            .expect("Read should succeed");
 
-            let new_task = task_repo
-                .read_one(task_id)
-                .await
-                .expect("Read should succeed");
+        assert_eq!(board.len(), 2);
+        assert_eq!(board[0].task_id, test_constants::TASK0_ID);
+        assert_eq!(board[0].status, TaskStatus::Todo);
+        assert_eq!(board[1].task_id, test_constants::TASK1_ID);
+        assert_eq!(board[1].status, TaskStatus::Done);
 
-            let time = NaiveDateTime::from_timestamp_opt(Utc::now().timestamp(), 0).unwrap();
-            let time_difference_edited = time - new_task.edited_at;
-            let time_difference_deleted = time - new_task.deleted_at.unwrap();
+        task_repo.disconnect().await;
 
-            assert!(time_difference_edited.num_seconds() < 2);
-            assert!(time_difference_deleted.num_seconds() < 2);
-        }
+        Ok(())
+    }
 
-        // delete on already deleted task
+    #[sqlx::test(fixtures("task"), migrations = "migrations/no_seed")]
+    async fn move_task(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut task_repo = TaskRepository::new(arc_pool);
+
+        // Move into another column
 
         {
-            let task_id = test_constants::TASK0_ID;
+            let moved_task = task_repo
+                .move_task(
+                    test_constants::TASK0_ID,
+                    TaskMoveData {
+                        status: TaskStatus::Done,
+                        position: 0,
+                    },
+                )
+                .await
+                .expect("Move should succeed");
+
+            assert_eq!(moved_task.status, TaskStatus::Done);
+            assert_eq!(moved_task.position, 0);
+            assert!(moved_task.finished_at.is_some());
+        }
+
+        // Reorder within a column
+
+        {
+            let moved_task = task_repo
+                .move_task(
+                    test_constants::TASK1_ID,
+                    TaskMoveData {
+                        status: TaskStatus::Done,
+                        position: 0,
+                    },
+                )
+                .await
+                .expect("Move should succeed");
+
+            assert_eq!(moved_task.position, 0);
+
+            let other_task = task_repo
+                .read_one(test_constants::TASK0_ID)
+                .await
+                .expect("Read should succeed");
+
+            assert_eq!(other_task.status, TaskStatus::Done);
+            assert_eq!(other_task.position, 1);
+        }
+
+        // Position past the end of the column gets clamped
+
+        {
+            let moved_task = task_repo
+                .move_task(
+                    test_constants::TASK0_ID,
+                    TaskMoveData {
+                        status: TaskStatus::InProgress,
+                        position: 42,
+                    },
+                )
+                .await
+                .expect("Move should succeed");
+
+            assert_eq!(moved_task.status, TaskStatus::InProgress);
+            assert_eq!(moved_task.position, 0);
+            assert!(moved_task.finished_at.is_none());
+        }
+
+        // Invalid moves
+
+        {
+            task_repo
+                .move_task(
+                    test_constants::TASK0_ID,
+                    TaskMoveData {
+                        status: TaskStatus::Todo,
+                        position: -1,
+                    },
+                )
+                .await
+                .expect_err("Move should fail - negative position");
+
+            task_repo
+                .move_task(
+                    uuid!("a96d1d99-93b5-469b-ac62-654b0cf7ebd9"),
+                    TaskMoveData {
+                        status: TaskStatus::Todo,
+                        position: 0,
+                    },
+                )
+                .await
+                .expect_err("Move should fail - non existent task");
+        }
+
+        task_repo.disconnect().await;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("task"), migrations = "migrations/no_seed")]
+    async fn subtasks(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut task_repo = TaskRepository::new(arc_pool);
+
+        let subtask = task_repo
+            .create(NewTask {
+                event_id: test_constants::EVENT0_ID,
+                creator_id: test_constants::EVENT_STAFF0_ID,
+                description: None,
+                title: "Bring the cables".to_string(),
+                priority: TaskPriority::Low,
+                due_at: None,
+                estimated_hours: None,
+                parent_task_id: Some(test_constants::TASK0_ID),
+            })
+            .await
+            .expect("Create should succeed");
+
+        assert_eq!(subtask.parent_task_id, Some(test_constants::TASK0_ID));
+
+        let subtasks = task_repo
+            .read_subtasks(test_constants::TASK0_ID)
+            .await
+            .expect("Read should succeed");
+
+        assert_eq!(subtasks.len(), 1);
+        assert_eq!(subtasks[0].task_id, subtask.task_id);
+
+        // The parent can't be finished while the subtask is open.
+        assert!(task_repo
+            .has_open_blockers(test_constants::TASK0_ID)
+            .await
+            .expect("Check should succeed"));
+
+        task_repo
+            .move_task(
+                test_constants::TASK0_ID,
+                TaskMoveData {
+                    status: TaskStatus::Done,
+                    position: 0,
+                },
+            )
+            .await
+            .expect_err("Move should fail - open subtask");
+
+        // A task can't become a subtask of its own subtask.
+        task_repo
+            .update(
+                test_constants::TASK0_ID,
+                TaskData {
+                    title: None,
+                    description: None,
+                    finished_at: None,
+                    priority: None,
+                    accepts_staff: None,
+                    status: None,
+                    due_at: None,
+                    estimated_hours: None,
+                    parent_task_id: Some(subtask.task_id),
+                },
+            )
+            .await
+            .expect_err("Update should fail - cycle");
+
+        task_repo
+            .move_task(
+                subtask.task_id,
+                TaskMoveData {
+                    status: TaskStatus::Done,
+                    position: 0,
+                },
+            )
+            .await
+            .expect("Move should succeed");
+
+        assert!(!task_repo
+            .has_open_blockers(test_constants::TASK0_ID)
+            .await
+            .expect("Check should succeed"));
+
+        task_repo.disconnect().await;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("task"), migrations = "migrations/no_seed")]
+    async fn delete(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut task_repo = TaskRepository::new(arc_pool);
+
+        {
+            let task_id = test_constants::TASK0_ID;
+
+            let task = task_repo
+                .read_one(task_id)
+                .await
+                .expect("Read should succeed");
+
+            assert!(task.deleted_at.is_none());
+
+            task_repo.delete(task_id, None).await.unwrap();
+
+            let new_task = task_repo
+                .read_one(task_id)
+                .await
+                .expect("Read should succeed");
+
+            let time = NaiveDateTime::from_timestamp_opt(Utc::now().timestamp(), 0).unwrap();
+            let time_difference_edited = time - new_task.edited_at;
+            let time_difference_deleted = time - new_task.deleted_at.unwrap();
+
+            assert!(time_difference_edited.num_seconds() < 2);
+            assert!(time_difference_deleted.num_seconds() < 2);
+        }
+
+        // delete on already deleted task
+
+        {
+            let task_id = test_constants::TASK0_ID;
 
             let task = task_repo
                 .read_one(task_id)
@@ -2540,7 +3248,7 @@ pub mod task_repo_tests {
             assert!(task.deleted_at.is_some());
 
             task_repo
-                .delete(task_id)
+                .delete(task_id, None)
                 .await
                 .expect_err("Repository should return error on deleting an already deleted task");
         }
@@ -2551,7 +3259,7 @@ pub mod task_repo_tests {
             let task_id = uuid!("a96d1d99-93b5-469b-ac62-654b0cf7ebd9");
 
             task_repo
-                .delete(task_id)
+                .delete(task_id, None)
                 .await
                 .expect_err("Repository should return error on deleting a non-existing task");
         }
@@ -2562,1192 +3270,4409 @@ pub mod task_repo_tests {
     }
 }
 
-// needs event_staff, task
+// task
 #[cfg(test)]
-pub mod assigned_staff_repo_tests {
-    use std::sync::Arc;
-
-    use chrono::{NaiveDateTime, Utc};
+pub mod task_dependency_repo_tests {
     use organization::{
         common::DbResult,
-        models::AcceptanceStatus,
+        models::{TaskPriority, TaskStatus},
         repositories::{
-            assigned_staff::{
-                assigned_staff_repo::AssignedStaffRepository,
-                models::{AssignedStaffData, AssignedStaffFilter, NewAssignedStaff},
-            },
             repository::DbRepository,
+            task::{
+                models::{NewTask, TaskMoveData},
+                task_repo::TaskRepository,
+            },
+            task_dependency::{
+                models::NewTaskDependency, task_dependency_repo::TaskDependencyRepository,
+            },
         },
     };
     use sqlx::PgPool;
-    use uuid::uuid;
+    use std::sync::Arc;
 
     use crate::test_constants;
 
-    #[sqlx::test(fixtures("assigned_staff"), migrations = "migrations/no_seed")]
-    pub fn create(pool: PgPool) -> DbResult<()> {
+    #[sqlx::test(fixtures("task"), migrations = "migrations/no_seed")]
+    async fn create(pool: PgPool) -> DbResult<()> {
         let arc_pool = Arc::new(pool);
 
-        let mut assigned_staff_repo = AssignedStaffRepository::new(arc_pool);
-
-        let assigned_staff_data = NewAssignedStaff {
-            staff_id: test_constants::EVENT_STAFF1_ID,
-            task_id: test_constants::TASK1_ID,
-        };
+        let mut dependency_repo = TaskDependencyRepository::new(arc_pool.clone());
+        let mut task_repo = TaskRepository::new(arc_pool);
 
-        let new_assigned_staff = assigned_staff_repo
-            .create(assigned_staff_data.clone())
+        let dependency = dependency_repo
+            .create(NewTaskDependency {
+                task_id: test_constants::TASK0_ID,
+                blocker_id: test_constants::TASK1_ID,
+            })
             .await
             .expect("Create should succeed");
 
-        assert_eq!(new_assigned_staff.staff.id, assigned_staff_data.staff_id);
-        assert_eq!(new_assigned_staff.task_id, assigned_staff_data.task_id);
-        assert_eq!(new_assigned_staff.status, AcceptanceStatus::Pending);
+        assert_eq!(dependency.task_id, test_constants::TASK0_ID);
+        assert_eq!(dependency.blocker_id, test_constants::TASK1_ID);
+        assert!(dependency.deleted_at.is_none());
 
-        assert!(new_assigned_staff.decided_by.is_none());
-        assert!(new_assigned_staff.deleted_at.is_none());
+        let blockers = dependency_repo
+            .read_blockers(test_constants::TASK0_ID)
+            .await
+            .expect("Read should succeed");
 
-        let time = NaiveDateTime::from_timestamp_opt(Utc::now().timestamp(), 0).unwrap();
+        assert_eq!(blockers.len(), 1);
+        assert_eq!(blockers[0].task_id, test_constants::TASK1_ID);
 
-        let time_difference_created = time - new_assigned_staff.created_at;
-        let time_difference_edited = time - new_assigned_staff.edited_at;
+        let blocked = dependency_repo
+            .read_blocked(test_constants::TASK1_ID)
+            .await
+            .expect("Read should succeed");
 
-        assert!(time_difference_created.num_seconds() < 2);
-        assert!(time_difference_edited.num_seconds() < 2);
+        assert_eq!(blocked.len(), 1);
+        assert_eq!(blocked[0].task_id, test_constants::TASK0_ID);
 
-        assigned_staff_repo.disconnect().await;
+        let all = dependency_repo
+            .read_all_for_event(test_constants::EVENT0_ID)
+            .await
+            .expect("Read should succeed");
+
+        assert_eq!(all.len(), 1);
+
+        // Invalid edges
+
+        {
+            assert!(dependency_repo
+                .creates_cycle(test_constants::TASK1_ID, test_constants::TASK0_ID)
+                .await
+                .expect("Check should succeed"));
+
+            dependency_repo
+                .create(NewTaskDependency {
+                    task_id: test_constants::TASK1_ID,
+                    blocker_id: test_constants::TASK0_ID,
+                })
+                .await
+                .expect_err("Create should fail - cycle");
+
+            dependency_repo
+                .create(NewTaskDependency {
+                    task_id: test_constants::TASK0_ID,
+                    blocker_id: test_constants::TASK0_ID,
+                })
+                .await
+                .expect_err("Create should fail - self dependency");
+        }
+
+        dependency_repo.disconnect().await;
+        task_repo.disconnect().await;
 
         Ok(())
     }
 
-    #[sqlx::test(fixtures("assigned_staff"), migrations = "migrations/no_seed")]
-    pub fn read_one(pool: PgPool) -> DbResult<()> {
+    #[sqlx::test(fixtures("task"), migrations = "migrations/no_seed")]
+    async fn create_with_subtasks(pool: PgPool) -> DbResult<()> {
         let arc_pool = Arc::new(pool);
 
-        let mut assigned_staff_repo = AssignedStaffRepository::new(arc_pool);
+        let mut dependency_repo = TaskDependencyRepository::new(arc_pool.clone());
+        let mut task_repo = TaskRepository::new(arc_pool);
 
-        let staff_id = test_constants::EVENT_STAFF0_ID;
-        let task_id = test_constants::TASK0_ID;
+        let subtask = task_repo
+            .create(NewTask {
+                event_id: test_constants::EVENT0_ID,
+                creator_id: test_constants::EVENT_STAFF0_ID,
+                title: "Subtask".to_string(),
+                description: None,
+                priority: TaskPriority::Low,
+                due_at: None,
+                estimated_hours: None,
+                parent_task_id: Some(test_constants::TASK0_ID),
+            })
+            .await
+            .expect("Create should succeed");
 
-        let assigned_staff = assigned_staff_repo
-            .read_one(task_id, staff_id)
+        // The parent already waits for its subtask.
+        assert!(dependency_repo
+            .creates_cycle(subtask.task_id, test_constants::TASK0_ID)
             .await
-            .expect("Read should succeed");
+            .expect("Check should succeed"));
 
-        assert_eq!(assigned_staff.staff.user.name, "Dave Null");
-        assert_eq!(assigned_staff.decided_by_user.unwrap().name, "Dave Null");
+        dependency_repo
+            .create(NewTaskDependency {
+                task_id: subtask.task_id,
+                blocker_id: test_constants::TASK0_ID,
+            })
+            .await
+            .expect_err("Create should fail - the parent waits for its subtask");
 
-        assigned_staff_repo.disconnect().await;
+        dependency_repo
+            .create(NewTaskDependency {
+                task_id: test_constants::TASK1_ID,
+                blocker_id: test_constants::TASK0_ID,
+            })
+            .await
+            .expect("Create should succeed");
+
+        dependency_repo
+            .create(NewTaskDependency {
+                task_id: subtask.task_id,
+                blocker_id: test_constants::TASK1_ID,
+            })
+            .await
+            .expect_err("Create should fail - cycle through the parent");
+
+        dependency_repo.disconnect().await;
+        task_repo.disconnect().await;
 
         Ok(())
     }
 
-    #[sqlx::test(fixtures("assigned_staff"), migrations = "migrations/no_seed")]
-    pub fn read_all_per_task(pool: PgPool) -> DbResult<()> {
+    #[sqlx::test(fixtures("task"), migrations = "migrations/no_seed")]
+    async fn create_with_deleted_blocker(pool: PgPool) -> DbResult<()> {
         let arc_pool = Arc::new(pool);
 
-        let mut assigned_staff_repo = AssignedStaffRepository::new(arc_pool);
+        let mut dependency_repo = TaskDependencyRepository::new(arc_pool.clone());
+        let mut task_repo = TaskRepository::new(arc_pool);
 
-        let task_id = test_constants::TASK0_ID;
+        task_repo
+            .delete(test_constants::TASK1_ID, None)
+            .await
+            .expect("Delete should succeed");
 
-        let filter = AssignedStaffFilter {
-            limit: None,
-            offset: None,
-        };
+        dependency_repo
+            .create(NewTaskDependency {
+                task_id: test_constants::TASK0_ID,
+                blocker_id: test_constants::TASK1_ID,
+            })
+            .await
+            .expect_err("Create should fail - deleted blocker");
 
-        let mut assigned_staffs = assigned_staff_repo
-            .read_all_per_task(task_id, filter)
+        dependency_repo
+            .create(NewTaskDependency {
+                task_id: test_constants::TASK0_ID,
+                blocker_id: uuid::Uuid::nil(),
+            })
             .await
-            .expect("Read should succeed");
+            .expect_err("Create should fail - nonexistent blocker");
 
-        assert_eq!(assigned_staffs.len(), 2);
+        dependency_repo.disconnect().await;
+        task_repo.disconnect().await;
 
-        assigned_staffs.sort_by(|a, b| a.staff.user.name.cmp(&b.staff.user.name));
+        Ok(())
+    }
 
-        let assigned_staff = &assigned_staffs[0];
+    #[sqlx::test(fixtures("task"), migrations = "migrations/no_seed")]
+    async fn blocked_completion(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
 
-        assert_eq!(assigned_staff.staff.user.name, "Dave Null");
-        assert_eq!(
-            assigned_staff.decided_by_user.clone().unwrap().name,
-            "Dave Null"
-        );
+        let mut dependency_repo = TaskDependencyRepository::new(arc_pool.clone());
+        let mut task_repo = TaskRepository::new(arc_pool);
 
-        let assigned_staff = &assigned_staffs[1];
+        dependency_repo
+            .create(NewTaskDependency {
+                task_id: test_constants::TASK0_ID,
+                blocker_id: test_constants::TASK1_ID,
+            })
+            .await
+            .expect("Create should succeed");
 
-        assert_eq!(assigned_staff.staff.user.name, "Tana Smith");
-        assert!(assigned_staff.decided_by.clone().is_none());
+        task_repo
+            .move_task(
+                test_constants::TASK0_ID,
+                TaskMoveData {
+                    status: TaskStatus::Done,
+                    position: 0,
+                },
+            )
+            .await
+            .expect_err("Move should fail - open blocker");
+
+        task_repo
+            .move_task(
+                test_constants::TASK1_ID,
+                TaskMoveData {
+                    status: TaskStatus::Done,
+                    position: 0,
+                },
+            )
+            .await
+            .expect("Move should succeed");
 
-        assigned_staff_repo.disconnect().await;
+        let task = task_repo
+            .move_task(
+                test_constants::TASK0_ID,
+                TaskMoveData {
+                    status: TaskStatus::Done,
+                    position: 0,
+                },
+            )
+            .await
+            .expect("Move should succeed");
+
+        assert_eq!(task.status, TaskStatus::Done);
+
+        dependency_repo.disconnect().await;
+        task_repo.disconnect().await;
 
         Ok(())
     }
 
-    #[sqlx::test(fixtures("assigned_staff"), migrations = "migrations/no_seed")]
-    pub fn update(pool: PgPool) -> DbResult<()> {
+    #[sqlx::test(fixtures("task"), migrations = "migrations/no_seed")]
+    async fn delete(pool: PgPool) -> DbResult<()> {
         let arc_pool = Arc::new(pool);
 
-        let mut assigned_staff_repo = AssignedStaffRepository::new(arc_pool);
-
-        // Valid update
+        let mut dependency_repo = TaskDependencyRepository::new(arc_pool);
 
-        {
-            let decider_staff_id = test_constants::EVENT_STAFF0_ID;
+        dependency_repo
+            .create(NewTaskDependency {
+                task_id: test_constants::TASK0_ID,
+                blocker_id: test_constants::TASK1_ID,
+            })
+            .await
+            .expect("Create should succeed");
 
-            let staff_id = test_constants::EVENT_STAFF1_ID;
-            let task_id = test_constants::TASK0_ID;
+        dependency_repo
+            .delete(test_constants::TASK0_ID, test_constants::TASK1_ID)
+            .await
+            .expect("Delete should succeed");
 
-            let assigned_staff_data = AssignedStaffData {
-                status: AcceptanceStatus::Accepted,
-                decided_by: decider_staff_id,
-            };
+        let blockers = dependency_repo
+            .read_blockers(test_constants::TASK0_ID)
+            .await
+            .expect("Read should succeed");
 
-            let updated_assigned_staff = assigned_staff_repo
-                .update(task_id, staff_id, assigned_staff_data.clone())
-                .await
-                .expect("Update should succeed");
+        assert!(blockers.is_empty());
 
-            assert_eq!(updated_assigned_staff.status, AcceptanceStatus::Accepted);
-            assert_eq!(
-                updated_assigned_staff.decided_by,
-                Some(assigned_staff_data.decided_by)
-            );
+        dependency_repo
+            .delete(test_constants::TASK0_ID, test_constants::TASK1_ID)
+            .await
+            .expect_err("Repeated delete should fail");
+
+        // The edge can be added again after deletion.
+        dependency_repo
+            .create(NewTaskDependency {
+                task_id: test_constants::TASK0_ID,
+                blocker_id: test_constants::TASK1_ID,
+            })
+            .await
+            .expect("Create should succeed");
 
-            let time = NaiveDateTime::from_timestamp_opt(Utc::now().timestamp(), 0).unwrap();
+        dependency_repo.disconnect().await;
 
-            let time_difference_edited = time - updated_assigned_staff.edited_at;
-            assert!(time_difference_edited.num_seconds() < 2);
-
-            assert!(updated_assigned_staff.deleted_at.is_none());
-        }
-
-        // Non existent
-
-        {
-            let staff_id = test_constants::EVENT_STAFF1_ID;
-            let task_id = uuid!("a96d1d99-93b5-469b-ac62-654b0cf7ebd9");
+        Ok(())
+    }
+}
 
-            let assigned_staff_data = AssignedStaffData {
-                status: AcceptanceStatus::Accepted,
-                decided_by: test_constants::EVENT_STAFF0_ID,
-            };
+// task
+#[cfg(test)]
+pub mod task_checklist_repo_tests {
+    use organization::{
+        common::DbResult,
+        repositories::{
+            repository::DbRepository,
+            task_checklist::{
+                models::{ChecklistItemData, NewChecklistItem},
+                task_checklist_repo::TaskChecklistRepository,
+            },
+        },
+    };
+    use sqlx::PgPool;
+    use std::sync::Arc;
+    use uuid::uuid;
 
-            let _updated_assigned_staff = assigned_staff_repo
-                .update(task_id, staff_id, assigned_staff_data)
-                .await
-                .expect_err("Update should fail - non existent assigned staff");
-        }
+    use crate::test_constants;
 
-        // Already deleted
+    #[sqlx::test(fixtures("task"), migrations = "migrations/no_seed")]
+    async fn create(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
 
-        {
-            let staff_id = test_constants::EVENT_STAFF1_ID;
-            let task_id = test_constants::TASK0_ID;
+        let mut checklist_repo = TaskChecklistRepository::new(arc_pool);
 
-            let assigned_staff = assigned_staff_repo
-                .read_one(task_id, staff_id)
-                .await
-                .expect("Read should succeed");
+        let first = checklist_repo
+            .create(NewChecklistItem {
+                task_id: test_constants::TASK0_ID,
+                content: "Check the speakers".to_string(),
+            })
+            .await
+            .expect("Create should succeed");
 
-            assert!(assigned_staff.deleted_at.is_none());
+        let second = checklist_repo
+            .create(NewChecklistItem {
+                task_id: test_constants::TASK0_ID,
+                content: "Check the lights".to_string(),
+            })
+            .await
+            .expect("Create should succeed");
 
-            assigned_staff_repo
-                .delete(task_id, staff_id)
-                .await
-                .expect("Delete should succeed");
+        assert_eq!(first.position, 0);
+        assert_eq!(second.position, 1);
+        assert!(first.finished_at.is_none());
 
-            let _ = assigned_staff_repo
-                .read_one(task_id, staff_id)
-                .await
-                .expect_err("Read should not succeed");
+        let items = checklist_repo
+            .read_all_for_task(test_constants::TASK0_ID)
+            .await
+            .expect("Read should succeed");
 
-            let assigned_staff_data = AssignedStaffData {
-                status: AcceptanceStatus::Accepted,
-                decided_by: test_constants::EVENT_STAFF0_ID,
-            };
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].id, first.id);
+        assert_eq!(items[1].id, second.id);
 
-            let _updated_assigned_staff = assigned_staff_repo
-                .update(task_id, staff_id, assigned_staff_data)
-                .await
-                .expect_err("Update should fail - already deleted assigned staff");
-        }
+        checklist_repo
+            .create(NewChecklistItem {
+                task_id: uuid!("a96d1d99-93b5-469b-ac62-654b0cf7ebd9"),
+                content: "Nowhere".to_string(),
+            })
+            .await
+            .expect_err("Create should fail - non existent task");
 
-        assigned_staff_repo.disconnect().await;
+        checklist_repo.disconnect().await;
 
         Ok(())
     }
 
-    #[sqlx::test(fixtures("assigned_staff"), migrations = "migrations/no_seed")]
-    pub fn delete(pool: PgPool) -> DbResult<()> {
+    #[sqlx::test(fixtures("task"), migrations = "migrations/no_seed")]
+    async fn update(pool: PgPool) -> DbResult<()> {
         let arc_pool = Arc::new(pool);
 
-        let mut assigned_staff_repo = AssignedStaffRepository::new(arc_pool);
-
-        {
-            let staff_id = test_constants::EVENT_STAFF1_ID;
-            let task_id = test_constants::TASK0_ID;
+        let mut checklist_repo = TaskChecklistRepository::new(arc_pool);
 
-            let assigned_staff = assigned_staff_repo
-                .read_one(task_id, staff_id)
-                .await
-                .expect("Read should succeed");
+        let item = checklist_repo
+            .create(NewChecklistItem {
+                task_id: test_constants::TASK0_ID,
+                content: "Check the speakers".to_string(),
+            })
+            .await
+            .expect("Create should succeed");
 
-            assert!(assigned_staff.deleted_at.is_none());
+        let finished = checklist_repo
+            .update(
+                item.id,
+                ChecklistItemData {
+                    content: None,
+                    is_finished: Some(true),
+                },
+            )
+            .await
+            .expect("Update should succeed");
+
+        assert!(finished.finished_at.is_some());
+        assert_eq!(finished.content, item.content);
+
+        let reopened = checklist_repo
+            .update(
+                item.id,
+                ChecklistItemData {
+                    content: Some("Check the speakers twice".to_string()),
+                    is_finished: Some(false),
+                },
+            )
+            .await
+            .expect("Update should succeed");
+
+        assert!(reopened.finished_at.is_none());
+        assert_eq!(reopened.content, "Check the speakers twice");
+
+        checklist_repo
+            .update(
+                item.id,
+                ChecklistItemData {
+                    content: None,
+                    is_finished: None,
+                },
+            )
+            .await
+            .expect_err("Update should fail - empty data");
 
-            assigned_staff_repo.delete(task_id, staff_id).await.unwrap();
+        checklist_repo.disconnect().await;
 
-            let _ = assigned_staff_repo
-                .read_one(task_id, staff_id)
-                .await
-                .expect_err("Read should not succeed");
-        }
+        Ok(())
+    }
 
-        // delete on already deleted assigned staff
+    #[sqlx::test(fixtures("task"), migrations = "migrations/no_seed")]
+    async fn delete(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
 
-        {
-            let staff_id = test_constants::EVENT_STAFF1_ID;
-            let task_id = test_constants::TASK0_ID;
+        let mut checklist_repo = TaskChecklistRepository::new(arc_pool);
 
-            let _ = assigned_staff_repo
-                .read_one(task_id, staff_id)
-                .await
-                .expect_err("Read should not succeed");
+        let item = checklist_repo
+            .create(NewChecklistItem {
+                task_id: test_constants::TASK0_ID,
+                content: "Check the speakers".to_string(),
+            })
+            .await
+            .expect("Create should succeed");
 
-            assigned_staff_repo
-                .delete(task_id, staff_id)
-                .await
-                .expect_err(
-                    "Repository should return error on deleting an already deleted assigned staff",
-                );
-        }
+        let deleted = checklist_repo
+            .delete(item.id)
+            .await
+            .expect("Delete should succeed");
 
-        // delete on non-existing assigned staff
+        assert!(deleted.deleted_at.is_some());
 
-        {
-            let staff_id = test_constants::EVENT_STAFF1_ID;
-            let task_id = uuid!("a96d1d99-93b5-469b-ac62-654b0cf7ebd9");
+        checklist_repo
+            .read_one(item.id)
+            .await
+            .expect_err("Read should fail - deleted item");
 
-            assigned_staff_repo
-                .delete(task_id, staff_id)
-                .await
-                .expect_err(
-                    "Repository should return error on deleting a non-existing assigned staff",
-                );
-        }
+        checklist_repo
+            .delete(item.id)
+            .await
+            .expect_err("Repeated delete should fail");
 
-        assigned_staff_repo.disconnect().await;
+        checklist_repo.disconnect().await;
 
         Ok(())
     }
 }
 
-// needs event, task, user
+// needs event, task
 #[cfg(test)]
-pub mod comment_repo_tests {
-    use std::sync::Arc;
-
-    use chrono::{NaiveDateTime, Utc};
+pub mod event_template_repo_tests {
+    use chrono::NaiveDate;
     use organization::{
         common::DbResult,
+        models::TaskPriority,
         repositories::{
-            comment::{
-                comment_repo::CommentRepository,
-                models::{CommentData, CommentFilter, NewComment},
+            event_template::{
+                event_template_repo::EventTemplateRepository,
+                models::{NewEventFromTemplate, NewEventTemplate},
             },
             repository::DbRepository,
+            task::{
+                models::{NewTask, TaskData},
+                task_repo::TaskRepository,
+            },
+            task_checklist::{
+                models::NewChecklistItem, task_checklist_repo::TaskChecklistRepository,
+            },
         },
     };
     use sqlx::PgPool;
+    use std::sync::Arc;
     use uuid::uuid;
 
     use crate::test_constants;
 
-    #[sqlx::test(fixtures("comments"), migrations = "migrations/no_seed")]
-    async fn create(pool: PgPool) -> DbResult<()> {
+    #[sqlx::test(fixtures("task"), migrations = "migrations/no_seed")]
+    async fn create_and_instantiate(pool: PgPool) -> DbResult<()> {
         let arc_pool = Arc::new(pool);
 
-        let mut comment_repo = CommentRepository::new(arc_pool);
+        let mut template_repo = EventTemplateRepository::new(arc_pool.clone());
+        let mut task_repo = TaskRepository::new(arc_pool.clone());
+        let mut checklist_repo = TaskChecklistRepository::new(arc_pool);
+
+        task_repo
+            .update(
+                test_constants::TASK0_ID,
+                TaskData {
+                    title: None,
+                    description: None,
+                    finished_at: None,
+                    priority: Some(TaskPriority::High),
+                    accepts_staff: None,
+                    status: None,
+                    // The event starts on 15.08.1969
+                    due_at: Some(
+                        NaiveDate::from_ymd_opt(1969, 8, 16)
+                            .unwrap()
+                            .and_hms_opt(10, 30, 0)
+                            .unwrap(),
+                    ),
+                    estimated_hours: Some(2.0),
+                    parent_task_id: None,
+                },
+            )
+            .await
+            .expect("Update should succeed");
 
-        // Valid create
+        let subtask = task_repo
+            .create(NewTask {
+                event_id: test_constants::EVENT0_ID,
+                creator_id: test_constants::EVENT_STAFF0_ID,
+                description: None,
+                title: "Bring the cables".to_string(),
+                priority: TaskPriority::Low,
+                due_at: None,
+                estimated_hours: None,
+                parent_task_id: Some(test_constants::TASK0_ID),
+            })
+            .await
+            .expect("Create should succeed");
 
-        {
-            let new_comment_data = NewComment {
-                author_id: test_constants::USER0_ID,
-                event_id: Some(test_constants::EVENT0_ID),
-                task_id: None,
-                content: "Test Content".to_string(),
-            };
+        checklist_repo
+            .create(NewChecklistItem {
+                task_id: test_constants::TASK0_ID,
+                content: "Check the speakers".to_string(),
+            })
+            .await
+            .expect("Create should succeed");
 
-            let new_comment = comment_repo
-                .create(new_comment_data.clone())
-                .await
-                .expect("Create should succeed");
+        let template = template_repo
+            .create(NewEventTemplate {
+                event_id: test_constants::EVENT0_ID,
+                company_id: test_constants::COMPANY0_ID,
+                name: "Yearly festival".to_string(),
+            })
+            .await
+            .expect("Create should succeed");
 
-            assert_eq!(new_comment.author.id, new_comment_data.author_id);
-            assert_eq!(new_comment.event_id, new_comment_data.event_id);
-            assert_eq!(new_comment.task_id, new_comment_data.task_id);
-            assert_eq!(new_comment.content, new_comment_data.content);
-            assert_eq!(new_comment.deleted_at, None);
+        assert_eq!(template.name, "Yearly festival");
+        assert_eq!(template.company_id, test_constants::COMPANY0_ID);
+        assert_eq!(template.source_event_id, Some(test_constants::EVENT0_ID));
 
-            let time = NaiveDateTime::from_timestamp_opt(Utc::now().timestamp(), 0).unwrap();
+        let template_tasks = template_repo
+            .read_tasks(template.id)
+            .await
+            .expect("Read should succeed");
 
-            let time_difference_created = time - new_comment.created_at;
-            let time_difference_edited = time - new_comment.edited_at;
+        assert_eq!(template_tasks.len(), 3);
+        let stage_task = template_tasks
+            .iter()
+            .find(|task| task.title == "Prepare stage for Joe Cocker")
+            .expect("Should be copied");
+        assert_eq!(stage_task.priority, TaskPriority::High);
+        assert_eq!(stage_task.due_offset_minutes, Some(34 * 60 + 30));
+        let template_subtask = template_tasks
+            .iter()
+            .find(|task| task.title == subtask.title)
+            .expect("Should be copied");
+        assert_eq!(template_subtask.parent_id, Some(stage_task.id));
+
+        // A new edition, 61 years later.
+
+        let new_start = NaiveDate::from_ymd_opt(2030, 8, 15).unwrap();
+        let event = template_repo
+            .instantiate(
+                template.id,
+                NewEventFromTemplate {
+                    name: "Yearly festival 2030".to_string(),
+                    start_date: new_start,
+                    creator_id: test_constants::USER0_ID,
+                    company_id: test_constants::COMPANY0_ID,
+                },
+            )
+            .await
+            .expect("Instantiate should succeed");
 
-            assert!(time_difference_created.num_seconds() < 2);
-            assert!(time_difference_edited.num_seconds() < 2);
-        }
+        assert_eq!(event.name, "Yearly festival 2030");
+        assert_eq!(event.start_date, new_start);
+        assert_eq!(
+            event.end_date - event.start_date,
+            chrono::Duration::days(i64::from(template.duration_days))
+        );
 
-        // All are none
+        let tasks = task_repo
+            .read_board_for_event(event.id)
+            .await
+            .expect("Read should succeed");
 
-        {
-            let new_comment_data = NewComment {
-                author_id: test_constants::USER0_ID,
-                event_id: None,
-                task_id: None,
-                content: "Test Content".to_string(),
-            };
+        assert_eq!(tasks.len(), 3);
+        let stage_task = tasks
+            .iter()
+            .find(|task| task.title == "Prepare stage for Joe Cocker")
+            .expect("Should be instantiated");
+        assert_eq!(stage_task.priority, TaskPriority::High);
+        assert!(stage_task.finished_at.is_none());
+        assert_eq!(
+            stage_task.due_at,
+            Some(
+                NaiveDate::from_ymd_opt(2030, 8, 16)
+                    .unwrap()
+                    .and_hms_opt(10, 30, 0)
+                    .unwrap()
+            )
+        );
 
-            let _new_comment = comment_repo
-                .create(new_comment_data)
-                .await
-                .expect_err("Create should fail - all fields are none");
-        }
+        let subtasks = task_repo
+            .read_subtasks(stage_task.task_id)
+            .await
+            .expect("Read should succeed");
 
-        // Both are some
+        assert_eq!(subtasks.len(), 1);
+        assert_eq!(subtasks[0].title, subtask.title);
 
-        {
-            let new_comment_data = NewComment {
-                author_id: test_constants::USER0_ID,
-                event_id: Some(test_constants::EVENT0_ID),
-                task_id: Some(test_constants::TASK0_ID),
-                content: "Test Content".to_string(),
-            };
+        let items = checklist_repo
+            .read_all_for_task(stage_task.task_id)
+            .await
+            .expect("Read should succeed");
 
-            let _new_comment = comment_repo
-                .create(new_comment_data)
-                .await
-                .expect_err("Create should fail - both event and task are some");
-        }
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].content, "Check the speakers");
+        assert!(items[0].finished_at.is_none());
 
-        comment_repo.disconnect().await;
+        template_repo.disconnect().await;
+        task_repo.disconnect().await;
+        checklist_repo.disconnect().await;
 
         Ok(())
     }
 
-    #[sqlx::test(fixtures("comments"), migrations = "migrations/no_seed")]
-    async fn read_one(pool: PgPool) -> DbResult<()> {
+    #[sqlx::test(fixtures("task"), migrations = "migrations/no_seed")]
+    async fn delete(pool: PgPool) -> DbResult<()> {
         let arc_pool = Arc::new(pool);
 
-        let mut comment_repo = CommentRepository::new(arc_pool);
+        let mut template_repo = EventTemplateRepository::new(arc_pool);
 
-        let comment_id = test_constants::COMMENT0_ID;
+        template_repo
+            .create(NewEventTemplate {
+                event_id: uuid!("a96d1d99-93b5-469b-ac62-654b0cf7ebd9"),
+                company_id: test_constants::COMPANY0_ID,
+                name: "Nothing".to_string(),
+            })
+            .await
+            .expect_err("Create should fail - non existent event");
+
+        let template = template_repo
+            .create(NewEventTemplate {
+                event_id: test_constants::EVENT0_ID,
+                company_id: test_constants::COMPANY0_ID,
+                name: "Yearly festival".to_string(),
+            })
+            .await
+            .expect("Create should succeed");
 
-        let comment = comment_repo
-            .read_one(comment_id)
+        let templates = template_repo
+            .read_all_for_company(test_constants::COMPANY0_ID)
             .await
             .expect("Read should succeed");
 
-        assert_eq!(comment.content, "Joe will need 3 guitars on stage.");
-        assert_eq!(comment.author.name, "Dave Null");
-
-        comment_repo.disconnect().await;
+        assert_eq!(templates.len(), 1);
 
-        Ok(())
-    }
+        template_repo
+            .delete(template.id)
+            .await
+            .expect("Delete should succeed");
 
-    #[sqlx::test(fixtures("comments"), migrations = "migrations/no_seed")]
-    async fn read_all_per_event(pool: PgPool) -> DbResult<()> {
-        let arc_pool = Arc::new(pool);
+        template_repo
+            .read_one(template.id)
+            .await
+            .expect_err("Read should fail - deleted template");
 
-        let mut comment_repo = CommentRepository::new(arc_pool);
+        template_repo
+            .delete(template.id)
+            .await
+            .expect_err("Repeated delete should fail");
 
-        let event_id = test_constants::EVENT0_ID;
+        template_repo.disconnect().await;
 
-        let filter = CommentFilter {
-            limit: None,
-            offset: None,
-        };
+        Ok(())
+    }
+}
 
-        let mut comments = comment_repo
-            .read_all_per_event(event_id, filter)
+// needs event_staff, task
+#[cfg(test)]
+pub mod assigned_staff_repo_tests {
+    use std::sync::Arc;
+
+    use chrono::{NaiveDateTime, Utc};
+    use organization::{
+        common::DbResult,
+        models::AcceptanceStatus,
+        repositories::{
+            assigned_staff::{
+                assigned_staff_repo::AssignedStaffRepository,
+                models::{AssignedStaffData, AssignedStaffFilter, NewAssignedStaff},
+            },
+            repository::DbRepository,
+        },
+    };
+    use sqlx::PgPool;
+    use uuid::uuid;
+
+    use crate::test_constants;
+
+    #[sqlx::test(fixtures("assigned_staff"), migrations = "migrations/no_seed")]
+    pub fn create(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut assigned_staff_repo = AssignedStaffRepository::new(arc_pool);
+
+        let assigned_staff_data = NewAssignedStaff {
+            staff_id: test_constants::EVENT_STAFF1_ID,
+            task_id: test_constants::TASK1_ID,
+        };
+
+        let new_assigned_staff = assigned_staff_repo
+            .create(assigned_staff_data.clone())
             .await
-            .expect("Read should succeed");
+            .expect("Create should succeed");
 
-        assert_eq!(comments.len(), 2);
+        assert_eq!(new_assigned_staff.staff.id, assigned_staff_data.staff_id);
+        assert_eq!(new_assigned_staff.task_id, assigned_staff_data.task_id);
+        assert_eq!(new_assigned_staff.status, AcceptanceStatus::Pending);
 
-        comments.sort_by(|a, b| a.author.name.cmp(&b.author.name));
+        assert!(new_assigned_staff.decided_by.is_none());
+        assert!(new_assigned_staff.deleted_at.is_none());
 
-        let comment = &comments[0];
+        let time = NaiveDateTime::from_timestamp_opt(Utc::now().timestamp(), 0).unwrap();
 
-        assert_eq!(comment.content, "Oh nooo!");
-        assert_eq!(comment.author.name, "Dave Null");
+        let time_difference_created = time - new_assigned_staff.created_at;
+        let time_difference_edited = time - new_assigned_staff.edited_at;
 
-        let comment = &comments[1];
+        assert!(time_difference_created.num_seconds() < 2);
+        assert!(time_difference_edited.num_seconds() < 2);
 
-        assert_eq!(comment.content, "I have a problem.");
-        assert_eq!(comment.author.name, "Tana Smith");
+        assigned_staff_repo.disconnect().await;
 
-        comment_repo.disconnect().await;
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("assigned_staff"), migrations = "migrations/no_seed")]
+    pub fn read_one(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut assigned_staff_repo = AssignedStaffRepository::new(arc_pool);
+
+        let staff_id = test_constants::EVENT_STAFF0_ID;
+        let task_id = test_constants::TASK0_ID;
+
+        let assigned_staff = assigned_staff_repo
+            .read_one(task_id, staff_id)
+            .await
+            .expect("Read should succeed");
+
+        assert_eq!(assigned_staff.staff.user.name, "Dave Null");
+        assert_eq!(assigned_staff.decided_by_user.unwrap().name, "Dave Null");
+
+        assigned_staff_repo.disconnect().await;
 
         Ok(())
     }
 
-    #[sqlx::test(fixtures("comments"), migrations = "migrations/no_seed")]
-    async fn read_all_per_task(pool: PgPool) -> DbResult<()> {
+    #[sqlx::test(fixtures("assigned_staff"), migrations = "migrations/no_seed")]
+    pub fn read_all_per_task(pool: PgPool) -> DbResult<()> {
         let arc_pool = Arc::new(pool);
 
-        let mut comment_repo = CommentRepository::new(arc_pool);
+        let mut assigned_staff_repo = AssignedStaffRepository::new(arc_pool);
 
         let task_id = test_constants::TASK0_ID;
 
-        let filter = CommentFilter {
+        let filter = AssignedStaffFilter {
             limit: None,
             offset: None,
         };
 
-        let mut comments = comment_repo
+        let mut assigned_staffs = assigned_staff_repo
             .read_all_per_task(task_id, filter)
             .await
             .expect("Read should succeed");
 
-        assert_eq!(comments.len(), 2);
+        assert_eq!(assigned_staffs.len(), 2);
 
-        comments.sort_by(|a, b| a.author.name.cmp(&b.author.name));
+        assigned_staffs.sort_by(|a, b| a.staff.user.name.cmp(&b.staff.user.name));
 
-        let comment = &comments[0];
+        let assigned_staff = &assigned_staffs[0];
 
-        assert_eq!(comment.content, "Joe will need 3 guitars on stage.");
-        assert_eq!(comment.author.name, "Dave Null");
+        assert_eq!(assigned_staff.staff.user.name, "Dave Null");
+        assert_eq!(
+            assigned_staff.decided_by_user.clone().unwrap().name,
+            "Dave Null"
+        );
 
-        let comment = &comments[1];
+        let assigned_staff = &assigned_staffs[1];
 
-        assert_eq!(comment.content, "I will take care of it.");
-        assert_eq!(comment.author.name, "Tana Smith");
+        assert_eq!(assigned_staff.staff.user.name, "Tana Smith");
+        assert!(assigned_staff.decided_by.clone().is_none());
 
-        comment_repo.disconnect().await;
+        assigned_staff_repo.disconnect().await;
 
         Ok(())
     }
 
-    #[sqlx::test(fixtures("comments"), migrations = "migrations/no_seed")]
-    async fn update(pool: PgPool) -> DbResult<()> {
+    #[sqlx::test(fixtures("assigned_staff"), migrations = "migrations/no_seed")]
+    pub fn update(pool: PgPool) -> DbResult<()> {
         let arc_pool = Arc::new(pool);
 
-        let mut comment_repo = CommentRepository::new(arc_pool);
+        let mut assigned_staff_repo = AssignedStaffRepository::new(arc_pool);
 
         // Valid update
 
         {
-            let comment_id = test_constants::COMMENT0_ID;
+            let decider_staff_id = test_constants::EVENT_STAFF0_ID;
 
-            let _comment = comment_repo
-                .read_one(comment_id)
-                .await
-                .expect("Read should succeed");
+            let staff_id = test_constants::EVENT_STAFF1_ID;
+            let task_id = test_constants::TASK0_ID;
 
-            let new_comment_data = CommentData {
-                content: "New Content".to_string(),
+            let assigned_staff_data = AssignedStaffData {
+                status: AcceptanceStatus::Accepted,
+                decided_by: decider_staff_id,
             };
 
-            let updated_comment = comment_repo
-                .update(comment_id, new_comment_data.clone())
+            let updated_assigned_staff = assigned_staff_repo
+                .update(task_id, staff_id, assigned_staff_data.clone())
                 .await
                 .expect("Update should succeed");
 
-            assert_eq!(updated_comment.content, new_comment_data.content);
+            assert_eq!(updated_assigned_staff.status, AcceptanceStatus::Accepted);
+            assert_eq!(
+                updated_assigned_staff.decided_by,
+                Some(assigned_staff_data.decided_by)
+            );
 
             let time = NaiveDateTime::from_timestamp_opt(Utc::now().timestamp(), 0).unwrap();
 
-            let time_difference_edited = time - updated_comment.edited_at;
+            let time_difference_edited = time - updated_assigned_staff.edited_at;
             assert!(time_difference_edited.num_seconds() < 2);
 
-            assert!(updated_comment.deleted_at.is_none());
-        }
-
-        // All are none
-
-        {
-            let comment_id = test_constants::COMMENT0_ID;
-
-            let new_comment_data = CommentData {
-                content: "".to_string(),
-            };
-
-            let _updated_comment = comment_repo
-                .update(comment_id, new_comment_data)
-                .await
-                .expect_err("Update should fail - all fields are none");
+            assert!(updated_assigned_staff.deleted_at.is_none());
         }
 
         // Non existent
 
         {
-            let comment_id = uuid!("a96d1d99-93b5-469b-ac62-654b0cf7ebd9");
+            let staff_id = test_constants::EVENT_STAFF1_ID;
+            let task_id = uuid!("a96d1d99-93b5-469b-ac62-654b0cf7ebd9");
 
-            let new_comment_data = CommentData {
-                content: "New Content".to_string(),
+            let assigned_staff_data = AssignedStaffData {
+                status: AcceptanceStatus::Accepted,
+                decided_by: test_constants::EVENT_STAFF0_ID,
             };
 
-            let _updated_comment = comment_repo
-                .update(comment_id, new_comment_data)
+            let _updated_assigned_staff = assigned_staff_repo
+                .update(task_id, staff_id, assigned_staff_data)
                 .await
-                .expect_err("Update should fail - non existent comment");
+                .expect_err("Update should fail - non existent assigned staff");
         }
 
         // Already deleted
 
         {
-            let comment_id = test_constants::COMMENT0_ID;
+            let staff_id = test_constants::EVENT_STAFF1_ID;
+            let task_id = test_constants::TASK0_ID;
 
-            let comment = comment_repo
-                .read_one(comment_id)
+            let assigned_staff = assigned_staff_repo
+                .read_one(task_id, staff_id)
                 .await
                 .expect("Read should succeed");
 
-            assert!(comment.deleted_at.is_none());
+            assert!(assigned_staff.deleted_at.is_none());
 
-            comment_repo
-                .delete(comment_id)
+            assigned_staff_repo
+                .delete(task_id, staff_id)
                 .await
                 .expect("Delete should succeed");
 
-            let _ = comment_repo
-                .read_one(comment_id)
+            let _ = assigned_staff_repo
+                .read_one(task_id, staff_id)
                 .await
                 .expect_err("Read should not succeed");
 
-            let new_comment_data = CommentData {
-                content: "New Content".to_string(),
+            let assigned_staff_data = AssignedStaffData {
+                status: AcceptanceStatus::Accepted,
+                decided_by: test_constants::EVENT_STAFF0_ID,
             };
 
-            let _updated_comment = comment_repo
-                .update(comment_id, new_comment_data)
+            let _updated_assigned_staff = assigned_staff_repo
+                .update(task_id, staff_id, assigned_staff_data)
                 .await
-                .expect_err("Update should fail - already deleted comment");
+                .expect_err("Update should fail - already deleted assigned staff");
         }
 
-        comment_repo.disconnect().await;
+        assigned_staff_repo.disconnect().await;
 
         Ok(())
     }
 
-    #[sqlx::test(fixtures("comments"), migrations = "migrations/no_seed")]
-    async fn delete(pool: PgPool) -> DbResult<()> {
+    #[sqlx::test(fixtures("assigned_staff"), migrations = "migrations/no_seed")]
+    pub fn delete(pool: PgPool) -> DbResult<()> {
         let arc_pool = Arc::new(pool);
 
-        let mut comment_repo = CommentRepository::new(arc_pool);
-
-        // Valid delete
+        let mut assigned_staff_repo = AssignedStaffRepository::new(arc_pool);
 
         {
-            let comment_id = test_constants::COMMENT0_ID;
+            let staff_id = test_constants::EVENT_STAFF1_ID;
+            let task_id = test_constants::TASK0_ID;
 
-            let comment = comment_repo
-                .read_one(comment_id)
+            let assigned_staff = assigned_staff_repo
+                .read_one(task_id, staff_id)
                 .await
                 .expect("Read should succeed");
 
-            assert!(comment.deleted_at.is_none());
+            assert!(assigned_staff.deleted_at.is_none());
 
-            comment_repo.delete(comment_id).await.unwrap();
+            assigned_staff_repo.delete(task_id, staff_id).await.unwrap();
 
-            let _ = comment_repo
-                .read_one(comment_id)
+            let _ = assigned_staff_repo
+                .read_one(task_id, staff_id)
                 .await
                 .expect_err("Read should not succeed");
         }
 
-        // delete on already deleted comment
+        // delete on already deleted assigned staff
 
         {
-            let comment_id = test_constants::COMMENT0_ID;
+            let staff_id = test_constants::EVENT_STAFF1_ID;
+            let task_id = test_constants::TASK0_ID;
 
-            let _ = comment_repo
-                .read_one(comment_id)
+            let _ = assigned_staff_repo
+                .read_one(task_id, staff_id)
                 .await
                 .expect_err("Read should not succeed");
 
-            comment_repo.delete(comment_id).await.expect_err(
-                "Repository should return error on deleting an already deleted comment",
-            );
+            assigned_staff_repo
+                .delete(task_id, staff_id)
+                .await
+                .expect_err(
+                    "Repository should return error on deleting an already deleted assigned staff",
+                );
         }
 
-        // delete on non-existing comment
+        // delete on non-existing assigned staff
 
         {
-            let comment_id = uuid!("a96d1d99-93b5-469b-ac62-654b0cf7ebd9");
+            let staff_id = test_constants::EVENT_STAFF1_ID;
+            let task_id = uuid!("a96d1d99-93b5-469b-ac62-654b0cf7ebd9");
 
-            comment_repo
-                .delete(comment_id)
+            assigned_staff_repo
+                .delete(task_id, staff_id)
                 .await
-                .expect_err("Repository should return error on deleting a non-existing comment");
-        }
-
-        comment_repo.disconnect().await;
+                .expect_err(
+                    "Repository should return error on deleting a non-existing assigned staff",
+                );
+        }
+
+        assigned_staff_repo.disconnect().await;
 
         Ok(())
     }
 }
 
+// needs event, task, user
 #[cfg(test)]
-mod timesheet_repo_tests {
+pub mod comment_repo_tests {
     use std::sync::Arc;
 
-    use chrono::NaiveDate;
+    use chrono::{NaiveDateTime, Utc};
     use organization::{
-        models::ApprovalStatus,
-        repositories::timesheet::{
-            models::{TimesheetCreateData, TimesheetReadAllData, TimesheetUpdateData},
-            timesheet_repo::TimesheetRepository,
+        common::DbResult,
+        repositories::{
+            comment::{
+                comment_repo::CommentRepository,
+                models::{CommentData, CommentFilter, NewComment},
+            },
+            repository::DbRepository,
         },
     };
     use sqlx::PgPool;
+    use uuid::uuid;
 
-    use crate::test_constants::{
-        COMPANY1_ID, COMPANY2_ID, EVENT0_ID, TIMESHEET0_ID, TIMESHEET1_ID, TIMESHEET4_ID, USER1_ID,
-        USER2_ID,
-    };
-    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
-    async fn create(pool: PgPool) {
+    use crate::test_constants;
+
+    #[sqlx::test(fixtures("comments"), migrations = "migrations/no_seed")]
+    async fn create(pool: PgPool) -> DbResult<()> {
         let arc_pool = Arc::new(pool);
 
-        let timesheet_repo = TimesheetRepository::new(arc_pool);
+        let mut comment_repo = CommentRepository::new(arc_pool);
+
+        // Valid create
 
         {
-            let user_id = USER1_ID;
-            let company_id = COMPANY1_ID;
-            let event_id = EVENT0_ID;
-            let start_date = NaiveDate::from_ymd_opt(1969, 8, 15).unwrap();
-            let end_date = NaiveDate::from_ymd_opt(1969, 08, 18).unwrap();
-            let data = TimesheetCreateData {
-                start_date,
-                end_date,
-                user_id,
-                company_id,
-                event_id,
+            let new_comment_data = NewComment {
+                author_id: test_constants::USER0_ID,
+                event_id: Some(test_constants::EVENT0_ID),
+                task_id: None,
+                parent_id: None,
+                content: "Test Content".to_string(),
             };
 
-            let result = timesheet_repo.create(data).await.expect("Should succed");
-            assert_eq!(result.workdays.len(), 4);
-            for day in result.workdays.into_iter() {
-                assert!(day.date >= start_date);
-                assert!(day.date <= end_date);
-            }
-            assert_eq!(
-                result.timesheet.approval_status,
-                ApprovalStatus::NotRequested
-            );
-            assert!(result.timesheet.is_editable);
-        }
-    }
+            let new_comment = comment_repo
+                .create(new_comment_data.clone())
+                .await
+                .expect("Create should succeed");
 
-    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
-    async fn read_one(pool: PgPool) {
-        let arc_pool = Arc::new(pool);
+            assert_eq!(new_comment.author.id, new_comment_data.author_id);
+            assert_eq!(new_comment.event_id, new_comment_data.event_id);
+            assert_eq!(new_comment.task_id, new_comment_data.task_id);
+            assert_eq!(new_comment.content, new_comment_data.content);
+            assert_eq!(new_comment.deleted_at, None);
 
-        let timesheet_repo = TimesheetRepository::new(arc_pool);
+            let time = NaiveDateTime::from_timestamp_opt(Utc::now().timestamp(), 0).unwrap();
 
-        {
-            let sheet_id = TIMESHEET0_ID;
-            let user_id = USER2_ID;
-            let company_id = COMPANY1_ID;
-            let event_id = EVENT0_ID;
+            let time_difference_created = time - new_comment.created_at;
+            let time_difference_edited = time - new_comment.edited_at;
 
-            let result = timesheet_repo
-                ._read_one(sheet_id)
-                .await
-                .expect("Should succed");
-            assert_eq!(result.workdays.len(), 2);
-            assert_eq!(
-                result.timesheet.approval_status,
-                ApprovalStatus::NotRequested
-            );
-            assert!(result.timesheet.is_editable);
-            assert_eq!(result.timesheet.company_id, company_id);
-            assert_eq!(result.timesheet.event_id, event_id);
-            assert_eq!(result.timesheet.user_id, user_id);
+            assert!(time_difference_created.num_seconds() < 2);
+            assert!(time_difference_edited.num_seconds() < 2);
         }
 
+        // All are none
+
         {
-            let sheet_id = TIMESHEET1_ID;
+            let new_comment_data = NewComment {
+                author_id: test_constants::USER0_ID,
+                event_id: None,
+                task_id: None,
+                parent_id: None,
+                content: "Test Content".to_string(),
+            };
 
-            let result = timesheet_repo
-                ._read_one(sheet_id)
+            let _new_comment = comment_repo
+                .create(new_comment_data)
                 .await
-                .expect("Should succed");
-            assert_eq!(result.workdays.len(), 3);
-            assert_eq!(result.timesheet.approval_status, ApprovalStatus::Accepted);
+                .expect_err("Create should fail - all fields are none");
         }
 
-        // Non-existent sheet
+        // Both are some
+
         {
-            let sheet_id = TIMESHEET4_ID;
+            let new_comment_data = NewComment {
+                author_id: test_constants::USER0_ID,
+                event_id: Some(test_constants::EVENT0_ID),
+                task_id: Some(test_constants::TASK0_ID),
+                parent_id: None,
+                content: "Test Content".to_string(),
+            };
 
-            let _ = timesheet_repo
-                ._read_one(sheet_id)
+            let _new_comment = comment_repo
+                .create(new_comment_data)
                 .await
-                .expect_err("Should not succed");
+                .expect_err("Create should fail - both event and task are some");
         }
+
+        comment_repo.disconnect().await;
+
+        Ok(())
     }
 
-    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
-    async fn read_all_per_employment(pool: PgPool) {
+    #[sqlx::test(fixtures("comments"), migrations = "migrations/no_seed")]
+    async fn read_one(pool: PgPool) -> DbResult<()> {
         let arc_pool = Arc::new(pool);
 
-        let timesheet_repo = TimesheetRepository::new(arc_pool);
+        let mut comment_repo = CommentRepository::new(arc_pool);
 
-        {
-            let user_id = USER2_ID;
-            let company_id = COMPANY1_ID;
+        let comment_id = test_constants::COMMENT0_ID;
 
-            let data = TimesheetReadAllData {
-                limit: None,
-                offset: None,
-            };
-            let result = timesheet_repo
-                .read_all_timesheets_per_employment(user_id, company_id, data)
-                .await
-                .expect("Should succed");
-            assert_eq!(result.len(), 1);
-        }
+        let comment = comment_repo
+            .read_one(comment_id)
+            .await
+            .expect("Read should succeed");
 
-        // Non-existent employment
-        {
-            let user_id = USER2_ID;
-            let company_id = COMPANY2_ID;
+        assert_eq!(comment.content, "Joe will need 3 guitars on stage.");
+        assert_eq!(comment.author.name, "Dave Null");
 
-            let data = TimesheetReadAllData {
-                limit: None,
-                offset: None,
-            };
-            let result = timesheet_repo
-                .read_all_timesheets_per_employment(user_id, company_id, data)
-                .await
-                .expect("Should succed");
-            assert_eq!(result.len(), 0);
-        }
+        comment_repo.disconnect().await;
+
+        Ok(())
     }
 
-    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
-    async fn update(pool: PgPool) {
+    #[sqlx::test(fixtures("comments"), migrations = "migrations/no_seed")]
+    async fn read_all_per_event(pool: PgPool) -> DbResult<()> {
         let arc_pool = Arc::new(pool);
 
-        let timesheet_repo = TimesheetRepository::new(arc_pool);
+        let mut comment_repo = CommentRepository::new(arc_pool);
 
-        {
-            let sheet_id = TIMESHEET0_ID;
-            let user_id = USER2_ID;
-            let company_id = COMPANY1_ID;
-            let event_id = EVENT0_ID;
+        let event_id = test_constants::EVENT0_ID;
 
-            let result = timesheet_repo
-                ._read_one(sheet_id)
-                .await
-                .expect("Should succeed.");
-            assert_eq!(result.workdays.len(), 2);
-            assert_eq!(
-                result.timesheet.approval_status,
-                ApprovalStatus::NotRequested
-            );
-            assert!(result.timesheet.is_editable);
-            assert_eq!(result.timesheet.company_id, company_id);
-            assert_eq!(result.timesheet.event_id, event_id);
-            assert_eq!(result.timesheet.user_id, user_id);
+        let filter = CommentFilter {
+            limit: None,
+            offset: None,
+        };
 
-            let data = TimesheetUpdateData {
-                is_editable: None,
-                status: None,
-                manager_note: Some("Change X and Y.".to_string()),
-            };
+        let mut comments = comment_repo
+            .read_all_per_event(event_id, filter)
+            .await
+            .expect("Read should succeed");
 
-            let result = timesheet_repo
-                .update(sheet_id, data)
-                .await
-                .expect("Should succeed.");
-            assert_eq!(result.workdays.len(), 2);
-            assert_eq!(
-                result.timesheet.approval_status,
-                ApprovalStatus::NotRequested
-            );
-            assert!(result.timesheet.is_editable);
-            assert_eq!(result.timesheet.company_id, company_id);
-            assert_eq!(result.timesheet.event_id, event_id);
-            assert_eq!(result.timesheet.user_id, user_id);
-            assert!(result.timesheet.manager_note.is_some());
-            assert_eq!(
-                result.timesheet.manager_note.expect("Should be some"),
-                "Change X and Y.".to_string()
-            );
-        }
+        assert_eq!(comments.len(), 2);
 
-        // Empty data
-        {
-            let sheet_id = TIMESHEET0_ID;
-            let user_id = USER2_ID;
-            let company_id = COMPANY1_ID;
-            let event_id = EVENT0_ID;
+        comments.sort_by(|a, b| a.author.name.cmp(&b.author.name));
 
-            let result = timesheet_repo
-                ._read_one(sheet_id)
-                .await
-                .expect("Should succeed.");
-            assert_eq!(result.workdays.len(), 2);
-            assert_eq!(
-                result.timesheet.approval_status,
-                ApprovalStatus::NotRequested
-            );
-            assert!(result.timesheet.is_editable);
-            assert_eq!(result.timesheet.company_id, company_id);
-            assert_eq!(result.timesheet.event_id, event_id);
-            assert_eq!(result.timesheet.user_id, user_id);
+        let comment = &comments[0];
 
-            let data = TimesheetUpdateData {
-                is_editable: None,
-                status: None,
-                manager_note: None,
+        assert_eq!(comment.content, "Oh nooo!");
+        assert_eq!(comment.author.name, "Dave Null");
+
+        let comment = &comments[1];
+
+        assert_eq!(comment.content, "I have a problem.");
+        assert_eq!(comment.author.name, "Tana Smith");
+
+        comment_repo.disconnect().await;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("comments"), migrations = "migrations/no_seed")]
+    async fn read_all_per_task(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut comment_repo = CommentRepository::new(arc_pool);
+
+        let task_id = test_constants::TASK0_ID;
+
+        let filter = CommentFilter {
+            limit: None,
+            offset: None,
+        };
+
+        let mut comments = comment_repo
+            .read_all_per_task(task_id, filter)
+            .await
+            .expect("Read should succeed");
+
+        assert_eq!(comments.len(), 2);
+
+        comments.sort_by(|a, b| a.author.name.cmp(&b.author.name));
+
+        let comment = &comments[0];
+
+        assert_eq!(comment.content, "Joe will need 3 guitars on stage.");
+        assert_eq!(comment.author.name, "Dave Null");
+
+        let comment = &comments[1];
+
+        assert_eq!(comment.content, "I will take care of it.");
+        assert_eq!(comment.author.name, "Tana Smith");
+
+        comment_repo.disconnect().await;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("comments"), migrations = "migrations/no_seed")]
+    async fn update(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut comment_repo = CommentRepository::new(arc_pool);
+
+        // Valid update
+
+        {
+            let comment_id = test_constants::COMMENT0_ID;
+
+            let _comment = comment_repo
+                .read_one(comment_id)
+                .await
+                .expect("Read should succeed");
+
+            let new_comment_data = CommentData {
+                content: "New Content".to_string(),
             };
 
-            let _ = timesheet_repo
-                .update(sheet_id, data)
+            let updated_comment = comment_repo
+                .update(comment_id, new_comment_data.clone())
                 .await
-                .expect_err("Should not succeed.");
+                .expect("Update should succeed");
+
+            assert_eq!(updated_comment.content, new_comment_data.content);
+
+            let time = NaiveDateTime::from_timestamp_opt(Utc::now().timestamp(), 0).unwrap();
+
+            let time_difference_edited = time - updated_comment.edited_at;
+            assert!(time_difference_edited.num_seconds() < 2);
+
+            assert!(updated_comment.deleted_at.is_none());
         }
 
-        // Non-existent sheet
+        // All are none
+
         {
-            let sheet_id = TIMESHEET4_ID;
+            let comment_id = test_constants::COMMENT0_ID;
 
-            let _ = timesheet_repo
-                ._read_one(sheet_id)
+            let new_comment_data = CommentData {
+                content: "".to_string(),
+            };
+
+            let _updated_comment = comment_repo
+                .update(comment_id, new_comment_data)
                 .await
-                .expect_err("Should not succeed.");
+                .expect_err("Update should fail - all fields are none");
+        }
 
-            let data = TimesheetUpdateData {
-                is_editable: None,
-                status: None,
-                manager_note: Some("Change X and Y.".to_string()),
+        // Non existent
+
+        {
+            let comment_id = uuid!("a96d1d99-93b5-469b-ac62-654b0cf7ebd9");
+
+            let new_comment_data = CommentData {
+                content: "New Content".to_string(),
             };
 
-            let _ = timesheet_repo
-                .update(sheet_id, data)
+            let _updated_comment = comment_repo
+                .update(comment_id, new_comment_data)
                 .await
-                .expect_err("Should not succeed.");
+                .expect_err("Update should fail - non existent comment");
         }
 
-        // Deleted sheet
+        // Already deleted
+
         {
-            let sheet_id = TIMESHEET0_ID;
-            let user_id = USER2_ID;
-            let company_id = COMPANY1_ID;
-            let event_id = EVENT0_ID;
+            let comment_id = test_constants::COMMENT0_ID;
 
-            let result = timesheet_repo
-                ._read_one(sheet_id)
+            let comment = comment_repo
+                .read_one(comment_id)
                 .await
-                .expect("Should succeed.");
-            assert_eq!(result.workdays.len(), 2);
-            assert_eq!(
-                result.timesheet.approval_status,
-                ApprovalStatus::NotRequested
-            );
-            assert!(result.timesheet.is_editable);
-            assert_eq!(result.timesheet.company_id, company_id);
-            assert_eq!(result.timesheet.event_id, event_id);
-            assert_eq!(result.timesheet.user_id, user_id);
+                .expect("Read should succeed");
 
-            let _ = timesheet_repo
-                ._delete(sheet_id)
+            assert!(comment.deleted_at.is_none());
+
+            comment_repo
+                .delete(comment_id)
                 .await
-                .expect("Should succeed");
+                .expect("Delete should succeed");
 
-            let data = TimesheetUpdateData {
-                is_editable: None,
-                status: None,
-                manager_note: Some("Change X and Y.".to_string()),
+            let _ = comment_repo
+                .read_one(comment_id)
+                .await
+                .expect_err("Read should not succeed");
+
+            let new_comment_data = CommentData {
+                content: "New Content".to_string(),
             };
 
-            let _ = timesheet_repo
-                .update(sheet_id, data)
+            let _updated_comment = comment_repo
+                .update(comment_id, new_comment_data)
                 .await
-                .expect_err("Should fail because we can't edit a deleted timesheet.");
+                .expect_err("Update should fail - already deleted comment");
         }
+
+        comment_repo.disconnect().await;
+
+        Ok(())
     }
 
-    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
-    async fn delete(pool: PgPool) {
+    #[sqlx::test(fixtures("comments"), migrations = "migrations/no_seed")]
+    async fn delete(pool: PgPool) -> DbResult<()> {
         let arc_pool = Arc::new(pool);
 
-        let timesheet_repo = TimesheetRepository::new(arc_pool);
+        let mut comment_repo = CommentRepository::new(arc_pool);
+
+        // Valid delete
 
         {
-            let sheet_id = TIMESHEET0_ID;
+            let comment_id = test_constants::COMMENT0_ID;
 
-            let _ = timesheet_repo
-                ._read_one(sheet_id)
+            let comment = comment_repo
+                .read_one(comment_id)
                 .await
-                .expect("Should succeed.");
+                .expect("Read should succeed");
 
-            let _ = timesheet_repo
-                ._delete(sheet_id)
-                .await
-                .expect("Should succeed.");
+            assert!(comment.deleted_at.is_none());
 
-            let _ = timesheet_repo
-                ._read_one(sheet_id)
-                .await
-                .expect_err("Should fail.");
+            comment_repo.delete(comment_id).await.unwrap();
 
-            // Deleting an already deleted sheet.
-            let _ = timesheet_repo
-                ._delete(sheet_id)
+            let _ = comment_repo
+                .read_one(comment_id)
                 .await
-                .expect_err("Should fail.");
+                .expect_err("Read should not succeed");
         }
 
-        // Non-existent sheet
+        // delete on already deleted comment
+
         {
-            let sheet_id = TIMESHEET4_ID;
+            let comment_id = test_constants::COMMENT0_ID;
 
-            let _ = timesheet_repo
-                ._read_one(sheet_id)
+            let _ = comment_repo
+                .read_one(comment_id)
                 .await
-                .expect_err("Should not succeed.");
+                .expect_err("Read should not succeed");
 
-            let _ = timesheet_repo
-                ._delete(sheet_id)
+            comment_repo.delete(comment_id).await.expect_err(
+                "Repository should return error on deleting an already deleted comment",
+            );
+        }
+
+        // delete on non-existing comment
+
+        {
+            let comment_id = uuid!("a96d1d99-93b5-469b-ac62-654b0cf7ebd9");
+
+            comment_repo
+                .delete(comment_id)
                 .await
-                .expect_err("Should not succeed.");
+                .expect_err("Repository should return error on deleting a non-existing comment");
         }
+
+        comment_repo.disconnect().await;
+
+        Ok(())
     }
 
-    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
-    async fn read_all_with_date_from_to_per_employment(pool: PgPool) {
+    #[sqlx::test(fixtures("comments"), migrations = "migrations/no_seed")]
+    async fn create_reply(pool: PgPool) -> DbResult<()> {
         let arc_pool = Arc::new(pool);
 
-        let timesheet_repo = TimesheetRepository::new(arc_pool);
+        let mut comment_repo = CommentRepository::new(arc_pool);
+
+        // Valid reply
 
         {
-            let user_id = USER1_ID;
-            let company_id = COMPANY1_ID;
+            let new_comment_data = NewComment {
+                author_id: test_constants::USER1_ID,
+                event_id: None,
+                task_id: Some(test_constants::TASK0_ID),
+                parent_id: Some(test_constants::COMMENT0_ID),
+                content: "Two of them are already on stage.".to_string(),
+            };
 
-            //
-            // Check 1 timesheet is returned
-            // and it has only **2 workays** (as we request
-            // to omit workdays that are outside of `date range`).
-            //
-            {
-                let result = timesheet_repo
-                    ._read_all_with_date_from_to_per_employment(
-                        user_id,
-                        company_id,
-                        NaiveDate::from_ymd_opt(1969, 7, 28).unwrap(),
-                        NaiveDate::from_ymd_opt(1969, 7, 31).unwrap(),
-                        true,
-                    )
-                    .await
-                    .expect("Should succeed");
-                assert_eq!(result.len(), 1);
-                assert_eq!(result[0].workdays.len(), 2);
-            }
+            let reply = comment_repo
+                .create(new_comment_data)
+                .await
+                .expect("Create should succeed");
 
-            //
-            // Check 1 timesheet is returned
-            // and it has all its workdays (as we request
-            // to include workdays that are outside of `date range`).
-            //
-            {
-                let result = timesheet_repo
-                    ._read_all_with_date_from_to_per_employment(
-                        user_id,
-                        company_id,
-                        NaiveDate::from_ymd_opt(1969, 7, 28).unwrap(),
-                        NaiveDate::from_ymd_opt(1969, 7, 31).unwrap(),
-                        false,
-                    )
-                    .await
-                    .expect("Should succeed");
-                assert_eq!(result.len(), 1);
-                assert_eq!(result[0].workdays.len(), 6);
-            }
+            assert_eq!(reply.parent_id, Some(test_constants::COMMENT0_ID));
+        }
 
-            // check two timesheets are returned
-            {
-                let result = timesheet_repo
-                    ._read_all_with_date_from_to_per_employment(
-                        user_id,
-                        company_id,
-                        NaiveDate::from_ymd_opt(1969, 7, 28).unwrap(),
-                        NaiveDate::from_ymd_opt(1969, 8, 30).unwrap(),
-                        true,
-                    )
-                    .await
-                    .expect("Should succeed");
-                assert_eq!(result.len(), 2);
-                assert_eq!(result[0].workdays.len(), 3);
-                assert_eq!(result[1].workdays.len(), 6);
+        // Parent belongs to another discussion
+
+        {
+            let new_comment_data = NewComment {
+                author_id: test_constants::USER1_ID,
+                event_id: Some(test_constants::EVENT0_ID),
+                task_id: None,
+                parent_id: Some(test_constants::COMMENT0_ID),
+                content: "Wrong thread.".to_string(),
+            };
+
+            let _reply = comment_repo
+                .create(new_comment_data)
+                .await
+                .expect_err("Create should fail - parent is a task comment");
+        }
+
+        // Non existent parent
+
+        {
+            let new_comment_data = NewComment {
+                author_id: test_constants::USER1_ID,
+                event_id: None,
+                task_id: Some(test_constants::TASK0_ID),
+                parent_id: Some(uuid!("a96d1d99-93b5-469b-ac62-654b0cf7ebd9")),
+                content: "Nobody said that.".to_string(),
+            };
+
+            let _reply = comment_repo
+                .create(new_comment_data)
+                .await
+                .expect_err("Create should fail - non existent parent");
+        }
+
+        comment_repo.disconnect().await;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("comments"), migrations = "migrations/no_seed")]
+    async fn mentions(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut comment_repo = CommentRepository::new(arc_pool);
+
+        // Only accepted staff of the event can be mentioned,
+        // Tana Smith is still pending.
+        let new_comment_data = NewComment {
+            author_id: test_constants::USER1_ID,
+            event_id: Some(test_constants::EVENT0_ID),
+            task_id: None,
+            parent_id: None,
+            content: "@dave null and @Tana Smith, please check the stage.".to_string(),
+        };
+
+        let comment = comment_repo
+            .create(new_comment_data)
+            .await
+            .expect("Create should succeed");
+
+        let mentioned = comment_repo
+            .read_mentioned_staff(comment.comment_id)
+            .await
+            .expect("Read should succeed");
+
+        assert_eq!(mentioned, vec![test_constants::EVENT_STAFF0_ID]);
+
+        let updated_comment_data = CommentData {
+            content: "Never mind, it's done.".to_string(),
+        };
+
+        comment_repo
+            .update(comment.comment_id, updated_comment_data)
+            .await
+            .expect("Update should succeed");
+
+        let mentioned = comment_repo
+            .read_mentioned_staff(comment.comment_id)
+            .await
+            .expect("Read should succeed");
+
+        assert!(mentioned.is_empty());
+
+        comment_repo.disconnect().await;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("comments"), migrations = "migrations/no_seed")]
+    async fn read_revisions(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut comment_repo = CommentRepository::new(arc_pool);
+
+        let comment_id = test_constants::COMMENT0_ID;
+
+        let revisions = comment_repo
+            .read_revisions(comment_id)
+            .await
+            .expect("Read should succeed");
+
+        assert!(revisions.is_empty());
+
+        for content in ["Joe will need 4 guitars.", "Joe will need 5 guitars."] {
+            let new_comment_data = CommentData {
+                content: content.to_string(),
+            };
+
+            comment_repo
+                .update(comment_id, new_comment_data)
+                .await
+                .expect("Update should succeed");
+        }
+
+        let revisions = comment_repo
+            .read_revisions(comment_id)
+            .await
+            .expect("Read should succeed");
+
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].content, "Joe will need 4 guitars.");
+        assert_eq!(revisions[1].content, "Joe will need 3 guitars on stage.");
+
+        let event_id = comment_repo
+            .read_event_id(comment_id)
+            .await
+            .expect("Read should succeed");
+
+        assert_eq!(event_id, test_constants::EVENT0_ID);
+
+        comment_repo.disconnect().await;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod timesheet_repo_tests {
+    use std::sync::Arc;
+
+    use chrono::NaiveDate;
+    use organization::{
+        models::{ApprovalStatus, TimesheetTransition},
+        repositories::{
+            employment::{employment_repo::EmploymentRepository, models::EmploymentData},
+            repository::DbRepository,
+            timesheet::{
+                models::{
+                    ReviewQueueFilter, TimesheetCreateData, TimesheetReadAllData,
+                    TimesheetTransitionData, TimesheetUpdateData, WorkdayFlagData,
+                    WorkdayUpdateData,
+                },
+                timesheet_repo::TimesheetRepository,
+            },
+        },
+    };
+    use sqlx::PgPool;
+
+    use crate::test_constants::{
+        COMPANY1_ID, COMPANY2_ID, EVENT0_ID, TIMESHEET0_ID, TIMESHEET1_ID, TIMESHEET4_ID, USER0_ID,
+        USER1_ID, USER2_ID,
+    };
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn create(pool: PgPool) {
+        let arc_pool = Arc::new(pool);
+
+        let timesheet_repo = TimesheetRepository::new(arc_pool);
+
+        {
+            let user_id = USER1_ID;
+            let company_id = COMPANY1_ID;
+            let event_id = EVENT0_ID;
+            let start_date = NaiveDate::from_ymd_opt(1969, 8, 15).unwrap();
+            let end_date = NaiveDate::from_ymd_opt(1969, 08, 18).unwrap();
+            let data = TimesheetCreateData {
+                start_date,
+                end_date,
+                user_id,
+                company_id,
+                event_id,
+            };
+
+            let result = timesheet_repo.create(data).await.expect("Should succed");
+            assert_eq!(result.workdays.len(), 4);
+            for day in result.workdays.into_iter() {
+                assert!(day.date >= start_date);
+                assert!(day.date <= end_date);
             }
+            assert_eq!(
+                result.timesheet.approval_status,
+                ApprovalStatus::NotRequested
+            );
+            assert!(result.timesheet.is_editable);
+        }
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn read_one(pool: PgPool) {
+        let arc_pool = Arc::new(pool);
+
+        let timesheet_repo = TimesheetRepository::new(arc_pool);
+
+        {
+            let sheet_id = TIMESHEET0_ID;
+            let user_id = USER2_ID;
+            let company_id = COMPANY1_ID;
+            let event_id = EVENT0_ID;
+
+            let result = timesheet_repo
+                ._read_one(sheet_id)
+                .await
+                .expect("Should succed");
+            assert_eq!(result.workdays.len(), 2);
+            assert_eq!(
+                result.timesheet.approval_status,
+                ApprovalStatus::NotRequested
+            );
+            assert!(result.timesheet.is_editable);
+            assert_eq!(result.timesheet.company_id, company_id);
+            assert_eq!(result.timesheet.event_id, event_id);
+            assert_eq!(result.timesheet.user_id, user_id);
+        }
+
+        {
+            let sheet_id = TIMESHEET1_ID;
+
+            let result = timesheet_repo
+                ._read_one(sheet_id)
+                .await
+                .expect("Should succed");
+            assert_eq!(result.workdays.len(), 3);
+            assert_eq!(result.timesheet.approval_status, ApprovalStatus::Accepted);
+        }
+
+        // Non-existent sheet
+        {
+            let sheet_id = TIMESHEET4_ID;
+
+            let _ = timesheet_repo
+                ._read_one(sheet_id)
+                .await
+                .expect_err("Should not succed");
+        }
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn read_all_per_employment(pool: PgPool) {
+        let arc_pool = Arc::new(pool);
+
+        let timesheet_repo = TimesheetRepository::new(arc_pool);
+
+        {
+            let user_id = USER2_ID;
+            let company_id = COMPANY1_ID;
+
+            let data = TimesheetReadAllData {
+                limit: None,
+                offset: None,
+            };
+            let result = timesheet_repo
+                .read_all_timesheets_per_employment(user_id, company_id, data)
+                .await
+                .expect("Should succed");
+            assert_eq!(result.len(), 1);
+        }
+
+        // Non-existent employment
+        {
+            let user_id = USER2_ID;
+            let company_id = COMPANY2_ID;
+
+            let data = TimesheetReadAllData {
+                limit: None,
+                offset: None,
+            };
+            let result = timesheet_repo
+                .read_all_timesheets_per_employment(user_id, company_id, data)
+                .await
+                .expect("Should succed");
+            assert_eq!(result.len(), 0);
+        }
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn update(pool: PgPool) {
+        let arc_pool = Arc::new(pool);
+
+        let timesheet_repo = TimesheetRepository::new(arc_pool);
+
+        {
+            let sheet_id = TIMESHEET0_ID;
+            let user_id = USER2_ID;
+            let company_id = COMPANY1_ID;
+            let event_id = EVENT0_ID;
+
+            let result = timesheet_repo
+                ._read_one(sheet_id)
+                .await
+                .expect("Should succeed.");
+            assert_eq!(result.workdays.len(), 2);
+            assert_eq!(
+                result.timesheet.approval_status,
+                ApprovalStatus::NotRequested
+            );
+            assert!(result.timesheet.is_editable);
+            assert_eq!(result.timesheet.company_id, company_id);
+            assert_eq!(result.timesheet.event_id, event_id);
+            assert_eq!(result.timesheet.user_id, user_id);
+
+            let data = TimesheetUpdateData {
+                manager_note: Some("Change X and Y.".to_string()),
+            };
+
+            let result = timesheet_repo
+                .update(sheet_id, data)
+                .await
+                .expect("Should succeed.");
+            assert_eq!(result.workdays.len(), 2);
+            assert_eq!(
+                result.timesheet.approval_status,
+                ApprovalStatus::NotRequested
+            );
+            assert!(result.timesheet.is_editable);
+            assert_eq!(result.timesheet.company_id, company_id);
+            assert_eq!(result.timesheet.event_id, event_id);
+            assert_eq!(result.timesheet.user_id, user_id);
+            assert!(result.timesheet.manager_note.is_some());
+            assert_eq!(
+                result.timesheet.manager_note.expect("Should be some"),
+                "Change X and Y.".to_string()
+            );
+        }
+
+        // Empty data
+        {
+            let sheet_id = TIMESHEET0_ID;
+            let user_id = USER2_ID;
+            let company_id = COMPANY1_ID;
+            let event_id = EVENT0_ID;
+
+            let result = timesheet_repo
+                ._read_one(sheet_id)
+                .await
+                .expect("Should succeed.");
+            assert_eq!(result.workdays.len(), 2);
+            assert_eq!(
+                result.timesheet.approval_status,
+                ApprovalStatus::NotRequested
+            );
+            assert!(result.timesheet.is_editable);
+            assert_eq!(result.timesheet.company_id, company_id);
+            assert_eq!(result.timesheet.event_id, event_id);
+            assert_eq!(result.timesheet.user_id, user_id);
+
+            let data = TimesheetUpdateData { manager_note: None };
+
+            let _ = timesheet_repo
+                .update(sheet_id, data)
+                .await
+                .expect_err("Should not succeed.");
+        }
+
+        // Non-existent sheet
+        {
+            let sheet_id = TIMESHEET4_ID;
+
+            let _ = timesheet_repo
+                ._read_one(sheet_id)
+                .await
+                .expect_err("Should not succeed.");
+
+            let data = TimesheetUpdateData {
+                manager_note: Some("Change X and Y.".to_string()),
+            };
+
+            let _ = timesheet_repo
+                .update(sheet_id, data)
+                .await
+                .expect_err("Should not succeed.");
+        }
+
+        // Deleted sheet
+        {
+            let sheet_id = TIMESHEET0_ID;
+            let user_id = USER2_ID;
+            let company_id = COMPANY1_ID;
+            let event_id = EVENT0_ID;
+
+            let result = timesheet_repo
+                ._read_one(sheet_id)
+                .await
+                .expect("Should succeed.");
+            assert_eq!(result.workdays.len(), 2);
+            assert_eq!(
+                result.timesheet.approval_status,
+                ApprovalStatus::NotRequested
+            );
+            assert!(result.timesheet.is_editable);
+            assert_eq!(result.timesheet.company_id, company_id);
+            assert_eq!(result.timesheet.event_id, event_id);
+            assert_eq!(result.timesheet.user_id, user_id);
+
+            let _ = timesheet_repo
+                ._delete(sheet_id)
+                .await
+                .expect("Should succeed");
+
+            let data = TimesheetUpdateData {
+                manager_note: Some("Change X and Y.".to_string()),
+            };
+
+            let _ = timesheet_repo
+                .update(sheet_id, data)
+                .await
+                .expect_err("Should fail because we can't edit a deleted timesheet.");
+        }
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn transition(pool: PgPool) {
+        let arc_pool = Arc::new(pool);
+
+        let timesheet_repo = TimesheetRepository::new(arc_pool);
+
+        let submit = |actor_id| TimesheetTransitionData {
+            actor_id,
+            transition: TimesheetTransition::Submit,
+            note: Some("All days filled in.".to_string()),
+        };
+
+        // A sheet can't skip the review.
+        let _ = timesheet_repo
+            .transition(
+                TIMESHEET0_ID,
+                TimesheetTransitionData {
+                    actor_id: USER0_ID,
+                    transition: TimesheetTransition::Approve,
+                    note: None,
+                },
+            )
+            .await
+            .expect_err("Should not succeed.");
+
+        let result = timesheet_repo
+            .transition(TIMESHEET0_ID, submit(USER2_ID))
+            .await
+            .expect("Should succeed.");
+        assert_eq!(result.timesheet.approval_status, ApprovalStatus::Pending);
+        assert!(!result.timesheet.is_editable);
+        assert_eq!(result.workdays.len(), 2);
+
+        let _ = timesheet_repo
+            .transition(TIMESHEET0_ID, submit(USER2_ID))
+            .await
+            .expect_err("Should not succeed because the sheet is already submitted.");
+
+        let result = timesheet_repo
+            .transition(
+                TIMESHEET0_ID,
+                TimesheetTransitionData {
+                    actor_id: USER0_ID,
+                    transition: TimesheetTransition::Reject,
+                    note: Some(" Fill in the comments. ".to_string()),
+                },
+            )
+            .await
+            .expect("Should succeed.");
+        assert_eq!(result.timesheet.approval_status, ApprovalStatus::Rejected);
+        assert!(result.timesheet.is_editable);
+        assert_eq!(
+            result.timesheet.manager_note,
+            Some("Fill in the comments.".to_string())
+        );
+
+        let history = timesheet_repo
+            .read_history(TIMESHEET0_ID)
+            .await
+            .expect("Should succeed.");
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].transition, TimesheetTransition::Reject);
+        assert_eq!(history[0].actor_id, USER0_ID);
+        assert_eq!(history[0].actor_name, "Dave Null");
+        assert_eq!(history[0].from_status, ApprovalStatus::Pending);
+        assert_eq!(history[0].to_status, ApprovalStatus::Rejected);
+        assert_eq!(history[1].transition, TimesheetTransition::Submit);
+        assert_eq!(history[1].actor_id, USER2_ID);
+        assert_eq!(history[1].note, Some("All days filled in.".to_string()));
+
+        // Non-existent sheet
+        let _ = timesheet_repo
+            .transition(TIMESHEET4_ID, submit(USER2_ID))
+            .await
+            .expect_err("Should not succeed.");
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn flags_and_adjustments(pool: PgPool) {
+        let arc_pool = Arc::new(pool);
+
+        let timesheet_repo = TimesheetRepository::new(arc_pool);
+
+        let first_day = NaiveDate::from_ymd_opt(1969, 8, 15).unwrap();
+        let second_day = NaiveDate::from_ymd_opt(1969, 8, 16).unwrap();
+        let flag = |date, reason: &str, suggested_hours| WorkdayFlagData {
+            timesheet_id: TIMESHEET0_ID,
+            date,
+            flagged_by: USER0_ID,
+            reason: reason.to_string(),
+            suggested_hours,
+            suggested_comment: None,
+        };
+        let transition = |actor_id, transition| TimesheetTransitionData {
+            actor_id,
+            transition,
+            note: None,
+        };
+
+        // Only a submitted sheet can be flagged.
+        let _ = timesheet_repo
+            .flag_workday(flag(first_day, "Too long.", Some(8.0)))
+            .await
+            .expect_err("Should not succeed.");
+
+        timesheet_repo
+            .transition(
+                TIMESHEET0_ID,
+                transition(USER2_ID, TimesheetTransition::Submit),
+            )
+            .await
+            .expect("Should succeed.");
+        timesheet_repo
+            .flag_workday(flag(first_day, "Too long.", Some(8.0)))
+            .await
+            .expect("Should succeed.");
+        timesheet_repo
+            .flag_workday(flag(second_day, "Missing comment.", None))
+            .await
+            .expect("Should succeed.");
+        // Flagging the day again replaces the flag.
+        timesheet_repo
+            .flag_workday(flag(second_day, "Describe the work.", None))
+            .await
+            .expect("Should succeed.");
+
+        let review = timesheet_repo
+            .read_review(TIMESHEET0_ID)
+            .await
+            .expect("Should succeed.");
+        assert_eq!(review.flags.len(), 2);
+        assert_eq!(review.flags[1].reason, "Describe the work.");
+        assert!(!review.flags.iter().any(|flag| flag.is_changed()));
+
+        // The employee corrects the second day and submits the sheet again.
+        timesheet_repo
+            .transition(
+                TIMESHEET0_ID,
+                transition(USER0_ID, TimesheetTransition::Reject),
+            )
+            .await
+            .expect("Should succeed.");
+        timesheet_repo
+            .update_workday(
+                TIMESHEET0_ID,
+                second_day,
+                WorkdayUpdateData {
+                    timesheet_id: TIMESHEET0_ID,
+                    date: second_day,
+                    total_hours: None,
+                    comment: Some("Stage building.".to_string()),
+                },
+            )
+            .await
+            .expect("Should succeed.");
+        timesheet_repo
+            .transition(
+                TIMESHEET0_ID,
+                transition(USER2_ID, TimesheetTransition::Submit),
+            )
+            .await
+            .expect("Should succeed.");
+
+        let review = timesheet_repo
+            .read_review(TIMESHEET0_ID)
+            .await
+            .expect("Should succeed.");
+        assert_eq!(review.flags.len(), 2);
+        assert!(!review.flags[0].is_changed());
+        assert!(review.flags[1].is_changed());
+        assert_eq!(review.flags[1].original_comment, Some("".to_string()));
+        assert_eq!(
+            review.flags[1].current_comment,
+            Some("Stage building.".to_string())
+        );
+
+        let result = timesheet_repo
+            .transition(
+                TIMESHEET0_ID,
+                transition(USER0_ID, TimesheetTransition::Adjust),
+            )
+            .await
+            .expect("Should succeed.");
+        assert_eq!(result.timesheet.approval_status, ApprovalStatus::Accepted);
+        assert_eq!(result.timesheet.total_hours, 18.0);
+        assert_eq!(result.workdays[0].total_hours, 8.0);
+
+        // Only the applied flag is left, until the employee acknowledges it.
+        let review = timesheet_repo
+            .read_review(TIMESHEET0_ID)
+            .await
+            .expect("Should succeed.");
+        assert_eq!(review.flags.len(), 1);
+        assert!(review.flags[0].applied);
+        assert_eq!(review.flags[0].original_hours, 12.0);
+        assert!(review.awaits_acknowledgement());
+
+        timesheet_repo
+            .transition(
+                TIMESHEET0_ID,
+                transition(USER2_ID, TimesheetTransition::Acknowledge),
+            )
+            .await
+            .expect("Should succeed.");
+        let review = timesheet_repo
+            .read_review(TIMESHEET0_ID)
+            .await
+            .expect("Should succeed.");
+        assert!(!review.awaits_acknowledgement());
+
+        let _ = timesheet_repo
+            .transition(
+                TIMESHEET0_ID,
+                transition(USER2_ID, TimesheetTransition::Acknowledge),
+            )
+            .await
+            .expect_err("Should not succeed because it's already acknowledged.");
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn delete(pool: PgPool) {
+        let arc_pool = Arc::new(pool);
+
+        let timesheet_repo = TimesheetRepository::new(arc_pool);
+
+        {
+            let sheet_id = TIMESHEET0_ID;
+
+            let _ = timesheet_repo
+                ._read_one(sheet_id)
+                .await
+                .expect("Should succeed.");
+
+            let _ = timesheet_repo
+                ._delete(sheet_id)
+                .await
+                .expect("Should succeed.");
+
+            let _ = timesheet_repo
+                ._read_one(sheet_id)
+                .await
+                .expect_err("Should fail.");
+
+            // Deleting an already deleted sheet.
+            let _ = timesheet_repo
+                ._delete(sheet_id)
+                .await
+                .expect_err("Should fail.");
+        }
+
+        // Non-existent sheet
+        {
+            let sheet_id = TIMESHEET4_ID;
+
+            let _ = timesheet_repo
+                ._read_one(sheet_id)
+                .await
+                .expect_err("Should not succeed.");
+
+            let _ = timesheet_repo
+                ._delete(sheet_id)
+                .await
+                .expect_err("Should not succeed.");
+        }
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn read_all_with_date_from_to_per_employment(pool: PgPool) {
+        let arc_pool = Arc::new(pool);
+
+        let timesheet_repo = TimesheetRepository::new(arc_pool);
+
+        {
+            let user_id = USER1_ID;
+            let company_id = COMPANY1_ID;
+
+            //
+            // Check 1 timesheet is returned
+            // and it has only **2 workays** (as we request
+            // to omit workdays that are outside of `date range`).
+            //
+            {
+                let result = timesheet_repo
+                    ._read_all_with_date_from_to_per_employment(
+                        user_id,
+                        company_id,
+                        NaiveDate::from_ymd_opt(1969, 7, 28).unwrap(),
+                        NaiveDate::from_ymd_opt(1969, 7, 31).unwrap(),
+                        true,
+                    )
+                    .await
+                    .expect("Should succeed");
+                assert_eq!(result.len(), 1);
+                assert_eq!(result[0].workdays.len(), 2);
+            }
+
+            //
+            // Check 1 timesheet is returned
+            // and it has all its workdays (as we request
+            // to include workdays that are outside of `date range`).
+            //
+            {
+                let result = timesheet_repo
+                    ._read_all_with_date_from_to_per_employment(
+                        user_id,
+                        company_id,
+                        NaiveDate::from_ymd_opt(1969, 7, 28).unwrap(),
+                        NaiveDate::from_ymd_opt(1969, 7, 31).unwrap(),
+                        false,
+                    )
+                    .await
+                    .expect("Should succeed");
+                assert_eq!(result.len(), 1);
+                assert_eq!(result[0].workdays.len(), 6);
+            }
+
+            // check two timesheets are returned
+            {
+                let result = timesheet_repo
+                    ._read_all_with_date_from_to_per_employment(
+                        user_id,
+                        company_id,
+                        NaiveDate::from_ymd_opt(1969, 7, 28).unwrap(),
+                        NaiveDate::from_ymd_opt(1969, 8, 30).unwrap(),
+                        true,
+                    )
+                    .await
+                    .expect("Should succeed");
+                assert_eq!(result.len(), 2);
+                assert_eq!(result[0].workdays.len(), 3);
+                assert_eq!(result[1].workdays.len(), 6);
+            }
+
+            // check date range yielding nothing
+            {
+                let result = timesheet_repo
+                    ._read_all_with_date_from_to_per_employment(
+                        user_id,
+                        company_id,
+                        NaiveDate::from_ymd_opt(1969, 8, 26).unwrap(),
+                        NaiveDate::from_ymd_opt(1969, 9, 10).unwrap(),
+                        false,
+                    )
+                    .await
+                    .expect("Should succeed");
+                assert_eq!(result.len(), 0);
+            }
+        }
+
+        //
+        // Check no timesheet is returned when we request a date range
+        // that's outside of employee's work window.
+        //
+        {
+            let user_id = USER2_ID;
+            let company_id = COMPANY1_ID;
+
+            let result = timesheet_repo
+                ._read_all_with_date_from_to_per_employment(
+                    user_id,
+                    company_id,
+                    NaiveDate::from_ymd_opt(1969, 9, 1).unwrap(),
+                    NaiveDate::from_ymd_opt(2020, 12, 31).unwrap(),
+                    true,
+                )
+                .await
+                .expect("Should succeed");
+            assert_eq!(result.len(), 0);
+        }
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn review_queue(pool: PgPool) {
+        let arc_pool = Arc::new(pool);
+
+        let timesheet_repo = TimesheetRepository::new(arc_pool.clone());
+        let employment_repo = EmploymentRepository::new(arc_pool);
+
+        // Anna Smeth reports to Tana Smith, who reports to Dave Null.
+        employment_repo
+            .update(
+                USER2_ID,
+                COMPANY1_ID,
+                EmploymentData {
+                    manager_id: Some(USER1_ID),
+                    hourly_wage: None,
+                    start_date: None,
+                    end_date: None,
+                    description: None,
+                    employment_type: None,
+                    level: None,
+                },
+            )
+            .await
+            .expect("Should succeed.");
+        assert!(employment_repo
+            .is_superior(USER0_ID, USER2_ID, COMPANY1_ID)
+            .await
+            .expect("Should succeed."));
+        assert!(!employment_repo
+            .is_superior(USER2_ID, USER1_ID, COMPANY1_ID)
+            .await
+            .expect("Should succeed."));
+
+        timesheet_repo
+            .transition(
+                TIMESHEET0_ID,
+                TimesheetTransitionData {
+                    actor_id: USER2_ID,
+                    transition: TimesheetTransition::Submit,
+                    note: None,
+                },
+            )
+            .await
+            .expect("Should succeed.");
+
+        let queue = timesheet_repo
+            .read_review_queue(USER0_ID, &ReviewQueueFilter::default())
+            .await
+            .expect("Should succeed.");
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue[0].id, TIMESHEET0_ID);
+        assert_eq!(queue[0].approval_status, ApprovalStatus::Pending);
+        assert!(!queue[0].is_direct);
+        assert!(queue[1..].iter().all(|entry| entry.is_direct));
+
+        let queue = timesheet_repo
+            .read_review_queue(USER1_ID, &ReviewQueueFilter::default())
+            .await
+            .expect("Should succeed.");
+        assert_eq!(queue.len(), 1);
+        assert!(queue[0].is_direct);
+
+        for (filter, expected) in [
+            (
+                ReviewQueueFilter {
+                    status: Some(ApprovalStatus::Pending),
+                    ..Default::default()
+                },
+                1,
+            ),
+            (
+                ReviewQueueFilter {
+                    event_id: Some(EVENT0_ID),
+                    ..Default::default()
+                },
+                2,
+            ),
+            (
+                ReviewQueueFilter {
+                    month: NaiveDate::from_ymd_opt(1969, 7, 1),
+                    ..Default::default()
+                },
+                1,
+            ),
+            (
+                ReviewQueueFilter {
+                    min_hours: Some(23.0),
+                    ..Default::default()
+                },
+                2,
+            ),
+        ] {
+            let queue = timesheet_repo
+                .read_review_queue(USER0_ID, &filter)
+                .await
+                .expect("Should succeed.");
+            assert_eq!(queue.len(), expected, "{:?}", filter);
+        }
+
+        let events = timesheet_repo
+            .read_review_queue_events(USER0_ID)
+            .await
+            .expect("Should succeed.");
+        assert_eq!(events.len(), 2);
+
+        let approve = TimesheetTransitionData {
+            actor_id: USER0_ID,
+            transition: TimesheetTransition::Approve,
+            note: Some("Thanks.".to_string()),
+        };
+
+        // The accepted sheet can't be approved, so neither sheet is.
+        let _ = timesheet_repo
+            .transition_all(&[TIMESHEET0_ID, TIMESHEET1_ID], approve.clone())
+            .await
+            .expect_err("Should not succeed.");
+        let sheet = timesheet_repo
+            ._read_one(TIMESHEET0_ID)
+            .await
+            .expect("Should succeed.");
+        assert_eq!(sheet.timesheet.approval_status, ApprovalStatus::Pending);
+
+        timesheet_repo
+            .transition_all(&[TIMESHEET0_ID], approve)
+            .await
+            .expect("Should succeed.");
+        let sheet = timesheet_repo
+            ._read_one(TIMESHEET0_ID)
+            .await
+            .expect("Should succeed.");
+        assert_eq!(sheet.timesheet.approval_status, ApprovalStatus::Accepted);
+        assert_eq!(sheet.timesheet.manager_note, Some("Thanks.".to_string()));
+
+        let history = timesheet_repo
+            .read_history(TIMESHEET0_ID)
+            .await
+            .expect("Should succeed.");
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].actor_id, USER0_ID);
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn read_all_with_date_from_to_per_employment_extended(pool: PgPool) {
+        let arc_pool = Arc::new(pool);
+
+        let timesheet_repo = TimesheetRepository::new(arc_pool);
+
+        {
+            let user_id = USER1_ID;
+            let company_id = COMPANY1_ID;
+            let date_from = NaiveDate::from_ymd_opt(1969, 07, 28).unwrap();
+            let date_to = NaiveDate::from_ymd_opt(1969, 08, 18).unwrap();
+
+            //
+            // Check 2 timesheet are returned.
+            //
+            {
+                let timesheets_extended = timesheet_repo
+                    .read_all_with_date_from_to_per_employment_extended_db(
+                        user_id, company_id, date_from, date_to,
+                    )
+                    .await
+                    .expect("Should succeed");
+
+                assert_eq!(timesheets_extended.timesheets.len(), 2);
+                assert_eq!(timesheets_extended.date_to_wage_presets.len(), 2);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod wage_preset_repo_tests {
+    use std::sync::Arc;
+
+    use chrono::NaiveDate;
+    use organization::{
+        common::DbResult,
+        repositories::{
+            repository::DbRepository, wage_preset::wage_preset_repo::WagePresetRepository,
+        },
+    };
+    use sqlx::PgPool;
+
+    use crate::test_constants::DELTA;
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn read_one(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut wage_preset_repo = WagePresetRepository::new(arc_pool);
+
+        {
+            let name = "cz_2024-01-01".to_string();
+
+            let preset = wage_preset_repo
+                ._read_one(&name)
+                .await
+                .expect("Should succeed");
+            assert_eq!(preset.currency, "CZK");
+        }
+
+        wage_preset_repo.disconnect().await;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn read_all(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut wage_preset_repo = WagePresetRepository::new(arc_pool);
+
+        let presets = wage_preset_repo._read_all().await.expect("Should succeed");
+        assert_eq!(presets.len(), 3);
+
+        wage_preset_repo.disconnect().await;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn read_optional_matching_date(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut wage_preset_repo = WagePresetRepository::new(arc_pool);
+
+        // non-existent
+        {
+            let preset_optional = wage_preset_repo
+                ._read_optional_matching_date(&NaiveDate::from_ymd_opt(1965, 12, 31).unwrap())
+                .await
+                .expect("Should succeed");
+            assert!(preset_optional.is_none());
+        }
+
+        {
+            let preset_optional = wage_preset_repo
+                ._read_optional_matching_date(&NaiveDate::from_ymd_opt(2023, 06, 01).unwrap())
+                .await
+                .expect("Should succeed");
+            assert!(preset_optional.is_some());
+            assert!(preset_optional.unwrap().min_hourly_wage - 100.0 < DELTA);
+        }
+
+        {
+            let preset_optional = wage_preset_repo
+                ._read_optional_matching_date(&NaiveDate::from_ymd_opt(2024, 01, 01).unwrap())
+                .await
+                .expect("Should succeed");
+            assert!(preset_optional.is_some());
+            assert!(preset_optional.unwrap().min_hourly_wage - 118.3 < DELTA);
+        }
+
+        {
+            let preset_optional = wage_preset_repo
+                ._read_optional_matching_date(&NaiveDate::from_ymd_opt(2030, 06, 10).unwrap())
+                .await
+                .expect("Should succeed");
+            assert!(preset_optional.is_some());
+            assert!(preset_optional.unwrap().min_hourly_wage - 118.3 < DELTA);
+        }
+
+        wage_preset_repo.disconnect().await;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod notification_repo_tests {
+    use std::sync::Arc;
+
+    use organization::{
+        common::DbResult,
+        models::{AcceptanceStatus, EventStatus, NotificationKind},
+        repositories::{
+            event::{event_repo::EventRepository, models::EventStatusData},
+            event_staff::{event_staff_repo::StaffRepository, models::StaffData},
+            notification::{
+                models::{NewNotification, NotificationFilter, NotificationPreferenceData},
+                notification_repo::NotificationRepository,
+            },
+            repository::DbRepository,
+        },
+    };
+    use sqlx::PgPool;
+
+    use crate::test_constants;
+
+    #[sqlx::test(fixtures("users"), migrations = "migrations/no_seed")]
+    async fn create_and_mark_read(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut notification_repo = NotificationRepository::new(arc_pool);
+
+        let user_id = test_constants::USER0_ID;
+
+        for message in ["First notification.", "Second notification."] {
+            let notification = notification_repo
+                .create(NewNotification {
+                    user_id,
+                    kind: NotificationKind::CommentMention,
+                    message: message.to_string(),
+                    link: None,
+                })
+                .await
+                .expect("Create should succeed")
+                .expect("Notification should be delivered");
+
+            assert_eq!(notification.user_id, user_id);
+            assert_eq!(notification.message, message);
+            assert!(notification.read_at.is_none());
+        }
+
+        notification_repo
+            .create(NewNotification {
+                user_id,
+                kind: NotificationKind::CommentMention,
+                message: " ".to_string(),
+                link: None,
+            })
+            .await
+            .expect_err("Create should fail - empty message");
+
+        let notifications = notification_repo
+            .read_all_for_user(
+                user_id,
+                NotificationFilter {
+                    limit: None,
+                    offset: None,
+                },
+            )
+            .await
+            .expect("Read should succeed");
+
+        assert_eq!(notifications.len(), 2);
+
+        let unread = notification_repo
+            .count_unread(user_id)
+            .await
+            .expect("Count should succeed");
+
+        assert_eq!(unread, 2);
+
+        let read = notification_repo
+            .mark_read(notifications[0].id)
+            .await
+            .expect("Mark read should succeed");
+
+        assert!(read.read_at.is_some());
+
+        let unread = notification_repo
+            .count_unread(user_id)
+            .await
+            .expect("Count should succeed");
+
+        assert_eq!(unread, 1);
+
+        notification_repo
+            .mark_all_read(user_id)
+            .await
+            .expect("Mark all read should succeed");
+
+        let unread = notification_repo
+            .count_unread(user_id)
+            .await
+            .expect("Count should succeed");
+
+        assert_eq!(unread, 0);
+
+        notification_repo.disconnect().await;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("users"), migrations = "migrations/no_seed")]
+    async fn preferences(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut notification_repo = NotificationRepository::new(arc_pool);
+
+        let user_id = test_constants::USER0_ID;
+
+        let preferences = notification_repo
+            .read_preferences(user_id)
+            .await
+            .expect("Read should succeed");
+
+        assert_eq!(preferences.len(), NotificationKind::all().len());
+        assert!(preferences.iter().all(|preference| preference.is_enabled));
+
+        let preference = notification_repo
+            .update_preference(
+                user_id,
+                NotificationPreferenceData {
+                    kind: NotificationKind::TimesheetRejection,
+                    is_enabled: false,
+                },
+            )
+            .await
+            .expect("Update should succeed");
+
+        assert_eq!(preference.kind, NotificationKind::TimesheetRejection);
+        assert!(!preference.is_enabled);
+
+        let preferences = notification_repo
+            .read_preferences(user_id)
+            .await
+            .expect("Read should succeed");
+
+        for preference in preferences {
+            assert_eq!(
+                preference.is_enabled,
+                preference.kind != NotificationKind::TimesheetRejection
+            );
+        }
+
+        let muted = notification_repo
+            .create(NewNotification {
+                user_id,
+                kind: NotificationKind::TimesheetRejection,
+                message: "Your timesheet was rejected.".to_string(),
+                link: None,
+            })
+            .await
+            .expect("Create should succeed");
+
+        assert!(muted.is_none());
+
+        let delivered = notification_repo
+            .create(NewNotification {
+                user_id,
+                kind: NotificationKind::StaffDecision,
+                message: "Your application was accepted.".to_string(),
+                link: None,
+            })
+            .await
+            .expect("Create should succeed");
+
+        assert!(delivered.is_some());
+
+        notification_repo.disconnect().await;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("event_staff"), migrations = "migrations/no_seed")]
+    async fn emitted_on_staff_decision(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut event_staff_repo = StaffRepository::new(arc_pool.clone());
+        let mut notification_repo = NotificationRepository::new(arc_pool);
+
+        let staff_data = StaffData {
+            role: None,
+            status: Some(AcceptanceStatus::Rejected),
+            decided_by: Some(test_constants::EVENT_STAFF0_ID),
+        };
+
+        // Repeating the same decision doesn't notify the applicant again.
+        for _ in 0..2 {
+            event_staff_repo
+                .update(test_constants::EVENT_STAFF1_ID, staff_data.clone())
+                .await
+                .expect("Update should succeed");
+        }
+
+        let notifications = notification_repo
+            .read_all_for_user(
+                test_constants::USER1_ID,
+                NotificationFilter {
+                    limit: None,
+                    offset: None,
+                },
+            )
+            .await
+            .expect("Read should succeed");
+
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].kind, NotificationKind::StaffDecision);
+        assert!(notifications[0].message.contains("rejected"));
+        assert!(notifications[0].link.is_some());
+
+        event_staff_repo.disconnect().await;
+        notification_repo.disconnect().await;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn emitted_on_event_cancellation(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut event_repo = EventRepository::new(arc_pool.clone());
+        let mut notification_repo = NotificationRepository::new(arc_pool);
+
+        event_repo
+            .change_status(
+                test_constants::EVENT0_ID,
+                EventStatusData {
+                    status: EventStatus::Cancelled,
+                    cancellation_reason: Some("Heavy rain.".to_string()),
+                    changed_by: test_constants::USER0_ID,
+                },
+            )
+            .await
+            .expect("Change should succeed");
+
+        let filter = NotificationFilter {
+            limit: None,
+            offset: None,
+        };
+
+        let notifications = notification_repo
+            .read_all_for_user(test_constants::USER1_ID, filter.clone())
+            .await
+            .expect("Read should succeed");
+
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].kind, NotificationKind::EventStatusChange);
+        assert!(notifications[0].message.contains("cancelled: Heavy rain."));
+
+        // The organizer who cancelled the event isn't notified.
+        let notifications = notification_repo
+            .read_all_for_user(test_constants::USER0_ID, filter)
+            .await
+            .expect("Read should succeed");
+
+        assert!(notifications.is_empty());
+
+        event_repo.disconnect().await;
+        notification_repo.disconnect().await;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod mail_outbox_repo_tests {
+    use std::sync::Arc;
+
+    use organization::{
+        common::DbResult,
+        models::{AcceptanceStatus, EventStatus, MailDelivery, MailStatus},
+        repositories::{
+            event::{event_repo::EventRepository, models::EventStatusData},
+            event_staff::{event_staff_repo::StaffRepository, models::StaffData},
+            mail_outbox::{
+                mail_outbox_repo::MailOutboxRepository,
+                models::{MailSettingData, MAX_MAIL_ATTEMPTS},
+            },
+            repository::DbRepository,
+        },
+        templates::mail::MailContent,
+        utils::mail::{
+            mail_sender::{MailConfig, MailSender},
+            mail_worker::deliver_due_mail,
+        },
+    };
+    use sqlx::PgPool;
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+        net::TcpListener,
+    };
+
+    use crate::test_constants;
+
+    fn staff_decision() -> MailContent {
+        MailContent::StaffDecision {
+            event_name: "Woodstock".to_string(),
+            accepted: true,
+        }
+    }
+
+    fn sender_for(port: u16) -> MailSender {
+        MailSender::new(&MailConfig {
+            smtp_host: "127.0.0.1".to_string(),
+            smtp_port: port,
+            smtp_username: None,
+            smtp_password: None,
+            smtp_tls: false,
+            mail_from: "Orchestrate <noreply@orchestrate.local>".to_string(),
+        })
+        .expect("Sender should be valid")
+    }
+
+    /// Accepts a single SMTP session and returns the received message.
+    async fn run_smtp_sink(listener: TcpListener) -> String {
+        let (stream, _) = listener.accept().await.expect("Should accept");
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+
+        writer.write_all(b"220 sink ESMTP\r\n").await.unwrap();
+
+        let mut message = String::new();
+        let mut in_data = false;
+        while let Some(line) = lines.next_line().await.unwrap() {
+            if in_data {
+                if line == "." {
+                    in_data = false;
+                    writer.write_all(b"250 queued\r\n").await.unwrap();
+                } else {
+                    message.push_str(&line);
+                    message.push('\n');
+                }
+                continue;
+            }
+
+            let reply: &[u8] = match line.to_uppercase().split(' ').next() {
+                Some("DATA") => {
+                    in_data = true;
+                    b"354 go ahead\r\n"
+                }
+                Some("QUIT") => {
+                    writer.write_all(b"221 bye\r\n").await.unwrap();
+                    break;
+                }
+                _ => b"250 OK\r\n",
+            };
+            writer.write_all(reply).await.unwrap();
+        }
+
+        message
+    }
+
+    #[sqlx::test(fixtures("event_staff"), migrations = "migrations/no_seed")]
+    async fn queued_on_staff_decision(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut event_staff_repo = StaffRepository::new(arc_pool.clone());
+        let mut mail_outbox_repo = MailOutboxRepository::new(arc_pool);
+
+        event_staff_repo
+            .update(
+                test_constants::EVENT_STAFF1_ID,
+                StaffData {
+                    role: None,
+                    status: Some(AcceptanceStatus::Accepted),
+                    decided_by: Some(test_constants::EVENT_STAFF0_ID),
+                },
+            )
+            .await
+            .expect("Update should succeed");
+
+        let mails = mail_outbox_repo
+            .read_due(10)
+            .await
+            .expect("Read should succeed");
+
+        assert_eq!(mails.len(), 1);
+        assert_eq!(mails[0].user_id, test_constants::USER1_ID);
+        assert_eq!(mails[0].recipient, "t.smith@seznam.cz");
+        assert_eq!(mails[0].status, MailStatus::Pending);
+        assert!(mails[0].subject.contains("accepted"));
+        assert!(mails[0].body_html.contains("Tana Smith"));
+
+        event_staff_repo.disconnect().await;
+        mail_outbox_repo.disconnect().await;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn queued_on_event_completion(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut event_repo = EventRepository::new(arc_pool.clone());
+        let mut mail_outbox_repo = MailOutboxRepository::new(arc_pool);
+
+        event_repo
+            .change_status(
+                test_constants::EVENT0_ID,
+                EventStatusData {
+                    status: EventStatus::Completed,
+                    cancellation_reason: None,
+                    changed_by: test_constants::USER0_ID,
+                },
+            )
+            .await
+            .expect("Change should succeed");
+
+        let mails = mail_outbox_repo
+            .read_due(10)
+            .await
+            .expect("Read should succeed");
+
+        let mail = mails
+            .iter()
+            .find(|mail| mail.user_id == test_constants::USER1_ID)
+            .expect("The accepted staff should be mailed");
+        assert_eq!(mail.subject, "Woodstock was completed");
+        assert!(mail
+            .body_text
+            .contains("Please submit your final timesheets."));
+        assert!(mails
+            .iter()
+            .all(|mail| mail.user_id != test_constants::USER0_ID));
+
+        event_repo.disconnect().await;
+        mail_outbox_repo.disconnect().await;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("users"), migrations = "migrations/no_seed")]
+    async fn daily_digest(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut mail_outbox_repo = MailOutboxRepository::new(arc_pool.clone());
+
+        let user_id = test_constants::USER1_ID;
+
+        let setting = mail_outbox_repo
+            .update_setting(
+                user_id,
+                MailSettingData {
+                    delivery: MailDelivery::DailyDigest,
+                },
+            )
+            .await
+            .expect("Update should succeed");
+
+        assert_eq!(setting.delivery, MailDelivery::DailyDigest);
+
+        for _ in 0..2 {
+            let mail = mail_outbox_repo
+                .enqueue(user_id, &staff_decision())
+                .await
+                .expect("Enqueue should succeed");
+
+            assert_eq!(mail.status, MailStatus::Digest);
+        }
+
+        let due = mail_outbox_repo
+            .read_due(10)
+            .await
+            .expect("Read should succeed");
+        assert!(due.is_empty());
+
+        // The digest went out just now.
+        let digests = mail_outbox_repo
+            .bundle_due_digests()
+            .await
+            .expect("Bundle should succeed");
+        assert!(digests.is_empty());
+
+        sqlx::query!(
+            "UPDATE mail_setting SET last_digest_at = NOW() - INTERVAL '25 hours' WHERE user_id = $1;",
+            user_id,
+        )
+        .execute(arc_pool.as_ref())
+        .await?;
+
+        let digests = mail_outbox_repo
+            .bundle_due_digests()
+            .await
+            .expect("Bundle should succeed");
+
+        assert_eq!(digests.len(), 1);
+        assert_eq!(digests[0].status, MailStatus::Pending);
+        assert!(digests[0].subject.contains("2 updates"));
+        assert_eq!(
+            digests[0]
+                .body_text
+                .matches(&staff_decision().subject())
+                .count(),
+            2
+        );
+
+        // Switching back to immediate delivery releases the waiting mail.
+        let waiting = mail_outbox_repo
+            .enqueue(user_id, &staff_decision())
+            .await
+            .expect("Enqueue should succeed");
+
+        mail_outbox_repo
+            .update_setting(
+                user_id,
+                MailSettingData {
+                    delivery: MailDelivery::Immediate,
+                },
+            )
+            .await
+            .expect("Update should succeed");
+
+        let released = mail_outbox_repo
+            .read_one(waiting.id)
+            .await
+            .expect("Read should succeed");
+        assert_eq!(released.status, MailStatus::Pending);
+
+        mail_outbox_repo.disconnect().await;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("users"), migrations = "migrations/no_seed")]
+    async fn retried_until_failed(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut mail_outbox_repo = MailOutboxRepository::new(arc_pool);
+
+        let mail = mail_outbox_repo
+            .enqueue(test_constants::USER0_ID, &staff_decision())
+            .await
+            .expect("Enqueue should succeed");
+
+        let retried = mail_outbox_repo
+            .mark_failed_attempt(mail.id, "Connection refused")
+            .await
+            .expect("Mark should succeed");
+
+        assert_eq!(retried.status, MailStatus::Pending);
+        assert_eq!(retried.attempts, 1);
+        assert!(retried.next_attempt_at > mail.next_attempt_at);
+
+        // Backing off until the next attempt.
+        let due = mail_outbox_repo
+            .read_due(10)
+            .await
+            .expect("Read should succeed");
+        assert!(due.is_empty());
+
+        let mut failed = retried;
+        for _ in 1..MAX_MAIL_ATTEMPTS {
+            failed = mail_outbox_repo
+                .mark_failed_attempt(mail.id, "Connection refused")
+                .await
+                .expect("Mark should succeed");
+        }
+
+        assert_eq!(failed.status, MailStatus::Failed);
+        assert_eq!(failed.attempts, MAX_MAIL_ATTEMPTS);
+        assert_eq!(failed.last_error, Some("Connection refused".to_string()));
+
+        mail_outbox_repo
+            .mark_failed_attempt(mail.id, "Connection refused")
+            .await
+            .expect_err("Mark should fail - the mail was given up on");
+
+        mail_outbox_repo.disconnect().await;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("users"), migrations = "migrations/no_seed")]
+    async fn delivered_over_smtp(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut mail_outbox_repo = MailOutboxRepository::new(arc_pool);
+
+        let mail = mail_outbox_repo
+            .enqueue(test_constants::USER0_ID, &staff_decision())
+            .await
+            .expect("Enqueue should succeed");
+
+        // Nobody listens on the port anymore.
+        let closed_port = {
+            let listener = TcpListener::bind("127.0.0.1:0").await?;
+            listener.local_addr()?.port()
+        };
+        let sent = deliver_due_mail(&mail_outbox_repo, &sender_for(closed_port))
+            .await
+            .expect("Delivery round should succeed");
+        assert_eq!(sent, 0);
+
+        let retried = mail_outbox_repo
+            .read_one(mail.id)
+            .await
+            .expect("Read should succeed");
+        assert_eq!(retried.attempts, 1);
+        assert!(retried.last_error.is_some());
+
+        let mail = mail_outbox_repo
+            .enqueue(test_constants::USER0_ID, &staff_decision())
+            .await
+            .expect("Enqueue should succeed");
+
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let sender = sender_for(listener.local_addr()?.port());
+        let sink = tokio::spawn(run_smtp_sink(listener));
+
+        let sent = deliver_due_mail(&mail_outbox_repo, &sender)
+            .await
+            .expect("Delivery round should succeed");
+        assert_eq!(sent, 1);
+
+        let message = sink.await.expect("Sink should finish");
+        assert!(message.contains("To: dave@null.com"));
+        assert!(message.contains(&format!("Subject: {}", mail.subject)));
+        assert!(message.contains("multipart/alternative"));
+
+        let delivered = mail_outbox_repo
+            .read_one(mail.id)
+            .await
+            .expect("Read should succeed");
+        assert_eq!(delivered.status, MailStatus::Sent);
+        assert!(delivered.sent_at.is_some());
+
+        mail_outbox_repo.disconnect().await;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod live_update_tests {
+    use std::{sync::Arc, time::Duration};
+
+    use futures::StreamExt;
+    use organization::{
+        common::DbResult,
+        repositories::{
+            comment::{comment_repo::CommentRepository, models::NewComment},
+            repository::DbRepository,
+            task::{models::TaskData, task_repo::TaskRepository},
+        },
+        utils::live_update::live_update_hub::LiveUpdateHub,
+    };
+    use sqlx::PgPool;
+
+    use crate::test_constants;
+
+    #[sqlx::test(fixtures("task"), migrations = "migrations/no_seed")]
+    async fn changes_reach_subscribers_of_the_event(pool: PgPool) -> DbResult<()> {
+        let hub = LiveUpdateHub::new();
+        let listener = hub.spawn_listener(pool.clone());
+
+        let arc_pool = Arc::new(pool);
+        let mut task_repo = TaskRepository::new(arc_pool.clone());
+        let mut comment_repo = CommentRepository::new(arc_pool);
+
+        let mut event0_updates = Box::pin(hub.subscribe(test_constants::EVENT0_ID));
+        let mut event1_updates = Box::pin(hub.subscribe(test_constants::EVENT1_ID));
+
+        // Give the listener a moment to start listening.
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        task_repo
+            .update(
+                test_constants::TASK0_ID,
+                TaskData {
+                    title: Some("Prepare the main stage".to_string()),
+                    description: None,
+                    finished_at: None,
+                    priority: None,
+                    accepts_staff: None,
+                    status: None,
+                    due_at: None,
+                    estimated_hours: None,
+                    parent_task_id: None,
+                },
+            )
+            .await
+            .expect("Update should succeed");
+
+        comment_repo
+            .create(NewComment {
+                author_id: test_constants::USER0_ID,
+                event_id: Some(test_constants::EVENT0_ID),
+                task_id: None,
+                parent_id: None,
+                content: "The stage is ready.".to_string(),
+            })
+            .await
+            .expect("Create should succeed");
+
+        for topic in ["tasks", "comments"] {
+            let message = tokio::time::timeout(Duration::from_secs(5), event0_updates.next())
+                .await
+                .expect("Update should arrive")
+                .expect("Stream should be open")
+                .expect("Message should be valid");
+
+            assert_eq!(
+                message,
+                format!("event: {}\ndata: {}\n\n", topic, topic).as_bytes()
+            );
+        }
+
+        let other_event =
+            tokio::time::timeout(Duration::from_millis(500), event1_updates.next()).await;
+        assert!(other_event.is_err());
+
+        listener.abort();
+        task_repo.disconnect().await;
+        comment_repo.disconnect().await;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod calendar_feed_repo_tests {
+    use std::sync::Arc;
+
+    use chrono::NaiveDateTime;
+    use organization::{
+        common::DbResult,
+        repositories::{
+            calendar_feed::calendar_feed_repo::CalendarFeedRepository,
+            repository::DbRepository,
+            task::{models::TaskData, task_repo::TaskRepository},
+        },
+    };
+    use sqlx::PgPool;
+
+    use crate::test_constants;
+
+    #[sqlx::test(fixtures("events"), migrations = "migrations/no_seed")]
+    async fn feeds_and_rotation(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut calendar_feed_repo = CalendarFeedRepository::new(arc_pool);
+
+        let user_id = test_constants::USER0_ID;
+
+        let feed = calendar_feed_repo
+            .read_or_create(user_id, None)
+            .await
+            .expect("Create should succeed");
+        let same_feed = calendar_feed_repo
+            .read_or_create(user_id, None)
+            .await
+            .expect("Read should succeed");
+
+        assert_eq!(feed.token, same_feed.token);
+        assert!(feed.event_id.is_none());
+
+        let event_feed = calendar_feed_repo
+            .read_or_create(user_id, Some(test_constants::EVENT0_ID))
+            .await
+            .expect("Create should succeed");
+
+        assert_ne!(event_feed.token, feed.token);
+        assert_eq!(event_feed.event_id, Some(test_constants::EVENT0_ID));
+
+        let rotated = calendar_feed_repo
+            .rotate(user_id, None)
+            .await
+            .expect("Rotate should succeed");
+
+        assert_ne!(rotated.token, feed.token);
+
+        calendar_feed_repo
+            .read_by_token(feed.token)
+            .await
+            .expect_err("Read should fail - the token was rotated");
+
+        let found = calendar_feed_repo
+            .read_by_token(rotated.token)
+            .await
+            .expect("Read should succeed");
+        assert_eq!(found.user_id, user_id);
+
+        // The event feed keeps its own token.
+        calendar_feed_repo
+            .read_by_token(event_feed.token)
+            .await
+            .expect("Read should succeed");
+
+        calendar_feed_repo.disconnect().await;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("assigned_staff"), migrations = "migrations/no_seed")]
+    async fn entries(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut calendar_feed_repo = CalendarFeedRepository::new(arc_pool.clone());
+        let mut task_repo = TaskRepository::new(arc_pool);
+
+        let tasks = calendar_feed_repo
+            .read_tasks_for_user(test_constants::USER0_ID)
+            .await
+            .expect("Read should succeed");
+        assert!(tasks.is_empty());
+
+        let due_at = NaiveDateTime::parse_from_str("1969-08-14 18:00:00", "%Y-%m-%d %H:%M:%S")
+            .expect("Should be valid");
+        task_repo
+            .update(
+                test_constants::TASK0_ID,
+                TaskData {
+                    title: None,
+                    description: None,
+                    finished_at: None,
+                    priority: None,
+                    accepts_staff: None,
+                    status: None,
+                    due_at: Some(due_at),
+                    estimated_hours: None,
+                    parent_task_id: None,
+                },
+            )
+            .await
+            .expect("Update should succeed");
+
+        let events = calendar_feed_repo
+            .read_events_for_user(test_constants::USER0_ID)
+            .await
+            .expect("Read should succeed");
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].id, test_constants::EVENT0_ID);
+
+        let tasks = calendar_feed_repo
+            .read_tasks_for_user(test_constants::USER0_ID)
+            .await
+            .expect("Read should succeed");
+
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].id, test_constants::TASK0_ID);
+        assert_eq!(tasks[0].due_at, due_at);
+
+        // Pending staff don't get the event into their calendar.
+        let events = calendar_feed_repo
+            .read_events_for_user(test_constants::USER1_ID)
+            .await
+            .expect("Read should succeed");
+        assert!(events.is_empty());
+
+        let event_tasks = calendar_feed_repo
+            .read_tasks_for_event(test_constants::EVENT0_ID)
+            .await
+            .expect("Read should succeed");
+        assert!(event_tasks
+            .iter()
+            .any(|task| task.id == test_constants::TASK0_ID));
+
+        calendar_feed_repo.disconnect().await;
+        task_repo.disconnect().await;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod event_budget_repo_tests {
+    use std::sync::Arc;
+
+    use chrono::NaiveDate;
+    use organization::{
+        common::DbResult,
+        models::{ApprovalStatus, EmploymentContract, EventRole},
+        repositories::{
+            event_budget::{event_budget_repo::EventBudgetRepository, models::NewBudgetLine},
+            repository::DbRepository,
+        },
+    };
+    use sqlx::PgPool;
+
+    use crate::test_constants;
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn lines_staff_and_timesheets(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut budget_repo = EventBudgetRepository::new(arc_pool);
+
+        let line = budget_repo
+            .create(NewBudgetLine {
+                event_id: test_constants::EVENT0_ID,
+                company_id: Some(test_constants::COMPANY0_ID),
+                role: EventRole::Organizer,
+                date: NaiveDate::from_ymd_opt(1969, 8, 16),
+                headcount: 2,
+                hours: 10.0,
+                hourly_rate: 300.0,
+            })
+            .await
+            .expect("Create should succeed");
+
+        assert_eq!(line.company_name.as_deref(), Some("AMD"));
+        assert_eq!(line.role, EventRole::Organizer);
+
+        let lines = budget_repo
+            .read_all_for_event(test_constants::EVENT0_ID)
+            .await
+            .expect("Read should succeed");
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].id, line.id);
+
+        let staff = budget_repo
+            .read_forecast_staff(test_constants::EVENT0_ID)
+            .await
+            .expect("Read should succeed");
+        let tana = staff
+            .iter()
+            .find(|member| member.user_id == test_constants::USER1_ID)
+            .expect("Tana is accepted staff of the event");
+        assert_eq!(tana.role, EventRole::Staff);
+        assert_eq!(tana.employment_type, EmploymentContract::Dpc);
+        assert_eq!(tana.hourly_wage, 200.0);
+
+        // The timesheet which wasn't submitted for approval doesn't count.
+        let timesheets = budget_repo
+            .read_actual_timesheets(test_constants::EVENT0_ID)
+            .await
+            .expect("Read should succeed");
+        assert_eq!(timesheets.len(), 1);
+        assert_eq!(timesheets[0].id, test_constants::TIMESHEET1_ID);
+        assert_eq!(timesheets[0].status, ApprovalStatus::Accepted);
+
+        budget_repo
+            .delete(line.id)
+            .await
+            .expect("Delete should succeed");
+        assert!(budget_repo.delete(line.id).await.is_err());
+        assert!(budget_repo
+            .read_all_for_event(test_constants::EVENT0_ID)
+            .await
+            .expect("Read should succeed")
+            .is_empty());
+
+        budget_repo.disconnect().await;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod report_repo_tests {
+    use std::sync::Arc;
+
+    use chrono::{NaiveDate, Utc};
+    use organization::{
+        common::DbResult,
+        models::TimesheetTransition,
+        repositories::{
+            report::{models::ReportFilter, report_repo::ReportRepository},
+            repository::DbRepository,
+            timesheet::{models::TimesheetTransitionData, timesheet_repo::TimesheetRepository},
+        },
+    };
+    use sqlx::PgPool;
+
+    use crate::test_constants;
+
+    fn summer_of_1969(company_id: Option<uuid::Uuid>) -> ReportFilter {
+        ReportFilter {
+            date_from: NaiveDate::from_ymd_opt(1969, 7, 1).unwrap(),
+            date_to: NaiveDate::from_ymd_opt(1969, 8, 31).unwrap(),
+            company_id,
+        }
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn hours_utilization_and_tasks(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut report_repo = ReportRepository::new(arc_pool);
+
+        let hours = report_repo
+            .read_monthly_hours(&summer_of_1969(None))
+            .await
+            .expect("Read should succeed");
+        assert_eq!(hours.len(), 3);
+        let woodstock = hours
+            .iter()
+            .find(|hours| hours.event_id == test_constants::EVENT0_ID)
+            .expect("Woodstock should have hours");
+        // Only one of the two timesheets is accepted.
+        assert_eq!(woodstock.reported_hours, 46.0);
+        assert_eq!(woodstock.accepted_hours, 24.0);
+        assert_eq!(woodstock.staff_count, 2);
+
+        let other_company = report_repo
+            .read_monthly_hours(&summer_of_1969(Some(test_constants::COMPANY0_ID)))
+            .await
+            .expect("Read should succeed");
+        assert!(other_company.is_empty());
+
+        let utilization = report_repo
+            .read_staff_utilization(&summer_of_1969(Some(test_constants::COMPANY1_ID)))
+            .await
+            .expect("Read should succeed");
+        assert_eq!(utilization.len(), 1);
+        // Tana Smith staffed both Woodstock and the Elvis revival.
+        assert_eq!(utilization[0].active_staff, 1);
+        assert_eq!(utilization[0].returning_staff, 1);
+        assert_eq!(utilization[0].events, 2);
+        assert_eq!(utilization[0].worked_hours, 99.0);
+        assert_eq!(utilization[0].retention_pct(), 100.0);
+
+        let tasks = report_repo
+            .read_task_completion(&summer_of_1969(Some(test_constants::COMPANY0_ID)))
+            .await
+            .expect("Read should succeed");
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].event_id, test_constants::EVENT0_ID);
+        assert_eq!(tasks[0].tasks, 1);
+        assert_eq!(tasks[0].completion_pct(), 0.0);
+
+        report_repo.disconnect().await;
+
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn approval_latency(pool: PgPool) -> DbResult<()> {
+        let arc_pool = Arc::new(pool);
+
+        let mut report_repo = ReportRepository::new(arc_pool.clone());
+        let timesheet_repo = TimesheetRepository::new(arc_pool);
+
+        let today = Utc::now().date_naive();
+        let filter = ReportFilter {
+            date_from: today.pred_opt().unwrap(),
+            date_to: today.succ_opt().unwrap(),
+            company_id: None,
+        };
+
+        // Timesheets reviewed before their submission was tracked are left out.
+        let latency = report_repo
+            .read_approval_latency(&filter)
+            .await
+            .expect("Read should succeed");
+        assert!(latency.is_empty());
+
+        for (actor_id, transition) in [
+            (test_constants::USER2_ID, TimesheetTransition::Submit),
+            (test_constants::USER0_ID, TimesheetTransition::Approve),
+        ] {
+            timesheet_repo
+                .transition(
+                    test_constants::TIMESHEET0_ID,
+                    TimesheetTransitionData {
+                        actor_id,
+                        transition,
+                        note: None,
+                    },
+                )
+                .await
+                .expect("Transition should succeed");
+        }
+
+        let latency = report_repo
+            .read_approval_latency(&filter)
+            .await
+            .expect("Read should succeed");
+        assert_eq!(latency.len(), 1);
+        assert_eq!(latency[0].company_id, test_constants::COMPANY1_ID);
+        assert_eq!(latency[0].submitted, 1);
+        assert_eq!(latency[0].reviewed, 1);
+        assert_eq!(latency[0].accepted, 1);
+        assert_eq!(latency[0].pending, 0);
+        assert!(latency[0].average_latency_hours.unwrap() >= 0.0);
+
+        report_repo.disconnect().await;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod submission_policy_repo_tests {
+    use std::sync::Arc;
+
+    use chrono::NaiveDate;
+    use organization::{
+        models::{ApprovalStatus, NotificationKind, OverduePolicy, TimesheetTransition},
+        repositories::{
+            employment::{employment_repo::EmploymentRepository, models::EmploymentData},
+            notification::{models::NotificationFilter, notification_repo::NotificationRepository},
+            repository::DbRepository,
+            submission_policy::{
+                models::SubmissionPolicyData, submission_policy_repo::SubmissionPolicyRepository,
+            },
+            timesheet::{models::TimesheetTransitionData, timesheet_repo::TimesheetRepository},
+        },
+    };
+    use sqlx::PgPool;
+
+    use crate::test_constants::{
+        COMPANY1_ID, TIMESHEET0_ID, USER0_ID, USER1_ID, USER2_ID, USER3_ID,
+    };
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(1969, 9, day).unwrap()
+    }
+
+    fn policy(overdue_policy: OverduePolicy) -> SubmissionPolicyData {
+        SubmissionPolicyData {
+            deadline_day: 5,
+            reminder_days: vec![3, 1],
+            overdue_policy,
+        }
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn crud(pool: PgPool) {
+        let submission_policy_repo = SubmissionPolicyRepository::new(Arc::new(pool));
+
+        let policy_res = submission_policy_repo
+            .read_one(COMPANY1_ID)
+            .await
+            .expect("Should succeed.");
+        assert!(policy_res.is_none());
+
+        submission_policy_repo
+            .upsert(COMPANY1_ID, policy(OverduePolicy::Lock))
+            .await
+            .expect("Should succeed.");
+        let policy = submission_policy_repo
+            .upsert(
+                COMPANY1_ID,
+                SubmissionPolicyData {
+                    deadline_day: 10,
+                    reminder_days: vec![7],
+                    overdue_policy: OverduePolicy::Submit,
+                },
+            )
+            .await
+            .expect("Should succeed.");
+        assert_eq!(policy.deadline_day, 10);
+        assert_eq!(policy.reminder_days, vec![7]);
+        assert_eq!(policy.overdue_policy, OverduePolicy::Submit);
+
+        submission_policy_repo
+            .delete(COMPANY1_ID)
+            .await
+            .expect("Should succeed.");
+        let policy_res = submission_policy_repo
+            .read_one(COMPANY1_ID)
+            .await
+            .expect("Should succeed.");
+        assert!(policy_res.is_none());
+        let _ = submission_policy_repo
+            .delete(COMPANY1_ID)
+            .await
+            .expect_err("Should not succeed.");
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn reminders_and_lock(pool: PgPool) {
+        let arc_pool = Arc::new(pool);
+
+        let submission_policy_repo = SubmissionPolicyRepository::new(arc_pool.clone());
+        let timesheet_repo = TimesheetRepository::new(arc_pool.clone());
+        let notification_repo = NotificationRepository::new(arc_pool.clone());
+        let employment_repo = EmploymentRepository::new(arc_pool);
+
+        // Anna Smeth reports to Tana Smith, who reports to Dave Null.
+        employment_repo
+            .update(
+                USER2_ID,
+                COMPANY1_ID,
+                EmploymentData {
+                    manager_id: Some(USER1_ID),
+                    hourly_wage: None,
+                    start_date: None,
+                    end_date: None,
+                    description: None,
+                    employment_type: None,
+                    level: None,
+                },
+            )
+            .await
+            .expect("Should succeed.");
+
+        // Anna Smeth's sheet ends in August 1969, so it is due on 5.9.1969.
+        submission_policy_repo
+            .upsert(COMPANY1_ID, policy(OverduePolicy::Lock))
+            .await
+            .expect("Should succeed.");
+
+        for (today, expected) in [(1, 0), (2, 1), (2, 0), (3, 0), (4, 1), (5, 0)] {
+            let reminded = submission_policy_repo
+                .send_due_reminders(day(today))
+                .await
+                .expect("Should succeed.");
+            assert_eq!(reminded, expected, "{}.9.1969", today);
+        }
+
+        let reminders = notification_repo
+            .read_all_for_user(
+                USER2_ID,
+                NotificationFilter {
+                    limit: None,
+                    offset: None,
+                },
+            )
+            .await
+            .expect("Should succeed.")
+            .into_iter()
+            .filter(|notification| notification.kind == NotificationKind::SubmissionReminder)
+            .collect::<Vec<_>>();
+        assert_eq!(reminders.len(), 2);
+        assert!(reminders[0].message.contains("05.09.1969"));
+
+        let overdue = submission_policy_repo
+            .apply_overdue_policies(day(5))
+            .await
+            .expect("Should succeed.");
+        assert_eq!(overdue, 0);
+        let overdue = submission_policy_repo
+            .apply_overdue_policies(day(6))
+            .await
+            .expect("Should succeed.");
+        assert_eq!(overdue, 1);
+        let overdue = submission_policy_repo
+            .apply_overdue_policies(day(7))
+            .await
+            .expect("Should succeed.");
+        assert_eq!(overdue, 0);
+
+        let sheet = timesheet_repo
+            ._read_one(TIMESHEET0_ID)
+            .await
+            .expect("Should succeed.");
+        assert!(!sheet.timesheet.is_editable);
+        assert_eq!(
+            sheet.timesheet.approval_status,
+            ApprovalStatus::NotRequested
+        );
 
-            // check date range yielding nothing
-            {
-                let result = timesheet_repo
-                    ._read_all_with_date_from_to_per_employment(
-                        user_id,
-                        company_id,
-                        NaiveDate::from_ymd_opt(1969, 8, 26).unwrap(),
-                        NaiveDate::from_ymd_opt(1969, 9, 10).unwrap(),
-                        false,
-                    )
-                    .await
-                    .expect("Should succeed");
-                assert_eq!(result.len(), 0);
-            }
+        for manager_id in [USER0_ID, USER1_ID] {
+            let overdue = submission_policy_repo
+                .read_overdue(manager_id)
+                .await
+                .expect("Should succeed.");
+            assert_eq!(overdue.len(), 1);
+            assert_eq!(overdue[0].id, TIMESHEET0_ID);
+            assert_eq!(overdue[0].deadline, day(5));
+            assert!(overdue[0].is_locked);
+        }
+        let overdue = submission_policy_repo
+            .read_overdue(USER3_ID)
+            .await
+            .expect("Should succeed.");
+        assert!(overdue.is_empty());
+
+        // The locked sheet can still be submitted as it is, withdrawing it
+        // doesn't unlock it.
+        for transition in [TimesheetTransition::Submit, TimesheetTransition::Withdraw] {
+            timesheet_repo
+                .transition(
+                    TIMESHEET0_ID,
+                    TimesheetTransitionData {
+                        actor_id: USER2_ID,
+                        transition,
+                        note: None,
+                    },
+                )
+                .await
+                .expect("Should succeed.");
+        }
+        let sheet = timesheet_repo
+            ._read_one(TIMESHEET0_ID)
+            .await
+            .expect("Should succeed.");
+        assert!(!sheet.timesheet.is_editable);
+
+        // Only the manager sending it back unlocks it.
+        for (actor_id, transition) in [
+            (USER2_ID, TimesheetTransition::Submit),
+            (USER0_ID, TimesheetTransition::Reject),
+        ] {
+            timesheet_repo
+                .transition(
+                    TIMESHEET0_ID,
+                    TimesheetTransitionData {
+                        actor_id,
+                        transition,
+                        note: Some("Please fix the hours.".to_string()),
+                    },
+                )
+                .await
+                .expect("Should succeed.");
         }
+        let sheet = timesheet_repo
+            ._read_one(TIMESHEET0_ID)
+            .await
+            .expect("Should succeed.");
+        assert!(sheet.timesheet.is_editable);
+        let overdue = submission_policy_repo
+            .read_overdue(USER0_ID)
+            .await
+            .expect("Should succeed.");
+        assert!(overdue.is_empty());
+    }
 
-        //
-        // Check no timesheet is returned when we request a date range
-        // that's outside of employee's work window.
-        //
-        {
-            let user_id = USER2_ID;
-            let company_id = COMPANY1_ID;
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn reject_after_lock(pool: PgPool) {
+        let arc_pool = Arc::new(pool);
 
-            let result = timesheet_repo
-                ._read_all_with_date_from_to_per_employment(
-                    user_id,
-                    company_id,
-                    NaiveDate::from_ymd_opt(1969, 9, 1).unwrap(),
-                    NaiveDate::from_ymd_opt(2020, 12, 31).unwrap(),
-                    true,
+        let submission_policy_repo = SubmissionPolicyRepository::new(arc_pool.clone());
+        let timesheet_repo = TimesheetRepository::new(arc_pool.clone());
+        let employment_repo = EmploymentRepository::new(arc_pool);
+
+        employment_repo
+            .update(
+                USER2_ID,
+                COMPANY1_ID,
+                EmploymentData {
+                    manager_id: Some(USER1_ID),
+                    hourly_wage: None,
+                    start_date: None,
+                    end_date: None,
+                    description: None,
+                    employment_type: None,
+                    level: None,
+                },
+            )
+            .await
+            .expect("Should succeed.");
+
+        submission_policy_repo
+            .upsert(COMPANY1_ID, policy(OverduePolicy::Lock))
+            .await
+            .expect("Should succeed.");
+        let overdue = submission_policy_repo
+            .apply_overdue_policies(day(6))
+            .await
+            .expect("Should succeed.");
+        assert_eq!(overdue, 1);
+
+        for (actor_id, transition) in [
+            (USER2_ID, TimesheetTransition::Submit),
+            (USER1_ID, TimesheetTransition::Reject),
+        ] {
+            timesheet_repo
+                .transition(
+                    TIMESHEET0_ID,
+                    TimesheetTransitionData {
+                        actor_id,
+                        transition,
+                        note: None,
+                    },
                 )
                 .await
-                .expect("Should succeed");
-            assert_eq!(result.len(), 0);
+                .expect("Should succeed.");
         }
+
+        // The rejected sheet got a new deadline, it isn't locked again
+        // straight away.
+        let overdue = submission_policy_repo
+            .apply_overdue_policies(day(7))
+            .await
+            .expect("Should succeed.");
+        assert_eq!(overdue, 0);
+        let sheet = timesheet_repo
+            ._read_one(TIMESHEET0_ID)
+            .await
+            .expect("Should succeed.");
+        assert!(sheet.timesheet.is_editable);
+        assert_eq!(sheet.timesheet.approval_status, ApprovalStatus::Rejected);
+        let overdue = submission_policy_repo
+            .read_overdue(USER0_ID)
+            .await
+            .expect("Should succeed.");
+        assert!(overdue.is_empty());
+
+        // Missing the new deadline locks it again.
+        let later = NaiveDate::from_ymd_opt(2100, 1, 1).unwrap();
+        let overdue = submission_policy_repo
+            .apply_overdue_policies(later)
+            .await
+            .expect("Should succeed.");
+        assert_eq!(overdue, 1);
+        let overdue = submission_policy_repo
+            .read_overdue(USER0_ID)
+            .await
+            .expect("Should succeed.");
+        assert_eq!(overdue.len(), 1);
+        assert!(overdue[0].is_locked);
+        assert!(overdue[0].deadline > day(5));
     }
 
     #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
-    async fn read_all_with_date_from_to_per_employment_extended(pool: PgPool) {
+    async fn overdue_submit(pool: PgPool) {
         let arc_pool = Arc::new(pool);
 
+        let submission_policy_repo = SubmissionPolicyRepository::new(arc_pool.clone());
         let timesheet_repo = TimesheetRepository::new(arc_pool);
 
-        {
-            let user_id = USER1_ID;
-            let company_id = COMPANY1_ID;
-            let date_from = NaiveDate::from_ymd_opt(1969, 07, 28).unwrap();
-            let date_to = NaiveDate::from_ymd_opt(1969, 08, 18).unwrap();
+        submission_policy_repo
+            .upsert(COMPANY1_ID, policy(OverduePolicy::Submit))
+            .await
+            .expect("Should succeed.");
 
-            //
-            // Check 2 timesheet are returned.
-            //
-            {
-                let timesheets_extended = timesheet_repo
-                    .read_all_with_date_from_to_per_employment_extended_db(
-                        user_id, company_id, date_from, date_to,
-                    )
-                    .await
-                    .expect("Should succeed");
+        let overdue = submission_policy_repo
+            .apply_overdue_policies(day(6))
+            .await
+            .expect("Should succeed.");
+        assert_eq!(overdue, 1);
 
-                assert_eq!(timesheets_extended.timesheets.len(), 2);
-                assert_eq!(timesheets_extended.date_to_wage_presets.len(), 2);
-            }
-        }
+        let sheet = timesheet_repo
+            ._read_one(TIMESHEET0_ID)
+            .await
+            .expect("Should succeed.");
+        assert_eq!(sheet.timesheet.approval_status, ApprovalStatus::Pending);
+
+        let history = timesheet_repo
+            .read_history(TIMESHEET0_ID)
+            .await
+            .expect("Should succeed.");
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].actor_id, USER2_ID);
+        assert_eq!(history[0].transition, TimesheetTransition::Submit);
+
+        // No reminders are sent for the submitted sheet.
+        let reminded = submission_policy_repo
+            .send_due_reminders(day(4))
+            .await
+            .expect("Should succeed.");
+        assert_eq!(reminded, 0);
     }
 }
 
 #[cfg(test)]
-mod wage_preset_repo_tests {
+mod timesheet_amendment_repo_tests {
     use std::sync::Arc;
 
-    use chrono::NaiveDate;
+    use chrono::{Datelike, Local, NaiveDate};
     use organization::{
-        common::DbResult,
+        models::AmendmentStatus,
         repositories::{
-            repository::DbRepository, wage_preset::wage_preset_repo::WagePresetRepository,
+            repository::DbRepository,
+            timesheet::timesheet_repo::TimesheetRepository,
+            timesheet_amendment::{
+                models::{AmendmentDayData, AmendmentDecisionData, NewTimesheetAmendment},
+                timesheet_amendment_repo::TimesheetAmendmentRepository,
+            },
         },
     };
     use sqlx::PgPool;
 
-    use crate::test_constants::DELTA;
+    use crate::test_constants::{COMPANY1_ID, TIMESHEET0_ID, TIMESHEET1_ID, USER0_ID, USER1_ID};
+
+    fn proposal(timesheet_id: uuid::Uuid, total_hours: f32) -> NewTimesheetAmendment {
+        NewTimesheetAmendment {
+            timesheet_id,
+            proposed_by: USER1_ID,
+            reason: "Forgot the teardown.".to_string(),
+            days: vec![AmendmentDayData {
+                date: NaiveDate::from_ymd_opt(1969, 8, 15).unwrap(),
+                total_hours,
+                comment: Some("Teardown".to_string()),
+            }],
+        }
+    }
 
     #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
-    async fn read_one(pool: PgPool) -> DbResult<()> {
+    async fn propose_and_decide(pool: PgPool) {
         let arc_pool = Arc::new(pool);
+        let amendment_repo = TimesheetAmendmentRepository::new(arc_pool.clone());
+        let timesheet_repo = TimesheetRepository::new(arc_pool);
 
-        let mut wage_preset_repo = WagePresetRepository::new(arc_pool);
+        // Only accepted sheets can be amended.
+        amendment_repo
+            .create(proposal(TIMESHEET0_ID, 6.5))
+            .await
+            .expect_err("Should fail.");
 
-        {
-            let name = "cz_2024-01-01".to_string();
+        let amendment = amendment_repo
+            .create(proposal(TIMESHEET1_ID, 6.5))
+            .await
+            .expect("Should succeed.");
+        assert_eq!(amendment.amendment.status, AmendmentStatus::Pending);
+        assert_eq!(amendment.amendment.proposed_by_name, "Tana Smith");
+        assert_eq!(amendment.days.len(), 1);
+        assert_eq!(amendment.days[0].original_hours, 4.5);
+        assert_eq!(amendment.days[0].total_hours, 6.5);
+
+        // A sheet has at most one pending amendment.
+        amendment_repo
+            .create(proposal(TIMESHEET1_ID, 7.0))
+            .await
+            .expect_err("Should fail.");
+
+        let approved = amendment_repo
+            .decide(
+                amendment.amendment.id,
+                AmendmentDecisionData {
+                    decided_by: USER0_ID,
+                    approve: true,
+                    note: None,
+                },
+            )
+            .await
+            .expect("Should succeed.");
+        assert_eq!(approved.amendment.status, AmendmentStatus::Approved);
+        assert_eq!(
+            approved.amendment.decided_by_name,
+            Some("Dave Null".to_string())
+        );
+        let today = Local::now().date_naive();
+        let next_month = NaiveDate::from_ymd_opt(today.year(), today.month(), 1)
+            .unwrap()
+            .checked_add_months(chrono::Months::new(1))
+            .unwrap();
+        assert_eq!(approved.amendment.settlement_month, Some(next_month));
+
+        // A decided amendment can't be decided again.
+        amendment_repo
+            .decide(
+                amendment.amendment.id,
+                AmendmentDecisionData {
+                    decided_by: USER0_ID,
+                    approve: false,
+                    note: Some("Too late.".to_string()),
+                },
+            )
+            .await
+            .expect_err("Should fail.");
 
-            let preset = wage_preset_repo
-                ._read_one(&name)
+        // The accepted sheet stays intact.
+        let workday = timesheet_repo
+            .read_one_workday(TIMESHEET1_ID, NaiveDate::from_ymd_opt(1969, 8, 15).unwrap())
+            .await
+            .expect("Should succeed.");
+        assert_eq!(workday.total_hours, 4.5);
+
+        // The next amendment starts from the approved values.
+        let second = amendment_repo
+            .create(proposal(TIMESHEET1_ID, 5.0))
+            .await
+            .expect("Should succeed.");
+        assert_eq!(second.days[0].original_hours, 6.5);
+        assert_eq!(
+            second.days[0].original_comment,
+            Some("Teardown".to_string())
+        );
+
+        let amendments = amendment_repo
+            .read_all_for_timesheet(TIMESHEET1_ID)
+            .await
+            .expect("Should succeed.");
+        assert_eq!(amendments.len(), 2);
+
+        let settled = amendment_repo
+            .read_settled_in(COMPANY1_ID, next_month)
+            .await
+            .expect("Should succeed.");
+        assert_eq!(settled.len(), 1);
+        assert_eq!(settled[0].amendment_id, amendment.amendment.id);
+        assert_eq!(settled[0].user_id, USER1_ID);
+    }
+}
+
+#[cfg(test)]
+mod job_repo_tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use organization::{
+        models::JobRunStatus,
+        repositories::{job::job_repo::JobRepository, repository::DbRepository},
+        utils::jobs::job_scheduler::{Job, JobScheduler},
+    };
+    use sqlx::PgPool;
+
+    const HOUR: Duration = Duration::from_secs(60 * 60);
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn claim_and_finish(pool: PgPool) {
+        let job_repo = JobRepository::new(Arc::new(pool));
+        let names = vec!["cleanup".to_string()];
+
+        job_repo
+            .register("cleanup", HOUR)
+            .await
+            .expect("Should succeed.");
+        // Registering again keeps the schedule.
+        job_repo
+            .register("cleanup", HOUR)
+            .await
+            .expect("Should succeed.");
+
+        let run = job_repo
+            .claim_due(&names, HOUR)
+            .await
+            .expect("Should succeed.")
+            .expect("Should be due.");
+        assert_eq!(run.attempt, 1);
+        assert_eq!(run.status, JobRunStatus::Running);
+
+        // The job is leased, no other instance can claim it.
+        let other = job_repo
+            .claim_due(&names, HOUR)
+            .await
+            .expect("Should succeed.");
+        assert!(other.is_none());
+        job_repo
+            .schedule_now("cleanup")
+            .await
+            .expect_err("Should fail.");
+
+        let failed = job_repo
+            .fail(run.id, "Database is down.", Duration::ZERO)
+            .await
+            .expect("Should succeed.");
+        assert_eq!(failed.status, JobRunStatus::Failed);
+        assert_eq!(failed.output, Some("Database is down.".to_string()));
+
+        // The retry counts the attempts.
+        let retry = job_repo
+            .claim_due(&names, Duration::ZERO)
+            .await
+            .expect("Should succeed.")
+            .expect("Should be due.");
+        assert_eq!(retry.attempt, 2);
+
+        // The lease of the retry expired, so the run is taken over.
+        let takeover = job_repo
+            .claim_due(&names, HOUR)
+            .await
+            .expect("Should succeed.")
+            .expect("Should be due.");
+        assert_eq!(takeover.attempt, 2);
+        job_repo
+            .succeed(retry.id, "Too late.")
+            .await
+            .expect_err("Should fail.");
+
+        let succeeded = job_repo
+            .succeed(takeover.id, "Done.")
+            .await
+            .expect("Should succeed.");
+        assert_eq!(succeeded.status, JobRunStatus::Succeeded);
+
+        let jobs = job_repo.read_all().await.expect("Should succeed.");
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].failed_attempts, 0);
+        assert_eq!(jobs[0].last_status, Some(JobRunStatus::Succeeded));
+        assert!(jobs[0].next_run_at > succeeded.started_at);
+        assert!(job_repo
+            .claim_due(&names, HOUR)
+            .await
+            .expect("Should succeed.")
+            .is_none());
+
+        let runs = job_repo.read_runs(10).await.expect("Should succeed.");
+        assert_eq!(runs.len(), 3);
+        assert_eq!(runs[0].id, takeover.id);
+        assert_eq!(runs[1].status, JobRunStatus::Failed);
+        assert_eq!(runs[1].output, Some("The run was interrupted.".to_string()));
+
+        job_repo
+            .schedule_now("cleanup")
+            .await
+            .expect("Should succeed.");
+        assert_eq!(
+            job_repo
+                .delete_runs_older_than(0)
                 .await
-                .expect("Should succeed");
-            assert_eq!(preset.currency, "CZK");
+                .expect("Should succeed."),
+            3
+        );
+    }
+
+    /// Fails on the first run.
+    struct FlakyJob {
+        runs: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Job for FlakyJob {
+        fn name(&self) -> &'static str {
+            "flaky"
         }
 
-        wage_preset_repo.disconnect().await;
+        fn period(&self) -> Duration {
+            HOUR
+        }
 
-        Ok(())
+        async fn run(&self) -> Result<String, String> {
+            match self.runs.fetch_add(1, Ordering::SeqCst) {
+                0 => Err("Not yet.".to_string()),
+                _ => Ok("Done.".to_string()),
+            }
+        }
     }
 
     #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
-    async fn read_all(pool: PgPool) -> DbResult<()> {
+    async fn scheduler_retries(pool: PgPool) {
+        let job_repo = JobRepository::new(Arc::new(pool));
+        let runs = Arc::new(AtomicUsize::new(0));
+        let scheduler =
+            JobScheduler::new(job_repo.clone()).with_job(FlakyJob { runs: runs.clone() });
+
+        scheduler.register().await.expect("Should succeed.");
+        assert_eq!(scheduler.run_due().await.expect("Should succeed."), 1);
+        // The retry waits for the backoff.
+        assert_eq!(scheduler.run_due().await.expect("Should succeed."), 0);
+
+        let jobs = job_repo.read_all().await.expect("Should succeed.");
+        assert_eq!(jobs[0].failed_attempts, 1);
+        assert_eq!(jobs[0].last_status, Some(JobRunStatus::Failed));
+
+        job_repo
+            .schedule_now("flaky")
+            .await
+            .expect("Should succeed.");
+        assert_eq!(scheduler.run_due().await.expect("Should succeed."), 1);
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+        let job_runs = job_repo.read_runs(10).await.expect("Should succeed.");
+        assert_eq!(job_runs[0].attempt, 2);
+        assert_eq!(job_runs[0].status, JobRunStatus::Succeeded);
+        assert_eq!(job_runs[1].output, Some("Not yet.".to_string()));
+    }
+}
+
+#[cfg(test)]
+mod timesheet_consistency_repo_tests {
+    use std::sync::Arc;
+
+    use chrono::NaiveDate;
+    use organization::repositories::{
+        repository::DbRepository, timesheet::timesheet_repo::TimesheetRepository,
+        timesheet_consistency::timesheet_consistency_repo::TimesheetConsistencyRepository,
+    };
+    use sqlx::PgPool;
+
+    use crate::test_constants::{TIMESHEET0_ID, TIMESHEET2_ID, TIMESHEET3_ID, USER0_ID};
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn check_and_repair(pool: PgPool) -> sqlx::Result<()> {
         let arc_pool = Arc::new(pool);
+        let consistency_repo = TimesheetConsistencyRepository::new(arc_pool.clone());
+        let timesheet_repo = TimesheetRepository::new(arc_pool.clone());
+
+        let report = consistency_repo.check().await?;
+        assert!(!report.is_consistent());
+        // The workdays of the fixture add up to more than its total.
+        assert_eq!(report.total_mismatches.len(), 1);
+        assert_eq!(report.total_mismatches[0].timesheet_id, TIMESHEET3_ID);
+        assert_eq!(report.total_mismatches[0].total_hours, 68.0);
+        assert_eq!(report.total_mismatches[0].workday_hours, 75.0);
+        assert!(report.workdays_out_of_range.is_empty());
+        // Both sheets cover only a part of their event.
+        assert_eq!(report.range_mismatches.len(), 2);
+        assert_eq!(report.range_mismatches[0].timesheet_id, TIMESHEET2_ID);
+        assert!(!report.range_mismatches[0].is_editable);
+        assert_eq!(report.range_mismatches[1].timesheet_id, TIMESHEET0_ID);
+        assert!(report.range_mismatches[1].is_editable);
+
+        sqlx::query!(
+            r#"INSERT INTO workday (timesheet_id, date, total_hours, comment)
+               VALUES ($1, '1969-08-20', 3, 'Cleaning up'),
+                      ($1, '1969-08-21', 0, NULL);"#,
+            TIMESHEET0_ID,
+        )
+        .execute(arc_pool.as_ref())
+        .await?;
+
+        let report = consistency_repo.check().await?;
+        assert_eq!(report.total_mismatches.len(), 2);
+        assert_eq!(report.workdays_out_of_range.len(), 1);
+        assert_eq!(report.workdays_out_of_range[0].timesheet_id, TIMESHEET0_ID);
+        assert_eq!(report.workdays_out_of_range[0].empty_days, 1);
+        assert_eq!(report.workdays_out_of_range[0].logged_days, 1);
+
+        assert_eq!(consistency_repo.repair_all_totals().await?, 2);
+        assert_eq!(consistency_repo.repair_all_totals().await?, 0);
+        let sheet = timesheet_repo._read_one(TIMESHEET3_ID).await?;
+        assert_eq!(sheet.timesheet.total_hours, 75.0);
+
+        // The empty day is dropped, the logged one is left to the manager.
+        consistency_repo
+            .repair_workdays(TIMESHEET0_ID, USER0_ID)
+            .await?;
+        let report = consistency_repo.check().await?;
+        assert!(report.total_mismatches.is_empty());
+        assert!(report.workdays_out_of_range.is_empty());
+        let review = timesheet_repo.read_review(TIMESHEET0_ID).await?;
+        assert_eq!(review.flags.len(), 1);
+        assert_eq!(
+            review.flags[0].date,
+            NaiveDate::from_ymd_opt(1969, 8, 20).unwrap()
+        );
 
-        let mut wage_preset_repo = WagePresetRepository::new(arc_pool);
+        // The submitted sheet is under review, it can't be moved.
+        consistency_repo
+            .repair_range(TIMESHEET2_ID, USER0_ID)
+            .await
+            .expect_err("Should fail.");
+        consistency_repo
+            .repair_range(TIMESHEET0_ID, USER0_ID)
+            .await?;
+        let sheet = timesheet_repo._read_one(TIMESHEET0_ID).await?;
+        assert_eq!(
+            sheet.timesheet.end_date,
+            NaiveDate::from_ymd_opt(1969, 8, 18).unwrap()
+        );
+        assert_eq!(sheet.workdays.len(), 5);
+        assert_eq!(sheet.timesheet.total_hours, 25.0);
 
-        let presets = wage_preset_repo._read_all().await.expect("Should succeed");
-        assert_eq!(presets.len(), 3);
+        let report = consistency_repo.check().await?;
+        assert!(report.total_mismatches.is_empty());
+        assert!(report.workdays_out_of_range.is_empty());
+        assert_eq!(report.range_mismatches.len(), 1);
 
-        wage_preset_repo.disconnect().await;
+        consistency_repo
+            .repair_total(uuid::Uuid::nil())
+            .await
+            .expect_err("Should fail.");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod trash_repo_tests {
+    use std::sync::Arc;
+
+    use organization::{
+        models::DeletedEntity,
+        repositories::{
+            company::company_repo::CompanyRepository,
+            employment::employment_repo::EmploymentRepository,
+            event::event_repo::EventRepository,
+            repository::DbRepository,
+            task::task_repo::TaskRepository,
+            trash::{models::PurgeResult, trash_repo::TrashRepository},
+            user::user_repo::UserRepository,
+        },
+    };
+    use sqlx::PgPool;
+    use uuid::uuid;
+
+    use crate::test_constants::{
+        COMPANY1_ID, EVENT0_ID, EVENT1_ID, TASK0_ID, TIMESHEET0_ID, USER0_ID, USER1_ID,
+    };
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn delete_and_restore(pool: PgPool) -> sqlx::Result<()> {
+        let arc_pool = Arc::new(pool);
+        let company_repo = CompanyRepository::new(arc_pool.clone());
+        let trash_repo = TrashRepository::new(arc_pool.clone());
+        let event3_id = uuid!("2321154f-8c57-4ee2-9493-c9243ae7426a");
+
+        company_repo.delete(COMPANY1_ID, Some(USER0_ID)).await?;
+
+        let deletions = trash_repo.read_all().await?;
+        assert_eq!(deletions.len(), 1);
+        assert_eq!(deletions[0].entity, DeletedEntity::Company);
+        assert_eq!(deletions[0].record_id, COMPANY1_ID);
+        assert_eq!(deletions[0].label, "ReportLab");
+        assert_eq!(deletions[0].deleted_by_name, Some("Dave Null".to_string()));
+
+        // The event organized only by the company goes along, the shared one stays.
+        let deleted_events = sqlx::query_scalar!(
+            r#"SELECT id FROM event WHERE deletion_id = $1;"#,
+            deletions[0].id,
+        )
+        .fetch_all(arc_pool.as_ref())
+        .await?;
+        assert_eq!(deleted_events, vec![event3_id]);
+        company_repo
+            ._read_one(COMPANY1_ID)
+            .await
+            .expect_err("Should be deleted.");
+        let sheet_deleted_at = sqlx::query_scalar!(
+            r#"SELECT deleted_at FROM timesheet WHERE id = $1;"#,
+            TIMESHEET0_ID,
+        )
+        .fetch_one(arc_pool.as_ref())
+        .await?;
+        assert!(sheet_deleted_at.is_some());
+        let staff_left = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!"
+               FROM event_staff
+               WHERE event_id = $1
+                 AND deleted_at IS NULL;"#,
+            EVENT1_ID,
+        )
+        .fetch_one(arc_pool.as_ref())
+        .await?;
+        assert_eq!(staff_left, 1);
+
+        // The employment went with the company, it can't be restored on its own.
+        let employment_repo = EmploymentRepository::new(arc_pool.clone());
+        employment_repo
+            .delete(USER1_ID, COMPANY1_ID, None)
+            .await
+            .expect_err("Should be deleted already.");
+
+        assert!(trash_repo.can_restore(&deletions[0]).await?);
+        trash_repo.restore(deletions[0].id).await?;
+        assert!(trash_repo.read_all().await?.is_empty());
+        company_repo._read_one(COMPANY1_ID).await?;
+        let restored = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!"
+               FROM timesheet
+               WHERE company_id = $1
+                 AND deleted_at IS NULL;"#,
+            COMPANY1_ID,
+        )
+        .fetch_one(arc_pool.as_ref())
+        .await?;
+        assert_eq!(restored, 3);
+        trash_repo
+            .restore(deletions[0].id)
+            .await
+            .expect_err("Should be restored already.");
 
         Ok(())
     }
 
     #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
-    async fn read_optional_matching_date(pool: PgPool) -> DbResult<()> {
+    async fn restore_keeps_earlier_deletions(pool: PgPool) -> sqlx::Result<()> {
         let arc_pool = Arc::new(pool);
+        let event_repo = EventRepository::new(arc_pool.clone());
+        let task_repo = TaskRepository::new(arc_pool.clone());
+        let trash_repo = TrashRepository::new(arc_pool.clone());
 
-        let mut wage_preset_repo = WagePresetRepository::new(arc_pool);
+        task_repo.delete(TASK0_ID, None).await?;
+        event_repo.delete(EVENT0_ID, Some(USER0_ID)).await?;
 
-        // non-existent
-        {
-            let preset_optional = wage_preset_repo
-                ._read_optional_matching_date(&NaiveDate::from_ymd_opt(1965, 12, 31).unwrap())
-                .await
-                .expect("Should succeed");
-            assert!(preset_optional.is_none());
-        }
+        let deletions = trash_repo.read_all().await?;
+        assert_eq!(deletions.len(), 2);
+        let (event_deletion, task_deletion) = (&deletions[0], &deletions[1]);
+        assert_eq!(event_deletion.entity, DeletedEntity::Event);
+        assert_eq!(task_deletion.entity, DeletedEntity::Task);
+        assert_eq!(task_deletion.deleted_by_name, None);
 
-        {
-            let preset_optional = wage_preset_repo
-                ._read_optional_matching_date(&NaiveDate::from_ymd_opt(2023, 06, 01).unwrap())
-                .await
-                .expect("Should succeed");
-            assert!(preset_optional.is_some());
-            assert!(preset_optional.unwrap().min_hourly_wage - 100.0 < DELTA);
-        }
+        // The task of a deleted event can't come back before the event does.
+        assert!(!trash_repo.can_restore(task_deletion).await?);
 
-        {
-            let preset_optional = wage_preset_repo
-                ._read_optional_matching_date(&NaiveDate::from_ymd_opt(2024, 01, 01).unwrap())
-                .await
-                .expect("Should succeed");
-            assert!(preset_optional.is_some());
-            assert!(preset_optional.unwrap().min_hourly_wage - 118.3 < DELTA);
-        }
+        trash_repo.restore(event_deletion.id).await?;
+        event_repo.read_one(EVENT0_ID).await?;
+        assert!(task_repo.read_one(TASK0_ID).await?.deleted_at.is_some());
 
-        {
-            let preset_optional = wage_preset_repo
-                ._read_optional_matching_date(&NaiveDate::from_ymd_opt(2030, 06, 10).unwrap())
-                .await
-                .expect("Should succeed");
-            assert!(preset_optional.is_some());
-            assert!(preset_optional.unwrap().min_hourly_wage - 118.3 < DELTA);
-        }
+        assert!(trash_repo.can_restore(task_deletion).await?);
+        trash_repo.restore(task_deletion.id).await?;
+        assert!(task_repo.read_one(TASK0_ID).await?.deleted_at.is_none());
 
-        wage_preset_repo.disconnect().await;
+        Ok(())
+    }
+
+    #[sqlx::test(fixtures("all_inclusive"), migrations = "migrations/no_seed")]
+    async fn purge(pool: PgPool) -> sqlx::Result<()> {
+        let arc_pool = Arc::new(pool);
+        let event_repo = EventRepository::new(arc_pool.clone());
+        let user_repo = UserRepository::new(arc_pool.clone());
+        let trash_repo = TrashRepository::new(arc_pool.clone());
+        let event2_id = uuid!("3f152dad-0bbd-4e9a-aec5-2a567d6370cc");
+
+        event_repo.delete(event2_id, Some(USER0_ID)).await?;
+        // The task created by the admin's staff stays, so the admin can't be purged.
+        user_repo.delete_user(USER0_ID, Some(USER0_ID)).await?;
+
+        assert_eq!(
+            trash_repo.purge_older_than(30).await?,
+            PurgeResult { purged: 0, kept: 0 }
+        );
+
+        sqlx::query!(r#"UPDATE deletion SET deleted_at = NOW() - INTERVAL '40 days';"#)
+            .execute(arc_pool.as_ref())
+            .await?;
+
+        let result = trash_repo.purge_older_than(30).await?;
+        assert_eq!(result.purged, 1);
+        assert_eq!(result.kept, 1);
+
+        let events_left = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!" FROM event WHERE id = $1;"#,
+            event2_id,
+        )
+        .fetch_one(arc_pool.as_ref())
+        .await?;
+        assert_eq!(events_left, 0);
+
+        let deletions = trash_repo.read_all().await?;
+        assert_eq!(deletions.len(), 1);
+        assert_eq!(deletions[0].entity, DeletedEntity::User);
+        assert_eq!(deletions[0].record_id, USER0_ID);
 
         Ok(())
     }
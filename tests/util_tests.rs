@@ -160,3 +160,861 @@ mod calculate_wage_tests {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod critical_path_tests {
+    use organization::utils::task_graph::critical_path::{find_critical_path, TaskNode};
+    use uuid::{uuid, Uuid};
+
+    const TASK_A: Uuid = uuid!("00000000-0000-0000-0000-00000000000a");
+    const TASK_B: Uuid = uuid!("00000000-0000-0000-0000-00000000000b");
+    const TASK_C: Uuid = uuid!("00000000-0000-0000-0000-00000000000c");
+    const TASK_D: Uuid = uuid!("00000000-0000-0000-0000-00000000000d");
+
+    fn node(id: Uuid, estimated_hours: f32) -> TaskNode {
+        TaskNode {
+            id,
+            estimated_hours,
+        }
+    }
+
+    #[test]
+    fn picks_longest_chain() {
+        // A -> B -> C takes 6 hours, A -> D takes 7.
+        let tasks = vec![
+            node(TASK_A, 1.0),
+            node(TASK_B, 2.0),
+            node(TASK_C, 3.0),
+            node(TASK_D, 6.0),
+        ];
+        let dependencies = vec![(TASK_A, TASK_B), (TASK_B, TASK_C), (TASK_A, TASK_D)];
+
+        assert_eq!(
+            find_critical_path(&tasks, &dependencies),
+            vec![TASK_A, TASK_D]
+        );
+    }
+
+    #[test]
+    fn counts_tasks_without_estimates() {
+        let tasks = vec![
+            node(TASK_A, 0.0),
+            node(TASK_B, 0.0),
+            node(TASK_C, 0.0),
+            node(TASK_D, 0.0),
+        ];
+        let dependencies = vec![(TASK_A, TASK_B), (TASK_B, TASK_C)];
+
+        assert_eq!(
+            find_critical_path(&tasks, &dependencies),
+            vec![TASK_A, TASK_B, TASK_C]
+        );
+    }
+
+    #[test]
+    fn ignores_unknown_tasks_and_cycles() {
+        let tasks = vec![node(TASK_A, 1.0), node(TASK_B, 1.0), node(TASK_C, 5.0)];
+        // B and C wait for each other, D is not part of the graph.
+        let dependencies = vec![(TASK_B, TASK_C), (TASK_C, TASK_B), (TASK_D, TASK_A)];
+
+        assert_eq!(find_critical_path(&tasks, &dependencies), vec![TASK_A]);
+    }
+
+    #[test]
+    fn empty_graph() {
+        assert!(find_critical_path(&[], &[]).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod comment_format_tests {
+    use organization::utils::comment_format::{
+        markdown::render_markdown,
+        mentions::{find_mentions, MentionCandidate},
+    };
+    use uuid::{uuid, Uuid};
+
+    const STAFF_A: Uuid = uuid!("00000000-0000-0000-0000-00000000000a");
+    const STAFF_B: Uuid = uuid!("00000000-0000-0000-0000-00000000000b");
+
+    fn candidates() -> Vec<MentionCandidate> {
+        vec![
+            MentionCandidate {
+                staff_id: STAFF_A,
+                name: "Joe".to_string(),
+            },
+            MentionCandidate {
+                staff_id: STAFF_B,
+                name: "Joe Cocker".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn prefers_longest_name() {
+        assert_eq!(
+            find_mentions("@joe cocker needs guitars, ask @Joe.", &candidates()),
+            vec![STAFF_B, STAFF_A]
+        );
+    }
+
+    #[test]
+    fn ignores_emails_and_partial_names() {
+        assert!(find_mentions("Write to stage@joe.com or @Joey.", &candidates()).is_empty());
+    }
+
+    #[test]
+    fn renders_markdown_without_raw_html() {
+        let html = render_markdown("**Bring** [cables](https://example.com)<script>x()</script>");
+
+        assert!(html.contains("<strong>Bring</strong>"));
+        assert!(html.contains("rel=\"noopener noreferrer nofollow\""));
+        assert!(!html.contains("<script>"));
+    }
+}
+
+#[cfg(test)]
+mod mail_template_tests {
+    use chrono::NaiveDate;
+    use organization::templates::mail::{DigestItem, MailContent};
+
+    #[test]
+    fn renders_html_and_plain_text() {
+        let mail = MailContent::TimesheetDecision {
+            event_name: "Woodstock".to_string(),
+            approved: false,
+            manager_note: Some("Hours on <b>Friday</b> are missing.".to_string()),
+        }
+        .render("Tana Smith")
+        .expect("Render should succeed");
+
+        assert_eq!(mail.subject, "Your timesheet for Woodstock was rejected");
+        assert!(mail.body_text.contains("Tana Smith"));
+        assert!(mail
+            .body_text
+            .contains("Hours on <b>Friday</b> are missing."));
+        assert!(mail.body_html.contains("&lt;b&gt;Friday&lt;/b&gt;"));
+        assert!(!mail.summary.contains("Tana Smith"));
+    }
+
+    #[test]
+    fn digest_lists_every_item() {
+        let items: Vec<DigestItem> = [
+            MailContent::StaffDecision {
+                event_name: "Woodstock".to_string(),
+                accepted: true,
+            },
+            MailContent::SubmissionDeadline {
+                event_name: "PyCon".to_string(),
+                deadline: NaiveDate::from_ymd_opt(2024, 5, 31).expect("Should be valid"),
+            },
+            MailContent::EventStatusChange {
+                event_name: "Woodstock".to_string(),
+                cancellation_reason: None,
+            },
+        ]
+        .iter()
+        .map(|content| DigestItem {
+            subject: content.subject(),
+            body_text: content
+                .render("Tana Smith")
+                .expect("Render should succeed")
+                .summary,
+        })
+        .collect();
+
+        let digest = MailContent::Digest {
+            items: items.clone(),
+        }
+        .render("Tana Smith")
+        .expect("Render should succeed");
+
+        assert!(digest.subject.contains("3 updates"));
+        assert!(digest
+            .body_text
+            .contains("Woodstock has been completed. Please submit your final timesheets."));
+        for item in items {
+            assert!(digest.body_text.contains(&item.subject));
+            assert!(digest.body_html.contains(&item.subject));
+        }
+    }
+}
+
+#[cfg(test)]
+mod ical_tests {
+    use chrono::{NaiveDate, NaiveDateTime};
+    use organization::utils::ical::{
+        calendar::{escape_text, fold_line, render_calendar},
+        models::{CalendarEntry, CalendarEntryStatus, CalendarTime},
+    };
+
+    fn timestamp(value: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").expect("Should be valid")
+    }
+
+    #[test]
+    fn escapes_text() {
+        assert_eq!(
+            escape_text("Stage; lights, sound\\n\nDone"),
+            "Stage\\; lights\\, sound\\\\n\\nDone"
+        );
+    }
+
+    #[test]
+    fn folds_long_lines_without_splitting_characters() {
+        let line = format!("SUMMARY:{}", "ž".repeat(60));
+        let folded = fold_line(&line);
+
+        assert!(folded.ends_with("\r\n"));
+        for part in folded.trim_end_matches("\r\n").split("\r\n") {
+            assert!(part.len() <= 75);
+        }
+        assert_eq!(folded.replace("\r\n ", "").trim_end(), line);
+    }
+
+    #[test]
+    fn renders_events() {
+        let entry = CalendarEntry {
+            uid: "event-1@orchestrate".to_string(),
+            summary: "Woodstock".to_string(),
+            description: Some("Cancelled: Rain, mud".to_string()),
+            start: CalendarTime::Date(NaiveDate::from_ymd_opt(1969, 8, 15).expect("Valid")),
+            end: CalendarTime::Date(NaiveDate::from_ymd_opt(1969, 8, 19).expect("Valid")),
+            status: CalendarEntryStatus::Cancelled,
+            created_at: timestamp("1969-01-01 10:00:00"),
+            last_modified: timestamp("1969-01-01 10:01:40"),
+        };
+        let task = CalendarEntry {
+            uid: "task-1@orchestrate".to_string(),
+            summary: "Build the stage".to_string(),
+            description: None,
+            start: CalendarTime::DateTime(timestamp("1969-08-14 09:00:00")),
+            end: CalendarTime::DateTime(timestamp("1969-08-14 10:00:00")),
+            status: CalendarEntryStatus::Confirmed,
+            created_at: timestamp("1969-01-01 10:00:00"),
+            last_modified: timestamp("1969-01-01 10:00:00"),
+        };
+
+        let calendar = render_calendar("Orchestrate", &[entry, task]);
+
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 2);
+        assert!(calendar.contains("UID:event-1@orchestrate\r\n"));
+        assert!(calendar.contains("DTSTART;VALUE=DATE:19690815\r\n"));
+        assert!(calendar.contains("DTEND;VALUE=DATE:19690819\r\n"));
+        assert!(calendar.contains("SEQUENCE:100\r\n"));
+        assert!(calendar.contains("DESCRIPTION:Cancelled: Rain\\, mud\r\n"));
+        assert!(calendar.contains("STATUS:CANCELLED\r\n"));
+        assert!(calendar.contains("DTSTART:19690814T090000\r\n"));
+        assert!(calendar.contains("DTSTAMP:19690101T100140Z\r\n"));
+        assert!(calendar.contains("SEQUENCE:0\r\n"));
+    }
+}
+
+#[cfg(test)]
+mod import_tests {
+    use chrono::NaiveDate;
+    use organization::{
+        models::{EmployeeLevel, EmploymentContract, Gender},
+        utils::import::{
+            models::{ImportColumn, ImportError, ImportFormat},
+            parser::{parse_record, read_records, OPEN_END_DATE},
+        },
+    };
+
+    #[test]
+    fn reads_semicolon_separated_files() {
+        let csv = "\u{feff}Full name;E-mail;Date of birth;Gender;Contract;Wage;Start;Manager\r\n\
+            Jan Novák;jan@novak.cz;1.2.1990;male;DPC;250,5;2024-06-01;\r\n\
+            ;;;;;;;\r\n\
+            Eva Nová;eva@nova.cz;1992-03-01;Female;hpp;180;2024-06-01;jan@novak.cz\r\n";
+
+        let records = read_records(ImportFormat::Csv, csv.as_bytes()).expect("Should be valid");
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].line, 2);
+        assert_eq!(records[1].line, 4);
+        assert_eq!(records[1].manager_email, "jan@novak.cz");
+
+        let row = parse_record(&records[0]).expect("Should be valid");
+        assert_eq!(row.name, "Jan Novák");
+        assert_eq!(row.birth, NaiveDate::from_ymd_opt(1990, 2, 1).unwrap());
+        assert_eq!(row.gender, Gender::Male);
+        assert_eq!(row.employment_type, EmploymentContract::Dpc);
+        assert_eq!(row.hourly_wage, 250.5);
+        assert_eq!(row.end_date, OPEN_END_DATE);
+        assert_eq!(row.level, EmployeeLevel::Basic);
+        assert!(row.manager_email.is_none());
+    }
+
+    #[test]
+    fn reports_missing_columns() {
+        let csv = "Name,Email,Gender\nJan,jan@novak.cz,male\n";
+
+        match read_records(ImportFormat::Csv, csv.as_bytes()) {
+            Err(ImportError::MissingColumns(columns)) => assert_eq!(
+                columns,
+                vec![
+                    ImportColumn::Birth,
+                    ImportColumn::EmploymentType,
+                    ImportColumn::HourlyWage,
+                    ImportColumn::StartDate,
+                ]
+            ),
+            _ => panic!("Should be missing columns"),
+        }
+
+        assert!(matches!(
+            read_records(ImportFormat::Csv, b"Name,Email\n"),
+            Err(ImportError::MissingColumns(_))
+        ));
+        assert_eq!(
+            ImportFormat::from_file_name("crew.XLSX"),
+            Some(ImportFormat::Xlsx)
+        );
+        assert_eq!(ImportFormat::from_file_name("crew.ods"), None);
+    }
+
+    #[test]
+    fn reports_every_invalid_value() {
+        let csv = "name,email,birth,gender,contract type,hourly wage,start date,end date,level\n\
+            Jan,jan@novak.cz,yesterday,robot,full-time,lots,2024-06-01,2024-13-01,boss\n";
+
+        let records = read_records(ImportFormat::Csv, csv.as_bytes()).expect("Should be valid");
+        let errors = parse_record(&records[0]).expect_err("Should be invalid");
+
+        assert_eq!(errors.len(), 6);
+        assert!(errors[0].starts_with("Birth Date 'yesterday'"));
+    }
+}
+
+#[cfg(test)]
+mod export_tests {
+    use std::io::Cursor;
+
+    use calamine::{Data, DataType, Reader, Xlsx};
+    use chrono::NaiveDate;
+    use organization::utils::export::{
+        models::{ExportCell, ExportFormat},
+        table_writer::{column_name, table_writer},
+    };
+
+    fn write_table(format: ExportFormat, rows: Vec<Vec<ExportCell>>) -> Vec<u8> {
+        let mut writer = table_writer(format, "Staff & Tasks");
+        let mut output = vec![];
+        for row in rows {
+            writer.write_row(&row, &mut output);
+        }
+        writer.finish(&mut output);
+
+        output
+    }
+
+    fn rows() -> Vec<Vec<ExportCell>> {
+        vec![
+            vec!["Name".into(), "Born".into(), "Wage".into(), "Note".into()],
+            vec![
+                "Jan \"Honza\" Novák".into(),
+                NaiveDate::from_ymd_opt(1990, 2, 1).unwrap().into(),
+                250.5.into(),
+                "a, b <c>".into(),
+            ],
+            vec![
+                "Eva".into(),
+                ExportCell::Empty,
+                180.0.into(),
+                None::<String>.into(),
+            ],
+        ]
+    }
+
+    #[test]
+    fn names_columns() {
+        assert_eq!(column_name(0), "A");
+        assert_eq!(column_name(25), "Z");
+        assert_eq!(column_name(26), "AA");
+        assert_eq!(column_name(701), "ZZ");
+        assert_eq!(column_name(702), "AAA");
+    }
+
+    #[test]
+    fn writes_csv() {
+        let csv = String::from_utf8(write_table(ExportFormat::Csv, rows())).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(
+            lines[0].trim_start_matches('\u{feff}'),
+            "Name,Born,Wage,Note"
+        );
+        assert_eq!(
+            lines[1],
+            "\"Jan \"\"Honza\"\" Novák\",1990-02-01,250.5,\"a, b <c>\""
+        );
+        assert_eq!(lines[2], "Eva,,180,");
+    }
+
+    #[test]
+    fn writes_xlsx_readable_by_spreadsheets() {
+        let bytes = write_table(ExportFormat::Xlsx, rows());
+        let mut workbook = Xlsx::new(Cursor::new(bytes)).expect("Should be a valid workbook");
+
+        assert_eq!(workbook.sheet_names(), vec!["Staff & Tasks".to_string()]);
+        let range = workbook
+            .worksheet_range_at(0)
+            .expect("Should have a sheet")
+            .expect("Should be valid");
+
+        assert_eq!(range.get_size(), (3, 4));
+        assert_eq!(
+            range.get((1, 0)).and_then(|cell| cell.get_string()),
+            Some("Jan \"Honza\" Novák")
+        );
+        assert_eq!(
+            range.get((1, 1)).and_then(|cell| cell.as_date()),
+            NaiveDate::from_ymd_opt(1990, 2, 1)
+        );
+        assert_eq!(
+            range.get((1, 2)).and_then(|cell| cell.get_float()),
+            Some(250.5)
+        );
+        assert_eq!(
+            range.get((1, 3)).and_then(|cell| cell.get_string()),
+            Some("a, b <c>")
+        );
+        assert_eq!(range.get((2, 1)), Some(&Data::Empty));
+    }
+}
+
+#[cfg(test)]
+mod payroll_tests {
+    use std::{path::Path, process::Command};
+
+    use chrono::NaiveDate;
+    use organization::{
+        models::EmploymentContract,
+        utils::{
+            payroll::{
+                accepted_wages::EmployeeWages,
+                exporter::payroll_exporter,
+                models::{
+                    IncomeConfirmation, Payroll, PayrollEntry, PayrollFormat, WithheldIncome,
+                },
+            },
+            wage_calc::models::DetailedWage,
+        },
+    };
+    use uuid::Uuid;
+
+    fn payroll() -> Payroll {
+        Payroll {
+            company_name: "Hobbit Hole & Co.".to_string(),
+            company_crn: "12345678".to_string(),
+            month: NaiveDate::from_ymd_opt(2024, 6, 1).unwrap(),
+            currency: "CZK".to_string(),
+            entries: vec![
+                PayrollEntry {
+                    user_id: Uuid::nil(),
+                    name: "Jan <Honza> Novák".to_string(),
+                    email: "jan@novak.cz".to_string(),
+                    employment_type: EmploymentContract::Dpc,
+                    hourly_wage: 250.0,
+                    wage: DetailedWage {
+                        tax_base: 10000.0,
+                        net_wage: 8900.0,
+                        worked_hours: 40.0,
+                        employee_social_insurance: 650.0,
+                        employee_health_insurance: 450.0,
+                        employer_social_insurance: 2480.0,
+                        employer_health_insurance: 900.0,
+                    },
+                },
+                PayrollEntry {
+                    user_id: Uuid::max(),
+                    name: "Eva Nová".to_string(),
+                    email: "eva@nova.cz".to_string(),
+                    employment_type: EmploymentContract::Dpp,
+                    hourly_wage: 180.0,
+                    wage: DetailedWage {
+                        tax_base: 1800.0,
+                        net_wage: 1800.0,
+                        worked_hours: 10.0,
+                        ..Default::default()
+                    },
+                },
+            ],
+            corrections: vec![PayrollEntry {
+                user_id: Uuid::max(),
+                name: "Eva Nová".to_string(),
+                email: "eva@nova.cz".to_string(),
+                employment_type: EmploymentContract::Dpp,
+                hourly_wage: 180.0,
+                wage: DetailedWage {
+                    tax_base: -360.0,
+                    net_wage: -360.0,
+                    worked_hours: -2.0,
+                    ..Default::default()
+                },
+            }],
+        }
+    }
+
+    /// Validates the document with `xmllint` against the published Pohoda
+    /// schemas (version 2), which `tests/fixtures/pohoda/download.sh` puts
+    /// unmodified into `tests/fixtures/pohoda`. Returns the errors reported by
+    /// `xmllint` for an invalid document.
+    fn validate(xml: &[u8], name: &str) -> Result<(), String> {
+        let schema = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/pohoda/data.xsd");
+        assert!(
+            schema.exists(),
+            "The published Pohoda schemas are missing, run tests/fixtures/pohoda/download.sh."
+        );
+
+        let file = std::env::temp_dir().join(format!("{}-{}.xml", name, std::process::id()));
+        std::fs::write(&file, xml).unwrap();
+        let output = Command::new("xmllint")
+            .arg("--noout")
+            .arg("--nonet")
+            .arg("--schema")
+            .arg(schema)
+            .arg(&file)
+            .output();
+        std::fs::remove_file(&file).unwrap();
+
+        let output = output.expect("xmllint (libxml2-utils) has to be installed.");
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(String::from_utf8_lossy(&output.stderr).into_owned())
+        }
+    }
+
+    /// The totals of the internal documents, as Pohoda sums up their items.
+    fn document_totals(xml: &str) -> Vec<f32> {
+        let value = |item: &str, tag: &str| -> f32 {
+            let start = item.find(&format!("<{}>", tag)).unwrap() + tag.len() + 2;
+            let end = item.find(&format!("</{}>", tag)).unwrap();
+            item[start..end].parse().unwrap()
+        };
+
+        xml.split("<int:intDoc ")
+            .skip(1)
+            .map(|document| {
+                document
+                    .split("<int:intDocItem>")
+                    .skip(1)
+                    .map(|item| value(item, "int:quantity") * value(item, "typ:unitPrice"))
+                    .sum()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pohoda_export_matches_schema() {
+        let exporter = payroll_exporter(PayrollFormat::Pohoda);
+        let xml = exporter.export(&payroll());
+        let text = String::from_utf8(xml.clone()).unwrap();
+
+        assert_eq!(exporter.extension(), "xml");
+        assert!(text.contains(r#"ico="12345678""#));
+        assert!(text.contains("<int:date>2024-06-30</int:date>"));
+        assert!(text.contains("<int:text>Mzda 06/2024 DPČ - Jan &lt;Honza&gt; Novák</int:text>"));
+        assert!(text.contains(concat!(
+            "<int:text>Hrubá mzda</int:text><int:quantity>40.00</int:quantity>",
+            "<int:unit>h</int:unit><int:rateVAT>none</int:rateVAT>",
+            "<int:homeCurrency><typ:unitPrice>250.00</typ:unitPrice></int:homeCurrency>"
+        )));
+        assert!(text.contains("<typ:unitPrice>-650.00</typ:unitPrice>"));
+        assert!(text.contains(
+            "<int:text>Pojištění zaměstnavatele 06/2024 DPČ - Jan &lt;Honza&gt; Novák</int:text>"
+        ));
+        assert!(text.contains(r#"<dat:dataPackItem id="2024-06-4" "#));
+        assert!(text.contains("<int:text>Oprava mzdy 06/2024 DPP - Eva Nová</int:text>"));
+        assert!(text.contains("<int:quantity>-2.00</int:quantity>"));
+        // Only Jan's employer pays insurance, it is a cost of its own.
+        assert_eq!(text.matches("<int:intDoc ").count(), 4);
+        assert!(!text.contains("Oprava pojištění zaměstnavatele"));
+
+        // The wage documents come to the net wage, gross = net + insurance + tax.
+        let totals = document_totals(&text);
+        assert_eq!(totals, vec![8900.0, 3380.0, 1800.0, -360.0]);
+
+        validate(&xml, "pohoda-payroll").unwrap();
+    }
+
+    #[test]
+    fn schema_rejects_invalid_documents() {
+        let xml = String::from_utf8(payroll_exporter(PayrollFormat::Pohoda).export(&payroll()))
+            .unwrap()
+            .replace(
+                "<int:rateVAT>none</int:rateVAT>",
+                "<int:rateVAT>zero</int:rateVAT>",
+            );
+
+        let errors = validate(xml.as_bytes(), "pohoda-invalid").unwrap_err();
+        assert!(errors.contains("rateVAT"));
+    }
+
+    #[test]
+    fn income_confirmation_splits_withheld_income() {
+        let month = |month| NaiveDate::from_ymd_opt(2024, month, 1).unwrap();
+        let employee = EmployeeWages {
+            user_id: Uuid::nil(),
+            name: "Jan Novák".to_string(),
+            email: "jan@novak.cz".to_string(),
+            employment_type: EmploymentContract::Dpc,
+            hourly_wage: 250.0,
+            months: [
+                (
+                    month(1),
+                    DetailedWage {
+                        tax_base: 10000.0,
+                        net_wage: 8900.0,
+                        worked_hours: 40.0,
+                        employee_social_insurance: 650.0,
+                        employee_health_insurance: 450.0,
+                        ..Default::default()
+                    },
+                ),
+                // The insurance isn't withheld with a signed pink paper.
+                (
+                    month(3),
+                    DetailedWage {
+                        tax_base: 5000.0,
+                        net_wage: 5000.0,
+                        worked_hours: 20.0,
+                        employee_social_insurance: 325.0,
+                        employee_health_insurance: 225.0,
+                        ..Default::default()
+                    },
+                ),
+                (month(4), DetailedWage::default()),
+            ]
+            .into_iter()
+            .collect(),
+            errors: vec![],
+        };
+
+        let confirmation = IncomeConfirmation::new(2024, "Hobbit Hole", "12345678", employee);
+
+        assert_eq!(confirmation.months_worked(), 2);
+        assert_eq!(confirmation.months[1].1.insurance_withheld, 0.0);
+        assert_eq!(
+            confirmation.total,
+            WithheldIncome {
+                worked_hours: 60.0,
+                gross_income: 15000.0,
+                insurance_withheld: 1100.0,
+                tax_withheld: 0.0,
+                net_income: 13900.0,
+            }
+        );
+
+        let with_tax = WithheldIncome::from(&DetailedWage {
+            tax_base: 10000.0,
+            net_wage: 7400.0,
+            employee_social_insurance: 650.0,
+            employee_health_insurance: 450.0,
+            ..Default::default()
+        });
+        assert_eq!(with_tax.insurance_withheld, 1100.0);
+        assert_eq!(with_tax.tax_withheld, 1500.0);
+    }
+}
+
+#[cfg(test)]
+mod budget_tests {
+    use chrono::NaiveDate;
+    use organization::{
+        models::{EmploymentContract, EventRole},
+        repositories::{
+            event_budget::models::{BudgetLine, ForecastStaff},
+            wage_preset::models::WagePreset,
+        },
+        utils::budget::{
+            models::{DayCost, ANY_COMPANY},
+            report::{budget_costs, build_report, forecast_costs},
+        },
+    };
+    use uuid::{uuid, Uuid};
+
+    const COMPANY_A: Uuid = uuid!("134d5286-5f55-4637-9b98-223a5820a464");
+    const COMPANY_B: Uuid = uuid!("71fa27d6-6f00-4ad0-8902-778e298aaed2");
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 5, day).unwrap()
+    }
+
+    fn line(
+        company: Option<(Uuid, &str)>,
+        date: Option<NaiveDate>,
+        headcount: i32,
+        hours: f32,
+        hourly_rate: f64,
+    ) -> BudgetLine {
+        let now = day(1).and_hms_opt(0, 0, 0).unwrap();
+        BudgetLine {
+            id: Uuid::nil(),
+            event_id: Uuid::nil(),
+            company_id: company.map(|(id, _)| id),
+            company_name: company.map(|(_, name)| name.to_string()),
+            role: EventRole::Staff,
+            date,
+            headcount,
+            hours,
+            hourly_rate,
+            created_at: now,
+            edited_at: now,
+            deleted_at: None,
+        }
+    }
+
+    fn staff(
+        company_id: Uuid,
+        company_name: &str,
+        employment_type: EmploymentContract,
+        hourly_wage: f64,
+    ) -> ForecastStaff {
+        ForecastStaff {
+            user_id: Uuid::nil(),
+            company_id,
+            company_name: company_name.to_string(),
+            role: EventRole::Staff,
+            employment_type,
+            hourly_wage,
+        }
+    }
+
+    fn preset() -> WagePreset {
+        let now = day(1).and_hms_opt(0, 0, 0).unwrap();
+        WagePreset {
+            name: "cz_2024".to_string(),
+            valid_from: NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            valid_to: None,
+            currency: "CZK".to_string(),
+            description: String::new(),
+            monthly_dpp_employee_no_tax_limit: 10000.0,
+            monthly_dpp_employer_no_tax_limit: 10000.0,
+            monthly_dpc_employee_no_tax_limit: 4000.0,
+            monthly_dpc_employer_no_tax_limit: 4000.0,
+            health_insurance_employee_tax_pct: 4.5,
+            social_insurance_employee_tax_pct: 6.5,
+            health_insurance_employer_tax_pct: 9.0,
+            social_insurance_employer_tax_pct: 25.0,
+            min_hourly_wage: 100.0,
+            min_monthly_hpp_salary: 17000.0,
+            created_at: now,
+            edited_at: now,
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn budget_spreads_undated_lines_over_the_event() {
+        let lines = vec![
+            line(None, None, 2, 8.0, 200.0),
+            line(Some((COMPANY_A, "Company A")), Some(day(2)), 1, 4.0, 300.0),
+        ];
+
+        let costs = budget_costs(&lines, day(1), day(3));
+
+        assert_eq!(costs.len(), 4);
+        assert_eq!(
+            costs[0],
+            DayCost {
+                company_name: ANY_COMPANY.to_string(),
+                date: day(1),
+                role: EventRole::Staff,
+                hours: 16.0,
+                cost: 3200.0,
+            }
+        );
+        assert_eq!(costs[3].company_name, "Company A");
+        assert_eq!(costs[3].cost, 1200.0);
+    }
+
+    #[test]
+    fn forecast_uses_the_most_specific_line_and_insurance_limits() {
+        let lines = vec![
+            line(None, None, 2, 8.0, 200.0),
+            line(Some((COMPANY_A, "Company A")), Some(day(2)), 1, 4.0, 300.0),
+        ];
+        let members = vec![
+            // Below the DPP limit, the employer doesn't pay insurance.
+            staff(COMPANY_A, "Company A", EmploymentContract::Dpp, 100.0),
+            staff(COMPANY_B, "Company B", EmploymentContract::Hpp, 150.0),
+        ];
+        let mut warnings = vec![];
+
+        let costs = forecast_costs(&lines, &members, &[preset()], day(1), day(3), &mut warnings);
+
+        assert!(warnings.is_empty());
+        let hours_of = |company: &str| -> f64 {
+            costs
+                .iter()
+                .filter(|cost| cost.company_name == company)
+                .map(|cost| cost.hours)
+                .sum()
+        };
+        assert_eq!(hours_of("Company A"), 20.0);
+        assert_eq!(hours_of("Company B"), 24.0);
+
+        let report = build_report(&budget_costs(&lines, day(1), day(3)), &costs, &[], warnings);
+        assert_eq!(report.total.budget_hours, 52.0);
+        assert_eq!(report.total.budget_cost, 10800.0);
+        assert!((report.total.forecast_cost - (2000.0 + 3600.0 * 1.34)).abs() < 0.001);
+        assert_eq!(report.total.actual_cost, 0.0);
+        assert!(report.total.actual_variance() < 0.0);
+
+        let companies: Vec<&str> = report
+            .by_company
+            .iter()
+            .map(|(company, _)| company.as_str())
+            .collect();
+        assert_eq!(companies, vec![ANY_COMPANY, "Company A", "Company B"]);
+        assert_eq!(report.by_day.len(), 3);
+        assert_eq!(report.by_day[1].1.budget_cost, 4400.0);
+        assert_eq!(report.by_day[1].1.forecast_hours, 12.0);
+        assert_eq!(report.by_role.len(), 1);
+    }
+
+    #[test]
+    fn forecast_warns_about_missing_presets() {
+        let lines = vec![line(None, None, 1, 8.0, 200.0)];
+        let members = vec![staff(
+            COMPANY_B,
+            "Company B",
+            EmploymentContract::Hpp,
+            150.0,
+        )];
+        let mut warnings = vec![];
+
+        let costs = forecast_costs(&lines, &members, &[], day(1), day(3), &mut warnings);
+
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(costs.iter().map(|cost| cost.cost).sum::<f64>(), 3600.0);
+    }
+}
+
+#[cfg(test)]
+mod job_tests {
+    use std::time::Duration;
+
+    use organization::utils::jobs::job_scheduler::{retry_delay, RETRY_BASE_DELAY};
+
+    #[test]
+    fn retry_delay_doubles_up_to_the_period() {
+        let hour = Duration::from_secs(60 * 60);
+
+        assert_eq!(retry_delay(1, hour), RETRY_BASE_DELAY);
+        assert_eq!(retry_delay(2, hour), RETRY_BASE_DELAY * 2);
+        assert_eq!(retry_delay(4, hour), RETRY_BASE_DELAY * 8);
+        assert_eq!(retry_delay(7, hour), hour);
+        // A job running more often than the base delay is retried in its period.
+        assert_eq!(
+            retry_delay(1, Duration::from_secs(30)),
+            Duration::from_secs(30)
+        );
+        assert_eq!(retry_delay(i32::MAX, hour), hour);
+    }
+}